@@ -1,4 +1,8 @@
-#[expect(clippy::unwrap_used, reason = "..")]
 fn main() {
-    uniffi::generate_scaffolding("src/did_sidekicks.udl").unwrap();
+    // CAUTION The UniFFI scaffolding is only required if the "uniffi" feature is enabled
+    #[cfg(feature = "uniffi")]
+    #[expect(clippy::unwrap_used, reason = "..")]
+    {
+        uniffi::generate_scaffolding("src/did_sidekicks.udl").unwrap();
+    }
 }
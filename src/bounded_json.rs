@@ -0,0 +1,232 @@
+// SPDX-License-Identifier: MIT
+
+//! A hardened (size- and depth-bounded) JSON deserialization wrapper for untrusted input,
+//! ensuring a consistent failure behaviour on hostile input across all modules.
+//!
+//! Prior to deserialization, any JSON text is checked against the (globally configured) [`DeserializationLimits`],
+//! whereas non-UTF-8 input and lone (UTF-16) surrogates in escape sequences (e.g. `"\ud800"`) are consistently rejected.
+//! Either failure is denoted by [`DidSidekicksError::DeserializationFailed`].
+
+use crate::errors::DidSidekicksError;
+use core::str::Chars;
+use serde::de::DeserializeOwned;
+use std::sync::{PoisonError, RwLock};
+
+static DESERIALIZATION_LIMITS: RwLock<DeserializationLimits> =
+    RwLock::new(DeserializationLimits::DEFAULT);
+
+/// The limits enforced while deserializing untrusted JSON input.
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct DeserializationLimits {
+    /// The maximum size (in bytes) of a single JSON text (e.g. a DID log entry or a DID document).
+    pub max_size: u64,
+    /// The maximum nesting depth of JSON arrays/objects.
+    ///
+    /// CAUTION The recursion limit of the underlying (`serde_json`) parser (128) applies regardless.
+    pub max_depth: u64,
+}
+
+impl DeserializationLimits {
+    /// The default limits, i.e. 16 MiB and 64 levels of nesting.
+    pub const DEFAULT: Self = Self {
+        max_size: 16_777_216, // 16 MiB
+        max_depth: 64,
+    };
+}
+
+impl Default for DeserializationLimits {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Configures the supplied `limits` globally, thus replacing the previously configured ones.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn set_deserialization_limits(limits: DeserializationLimits) {
+    *DESERIALIZATION_LIMITS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = limits;
+}
+
+/// Delivers the currently configured (global) limits.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn get_deserialization_limits() -> DeserializationLimits {
+    *DESERIALIZATION_LIMITS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Deserializes the supplied (untrusted) JSON `text`, while enforcing the configured [`DeserializationLimits`].
+#[inline]
+pub fn from_str_bounded<T: DeserializeOwned>(text: &str) -> Result<T, DidSidekicksError> {
+    check_bounds(text)?;
+    serde_json::from_str(text)
+        .map_err(|err| DidSidekicksError::DeserializationFailed(err.to_string()))
+}
+
+/// Deserializes the supplied (untrusted) JSON `bytes`, while enforcing the configured [`DeserializationLimits`].
+///
+/// Non-UTF-8 input is rejected.
+#[inline]
+pub fn from_slice_bounded<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, DidSidekicksError> {
+    let text = core::str::from_utf8(bytes).map_err(|err| {
+        DidSidekicksError::DeserializationFailed(format!(
+            "JSON text is not a valid UTF-8 string: {err}"
+        ))
+    })?;
+    from_str_bounded(text)
+}
+
+/// Checks the supplied JSON `text` against the configured [`DeserializationLimits`], while also rejecting lone surrogates.
+pub(crate) fn check_bounds(text: &str) -> Result<(), DidSidekicksError> {
+    check_limits(text, get_deserialization_limits())
+}
+
+/// Checks the supplied JSON `text` against the supplied `limits`, while also rejecting lone surrogates.
+///
+/// The check is a single pass over the text, so it is cheap compared to the deserialization itself.
+/// Any other malformation is deliberately ignored here, as it is reported by the parser anyway.
+#[cfg_attr(not(test), expect(clippy::single_call_fn, reason = ".."))]
+fn check_limits(text: &str, limits: DeserializationLimits) -> Result<(), DidSidekicksError> {
+    if u64::try_from(text.len()).unwrap_or(u64::MAX) > limits.max_size {
+        return Err(DidSidekicksError::DeserializationFailed(format!(
+            "JSON text exceeds the maximum size of {} bytes",
+            limits.max_size
+        )));
+    }
+
+    let mut depth: u64 = 0;
+    let mut in_string = false;
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if in_string {
+            match ch {
+                '"' => in_string = false,
+                '\\' if chars.next() == Some('u') => check_escaped_code_point(&mut chars)?,
+                _ => (),
+            }
+            continue;
+        }
+
+        match ch {
+            '"' => in_string = true,
+            '[' | '{' => {
+                depth = depth.saturating_add(1);
+                if depth > limits.max_depth {
+                    return Err(DidSidekicksError::DeserializationFailed(format!(
+                        "JSON text exceeds the maximum nesting depth of {}",
+                        limits.max_depth
+                    )));
+                }
+            }
+            ']' | '}' => depth = depth.saturating_sub(1),
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+/// Checks the code point of an escape sequence (`\uXXXX`, already consumed up to `u`), rejecting lone surrogates.
+#[expect(clippy::single_call_fn, reason = "..")]
+fn check_escaped_code_point(chars: &mut Chars) -> Result<(), DidSidekicksError> {
+    let lone_surrogate = |code: u32| {
+        DidSidekicksError::DeserializationFailed(format!(
+            "JSON text contains a lone surrogate '\\u{code:04x}'"
+        ))
+    };
+
+    let Some(code) = hex4(chars) else {
+        return Ok(()); // malformed escape sequence, reported by the parser
+    };
+    if (0xDC00..=0xDFFF).contains(&code) {
+        return Err(lone_surrogate(code));
+    }
+    if (0xD800..=0xDBFF).contains(&code) {
+        // a high surrogate must be followed by a low one
+        let low = (chars.next() == Some('\\') && chars.next() == Some('u'))
+            .then(|| hex4(chars))
+            .flatten();
+        if !low.is_some_and(|low_code| (0xDC00..=0xDFFF).contains(&low_code)) {
+            return Err(lone_surrogate(code));
+        }
+    }
+    Ok(())
+}
+
+fn hex4(chars: &mut Chars) -> Option<u32> {
+    let digits = [chars.next()?, chars.next()?, chars.next()?, chars.next()?];
+    digits.iter().try_fold(0, |code: u32, digit| {
+        code.checked_mul(16)?.checked_add(digit.to_digit(16)?)
+    })
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+    use serde_json::Value;
+
+    #[rstest]
+    #[case(r#"{"a":[1,{"b":"[[[[{{{{"}]}"#)]
+    #[case("\"\u{1f600} \u{e9} \\\\ud800\"")]
+    fn test_from_str_bounded(#[case] text: &str) {
+        assert_eq!(
+            from_str_bounded::<Value>(text).unwrap(),
+            serde_json::from_str::<Value>(text).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case(&"[".repeat(65), "maximum nesting depth of 64")]
+    #[case(r#"["\ud800"]"#, "lone surrogate '\\ud800'")]
+    #[case(r#"["\ud800A"]"#, "lone surrogate '\\ud800'")]
+    #[case(r#"["\udc00\ud800"]"#, "lone surrogate '\\udc00'")]
+    fn test_from_str_bounded_hostile_input(#[case] text: &str, #[case] error_contains: &str) {
+        assert_error(
+            from_str_bounded::<Value>(text),
+            DidSidekicksErrorKind::DeserializationFailed,
+            error_contains,
+        );
+    }
+
+    #[rstest]
+    fn test_from_slice_bounded_non_utf8() {
+        assert_error(
+            from_slice_bounded::<Value>(b"[\"\xff\"]"),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "not a valid UTF-8 string",
+        );
+    }
+
+    #[rstest]
+    #[case("[1,2]", "maximum size of 4 bytes")]
+    #[case("[[]]", "maximum nesting depth of 1")]
+    fn test_check_limits(#[case] text: &str, #[case] error_contains: &str) {
+        // CAUTION The global limits are deliberately left intact, as tests may run in parallel
+        assert_eq!(get_deserialization_limits(), DeserializationLimits::default());
+        let limits = DeserializationLimits {
+            max_size: 4,
+            max_depth: 1,
+        };
+        check_limits("[1]", limits).unwrap();
+        assert_error(
+            check_limits(text, limits),
+            DidSidekicksErrorKind::DeserializationFailed,
+            error_contains,
+        );
+    }
+}
@@ -0,0 +1,276 @@
+// SPDX-License-Identifier: MIT
+
+//! An opt-in (thread-safe) global cache layer featuring hit/miss counters.
+//!
+//! Once enabled (see [`set_caching_enabled`]), the following objects are cached (keyed by SHA2-256 hash of their origin):
+//! - compiled JSON schema validators (see `DidLogEntryValidator::from_cached`),
+//! - parsed DID documents (see [`DidDoc::from_json`]),
+//! - keys resolved from DID documents (see [`crate::did_doc::get_key_from_did_doc`]) and
+//! - successfully verified signatures of Data Integrity proofs, keyed by (hash data, key, signature),
+//!   so re-verifying the same DID log (e.g. after appending a single entry) skips already verified signatures.
+
+use crate::did_doc::{DidDoc, Jwk};
+#[cfg(feature = "jsonschema-validation")]
+use crate::did_jsonschema::DidLogEntryValidator;
+#[cfg(test)]
+use core::convert::Infallible;
+use hex::ToHex as _;
+use sha2::{Digest as _, Sha256};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// The maximum number of entries held by any of global caches (by default). Once reached, no further entries are cached.
+pub const MAX_CACHE_ENTRIES: usize = 1024;
+
+static CACHING_ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub(crate) static DID_DOC_CACHE: Cache<DidDoc> = Cache::new("did_doc");
+pub(crate) static KEY_CACHE: Cache<Jwk> = Cache::new("key");
+#[cfg(feature = "jsonschema-validation")]
+pub(crate) static VALIDATOR_CACHE: Cache<DidLogEntryValidator> = Cache::new("validator");
+pub(crate) static SIGNATURE_CACHE: Cache<()> = Cache::new("signature");
+
+/// Statistics of a single global cache, as delivered by [`get_cache_stats`].
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct CacheStats {
+    /// The name of the cache.
+    pub name: String,
+    /// The total number of cache hits (since the process start).
+    pub hits: u64,
+    /// The total number of cache misses (since the process start).
+    pub misses: u64,
+    /// The number of entries currently held by the cache.
+    pub entries: u64,
+}
+
+/// A thread-safe key-value cache featuring hit/miss counters.
+pub(crate) struct Cache<V> {
+    name: &'static str,
+    entries: Mutex<BTreeMap<String, V>>,
+    /// The maximum number of entries, [`MAX_CACHE_ENTRIES`] by default.
+    capacity: AtomicUsize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<V: Clone> Cache<V> {
+    const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            entries: Mutex::new(BTreeMap::new()),
+            capacity: AtomicUsize::new(MAX_CACHE_ENTRIES),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the value cached for the supplied `origin`, if any. Otherwise, the value is computed by `init` and cached.
+    ///
+    /// If caching is disabled, the cache is simply bypassed.
+    pub(crate) fn get_or_try_insert_with<F, E>(&self, origin: &str, init: F) -> Result<V, E>
+    where
+        F: FnOnce() -> Result<V, E>,
+    {
+        if !is_caching_enabled() {
+            return init();
+        }
+
+        let key = Sha256::digest(origin.as_bytes()).encode_hex::<String>();
+        let cached = self.lock().get(&key).cloned();
+        if let Some(value) = cached {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(value);
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let value = init()?;
+        let mut entries = self.lock();
+        if entries.len() < self.capacity.load(Ordering::Relaxed) {
+            entries.insert(key, value.clone());
+        }
+        drop(entries);
+        Ok(value)
+    }
+
+    /// The infallible variant of [`Self::get_or_try_insert_with`].
+    #[cfg(test)]
+    pub(crate) fn get_or_insert_with<F>(&self, origin: &str, init: F) -> V
+    where
+        F: FnOnce() -> V,
+    {
+        match self.get_or_try_insert_with(origin, || Ok::<V, Infallible>(init())) {
+            Ok(value) => value,
+            Err(never) => match never {},
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, V>> {
+        // a poisoned cache is still a perfectly valid cache
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn flush(&self) {
+        self.lock().clear();
+    }
+
+    /// Limits the number of entries held by the cache, thus flushing it if the supplied `capacity` is already exceeded.
+    fn set_capacity(&self, capacity: usize) {
+        self.capacity.store(capacity, Ordering::Relaxed);
+        let mut entries = self.lock();
+        if entries.len() > capacity {
+            entries.clear();
+        }
+    }
+
+    fn stats(&self) -> CacheStats {
+        CacheStats {
+            name: self.name.to_owned(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: u64::try_from(self.lock().len()).unwrap_or(u64::MAX),
+        }
+    }
+}
+
+/// Enables (or disables) the opt-in global cache layer. Caching is disabled by default.
+///
+/// Disabling the cache layer does not flush it (see [`flush_caches`]).
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn set_caching_enabled(enabled: bool) {
+    CACHING_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether the opt-in global cache layer is enabled.
+#[inline]
+pub fn is_caching_enabled() -> bool {
+    CACHING_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Delivers statistics (hit/miss counters etc.) for all available global caches.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn get_cache_stats() -> Vec<CacheStats> {
+    vec![
+        #[cfg(feature = "jsonschema-validation")]
+        VALIDATOR_CACHE.stats(),
+        DID_DOC_CACHE.stats(),
+        KEY_CACHE.stats(),
+        SIGNATURE_CACHE.stats(),
+    ]
+}
+
+/// Removes all entries from all available global caches. The hit/miss counters remain intact.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn flush_caches() {
+    #[cfg(feature = "jsonschema-validation")]
+    VALIDATOR_CACHE.flush();
+    DID_DOC_CACHE.flush();
+    KEY_CACHE.flush();
+    SIGNATURE_CACHE.flush();
+}
+
+/// Removes all entries from the (global) cache of verified signatures only, e.g. after a change of verification policy.
+/// The hit/miss counters remain intact.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn flush_signature_cache() {
+    SIGNATURE_CACHE.flush();
+}
+
+/// Limits the number of verified signatures held by the (global) cache, [`MAX_CACHE_ENTRIES`] by default.
+/// If the supplied `capacity` is already exceeded, the cache is flushed.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn set_signature_cache_capacity(capacity: u64) {
+    SIGNATURE_CACHE.set_capacity(usize::try_from(capacity).unwrap_or(usize::MAX));
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_cache_hits_and_misses() {
+        set_caching_enabled(true);
+
+        let cache: Cache<String> = Cache::new("test");
+        assert_eq!(
+            cache.get_or_insert_with("origin", || "value".to_owned()),
+            "value"
+        );
+        assert_eq!(
+            cache.get_or_insert_with("origin", || "other".to_owned()),
+            "value"
+        ); // cached
+        assert_eq!(
+            cache.get_or_insert_with("another", || "other".to_owned()),
+            "other"
+        );
+
+        let stats = cache.stats();
+        assert_eq!(stats.name, "test");
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 2);
+        assert_eq!(stats.entries, 2);
+
+        cache.flush();
+        let stats_after_flush = cache.stats();
+        assert_eq!(stats_after_flush.entries, 0);
+        assert_eq!(stats_after_flush.hits, 1); // counters remain intact
+    }
+
+    #[rstest]
+    fn test_cache_does_not_cache_errors() {
+        set_caching_enabled(true);
+
+        let cache: Cache<String> = Cache::new("test");
+        cache
+            .get_or_try_insert_with("origin", || Err::<String, String>("error".to_owned()))
+            .unwrap_err();
+        assert_eq!(
+            cache
+                .get_or_try_insert_with("origin", || Ok::<String, String>("value".to_owned()))
+                .unwrap(),
+            "value"
+        );
+        assert_eq!(cache.stats().misses, 2);
+        assert_eq!(cache.stats().entries, 1);
+    }
+
+    #[rstest]
+    fn test_cache_capacity() {
+        set_caching_enabled(true);
+
+        let cache: Cache<String> = Cache::new("test");
+        cache.set_capacity(1);
+        cache.get_or_insert_with("origin", || "value".to_owned());
+        cache.get_or_insert_with("another", || "other".to_owned()); // not cached anymore
+        assert_eq!(cache.stats().entries, 1);
+
+        cache.set_capacity(0); // flushes
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[rstest]
+    fn test_get_cache_stats() {
+        let stats = get_cache_stats();
+        assert!(stats.iter().any(|stat| stat.name == "did_doc"));
+        assert!(stats.iter().any(|stat| stat.name == "key"));
+    }
+}
@@ -0,0 +1,520 @@
+// SPDX-License-Identifier: MIT
+
+//! A minimal (stable) `extern "C"` interface, in parallel to UniFFI bindings, for consumers not targeted by UniFFI
+//! (e.g. .NET services via P/Invoke or legacy C/C++ code).
+//!
+//! The interface features the following operations, all delegating to the very same internal objects used by UniFFI bindings:
+//! - DID log entry (JSON schema) validation, backed by a [`ValidatorService`] object,
+//! - verification of a Data Integrity–secured JSON document (see [`verify_secured_document`]),
+//! - JCS SHA2-256 multihash calculation (see [`JcsSha256Hasher::base58btc_encode_multihash`]),
+//! - resolution of a key from a DID document (see [`get_key_from_did_doc`]),
+//! - the library lifecycle (see [`init_library`] and [`shutdown_library`]).
+//!
+//! Resolution of a DID itself is DID method-specific, hence out of scope of this crate.
+//!
+//! The conventions are as follows:
+//! - all strings (either way) are NUL-terminated and UTF-8 encoded,
+//! - each function returns a status code (see `DID_SIDEKICKS_*` constants),
+//! - any (optional) `error_out` argument receives an error message in case of failure,
+//! - any string delivered by this library must be released via [`did_sidekicks_string_free`],
+//! - any object created by `*_new` must be released via the matching `*_free` function.
+//!
+//! No panic ever crosses the FFI boundary, as any is reported as [`DID_SIDEKICKS_ERR_FAILED`].
+
+use crate::bounded_json::{from_str_bounded, DeserializationLimits};
+use crate::did_doc::get_key_from_did_doc;
+use crate::did_jsonschema::DidLogEntryJsonSchema;
+use crate::errors::DidSidekicksError;
+use crate::jcs_sha256_hasher::JcsSha256Hasher;
+use crate::lifecycle::{init_library, shutdown_library, LibraryConfig};
+use crate::panic_boundary::catch_panic;
+use crate::validator_service::ValidatorService;
+use crate::vc_data_integrity::verify_secured_document;
+use core::ffi::{c_char, CStr};
+use core::ptr;
+use std::ffi::CString;
+use std::sync::Arc;
+
+/// The status code denoting success.
+pub const DID_SIDEKICKS_OK: i32 = 0;
+
+/// The status code denoting an invalid argument (e.g. a null pointer or a non UTF-8 string).
+pub const DID_SIDEKICKS_ERR_INVALID_ARGUMENT: i32 = 1;
+
+/// The status code denoting a failed operation (e.g. a failed validation or verification).
+pub const DID_SIDEKICKS_ERR_FAILED: i32 = 2;
+
+/// An error occurred at the FFI boundary, along with its status code.
+struct CapiError {
+    status: i32,
+    message: String,
+}
+
+impl CapiError {
+    fn invalid_argument(message: &str) -> Self {
+        Self {
+            status: DID_SIDEKICKS_ERR_INVALID_ARGUMENT,
+            message: message.to_owned(),
+        }
+    }
+
+    fn failed(message: String) -> Self {
+        Self {
+            status: DID_SIDEKICKS_ERR_FAILED,
+            message,
+        }
+    }
+}
+
+impl From<DidSidekicksError> for CapiError {
+    #[inline]
+    fn from(err: DidSidekicksError) -> Self {
+        Self::failed(err.to_string())
+    }
+}
+
+/// A JSON schema supplied across the FFI boundary.
+struct CapiJsonSchema(String);
+
+#[expect(clippy::missing_trait_methods, reason = "..")]
+impl DidLogEntryJsonSchema for CapiJsonSchema {
+    fn get_json_schema(&self) -> String {
+        self.0.to_owned()
+    }
+}
+
+/// Reads the supplied (borrowed) C string.
+///
+/// # Safety
+///
+/// The `value` must either be null or point to a valid NUL-terminated string, outliving the returned reference.
+unsafe fn read_str<'str>(value: *const c_char, name: &str) -> Result<&'str str, CapiError> {
+    if value.is_null() {
+        return Err(CapiError::invalid_argument(&format!("'{name}' is null")));
+    }
+    // SAFETY: the pointer is non-null and (as required by the caller) points to a valid NUL-terminated string
+    let c_str = unsafe { CStr::from_ptr(value) };
+    c_str.to_str().map_err(|err| {
+        CapiError::invalid_argument(&format!("'{name}' is not UTF-8 encoded: {err}"))
+    })
+}
+
+/// Hands the supplied `value` over to the caller, via the supplied (optional) `out` argument.
+///
+/// # Safety
+///
+/// The `out` must either be null or point to a valid (writable) `*mut c_char`.
+unsafe fn write_string(out: *mut *mut c_char, value: String) {
+    if out.is_null() {
+        return;
+    }
+    // any interior NUL is replaced, so the value is never lost entirely
+    let c_string = CString::new(value.replace('\0', "\u{FFFD}")).unwrap_or_default();
+    // SAFETY: the pointer is non-null and (as required by the caller) valid for writes
+    unsafe {
+        out.write(c_string.into_raw());
+    }
+}
+
+/// Runs the supplied `operation` (panic-safe), while delivering its result via `value_out` and any error message via `error_out`.
+///
+/// # Safety
+///
+/// Both `value_out` and `error_out` must either be null or point to a valid (writable) `*mut c_char`.
+unsafe fn run<F>(operation: F, value_out: *mut *mut c_char, error_out: *mut *mut c_char) -> i32
+where
+    F: FnOnce() -> Result<Option<String>, CapiError>,
+{
+    match catch_panic(operation, CapiError::failed) {
+        Ok(value) => {
+            if let Some(val) = value {
+                // SAFETY: the pointer is valid, as required by the caller
+                unsafe {
+                    write_string(value_out, val);
+                }
+            }
+            DID_SIDEKICKS_OK
+        }
+        Err(err) => {
+            // SAFETY: the pointer is valid, as required by the caller
+            unsafe {
+                write_string(error_out, err.message);
+            }
+            err.status
+        }
+    }
+}
+
+/// Releases a string delivered by this library. Passing null is a no-op.
+///
+/// # Safety
+///
+/// The `value` must either be null or a string delivered by this library, not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn did_sidekicks_string_free(value: *mut c_char) {
+    if value.is_null() {
+        return;
+    }
+    // SAFETY: the string was created via `CString::into_raw` and (as required by the caller) is not released yet
+    drop(unsafe { CString::from_raw(value) });
+}
+
+/// Initializes all global state of the library (see [`init_library`]). The call is idempotent.
+///
+/// # Safety
+///
+/// The `error_out` must either be null or point to a valid (writable) `*mut c_char`.
+#[no_mangle]
+pub unsafe extern "C" fn did_sidekicks_init(
+    caching_enabled: bool,
+    signature_cache_capacity: u64,
+    max_json_size: u64,
+    max_json_depth: u64,
+    error_out: *mut *mut c_char,
+) -> i32 {
+    let operation = || {
+        init_library(LibraryConfig {
+            caching_enabled,
+            signature_cache_capacity,
+            deserialization_limits: DeserializationLimits {
+                max_size: max_json_size,
+                max_depth: max_json_depth,
+            },
+        })?;
+        Ok(None)
+    };
+    // SAFETY: the pointer is valid, as required by the caller
+    unsafe { run(operation, ptr::null_mut(), error_out) }
+}
+
+/// Resets all global state of the library to its defaults (see [`shutdown_library`]). The call is idempotent.
+#[no_mangle]
+pub extern "C" fn did_sidekicks_shutdown() -> i32 {
+    match catch_panic(|| Ok(shutdown_library()), CapiError::failed) {
+        Ok(_) => DID_SIDEKICKS_OK,
+        Err(err) => err.status,
+    }
+}
+
+/// Creates a new (empty) [`ValidatorService`] object, to be released via [`did_sidekicks_validator_service_free`].
+#[no_mangle]
+pub extern "C" fn did_sidekicks_validator_service_new() -> *mut ValidatorService {
+    Arc::into_raw(Arc::new(ValidatorService::new())).cast_mut()
+}
+
+/// Releases a [`ValidatorService`] object created via [`did_sidekicks_validator_service_new`]. Passing null is a no-op.
+///
+/// # Safety
+///
+/// The `service` must either be null or an object created via [`did_sidekicks_validator_service_new`], not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn did_sidekicks_validator_service_free(service: *mut ValidatorService) {
+    if service.is_null() {
+        return;
+    }
+    // SAFETY: the object was created via `Arc::into_raw` and (as required by the caller) is not released yet
+    drop(unsafe { Arc::from_raw(service.cast_const()) });
+}
+
+/// (Re)registers the supplied JSON `schema` under the supplied `version` (see [`ValidatorService::register_schema`]).
+///
+/// # Safety
+///
+/// The `service` must be a valid object created via [`did_sidekicks_validator_service_new`],
+/// both `version` and `schema` must be valid NUL-terminated strings and
+/// the `error_out` must either be null or point to a valid (writable) `*mut c_char`.
+#[no_mangle]
+pub unsafe extern "C" fn did_sidekicks_validator_service_register_schema(
+    service: *const ValidatorService,
+    version: *const c_char,
+    schema: *const c_char,
+    error_out: *mut *mut c_char,
+) -> i32 {
+    let operation = || {
+        // SAFETY: the pointer is either null or valid, as required by the caller
+        let svc = unsafe { service.as_ref() }
+            .ok_or_else(|| CapiError::invalid_argument("'service' is null"))?;
+        // SAFETY: the string is valid, as required by the caller
+        let ver = unsafe { read_str(version, "version") }?;
+        // SAFETY: the string is valid, as required by the caller
+        let json_schema = unsafe { read_str(schema, "schema") }?;
+        svc.register_schema(
+            ver.to_owned(),
+            Arc::new(CapiJsonSchema(json_schema.to_owned())),
+        )
+        .map_err(|err| CapiError::failed(err.to_string()))?;
+        Ok(None)
+    };
+    // SAFETY: the pointer is valid, as required by the caller
+    unsafe { run(operation, ptr::null_mut(), error_out) }
+}
+
+/// Validates the supplied JSON `instance` against the schema registered under the supplied `version` (see [`ValidatorService::validate`]).
+///
+/// # Safety
+///
+/// The `service` must be a valid object created via [`did_sidekicks_validator_service_new`],
+/// both `version` and `instance` must be valid NUL-terminated strings and
+/// the `error_out` must either be null or point to a valid (writable) `*mut c_char`.
+#[no_mangle]
+pub unsafe extern "C" fn did_sidekicks_validator_service_validate(
+    service: *const ValidatorService,
+    version: *const c_char,
+    instance: *const c_char,
+    error_out: *mut *mut c_char,
+) -> i32 {
+    let operation = || {
+        // SAFETY: the pointer is either null or valid, as required by the caller
+        let svc = unsafe { service.as_ref() }
+            .ok_or_else(|| CapiError::invalid_argument("'service' is null"))?;
+        // SAFETY: the string is valid, as required by the caller
+        let ver = unsafe { read_str(version, "version") }?;
+        // SAFETY: the string is valid, as required by the caller
+        let json = unsafe { read_str(instance, "instance") }?;
+        svc.validate(ver.to_owned(), json.to_owned())
+            .map_err(|err| CapiError::failed(err.to_string()))?;
+        Ok(None)
+    };
+    // SAFETY: the pointer is valid, as required by the caller
+    unsafe { run(operation, ptr::null_mut(), error_out) }
+}
+
+/// Verifies the proof of the supplied Data Integrity–secured JSON `document` (see [`verify_secured_document`]),
+/// thus delivering the `did:key` DID of the signer via `signer_out`.
+///
+/// # Safety
+///
+/// The `document` must be a valid NUL-terminated string, whereas
+/// both `signer_out` and `error_out` must either be null or point to a valid (writable) `*mut c_char`.
+#[no_mangle]
+pub unsafe extern "C" fn did_sidekicks_verify_secured_document(
+    document: *const c_char,
+    signer_out: *mut *mut c_char,
+    error_out: *mut *mut c_char,
+) -> i32 {
+    let operation = || {
+        // SAFETY: the string is valid, as required by the caller
+        let json = unsafe { read_str(document, "document") }?;
+        let secured =
+            from_str_bounded(json).map_err(|err| CapiError::invalid_argument(&err.to_string()))?;
+        Ok(Some(verify_secured_document(&secured)?))
+    };
+    // SAFETY: the pointers are valid, as required by the caller
+    unsafe { run(operation, signer_out, error_out) }
+}
+
+/// Calculates the (base58btc-encoded) JCS SHA2-256 multihash of the supplied `json` (see [`JcsSha256Hasher::base58btc_encode_multihash`]),
+/// delivered via `hash_out`.
+///
+/// # Safety
+///
+/// The `json` must be a valid NUL-terminated string, whereas
+/// both `hash_out` and `error_out` must either be null or point to a valid (writable) `*mut c_char`.
+#[no_mangle]
+pub unsafe extern "C" fn did_sidekicks_jcs_sha256_multihash(
+    json: *const c_char,
+    hash_out: *mut *mut c_char,
+    error_out: *mut *mut c_char,
+) -> i32 {
+    let operation = || {
+        // SAFETY: the string is valid, as required by the caller
+        let json_str = unsafe { read_str(json, "json") }?;
+        let value = from_str_bounded(json_str)
+            .map_err(|err| CapiError::invalid_argument(&err.to_string()))?;
+        let hash = JcsSha256Hasher::default()
+            .base58btc_encode_multihash(&value)
+            .map_err(|err| CapiError::failed(err.to_string()))?;
+        Ok(Some(hash))
+    };
+    // SAFETY: the pointers are valid, as required by the caller
+    unsafe { run(operation, hash_out, error_out) }
+}
+
+/// Resolves the key (referenced by the supplied `key_id`) from the supplied `did_doc` (see [`get_key_from_did_doc`]),
+/// delivered as JWK (in JSON format) via `jwk_out`.
+///
+/// # Safety
+///
+/// Both `did_doc` and `key_id` must be valid NUL-terminated strings, whereas
+/// both `jwk_out` and `error_out` must either be null or point to a valid (writable) `*mut c_char`.
+#[no_mangle]
+pub unsafe extern "C" fn did_sidekicks_resolve_key(
+    did_doc: *const c_char,
+    key_id: *const c_char,
+    jwk_out: *mut *mut c_char,
+    error_out: *mut *mut c_char,
+) -> i32 {
+    let operation = || {
+        // SAFETY: the string is valid, as required by the caller
+        let doc = unsafe { read_str(did_doc, "did_doc") }?;
+        // SAFETY: the string is valid, as required by the caller
+        let id = unsafe { read_str(key_id, "key_id") }?;
+        let jwk = get_key_from_did_doc(doc.to_owned(), id.to_owned())?;
+        let json = serde_json::to_string(&jwk).map_err(|err| CapiError::failed(err.to_string()))?;
+        Ok(Some(json))
+    };
+    // SAFETY: the pointers are valid, as required by the caller
+    unsafe { run(operation, jwk_out, error_out) }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(
+    clippy::undocumented_unsafe_blocks,
+    reason = "all pointers are created by the test cases themselves"
+)]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+mod test {
+    use super::*;
+    use crate::ed25519::{
+        did_key_verification_method_from_verifying_key, Ed25519KeyPair, MultiBaseConverter as _,
+        VerificationOptions,
+    };
+    use crate::vc_data_integrity::{
+        CryptoSuiteProofOptions, EddsaJcs2022Cryptosuite, VCDataIntegrity as _,
+    };
+    use rstest::rstest;
+    use serde_json::json;
+
+    /// Takes over the supplied string delivered by the library.
+    fn take_string(value: *mut c_char) -> String {
+        assert!(!value.is_null());
+        let taken = unsafe { CStr::from_ptr(value) }
+            .to_str()
+            .unwrap()
+            .to_owned();
+        unsafe {
+            did_sidekicks_string_free(value);
+        }
+        taken
+    }
+
+    #[rstest]
+    fn test_validator_service() {
+        let service = did_sidekicks_validator_service_new();
+        let version = CString::new("1.0").unwrap();
+        let schema = CString::new(json!({"type": "array"}).to_string()).unwrap();
+        let mut error: *mut c_char = ptr::null_mut();
+
+        let status = unsafe {
+            did_sidekicks_validator_service_register_schema(
+                service,
+                version.as_ptr(),
+                schema.as_ptr(),
+                &mut error,
+            )
+        };
+        assert_eq!(status, DID_SIDEKICKS_OK);
+        assert!(error.is_null());
+
+        let valid = CString::new("[1,2]").unwrap();
+        let status_2 = unsafe {
+            did_sidekicks_validator_service_validate(
+                service,
+                version.as_ptr(),
+                valid.as_ptr(),
+                &mut error,
+            )
+        };
+        assert_eq!(status_2, DID_SIDEKICKS_OK);
+
+        let invalid = CString::new("{}").unwrap();
+        let status_3 = unsafe {
+            did_sidekicks_validator_service_validate(
+                service,
+                version.as_ptr(),
+                invalid.as_ptr(),
+                &mut error,
+            )
+        };
+        assert_eq!(status_3, DID_SIDEKICKS_ERR_FAILED);
+        assert!(!take_string(error).is_empty());
+
+        unsafe {
+            did_sidekicks_validator_service_free(service);
+        }
+    }
+
+    #[rstest]
+    fn test_null_arguments() {
+        let mut error: *mut c_char = ptr::null_mut();
+        let status = unsafe {
+            did_sidekicks_validator_service_validate(
+                ptr::null(),
+                ptr::null(),
+                ptr::null(),
+                &mut error,
+            )
+        };
+        assert_eq!(status, DID_SIDEKICKS_ERR_INVALID_ARGUMENT);
+        assert_eq!(take_string(error), "'service' is null");
+
+        // null output arguments are simply ignored
+        let status_2 = unsafe {
+            did_sidekicks_jcs_sha256_multihash(ptr::null(), ptr::null_mut(), ptr::null_mut())
+        };
+        assert_eq!(status_2, DID_SIDEKICKS_ERR_INVALID_ARGUMENT);
+
+        unsafe {
+
+            did_sidekicks_string_free(ptr::null_mut());
+
+        }
+        unsafe {
+            did_sidekicks_validator_service_free(ptr::null_mut());
+        }
+    }
+
+    #[rstest]
+    fn test_jcs_sha256_multihash() {
+        let json = CString::new(r#"{"b":1,"a":2}"#).unwrap();
+        let mut hash: *mut c_char = ptr::null_mut();
+        let status = unsafe {
+            did_sidekicks_jcs_sha256_multihash(json.as_ptr(), &mut hash, ptr::null_mut())
+        };
+        assert_eq!(status, DID_SIDEKICKS_OK);
+        assert_eq!(
+            take_string(hash),
+            JcsSha256Hasher::default()
+                .base58btc_encode_multihash(&json!({"a": 2, "b": 1}))
+                .unwrap()
+        );
+    }
+
+    #[rstest]
+    fn test_verify_secured_document() {
+        let key_pair = Ed25519KeyPair::generate();
+        let multikey = key_pair.verifying_key.to_multibase();
+        let suite = EddsaJcs2022Cryptosuite {
+            verifying_key: None,
+            signing_key: Some(key_pair.signing_key.clone()),
+            verification_options: VerificationOptions::default(),
+        };
+        let options = CryptoSuiteProofOptions::from_verification_method(
+            &did_key_verification_method_from_verifying_key(&key_pair.verifying_key),
+        )
+        .with_proof_purpose("assertionMethod");
+        let secured = suite
+            .add_proof(&json!({"hello": "world"}), &options)
+            .unwrap();
+
+        let document = CString::new(secured.to_string()).unwrap();
+        let mut signer: *mut c_char = ptr::null_mut();
+        let status = unsafe {
+            did_sidekicks_verify_secured_document(document.as_ptr(), &mut signer, ptr::null_mut())
+        };
+        assert_eq!(status, DID_SIDEKICKS_OK);
+        assert_eq!(take_string(signer), format!("did:key:{multikey}"));
+
+        let unsecured = CString::new(r#"{"hello":"world"}"#).unwrap();
+        let mut error: *mut c_char = ptr::null_mut();
+        let status_2 = unsafe {
+            did_sidekicks_verify_secured_document(unsecured.as_ptr(), ptr::null_mut(), &mut error)
+        };
+        assert_eq!(status_2, DID_SIDEKICKS_ERR_FAILED);
+        assert!(take_string(error).contains("features no proof"));
+    }
+}
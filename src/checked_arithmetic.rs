@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: MIT
+
+//! Overflow-guarded helpers around numbers from untrusted input, e.g. array indices (such as status list indices)
+//! featured by JSON documents and buffer sizes derived from them.
+//!
+//! Any overflow (or out-of-range value) is reported explicitly, instead of being silently wrapped or saturated.
+
+use crate::errors::DidSidekicksError;
+use serde_json::Value;
+
+/// The number of bits per byte.
+const BITS_PER_BYTE: u64 = 8;
+
+/// Converts the supplied (untrusted) JSON `value` into an index of an array (or buffer) of the supplied length (`len`).
+///
+/// Anything but a non-negative integer (e.g. a negative, fractional or huge number) and
+/// any index not less than `len` is denoted by [`DidSidekicksError::DeserializationFailed`].
+#[inline]
+pub fn index_from_json(value: &Value, len: usize) -> Result<usize, DidSidekicksError> {
+    let index = value
+        .as_u64()
+        .ok_or_else(|| {
+            DidSidekicksError::DeserializationFailed(format!(
+                "Invalid index {value}: a non-negative integer expected"
+            ))
+        })
+        .and_then(|index| {
+            usize::try_from(index).map_err(|err| {
+                DidSidekicksError::DeserializationFailed(format!(
+                    "Invalid index {index}: exceeds the platform's address space ({err})"
+                ))
+            })
+        })?;
+    if index >= len {
+        return Err(DidSidekicksError::DeserializationFailed(format!(
+            "Index {index} is out of range (length {len})"
+        )));
+    }
+    Ok(index)
+}
+
+/// Delivers the size (in bytes) of a buffer holding `count` items of `item_size` bytes each.
+///
+/// An overflow is denoted by [`DidSidekicksError::DeserializationFailed`].
+#[inline]
+pub fn checked_buffer_size(count: usize, item_size: usize) -> Result<usize, DidSidekicksError> {
+    count.checked_mul(item_size).ok_or_else(|| {
+        DidSidekicksError::DeserializationFailed(format!(
+            "Buffer size overflow: {count} items of {item_size} bytes each"
+        ))
+    })
+}
+
+/// Locates the entry at the supplied `index` within a bitstring (e.g. a status list) of `buffer_len` bytes.
+///
+/// Each entry spans `bits_per_entry` bits, thus the byte offset is delivered along with the bit offset
+/// (within that byte, counted in the order of the bitstring).
+///
+/// `bits_per_entry` must be either 1, 2, 4 or 8 (so that no entry spans multiple bytes),
+/// otherwise (or if the entry lies beyond the bitstring) [`DidSidekicksError::DeserializationFailed`] is returned.
+#[inline]
+pub fn bit_position(
+    index: u64,
+    bits_per_entry: u8,
+    buffer_len: usize,
+) -> Result<(usize, u8), DidSidekicksError> {
+    if !matches!(bits_per_entry, 1 | 2 | 4 | 8) {
+        return Err(DidSidekicksError::DeserializationFailed(format!(
+            "Invalid number of bits per entry: {bits_per_entry} (1, 2, 4 or 8 expected)"
+        )));
+    }
+    let out_of_range = || {
+        DidSidekicksError::DeserializationFailed(format!(
+            "Index {index} is out of range of the bitstring ({buffer_len} bytes, {bits_per_entry} bits per entry)"
+        ))
+    };
+    let bit = index
+        .checked_mul(u64::from(bits_per_entry))
+        .ok_or_else(out_of_range)?;
+    let byte_offset = usize::try_from(bit.div_euclid(BITS_PER_BYTE))
+        .ok()
+        .ok_or_else(out_of_range)?;
+    if byte_offset >= buffer_len {
+        return Err(out_of_range());
+    }
+    let bit_offset = u8::try_from(bit.rem_euclid(BITS_PER_BYTE))
+        .ok()
+        .ok_or_else(out_of_range)?;
+    Ok((byte_offset, bit_offset))
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rand::Rng as _;
+    use rstest::rstest;
+    use serde_json::json;
+
+    #[rstest]
+    #[case(json!(0), 1, 0)]
+    #[case(json!(41), 42, 41)]
+    fn test_index_from_json(#[case] value: Value, #[case] len: usize, #[case] expected: usize) {
+        assert_eq!(index_from_json(&value, len).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(json!(-1), "a non-negative integer expected")]
+    #[case(json!(1.5), "a non-negative integer expected")]
+    #[case(json!("1"), "a non-negative integer expected")]
+    #[case(json!(u64::MAX), "out of range")]
+    #[case(json!(42), "Index 42 is out of range (length 42)")]
+    fn test_invalid_index_from_json(#[case] value: Value, #[case] error_string: &str) {
+        assert_error(
+            index_from_json(&value, 42),
+            DidSidekicksErrorKind::DeserializationFailed,
+            error_string,
+        );
+    }
+
+    #[rstest]
+    fn test_checked_buffer_size() {
+        assert_eq!(checked_buffer_size(4, 8).unwrap(), 32);
+        assert_error(
+            checked_buffer_size(usize::MAX, 2),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "Buffer size overflow",
+        );
+    }
+
+    #[rstest]
+    #[case(0, 1, (0, 0))]
+    #[case(9, 1, (1, 1))]
+    #[case(3, 2, (0, 6))]
+    #[case(5, 4, (2, 4))]
+    #[case(15, 8, (15, 0))]
+    fn test_bit_position(#[case] index: u64, #[case] bits: u8, #[case] expected: (usize, u8)) {
+        assert_eq!(bit_position(index, bits, 16).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(128, 1, "out of range")]
+    #[case(u64::MAX, 8, "out of range")] // overflow
+    #[case(0, 3, "Invalid number of bits per entry")]
+    fn test_invalid_bit_position(#[case] index: u64, #[case] bits: u8, #[case] error_string: &str) {
+        assert_error(
+            bit_position(index, bits, 16),
+            DidSidekicksErrorKind::DeserializationFailed,
+            error_string,
+        );
+    }
+
+    #[rstest]
+    fn test_bit_position_fuzz() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..10_000 {
+            let index = if rng.gen() {
+                rng.gen_range(0..10_000)
+            } else {
+                rng.gen::<u64>()
+            };
+            let bits = *[1, 2, 4, 8].get(rng.gen_range(0..4)).unwrap();
+            let buffer_len = rng.gen_range(0..1024);
+            // must never panic, whereas any delivered position must lie within the buffer
+            if let Ok((byte_offset, bit_offset)) = bit_position(index, bits, buffer_len) {
+                assert!(byte_offset < buffer_len);
+                assert!(bit_offset < 8);
+            }
+        }
+    }
+}
@@ -0,0 +1,621 @@
+// SPDX-License-Identifier: MIT
+
+//! The response to the compromise of a key of a DID (see [`CompromiseResponse`]), e.g. as automated by incident runbooks.
+//!
+//! Given a (fully) verified DID log (see [`DidLog`]), the minimal sequence of DID log entries is generated that:
+//! 1. revokes the compromised key, i.e. removes any verification method denoted by (or featuring) it from the DID document,
+//!    along with all the references to it, and drops it from `updateKeys`,
+//! 2. rotates `updateKeys`, i.e. in case of pre-rotation, to keys of the pre-rotation set (as committed to in `nextKeyHashes`),
+//! 3. optionally deactivates the DID, i.e. sets `deactivated` and empties `updateKeys`.
+//!
+//! All of the above is featured by a single DID log entry, unless the DID featuring pre-rotation is to be deactivated:
+//! as no key but the ones committed to may be published (and sign) in case of pre-rotation, pre-rotation is ended
+//! by the first DID log entry (rotating `updateKeys`), whereas the DID is deactivated by the second one.
+//! In case of a DID featuring no pre-rotation, `updateKeys` is not rotated (but emptied) when deactivating the DID.
+//!
+//! The resulting DID log (i.e. the supplied one along with the generated DID log entries) is verified as a whole
+//! the very same way as by [`verify_and_resolve`](crate::did_log_resolution::verify_and_resolve), before any DID log entry
+//! is delivered.
+//!
+//! CAUTION The compromised key never signs any of the generated DID log entries, as such a DID log entry might have been
+//! created by an attacker just as well.
+
+use crate::bounded_json::from_str_bounded;
+use crate::did_log::{DidLog, PARAM_DEACTIVATED};
+use crate::did_log_resolution::{replay, ResolutionOptions};
+use crate::ed25519::{
+    did_key_verification_method_from_verifying_key, Ed25519KeyPair, MultiBaseConverter as _,
+    VerificationOptions,
+};
+use crate::errors::DidSidekicksError;
+use crate::jcs_sha256_hasher::JcsSha256Hasher;
+use crate::panic_boundary::catch_panic;
+use crate::vc_data_integrity::{
+    authorize_update_keys, is_pre_rotation_active, CryptoSuiteProofOptions,
+    EddsaJcs2022Cryptosuite, VCDataIntegrity as _, PARAM_NEXT_KEY_HASHES, PARAM_UPDATE_KEYS,
+};
+use crate::version_id::VersionId;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+
+/// The prefix of a `did:key` DID, as featured by the `verificationMethod` of a proof created by an update key.
+const DID_KEY_PREFIX: &str = "did:key:";
+/// The members of a DID document listing (or referencing) verification methods.
+const VERIFICATION_METHOD_MEMBERS: [&str; 6] = [
+    "verificationMethod",
+    "authentication",
+    "assertionMethod",
+    "keyAgreement",
+    "capabilityInvocation",
+    "capabilityDelegation",
+];
+
+/// The options of [`CompromiseResponse::respond`].
+#[derive(Debug, Clone, Default)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct CompromiseResponseOptions {
+    /// The update keys (multikeys) to rotate to, whereas the signer must be one of them in case of pre-rotation.
+    /// In case of pre-rotation, each of them must be committed to in `nextKeyHashes` (i.e. belong to the pre-rotation set).
+    /// Irrelevant when deactivating a DID featuring no pre-rotation.
+    pub update_keys: Vec<String>,
+    /// The hashes of the keys committed to for the next rotation (see [`JcsSha256Hasher::base58btc_encode_multihash_multikey`]),
+    /// if pre-rotation is to be continued (or activated). Irrelevant when deactivating the DID.
+    pub next_key_hashes: Vec<String>,
+    /// Whether the DID is to be deactivated as well.
+    pub deactivate: bool,
+    /// The `versionTime` of the generated DID log entries, the current datetime by default.
+    pub version_time: Option<DateTime<Utc>>,
+}
+
+/// The response to the compromise of a key of a DID, as of its (fully) verified DID log (see [module documentation](self)).
+#[derive(Debug, Clone)]
+pub struct CompromiseResponse {
+    did: String,
+    log_jsonl: String,
+    options: ResolutionOptions,
+    did_log: DidLog,
+}
+
+impl CompromiseResponse {
+    /// Verifies the supplied DID log (`log_jsonl`, in JSON Lines format) of the supplied `did` according to the supplied `options`
+    /// (see [`DidLog::verify`]), whereas the very same `options` apply to the resulting DID log as well.
+    #[inline]
+    pub fn new(
+        did: &str,
+        log_jsonl: &str,
+        options: ResolutionOptions,
+    ) -> Result<Self, DidSidekicksError> {
+        Ok(Self {
+            did: did.to_owned(),
+            log_jsonl: log_jsonl.trim_end().to_owned(),
+            did_log: DidLog::verify(did, log_jsonl, options.clone())?,
+            options,
+        })
+    }
+
+    /// Generates the minimal sequence of DID log entries (each in JSON text form) responding to the compromise of the key
+    /// denoted by the supplied `compromised_key_id`, i.e. either the (absolute or relative) `id` of a verification method,
+    /// a multikey or a `did:key` verification method (e.g. as featured by a proof), whereas each DID log entry is signed
+    /// by the supplied `signer` (see [module documentation](self)).
+    ///
+    /// The DID log entries are delivered only if the resulting DID log is valid, otherwise the very first [`DidSidekicksError`]
+    /// occurred is returned, e.g.:
+    /// - [`DidSidekicksError::KeyNotFound`], if the compromised key is featured by neither the DID document nor `updateKeys`,
+    /// - [`DidSidekicksError::KeyNotAuthorized`], if the compromised key were to sign or remain in `updateKeys`, or in case of
+    ///   pre-rotation, if any of the `updateKeys` to rotate to is not committed to,
+    /// - [`DidSidekicksError::InvalidDidDocument`], if the DID is already deactivated.
+    #[inline]
+    pub fn respond(
+        &self,
+        compromised_key_id: &str,
+        signer: &Ed25519KeyPair,
+        options: &CompromiseResponseOptions,
+    ) -> Result<Vec<String>, DidSidekicksError> {
+        let latest = self.did_log.get_entries().pop().ok_or_else(|| {
+            DidSidekicksError::InternalError("the DID log features no entries".to_owned())
+        })?;
+        if latest.is_deactivated() {
+            return Err(DidSidekicksError::InvalidDidDocument(
+                "the DID is already deactivated".to_owned(),
+            ));
+        }
+
+        let parameters = latest.get_parameters();
+        let mut did_doc = latest.get_did_doc_value().clone();
+        let revoked = revoke_verification_methods(&mut did_doc, compromised_key_id);
+        // the compromised key material, i.e. the key denoted by the compromised_key_id itself along with the one
+        // of any revoked verification method
+        let compromised_keys = revoked
+            .iter()
+            .filter_map(|method| method.get("publicKeyMultibase").and_then(Value::as_str))
+            .chain(core::iter::once(multikey_of(compromised_key_id)))
+            .collect::<HashSet<_>>();
+        let is_update_key = parameters
+            .get(PARAM_UPDATE_KEYS)
+            .and_then(|param| param.get_string_array_value())
+            .is_some_and(|update_keys| {
+                update_keys
+                    .iter()
+                    .any(|key| compromised_keys.contains(key.as_str()))
+            });
+        if revoked.is_empty() && !is_update_key {
+            return Err(DidSidekicksError::KeyNotFound(format!(
+                "the key '{compromised_key_id}' is featured by neither the DID document nor '{PARAM_UPDATE_KEYS}'"
+            )));
+        }
+        if compromised_keys.contains(signer.verifying_key.to_multibase().as_str()) {
+            return Err(DidSidekicksError::KeyNotAuthorized(format!(
+                "the compromised key '{compromised_key_id}' must not sign any DID log entry"
+            )));
+        }
+
+        // the DID method parameters of each DID log entry to generate (see module documentation)
+        let is_pre_rotation = is_pre_rotation_active(parameters);
+        let mut changed_parameters = Vec::new();
+        if is_pre_rotation || !options.deactivate {
+            if options.update_keys.is_empty() {
+                return Err(DidSidekicksError::InvalidDidMethodParameter(format!(
+                    "no '{PARAM_UPDATE_KEYS}' to rotate to"
+                )));
+            }
+            if options
+                .update_keys
+                .iter()
+                .any(|key| compromised_keys.contains(key.as_str()))
+            {
+                return Err(DidSidekicksError::KeyNotAuthorized(format!(
+                    "the compromised key '{compromised_key_id}' must not remain in '{PARAM_UPDATE_KEYS}'"
+                )));
+            }
+            authorize_update_keys(&options.update_keys, parameters)?;
+
+            let mut rotation = Map::new();
+            rotation.insert(PARAM_UPDATE_KEYS.to_owned(), json!(options.update_keys));
+            // pre-rotation is ended by a deactivation, whereas it is left untouched unless either active or started
+            let next_key_hashes = if options.deactivate {
+                Some(json!([]))
+            } else if is_pre_rotation || !options.next_key_hashes.is_empty() {
+                Some(json!(options.next_key_hashes))
+            } else {
+                None
+            };
+            if let Some(hashes) = next_key_hashes {
+                rotation.insert(PARAM_NEXT_KEY_HASHES.to_owned(), hashes);
+            }
+            changed_parameters.push(Value::Object(rotation));
+        }
+        if options.deactivate {
+            changed_parameters.push(json!({PARAM_DEACTIVATED: true, PARAM_UPDATE_KEYS: []}));
+        }
+
+        let version_time = options.version_time.unwrap_or_else(Utc::now);
+        let mut previous = self
+            .log_jsonl
+            .lines()
+            .last()
+            .map(from_str_bounded::<Value>)
+            .transpose()?
+            .ok_or_else(|| {
+                DidSidekicksError::InternalError("the DID log features no entries".to_owned())
+            })?;
+        let mut entries = Vec::new();
+        for parameters_value in changed_parameters {
+            let entry = create_entry(&previous, parameters_value, &did_doc, &version_time, signer)?;
+            entries.push(entry.to_string());
+            previous = entry;
+        }
+
+        // the resulting DID log is verified as a whole, i.e. including the hash chain and the authorization of each proof
+        let log_jsonl = core::iter::once(self.log_jsonl.clone())
+            .chain(entries.iter().cloned())
+            .collect::<Vec<_>>()
+            .join("\n");
+        catch_panic(
+            || {
+                let (state, _) = replay(&log_jsonl, &self.options)?;
+                state.into_result(&self.did)
+            },
+            DidSidekicksError::InternalError,
+        )?;
+        Ok(entries)
+    }
+}
+
+/// Delivers the multikey of the supplied `key_id`, if it denotes a `did:key` (verification method), otherwise the `key_id` itself.
+fn multikey_of(key_id: &str) -> &str {
+    key_id
+        .strip_prefix(DID_KEY_PREFIX)
+        .map_or(key_id, |did_key| {
+            did_key
+                .split_once('#')
+                .map_or(did_key, |(multikey, _)| multikey)
+        })
+}
+
+/// Delivers the fragment of the supplied (absolute or relative) `id`, or the `id` itself if it features no fragment.
+fn fragment_of(id: &str) -> &str {
+    id.rsplit_once('#').map_or(id, |(_, fragment)| fragment)
+}
+
+/// Removes any verification method denoted by the supplied `key_id` (or featuring the key it denotes) from the supplied `did_doc`,
+/// along with all the references to it (e.g. by `assertionMethod`).
+///
+/// Returns the removed verification methods, whether embedded by a verification relationship or not.
+#[expect(clippy::single_call_fn, reason = "..")]
+fn revoke_verification_methods(did_doc: &mut Value, key_id: &str) -> Vec<Value> {
+    let multikey = multikey_of(key_id);
+    let revoked = VERIFICATION_METHOD_MEMBERS
+        .iter()
+        .filter_map(|member| did_doc.get(member).and_then(Value::as_array))
+        .flatten()
+        .filter(|method| {
+            method
+                .get("id")
+                .and_then(Value::as_str)
+                .is_some_and(|id| id == key_id || fragment_of(id) == fragment_of(key_id))
+                || method.get("publicKeyMultibase").and_then(Value::as_str) == Some(multikey)
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    let fragments = revoked
+        .iter()
+        .filter_map(|method| method.get("id").and_then(Value::as_str))
+        .map(fragment_of)
+        .collect::<HashSet<_>>();
+
+    for member in VERIFICATION_METHOD_MEMBERS {
+        if let Some(methods) = did_doc.get_mut(member).and_then(Value::as_array_mut) {
+            methods.retain(|method| {
+                !method
+                    .as_str()
+                    .or_else(|| method.get("id").and_then(Value::as_str))
+                    .is_some_and(|id| fragments.contains(fragment_of(id)))
+            });
+        }
+    }
+    revoked
+}
+
+/// Creates the DID log entry succeeding the supplied `previous` one (of the very same form), featuring the supplied DID method
+/// `parameters` and `did_doc`, whereas it is signed by the supplied `signer`.
+#[expect(clippy::single_call_fn, reason = "..")]
+fn create_entry(
+    previous: &Value,
+    parameters: Value,
+    did_doc: &Value,
+    version_time: &DateTime<Utc>,
+    signer: &Ed25519KeyPair,
+) -> Result<Value, DidSidekicksError> {
+    let is_array_form = previous.is_array();
+    let previous_version_id = if is_array_form {
+        previous.get(0)
+    } else {
+        previous.get("versionId")
+    }
+    .and_then(Value::as_str)
+    .ok_or_else(|| DidSidekicksError::InternalError("missing 'versionId'".to_owned()))?
+    .parse::<VersionId>()?;
+
+    // the entry hash is calculated over the DID log entry featuring the previous versionId (instead of its own) and no proof
+    let time = version_time.to_rfc3339_opts(SecondsFormat::AutoSi, true);
+    let mut entry = if is_array_form {
+        json!([previous_version_id.to_string(), time, parameters, {"value": did_doc}])
+    } else {
+        json!({
+            "versionId": previous_version_id.to_string(),
+            "versionTime": time,
+            "parameters": parameters,
+            "state": did_doc,
+        })
+    };
+    let entry_hash = JcsSha256Hasher::default()
+        .base58btc_encode_multihash(&entry)
+        .map_err(|err| DidSidekicksError::SerializationFailed(err.to_string()))?;
+    let version_id = previous_version_id.next(&entry_hash)?.to_string();
+
+    let suite = EddsaJcs2022Cryptosuite {
+        verifying_key: Some(signer.verifying_key.clone()),
+        signing_key: Some(signer.signing_key.clone()),
+        verification_options: VerificationOptions::default(),
+    };
+    let proof_options = CryptoSuiteProofOptions::from_verification_method(
+        &did_key_verification_method_from_verifying_key(&signer.verifying_key),
+    )
+    .with_created(*version_time)
+    .with_proof_purpose("assertionMethod");
+    if let Some(items) = entry.as_array_mut() {
+        // the proof of a DID log entry of the JSON array form (did:tdw v0.3) covers the DID document only
+        let secured = suite.add_proof(did_doc, &proof_options.with_challenge(&version_id))?;
+        if let Some(first) = items.first_mut() {
+            *first = json!(version_id);
+        }
+        items.push(secured.get("proof").cloned().unwrap_or_default());
+        return Ok(entry);
+    }
+    if let Some(members) = entry.as_object_mut() {
+        members.insert("versionId".to_owned(), json!(version_id));
+    }
+    suite.add_proof(&entry, &proof_options)
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(
+    clippy::indexing_slicing,
+    reason = "panic-safe as long as test case setup is correct"
+)]
+#[expect(clippy::shadow_reuse, reason = "..")]
+#[expect(clippy::shadow_unrelated, reason = "..")]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+
+    const DID_TEMPLATE: &str = "did:webvh:{SCID}:example.com";
+    const SCID_PLACEHOLDER: &str = "{SCID}";
+    const VERSION_TIME: &str = "2012-12-12T12:12:12Z";
+
+    /// Delivers the key pair derived from the supplied (single-byte) `seed`, for the sake of reproducible DID logs.
+    fn key_pair(seed: u8) -> Ed25519KeyPair {
+        Ed25519KeyPair::from_seed(&[seed; 32])
+    }
+
+    fn multikey(seed: u8) -> String {
+        key_pair(seed).verifying_key.to_multibase()
+    }
+
+    fn key_hash(seed: u8) -> String {
+        JcsSha256Hasher::default().base58btc_encode_multihash_multikey(&multikey(seed))
+    }
+
+    /// Delivers a DID along with its (single-entry) DID log, whose DID document features the verification method `#key-1`
+    /// (of the key pair derived from the seed 9), whereas the key pair derived from the seed 1 is authorized and,
+    /// in case of pre-rotation, the ones derived from the seeds 2 and 3 are committed to.
+    fn genesis_log(is_pre_rotation: bool) -> (String, String) {
+        let mut parameters = json!({
+            "method": "did:webvh:1.0",
+            "scid": SCID_PLACEHOLDER,
+            "updateKeys": [multikey(1)],
+        });
+        if is_pre_rotation {
+            parameters["nextKeyHashes"] = json!([key_hash(2), key_hash(3)]);
+        }
+        let key_id = format!("{DID_TEMPLATE}#key-1");
+        let entry = json!({
+            "versionId": SCID_PLACEHOLDER,
+            "versionTime": VERSION_TIME,
+            "parameters": parameters,
+            "state": {
+                "@context": ["https://www.w3.org/ns/did/v1", "https://w3id.org/security/multikey/v1"],
+                "id": DID_TEMPLATE,
+                "verificationMethod": [{
+                    "id": key_id,
+                    "controller": DID_TEMPLATE,
+                    "type": "Multikey",
+                    "publicKeyMultibase": multikey(9),
+                }],
+                "authentication": [key_id],
+                "assertionMethod": [key_id],
+            },
+        });
+        let scid = JcsSha256Hasher::default()
+            .base58btc_encode_multihash(&entry)
+            .unwrap();
+        let mut entry: Value =
+            serde_json::from_str(&entry.to_string().replace(SCID_PLACEHOLDER, &scid)).unwrap();
+        let entry_hash = JcsSha256Hasher::default()
+            .base58btc_encode_multihash(&entry)
+            .unwrap();
+        entry["versionId"] = json!(format!("1-{entry_hash}"));
+
+        let signer = key_pair(1);
+        let suite = EddsaJcs2022Cryptosuite {
+            verifying_key: Some(signer.verifying_key.clone()),
+            signing_key: Some(signer.signing_key.clone()),
+            verification_options: VerificationOptions::default(),
+        };
+        let proof_options = CryptoSuiteProofOptions::from_verification_method(
+            &signer.verifying_key.to_did_key_verification_method(),
+        )
+        .with_created(DateTime::parse_from_rfc3339(VERSION_TIME).unwrap().to_utc())
+        .with_proof_purpose("assertionMethod");
+        (
+            DID_TEMPLATE.replace(SCID_PLACEHOLDER, &scid),
+            suite.add_proof(&entry, &proof_options).unwrap().to_string(),
+        )
+    }
+
+    /// Verifies the supplied DID log along with the supplied (generated) `entries`.
+    fn verify_with(did: &str, log_jsonl: &str, entries: &[String]) -> DidLog {
+        DidLog::verify(
+            did,
+            &format!("{log_jsonl}\n{}", entries.join("\n")),
+            ResolutionOptions::default(),
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    #[case("#key-1")]
+    #[case("key-1")]
+    #[case("did:key:{multikey}#{multikey}")]
+    fn test_respond_pre_rotation(#[case] compromised_key_id: &str) {
+        let (did, log_jsonl) = genesis_log(true);
+        let compromised_key_id = compromised_key_id.replace("{multikey}", &multikey(9));
+        let response =
+            CompromiseResponse::new(&did, &log_jsonl, ResolutionOptions::default()).unwrap();
+
+        let entries = response
+            .respond(
+                &compromised_key_id,
+                &key_pair(2),
+                &CompromiseResponseOptions {
+                    update_keys: vec![multikey(2)],
+                    next_key_hashes: vec![key_hash(4)],
+                    ..CompromiseResponseOptions::default()
+                },
+            )
+            .unwrap();
+
+        // a single DID log entry revokes the key and rotates updateKeys (to a key of the pre-rotation set)
+        assert_eq!(entries.len(), 1);
+        let did_log = verify_with(&did, &log_jsonl, &entries);
+        let latest = did_log.get_entries().pop().unwrap();
+        assert!(!latest.is_deactivated());
+        assert_eq!(
+            latest.get_signer(),
+            key_pair(2).verifying_key.to_did_key_verification_method()
+        );
+        assert_eq!(
+            latest.get_parameters()[PARAM_UPDATE_KEYS].get_string_array_value(),
+            Some(vec![multikey(2)])
+        );
+        let validity = did_log.key_validity("#key-1").unwrap();
+        assert_eq!(validity.len(), 1);
+        assert_eq!(validity[0].to_version, Some(latest.get_version_id()));
+        assert!(latest.get_did_doc_value()["verificationMethod"]
+            .as_array()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[rstest]
+    // in case of pre-rotation, pre-rotation is ended before the DID is deactivated
+    #[case(true, 2, 2)]
+    // otherwise, the DID is deactivated right away (by a key in force)
+    #[case(false, 1, 1)]
+    fn test_respond_deactivate(
+        #[case] is_pre_rotation: bool,
+        #[case] signer: u8,
+        #[case] expected_entries: usize,
+    ) {
+        let (did, log_jsonl) = genesis_log(is_pre_rotation);
+        let response =
+            CompromiseResponse::new(&did, &log_jsonl, ResolutionOptions::default()).unwrap();
+
+        let entries = response
+            .respond(
+                &multikey(9),
+                &key_pair(signer),
+                &CompromiseResponseOptions {
+                    update_keys: vec![multikey(2)],
+                    deactivate: true,
+                    ..CompromiseResponseOptions::default()
+                },
+            )
+            .unwrap();
+
+        assert_eq!(entries.len(), expected_entries);
+        let did_log = verify_with(&did, &log_jsonl, &entries);
+        let latest = did_log.get_entries().pop().unwrap();
+        assert!(latest.is_deactivated());
+        let param = &latest.get_parameters()[PARAM_UPDATE_KEYS];
+        assert!(param.is_array() && param.is_empty_array());
+        assert!(did_log.key_validity("#key-1").unwrap()[0]
+            .to_version
+            .is_some());
+    }
+
+    #[rstest]
+    fn test_respond_update_key() {
+        // the compromised update key is rotated, although not featured by the DID document
+        let (did, log_jsonl) = genesis_log(false);
+        let response =
+            CompromiseResponse::new(&did, &log_jsonl, ResolutionOptions::default()).unwrap();
+
+        // as the compromised key is the only one in force, no other key may sign the rotation
+        assert_error(
+            response.respond(
+                &key_pair(1).verifying_key.to_did_key_verification_method(),
+                &key_pair(2),
+                &CompromiseResponseOptions {
+                    update_keys: vec![multikey(2)],
+                    ..CompromiseResponseOptions::default()
+                },
+            ),
+            DidSidekicksErrorKind::KeyNotAuthorized,
+            "not listed in 'updateKeys'",
+        );
+
+        let (did, log_jsonl) = genesis_log(true);
+        let response =
+            CompromiseResponse::new(&did, &log_jsonl, ResolutionOptions::default()).unwrap();
+        let entries = response
+            .respond(
+                &multikey(1),
+                &key_pair(3),
+                &CompromiseResponseOptions {
+                    update_keys: vec![multikey(2), multikey(3)],
+                    ..CompromiseResponseOptions::default()
+                },
+            )
+            .unwrap();
+        let did_log = verify_with(&did, &log_jsonl, &entries);
+        let latest = did_log.get_entries().pop().unwrap();
+        // pre-rotation is ended, as no key hashes are committed to
+        let param = &latest.get_parameters()[PARAM_NEXT_KEY_HASHES];
+        assert!(param.is_array() && param.is_empty_array());
+        assert_eq!(did_log.key_validity("#key-1").unwrap()[0].to_version, None);
+    }
+
+    #[rstest]
+    #[case("#unknown", 2, &[2], DidSidekicksErrorKind::KeyNotFound, "is featured by neither")]
+    #[case("#key-1", 9, &[2], DidSidekicksErrorKind::KeyNotAuthorized, "must not sign")]
+    #[case("#key-1", 9, &[9], DidSidekicksErrorKind::KeyNotAuthorized, "must not sign")]
+    #[case(&multikey(1), 1, &[2], DidSidekicksErrorKind::KeyNotAuthorized, "must not sign")]
+    #[case("#key-1", 2, &[2, 9], DidSidekicksErrorKind::KeyNotAuthorized, "must not remain")]
+    #[case("#key-1", 2, &[], DidSidekicksErrorKind::InvalidDidMethodParameter, "no 'updateKeys'")]
+    // rotating to a key not committed to
+    #[case("#key-1", 4, &[4], DidSidekicksErrorKind::KeyNotAuthorized, "not committed to")]
+    // the resulting DID log is invalid, as the signer is not listed in the updateKeys rotated to
+    #[case("#key-1", 1, &[2], DidSidekicksErrorKind::KeyNotAuthorized, "not listed in 'updateKeys'")]
+    fn test_respond_invalid(
+        #[case] compromised_key_id: &str,
+        #[case] signer: u8,
+        #[case] update_keys: &[u8],
+        #[case] error_kind: DidSidekicksErrorKind,
+        #[case] error_contains: &str,
+    ) {
+        let (did, log_jsonl) = genesis_log(true);
+        let response =
+            CompromiseResponse::new(&did, &log_jsonl, ResolutionOptions::default()).unwrap();
+
+        assert_error(
+            response.respond(
+                compromised_key_id,
+                &key_pair(signer),
+                &CompromiseResponseOptions {
+                    update_keys: update_keys.iter().map(|seed| multikey(*seed)).collect(),
+                    ..CompromiseResponseOptions::default()
+                },
+            ),
+            error_kind,
+            error_contains,
+        );
+    }
+
+    #[rstest]
+    fn test_respond_deactivated() {
+        let response = CompromiseResponse::new(
+            "did:tdw:QmdSU7F2rF8r4m6GZK7Evi2tthfDDxhw3NppU8pJMbd2hB:example.com",
+            include_str!("../test_data/generated_by_tdw_js/deactivated.jsonl"),
+            ResolutionOptions::default(),
+        )
+        .unwrap();
+
+        assert_error(
+            response.respond(
+                "#KsXDA8UP",
+                &key_pair(2),
+                &CompromiseResponseOptions::default(),
+            ),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            "already deactivated",
+        );
+    }
+}
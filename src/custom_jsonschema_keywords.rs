@@ -1,5 +1,8 @@
 // SPDX-License-Identifier: MIT
 
+use crate::did_jsonschema::consume_validation_step;
+use crate::errors::DidSidekicksError;
+use crate::version_id::VersionId;
 use chrono::{DateTime, Local};
 use core::cmp::Ordering;
 use jsonschema::{
@@ -8,10 +11,29 @@ use jsonschema::{
 };
 use serde_json::{Map, Value};
 
+/// Consumes a single step of the budget of the validation currently running (see [`crate::did_jsonschema::ValidationLimits`]),
+/// thus failing fast once the budget is exceeded (the validation is then aborted by the validator itself).
+#[expect(clippy::result_large_err, reason = "..")] // "the `Err`-variant is at least 224 bytes" (default: 128)
+#[expect(clippy::single_char_lifetime_names, reason = "for the sake of brevity")]
+fn consume_step<'i>(
+    instance: &'i Value,
+    location: &LazyLocation,
+) -> Result<(), ValidationError<'i>> {
+    if consume_validation_step() {
+        return Ok(());
+    }
+    Err(ValidationError::custom(
+        Location::new(),
+        location.into(),
+        instance,
+        "The validation budget is exceeded",
+    ))
+}
+
 /// Yet another custom [`Keyword`] trait implementation able to validate if a JSON array represents
 /// a regular `didwebvh` DID log entry (as defined by https://identity.foundation/didwebvh/v0.3/#overview).
 ///
-/// This [`Keyword`] trait implementation validates instances according to https://identity.foundation/didwebvh/v0.3/#overview
+/// This [`Keyword`] trait implementation validates instances according to https://identity.foundation/didwebvh/v0.3/#overview.
 #[expect(clippy::exhaustive_structs, reason = "..")]
 pub struct DidLogEntryKeyword;
 
@@ -60,8 +82,9 @@ impl Keyword for DidLogEntryKeyword {
         instance: &'i Value,
         location: &LazyLocation,
     ) -> Result<(), ValidationError<'i>> {
+        consume_step(instance, location)?;
         if let Value::Array(_) = *instance {
-            if self.is_valid(instance) {
+            if Self::is_valid_entry(instance) {
                 Ok(())
             } else {
                 Err(ValidationError::custom(
@@ -92,6 +115,13 @@ impl Keyword for DidLogEntryKeyword {
     /// 5. A Data Integrity (DI) proof across the entry, signed by a DID authorized to update the DIDDoc, using the `versionId` as the challenge.
     #[inline]
     fn is_valid(&self, instance: &Value) -> bool {
+        consume_validation_step() && Self::is_valid_entry(instance)
+    }
+}
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
+impl DidLogEntryKeyword {
+    fn is_valid_entry(instance: &Value) -> bool {
         // "each DID log entry includes a JSON array of five items"
         instance.as_array().is_some_and(|inst| {
             inst.len() == 5
@@ -168,6 +198,7 @@ impl Keyword for DidVersionTimeKeyword {
         instance: &'i Value,
         location: &LazyLocation,
     ) -> Result<(), ValidationError<'i>> {
+        consume_step(instance, location)?;
         if let Value::String(dt) = instance {
             // versionTime:
             // 1. Valid datetime in ISO8601 format SPEC
@@ -210,10 +241,96 @@ impl Keyword for DidVersionTimeKeyword {
     /// 2. is (as datetime) before the current time
     #[inline]
     fn is_valid(&self, instance: &Value) -> bool {
-        instance.as_str().is_some_and(|str| {
-            DateTime::parse_from_rfc3339(str)
-                .is_ok_and(|dt| dt.cmp(&Local::now().fixed_offset()) == Ordering::Less)
-        })
+        consume_validation_step()
+            && instance.as_str().is_some_and(|str| {
+                DateTime::parse_from_rfc3339(str)
+                    .is_ok_and(|dt| dt.cmp(&Local::now().fixed_offset()) == Ordering::Less)
+            })
+    }
+}
+
+/// Yet another custom [`Keyword`] trait implementation.
+///
+/// It is able to validate the `versionId` DID log entry item (as specified by https://identity.foundation/didwebvh/v1.0/#the-did-log-file),
+/// i.e. besides the `<n>-<entryHash>` structure, the entry hash must feature the base58btc alphabet only
+/// and decode into a SHA2-256 multihash (see [`VersionId::check_entry_hash_encoding`]).
+/// Hence, obviously corrupted DID logs are rejected at schema stage already, well before any expensive verification runs.
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct DidVersionIdKeyword;
+
+impl DidVersionIdKeyword {
+    /// Required to register this custom keyword validator using `jsonschema::ValidationOptions::with_keyword`.
+    pub const KEYWORD_NAME: &'static str = "did-version-id";
+
+    /// Required to register this custom keyword validator using `jsonschema::ValidationOptions::with_keyword`.
+    #[inline]
+    #[expect(clippy::result_large_err, reason = "..")] // "the `Err`-variant is at least 224 bytes" (default: 128)
+    #[expect(clippy::single_char_lifetime_names, reason = "..")]
+    pub fn factory<'a>(
+        _parent: &'a Map<String, Value>,
+        value: &'a Value,
+        path: Location,
+    ) -> Result<Box<dyn Keyword>, ValidationError<'a>> {
+        if value
+            .as_bool()
+            .is_some_and(|_| path.to_string().ends_with(Self::KEYWORD_NAME))
+        {
+            Ok(Box::new(Self))
+        } else {
+            Err(ValidationError::custom(
+                Location::new(),
+                path,
+                value,
+                "The 'did-version-id' keyword must be set to true",
+            ))
+        }
+    }
+
+    fn check(version_id: &str) -> Result<(), DidSidekicksError> {
+        version_id.parse::<VersionId>()?.check_entry_hash_encoding()
+    }
+}
+
+impl Keyword for DidVersionIdKeyword {
+    /// Validate instance according to https://identity.foundation/didwebvh/v1.0/#the-did-log-file i.e. a `versionId` qualifies as "valid" if:
+    /// 1. it is a string of the form `<n>-<entryHash>`, whereas `n` is a positive number featuring no leading zeros
+    /// 2. the `entryHash` is a base58btc-encoded SHA2-256 multihash
+    #[inline]
+    #[expect(clippy::single_char_lifetime_names, reason = "for the sake of brevity")]
+    fn validate<'i>(
+        &self,
+        instance: &'i Value,
+        location: &LazyLocation,
+    ) -> Result<(), ValidationError<'i>> {
+        consume_step(instance, location)?;
+        match instance.as_str().map(Self::check) {
+            Some(Ok(())) => Ok(()),
+            Some(Err(err)) => Err(ValidationError::custom(
+                Location::new(),
+                location.into(),
+                instance,
+                err.detail(),
+            )),
+            None => Err(ValidationError::custom(
+                Location::new(),
+                location.into(),
+                instance,
+                "Value must be a string representing some versionId",
+            )),
+        }
+    }
+
+    /// Validate instance and return a boolean result.
+    ///
+    /// A `versionId` qualifies as "valid" if:
+    /// 1. it is a string of the form `<n>-<entryHash>`, whereas `n` is a positive number featuring no leading zeros
+    /// 2. the `entryHash` is a base58btc-encoded SHA2-256 multihash
+    #[inline]
+    fn is_valid(&self, instance: &Value) -> bool {
+        consume_validation_step()
+            && instance
+                .as_str()
+                .is_some_and(|version_id| Self::check(version_id).is_ok())
     }
 }
 
@@ -223,6 +340,8 @@ impl Keyword for DidVersionTimeKeyword {
     clippy::unwrap_used,
     reason = "unwrap calls are panic-safe as long as test case setup is correct"
 )]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+#[expect(clippy::result_large_err, reason = "..")]
 mod test {
     use crate::custom_jsonschema_keywords::*;
     use jsonschema::options as jsch_opts;
@@ -400,4 +519,42 @@ mod test {
         // should always fail since "type" is wrong ("integer" instead of "string")
         assert!(!validator.is_valid(&json!(instance)));
     }
+
+    #[rstest]
+    #[case(json!("1-QmQq6Kg4ZZ1p49znzxnWmes4LkkWgMWLrnrfPre8UD56bz"), true)]
+    #[case(json!("12-QmQq6Kg4ZZ1p49znzxnWmes4LkkWgMWLrnrfPre8UD56bz"), true)]
+    #[case(json!("01-QmQq6Kg4ZZ1p49znzxnWmes4LkkWgMWLrnrfPre8UD56bz"), false)] // leading zero
+    #[case(json!("1-QmQq6Kg4ZZ1p49znzxnWmes4LkkWgMWLrnrfPre8UD560z"), false)] // '0' is not in the base58btc alphabet
+    #[case(json!("1-QmQq6Kg4ZZ1p49znzxnWmes4LkkWgMWLrnrfPre8UD56"), false)] // truncated
+    #[case(json!("1-QmA"), false)]
+    #[case(json!(1), false)]
+    fn test_did_version_id_keyword(#[case] instance: Value, #[case] expected: bool) {
+        let schema = json!({DidVersionIdKeyword::KEYWORD_NAME: true});
+
+        let validator = jsch_opts()
+            .with_keyword(
+                DidVersionIdKeyword::KEYWORD_NAME,
+                DidVersionIdKeyword::factory,
+            ) // using factory
+            .build(&schema)
+            .unwrap();
+
+        assert_eq!(expected, validator.validate(&instance).is_ok());
+        assert_eq!(expected, validator.is_valid(&instance));
+    }
+
+    #[rstest]
+    fn test_did_version_id_keyword_wrong_keyword() {
+        const WRONG_KEYWORD_NAME: &str = "anything-but-proper-keyword-name";
+        let schema = json!({WRONG_KEYWORD_NAME: true, "type": "string"});
+
+        let validator = jsch_opts()
+            .with_keyword(WRONG_KEYWORD_NAME, DidVersionIdKeyword::factory)
+            .build(&schema);
+
+        assert!(validator.err().is_some_and(|err| {
+            err.to_string()
+                .contains("The 'did-version-id' keyword must be set to true")
+        }));
+    }
 }
@@ -0,0 +1,449 @@
+// SPDX-License-Identifier: MIT
+
+//! Shared control of a DID by multiple DID controllers (e.g. institution-owned identifiers),
+//! as specified by https://www.w3.org/TR/did-core/#did-controller.
+//!
+//! Besides validation of the `controller` property of a DID document, proofs created by any of the controllers
+//! may be verified (while resolving the controller DIDs by means of a [`ControllerResolver`]), as long as the `vc-data-integrity`
+//! feature is enabled. In the same manner, controller-based authorization is available for DID log verification
+//! (see [`authorize_proof_or_controller`] and [`crate::did_log_resolution::ResolutionOptions::controller_resolver`]).
+
+use crate::did_doc::DidDoc;
+use crate::errors::{DidResolverError, DidSidekicksError};
+use crate::panic_boundary::catch_panic;
+use std::sync::Arc;
+#[cfg(feature = "vc-data-integrity")]
+pub use verification::*;
+
+/// A resolver of controller DIDs, as required for verifying proofs created by any controller of a DID document.
+///
+/// A UniFFI-compliant trait (that may also be implemented by foreign code).
+pub trait ControllerResolver: Send + Sync {
+    /// Resolves the supplied (controller) `did`, thus delivering its DID document.
+    fn resolve_controller(&self, did: String) -> Result<Arc<DidDoc>, DidResolverError>;
+}
+
+impl DidDoc {
+    /// Whether the supplied `did` controls this DID document, i.e. it is listed in `controller`.
+    ///
+    /// In absence of any `controller`, the DID subject is considered its own (sole) controller.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn is_controlled_by(&self, did: &str) -> bool {
+        if self.controller.is_empty() {
+            return self.id == did;
+        }
+        self.controller.iter().any(|controller| controller == did)
+    }
+
+    /// Validates the `controller` property (if any) of this DID document, i.e. each controller must be a distinct DID
+    /// (not a DID URL).
+    ///
+    /// Any violation is denoted by [`DidSidekicksError::InvalidDidDocument`].
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn validate_controllers(&self) -> Result<(), DidSidekicksError> {
+        catch_panic(
+            || {
+                for (pos, controller) in self.controller.iter().enumerate() {
+                    if !controller.starts_with("did:")
+                        || controller.contains(['#', '?', '/'])
+                        || controller.split(':').count() < 3
+                    {
+                        return Err(DidSidekicksError::InvalidDidDocument(format!(
+                            "The controller '{controller}' of {} is not a DID",
+                            self.id
+                        )));
+                    }
+                    if self
+                        .controller
+                        .iter()
+                        .skip(pos.saturating_add(1))
+                        .any(|other| other == controller)
+                    {
+                        return Err(DidSidekicksError::InvalidDidDocument(format!(
+                            "The controller '{controller}' of {} is listed more than once",
+                            self.id
+                        )));
+                    }
+                }
+                Ok(())
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+}
+
+#[cfg(feature = "vc-data-integrity")]
+mod verification {
+    use super::ControllerResolver;
+    use crate::did_doc::{DidDoc, VerificationMethod};
+    use crate::did_method_parameters::DidMethodParameter;
+    use crate::ed25519::{Ed25519VerifyingKey, MultiBaseConverter as _, VerificationOptions};
+    use crate::errors::DidSidekicksError;
+    use crate::key_interop::verifying_key_from_verification_method;
+    use crate::vc_data_integrity::{
+        authorize_proof, DataIntegrityProof, EddsaJcs2022Cryptosuite, ProofDocument,
+        VCDataIntegrity as _,
+    };
+    use core::hash::BuildHasher;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    /// The outcome of [`authorize_proof_or_controller`], i.e. who created the (verified) proof.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[expect(
+        clippy::exhaustive_enums,
+        reason = "further enum variants may be added in the future"
+    )]
+    pub enum ProofAuthorization {
+        /// The proof was created (and verified) by an update key (multikey) authorized by the DID method parameters.
+        UpdateKey(String),
+        /// The proof was created (and verified) by a key of the featured DID controller.
+        Controller(String),
+    }
+
+    /// Verifies the supplied `proof` (of the supplied `document`, see [`ProofDocument`]) created by any controller of the supplied `did_doc`,
+    /// thus delivering the controller DID.
+    ///
+    /// The DID of the proof's `verificationMethod` must be listed as controller (see [`DidDoc::is_controlled_by`]).
+    /// The controller DID is resolved by means of the supplied `resolver`, whereas the verification method must be featured
+    /// by the verification relationship matching the proof's `proofPurpose`.
+    ///
+    /// Returns:
+    /// - [`DidSidekicksError::KeyNotAuthorized`], if the proof was not created by a controller,
+    /// - [`DidSidekicksError::KeyNotFound`], if the controller's DID document features no such verification method,
+    /// - [`DidSidekicksError::InvalidDidDocument`], if the controller DID could not be resolved and
+    /// - [`DidSidekicksError::InvalidDataIntegrityProof`], if the proof itself is invalid (e.g. featuring an unknown `proofPurpose`).
+    #[inline]
+    pub fn verify_controller_proof<'doc, D: Into<ProofDocument<'doc>>>(
+        did_doc: &DidDoc,
+        proof: &DataIntegrityProof,
+        document: D,
+        resolver: &dyn ControllerResolver,
+    ) -> Result<String, DidSidekicksError> {
+        let method_id = proof.verification_method.as_str();
+        let controller = method_id.split_once('#').map_or(method_id, |(did, _)| did);
+        if controller == did_doc.id || !did_doc.is_controlled_by(controller) {
+            return Err(DidSidekicksError::KeyNotAuthorized(format!(
+                "proof's verificationMethod '{method_id}' does not refer to any controller of {}",
+                did_doc.id
+            )));
+        }
+
+        let controller_doc = resolver
+            .resolve_controller(controller.to_owned())
+            .map_err(|err| {
+                DidSidekicksError::InvalidDidDocument(format!(
+                    "Could not resolve controller '{controller}': {err}"
+                ))
+            })?;
+        let relationship = match proof.proof_purpose.as_str() {
+            "assertionMethod" => &controller_doc.assertion_method,
+            "authentication" => &controller_doc.authentication,
+            "capabilityInvocation" => &controller_doc.capability_invocation,
+            "capabilityDelegation" => &controller_doc.capability_delegation,
+            purpose => {
+                return Err(DidSidekicksError::InvalidDataIntegrityProof(format!(
+                    "Unsupported proofPurpose '{purpose}' of a proof created by controller '{controller}'"
+                )));
+            }
+        };
+        let method = relationship
+            .iter()
+            .find(|method| refers_to(method, controller, method_id))
+            .ok_or_else(|| {
+                DidSidekicksError::KeyNotFound(format!(
+                    "{controller} features no '{}' verification method '{method_id}'",
+                    proof.proof_purpose
+                ))
+            })?;
+
+        let cryptosuite = EddsaJcs2022Cryptosuite {
+            verifying_key: Some(verifying_key_from_verification_method(method)?),
+            signing_key: None,
+            verification_options: VerificationOptions::default(),
+        };
+        cryptosuite.verify_proof(proof, document)?;
+        Ok(controller.to_owned())
+    }
+
+    /// Verifies the supplied (DID log entry) `proof` (of the supplied `document`, see [`ProofDocument`]) as an authorized one.
+    ///
+    /// The proof is authorized, if either its key is authorized by the supplied (active) DID method `parameters` (see [`authorize_proof`])
+    /// or it was created by a controller of the supplied `did_doc` (see [`verify_controller_proof`]).
+    ///
+    /// Either way, the proof itself is verified.
+    #[inline]
+    pub fn authorize_proof_or_controller<'doc, S, D>(
+        proof: &DataIntegrityProof,
+        parameters: &HashMap<String, Arc<DidMethodParameter>, S>,
+        did_doc: &DidDoc,
+        document: D,
+        resolver: &dyn ControllerResolver,
+    ) -> Result<ProofAuthorization, DidSidekicksError>
+    where
+        S: BuildHasher,
+        D: Into<ProofDocument<'doc>>,
+    {
+        match authorize_proof(proof, parameters) {
+            Ok(key) => {
+                let cryptosuite = EddsaJcs2022Cryptosuite {
+                    verifying_key: Some(Ed25519VerifyingKey::from_multibase(&key)?),
+                    signing_key: None,
+                    verification_options: VerificationOptions::default(),
+                };
+                cryptosuite.verify_proof(proof, document)?;
+                Ok(ProofAuthorization::UpdateKey(key))
+            }
+            Err(DidSidekicksError::KeyNotAuthorized(_) | DidSidekicksError::KeyNotFound(_))
+                if !did_doc.controller.is_empty() =>
+            {
+                verify_controller_proof(did_doc, proof, document, resolver)
+                    .map(ProofAuthorization::Controller)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Whether the supplied `method` (of the `controller` DID document) is denoted by the supplied (absolute) `method_id`.
+    #[expect(clippy::single_call_fn, reason = "..")]
+    fn refers_to(method: &VerificationMethod, controller: &str, method_id: &str) -> bool {
+        method.id == method_id
+            || (method.id.starts_with('#') && format!("{controller}{}", method.id) == method_id)
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+    use serde_json::{json, Value};
+
+    const DID: &str = "did:example:institution";
+    const CONTROLLER_A: &str = "did:example:alice";
+    const CONTROLLER_B: &str = "did:example:bob";
+
+    fn did_doc(controller: Value) -> DidDoc {
+        DidDoc::from_json(
+            &json!({
+                "@context": ["https://www.w3.org/ns/did/v1"],
+                "id": DID,
+                "controller": controller,
+                "verificationMethod": [],
+            })
+            .to_string(),
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    fn test_controllers() {
+        let single = did_doc(json!(CONTROLLER_A));
+        assert_eq!(single.get_controller(), vec![CONTROLLER_A]);
+        assert!(single.is_controlled_by(CONTROLLER_A));
+        assert!(!single.is_controlled_by(DID));
+
+        let shared = did_doc(json!([CONTROLLER_A, CONTROLLER_B]));
+        assert!(shared.is_controlled_by(CONTROLLER_A));
+        assert!(shared.is_controlled_by(CONTROLLER_B));
+        shared.validate_controllers().unwrap();
+
+        // the DID subject is its own controller, if none is featured
+        assert!(did_doc(json!([])).is_controlled_by(DID));
+    }
+
+    #[rstest]
+    #[case(json!(["did:example:alice#key-1"]), "is not a DID")]
+    #[case(json!(["https://example.com"]), "is not a DID")]
+    #[case(json!([CONTROLLER_A, CONTROLLER_A]), "is listed more than once")]
+    fn test_invalid_controllers(#[case] controller: Value, #[case] error_string: &str) {
+        assert_error(
+            did_doc(controller).validate_controllers(),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            error_string,
+        );
+    }
+
+    #[cfg(feature = "vc-data-integrity")]
+    #[rstest]
+    fn test_verify_controller_proof() {
+        use crate::did_method_parameters::DidMethodParameter;
+        use crate::ed25519::{
+            did_key_from_verifying_key, did_key_verification_method_from_verifying_key,
+            Ed25519KeyPair, MultiBaseConverter as _, VerificationOptions,
+        };
+        use crate::jcs_sha256_hasher::JcsSha256Hasher;
+        use crate::vc_data_integrity::{
+            CryptoSuiteProofOptions, DataIntegrityProof, EddsaJcs2022Cryptosuite,
+            VCDataIntegrity as _, PARAM_UPDATE_KEYS,
+        };
+        use std::collections::HashMap;
+
+        struct StaticControllerResolver(Arc<DidDoc>);
+        impl ControllerResolver for StaticControllerResolver {
+            fn resolve_controller(&self, did: String) -> Result<Arc<DidDoc>, DidResolverError> {
+                if did == self.0.id {
+                    Ok(Arc::clone(&self.0))
+                } else {
+                    Err(DidResolverError::InvalidMethodSpecificId(did))
+                }
+            }
+        }
+
+        let alice = Ed25519KeyPair::generate();
+        let alice_did = did_key_from_verifying_key(&alice.verifying_key);
+        let alice_method_id = did_key_verification_method_from_verifying_key(&alice.verifying_key);
+        let bob = Ed25519KeyPair::generate();
+        let bob_did = did_key_from_verifying_key(&bob.verifying_key);
+        let method = json!({
+            "id": &alice_method_id,
+            "type": "Multikey",
+            "publicKeyMultibase": alice.verifying_key.to_multibase(),
+        });
+        let alice_doc = DidDoc::from_json(
+            &json!({
+                "@context": ["https://www.w3.org/ns/did/v1"],
+                "id": &alice_did,
+                "verificationMethod": [method],
+                "assertionMethod": [method],
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let resolver = StaticControllerResolver(Arc::new(alice_doc));
+
+        let document = json!({"versionId": "2-QmB"});
+        let sign = |key_pair: &Ed25519KeyPair, verification_method: &str| {
+            let suite = EddsaJcs2022Cryptosuite {
+                verifying_key: Some(key_pair.verifying_key.clone()),
+                signing_key: Some(key_pair.signing_key.clone()),
+                verification_options: VerificationOptions::default(),
+            };
+            let options = CryptoSuiteProofOptions::from_verification_method(verification_method)
+                .with_proof_purpose("assertionMethod");
+            let secured = suite.add_proof(&document, &options).unwrap();
+            DataIntegrityProof::from(secured.get("proof").unwrap().to_string()).unwrap()
+        };
+        let doc_hash = JcsSha256Hasher::default().encode_hex(&document).unwrap();
+        let shared = did_doc(json!([&alice_did, &bob_did]));
+
+        let proof = sign(&alice, &alice_method_id);
+        assert_eq!(
+            verify_controller_proof(&shared, &proof, &doc_hash, &resolver).unwrap(),
+            alice_did
+        );
+        let parameters = HashMap::from([(
+            PARAM_UPDATE_KEYS.to_owned(),
+            Arc::new(
+                DidMethodParameter::try_from_value(PARAM_UPDATE_KEYS, Vec::<String>::new())
+                    .unwrap(),
+            ),
+        )]);
+        assert_eq!(
+            authorize_proof_or_controller(&proof, &parameters, &shared, &doc_hash, &resolver)
+                .unwrap(),
+            ProofAuthorization::Controller(alice_did.to_owned())
+        );
+        let update_keys = |key: &Ed25519KeyPair| {
+            HashMap::from([(
+                PARAM_UPDATE_KEYS.to_owned(),
+                Arc::new(
+                    DidMethodParameter::try_from_value(
+                        PARAM_UPDATE_KEYS,
+                        vec![key.verifying_key.to_multibase()],
+                    )
+                    .unwrap(),
+                ),
+            )])
+        };
+        assert_eq!(
+            authorize_proof_or_controller(
+                &proof,
+                &update_keys(&alice),
+                &shared,
+                &doc_hash,
+                &resolver
+            )
+            .unwrap(),
+            ProofAuthorization::UpdateKey(alice.verifying_key.to_multibase())
+        );
+
+        let mallory = Ed25519KeyPair::generate();
+        // the proof is verified regardless of the way it is authorized
+        assert_error(
+            authorize_proof_or_controller(
+                &sign(&mallory, &alice_method_id),
+                &update_keys(&alice),
+                &shared,
+                &doc_hash,
+                &resolver,
+            ),
+            DidSidekicksErrorKind::SignatureInvalid,
+            "",
+        );
+        // a proof purpose not denoting any verification relationship (which neither `add_proof` nor parsing would deliver)
+        let mut key_agreement_proof = sign(&alice, &alice_method_id);
+        key_agreement_proof.proof_purpose = "keyAgreement".to_owned();
+        assert_error(
+            verify_controller_proof(
+                &shared,
+                &key_agreement_proof,
+                &doc_hash,
+                &resolver,
+            ),
+            DidSidekicksErrorKind::InvalidIntegrityProof,
+            "Unsupported proofPurpose 'keyAgreement'",
+        );
+        // a verification method not featured by the controller's DID document
+        assert_error(
+            verify_controller_proof(
+                &shared,
+                &sign(&mallory, &format!("{alice_did}#key-2")),
+                &doc_hash,
+                &resolver,
+            ),
+            DidSidekicksErrorKind::KeyNotFound,
+            "features no 'assertionMethod' verification method",
+        );
+        // a signature not matching the controller's key
+        assert_error(
+            verify_controller_proof(
+                &shared,
+                &sign(&mallory, &alice_method_id),
+                &doc_hash,
+                &resolver,
+            ),
+            DidSidekicksErrorKind::SignatureInvalid,
+            "",
+        );
+        // not a controller at all
+        assert_error(
+            verify_controller_proof(&did_doc(json!([&bob_did])), &proof, &doc_hash, &resolver),
+            DidSidekicksErrorKind::KeyNotAuthorized,
+            "does not refer to any controller",
+        );
+        // an unresolvable controller
+        assert_error(
+            verify_controller_proof(
+                &shared,
+                &sign(
+                    &bob,
+                    &did_key_verification_method_from_verifying_key(&bob.verifying_key),
+                ),
+                &doc_hash,
+                &resolver,
+            ),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            &format!("Could not resolve controller '{bob_did}'"),
+        );
+    }
+}
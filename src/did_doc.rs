@@ -1,13 +1,18 @@
 // SPDX-License-Identifier: MIT
 
+use crate::bounded_json::from_str_bounded;
+use crate::cache::{DID_DOC_CACHE, KEY_CACHE};
 use crate::did_method_parameters::DidMethodParameter;
 use crate::errors::DidSidekicksError;
+use crate::json_emission::{emit_json, EmitOptions};
+use crate::panic_boundary::catch_panic;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
 
 /// An entry in DID log file as shown here
-/// https://bcgov.github.io/trustdidweb/#term:did-log-entry
+/// https://bcgov.github.io/trustdidweb/#term:did-log-entry.
 
 // Implement basic properties related to EC algorithm
 // https://www.rfc-editor.org/rfc/rfc7517#section-4
@@ -50,6 +55,10 @@ pub struct VerificationMethod {
     pub public_key_multibase: Option<String>,
     #[serde(rename = "publicKeyJwk", skip_serializing_if = "Option::is_none")]
     pub public_key_jwk: Option<Jwk>,
+    /// Whether the key has been proven to be hardware-bound (see [`DidDoc::with_key_attestations`]).
+    /// Never (de)serialized, hence `false` for any freshly parsed DID document.
+    #[serde(skip)]
+    pub attested: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -67,10 +76,6 @@ pub enum VerificationType {
 
 impl core::fmt::Display for VerificationType {
     #[inline]
-    #[expect(
-        clippy::min_ident_chars,
-        reason = "default name of function parameter of trait impl. used to prevent clippy::renamed_function_params warning"
-    )]
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         let string_representation = match *self {
             Self::Multikey => String::from("Multikey"),
@@ -95,6 +100,7 @@ impl VerificationMethod {
             verification_type,
             public_key_multibase: Some(public_key_multibase),
             public_key_jwk: None,
+            attested: false,
         }
     }
 }
@@ -112,6 +118,7 @@ impl Clone for VerificationMethod {
             verification_type: self.verification_type.clone(),
             public_key_multibase: self.public_key_multibase.clone(),
             public_key_jwk: self.public_key_jwk.clone(),
+            attested: self.attested,
         }
     }
 }
@@ -119,7 +126,8 @@ impl Clone for VerificationMethod {
 // See      https://www.w3.org/TR/did-core/#dfn-did-documents
 // Examples https://www.w3.org/TR/did-core/#did-documents
 // According to https://www.w3.org/TR/did-core/#did-document-properties
-#[derive(Serialize, Deserialize, Debug, Clone)]
+// The Debug implementation is redacted, see crate::redaction module.
+#[derive(Serialize, Deserialize, Clone)]
 #[expect(clippy::exhaustive_structs, reason = "..")]
 pub struct DidDoc {
     #[serde(rename = "@context")]
@@ -157,10 +165,22 @@ pub struct DidDoc {
     // - https://jira.bit.admin.ch/browse/EIDSYS-352
     // - https://confluence.bit.admin.ch/display/EIDTEAM/DID+Doc+Conformity+Check
     // It is kept for the sake of backward compatibility only.
-    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    // Either a single DID or a set of DIDs (shared control) is accepted, see https://www.w3.org/TR/did-core/#did-controller
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "deserialize_one_or_many",
+        default
+    )]
     pub controller: Vec<String>,
+    // See https://www.w3.org/TR/did-core/#also-known-as
+    #[serde(rename = "alsoKnownAs", skip_serializing_if = "Vec::is_empty", default)]
+    pub also_known_as: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deactivated: Option<bool>,
+    // See https://www.w3.org/TR/did-core/#services
+    // Kept as opaque JSON objects, as the service types are rather open-ended.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub service: Vec<Value>,
 }
 
 // See      https://www.w3.org/TR/did-core/#dfn-did-documents
@@ -210,20 +230,24 @@ pub struct DidDocNormalized {
     // - https://jira.bit.admin.ch/browse/EIDSYS-352
     // - https://confluence.bit.admin.ch/display/EIDTEAM/DID+Doc+Conformity+Check
     // It is kept for the sake of backward compatibility only.
-    #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub controller: Option<String>,
+    // Either a single DID or a set of DIDs (shared control) is accepted, see https://www.w3.org/TR/did-core/#did-controller
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "deserialize_one_or_many",
+        default
+    )]
+    pub controller: Vec<String>,
+    #[serde(rename = "alsoKnownAs", skip_serializing_if = "Vec::is_empty", default)]
+    pub also_known_as: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deactivated: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub service: Vec<Value>,
 }
 
 impl DidDocNormalized {
     #[inline]
     pub fn to_did_doc(&self) -> Result<DidDoc, DidSidekicksError> {
-        let controller = self
-            .controller
-            .clone()
-            .map_or_else(Vec::new, |ctrl| vec![ctrl]);
-
         let mut did_doc = DidDoc {
             context: self.context.clone(), // vec![],
             id: self.id.clone(),
@@ -233,9 +257,10 @@ impl DidDocNormalized {
             capability_delegation: vec![],
             assertion_method: vec![],
             key_agreement: vec![],
-            //controller: self.controller.clone(),
-            controller,
+            controller: self.controller.clone(),
+            also_known_as: self.also_known_as.clone(),
             deactivated: self.deactivated,
+            service: self.service.clone(),
         };
         if !self.authentication.is_empty() {
             did_doc.authentication = vec![];
@@ -302,14 +327,135 @@ impl DidDocNormalized {
     }
 }
 
+/// Deserializes either a single string or an array of strings (e.g. `controller`) into a vector.
+fn deserialize_one_or_many<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(one) => vec![one],
+        OneOrMany::Many(many) => many,
+    })
+}
+
 /// A simple container for both [`DidDoc`] and the related collection of [`DidMethodParameter`] objects.
 #[derive(Debug, Clone)]
 pub struct DidDocExtended {
-    did_doc: DidDoc,
+    did_doc: Arc<DidDoc>,
     did_method_parameters: HashMap<String, Arc<DidMethodParameter>>,
 }
 
+/// The base context of a DID document (see https://www.w3.org/TR/did-core/#json-ld).
+pub const DID_CONTEXT_V1: &str = "https://www.w3.org/ns/did/v1";
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
 impl DidDoc {
+    /// The builder-like constructor of the type, featuring the supplied `id` along with the base context ([`DID_CONTEXT_V1`])
+    /// only, whereas any further member may be added by the respective builder method, e.g.:
+    ///
+    /// ```ignore
+    /// let did_doc = DidDoc::new("did:webvh:Qm...:example.com")
+    ///     .with_verification_method(method.clone())
+    ///     .with_assertion_method(method);
+    /// ```
+    #[inline]
+    pub fn new(id: &str) -> Self {
+        Self {
+            context: vec![DID_CONTEXT_V1.to_owned()],
+            id: id.to_owned(),
+            verification_method: Vec::new(),
+            authentication: Vec::new(),
+            capability_invocation: Vec::new(),
+            capability_delegation: Vec::new(),
+            assertion_method: Vec::new(),
+            key_agreement: Vec::new(),
+            controller: Vec::new(),
+            also_known_as: Vec::new(),
+            deactivated: None,
+            service: Vec::new(),
+        }
+    }
+
+    /// Adds the supplied (JSON-LD) `context` (e.g. `https://w3id.org/security/multikey/v1`), unless featured already.
+    #[inline]
+    #[must_use]
+    pub fn with_context(mut self, context: &str) -> Self {
+        if !self.context.iter().any(|existing| existing == context) {
+            self.context.push(context.to_owned());
+        }
+        self
+    }
+
+    /// Adds the supplied `method` to `verificationMethod`.
+    #[inline]
+    #[must_use]
+    pub fn with_verification_method(mut self, method: VerificationMethod) -> Self {
+        self.verification_method.push(method);
+        self
+    }
+
+    /// Adds the supplied `method` to `authentication`.
+    #[inline]
+    #[must_use]
+    pub fn with_authentication(mut self, method: VerificationMethod) -> Self {
+        self.authentication.push(method);
+        self
+    }
+
+    /// Adds the supplied `method` to `capabilityInvocation`.
+    #[inline]
+    #[must_use]
+    pub fn with_capability_invocation(mut self, method: VerificationMethod) -> Self {
+        self.capability_invocation.push(method);
+        self
+    }
+
+    /// Adds the supplied `method` to `capabilityDelegation`.
+    #[inline]
+    #[must_use]
+    pub fn with_capability_delegation(mut self, method: VerificationMethod) -> Self {
+        self.capability_delegation.push(method);
+        self
+    }
+
+    /// Adds the supplied `method` to `assertionMethod`.
+    #[inline]
+    #[must_use]
+    pub fn with_assertion_method(mut self, method: VerificationMethod) -> Self {
+        self.assertion_method.push(method);
+        self
+    }
+
+    /// Adds the supplied `method` to `keyAgreement`.
+    #[inline]
+    #[must_use]
+    pub fn with_key_agreement(mut self, method: VerificationMethod) -> Self {
+        self.key_agreement.push(method);
+        self
+    }
+
+    /// Adds the supplied `did` to `alsoKnownAs`.
+    #[inline]
+    #[must_use]
+    pub fn with_also_known_as(mut self, did: &str) -> Self {
+        self.also_known_as.push(did.to_owned());
+        self
+    }
+
+    /// Adds the supplied `service` (a JSON object, see https://www.w3.org/TR/did-core/#services) to `service`.
+    #[inline]
+    #[must_use]
+    pub fn with_service(mut self, service: Value) -> Self {
+        self.service.push(service);
+        self
+    }
+
     #[inline]
     pub fn get_context(&self) -> Vec<String> {
         self.context.clone()
@@ -350,23 +496,65 @@ impl DidDoc {
         self.controller.clone()
     }
 
+    #[inline]
+    pub fn get_also_known_as(&self) -> Vec<String> {
+        self.also_known_as.clone()
+    }
+
     #[inline]
     pub fn get_deactivated(&self) -> bool {
         self.deactivated.unwrap_or(false)
     }
 
+    /// Delivers the services (if any), each of them as JSON text.
+    #[inline]
+    pub fn get_service(&self) -> Vec<String> {
+        self.service.iter().map(Value::to_string).collect()
+    }
+
+    /// Parses the supplied DID doc as string.
+    ///
+    /// If the opt-in global cache layer is enabled (see [`crate::cache::set_caching_enabled`]),
+    /// the very same `json_content` is parsed only once.
     #[inline]
     pub fn from_json(json_content: &str) -> Result<Self, DidSidekicksError> {
-        let did_doc: Self = match serde_json::from_str(json_content) {
-            Ok(did_doc) => did_doc,
-            Err(err) => {
-                return Err(DidSidekicksError::DeserializationFailed(format!(
-                    "Error parsing DID Document. Make sure the content is correct -> {err}"
-                )));
-            }
-        };
+        catch_panic(
+            || {
+                DID_DOC_CACHE.get_or_try_insert_with(json_content, || {
+                    match from_str_bounded(json_content) {
+                        Ok(did_doc) => Ok(did_doc),
+                        Err(err) => Err(DidSidekicksError::DeserializationFailed(format!(
+                            "Error parsing DID Document. Make sure the content is correct -> {}",
+                            err.detail()
+                        ))),
+                    }
+                })
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
 
-        Ok(did_doc)
+    /// Emits this DID document as JSON text according to the supplied `options`.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn to_json(&self, options: EmitOptions) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || emit_json(self, options),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Extracts an arbitrary nested field of this DID document by means of a JSON pointer
+    /// (as specified by https://www.rfc-editor.org/rfc/rfc6901), e.g. `/verificationMethod/0/publicKeyJwk/kid`.
+    ///
+    /// Returns `None` if the `pointer` refers to a non-existing field.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn get_by_pointer(&self, pointer: String) -> Option<Arc<DidMethodParameter>> {
+        let json = serde_json::to_value(self).ok()?;
+        DidMethodParameter::from_json_pointer(&json, &pointer).map(Arc::new)
     }
 
     /// Returns a cryptographic public key ([`Jwk`]) referenced by the supplied `key_id`, if any.
@@ -378,6 +566,10 @@ impl DidDoc {
     /// If no such key exists, [`DidSidekicksError::KeyNotFound`] is returned.
     #[inline]
     pub fn get_key(&self, key_id: String) -> Result<Jwk, DidSidekicksError> {
+        catch_panic(|| self.find_key(key_id), DidSidekicksError::InternalError)
+    }
+
+    fn find_key(&self, key_id: String) -> Result<Jwk, DidSidekicksError> {
         // A JWK referenced by the supplied key_id might be anywhere in this DID doc
         match self
             .verification_method
@@ -406,6 +598,186 @@ impl DidDoc {
     }
 }
 
+/// A single violation of verification method id conventions, as detected by [`DidDoc::check_verification_method_ids`].
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct VerificationMethodIdIssue {
+    /// The offending verification method id.
+    pub id: String,
+    /// Human-readable description of the violated convention.
+    pub reason: String,
+    /// The suggested (auto-fixed) verification method id, if any.
+    pub suggestion: Option<String>,
+}
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
+impl DidDoc {
+    /// Checks whether all verification method ids (across `verificationMethod` and all verification relationships)
+    /// follow the `<DID>#<fragment>` convention, i.e.:
+    /// - an id must be an absolute DID URL referring to this very DID document (`id`),
+    /// - an id must feature a non-empty fragment and
+    /// - in case of a JWK featuring `kid`, the fragment must match the `kid`.
+    ///
+    /// Each violation comes along with an auto-fix suggestion, wherever possible.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn check_verification_method_ids(&self) -> Vec<VerificationMethodIdIssue> {
+        let mut issues: Vec<VerificationMethodIdIssue> = Vec::new();
+        self.verification_method
+            .iter()
+            .chain(self.authentication.iter())
+            .chain(self.capability_invocation.iter())
+            .chain(self.capability_delegation.iter())
+            .chain(self.assertion_method.iter())
+            .chain(self.key_agreement.iter())
+            .filter_map(|method| self.check_verification_method_id(method))
+            .for_each(|issue| {
+                if !issues.contains(&issue) {
+                    issues.push(issue);
+                }
+            });
+        issues
+    }
+
+    /// Same as [`DidDoc::check_verification_method_ids`], but returning [`DidSidekicksError::InvalidDidDocument`]
+    /// (listing all violations along with auto-fix suggestions) if any of conventions is violated.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn validate_verification_method_ids(&self) -> Result<(), DidSidekicksError> {
+        catch_panic(|| self.validate_ids(), DidSidekicksError::InternalError)
+    }
+
+    fn validate_ids(&self) -> Result<(), DidSidekicksError> {
+        let issues = self.check_verification_method_ids();
+        if issues.is_empty() {
+            return Ok(());
+        }
+
+        let details = issues
+            .iter()
+            .map(|issue| {
+                issue.suggestion.as_ref().map_or_else(
+                    || format!("{}: {}", issue.id, issue.reason),
+                    |suggestion| {
+                        format!(
+                            "{}: {} (suggested id: {suggestion})",
+                            issue.id, issue.reason
+                        )
+                    },
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("; ");
+        Err(DidSidekicksError::InvalidDidDocument(format!(
+            "Malformed verification method id(s): {details}"
+        )))
+    }
+
+    fn check_verification_method_id(
+        &self,
+        method: &VerificationMethod,
+    ) -> Option<VerificationMethodIdIssue> {
+        let kid = method
+            .public_key_jwk
+            .as_ref()
+            .and_then(|jwk| jwk.kid.to_owned());
+        // the preferred fragment, if the current one is unusable
+        let preferred_fragment = kid
+            .to_owned()
+            .or_else(|| method.public_key_multibase.to_owned());
+        let suggest = |fragment: Option<&str>| {
+            fragment
+                .filter(|frag| !frag.is_empty())
+                .map(|frag| format!("{}#{frag}", self.id))
+        };
+        let issue = |reason: &str, suggestion: Option<String>| {
+            Some(VerificationMethodIdIssue {
+                id: method.id.to_owned(),
+                reason: reason.to_owned(),
+                suggestion,
+            })
+        };
+
+        let Some((did, fragment)) = method.id.split_once('#') else {
+            return issue("missing fragment", suggest(preferred_fragment.as_deref()));
+        };
+
+        if fragment.is_empty() {
+            return issue("empty fragment", suggest(preferred_fragment.as_deref()));
+        }
+
+        if did.is_empty() {
+            return issue("relative DID URL", suggest(Some(fragment)));
+        }
+
+        if did != self.id {
+            return issue(
+                "DID URL not referring to the DID document",
+                suggest(Some(fragment)),
+            );
+        }
+
+        if let Some(key_id) = kid.as_ref() {
+            if fragment != key_id {
+                return issue("fragment not matching the JWK kid", suggest(Some(key_id)));
+            }
+        }
+
+        None
+    }
+}
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
+impl DidDoc {
+    /// Whether the supplied `other_did` is listed among the alternative identifiers (`alsoKnownAs`) of this DID document.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn is_also_known_as(&self, other_did: &str) -> bool {
+        self.also_known_as.iter().any(|aka| aka == other_did)
+    }
+
+    /// Verifies the (bidirectional) equivalence of this DID document and the supplied `other` one,
+    /// i.e. each of them must list the DID of the other one as an alternative identifier (`alsoKnownAs`),
+    /// as required when moving (or rebranding) a DID.
+    ///
+    /// A unidirectional reference is not sufficient, as any DID controller may claim any other DID unilaterally.
+    /// Any failure is denoted by [`DidSidekicksError::InvalidDidDocument`].
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn verify_also_known_as(&self, other: &Self) -> Result<(), DidSidekicksError> {
+        catch_panic(
+            || {
+                if self.id == other.id {
+                    return Err(DidSidekicksError::InvalidDidDocument(format!(
+                        "A DID document cannot be equivalent to itself: {}",
+                        self.id
+                    )));
+                }
+
+                let missing = [(self, other), (other, self)]
+                    .iter()
+                    .filter(|&&(doc, referred)| !doc.is_also_known_as(&referred.id))
+                    .map(|&(doc, referred)| format!("{} does not refer to {}", doc.id, referred.id))
+                    .collect::<Vec<String>>();
+                if missing.is_empty() {
+                    return Ok(());
+                }
+                Err(DidSidekicksError::InvalidDidDocument(format!(
+                    "The alsoKnownAs equivalence is not bidirectional: {}",
+                    missing.join(", ")
+                )))
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+}
+
 /// The helper parses the supplied DID doc as string and returns a cryptographic public key ([`Jwk`]) referenced by the supplied `key_id`, if any.
 ///
 /// Parsing failure is denoted by returning [`DidSidekicksError::DeserializationFailed`].
@@ -415,44 +787,64 @@ impl DidDoc {
 /// (`authentication`, `assertionMethod`, `keyAgreement`, `capabilityInvocation`, `capabilityInvocation`).
 ///
 /// If no such key exists, [`DidSidekicksError::KeyNotFound`] is returned.
+///
+/// If the opt-in global cache layer is enabled (see [`crate::cache::set_caching_enabled`]),
+/// the very same key is resolved only once.
 #[inline]
 pub fn get_key_from_did_doc(did_doc: String, key_id: String) -> Result<Jwk, DidSidekicksError> {
-    let doc = match serde_json::from_str::<DidDocNormalized>(did_doc.as_str()) {
-        Ok(doc_norm) => match doc_norm.to_did_doc() {
-            Ok(doc) => doc,
-            Err(err) => return Err(DidSidekicksError::DeserializationFailed(err.to_string())),
-        },
-        Err(_) => match serde_json::from_str::<DidDoc>(did_doc.as_str()) {
-            Ok(doc) => doc,
-            Err(err) => return Err(DidSidekicksError::DeserializationFailed(err.to_string())),
-        },
-    };
+    // CAUTION The length prefix prevents ambiguous concatenations
+    let origin = format!("{}:{key_id}:{did_doc}", key_id.len());
+    catch_panic(
+        || KEY_CACHE.get_or_try_insert_with(&origin, || resolve_key_from_did_doc(did_doc, key_id)),
+        DidSidekicksError::InternalError,
+    )
+}
 
-    doc.get_key(key_id)
+#[expect(clippy::single_call_fn, reason = "..")]
+fn resolve_key_from_did_doc(did_doc: String, key_id: String) -> Result<Jwk, DidSidekicksError> {
+    parse_did_doc(did_doc.as_str())?.get_key(key_id)
+}
+
+/// Parses the supplied DID doc as string, regardless of whether it is normalized (see [`DidDocNormalized`]) or not.
+#[cfg_attr(
+    not(all(feature = "jsonschema-validation", feature = "vc-data-integrity")),
+    expect(clippy::single_call_fn, reason = "..")
+)]
+pub(crate) fn parse_did_doc(did_doc: &str) -> Result<DidDoc, DidSidekicksError> {
+    from_str_bounded::<DidDocNormalized>(did_doc).map_or_else(
+        |_| from_str_bounded::<DidDoc>(did_doc),
+        |doc_norm| {
+            doc_norm
+                .to_did_doc()
+                .map_err(|err| DidSidekicksError::DeserializationFailed(err.to_string()))
+        },
+    )
 }
 
 impl DidDocExtended {
     /// The only non-empty constructor of the type.
     #[inline]
-    pub const fn new(
+    pub fn new(
         did_doc: DidDoc,
         did_method_parameters: HashMap<String, Arc<DidMethodParameter>>,
     ) -> Self {
         Self {
-            did_doc,
+            did_doc: Arc::new(did_doc),
             did_method_parameters,
         }
     }
 
     #[inline]
     pub fn get_did_doc_obj(&self) -> DidDoc {
-        self.did_doc.clone()
+        (*self.did_doc).clone()
     }
 
     /// A UniFFI-compliant version of [`DidDocExtended::get_did_doc_obj`] getter.
+    ///
+    /// The [`DidDoc`] is shared (reference-counted) rather than cloned, so calling it repeatedly is cheap.
     #[inline]
     pub fn get_did_doc(&self) -> Arc<DidDoc> {
-        Arc::new(self.get_did_doc_obj())
+        Arc::clone(&self.did_doc)
     }
 
     /// A UniFFI-compliant getter.
@@ -461,3 +853,101 @@ impl DidDocExtended {
         self.did_method_parameters.clone()
     }
 }
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+    use serde_json::json;
+
+    const OLD_DID: &str = "did:webvh:QmOld:old.example.com";
+    const NEW_DID: &str = "did:webvh:QmNew:new.example.com";
+
+    fn did_doc(id: &str, also_known_as: &[&str]) -> DidDoc {
+        DidDoc::from_json(
+            &json!({
+                "@context": ["https://www.w3.org/ns/did/v1"],
+                "id": id,
+                "verificationMethod": [],
+                "alsoKnownAs": also_known_as,
+            })
+            .to_string(),
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    fn test_builder() {
+        let method = VerificationMethod::new(
+            format!("{OLD_DID}#key-1"),
+            OLD_DID.to_owned(),
+            "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2".to_owned(),
+            VerificationType::Multikey,
+        );
+        let doc = DidDoc::new(OLD_DID)
+            .with_context("https://w3id.org/security/multikey/v1")
+            .with_context(DID_CONTEXT_V1)
+            .with_verification_method(method.clone())
+            .with_assertion_method(method)
+            .with_also_known_as(NEW_DID)
+            .with_service(json!({"id": "#files", "type": "relativeRef", "serviceEndpoint": "https://example.com"}));
+        let json = doc.to_json(EmitOptions::COMPACT).unwrap();
+
+        let parsed = DidDoc::from_json(&json).unwrap();
+        assert_eq!(
+            parsed.get_context(),
+            vec![DID_CONTEXT_V1, "https://w3id.org/security/multikey/v1"]
+        );
+        assert_eq!(parsed.get_id(), OLD_DID);
+        assert_eq!(parsed.get_verification_method().len(), 1);
+        assert_eq!(parsed.get_assertion_method().len(), 1);
+        assert!(parsed.get_authentication().is_empty());
+        assert!(parsed.is_also_known_as(NEW_DID));
+        assert_eq!(parsed.get_service().len(), 1);
+        assert_eq!(parsed.to_json(EmitOptions::COMPACT).unwrap(), json);
+    }
+
+    #[rstest]
+    fn test_is_also_known_as() {
+        let doc = did_doc(OLD_DID, &[NEW_DID, "https://example.com"]);
+        assert!(doc.is_also_known_as(NEW_DID));
+        assert!(!doc.is_also_known_as(OLD_DID));
+        assert_eq!(doc.get_also_known_as().len(), 2);
+        assert!(!did_doc(OLD_DID, &[]).is_also_known_as(NEW_DID));
+    }
+
+    #[rstest]
+    fn test_verify_also_known_as() {
+        let old = did_doc(OLD_DID, &[NEW_DID]);
+        let new = did_doc(NEW_DID, &[OLD_DID]);
+        old.verify_also_known_as(&new).unwrap();
+        new.verify_also_known_as(&old).unwrap();
+
+        assert_error(
+            old.verify_also_known_as(&did_doc(NEW_DID, &[])),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            &format!("{NEW_DID} does not refer to {OLD_DID}"),
+        );
+        assert_error(
+            old.verify_also_known_as(&old),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            "cannot be equivalent to itself",
+        );
+    }
+
+    #[rstest]
+    fn test_did_doc_extended_shares_did_doc() {
+        let extended = DidDocExtended::new(did_doc(OLD_DID, &[]), HashMap::new());
+        assert!(Arc::ptr_eq(
+            &extended.get_did_doc(),
+            &extended.get_did_doc()
+        ));
+        assert_eq!(extended.get_did_doc_obj().id, OLD_DID);
+    }
+}
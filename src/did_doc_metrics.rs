@@ -0,0 +1,255 @@
+// SPDX-License-Identifier: MIT
+
+//! Size and complexity metrics of a DID document, along with (optional) thresholds,
+//! so that registries may enforce DID document size limits consistently (e.g. as part of validation).
+
+use crate::did_doc::DidDoc;
+use crate::errors::DidSidekicksError;
+use crate::panic_boundary::catch_panic;
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// The size and complexity metrics of a DID document, as delivered by [`DidDoc::metrics`].
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct DidDocMetrics {
+    /// The size (in bytes) of the (compact) JSON text of the DID document.
+    pub byte_size: u64,
+    /// The number of distinct keys (verification methods), across `verificationMethod` and all verification relationships.
+    pub key_count: u64,
+    /// The number of services.
+    pub service_count: u64,
+    /// The number of `@context` entries.
+    pub context_count: u64,
+    /// The maximum nesting depth of JSON arrays/objects (counted the same way as [`crate::bounded_json::DeserializationLimits::max_depth`]).
+    pub max_depth: u64,
+}
+
+/// The (optional) thresholds to check [`DidDocMetrics`] against. A missing threshold is not checked at all.
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct DidDocThresholds {
+    pub max_byte_size: Option<u64>,
+    pub max_key_count: Option<u64>,
+    pub max_service_count: Option<u64>,
+    pub max_context_count: Option<u64>,
+    pub max_depth: Option<u64>,
+}
+
+/// A single exceeded threshold, as detected by [`DidDocMetrics::check`].
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct DidDocThresholdViolation {
+    /// The name of the metric (e.g. `byte_size`).
+    pub metric: String,
+    pub value: u64,
+    pub threshold: u64,
+}
+
+impl DidDocMetrics {
+    /// Checks these metrics against the supplied `thresholds`, thus delivering all exceeded ones (if any).
+    #[inline]
+    pub fn check(&self, thresholds: &DidDocThresholds) -> Vec<DidDocThresholdViolation> {
+        [
+            ("byte_size", self.byte_size, thresholds.max_byte_size),
+            ("key_count", self.key_count, thresholds.max_key_count),
+            (
+                "service_count",
+                self.service_count,
+                thresholds.max_service_count,
+            ),
+            (
+                "context_count",
+                self.context_count,
+                thresholds.max_context_count,
+            ),
+            ("max_depth", self.max_depth, thresholds.max_depth),
+        ]
+        .into_iter()
+        .filter_map(|(metric, value, threshold)| {
+            threshold
+                .filter(|max| value > *max)
+                .map(|max| DidDocThresholdViolation {
+                    metric: metric.to_owned(),
+                    value,
+                    threshold: max,
+                })
+        })
+        .collect()
+    }
+}
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
+impl DidDoc {
+    /// Delivers the size and complexity metrics of this DID document.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn metrics(&self) -> DidDocMetrics {
+        let json = serde_json::to_value(self).unwrap_or_default();
+        let key_ids: HashSet<&str> = self
+            .verification_method
+            .iter()
+            .chain(self.authentication.iter())
+            .chain(self.capability_invocation.iter())
+            .chain(self.capability_delegation.iter())
+            .chain(self.assertion_method.iter())
+            .chain(self.key_agreement.iter())
+            .map(|method| method.id.as_str())
+            .collect();
+
+        DidDocMetrics {
+            byte_size: to_u64(json.to_string().len()),
+            key_count: to_u64(key_ids.len()),
+            service_count: to_u64(self.service.len()),
+            context_count: to_u64(self.context.len()),
+            max_depth: depth(&json),
+        }
+    }
+
+    /// Checks the metrics of this DID document (see [`DidDoc::metrics`]) against the supplied `thresholds`,
+    /// thus delivering all exceeded ones (if any).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn check_metrics(&self, thresholds: DidDocThresholds) -> Vec<DidDocThresholdViolation> {
+        self.metrics().check(&thresholds)
+    }
+
+    /// Same as [`DidDoc::check_metrics`], but returning [`DidSidekicksError::InvalidDidDocument`] if any of `thresholds` is exceeded.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn validate_metrics(&self, thresholds: DidDocThresholds) -> Result<(), DidSidekicksError> {
+        catch_panic(
+            || {
+                let violations = self.check_metrics(thresholds);
+                if violations.is_empty() {
+                    return Ok(());
+                }
+                Err(DidSidekicksError::InvalidDidDocument(format!(
+                    "DID document exceeds its size/complexity thresholds: {}",
+                    violations
+                        .iter()
+                        .map(|violation| format!(
+                            "{} of {} exceeds {}",
+                            violation.metric, violation.value, violation.threshold
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )))
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+}
+
+fn to_u64(value: usize) -> u64 {
+    u64::try_from(value).unwrap_or(u64::MAX)
+}
+
+/// The nesting depth of JSON arrays/objects, i.e. `0` for any scalar value and `1` for an empty array/object.
+#[expect(clippy::pattern_type_mismatch, reason = "..")]
+fn depth(json: &Value) -> u64 {
+    let children_depth = match json {
+        Value::Array(elements) => elements.iter().map(depth).max(),
+        Value::Object(members) => members.values().map(depth).max(),
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => return 0,
+    };
+    children_depth.unwrap_or_default().saturating_add(1)
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::json_emission::EmitOptions;
+    use crate::test::assert_error;
+    use rstest::rstest;
+    use serde_json::json;
+
+    const DID: &str = "did:example:123";
+
+    fn did_doc() -> DidDoc {
+        let key = json!({"id": format!("{DID}#key-1"), "type": "Multikey", "publicKeyMultibase": "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2"});
+        DidDoc::from_json(
+            &json!({
+                "@context": ["https://www.w3.org/ns/did/v1", "https://w3id.org/security/multikey/v1"],
+                "id": DID,
+                "verificationMethod": [key],
+                "assertionMethod": [key],
+                "service": [{"id": "#files", "type": "LinkedDomains", "serviceEndpoint": {"origins": ["https://example.com"]}}],
+            })
+            .to_string(),
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    fn test_metrics() {
+        let did_doc = did_doc();
+        assert_eq!(
+            did_doc.metrics(),
+            DidDocMetrics {
+                byte_size: to_u64(did_doc.to_json(EmitOptions::COMPACT).unwrap().len()),
+                key_count: 1, // the very same key referenced twice
+                service_count: 1,
+                context_count: 2,
+                max_depth: 5, // service -> {} -> serviceEndpoint -> origins
+            }
+        );
+        assert!(did_doc
+            .check_metrics(DidDocThresholds::default())
+            .is_empty());
+    }
+
+    #[rstest]
+    fn test_check_metrics() {
+        let did_doc = did_doc();
+        let thresholds = DidDocThresholds {
+            max_byte_size: Some(100),
+            max_key_count: Some(1),
+            max_context_count: Some(1),
+            max_depth: Some(4),
+            ..DidDocThresholds::default()
+        };
+        let violations = did_doc.check_metrics(thresholds);
+        assert_eq!(
+            violations
+                .iter()
+                .map(|violation| violation.metric.as_str())
+                .collect::<Vec<_>>(),
+            vec!["byte_size", "context_count", "max_depth"]
+        );
+        assert_error(
+            did_doc.validate_metrics(thresholds),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            "context_count of 2 exceeds 1, max_depth of 5 exceeds 4",
+        );
+        did_doc
+            .validate_metrics(DidDocThresholds {
+                max_depth: Some(5),
+                ..DidDocThresholds::default()
+            })
+            .unwrap();
+    }
+
+    #[rstest]
+    #[case(json!(1), 0)]
+    #[case(json!([]), 1)]
+    #[case(json!({"a": [1, {"b": {}}], "c": []}), 4)]
+    fn test_depth(#[case] json: Value, #[case] expected: u64) {
+        assert_eq!(depth(&json), expected);
+    }
+}
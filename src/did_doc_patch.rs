@@ -0,0 +1,582 @@
+// SPDX-License-Identifier: MIT
+
+//! JSON Patch generation between two versions of a DID document, as well as application of such patches.
+//!
+//! As specified by https://www.rfc-editor.org/rfc/rfc6902, e.g. for compact update entries or UIs displaying the precise operations.
+//!
+//! The generated patch is canonical, i.e. the very same pair of DID documents always yields the very same list of operations:
+//! - object members are visited in (lexicographical) order of their names, removals first,
+//! - array elements are compared index-wise, whereas surplus elements are either appended (in ascending order)
+//!   or removed (in descending order, so that the indices of the remaining elements remain intact) and
+//! - only `add`, `remove` and `replace` operations are generated (while all six operations are supported on application).
+
+use crate::bounded_json::from_str_bounded;
+use crate::did_doc::DidDoc;
+use crate::errors::DidSidekicksError;
+use crate::json_emission::{emit_json, EmitOptions};
+use crate::panic_boundary::catch_panic;
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+
+/// The operations of a JSON Patch, as specified by https://www.rfc-editor.org/rfc/rfc6902#section-4.
+///
+/// A UniFFI-compliant enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum PatchOperation {
+    Add,
+    Remove,
+    Replace,
+    Move,
+    Copy,
+    Test,
+}
+
+impl PatchOperation {
+    /// The name of the operation, as used by the `op` member of a JSON Patch operation.
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        match *self {
+            Self::Add => "add",
+            Self::Remove => "remove",
+            Self::Replace => "replace",
+            Self::Move => "move",
+            Self::Copy => "copy",
+            Self::Test => "test",
+        }
+    }
+
+    #[expect(clippy::single_call_fn, reason = "..")]
+    fn parse(op: &str) -> Option<Self> {
+        [
+            Self::Add,
+            Self::Remove,
+            Self::Replace,
+            Self::Move,
+            Self::Copy,
+            Self::Test,
+        ]
+        .into_iter()
+        .find(|operation| operation.as_str() == op)
+    }
+}
+
+/// A single JSON Patch operation, as specified by https://www.rfc-editor.org/rfc/rfc6902#section-4.
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct PatchOp {
+    pub op: PatchOperation,
+    /// The target location (JSON pointer, as specified by https://www.rfc-editor.org/rfc/rfc6901).
+    pub path: String,
+    /// The source location (JSON pointer), required by `move` and `copy` operations only.
+    pub from: Option<String>,
+    /// The value (as compact JSON text), required by `add`, `replace` and `test` operations only.
+    pub value: Option<String>,
+}
+
+impl PatchOp {
+    fn new(op: PatchOperation, path: String, value: Option<&Value>) -> Self {
+        Self {
+            op,
+            path,
+            from: None,
+            value: value.map(Value::to_string),
+        }
+    }
+
+    fn parsed_value(&self) -> Result<Value, DidSidekicksError> {
+        let Some(value) = self.value.as_ref() else {
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "The '{}' operation at '{}' lacks a value",
+                self.op.as_str(),
+                self.path
+            )));
+        };
+        from_str_bounded(value).map_err(|err| {
+            DidSidekicksError::DeserializationFailed(format!(
+                "The value of the '{}' operation at '{}' is malformed: {err}",
+                self.op.as_str(),
+                self.path
+            ))
+        })
+    }
+
+    fn source_pointer(&self) -> Result<&str, DidSidekicksError> {
+        self.from.as_deref().ok_or_else(|| {
+            DidSidekicksError::DeserializationFailed(format!(
+                "The '{}' operation at '{}' lacks a source location ('from')",
+                self.op.as_str(),
+                self.path
+            ))
+        })
+    }
+}
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
+impl DidDoc {
+    /// Delivers the (canonical) JSON Patch transforming this DID document into the supplied `other` one.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn json_patch_to(&self, other: &Self) -> Vec<PatchOp> {
+        match (serde_json::to_value(self), serde_json::to_value(other)) {
+            (Ok(from), Ok(to)) => json_patch(&from, &to),
+            _ => vec![], // a DID document is always serializable
+        }
+    }
+
+    /// Delivers a copy of this DID document, where the supplied `patch` is applied.
+    ///
+    /// A malformed `patch` is denoted by [`DidSidekicksError::DeserializationFailed`],
+    /// whereas [`DidSidekicksError::InvalidDidDocument`] is returned if the `patch` is not applicable (e.g. a failing `test` operation)
+    /// or if it does not result in a valid DID document.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn apply_json_patch(&self, patch: Vec<PatchOp>) -> Result<Arc<Self>, DidSidekicksError> {
+        catch_panic(
+            || {
+                let mut json = serde_json::to_value(self)
+                    .map_err(|err| DidSidekicksError::SerializationFailed(err.to_string()))?;
+                apply_json_patch(&mut json, &patch)?;
+                serde_json::from_value(json).map(Arc::new).map_err(|err| {
+                    DidSidekicksError::InvalidDidDocument(format!(
+                        "The JSON Patch does not result in a valid DID document: {err}"
+                    ))
+                })
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+}
+
+/// Delivers the (canonical) JSON Patch transforming the supplied `from` JSON value into the supplied `to` one.
+#[inline]
+pub fn json_patch(from: &Value, to: &Value) -> Vec<PatchOp> {
+    let mut ops = vec![];
+    diff("", from, to, &mut ops);
+    ops
+}
+
+#[expect(clippy::pattern_type_mismatch, reason = "..")]
+fn diff(path: &str, from: &Value, to: &Value, ops: &mut Vec<PatchOp>) {
+    if from == to {
+        return;
+    }
+    match (from, to) {
+        (Value::Object(from_members), Value::Object(to_members)) => {
+            diff_objects(path, from_members, to_members, ops);
+        }
+        (Value::Array(from_elements), Value::Array(to_elements)) => {
+            diff_arrays(path, from_elements, to_elements, ops);
+        }
+        _ => ops.push(PatchOp::new(
+            PatchOperation::Replace,
+            path.to_owned(),
+            Some(to),
+        )),
+    }
+}
+
+#[expect(clippy::single_call_fn, reason = "..")]
+fn diff_objects(
+    path: &str,
+    from: &Map<String, Value>,
+    to: &Map<String, Value>,
+    ops: &mut Vec<PatchOp>,
+) {
+    let mut removed: Vec<&String> = from.keys().filter(|key| !to.contains_key(*key)).collect();
+    removed.sort();
+    for key in removed {
+        ops.push(PatchOp::new(
+            PatchOperation::Remove,
+            child_path(path, key),
+            None,
+        ));
+    }
+
+    let mut members: Vec<(&String, &Value)> = to.iter().collect();
+    members.sort_by_key(|&(key, _)| key);
+    for (key, value) in members {
+        match from.get(key) {
+            Some(previous) => diff(&child_path(path, key), previous, value, ops),
+            None => ops.push(PatchOp::new(
+                PatchOperation::Add,
+                child_path(path, key),
+                Some(value),
+            )),
+        }
+    }
+}
+
+#[expect(clippy::single_call_fn, reason = "..")]
+fn diff_arrays(path: &str, from: &[Value], to: &[Value], ops: &mut Vec<PatchOp>) {
+    for (index, (previous, value)) in from.iter().zip(to.iter()).enumerate() {
+        diff(&child_path(path, &index.to_string()), previous, value, ops);
+    }
+    for (index, value) in to.iter().enumerate().skip(from.len()) {
+        ops.push(PatchOp::new(
+            PatchOperation::Add,
+            child_path(path, &index.to_string()),
+            Some(value),
+        ));
+    }
+    for index in (to.len()..from.len()).rev() {
+        ops.push(PatchOp::new(
+            PatchOperation::Remove,
+            child_path(path, &index.to_string()),
+            None,
+        ));
+    }
+}
+
+/// Appends the supplied `token` to the supplied JSON pointer `path`, as specified by https://www.rfc-editor.org/rfc/rfc6901#section-3.
+fn child_path(path: &str, token: &str) -> String {
+    format!("{path}/{}", token.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Applies the supplied `patch` to the supplied JSON value in place, as specified by https://www.rfc-editor.org/rfc/rfc6902#section-4.
+///
+/// The operations are applied sequentially, so the JSON value might be left partially patched in case of failure.
+/// A malformed `patch` is denoted by [`DidSidekicksError::DeserializationFailed`],
+/// whereas a non-applicable one is denoted by [`DidSidekicksError::InvalidDidDocument`].
+#[inline]
+pub fn apply_json_patch(json: &mut Value, patch: &[PatchOp]) -> Result<(), DidSidekicksError> {
+    for op in patch {
+        match op.op {
+            PatchOperation::Add => add(json, &op.path, op.parsed_value()?)?,
+            PatchOperation::Remove => {
+                remove(json, &op.path)?;
+            }
+            PatchOperation::Replace => {
+                let value = op.parsed_value()?;
+                *lookup_mut(json, &op.path)? = value;
+            }
+            PatchOperation::Move => {
+                let from = op.source_pointer()?;
+                if op.path.starts_with(&format!("{from}/")) {
+                    return Err(not_applicable(
+                        &op.path,
+                        "a location cannot be moved into one of its children",
+                    ));
+                }
+                let value = remove(json, from)?;
+                add(json, &op.path, value)?;
+            }
+            PatchOperation::Copy => {
+                let from = op.source_pointer()?;
+                let value = json
+                    .pointer(from)
+                    .cloned()
+                    .ok_or_else(|| not_applicable(from, "non-existing location"))?;
+                add(json, &op.path, value)?;
+            }
+            PatchOperation::Test => {
+                let value = op.parsed_value()?;
+                if json.pointer(&op.path) != Some(&value) {
+                    return Err(not_applicable(&op.path, "test failed"));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn add(json: &mut Value, path: &str, value: Value) -> Result<(), DidSidekicksError> {
+    let Some((parent, token)) = split_path(path)? else {
+        *json = value;
+        return Ok(());
+    };
+    match *lookup_mut(json, parent)? {
+        Value::Object(ref mut object) => {
+            object.insert(token, value);
+            Ok(())
+        }
+        Value::Array(ref mut array) => {
+            let index = if token == "-" {
+                array.len()
+            } else {
+                array_index(&token)
+                    .filter(|index| *index <= array.len())
+                    .ok_or_else(|| not_applicable(path, "array index out of bounds"))?
+            };
+            array.insert(index, value);
+            Ok(())
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => Err(not_applicable(
+            path,
+            "the parent is neither an object nor an array",
+        )),
+    }
+}
+
+fn remove(json: &mut Value, path: &str) -> Result<Value, DidSidekicksError> {
+    let Some((parent, token)) = split_path(path)? else {
+        return Err(not_applicable(path, "the whole document cannot be removed"));
+    };
+    match *lookup_mut(json, parent)? {
+        Value::Object(ref mut object) => object
+            .shift_remove(&token)
+            .ok_or_else(|| not_applicable(path, "non-existing location")),
+        Value::Array(ref mut array) => {
+            let index = array_index(&token)
+                .filter(|index| *index < array.len())
+                .ok_or_else(|| not_applicable(path, "array index out of bounds"))?;
+            Ok(array.remove(index))
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => {
+            Err(not_applicable(path, "non-existing location"))
+        }
+    }
+}
+
+fn lookup_mut<'json>(
+    json: &'json mut Value,
+    path: &str,
+) -> Result<&'json mut Value, DidSidekicksError> {
+    json.pointer_mut(path)
+        .ok_or_else(|| not_applicable(path, "non-existing location"))
+}
+
+/// Splits the supplied JSON pointer `path` into its parent and its (unescaped) last token, unless it denotes the whole document.
+fn split_path(path: &str) -> Result<Option<(&str, String)>, DidSidekicksError> {
+    if path.is_empty() {
+        return Ok(None);
+    }
+    let Some((parent, token)) = path.rsplit_once('/') else {
+        return Err(DidSidekicksError::DeserializationFailed(format!(
+            "'{path}' is not a valid JSON pointer"
+        )));
+    };
+    Ok(Some((parent, token.replace("~1", "/").replace("~0", "~"))))
+}
+
+/// Parses an array index, as specified by https://www.rfc-editor.org/rfc/rfc6901#section-4 (i.e. leading zeros are not allowed).
+fn array_index(token: &str) -> Option<usize> {
+    if token.len() > 1 && token.starts_with('0') {
+        return None;
+    }
+    token.parse().ok()
+}
+
+fn not_applicable(path: &str, reason: &str) -> DidSidekicksError {
+    DidSidekicksError::InvalidDidDocument(format!(
+        "The JSON Patch is not applicable at '{path}': {reason}"
+    ))
+}
+
+/// Emits the supplied `patch` as JSON Patch document (JSON text), as specified by https://www.rfc-editor.org/rfc/rfc6902#section-3.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn json_patch_to_json(patch: Vec<PatchOp>) -> Result<String, DidSidekicksError> {
+    catch_panic(
+        || {
+            let ops = patch
+                .iter()
+                .map(|op| {
+                    let mut json = Map::new();
+                    json.insert("op".to_owned(), json!(op.op.as_str()));
+                    json.insert("path".to_owned(), json!(op.path));
+                    if let Some(from) = op.from.as_ref() {
+                        json.insert("from".to_owned(), json!(from));
+                    }
+                    if op.value.is_some() {
+                        json.insert("value".to_owned(), op.parsed_value()?);
+                    }
+                    Ok(Value::Object(json))
+                })
+                .collect::<Result<Vec<_>, DidSidekicksError>>()?;
+            emit_json(&ops, EmitOptions::COMPACT)
+        },
+        DidSidekicksError::InternalError,
+    )
+}
+
+/// Parses the supplied JSON Patch document (JSON text), as specified by https://www.rfc-editor.org/rfc/rfc6902#section-3.
+///
+/// Any failure is denoted by [`DidSidekicksError::DeserializationFailed`].
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn json_patch_from_json(json: String) -> Result<Vec<PatchOp>, DidSidekicksError> {
+    catch_panic(
+        || {
+            let malformed = |reason: String| {
+                DidSidekicksError::DeserializationFailed(format!("Malformed JSON Patch: {reason}"))
+            };
+            let Value::Array(ops) =
+                from_str_bounded(&json).map_err(|err| malformed(err.to_string()))?
+            else {
+                return Err(malformed("not an array".to_owned()));
+            };
+            ops.iter()
+                .map(|op| {
+                    let member = |name: &str| op.get(name).and_then(Value::as_str);
+                    let Some(operation) = member("op").and_then(PatchOperation::parse) else {
+                        return Err(malformed(format!("missing or unsupported 'op' in {op}")));
+                    };
+                    let Some(path) = member("path") else {
+                        return Err(malformed(format!("missing 'path' in {op}")));
+                    };
+                    Ok(PatchOp {
+                        op: operation,
+                        path: path.to_owned(),
+                        from: member("from").map(str::to_owned),
+                        value: op.get("value").map(Value::to_string),
+                    })
+                })
+                .collect()
+        },
+        DidSidekicksError::InternalError,
+    )
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+#[expect(clippy::shadow_reuse, reason = "..")]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+
+    const DID: &str = "did:example:123";
+
+    fn did_doc(verification_method: Value, service: Value) -> DidDoc {
+        DidDoc::from_json(
+            &json!({
+                "@context": ["https://www.w3.org/ns/did/v1"],
+                "id": DID,
+                "verificationMethod": verification_method,
+                "service": service,
+            })
+            .to_string(),
+        )
+        .unwrap()
+    }
+
+    fn op(op: PatchOperation, path: &str, value: Option<Value>) -> PatchOp {
+        PatchOp {
+            op,
+            path: path.to_owned(),
+            from: None,
+            value: value.as_ref().map(Value::to_string),
+        }
+    }
+
+    #[rstest]
+    fn test_json_patch_to() {
+        let key_1 = json!({"id": format!("{DID}#key-1"), "type": "Multikey", "publicKeyMultibase": "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2"});
+        let key_2 = json!({"id": format!("{DID}#key-2"), "type": "Multikey", "publicKeyMultibase": "z6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw"});
+        let from = did_doc(
+            json!([key_1, key_2]),
+            json!([{"id": "#files", "type": "LinkedDomains", "serviceEndpoint": "https://example.com/a/~"}]),
+        );
+        let to = did_doc(
+            json!([key_2]),
+            json!([{"id": "#files", "type": "LinkedDomains", "serviceEndpoint": "https://example.com/b/~"}, {"id": "#other", "type": "Other", "serviceEndpoint": "https://example.com"}]),
+        );
+
+        let patch = from.json_patch_to(&to);
+        assert_eq!(
+            patch,
+            vec![
+                op(
+                    PatchOperation::Replace,
+                    "/service/0/serviceEndpoint",
+                    Some(json!("https://example.com/b/~"))
+                ),
+                op(
+                    PatchOperation::Add,
+                    "/service/1",
+                    Some(
+                        json!({"id": "#other", "type": "Other", "serviceEndpoint": "https://example.com"})
+                    )
+                ),
+                op(
+                    PatchOperation::Replace,
+                    "/verificationMethod/0/id",
+                    Some(json!(format!("{DID}#key-2")))
+                ),
+                op(
+                    PatchOperation::Replace,
+                    "/verificationMethod/0/publicKeyMultibase",
+                    Some(json!("z6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw"))
+                ),
+                op(PatchOperation::Remove, "/verificationMethod/1", None),
+            ]
+        );
+        assert!(from.json_patch_to(&from).is_empty());
+
+        let patched = from.apply_json_patch(patch.to_owned()).unwrap();
+        assert_eq!(
+            patched.to_json(EmitOptions::CANONICAL).unwrap(),
+            to.to_json(EmitOptions::CANONICAL).unwrap()
+        );
+
+        // round-trip through a JSON Patch document
+        let json = json_patch_to_json(patch.to_owned()).unwrap();
+        assert_eq!(json_patch_from_json(json).unwrap(), patch);
+    }
+
+    #[rstest]
+    fn test_apply_json_patch() {
+        let mut json = json!({"a/b": {"c~d": [1, 2]}, "e": null});
+        let patch = json_patch_from_json(
+            json!([
+                {"op": "test", "path": "/a~1b/c~0d/1", "value": 2},
+                {"op": "add", "path": "/a~1b/c~0d/-", "value": 3},
+                {"op": "add", "path": "/a~1b/c~0d/0", "value": 0},
+                {"op": "copy", "from": "/a~1b/c~0d", "path": "/f"},
+                {"op": "move", "from": "/e", "path": "/g"},
+                {"op": "remove", "path": "/f/0"},
+                {"op": "replace", "path": "/a~1b", "value": true},
+            ])
+            .to_string(),
+        )
+        .unwrap();
+        apply_json_patch(&mut json, &patch).unwrap();
+        assert_eq!(json, json!({"a/b": true, "f": [1, 2, 3], "g": null}));
+    }
+
+    #[rstest]
+    #[case(json!([{"op": "test", "path": "/a", "value": 2}]), DidSidekicksErrorKind::InvalidDidDocument, "at '/a': test failed")]
+    #[case(json!([{"op": "add", "path": "/b/3", "value": 2}]), DidSidekicksErrorKind::InvalidDidDocument, "array index out of bounds")]
+    #[case(json!([{"op": "remove", "path": "/b/01"}]), DidSidekicksErrorKind::InvalidDidDocument, "array index out of bounds")]
+    #[case(json!([{"op": "remove", "path": "/c"}]), DidSidekicksErrorKind::InvalidDidDocument, "non-existing location")]
+    #[case(json!([{"op": "move", "from": "/b", "path": "/b/0"}]), DidSidekicksErrorKind::InvalidDidDocument, "moved into one of its children")]
+    #[case(json!([{"op": "copy", "path": "/c"}]), DidSidekicksErrorKind::DeserializationFailed, "lacks a source location")]
+    #[case(json!([{"op": "replace", "path": "/a"}]), DidSidekicksErrorKind::DeserializationFailed, "lacks a value")]
+    #[case(json!([{"op": "add", "path": "a", "value": 1}]), DidSidekicksErrorKind::DeserializationFailed, "not a valid JSON pointer")]
+    fn test_apply_invalid_json_patch(
+        #[case] patch: Value,
+        #[case] kind: DidSidekicksErrorKind,
+        #[case] expected: &str,
+    ) {
+        let mut json = json!({"a": 1, "b": [1, 2]});
+        let patch = json_patch_from_json(patch.to_string()).unwrap();
+        assert_error(apply_json_patch(&mut json, &patch), kind, expected);
+    }
+
+    #[rstest]
+    #[case(r#"{"op": "add"}"#, "not an array")]
+    #[case(r#"[{"op": "merge", "path": "/a"}]"#, "unsupported 'op'")]
+    #[case(r#"[{"op": "add", "value": 1}]"#, "missing 'path'")]
+    fn test_json_patch_from_malformed_json(#[case] json: &str, #[case] expected: &str) {
+        assert_error(
+            json_patch_from_json(json.to_owned()),
+            DidSidekicksErrorKind::DeserializationFailed,
+            expected,
+        );
+    }
+}
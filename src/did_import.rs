@@ -0,0 +1,268 @@
+// SPDX-License-Identifier: MIT
+
+//! Import of DID documents resolved from other DID methods (`did:key`, `did:jwk` and `did:web`),
+//! intended to ease the migration of existing issuers onto `did:webvh`.
+//!
+//! The keys (verification methods along with all verification relationships) and services of the supplied DID document
+//! are re-expressed as a `did:webvh` genesis DID document candidate, i.e. featuring the `{SCID}` placeholder
+//! (see https://identity.foundation/didwebvh/v1.0/#create-register), whereas the candidate is validated prior to delivery.
+
+use crate::did_doc::{DidDoc, VerificationMethod};
+use crate::errors::DidSidekicksError;
+use crate::panic_boundary::catch_panic;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// The placeholder of the SCID (self-certifying identifier), to be replaced once the genesis DID log entry is created.
+pub const DID_WEBVH_SCID_PLACEHOLDER: &str = "{SCID}";
+
+const DID_WEBVH_PREFIX: &str = "did:webvh:";
+const DID_KEY_PREFIX: &str = "did:key:";
+const DID_JWK_PREFIX: &str = "did:jwk:";
+const DID_WEB_PREFIX: &str = "did:web:";
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
+impl DidDoc {
+    /// Re-expresses this DID document (resolved from either `did:key`, `did:jwk` or `did:web`) as a `did:webvh` genesis
+    /// DID document candidate, i.e. featuring the `did:webvh:{SCID}:<domain>` DID, while reusing all keys and services.
+    ///
+    /// The `domain` (e.g. `example.com:issuers:123`) is required for `did:key`/`did:jwk`, whereas for `did:web` it defaults
+    /// to the method-specific identifier of the DID (e.g. `example.com:issuers:123` in case of `did:web:example.com:issuers:123`).
+    ///
+    /// Any failure (e.g. an unsupported DID method, a deactivated DID document or malformed verification method ids)
+    /// is denoted by [`DidSidekicksError::InvalidDidDocument`].
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn to_webvh_genesis_candidate(
+        &self,
+        domain: Option<String>,
+    ) -> Result<Arc<Self>, DidSidekicksError> {
+        catch_panic(
+            || {
+                if ![DID_KEY_PREFIX, DID_JWK_PREFIX, DID_WEB_PREFIX]
+                    .iter()
+                    .any(|prefix| self.id.starts_with(prefix))
+                {
+                    return Err(DidSidekicksError::InvalidDidDocument(format!(
+                        "Unsupported DID method of {}, expected any of did:key, did:jwk or did:web",
+                        self.id
+                    )));
+                }
+                let Some(webvh_domain) =
+                    domain.or_else(|| self.id.strip_prefix(DID_WEB_PREFIX).map(str::to_owned))
+                else {
+                    return Err(DidSidekicksError::InvalidDidDocument(format!(
+                        "A domain is required to import the DID document of {}",
+                        self.id
+                    )));
+                };
+                if webvh_domain.is_empty() || webvh_domain.contains(['/', '#', '?']) {
+                    return Err(DidSidekicksError::InvalidDidDocument(format!(
+                        "Invalid domain '{webvh_domain}', expected e.g. 'example.com:issuers:123'"
+                    )));
+                }
+                if self.get_deactivated() {
+                    return Err(DidSidekicksError::InvalidDidDocument(format!(
+                        "The DID document of {} is deactivated",
+                        self.id
+                    )));
+                }
+                if self.verification_method.is_empty() {
+                    return Err(DidSidekicksError::InvalidDidDocument(format!(
+                        "The DID document of {} features no verification method",
+                        self.id
+                    )));
+                }
+
+                let did = format!("{DID_WEBVH_PREFIX}{DID_WEBVH_SCID_PLACEHOLDER}:{webvh_domain}");
+                let rebase = |methods: &[VerificationMethod]| {
+                    methods
+                        .iter()
+                        .map(|method| self.rebase_verification_method(method, &did))
+                        .collect::<Result<Vec<_>, _>>()
+                };
+                let candidate = Self {
+                    context: self.context.clone(),
+                    id: did.to_owned(),
+                    verification_method: rebase(&self.verification_method)?,
+                    authentication: rebase(&self.authentication)?,
+                    capability_invocation: rebase(&self.capability_invocation)?,
+                    capability_delegation: rebase(&self.capability_delegation)?,
+                    assertion_method: rebase(&self.assertion_method)?,
+                    key_agreement: rebase(&self.key_agreement)?,
+                    // CAUTION The "controller" property must not be present (see DidDoc::controller)
+                    controller: vec![],
+                    also_known_as: self.also_known_as.clone(),
+                    deactivated: None,
+                    service: self
+                        .service
+                        .iter()
+                        .map(|service| self.rebase_service(service, &did))
+                        .collect(),
+                };
+
+                candidate.validate_verification_method_ids()?;
+                Ok(Arc::new(candidate))
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Re-expresses the supplied `method` (of this DID document) in terms of the supplied `did`, while retaining its fragment.
+    fn rebase_verification_method(
+        &self,
+        method: &VerificationMethod,
+        did: &str,
+    ) -> Result<VerificationMethod, DidSidekicksError> {
+        let fragment = match method.id.split_once('#') {
+            Some((source_did, fragment))
+                if !fragment.is_empty() && (source_did.is_empty() || source_did == self.id) =>
+            {
+                fragment
+            }
+            Some(_) | None => {
+                return Err(DidSidekicksError::InvalidDidDocument(format!(
+                    "The verification method id {} does not refer to the DID document of {}",
+                    method.id, self.id
+                )))
+            }
+        };
+
+        let mut rebased = method.clone();
+        rebased.id = format!("{did}#{fragment}");
+        // CAUTION The "controller" property must not be present (see VerificationMethod::controller)
+        rebased.controller = String::new();
+        Ok(rebased)
+    }
+
+    /// Re-expresses the id of the supplied `service` (of this DID document) in terms of the supplied `did`, if required.
+    fn rebase_service(&self, service: &Value, did: &str) -> Value {
+        let mut rebased = service.clone();
+        if let Some(id) = rebased.get_mut("id") {
+            if let Some(fragment) = id
+                .as_str()
+                .and_then(|service_id| service_id.strip_prefix(self.id.as_str()))
+                .filter(|fragment| fragment.starts_with('#'))
+            {
+                *id = Value::String(format!("{did}{fragment}"));
+            }
+        }
+        rebased
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+    use serde_json::json;
+
+    const MULTIKEY: &str = "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2";
+
+    fn did_key_doc() -> DidDoc {
+        let did = format!("did:key:{MULTIKEY}");
+        let method = json!({"id": format!("{did}#{MULTIKEY}"), "type": "Multikey", "controller": did, "publicKeyMultibase": MULTIKEY});
+        DidDoc::from_json(
+            &json!({
+                "@context": ["https://www.w3.org/ns/did/v1", "https://w3id.org/security/multikey/v1"],
+                "id": did,
+                "verificationMethod": [method],
+                "authentication": [method],
+                "assertionMethod": [method],
+            })
+            .to_string(),
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    fn test_to_webvh_genesis_candidate_from_did_key() {
+        let candidate = did_key_doc()
+            .to_webvh_genesis_candidate(Some("example.com:issuers:123".to_owned()))
+            .unwrap();
+
+        let did = "did:webvh:{SCID}:example.com:issuers:123";
+        assert_eq!(candidate.id, did);
+        assert_eq!(
+            candidate.verification_method.first().unwrap().id,
+            format!("{did}#{MULTIKEY}")
+        );
+        assert!(candidate.verification_method.first().unwrap().controller.is_empty());
+        assert_eq!(candidate.authentication.len(), 1);
+        assert_eq!(candidate.assertion_method.len(), 1);
+    }
+
+    #[rstest]
+    fn test_to_webvh_genesis_candidate_from_did_web() {
+        let did_doc = DidDoc::from_json(
+            &json!({
+                "@context": ["https://www.w3.org/ns/did/v1"],
+                "id": "did:web:example.com:issuer",
+                "verificationMethod": [{"id": "#key-1", "type": "JsonWebKey2020", "publicKeyJwk": {"kty": "OKP", "crv": "Ed25519", "x": "rPmqPT3Y4RbGRhqkTE_rOnnAHuy3gIG6i1GT8tVeVSI"}}],
+                "service": [
+                    {"id": "did:web:example.com:issuer#linked-domain", "type": "LinkedDomains", "serviceEndpoint": "https://example.com"},
+                    {"id": "#whois", "type": "LinkedVerifiablePresentation", "serviceEndpoint": "https://example.com/whois.vp"},
+                ],
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let candidate = did_doc.to_webvh_genesis_candidate(None).unwrap();
+
+        let did = "did:webvh:{SCID}:example.com:issuer";
+        assert_eq!(candidate.id, did);
+        assert_eq!(
+            candidate.verification_method.first().unwrap().id,
+            format!("{did}#key-1")
+        );
+        candidate.get_key("key-1".to_owned()).unwrap();
+        assert_eq!(
+            candidate.get_service(),
+            vec![
+                json!({"id": format!("{did}#linked-domain"), "type": "LinkedDomains", "serviceEndpoint": "https://example.com"}).to_string(),
+                json!({"id": "#whois", "type": "LinkedVerifiablePresentation", "serviceEndpoint": "https://example.com/whois.vp"}).to_string(),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(None, "A domain is required")]
+    #[case(Some(""), "Invalid domain")]
+    #[case(Some("example.com/issuer"), "Invalid domain")]
+    fn test_to_webvh_genesis_candidate_invalid_domain(
+        #[case] domain: Option<&str>,
+        #[case] error_contains: &str,
+    ) {
+        assert_error(
+            did_key_doc().to_webvh_genesis_candidate(domain.map(str::to_owned)),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            error_contains,
+        );
+    }
+
+    #[rstest]
+    fn test_to_webvh_genesis_candidate_unsupported() {
+        let mut did_doc = did_key_doc();
+        did_doc.deactivated = Some(true);
+        assert_error(
+            did_doc.to_webvh_genesis_candidate(Some("example.com".to_owned())),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            "is deactivated",
+        );
+
+        did_doc.id = "did:example:123".to_owned();
+        assert_error(
+            did_doc.to_webvh_genesis_candidate(Some("example.com".to_owned())),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            "Unsupported DID method",
+        );
+    }
+}
@@ -1,12 +1,27 @@
 // SPDX-License-Identifier: MIT
 
+use core::cell::Cell;
+use core::time::Duration;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 use thiserror::Error;
 
+use crate::bounded_json::from_str_bounded;
+use crate::cache::VALIDATOR_CACHE;
 use crate::custom_jsonschema_keywords::*;
+use crate::did_log_entry_example::example_instance;
+use crate::metrics::observe_verification;
+use crate::panic_boundary::catch_panic;
 use jsonschema::draft202012::meta as jsch_meta;
-use jsonschema::{options as jsch_opts, Draft, Validator as ValidatorBase};
-use serde_json::from_str as json_from_str;
+use jsonschema::error::ValidationErrorKind;
+use jsonschema::{
+    options as jsch_opts, Draft, PatternOptions, ValidationError, Validator as ValidatorBase,
+};
+use serde_json::{from_str as json_from_str, json, Value};
+
+/// The base of the `$id` assigned to any schema embedded by [`DidLogEntryValidator::try_from_method_schemas`] lacking one.
+const EMBEDDED_SCHEMA_ID_BASE: &str = "urn:did-sidekicks:schema:";
 
 /// Represents any error condition that might occur in conjunction with [`DidLogEntryValidator`].
 ///
@@ -21,6 +36,12 @@ pub enum DidLogEntryValidatorError {
     ValidationError(String),
     #[error("the supplied JSON instance cannot be deserialized: {0}")]
     DeserializationError(String),
+    /// An unexpected internal error (e.g. a panic caught at the FFI boundary).
+    #[error("internal error: {0}")]
+    InternalError(String),
+    /// The budget of the validation (see [`ValidationLimits`]) has been exceeded, hence the validation was aborted.
+    #[error("the validation budget is exceeded: {0}")]
+    BudgetExceeded(String),
 }
 
 impl DidLogEntryValidatorError {
@@ -30,6 +51,8 @@ impl DidLogEntryValidatorError {
         match *self {
             Self::ValidationError(_) => DidLogEntryValidatorErrorKind::ValidationError,
             Self::DeserializationError(_) => DidLogEntryValidatorErrorKind::DeserializationError,
+            Self::InternalError(_) => DidLogEntryValidatorErrorKind::InternalError,
+            Self::BudgetExceeded(_) => DidLogEntryValidatorErrorKind::BudgetExceeded,
         }
     }
 }
@@ -45,14 +68,246 @@ impl DidLogEntryValidatorError {
 pub enum DidLogEntryValidatorErrorKind {
     ValidationError,
     DeserializationError,
+    InternalError,
+    BudgetExceeded,
+}
+
+/// The budget of a single validation performed by [`DidLogEntryValidator`].
+///
+/// It protects (e.g. internet-facing) services from validation-based DoS, i.e. from hostile input causing pathological
+/// regex or keyword evaluation.
+///
+/// Once exhausted, the validation is aborted and [`DidLogEntryValidatorError::BudgetExceeded`] is returned.
+///
+/// CAUTION Only the following is bounded, as the evaluation of built-in keywords can not be interrupted:
+/// - the evaluation of custom keywords (e.g. `did-version-id`) as well as the reporting of violations (i.e. steps),
+/// - the regex backtracking (e.g. due to `pattern` keyword) and
+/// - the duration, as checked on each step as well as once the validation is over.
+///
+/// Hence, the evaluation of built-in keywords is bounded solely by the size (and depth) of the instance
+/// (see [`crate::bounded_json::DeserializationLimits`]) along with the complexity of the schema. For the very same reason,
+/// the duration may be exceeded (e.g. by a schema featuring nested `anyOf` against a large instance) before the validation is aborted,
+/// yet such a validation never succeeds. A [`crate::did_log_verifier::DidLogVerifier`] checks the duration between DID log entries, too.
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct ValidationLimits {
+    /// The maximum duration (in milliseconds) of a single validation, as checked on each step (see `max_steps`)
+    /// as well as once the validation is over.
+    pub max_duration_millis: u64,
+    /// The maximum number of steps of a single validation, i.e. evaluations of custom keywords (e.g. `did-version-id`)
+    /// as well as reported violations.
+    pub max_steps: u64,
+    /// The maximum number of backtracking steps of a single regex evaluation (e.g. due to `pattern` keyword).
+    pub max_regex_backtracks: u64,
+}
+
+impl ValidationLimits {
+    /// The default limits, i.e. 5 seconds, 100k steps and 1M backtracking steps (the default of the regex engine).
+    pub const DEFAULT: Self = Self {
+        max_duration_millis: 5_000,
+        max_steps: 100_000,
+        max_regex_backtracks: 1_000_000,
+    };
+
+    /// Delivers the deadline of a validation starting right now, if representable at all.
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        Instant::now().checked_add(Duration::from_millis(self.max_duration_millis))
+    }
+}
+
+impl Default for ValidationLimits {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+thread_local! {
+    /// The (remaining) budget of the validation currently running on this thread, if any.
+    static VALIDATION_BUDGET: Cell<Option<ValidationBudget>> = const { Cell::new(None) };
+}
+
+/// The (remaining) budget of a single validation, as denoted by [`ValidationLimits`].
+#[derive(Clone, Copy)]
+struct ValidationBudget {
+    deadline: Option<Instant>,
+    remaining_steps: u64,
+    exceeded: bool,
+}
+
+impl ValidationBudget {
+    #[expect(clippy::single_call_fn, reason = "..")]
+    fn new(limits: ValidationLimits) -> Self {
+        Self {
+            deadline: limits.deadline(),
+            remaining_steps: limits.max_steps,
+            exceeded: false,
+        }
+    }
+
+    fn is_exceeded(&self) -> bool {
+        self.exceeded
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Consumes a single step, thus delivering whether the budget is not exceeded (yet).
+    fn consume(&mut self) -> bool {
+        if self.remaining_steps == 0 || self.is_exceeded() {
+            self.exceeded = true;
+            return false;
+        }
+        self.remaining_steps = self.remaining_steps.saturating_sub(1);
+        true
+    }
+}
+
+/// Restores the budget of the enclosing validation (if any) once dropped, i.e. even in case of a panic.
+struct ValidationBudgetScope(Option<ValidationBudget>);
+
+impl ValidationBudgetScope {
+    #[expect(clippy::single_call_fn, reason = "..")]
+    fn enter(limits: ValidationLimits) -> Self {
+        Self(VALIDATION_BUDGET.with(|cell| cell.replace(Some(ValidationBudget::new(limits)))))
+    }
+
+    fn is_exceeded(&self) -> bool {
+        VALIDATION_BUDGET.with(|cell| cell.get().is_some_and(|budget| budget.is_exceeded()))
+    }
+}
+
+impl Drop for ValidationBudgetScope {
+    #[inline]
+    fn drop(&mut self) {
+        let enclosing = self.0;
+        VALIDATION_BUDGET.with(|cell| cell.set(enclosing));
+    }
+}
+
+/// Consumes a single step of the budget of the validation currently running on this thread (if any),
+/// thus delivering whether the budget is not exceeded (yet). Intended to be called by any custom keyword.
+pub(crate) fn consume_validation_step() -> bool {
+    VALIDATION_BUDGET.with(|cell| {
+        cell.get().is_none_or(|mut budget| {
+            let available = budget.consume();
+            cell.set(Some(budget));
+            available
+        })
+    })
+}
+
+/// Converts the supplied schema validation `error`, whereas an exceeded regex backtracking limit
+/// is denoted by [`DidLogEntryValidatorError::BudgetExceeded`].
+fn to_validator_error(error: &ValidationError) -> DidLogEntryValidatorError {
+    if matches!(
+        error.kind,
+        ValidationErrorKind::BacktrackLimitExceeded { .. }
+    ) {
+        return DidLogEntryValidatorError::BudgetExceeded(error.to_string());
+    }
+    DidLogEntryValidatorError::ValidationError(error.to_string())
+}
+
+/// A (formally approved) waiver of a specific schema violation, so the violation is accepted rather than treated as failure.
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct SchemaWaiver {
+    /// The schema keyword of the waived violation (e.g. `format`, `pattern` or `additionalProperties`).
+    pub keyword: String,
+    /// The JSON pointer (as specified by https://www.rfc-editor.org/rfc/rfc6901) of the violating part of the instance,
+    /// whereas `*` matches any single segment (e.g. `/3/witness/witnesses/*/id`) and a trailing `/**` matches any suffix.
+    pub pointer_pattern: String,
+    /// The reason of the waiver (e.g. a reference to the governance decision), for reporting purposes only.
+    pub reason: String,
+}
+
+impl SchemaWaiver {
+    /// Whether this waiver applies to the supplied `violation`.
+    #[inline]
+    pub fn applies_to(&self, violation: &SchemaViolation) -> bool {
+        self.keyword == violation.keyword
+            && pointer_matches(&self.pointer_pattern, &violation.instance_path)
+    }
+}
+
+/// Whether the supplied JSON `pointer` matches the supplied `pattern` (see [`SchemaWaiver::pointer_pattern`]).
+#[cfg_attr(not(test), expect(clippy::single_call_fn, reason = ".."))]
+fn pointer_matches(pattern: &str, pointer: &str) -> bool {
+    let mut pattern_segments = pattern.split('/');
+    let mut pointer_segments = pointer.split('/');
+    loop {
+        match (pattern_segments.next(), pointer_segments.next()) {
+            (Some("**"), _) => return pattern_segments.next().is_none(),
+            (Some(pattern_segment), Some(pointer_segment)) => {
+                if pattern_segment != "*" && pattern_segment != pointer_segment {
+                    return false;
+                }
+            }
+            (None, None) => return true,
+            (Some(_), None) | (None, Some(_)) => return false,
+        }
+    }
 }
 
-/// W.r.t. any of specification versions available at https://identity.foundation/didwebvh
+/// A single schema violation, as reported by [`DidLogEntryValidator::validate_with_waivers`].
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct SchemaViolation {
+    /// The schema keyword of the violation (e.g. `format`).
+    pub keyword: String,
+    /// The JSON pointer of the violating part of the instance (empty in case of the whole instance).
+    pub instance_path: String,
+    /// The JSON pointer of the violated part of the schema.
+    pub schema_path: String,
+    pub message: String,
+    /// The reason of the waiver (if waived).
+    pub waiver_reason: Option<String>,
+}
+
+/// W.r.t. any of specification versions available at https://identity.foundation/didwebvh.
 ///
 /// A UniFFI-compliant trait.
 pub trait DidLogEntryJsonSchema: Send + Sync {
     /// Delivers a proper JSON schema (in UTF-8 format) fully describing a DID log entry.
     fn get_json_schema(&self) -> String;
+
+    /// Delivers a minimal DID log entry (as JSON text) valid against the schema delivered by [`Self::get_json_schema`],
+    /// i.e. featuring required properties (and items) only, while honoring any custom keyword (e.g. [`DidVersionIdKeyword`]).
+    /// Useful for tests, documentation tooling and binding smoke tests.
+    ///
+    /// If the generated entry does not satisfy the schema (e.g. due to some `pattern`), [`DidLogEntryValidatorError::ValidationError`]
+    /// is returned, whereas a malformed schema is denoted by [`DidLogEntryValidatorError::InternalError`].
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    fn example_entry(&self) -> Result<String, DidLogEntryValidatorError> {
+        catch_panic(
+            || {
+                let json_schema = self.get_json_schema();
+                let schema: Value = json_from_str(&json_schema).map_err(|err| {
+                    DidLogEntryValidatorError::InternalError(format!("malformed schema: {err}"))
+                })?;
+                let entry = example_instance(&schema)
+                    .ok_or_else(|| {
+                        DidLogEntryValidatorError::ValidationError(
+                            "The schema is either unsatisfiable or nested too deeply".to_owned(),
+                        )
+                    })?
+                    .to_string();
+
+                DidLogEntryValidator::try_from_json_schema(&json_schema)?.validate_str(&entry)?;
+                Ok(entry)
+            },
+            DidLogEntryValidatorError::InternalError,
+        )
+    }
 }
 
 /// A compiled JSON Schema validator.
@@ -63,9 +318,12 @@ pub trait DidLogEntryJsonSchema: Send + Sync {
 ///
 /// Intended to be used predominantly in conjunction with [`DidLogEntryJsonSchema`] trait implementations.
 //#[derive(Debug, Default, PartialEq)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DidLogEntryValidator {
-    validator: ValidatorBase,
+    // shared, as a compiled validator is reused by the opt-in global cache layer (see DidLogEntryValidator::from_cached).
+    // A malformed schema (supplied via any of the infallible constructors) is reported by each validation instead.
+    validator: Result<Arc<ValidatorBase>, String>,
+    limits: ValidationLimits,
 }
 
 impl DidLogEntryValidator {
@@ -76,22 +334,293 @@ impl DidLogEntryValidator {
     /// A UniFFI-compliant method.
     #[inline]
     pub fn validate(&self, instance: String) -> Result<(), DidLogEntryValidatorError> {
-        self.validate_str(&instance)
+        catch_panic(
+            || self.validate_str(&instance),
+            DidLogEntryValidatorError::InternalError,
+        )
+    }
+
+    /// Same as [`From`], but returning [`DidLogEntryValidatorError::InternalError`] in case of a malformed `schema`
+    /// (instead of deferring the error to each validation).
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub fn try_from_schema(
+        schema: Arc<dyn DidLogEntryJsonSchema>,
+    ) -> Result<Self, DidLogEntryValidatorError> {
+        Self::try_from_json_schema(&schema.get_json_schema())
+    }
+
+    /// Same as [`DidLogEntryValidator::try_from_schema`], but for a schema supplied as `&str`.
+    #[inline]
+    pub fn try_from_json_schema(schema: &str) -> Result<Self, DidLogEntryValidatorError> {
+        catch_panic(
+            || Ok(Self::build(schema, ValidationLimits::DEFAULT)),
+            DidLogEntryValidatorError::InternalError,
+        )
+    }
+
+    /// Same as [`DidLogEntryValidator::try_from_schema`], while enforcing the supplied `limits` on each validation
+    /// (instead of [`ValidationLimits::DEFAULT`]).
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub fn try_from_schema_with_limits(
+        schema: Arc<dyn DidLogEntryJsonSchema>,
+        limits: ValidationLimits,
+    ) -> Result<Self, DidLogEntryValidatorError> {
+        catch_panic(
+            || Ok(Self::build(&schema.get_json_schema(), limits)),
+            DidLogEntryValidatorError::InternalError,
+        )
+    }
+
+    /// Create a new DID log entry JSON Schema validator (the same way [`DidLogEntryValidator::try_from_schema`] does it),
+    /// while relying on the opt-in global cache layer (see [`crate::cache::set_caching_enabled`]).
+    ///
+    /// Hence, if caching is enabled, the very same `schema` is compiled only once.
+    ///
+    /// A malformed schema is denoted by [`DidLogEntryValidatorError::InternalError`] (and never cached).
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub fn from_cached(
+        schema: Arc<dyn DidLogEntryJsonSchema>,
+    ) -> Result<Self, DidLogEntryValidatorError> {
+        catch_panic(
+            || {
+                let json_schema = schema.get_json_schema();
+                VALIDATOR_CACHE.get_or_try_insert_with(&json_schema, || {
+                    Self::try_from_json_schema(&json_schema)
+                })
+            },
+            DidLogEntryValidatorError::InternalError,
+        )
+    }
+
+    /// Create a new DID log entry JSON Schema validator (the same way [`From`] does it) capable of validating
+    /// mixed-version DID logs, i.e. relying on a single combined schema dispatching (via `if`/`then`/`else`)
+    /// on the `method` DID parameter of an entry, as featured by the supplied `schemas` (e.g. `did:webvh:1.0`).
+    ///
+    /// Both object (`{"parameters":{"method":..}}`) and array (`did:tdw`, i.e. `[.., .., {"method":..}, ..]`) entries are supported.
+    /// Any entry featuring no (listed) `method` (e.g. any non-first entry) is validated against the `fallback` schema, if supplied.
+    /// Otherwise, it is rejected.
+    ///
+    /// A malformed schema is denoted by [`DidLogEntryValidatorError::InternalError`].
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub fn try_from_method_schemas(
+        schemas: HashMap<String, Arc<dyn DidLogEntryJsonSchema>>,
+        fallback: Option<Arc<dyn DidLogEntryJsonSchema>>,
+    ) -> Result<Self, DidLogEntryValidatorError> {
+        let combined = combine_method_schemas(&schemas, fallback.as_deref())?;
+        Self::try_from_json_schema(&combined.to_string())
+    }
+
+    /// Validate the supplied `instance` against the `schema` (supplied earlier via constructor), while accepting any violation
+    /// matching any of supplied `waivers`. Hence, the waived violations are delivered (rather than treated as failure).
+    ///
+    /// If any violation is not waived, [`DidLogEntryValidatorError::ValidationError`] (listing all such violations) is returned.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn validate_with_waivers(
+        &self,
+        instance: String,
+        waivers: Vec<SchemaWaiver>,
+    ) -> Result<Vec<SchemaViolation>, DidLogEntryValidatorError> {
+        catch_panic(
+            || self.validate_str_with_waivers(&instance, &waivers),
+            DidLogEntryValidatorError::InternalError,
+        )
+    }
+
+    fn validate_str_with_waivers(
+        &self,
+        instance: &str,
+        waivers: &[SchemaWaiver],
+    ) -> Result<Vec<SchemaViolation>, DidLogEntryValidatorError> {
+        let val: Value = from_str_bounded(instance).map_err(|err| {
+            DidLogEntryValidatorError::DeserializationError(err.detail().into_owned())
+        })?;
+
+        let violations = self.within_budget(|| {
+            let mut violations = Vec::new();
+            for err in self.compiled()?.iter_errors(&val) {
+                if matches!(err.kind, ValidationErrorKind::BacktrackLimitExceeded { .. }) {
+                    return Err(to_validator_error(&err));
+                }
+                if !consume_validation_step() {
+                    break; // thus aborting the (lazy) evaluation of any further violation
+                }
+                let schema_path = err.schema_path.to_string();
+                let mut violation = SchemaViolation {
+                    keyword: schema_path.rsplit('/').next().unwrap_or_default().to_owned(),
+                    instance_path: err.instance_path.to_string(),
+                    schema_path,
+                    message: err.to_string(),
+                    waiver_reason: None,
+                };
+                violation.waiver_reason = waivers
+                    .iter()
+                    .find(|waiver| waiver.applies_to(&violation))
+                    .map(|waiver| waiver.reason.to_owned());
+                violations.push(violation);
+            }
+            Ok(violations)
+        })?;
+        let (waived, failures): (Vec<_>, Vec<_>) = violations
+            .into_iter()
+            .partition(|violation| violation.waiver_reason.is_some());
+
+        if !failures.is_empty() {
+            return Err(DidLogEntryValidatorError::ValidationError(
+                failures
+                    .iter()
+                    .map(|violation| format!("{} (at '{}')", violation.message, violation.instance_path))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ));
+        }
+        Ok(waived)
     }
 
     /// Validate `instance` against `schema` and return the first error if any.
     #[inline]
     pub fn validate_str(&self, instance: &str) -> Result<(), DidLogEntryValidatorError> {
-        match json_from_str(instance) {
-            Ok(val) => match self.validator.validate(&val) {
-                Ok(_) => Ok(()),
-                Err(err) => Err(DidLogEntryValidatorError::ValidationError(err.to_string())),
+        observe_verification(
+            "did_log_entry_schema",
+            |err: &DidLogEntryValidatorError| format!("{:?}", err.kind()),
+            || match from_str_bounded(instance) {
+                Ok(val) => self.within_budget(|| match self.compiled()?.validate(&val) {
+                    Ok(_) => Ok(()),
+                    Err(err) => Err(to_validator_error(&err)),
+                }),
+                Err(err) => Err(DidLogEntryValidatorError::DeserializationError(
+                    err.detail().into_owned(),
+                )),
+            },
+        )
+    }
+
+    /// Delivers the limits enforced on each validation.
+    pub(crate) const fn limits(&self) -> ValidationLimits {
+        self.limits
+    }
+
+    /// Delivers the compiled schema, or [`DidLogEntryValidatorError::InternalError`] in case of a malformed one.
+    fn compiled(&self) -> Result<&ValidatorBase, DidLogEntryValidatorError> {
+        self.validator.as_deref().map_err(|err| {
+            DidLogEntryValidatorError::InternalError(format!("malformed schema: {err}"))
+        })
+    }
+
+    /// Runs the supplied `validation` within the budget denoted by the limits of this validator, so that
+    /// [`DidLogEntryValidatorError::BudgetExceeded`] is returned in case it has been exceeded meanwhile.
+    fn within_budget<T>(
+        &self,
+        validation: impl FnOnce() -> Result<T, DidLogEntryValidatorError>,
+    ) -> Result<T, DidLogEntryValidatorError> {
+        let scope = ValidationBudgetScope::enter(self.limits);
+        let result = validation();
+        if scope.is_exceeded() {
+            return Err(DidLogEntryValidatorError::BudgetExceeded(format!(
+                "more than {} ms or {} steps",
+                self.limits.max_duration_millis, self.limits.max_steps
+            )));
+        }
+        result
+    }
+
+    /// Compiles the supplied `schema`, thus panicking in case of a malformed one.
+    #[expect(clippy::panic, reason = "..")]
+    fn build(schema: &str, limits: ValidationLimits) -> Self {
+        match json_from_str(schema) {
+            Ok(sch) => {
+                let _x = jsch_meta::validate(&sch).is_err_and(|err| panic!("{err}"));
+                match jsch_opts()
+                    .with_draft(Draft::Draft202012)
+                    .with_pattern_options(PatternOptions::fancy_regex().backtrack_limit(
+                        usize::try_from(limits.max_regex_backtracks).unwrap_or(usize::MAX),
+                    ))
+                    .with_keyword(
+                        DidLogEntryKeyword::KEYWORD_NAME,
+                        DidLogEntryKeyword::factory,
+                    )
+                    .with_keyword(
+                        DidVersionTimeKeyword::KEYWORD_NAME,
+                        DidVersionTimeKeyword::factory,
+                    )
+                    .with_keyword(
+                        DidVersionIdKeyword::KEYWORD_NAME,
+                        DidVersionIdKeyword::factory,
+                    )
+                    .build(&sch)
+                {
+                    Ok(validator) => Self {
+                        validator: Ok(Arc::new(validator)),
+                        limits,
+                    },
+                    Err(err) => panic!("{err}"),
+                }
+            }
+            Err(err) => panic!("{err}"),
+        }
+    }
+}
+
+/// Combines the supplied `schemas` (keyed by `method` DID parameter) into a single schema (see [`DidLogEntryValidator::try_from_method_schemas`]).
+///
+/// As each schema is embedded as a distinct schema resource, any `$ref` (e.g. `#/$defs/..`) is still resolved against the schema itself.
+#[expect(clippy::single_call_fn, reason = "..")]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+fn combine_method_schemas(
+    schemas: &HashMap<String, Arc<dyn DidLogEntryJsonSchema>>,
+    fallback: Option<&dyn DidLogEntryJsonSchema>,
+) -> Result<Value, DidLogEntryValidatorError> {
+    let mut methods: Vec<_> = schemas.iter().collect();
+    methods.sort_by_key(|&(method, _)| method);
+
+    let mut combined = match fallback {
+        Some(schema) => embed_schema(schema, "fallback")?,
+        None => Value::Bool(false),
+    };
+    for (method, schema) in methods.into_iter().rev() {
+        let parameters = json!({
+            "type": "object",
+            "properties": {"method": {"const": method}},
+            "required": ["method"],
+        });
+        combined = json!({
+            "if": {
+                "anyOf": [
+                    {"type": "object", "properties": {"parameters": parameters}, "required": ["parameters"]},
+                    {"type": "array", "prefixItems": [true, true, parameters], "minItems": 3},
+                ],
             },
-            Err(err) => Err(DidLogEntryValidatorError::DeserializationError(
-                err.to_string(),
-            )),
+            "then": embed_schema(schema.as_ref(), method)?,
+            "else": combined,
+        });
+    }
+    Ok(combined)
+}
+
+/// Parses the supplied `schema` while ensuring it is a distinct schema resource (i.e. featuring `$id`).
+fn embed_schema(
+    schema: &dyn DidLogEntryJsonSchema,
+    name: &str,
+) -> Result<Value, DidLogEntryValidatorError> {
+    let mut embedded: Value = json_from_str(&schema.get_json_schema()).map_err(|err| {
+        DidLogEntryValidatorError::InternalError(format!("malformed schema '{name}': {err}"))
+    })?;
+    if let Some(members) = embedded.as_object_mut() {
+        if !members.contains_key("$id") {
+            let id = format!("{EMBEDDED_SCHEMA_ID_BASE}{}", hex::encode(name));
+            members.insert("$id".to_owned(), Value::String(id));
         }
     }
+    Ok(embedded)
 }
 
 impl From<Arc<dyn DidLogEntryJsonSchema>> for DidLogEntryValidator {
@@ -99,13 +628,20 @@ impl From<Arc<dyn DidLogEntryJsonSchema>> for DidLogEntryValidator {
     /// and supplied thread-safe [`DidLogEntryJsonSchema`] trait implementation.
     ///
     /// Relies heavily on custom [`jsonschema::Keyword`] trait implementation like:
-    /// - [`DidLogEntryKeyword`] and
-    /// - [`DidVersionTimeKeyword`].
+    /// - [`DidLogEntryKeyword`],
+    /// - [`DidVersionTimeKeyword`] and
+    /// - [`DidVersionIdKeyword`].
+    ///
+    /// Never panics, as a malformed `schema` is denoted by [`DidLogEntryValidatorError::InternalError`]
+    /// on each validation instead (see [`DidLogEntryValidator::try_from_schema`] for an eager alternative).
     ///
     /// A UniFFI-compliant constructor.
     #[inline]
     fn from(schema: Arc<dyn DidLogEntryJsonSchema>) -> Self {
-        Self::from(schema.get_json_schema().as_str())
+        Self::try_from_schema(schema).unwrap_or_else(|err| Self {
+            validator: Err(err.to_string()),
+            limits: ValidationLimits::DEFAULT,
+        })
     }
 }
 
@@ -114,8 +650,9 @@ impl From<&dyn DidLogEntryJsonSchema> for DidLogEntryValidator {
     /// and supplied [`DidLogEntryJsonSchema`] trait implementation.
     ///
     /// Relies heavily on custom [`jsonschema::Keyword`] trait implementation like:
-    /// - [`DidLogEntryKeyword`] and
-    /// - [`DidVersionTimeKeyword`].
+    /// - [`DidLogEntryKeyword`],
+    /// - [`DidVersionTimeKeyword`] and
+    /// - [`DidVersionIdKeyword`].
     #[inline]
     fn from(schema: &dyn DidLogEntryJsonSchema) -> Self {
         Self::from(schema.get_json_schema().as_str())
@@ -127,8 +664,9 @@ impl From<String> for DidLogEntryValidator {
     /// and supplied [`String`].
     ///
     /// Relies heavily on custom [`jsonschema::Keyword`] trait implementation like:
-    /// - [`DidLogEntryKeyword`] and
-    /// - [`DidVersionTimeKeyword`].
+    /// - [`DidLogEntryKeyword`],
+    /// - [`DidVersionTimeKeyword`] and
+    /// - [`DidVersionIdKeyword`].
     ///
     /// A UniFFI-compliant constructor.
     #[inline]
@@ -137,48 +675,41 @@ impl From<String> for DidLogEntryValidator {
     }
 }
 
-#[expect(clippy::fallible_impl_from, reason = "..")]
-#[expect(clippy::panic, reason = "..")]
 impl From<&str> for DidLogEntryValidator {
     /// Create a new JSON Schema validator using `JSON Schema Draft 2020-12` specifications
     /// and a schema supplied as `&str`.
     ///
     /// Relies heavily on custom [`jsonschema::Keyword`] trait implementation like:
-    /// - [`DidLogEntryKeyword`] and
-    /// - [`DidVersionTimeKeyword`].
+    /// - [`DidLogEntryKeyword`],
+    /// - [`DidVersionTimeKeyword`] and
+    /// - [`DidVersionIdKeyword`].
     #[inline]
     fn from(value: &str) -> Self {
-        match json_from_str(value) {
-            Ok(sch) => {
-                let _x = jsch_meta::validate(&sch).is_err_and(|err| panic!("{err}"));
-                match jsch_opts()
-                    .with_draft(Draft::Draft202012)
-                    .with_keyword(
-                        DidLogEntryKeyword::KEYWORD_NAME,
-                        DidLogEntryKeyword::factory,
-                    )
-                    .with_keyword(
-                        DidVersionTimeKeyword::KEYWORD_NAME,
-                        DidVersionTimeKeyword::factory,
-                    )
-                    .build(&sch)
-                {
-                    Ok(validator) => Self { validator },
-                    Err(err) => panic!("{err}"),
-                }
-            }
-            Err(err) => panic!("{err}"),
-        }
+        Self::build(value, ValidationLimits::DEFAULT)
     }
 }
 
 #[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+#[expect(clippy::shadow_reuse, reason = "..")]
+#[expect(clippy::shadow_unrelated, reason = "..")]
 mod test {
+    use crate::did_jsonschema::{
+        pointer_matches, DidLogEntryValidatorErrorKind, SchemaViolation, SchemaWaiver,
+        ValidationLimits,
+    };
     use crate::{DidLogEntryJsonSchema, DidLogEntryValidator};
     use rstest::rstest;
+    use serde_json::json;
+    use std::collections::HashMap;
     use std::sync::Arc;
 
     struct EmptyDidLogEntryJsonSchemaImpl;
+    #[expect(clippy::missing_trait_methods, reason = "..")]
     impl DidLogEntryJsonSchema for EmptyDidLogEntryJsonSchemaImpl {
         fn get_json_schema(&self) -> String {
             "".to_owned()
@@ -186,10 +717,14 @@ mod test {
     }
 
     #[rstest]
-    #[should_panic(expected = "EOF while parsing a value at line 1 column 0")]
     fn test_create_validator_from_empty_schema_thread_safe() {
         let schema: Arc<dyn DidLogEntryJsonSchema> = Arc::new(EmptyDidLogEntryJsonSchemaImpl {});
-        let _x = DidLogEntryValidator::from(schema);
+        let validator = DidLogEntryValidator::from(schema);
+        let err = validator.validate("{}".to_owned()).unwrap_err();
+        assert_eq!(err.kind(), DidLogEntryValidatorErrorKind::InternalError);
+        assert!(err
+            .to_string()
+            .contains("EOF while parsing a value at line 1 column 0"));
     }
 
     #[rstest]
@@ -198,4 +733,264 @@ mod test {
         let schema: &dyn DidLogEntryJsonSchema = &EmptyDidLogEntryJsonSchemaImpl {};
         let _x = DidLogEntryValidator::from(schema);
     }
+
+    #[rstest]
+    fn test_try_create_validator_from_empty_schema() {
+        let schema: Arc<dyn DidLogEntryJsonSchema> = Arc::new(EmptyDidLogEntryJsonSchemaImpl {});
+        let err = DidLogEntryValidator::try_from_schema(schema).unwrap_err();
+        assert_eq!(err.kind(), DidLogEntryValidatorErrorKind::InternalError);
+        assert!(err
+            .to_string()
+            .contains("EOF while parsing a value at line 1 column 0"));
+    }
+
+    #[rstest]
+    fn test_from_cached_empty_schema() {
+        let schema: Arc<dyn DidLogEntryJsonSchema> = Arc::new(EmptyDidLogEntryJsonSchemaImpl {});
+        let err = DidLogEntryValidator::from_cached(schema).unwrap_err();
+        assert_eq!(err.kind(), DidLogEntryValidatorErrorKind::InternalError);
+    }
+
+    struct JsonSchemaImpl(serde_json::Value);
+    #[expect(clippy::missing_trait_methods, reason = "..")]
+    impl DidLogEntryJsonSchema for JsonSchemaImpl {
+        fn get_json_schema(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    #[rstest]
+    // did:webvh:1.0-alike object entries
+    #[case(json!({
+        "$defs": {
+            "parameters": {"type": "object", "properties": {"method": {"const": "did:webvh:1.0"}}, "required": ["method"]},
+            "proof": {"type": "object", "properties": {"type": {"enum": ["DataIntegrityProof"]}}, "required": ["type"]},
+        },
+        "type": "object",
+        "properties": {
+            "versionId": {"type": "string", "did-version-id": true},
+            "versionTime": {"type": "string", "did-version-time": true},
+            "parameters": {"$ref": "#/$defs/parameters"},
+            "state": {"type": "object", "properties": {"id": {"type": "string", "format": "uri"}}, "required": ["id"]},
+            "proof": {"type": "array", "items": {"$ref": "#/$defs/proof"}, "minItems": 1},
+        },
+        "required": ["versionId", "versionTime", "parameters", "state", "proof"],
+    }))]
+    // did:tdw:0.3-alike array entries
+    #[case(json!({"type": "array", "did-log-entry": true}))]
+    #[case(json!({
+        "type": "array",
+        "did-log-entry": true,
+        "prefixItems": [
+            {"type": "string", "did-version-id": true},
+            {"type": "string", "did-version-time": true},
+            {"allOf": [{"type": "object", "required": ["method"]}, {"properties": {"method": {"const": "did:tdw:0.3"}}}]},
+            {"anyOf": [{"required": ["value"]}, {"required": ["patch"]}], "type": "object"},
+            {"type": "array", "items": {"type": "object", "required": ["proofValue"]}, "minItems": 1},
+        ],
+        "minItems": 5,
+    }))]
+    fn test_example_entry(#[case] schema: serde_json::Value) {
+        let schema = JsonSchemaImpl(schema);
+        let entry = schema.example_entry().unwrap();
+        let dyn_schema: &dyn DidLogEntryJsonSchema = &schema;
+        DidLogEntryValidator::from(dyn_schema)
+            .validate(entry)
+            .unwrap();
+    }
+
+    #[rstest]
+    fn test_example_entry_invalid() {
+        let unsatisfiable = JsonSchemaImpl(json!({"type": "string", "pattern": "^did:"}));
+        let err = unsatisfiable.example_entry().unwrap_err();
+        assert_eq!(err.kind(), DidLogEntryValidatorErrorKind::ValidationError);
+
+        let err = JsonSchemaImpl(json!(false)).example_entry().unwrap_err();
+        assert_eq!(err.kind(), DidLogEntryValidatorErrorKind::ValidationError);
+        assert!(err.to_string().contains("unsatisfiable"));
+
+        let err = EmptyDidLogEntryJsonSchemaImpl.example_entry().unwrap_err();
+        assert_eq!(err.kind(), DidLogEntryValidatorErrorKind::InternalError);
+    }
+
+    #[rstest]
+    fn test_try_from_method_schemas() {
+        let schema = |version: &str| -> Arc<dyn DidLogEntryJsonSchema> {
+            Arc::new(JsonSchemaImpl(json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "$defs": {"version": {"const": version}},
+                "properties": {"version": {"$ref": "#/$defs/version"}},
+                "required": ["version"],
+            })))
+        };
+        let schemas = HashMap::from([
+            ("did:tdw:0.3".to_owned(), schema("0.3")),
+            ("did:webvh:1.0".to_owned(), schema("1.0")),
+        ]);
+        let entry = |method: &str, version: &str| {
+            json!({"parameters": {"method": method}, "version": version}).to_string()
+        };
+
+        let validator =
+            DidLogEntryValidator::try_from_method_schemas(schemas.clone(), None).unwrap();
+        validator.validate(entry("did:webvh:1.0", "1.0")).unwrap();
+        validator.validate(entry("did:tdw:0.3", "0.3")).unwrap();
+        validator
+            .validate(entry("did:webvh:1.0", "0.3"))
+            .unwrap_err();
+        validator.validate(entry("did:tdw:0.3", "1.0")).unwrap_err();
+        validator.validate(entry("did:unknown", "1.0")).unwrap_err();
+        // did:tdw entries are arrays, featuring the parameters at index 2
+        let array_entry = |method: &str| json!(["1-Qm", "2025", {"method": method}]).to_string();
+        validator.validate(array_entry("did:tdw:0.3")).unwrap();
+        validator.validate(array_entry("did:unknown")).unwrap_err();
+
+        // any entry lacking the method is validated against the fallback schema
+        let fallback = Arc::new(JsonSchemaImpl(json!({"required": ["version"]})));
+        let validator =
+            DidLogEntryValidator::try_from_method_schemas(schemas, Some(fallback)).unwrap();
+        validator
+            .validate(json!({"parameters": {}, "version": "x"}).to_string())
+            .unwrap();
+        validator
+            .validate(json!({"parameters": {}}).to_string())
+            .unwrap_err();
+
+        let malformed: Arc<dyn DidLogEntryJsonSchema> = Arc::new(EmptyDidLogEntryJsonSchemaImpl {});
+        let err = DidLogEntryValidator::try_from_method_schemas(
+            HashMap::from([("did:webvh:1.0".to_owned(), malformed)]),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), DidLogEntryValidatorErrorKind::InternalError);
+        assert!(err.to_string().contains("malformed schema 'did:webvh:1.0'"));
+    }
+
+    #[rstest]
+    #[case("/a", "/a", true)]
+    #[case("/a", "/b", false)]
+    #[case("/a", "/a/0", false)]
+    #[case("/*/id", "/3/id", true)]
+    #[case("/*/id", "/3/name", false)]
+    #[case("/3/**", "/3/witness/witnesses/0", true)]
+    #[case("/3/**", "/4/witness", false)]
+    #[case("", "", true)]
+    fn test_pointer_matches(#[case] pattern: &str, #[case] pointer: &str, #[case] expected: bool) {
+        assert_eq!(pointer_matches(pattern, pointer), expected);
+    }
+
+    #[rstest]
+    fn test_validate_with_waivers() {
+        let validator = DidLogEntryValidator::from(
+            json!({
+                "type": "object",
+                "properties": {
+                    "a": {"type": "string", "pattern": "^x"},
+                    "b": {"type": "array", "items": {"type": "integer"}},
+                },
+            })
+            .to_string(),
+        );
+        let instance = json!({"a": "y", "b": [1, "z"]}).to_string();
+        let waiver = |keyword: &str, pointer_pattern: &str| SchemaWaiver {
+            keyword: keyword.to_owned(),
+            pointer_pattern: pointer_pattern.to_owned(),
+            reason: format!("waived {keyword}"),
+        };
+
+        let waived = validator
+            .validate_with_waivers(
+                instance.to_owned(),
+                vec![waiver("pattern", "/a"), waiver("type", "/b/*")],
+            )
+            .unwrap();
+        assert_eq!(
+            waived
+                .iter()
+                .map(|violation| (violation.keyword.as_str(), violation.instance_path.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("pattern", "/a"), ("type", "/b/1")]
+        );
+        assert!(waived
+            .iter()
+            .all(|violation: &SchemaViolation| violation.waiver_reason.is_some()));
+
+        // the very same instance is valid w.r.t. no violation at all
+        assert!(validator
+            .validate_with_waivers(json!({"a": "x"}).to_string(), vec![])
+            .unwrap()
+            .is_empty());
+
+        let err = validator
+            .validate_with_waivers(instance, vec![waiver("pattern", "/a"), waiver("type", "/a")])
+            .unwrap_err();
+        assert_eq!(err.kind(), DidLogEntryValidatorErrorKind::ValidationError);
+        assert!(err.to_string().contains("(at '/b/1')"));
+        assert!(!err.to_string().contains("(at '/a')"));
+    }
+
+    #[rstest]
+    fn test_validation_limits() {
+        let limits = |max_steps: u64, max_regex_backtracks: u64| ValidationLimits {
+            max_steps,
+            max_regex_backtracks,
+            ..ValidationLimits::DEFAULT
+        };
+        let validator = |schema: serde_json::Value, limits: ValidationLimits| {
+            DidLogEntryValidator::try_from_schema_with_limits(
+                Arc::new(JsonSchemaImpl(schema)),
+                limits,
+            )
+            .unwrap()
+        };
+
+        // each reported violation consumes a step
+        let schema = json!({"type": "array", "items": {"type": "string"}});
+        let instance = json!([1, 2, 3, 4, 5, 6, 7, 8, 9, 10]).to_string();
+        let err = validator(schema.clone(), limits(5, 1_000_000))
+            .validate_with_waivers(instance.to_owned(), vec![])
+            .unwrap_err();
+        assert_eq!(err.kind(), DidLogEntryValidatorErrorKind::BudgetExceeded);
+        let err = validator(schema, ValidationLimits::default())
+            .validate_with_waivers(instance, vec![])
+            .unwrap_err();
+        assert_eq!(err.kind(), DidLogEntryValidatorErrorKind::ValidationError);
+
+        // each evaluation of a custom keyword consumes a step
+        let schema = json!({"type": "string", "did-version-id": true});
+        let instance = json!("1-QmQq6Kg4ZZ1p49znzxnWmes4LkkWgMWLrnrfPre8UD56bz").to_string();
+        let err = validator(schema.clone(), limits(0, 1_000_000))
+            .validate(instance.to_owned())
+            .unwrap_err();
+        assert_eq!(err.kind(), DidLogEntryValidatorErrorKind::BudgetExceeded);
+        assert!(err.to_string().contains("more than 5000 ms or 0 steps"));
+        validator(schema.clone(), limits(1, 1_000_000))
+            .validate(instance.to_owned())
+            .unwrap();
+
+        // the duration is checked on each step, as well as once the validation is over
+        let no_time = ValidationLimits {
+            max_duration_millis: 0,
+            ..ValidationLimits::DEFAULT
+        };
+        let err = validator(schema, no_time).validate(instance).unwrap_err();
+        assert_eq!(err.kind(), DidLogEntryValidatorErrorKind::BudgetExceeded);
+        assert!(err.to_string().contains("more than 0 ms or 100000 steps"));
+        let err = validator(json!({"type": "array"}), no_time)
+            .validate("[]".to_owned())
+            .unwrap_err();
+        assert_eq!(err.kind(), DidLogEntryValidatorErrorKind::BudgetExceeded);
+
+        // pathological regex backtracking
+        let schema = json!({"type": "string", "pattern": "^(a|aa)+\\1b$"});
+        let instance = json!("a".repeat(30)).to_string();
+        let err = validator(schema, limits(100, 1_000))
+            .validate(instance)
+            .unwrap_err();
+        assert_eq!(
+            err.kind(),
+            DidLogEntryValidatorErrorKind::BudgetExceeded,
+            "{err}"
+        );
+    }
 }
@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: MIT
+
+//! Export of the public keys of a DID document as JWK Set.
+//!
+//! As specified by https://www.rfc-editor.org/rfc/rfc7517#section-5, intended for interoperability with OIDC-style services
+//! capable of consuming JWKS endpoints only.
+//!
+//! Besides verification methods featuring `publicKeyJwk`, Ed25519 keys expressed as `publicKeyMultibase`
+//! (see https://www.w3.org/TR/controller-document/#Multikey) are converted into OKP keys (https://www.rfc-editor.org/rfc/rfc8037#section-2),
+//! whereas any other key is skipped.
+
+use crate::did_doc::{DidDoc, Jwk, VerificationMethod};
+use crate::errors::DidSidekicksError;
+use crate::json_emission::{emit_json, EmitOptions};
+use crate::multibase::{MulticodecKeyType, Multikey};
+use crate::panic_boundary::catch_panic;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use serde::Serialize;
+
+/// A JWK Set, as specified by https://www.rfc-editor.org/rfc/rfc7517#section-5.
+#[derive(Serialize, Debug, Clone)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct Jwks {
+    pub keys: Vec<Jwk>,
+}
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
+impl DidDoc {
+    /// Delivers all public keys of this DID document (across `verificationMethod` and all verification relationships)
+    /// as JWK Set, whereas the `kid` of each key is the (absolute) id of its verification method.
+    ///
+    /// Each verification method is exported only once, while keys that cannot be expressed as JWK are skipped.
+    #[inline]
+    pub fn jwks(&self) -> Jwks {
+        let mut ids: Vec<String> = Vec::new();
+        let mut keys: Vec<Jwk> = Vec::new();
+        self.verification_method
+            .iter()
+            .chain(self.authentication.iter())
+            .chain(self.capability_invocation.iter())
+            .chain(self.capability_delegation.iter())
+            .chain(self.assertion_method.iter())
+            .chain(self.key_agreement.iter())
+            .for_each(|method| {
+                let kid = self.absolute_verification_method_id(&method.id);
+                if ids.contains(&kid) {
+                    return;
+                }
+                if let Some(mut jwk) = to_jwk(method) {
+                    jwk.kid = Some(kid.to_owned());
+                    ids.push(kid);
+                    keys.push(jwk);
+                }
+            });
+        Jwks { keys }
+    }
+
+    /// The same as [`DidDoc::jwks`], but delivering the JWK Set as (compact) JSON text, e.g. `{"keys":[...]}`.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn to_jwks(&self) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || emit_json(&self.jwks(), EmitOptions::COMPACT),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Resolves a relative verification method id (e.g. `#key-1`) against the DID of this DID document.
+    fn absolute_verification_method_id(&self, id: &str) -> String {
+        if id.starts_with('#') {
+            return format!("{}{id}", self.id);
+        }
+        id.to_owned()
+    }
+}
+
+/// Expresses the public key of the supplied verification `method` as JWK (lacking `kid`), if possible.
+#[expect(clippy::single_call_fn, reason = "..")]
+fn to_jwk(method: &VerificationMethod) -> Option<Jwk> {
+    if let Some(jwk) = method.public_key_jwk.as_ref() {
+        return Some(jwk.clone());
+    }
+
+    let multikey = Multikey::from_multibase(method.public_key_multibase.as_deref()?)
+        .ok()
+        .filter(|multikey| multikey.key_type() == MulticodecKeyType::Ed25519Pub)?;
+    Some(Jwk {
+        alg: None,
+        kid: None,
+        kty: Some("OKP".to_owned()),
+        crv: Some("Ed25519".to_owned()),
+        x: Some(URL_SAFE_NO_PAD.encode(multikey.key())),
+        y: None,
+    })
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use rstest::rstest;
+    use serde_json::{json, Value};
+
+    const DID: &str = "did:example:issuer";
+
+    #[rstest]
+    fn test_to_jwks() {
+        let jwk_method = json!({"id": format!("{DID}#key-1"), "type": "JsonWebKey2020", "publicKeyJwk": {"kty": "OKP", "crv": "Ed25519", "x": "rPmqPT3Y4RbGRhqkTE_rOnnAHuy3gIG6i1GT8tVeVSI", "kid": "key-1"}});
+        let did_doc = DidDoc::from_json(
+            &json!({
+                "@context": ["https://www.w3.org/ns/did/v1"],
+                "id": DID,
+                "verificationMethod": [
+                    jwk_method,
+                    // Example taken from https://www.w3.org/TR/vc-di-eddsa/#example-private-and-public-keys-for-signature-1
+                    {"id": "#key-2", "type": "Multikey", "publicKeyMultibase": "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2"},
+                    // not an Ed25519 multikey, hence skipped
+                    {"id": "#key-3", "type": "Multikey", "publicKeyMultibase": "zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169"},
+                ],
+                "assertionMethod": [jwk_method],
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let jwks: Value = serde_json::from_str(&did_doc.to_jwks().unwrap()).unwrap();
+        assert_eq!(
+            jwks,
+            json!({"keys": [
+                {"kty": "OKP", "crv": "Ed25519", "x": "rPmqPT3Y4RbGRhqkTE_rOnnAHuy3gIG6i1GT8tVeVSI", "kid": format!("{DID}#key-1")},
+                {"kty": "OKP", "crv": "Ed25519", "x": "sA2Nk45_dz1RVlqtNqYj9TRPf10ZYPnPPo4SYg6igQ8", "kid": format!("{DID}#key-2")},
+            ]})
+        );
+    }
+}
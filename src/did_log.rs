@@ -0,0 +1,1019 @@
+// SPDX-License-Identifier: MIT
+
+//! A (fully) verified DID log (see [`DidLog`]).
+//!
+//! A DID log is verified the very same way as in case of [`verify_and_resolve`](crate::did_log_resolution::verify_and_resolve),
+//! thus featuring all of its DID log entries along with the state of the DID as of each of them (see [`DidLogEntry`]).
+//!
+//! As the state of the DID is captured by each (verified) DID log entry, no DID log entry must ever be decoded by hand,
+//! e.g. a DID log entry may be explained in a human-readable way (see [`DidLogEntry::explain`]) and the DID may be resolved
+//! as of any point in time (see [`DidLog::resolve_at`]), whereas the validity of any key may be tracked throughout
+//! the whole DID log (see [`DidLog::key_validity`]). Besides, the statistics of a DID log (including simple anomaly flags)
+//! are available as well (see [`DidLog::stats`]).
+
+use crate::did_doc::{parse_did_doc, DidDoc, DidDocExtended};
+use crate::did_log_resolution::{replay, ResolutionOptions, ResolutionResult};
+use crate::did_method_parameters::DidMethodParameter;
+use crate::errors::DidSidekicksError;
+use crate::json_emission::{emit_json, EmitOptions};
+use crate::panic_boundary::catch_panic;
+use chrono::{DateTime, FixedOffset, SecondsFormat};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The name of the DID method parameter denoting the deactivation of the DID.
+pub(crate) const PARAM_DEACTIVATED: &str = "deactivated";
+/// The number of DID log entries (at least) created within [`BURST_WINDOW_SECONDS`] considered a burst of updates.
+const BURST_MIN_ENTRIES: usize = 3;
+/// See [`BURST_MIN_ENTRIES`].
+const BURST_WINDOW_SECONDS: u64 = 60;
+/// The number of DID log entries (at least) as of which a signer concentration is detected at all.
+const SIGNER_CONCENTRATION_MIN_ENTRIES: u64 = 10;
+/// The share (in percent) of DID log entries signed by a single signer considered a signer concentration.
+const SIGNER_CONCENTRATION_PERCENT: u64 = 90;
+/// The number of intervals between DID log entries (at least) as of which unusual gaps are detected at all.
+const UNUSUAL_GAP_MIN_INTERVALS: usize = 3;
+/// The factor (w.r.t. the median interval) an interval between DID log entries must exceed to be considered an unusual gap.
+const UNUSUAL_GAP_FACTOR: u64 = 10;
+
+/// The way a DID log entry updates the DID document.
+///
+/// A UniFFI-compliant enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum DidDocUpdate {
+    /// The DID log entry features the whole DID document.
+    Replaced,
+    /// The DID log entry features a JSON patch (see https://www.rfc-editor.org/rfc/rfc6902) to apply to the previous DID document.
+    Patched,
+}
+
+/// A human-readable, yet structured explanation of a single (verified) DID log entry (see [`DidLogEntry::explain`]).
+///
+/// I.e. who signed it, what changed and which DID method parameters are in force as of it.
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct DidLogEntryExplanation {
+    /// The `versionId` of the DID log entry.
+    pub version_id: String,
+    /// The `versionTime` of the DID log entry.
+    pub version_time: String,
+    /// The `verificationMethod` (i.e. the authorized key) of the proof of the DID log entry.
+    pub signer: String,
+    /// The way the DID log entry updates the DID document.
+    pub did_doc_update: DidDocUpdate,
+    /// The names of the DID method parameters featured (i.e. changed) by the DID log entry, in alphabetical order.
+    pub changed_parameters: Vec<String>,
+    /// All the DID method parameters in force as of the DID log entry (i.e. merged).
+    pub parameters: HashMap<String, Arc<DidMethodParameter>>,
+    /// Whether the DID is deactivated by the DID log entry.
+    pub deactivated: bool,
+    /// A (multi-line) rendering of all of the above, intended for humans (e.g. support engineers) rather than parsers.
+    pub summary: String,
+}
+
+/// The kind of an anomaly detected in a DID log (see [`DidLogStats::anomalies`]).
+///
+/// A UniFFI-compliant enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum DidLogAnomalyKind {
+    /// Several DID log entries created within a short time window (see [`BURST_MIN_ENTRIES`] and [`BURST_WINDOW_SECONDS`]).
+    BurstUpdates,
+    /// (Almost) all DID log entries of a long(er) DID log signed by a single signer, i.e. keys are never rotated
+    /// (see [`SIGNER_CONCENTRATION_MIN_ENTRIES`] and [`SIGNER_CONCENTRATION_PERCENT`]).
+    SignerConcentration,
+    /// An interval between DID log entries way exceeding the median one (see [`UNUSUAL_GAP_FACTOR`]).
+    UnusualGap,
+}
+
+/// A single anomaly detected in a DID log (see [`DidLogStats::anomalies`]).
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct DidLogAnomaly {
+    pub kind: DidLogAnomalyKind,
+    /// The `versionId` of the DID log entry the anomaly is detected at.
+    pub version_id: String,
+    /// A human-readable description of the anomaly.
+    pub detail: String,
+}
+
+/// The statistics of a (verified) DID log (see [`DidLog::stats`]), e.g. for the sake of registry monitoring dashboards.
+///
+/// Intervals between DID log entries are measured in seconds, as denoted by their `versionTime`.
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct DidLogStats {
+    pub entry_count: u64,
+    /// The `versionTime` of the very first DID log entry.
+    pub first_version_time: String,
+    /// The `versionTime` of the latest DID log entry.
+    pub last_version_time: String,
+    /// The interval between the very first and the latest DID log entry.
+    pub span_seconds: u64,
+    /// The mean interval between consecutive DID log entries, if there are any (i.e. the update frequency).
+    pub mean_update_interval_seconds: Option<u64>,
+    /// The shortest interval between consecutive DID log entries, if there are any.
+    pub min_update_interval_seconds: Option<u64>,
+    /// The longest interval between consecutive DID log entries, if there are any.
+    pub max_update_interval_seconds: Option<u64>,
+    /// The number of DID log entries signed by each signer (i.e. the `verificationMethod` of the proof).
+    pub signer_distribution: HashMap<String, u64>,
+    /// The number of DID log entries changing each DID method parameter (the very first DID log entry aside).
+    pub parameter_churn: HashMap<String, u64>,
+    /// The anomalies detected, grouped by their kind (in the order of the DID log entries they are detected at).
+    pub anomalies: Vec<DidLogAnomaly>,
+}
+
+impl DidLogStats {
+    /// Serializes these statistics into JSON, according to the supplied `options`.
+    #[inline]
+    pub fn to_json(&self, options: EmitOptions) -> Result<String, DidSidekicksError> {
+        emit_json(self, options)
+    }
+}
+
+/// An interval of (consecutive) DID log entries during which a verification method is present and authorized
+/// (see [`DidLog::key_validity`]).
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct KeyValidityInterval {
+    /// The `versionId` of the DID log entry as of which the verification method is valid.
+    pub from_version: String,
+    /// The `versionId` of the DID log entry as of which the verification method is no longer valid (i.e. exclusive), if any.
+    /// Otherwise, the verification method is still valid as of the latest DID log entry.
+    pub to_version: Option<String>,
+}
+
+/// A single (verified) DID log entry of a [`DidLog`], along with the state of the DID as of it.
+///
+/// A UniFFI-compliant object.
+#[derive(Debug, Clone)]
+pub struct DidLogEntry {
+    version_id: String,
+    version_time: DateTime<FixedOffset>,
+    /// The `verificationMethod` (i.e. the authorized key) of the proof.
+    signer: String,
+    /// The DID document as of this DID log entry (i.e. patched, if need be).
+    did_doc: Value,
+    did_doc_update: DidDocUpdate,
+    /// The names of the DID method parameters featured by this DID log entry, in alphabetical order.
+    changed_parameters: Vec<String>,
+    /// All the DID method parameters in force as of this DID log entry.
+    parameters: HashMap<String, Arc<DidMethodParameter>>,
+}
+
+impl DidLogEntry {
+    /// The only constructor of the type, intended for the verification pipeline only (see [`replay`]).
+    #[cfg_attr(not(test), expect(clippy::single_call_fn, reason = ".."))]
+    pub(crate) fn new(
+        version_id: String,
+        version_time: DateTime<FixedOffset>,
+        signer: String,
+        did_doc: Value,
+        did_doc_update: DidDocUpdate,
+        mut changed_parameters: Vec<String>,
+        parameters: HashMap<String, Arc<DidMethodParameter>>,
+    ) -> Self {
+        changed_parameters.sort();
+        Self {
+            version_id,
+            version_time,
+            signer,
+            did_doc,
+            did_doc_update,
+            changed_parameters,
+            parameters,
+        }
+    }
+
+    /// A UniFFI-compliant getter.
+    #[inline]
+    pub fn get_version_id(&self) -> String {
+        self.version_id.clone()
+    }
+
+    /// A UniFFI-compliant getter, delivering the `versionTime` as RFC 3339 string (e.g. `2012-12-12T12:12:12Z`).
+    #[inline]
+    pub fn get_version_time(&self) -> String {
+        self.version_time
+            .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+    }
+
+    /// Delivers the DID document as of this DID log entry (i.e. patched, if need be).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn get_did_doc(&self) -> Result<Arc<DidDoc>, DidSidekicksError> {
+        catch_panic(
+            || parse_did_doc(&self.did_doc.to_string()).map(Arc::new),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// A UniFFI-compliant getter, delivering the `verificationMethod` (i.e. the authorized key) of the proof.
+    #[inline]
+    pub fn get_signer(&self) -> String {
+        self.signer.clone()
+    }
+
+    /// Whether the DID is deactivated as of this DID log entry (i.e. the `deactivated` DID method parameter is set).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn is_deactivated(&self) -> bool {
+        self.parameters
+            .get(PARAM_DEACTIVATED)
+            .and_then(|param| param.get_bool_value())
+            .unwrap_or_default()
+    }
+
+    /// Delivers the DID document as of this DID log entry (i.e. patched, if need be) as it is, i.e. not parsed.
+    #[inline]
+    pub(crate) const fn get_did_doc_value(&self) -> &Value {
+        &self.did_doc
+    }
+
+    /// Delivers all the DID method parameters in force as of this DID log entry.
+    #[inline]
+    pub(crate) const fn get_parameters(&self) -> &HashMap<String, Arc<DidMethodParameter>> {
+        &self.parameters
+    }
+
+    /// Explains this DID log entry in a human-readable, yet structured way (see [`DidLogEntryExplanation`]),
+    /// e.g. for the sake of CLI/diagnostic tooling.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn explain(&self) -> DidLogEntryExplanation {
+        let deactivated = self.is_deactivated();
+        let version_time = self.get_version_time();
+
+        // parameters are listed in alphabetical order, so the summary is deterministic
+        let mut in_force = self
+            .parameters
+            .iter()
+            .map(|(name, param)| format!("{name}={}", param.get_json_text()))
+            .collect::<Vec<_>>();
+        in_force.sort();
+        let changed = if self.changed_parameters.is_empty() {
+            "none".to_owned()
+        } else {
+            self.changed_parameters.join(", ")
+        };
+        let update = match self.did_doc_update {
+            DidDocUpdate::Replaced => "replaced",
+            DidDocUpdate::Patched => "patched",
+        };
+        let mut summary = format!(
+            "versionId {} ({version_time})\n  signed by: {}\n  DID document: {update}\n  parameters changed: {changed}\n  parameters in force: {}",
+            self.version_id,
+            self.signer,
+            in_force.join(", ")
+        );
+        if deactivated {
+            summary.push_str("\n  the DID is deactivated");
+        }
+
+        DidLogEntryExplanation {
+            version_id: self.version_id.clone(),
+            version_time,
+            signer: self.signer.clone(),
+            did_doc_update: self.did_doc_update,
+            changed_parameters: self.changed_parameters.clone(),
+            parameters: self.parameters.clone(),
+            deactivated,
+            summary,
+        }
+    }
+}
+
+/// A (fully) verified DID log of a DID (see [module documentation](self)).
+///
+/// A UniFFI-compliant object.
+#[derive(Debug, Clone)]
+pub struct DidLog {
+    did: String,
+    /// All the (verified) DID log entries, in the order of their appearance.
+    entries: Vec<Arc<DidLogEntry>>,
+}
+
+impl DidLog {
+    /// Verifies the supplied DID log (`log_jsonl`, in JSON Lines format) of the supplied `did` according to the supplied `options`,
+    /// the very same way as [`verify_and_resolve`](crate::did_log_resolution::verify_and_resolve) does it,
+    /// whereas any failure is denoted the very same way.
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub fn verify(
+        did: &str,
+        log_jsonl: &str,
+        options: ResolutionOptions,
+    ) -> Result<Self, DidSidekicksError> {
+        catch_panic(
+            || {
+                let (state, entries) = replay(log_jsonl, &options)?;
+                // the DID (and SCID) must be featured by the latest DID document
+                state.into_result(did)?;
+                Ok(Self {
+                    did: did.to_owned(),
+                    entries: entries.into_iter().map(Arc::new).collect(),
+                })
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// A UniFFI-compliant getter.
+    #[inline]
+    pub fn get_did(&self) -> String {
+        self.did.clone()
+    }
+
+    /// Resolves the DID as of the supplied `version_time` (RFC 3339), i.e. as of the latest DID log entry not succeeding it,
+    /// e.g. to verify a credential issued in the past against the DID document (and keys) active at issuance time.
+    ///
+    /// If the `version_time` precedes the very first DID log entry, [`DidSidekicksError::InvalidDidDocument`] is returned.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn resolve_at(&self, version_time: &str) -> Result<ResolutionResult, DidSidekicksError> {
+        catch_panic(
+            || {
+                let time = DateTime::parse_from_rfc3339(version_time).map_err(|err| {
+                    DidSidekicksError::DeserializationFailed(format!("invalid versionTime: {err}"))
+                })?;
+                // as the versionTime of DID log entries is non-descending, the latest matching one is the very last one
+                let index = self
+                    .entries
+                    .iter()
+                    .rposition(|entry| entry.version_time <= time)
+                    .ok_or_else(|| {
+                        DidSidekicksError::InvalidDidDocument(format!(
+                            "the versionTime '{version_time}' precedes the very first DID log entry"
+                        ))
+                    })?;
+                self.resolve_entry(index)
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Resolves the DID as of the DID log entry featuring the supplied `version_id`.
+    ///
+    /// If there is no such DID log entry, [`DidSidekicksError::InvalidDidDocument`] is returned.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn resolve_version(&self, version_id: &str) -> Result<ResolutionResult, DidSidekicksError> {
+        catch_panic(
+            || {
+                let index = self
+                    .entries
+                    .iter()
+                    .position(|entry| entry.version_id == version_id)
+                    .ok_or_else(|| {
+                        DidSidekicksError::InvalidDidDocument(format!(
+                            "no DID log entry featuring the versionId '{version_id}'"
+                        ))
+                    })?;
+                self.resolve_entry(index)
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Resolves the DID as of the DID log entry at the supplied `index`.
+    fn resolve_entry(&self, index: usize) -> Result<ResolutionResult, DidSidekicksError> {
+        let entry = self.entries.get(index).ok_or_else(|| {
+            DidSidekicksError::InternalError(format!("no DID log entry at {index}"))
+        })?;
+        Ok(ResolutionResult {
+            did_doc: Arc::new(DidDocExtended::new(
+                parse_did_doc(&entry.did_doc.to_string())?,
+                entry.parameters.clone(),
+            )),
+            version_id: entry.get_version_id(),
+            version_time: entry.get_version_time(),
+            deactivated: entry.is_deactivated(),
+            entry_count: u64::try_from(index.saturating_add(1)).unwrap_or(u64::MAX),
+        })
+    }
+
+    /// Delivers the intervals of DID log entries during which the verification method denoted by the supplied `key_id`
+    /// (either the absolute `id` or a relative one, e.g. `#key-1`) is present and authorized, in chronological order.
+    /// A relative `id` is resolved against the DID of this log, so a verification method of any other DID never matches.
+    ///
+    /// A verification method is considered authorized as long as it is referenced by any verification relationship
+    /// (e.g. `assertionMethod`) and the DID is not deactivated. The result is empty, if the verification method has never been valid.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn key_validity(
+        &self,
+        key_id: &str,
+    ) -> Result<Vec<KeyValidityInterval>, DidSidekicksError> {
+        catch_panic(
+            || {
+                let mut intervals: Vec<KeyValidityInterval> = Vec::new();
+                let mut is_valid = false;
+                for entry in &self.entries {
+                    let is_valid_as_of = !entry.is_deactivated()
+                        && is_authorized(
+                            &parse_did_doc(&entry.did_doc.to_string())?,
+                            &absolute_id(&self.did, key_id),
+                        );
+                    match (is_valid, is_valid_as_of) {
+                        (false, true) => intervals.push(KeyValidityInterval {
+                            from_version: entry.get_version_id(),
+                            to_version: None,
+                        }),
+                        (true, false) => {
+                            if let Some(interval) = intervals.last_mut() {
+                                interval.to_version = Some(entry.get_version_id());
+                            }
+                        }
+                        (true, true) | (false, false) => {}
+                    }
+                    is_valid = is_valid_as_of;
+                }
+                Ok(intervals)
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Delivers the statistics of this DID log, i.e. update frequency, signer distribution and parameter churn,
+    /// along with (simple) anomaly flags (see [`DidLogAnomalyKind`]).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn stats(&self) -> DidLogStats {
+        let intervals = self
+            .entries
+            .iter()
+            .zip(self.entries.iter().skip(1))
+            .map(|(previous, next)| seconds_between(previous, next))
+            .collect::<Vec<_>>();
+        let span_seconds = match (self.entries.first(), self.entries.last()) {
+            (Some(first), Some(last)) => seconds_between(first, last),
+            _ => 0,
+        };
+
+        let mut signer_distribution: HashMap<String, u64> = HashMap::new();
+        for entry in &self.entries {
+            count(&mut signer_distribution, &entry.signer);
+        }
+        let mut parameter_churn: HashMap<String, u64> = HashMap::new();
+        for name in self
+            .entries
+            .iter()
+            .skip(1)
+            .flat_map(|entry| entry.changed_parameters.iter())
+        {
+            count(&mut parameter_churn, name);
+        }
+
+        let entry_count = u64::try_from(self.entries.len()).unwrap_or(u64::MAX);
+        let mut anomalies = self.detect_bursts();
+        anomalies.extend(self.detect_signer_concentration(entry_count, &signer_distribution));
+        anomalies.extend(self.detect_unusual_gaps(&intervals));
+
+        DidLogStats {
+            entry_count,
+            first_version_time: self
+                .entries
+                .first()
+                .map(|entry| entry.get_version_time())
+                .unwrap_or_default(),
+            last_version_time: self
+                .entries
+                .last()
+                .map(|entry| entry.get_version_time())
+                .unwrap_or_default(),
+            span_seconds,
+            mean_update_interval_seconds: span_seconds
+                .checked_div(u64::try_from(intervals.len()).unwrap_or(u64::MAX)),
+            min_update_interval_seconds: intervals.iter().min().copied(),
+            max_update_interval_seconds: intervals.iter().max().copied(),
+            signer_distribution,
+            parameter_churn,
+            anomalies,
+        }
+    }
+
+    /// Serializes the statistics of this DID log (see [`DidLog::stats`]) into JSON, according to the supplied `options`.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn stats_to_json(&self, options: EmitOptions) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || self.stats().to_json(options),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Detects bursts of updates, i.e. [`BURST_MIN_ENTRIES`] DID log entries (at least) created within [`BURST_WINDOW_SECONDS`],
+    /// whereas a burst is reported once (at the DID log entry completing it).
+    fn detect_bursts(&self) -> Vec<DidLogAnomaly> {
+        let mut anomalies = Vec::new();
+        let mut is_bursting = false;
+        for (first, last) in self.entries.iter().zip(
+            self.entries
+                .iter()
+                .skip(BURST_MIN_ENTRIES.saturating_sub(1)),
+        ) {
+            let seconds = seconds_between(first, last);
+            let is_burst = seconds <= BURST_WINDOW_SECONDS;
+            if is_burst && !is_bursting {
+                anomalies.push(DidLogAnomaly {
+                    kind: DidLogAnomalyKind::BurstUpdates,
+                    version_id: last.get_version_id(),
+                    detail: format!(
+                        "{BURST_MIN_ENTRIES} DID log entries created within {seconds} seconds (as of '{}')",
+                        first.version_id
+                    ),
+                });
+            }
+            is_bursting = is_burst;
+        }
+        anomalies
+    }
+
+    /// Detects a signer concentration, i.e. [`SIGNER_CONCENTRATION_PERCENT`] of DID log entries (at least)
+    /// signed by a single signer, as long as the DID log features [`SIGNER_CONCENTRATION_MIN_ENTRIES`] (at least).
+    fn detect_signer_concentration(
+        &self,
+        entry_count: u64,
+        signer_distribution: &HashMap<String, u64>,
+    ) -> Option<DidLogAnomaly> {
+        if entry_count < SIGNER_CONCENTRATION_MIN_ENTRIES {
+            return None;
+        }
+        let (signer, signed) = signer_distribution
+            .iter()
+            .max_by(|left, right| left.1.cmp(right.1).then_with(|| right.0.cmp(left.0)))?;
+        (signed.saturating_mul(100) >= entry_count.saturating_mul(SIGNER_CONCENTRATION_PERCENT))
+            .then(|| DidLogAnomaly {
+                kind: DidLogAnomalyKind::SignerConcentration,
+                version_id: self
+                    .entries
+                    .last()
+                    .map(|entry| entry.get_version_id())
+                    .unwrap_or_default(),
+                detail: format!("'{signer}' signed {signed} of {entry_count} DID log entries"),
+            })
+    }
+
+    /// Detects unusual gaps, i.e. any of the supplied `intervals` (between consecutive DID log entries) exceeding
+    /// the median one by [`UNUSUAL_GAP_FACTOR`], as long as there are [`UNUSUAL_GAP_MIN_INTERVALS`] (at least).
+    fn detect_unusual_gaps(&self, intervals: &[u64]) -> Vec<DidLogAnomaly> {
+        if intervals.len() < UNUSUAL_GAP_MIN_INTERVALS {
+            return Vec::new();
+        }
+        let mut sorted = intervals.to_vec();
+        sorted.sort_unstable();
+        let median = sorted
+            .get(sorted.len().checked_div(2).unwrap_or_default())
+            .copied()
+            .unwrap_or_default();
+        // a median of zero (i.e. DID log entries created at once) would render any gap unusual
+        let threshold = median.max(1).saturating_mul(UNUSUAL_GAP_FACTOR);
+
+        intervals
+            .iter()
+            .zip(self.entries.iter().skip(1))
+            .filter(|&(&interval, _)| interval > threshold)
+            .map(|(interval, entry)| DidLogAnomaly {
+                kind: DidLogAnomalyKind::UnusualGap,
+                version_id: entry.get_version_id(),
+                detail: format!(
+                    "{interval} seconds since the previous DID log entry (the median being {median} seconds)"
+                ),
+            })
+            .collect()
+    }
+
+    /// Delivers all the (verified) DID log entries, in the order of their appearance.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn get_entries(&self) -> Vec<Arc<DidLogEntry>> {
+        self.entries.clone()
+    }
+}
+
+/// The (non-negative) interval in seconds between the supplied DID log entries.
+fn seconds_between(previous: &DidLogEntry, next: &DidLogEntry) -> u64 {
+    u64::try_from(
+        next.version_time
+            .signed_duration_since(previous.version_time)
+            .num_seconds(),
+    )
+    .unwrap_or_default()
+}
+
+/// Counts the supplied `key` in the supplied `counts`.
+fn count(counts: &mut HashMap<String, u64>, key: &str) {
+    counts
+        .entry(key.to_owned())
+        .and_modify(|counted| *counted = counted.saturating_add(1))
+        .or_insert(1);
+}
+
+/// Resolves the supplied (verification method) `id` against the supplied `did`, if relative (e.g. `#key-1`).
+fn absolute_id(did: &str, id: &str) -> String {
+    if id.starts_with('#') {
+        return format!("{did}{id}");
+    }
+    id.to_owned()
+}
+
+/// Whether the verification method denoted by the supplied (absolute) `key_id` is referenced
+/// by any verification relationship of the supplied DID document.
+#[expect(clippy::single_call_fn, reason = "..")]
+fn is_authorized(did_doc: &DidDoc, key_id: &str) -> bool {
+    did_doc
+        .authentication
+        .iter()
+        .chain(did_doc.assertion_method.iter())
+        .chain(did_doc.key_agreement.iter())
+        .chain(did_doc.capability_invocation.iter())
+        .chain(did_doc.capability_delegation.iter())
+        .any(|method| absolute_id(&did_doc.id, &method.id) == key_id)
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(
+    clippy::indexing_slicing,
+    reason = "panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+
+    const TDW_JS_DID_DEACTIVATED: &str =
+        "did:tdw:QmdSU7F2rF8r4m6GZK7Evi2tthfDDxhw3NppU8pJMbd2hB:example.com";
+    const DIDTOOLBOX_DID: &str = "did:tdw:QmPsui8ffosRTxUBP8vJoejauqEUGvhmWe77BNo1StgLk7:identifier-reg.trust-infra.swiyu-int.admin.ch:api:v1:did:18fa7c77-9dd1-4e20-a147-fb1bec146085";
+
+    #[rstest]
+    fn test_explain() {
+        let did_log = DidLog::verify(
+            TDW_JS_DID_DEACTIVATED,
+            include_str!("../test_data/generated_by_tdw_js/deactivated.jsonl"),
+            ResolutionOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(did_log.get_did(), TDW_JS_DID_DEACTIVATED);
+        assert!(did_log
+            .get_entries()
+            .iter()
+            .all(|entry| entry.get_did_doc().unwrap().id == TDW_JS_DID_DEACTIVATED));
+        let explanations = did_log
+            .get_entries()
+            .iter()
+            .map(|entry| entry.explain())
+            .collect::<Vec<_>>();
+
+        assert_eq!(explanations.len(), 3);
+        assert!(explanations
+            .iter()
+            .all(
+                |explanation| explanation.did_doc_update == DidDocUpdate::Replaced
+                    && explanation.signer.starts_with("did:key:z6Mk")
+                    && explanation.version_time == "2012-12-12T12:12:12Z"
+            ));
+        assert!(explanations[0]
+            .changed_parameters
+            .contains(&"scid".to_owned()));
+        assert!(!explanations[0].deactivated);
+        assert_eq!(
+            explanations[1].changed_parameters,
+            vec!["updateKeys", "witnessThreshold", "witnesses"]
+        );
+        assert!(explanations[1].parameters.contains_key("scid"));
+        assert_ne!(explanations[1].signer, explanations[0].signer);
+        assert_eq!(explanations[2].changed_parameters, vec!["deactivated"]);
+        assert!(explanations[2].deactivated);
+        assert!(explanations[2].summary.starts_with("versionId 3-"));
+        assert!(explanations[2]
+            .summary
+            .contains("parameters changed: deactivated"));
+        assert!(explanations[2].summary.ends_with("the DID is deactivated"));
+
+        let entries = DidLog::verify(
+            DIDTOOLBOX_DID,
+            include_str!("../test_data/generated_by_didtoolbox_java/v005_did.jsonl"),
+            ResolutionOptions::default(),
+        )
+        .unwrap()
+        .get_entries();
+        assert_eq!(entries.len(), 5);
+        let explanation = entries[4].explain();
+        assert!(explanation.changed_parameters.is_empty());
+        assert!(explanation.summary.contains("parameters changed: none"));
+    }
+
+    #[rstest]
+    #[case(
+        "2025-05-31T14:36:53Z",
+        "2-QmVA5UuLakpdb7yW32Ay1WW1PC1WPRtFNsn86vf1de9djE",
+        2
+    )]
+    #[case(
+        "2025-05-31T16:36:54.5+02:00",
+        "3-QmNbS1EncLwJt9BfqgFXQRtBeEkS7ZRRkZcsJLrRYPXZKb",
+        3
+    )]
+    #[case(
+        "2030-01-01T00:00:00Z",
+        "5-QmSw3UQaPyUqWM9VAv2dkEmY5J7HAAQM4rrj13bV1jW49Q",
+        5
+    )]
+    fn test_resolve_at(
+        #[case] version_time: &str,
+        #[case] version_id: &str,
+        #[case] entry_count: u64,
+    ) {
+        let did_log = DidLog::verify(
+            DIDTOOLBOX_DID,
+            include_str!("../test_data/generated_by_didtoolbox_java/v005_did.jsonl"),
+            ResolutionOptions::default(),
+        )
+        .unwrap();
+
+        let result = did_log.resolve_at(version_time).unwrap();
+        assert_eq!(result.version_id, version_id);
+        assert_eq!(result.entry_count, entry_count);
+        assert_eq!(result.did_doc.get_did_doc_obj().id, DIDTOOLBOX_DID);
+        assert!(!result.deactivated);
+
+        // the very same state must be resolved by versionId
+        let by_version = did_log.resolve_version(version_id).unwrap();
+        assert_eq!(by_version.version_time, result.version_time);
+        assert_eq!(by_version.entry_count, entry_count);
+        assert_eq!(
+            serde_json::to_string(&by_version.did_doc.get_did_doc_obj()).unwrap(),
+            serde_json::to_string(&result.did_doc.get_did_doc_obj()).unwrap()
+        );
+    }
+
+    #[rstest]
+    fn test_resolve_deactivated() {
+        let did_log = DidLog::verify(
+            TDW_JS_DID_DEACTIVATED,
+            include_str!("../test_data/generated_by_tdw_js/deactivated.jsonl"),
+            ResolutionOptions::default(),
+        )
+        .unwrap();
+        let entries = did_log.get_entries();
+
+        // the DID was active prior to its deactivation
+        assert!(
+            !did_log
+                .resolve_version(&entries[1].get_version_id())
+                .unwrap()
+                .deactivated
+        );
+        assert!(
+            did_log
+                .resolve_version(&entries[2].get_version_id())
+                .unwrap()
+                .deactivated
+        );
+    }
+
+    #[rstest]
+    #[case(
+        DIDTOOLBOX_DID,
+        include_str!("../test_data/generated_by_didtoolbox_java/v005_did.jsonl"),
+        "#assert-key-01",
+        vec![("1-QmdFXCA7RgH2NszV3WgnbemHqLxpXeE66FswLjpTC2hcvV", Some("2-QmVA5UuLakpdb7yW32Ay1WW1PC1WPRtFNsn86vf1de9djE"))]
+    )]
+    #[case(
+        DIDTOOLBOX_DID,
+        include_str!("../test_data/generated_by_didtoolbox_java/v005_did.jsonl"),
+        &format!("{DIDTOOLBOX_DID}#my-assert-key-01"),
+        vec![("2-QmVA5UuLakpdb7yW32Ay1WW1PC1WPRtFNsn86vf1de9djE", None)]
+    )]
+    #[case(
+        TDW_JS_DID_DEACTIVATED,
+        include_str!("../test_data/generated_by_tdw_js/deactivated.jsonl"),
+        "#KsXDA8UP",
+        vec![("2-QmRe4zMcdbxjHuR2MvPxryb2TrV8cjQ8UbyCrdCefmime4", Some("3-QmcAcULsrhUzaSLX5Cu2nhYXxbfGyGssjmhhGwdiMXwwr4"))]
+    )]
+    // neither a bare fragment nor a verification method of any other DID matches
+    #[case(
+        TDW_JS_DID_DEACTIVATED,
+        include_str!("../test_data/generated_by_tdw_js/deactivated.jsonl"),
+        "KsXDA8UP",
+        vec![]
+    )]
+    #[case(
+        DIDTOOLBOX_DID,
+        include_str!("../test_data/generated_by_didtoolbox_java/v005_did.jsonl"),
+        "did:other:example.com#assert-key-01",
+        vec![]
+    )]
+    #[case(
+        TDW_JS_DID_DEACTIVATED,
+        include_str!("../test_data/generated_by_tdw_js/deactivated.jsonl"),
+        "#unknown",
+        vec![]
+    )]
+    fn test_key_validity(
+        #[case] did: &str,
+        #[case] log_jsonl: &str,
+        #[case] key_id: &str,
+        #[case] expected: Vec<(&str, Option<&str>)>,
+    ) {
+        let did_log = DidLog::verify(did, log_jsonl, ResolutionOptions::default()).unwrap();
+
+        assert_eq!(
+            did_log.key_validity(key_id).unwrap(),
+            expected
+                .into_iter()
+                .map(|(from_version, to_version)| KeyValidityInterval {
+                    from_version: from_version.to_owned(),
+                    to_version: to_version.map(str::to_owned),
+                })
+                .collect::<Vec<_>>()
+        );
+    }
+
+    /// Delivers a (synthetic) DID log featuring a DID log entry per each of the supplied `version_times`,
+    /// whereas each DID log entry is signed by the respective one of the supplied `signers` and changes the `witness` parameter.
+    fn synthetic_log(version_times: &[&str], signers: &[&str]) -> DidLog {
+        DidLog {
+            did: "did:webvh:QmScid:example.com".to_owned(),
+            entries: version_times
+                .iter()
+                .zip(signers.iter())
+                .zip(1..)
+                .map(|((version_time, signer), number)| {
+                    Arc::new(DidLogEntry::new(
+                        format!("{number}-QmHash"),
+                        DateTime::parse_from_rfc3339(version_time).unwrap(),
+                        (*signer).to_owned(),
+                        Value::Null,
+                        DidDocUpdate::Replaced,
+                        vec!["witness".to_owned()],
+                        HashMap::new(),
+                    ))
+                })
+                .collect(),
+        }
+    }
+
+    #[rstest]
+    fn test_stats() {
+        let stats = DidLog::verify(
+            DIDTOOLBOX_DID,
+            include_str!("../test_data/generated_by_didtoolbox_java/v005_did.jsonl"),
+            ResolutionOptions::default(),
+        )
+        .unwrap()
+        .stats();
+
+        assert_eq!(stats.entry_count, 5);
+        assert_eq!(stats.first_version_time, "2025-05-31T14:36:53Z");
+        assert_eq!(stats.last_version_time, "2025-05-31T14:36:55Z");
+        assert_eq!(stats.span_seconds, 2);
+        assert_eq!(stats.mean_update_interval_seconds, Some(0));
+        assert_eq!(stats.min_update_interval_seconds, Some(0));
+        assert_eq!(stats.max_update_interval_seconds, Some(1));
+        assert_eq!(stats.signer_distribution.values().sum::<u64>(), 5);
+        assert!(stats.parameter_churn.is_empty());
+        // the very first 3 DID log entries are created within 1 second, whereas the burst is reported just once
+        assert_eq!(
+            stats.anomalies,
+            vec![DidLogAnomaly {
+                kind: DidLogAnomalyKind::BurstUpdates,
+                version_id: "3-QmNbS1EncLwJt9BfqgFXQRtBeEkS7ZRRkZcsJLrRYPXZKb".to_owned(),
+                detail: "3 DID log entries created within 1 seconds (as of '1-QmdFXCA7RgH2NszV3WgnbemHqLxpXeE66FswLjpTC2hcvV')".to_owned(),
+            }]
+        );
+
+        let json = stats.to_json(EmitOptions::CANONICAL).unwrap();
+        assert!(json.contains(r#""kind":"burstUpdates""#), "{json}");
+        assert!(json.contains(r#""spanSeconds":2"#), "{json}");
+    }
+
+    #[rstest]
+    fn test_stats_anomalies() {
+        // a DID log updated daily by a single signer, except for a month-long gap
+        let version_times = (1..=12)
+            .map(|day: u32| {
+                let month = if day > 10 { "02" } else { "01" };
+                format!("2025-{month}-{day:02}T12:00:00Z")
+            })
+            .collect::<Vec<_>>();
+        let did_log = synthetic_log(
+            &version_times.iter().map(String::as_str).collect::<Vec<_>>(),
+            &["did:key:z6MkA#z6MkA"; 12],
+        );
+        let stats = did_log.stats();
+
+        assert_eq!(stats.entry_count, 12);
+        assert_eq!(stats.signer_distribution["did:key:z6MkA#z6MkA"], 12);
+        assert_eq!(stats.parameter_churn["witness"], 11);
+        assert_eq!(stats.min_update_interval_seconds, Some(86_400));
+        assert_eq!(
+            stats
+                .anomalies
+                .iter()
+                .map(|anomaly| (anomaly.kind, anomaly.version_id.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (DidLogAnomalyKind::SignerConcentration, "12-QmHash"),
+                (DidLogAnomalyKind::UnusualGap, "11-QmHash"),
+            ]
+        );
+
+        // no anomalies, as long as signers are rotated and updates are regular
+        let signers = ["did:key:z6MkA#z6MkA", "did:key:z6MkB#z6MkB"].repeat(6);
+        let regular_log = synthetic_log(
+            &version_times
+                .iter()
+                .take(10)
+                .map(String::as_str)
+                .collect::<Vec<_>>(),
+            &signers,
+        );
+        assert!(regular_log.stats().anomalies.is_empty());
+    }
+
+    #[rstest]
+    fn test_resolve_invalid() {
+        let did_log = DidLog::verify(
+            DIDTOOLBOX_DID,
+            include_str!("../test_data/generated_by_didtoolbox_java/v005_did.jsonl"),
+            ResolutionOptions::default(),
+        )
+        .unwrap();
+
+        assert_error(
+            did_log.resolve_at("2025-05-31T14:36:52Z"),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            "precedes the very first DID log entry",
+        );
+        assert_error(
+            did_log.resolve_at("yesterday"),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "invalid versionTime",
+        );
+        assert_error(
+            did_log.resolve_version("6-QmSw3UQaPyUqWM9VAv2dkEmY5J7HAAQM4rrj13bV1jW49Q"),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            "no DID log entry featuring the versionId",
+        );
+    }
+
+    #[rstest]
+    #[case(
+        TDW_JS_DID_DEACTIVATED,
+        include_str!("../test_data/generated_by_tdw_js/already_deactivated.jsonl"),
+        "already deactivated"
+    )]
+    #[case(
+        DIDTOOLBOX_DID,
+        include_str!("../test_data/generated_by_tdw_js/deactivated.jsonl"),
+        "instead of"
+    )]
+    fn test_verify_invalid(
+        #[case] did: &str,
+        #[case] log_jsonl: &str,
+        #[case] error_contains: &str,
+    ) {
+        assert_error(
+            DidLog::verify(did, log_jsonl, ResolutionOptions::default()),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            error_contains,
+        );
+    }
+}
@@ -0,0 +1,246 @@
+// SPDX-License-Identifier: MIT
+
+//! Generation of a minimal (example) instance of a JSON schema describing a DID log entry
+//! (see [`crate::did_jsonschema::DidLogEntryJsonSchema::example_entry`]).
+//!
+//! Besides the common JSON schema keywords (`type`, `const`, `enum`, `required`, `prefixItems`, `minItems`, `$ref`, `allOf`, `anyOf`, ...),
+//! the custom keywords (see [`crate::custom_jsonschema_keywords`]) are honored, too. Any other constraint (e.g. `pattern`)
+//! is not taken into account, so the generated instance is not necessarily valid.
+
+use crate::custom_jsonschema_keywords::{
+    DidLogEntryKeyword, DidVersionIdKeyword, DidVersionTimeKeyword,
+};
+use crate::jcs_sha256_hasher::JcsSha256Hasher;
+use serde_json::{json, Map, Value};
+
+/// The maximum depth of (nested) subschemas, thus preventing an endless recursion in case of a recursive `$ref`.
+const MAX_DEPTH: usize = 32;
+
+/// The datetime featured by any generated `versionTime` (or `date-time` string), obviously in the past.
+const EXAMPLE_DATE_TIME: &str = "2012-12-12T12:12:12Z";
+
+/// Generates a minimal instance of the supplied `schema`, i.e. featuring the required properties (and items) only.
+///
+/// Returns `None`, if the schema is unsatisfiable (e.g. `false`) or nested too deeply.
+#[expect(clippy::single_call_fn, reason = "..")]
+pub fn example_instance(schema: &Value) -> Option<Value> {
+    generate(schema, schema, 0)
+}
+
+#[expect(clippy::pattern_type_mismatch, reason = "..")]
+fn generate(schema: &Value, root: &Value, depth: usize) -> Option<Value> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    let next = depth.saturating_add(1);
+
+    let members = match schema {
+        Value::Object(members) => members,
+        Value::Bool(true) => return Some(Value::Null),
+        Value::Bool(false)
+        | Value::Null
+        | Value::Number(_)
+        | Value::String(_)
+        | Value::Array(_) => return None,
+    };
+
+    // any (referenced or combined) subschema must be satisfied along with the schema itself, so their keywords are merged
+    if let Some(reference) = members.get("$ref").and_then(Value::as_str) {
+        let target = reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))?;
+        return generate(&merged(&without(members, "$ref"), target), root, next);
+    }
+    if let Some(subschemas) = members.get("allOf").and_then(Value::as_array) {
+        let mut all = Value::Object(without(members, "allOf"));
+        for subschema in subschemas {
+            all = merged(all.as_object()?, subschema);
+        }
+        return generate(&all, root, next);
+    }
+    for keyword in ["anyOf", "oneOf"] {
+        if let Some(subschemas) = members.get(keyword).and_then(Value::as_array) {
+            return subschemas.iter().find_map(|subschema| {
+                generate(&merged(&without(members, keyword), subschema), root, next)
+            });
+        }
+    }
+
+    if let Some(value) = members.get("const") {
+        return Some(value.to_owned());
+    }
+    if let Some(values) = members.get("enum").and_then(Value::as_array) {
+        return values.first().cloned();
+    }
+    if is_set(members, DidVersionIdKeyword::KEYWORD_NAME) {
+        return example_version_id();
+    }
+    if is_set(members, DidVersionTimeKeyword::KEYWORD_NAME) {
+        return Some(Value::String(EXAMPLE_DATE_TIME.to_owned()));
+    }
+    if is_set(members, DidLogEntryKeyword::KEYWORD_NAME) && !members.contains_key("prefixItems") {
+        // a JSON array of five items, as expected by the keyword
+        return Some(json!([
+            example_version_id()?,
+            EXAMPLE_DATE_TIME,
+            {},
+            {"value": {}},
+            [{"type": "DataIntegrityProof"}],
+        ]));
+    }
+
+    let schema_type = match members.get("type") {
+        Some(Value::String(schema_type)) => Some(schema_type.as_str()),
+        Some(Value::Array(schema_types)) => schema_types.first().and_then(Value::as_str),
+        Some(_) | None
+            if members.contains_key("properties") || members.contains_key("required") =>
+        {
+            Some("object")
+        }
+        Some(_) | None if members.contains_key("prefixItems") || members.contains_key("items") => {
+            Some("array")
+        }
+        Some(_) | None => None,
+    };
+    match schema_type {
+        Some("object") => generate_object(members, root, next),
+        Some("array") => generate_array(members, root, next),
+        Some("string") => Some(Value::String(example_string(members))),
+        Some("integer" | "number") => Some(example_number(members)),
+        Some("boolean") => Some(Value::Bool(false)),
+        Some(_) | None => Some(Value::Null),
+    }
+}
+
+#[expect(clippy::single_call_fn, reason = "..")]
+fn generate_object(members: &Map<String, Value>, root: &Value, depth: usize) -> Option<Value> {
+    let properties = members.get("properties").and_then(Value::as_object);
+    let additional = members.get("additionalProperties");
+    let mut object = Map::new();
+    for name in members
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+    {
+        let subschema = properties
+            .and_then(|property_schemas| property_schemas.get(name))
+            .or(additional)
+            .unwrap_or(&Value::Bool(true));
+        object.insert(name.to_owned(), generate(subschema, root, depth)?);
+    }
+    Some(Value::Object(object))
+}
+
+#[expect(clippy::single_call_fn, reason = "..")]
+fn generate_array(members: &Map<String, Value>, root: &Value, depth: usize) -> Option<Value> {
+    let mut array = members
+        .get("prefixItems")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|subschema| generate(subschema, root, depth))
+        .collect::<Option<Vec<_>>>()?;
+    let min_items = members
+        .get("minItems")
+        .and_then(Value::as_u64)
+        .and_then(|min_items| usize::try_from(min_items).ok())
+        .unwrap_or_default();
+    if array.len() < min_items {
+        let item = generate(
+            members.get("items").unwrap_or(&Value::Bool(true)),
+            root,
+            depth,
+        )?;
+        array.resize(min_items, item);
+    }
+    Some(Value::Array(array))
+}
+
+#[expect(clippy::single_call_fn, reason = "..")]
+fn example_string(members: &Map<String, Value>) -> String {
+    match members.get("format").and_then(Value::as_str) {
+        Some("date-time") => EXAMPLE_DATE_TIME.to_owned(),
+        Some("date") => EXAMPLE_DATE_TIME.get(..10).unwrap_or_default().to_owned(),
+        Some("uri") => "https://example.com".to_owned(),
+        Some(_) | None => {
+            let min_length = members
+                .get("minLength")
+                .and_then(Value::as_u64)
+                .and_then(|min_length| usize::try_from(min_length).ok())
+                .unwrap_or_default();
+            "a".repeat(min_length)
+        }
+    }
+}
+
+#[expect(clippy::single_call_fn, reason = "..")]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+fn example_number(members: &Map<String, Value>) -> Value {
+    if let Some(minimum) = members.get("minimum") {
+        return minimum.to_owned();
+    }
+    members
+        .get("exclusiveMinimum")
+        .and_then(Value::as_i64)
+        .map_or_else(|| json!(0), |minimum| json!(minimum.saturating_add(1)))
+}
+
+/// Delivers a `versionId` (i.e. `1-<entryHash>`) featuring a proper (yet arbitrary) entry hash.
+fn example_version_id() -> Option<Value> {
+    let entry_hash = JcsSha256Hasher::default()
+        .base58btc_encode_multihash(&json!({}))
+        .ok()?;
+    Some(Value::String(format!("1-{entry_hash}")))
+}
+
+fn is_set(members: &Map<String, Value>, keyword: &str) -> bool {
+    members.get(keyword) == Some(&Value::Bool(true))
+}
+
+fn without(members: &Map<String, Value>, keyword: &str) -> Map<String, Value> {
+    let mut rest = members.to_owned();
+    rest.remove(keyword);
+    rest
+}
+
+/// Merges the keywords of the supplied `subschema` into the supplied `members`.
+///
+/// Both `properties` and `required` are combined, whereas any other keyword already present is retained.
+fn merged(members: &Map<String, Value>, subschema: &Value) -> Value {
+    let mut merged = members.to_owned();
+    let Some(sub_members) = subschema.as_object() else {
+        // a boolean subschema is either irrelevant (true) or unsatisfiable (false)
+        return if *subschema == Value::Bool(false) {
+            Value::Bool(false)
+        } else {
+            Value::Object(merged)
+        };
+    };
+    for (name, value) in sub_members {
+        match (name.as_str(), merged.get_mut(name)) {
+            ("properties", Some(&mut Value::Object(ref mut properties))) => {
+                for (property, property_schema) in value.as_object().into_iter().flatten() {
+                    let combined = properties.remove(property).map_or_else(
+                        || property_schema.to_owned(),
+                        |existing| json!({"allOf": [existing, property_schema]}),
+                    );
+                    properties.insert(property.to_owned(), combined);
+                }
+            }
+            ("required", Some(&mut Value::Array(ref mut required))) => {
+                for property in value.as_array().into_iter().flatten() {
+                    if !required.contains(property) {
+                        required.push(property.to_owned());
+                    }
+                }
+            }
+            (_, Some(_)) => {}
+            (_, None) => {
+                merged.insert(name.to_owned(), value.to_owned());
+            }
+        }
+    }
+    Value::Object(merged)
+}
@@ -0,0 +1,322 @@
+// SPDX-License-Identifier: MIT
+
+//! Merkleization of a DID log, i.e. a Merkle tree over its entry hashes (the `<entryHash>` part of each `versionId`).
+//!
+//! It enables lightweight clients to verify the inclusion of a specific version without having to fetch the full DID log.
+//!
+//! The tree (as well as the inclusion proofs) is built as specified by https://www.rfc-editor.org/rfc/rfc9162#section-2.1,
+//! hence the leaf hash of an entry is `SHA-256(0x00 || entryHash)` and the hash of an inner node is `SHA-256(0x01 || left || right)`.
+//! All hashes are encoded as (lower case) hex strings.
+
+use crate::errors::DidSidekicksError;
+use crate::metrics::count_hash_operation;
+use crate::panic_boundary::catch_panic;
+use crate::version_id::VersionId;
+use hex::ToHex as _;
+use sha2::{Digest as _, Sha256};
+
+const LEAF_HASH_PREFIX: u8 = 0x00;
+const NODE_HASH_PREFIX: u8 = 0x01;
+
+type NodeHash = [u8; 32];
+
+/// An inclusion proof of a single DID log entry, as specified by https://www.rfc-editor.org/rfc/rfc9162#section-2.1.3.
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct InclusionProof {
+    /// The (zero-based) index of the DID log entry, i.e. its version number minus one.
+    pub leaf_index: u64,
+    /// The number of DID log entries the tree is built over.
+    pub tree_size: u64,
+    /// The audit path (bottom-up), i.e. the hashes of all sibling nodes required to recompute the root hash.
+    pub path: Vec<String>,
+}
+
+/// A Merkle tree over the entry hashes of a DID log.
+///
+/// A UniFFI-compliant object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DidLogMerkleTree {
+    /// All levels of the tree (bottom-up), i.e. the first one features the leaf hashes and the last one the root hash only.
+    /// A node lacking a sibling is promoted to the next level as is, which is equivalent to the tree specified by RFC 9162.
+    levels: Vec<Vec<NodeHash>>,
+}
+
+impl DidLogMerkleTree {
+    /// The only non-empty constructor of the type, building the tree over the supplied (non-empty) list of `entry_hashes`,
+    /// in order of the DID log entries.
+    ///
+    /// An empty list is denoted by [`DidSidekicksError::DeserializationFailed`].
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub fn new(entry_hashes: Vec<String>) -> Result<Self, DidSidekicksError> {
+        catch_panic(
+            || {
+                if entry_hashes.is_empty() {
+                    return Err(DidSidekicksError::DeserializationFailed(
+                        "A Merkle tree requires at least one DID log entry hash".to_owned(),
+                    ));
+                }
+
+                let mut levels = vec![entry_hashes
+                    .iter()
+                    .map(|entry_hash| leaf_hash(entry_hash))
+                    .collect::<Vec<_>>()];
+                while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+                    let mut next = Vec::with_capacity(level.len().div_ceil(2));
+                    let mut nodes = level.iter();
+                    while let Some(left) = nodes.next() {
+                        next.push(nodes.next().map_or(*left, |right| node_hash(left, right)));
+                    }
+                    levels.push(next);
+                }
+                Ok(Self { levels })
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Builds the tree over the entry hashes of the supplied `version_ids`, in order of the DID log entries.
+    #[inline]
+    pub fn from_version_ids(version_ids: &[VersionId]) -> Result<Self, DidSidekicksError> {
+        Self::new(
+            version_ids
+                .iter()
+                .map(|version_id| version_id.entry_hash().to_owned())
+                .collect(),
+        )
+    }
+
+    /// The number of DID log entries the tree is built over.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn size(&self) -> u64 {
+        self.levels
+            .first()
+            .map_or(0, |leaves| u64::try_from(leaves.len()).unwrap_or(u64::MAX))
+    }
+
+    /// The root hash of the tree (hex-encoded).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn root(&self) -> String {
+        self.levels
+            .last()
+            .and_then(|level| level.first())
+            .map(|root| root.encode_hex())
+            .unwrap_or_default()
+    }
+
+    /// Delivers the inclusion proof of the DID log entry denoted by the supplied (zero-based) `leaf_index`.
+    ///
+    /// An index out of range is denoted by [`DidSidekicksError::DeserializationFailed`].
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn inclusion_proof(&self, leaf_index: u64) -> Result<InclusionProof, DidSidekicksError> {
+        catch_panic(
+            || {
+                let tree_size = self.size();
+                let Some(mut index) = usize::try_from(leaf_index)
+                    .ok()
+                    .filter(|_| leaf_index < tree_size)
+                else {
+                    return Err(DidSidekicksError::DeserializationFailed(format!(
+                        "Leaf index {leaf_index} is out of range, as the Merkle tree features {tree_size} DID log entries"
+                    )));
+                };
+
+                let mut path = vec![];
+                for level in &self.levels {
+                    if let Some(sibling) = level.get(index ^ 1) {
+                        path.push(sibling.encode_hex());
+                    }
+                    index /= 2;
+                }
+                // the root level never features a sibling, hence it contributes nothing to the path
+                Ok(InclusionProof {
+                    leaf_index,
+                    tree_size,
+                    path,
+                })
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+}
+
+/// Verifies the supplied inclusion `proof` of the supplied `entry_hash` against the supplied (hex-encoded) `root` hash,
+/// as specified by https://www.rfc-editor.org/rfc/rfc9162#section-2.1.3.2.
+///
+/// Returns `false` if the proof does not match, whereas a malformed proof (or root hash)
+/// is denoted by [`DidSidekicksError::DeserializationFailed`].
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn verify_inclusion_proof(
+    root: String,
+    entry_hash: String,
+    proof: InclusionProof,
+) -> Result<bool, DidSidekicksError> {
+    catch_panic(
+        || {
+            let root_hash = decode_hash(&root)?;
+            if proof.leaf_index >= proof.tree_size {
+                return Err(DidSidekicksError::DeserializationFailed(format!(
+                    "Leaf index {} of the inclusion proof is out of range (tree size {})",
+                    proof.leaf_index, proof.tree_size
+                )));
+            }
+
+            let mut index = proof.leaf_index;
+            let mut last_index = proof.tree_size.saturating_sub(1);
+            let mut hash = leaf_hash(&entry_hash);
+            for encoded_sibling in &proof.path {
+                let sibling = decode_hash(encoded_sibling)?;
+                if last_index == 0 {
+                    return Ok(false); // the path is too long
+                }
+                if !index.is_multiple_of(2) || index == last_index {
+                    hash = node_hash(&sibling, &hash);
+                    while index.is_multiple_of(2) && index != 0 {
+                        index = index.div_euclid(2);
+                        last_index = last_index.div_euclid(2);
+                    }
+                } else {
+                    hash = node_hash(&hash, &sibling);
+                }
+                index = index.div_euclid(2);
+                last_index = last_index.div_euclid(2);
+            }
+            Ok(last_index == 0 && hash == root_hash)
+        },
+        DidSidekicksError::InternalError,
+    )
+}
+
+fn leaf_hash(entry_hash: &str) -> NodeHash {
+    count_hash_operation("merkle_leaf");
+    Sha256::new()
+        .chain_update([LEAF_HASH_PREFIX])
+        .chain_update(entry_hash)
+        .finalize()
+        .into()
+}
+
+fn node_hash(left: &NodeHash, right: &NodeHash) -> NodeHash {
+    count_hash_operation("merkle_node");
+    Sha256::new()
+        .chain_update([NODE_HASH_PREFIX])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+fn decode_hash(hash: &str) -> Result<NodeHash, DidSidekicksError> {
+    hex::FromHex::from_hex(hash).map_err(|err| {
+        DidSidekicksError::DeserializationFailed(format!(
+            "Malformed Merkle tree hash '{hash}': {err}"
+        ))
+    })
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+
+    fn entry_hashes(count: usize) -> Vec<String> {
+        (1..=count).map(|number| format!("QmEntry{number}")).collect()
+    }
+
+    /// The root hash as specified by https://www.rfc-editor.org/rfc/rfc9162#section-2.1.1 (i.e. the recursive definition).
+    #[expect(clippy::pattern_type_mismatch, reason = "..")]
+    fn reference_root(leaves: &[String]) -> NodeHash {
+        match leaves {
+            [single] => leaf_hash(single),
+            _ => {
+                let split = leaves.len().next_power_of_two().div_euclid(2);
+                let (left, right) = leaves.split_at(split);
+                node_hash(&reference_root(left), &reference_root(right))
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    #[case(3)]
+    #[case(5)]
+    #[case(7)]
+    #[case(8)]
+    #[case(13)]
+    fn test_inclusion_proofs(#[case] count: usize) {
+        let entry_hashes = entry_hashes(count);
+        let tree = DidLogMerkleTree::new(entry_hashes.to_owned()).unwrap();
+        assert_eq!(tree.size(), u64::try_from(count).unwrap());
+        assert_eq!(
+            tree.root(),
+            reference_root(&entry_hashes).encode_hex::<String>()
+        );
+
+        for (index, entry_hash) in entry_hashes.iter().enumerate() {
+            let proof = tree.inclusion_proof(u64::try_from(index).unwrap()).unwrap();
+            assert!(verify_inclusion_proof(tree.root(), entry_hash.to_owned(), proof.clone()).unwrap());
+            // any other entry hash must not be proven by the very same proof
+            assert!(!verify_inclusion_proof(tree.root(), "QmOther".to_owned(), proof.clone()).unwrap());
+
+            let mut tampered = proof.clone();
+            tampered.path.push(tree.root());
+            assert!(!verify_inclusion_proof(tree.root(), entry_hash.to_owned(), tampered).unwrap());
+        }
+    }
+
+    #[rstest]
+    fn test_from_version_ids() {
+        let version_ids: Vec<VersionId> = ["1-QmEntry1", "2-QmEntry2", "3-QmEntry3"]
+            .iter()
+            .map(|version_id| version_id.parse().unwrap())
+            .collect();
+        assert_eq!(
+            DidLogMerkleTree::from_version_ids(&version_ids).unwrap(),
+            DidLogMerkleTree::new(entry_hashes(3)).unwrap()
+        );
+    }
+
+    #[rstest]
+    fn test_invalid_input() {
+        assert_error(
+            DidLogMerkleTree::new(vec![]),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "at least one DID log entry hash",
+        );
+        assert_error(
+            DidLogMerkleTree::new(entry_hashes(3))
+                .unwrap()
+                .inclusion_proof(3),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "out of range",
+        );
+
+        let tree = DidLogMerkleTree::new(entry_hashes(3)).unwrap();
+        let mut proof = tree.inclusion_proof(0).unwrap();
+        proof.path.push("xyz".to_owned());
+        assert_error(
+            verify_inclusion_proof(tree.root(), "QmEntry1".to_owned(), proof),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "Malformed Merkle tree hash 'xyz'",
+        );
+    }
+}
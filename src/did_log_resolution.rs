@@ -0,0 +1,1191 @@
+// SPDX-License-Identifier: MIT
+
+//! A convenience facade wiring the building blocks of this crate into a single (offline) DID log resolution pipeline.
+//!
+//! Consumers do not have to assemble it on their own (and in the right order), as [`verify_and_resolve`] does it for them, i.e.:
+//! 1. each DID log entry is validated against a JSON schema, whereas a [`VerificationPolicy`] (if any) is enforced on top
+//!    (see [`DidLogVerifier`]),
+//! 2. the DID log must be continuous, i.e. featuring consecutive version numbers (starting at 1), non-descending `versionTime`
+//!    and no DID log entry following the deactivation of the DID,
+//! 3. the entry hash (featured by the `versionId`) of each DID log entry must match the DID log entry itself, chained
+//!    to the previous `versionId` (or to the SCID, in case of the very first DID log entry), whereas the very first DID log entry
+//!    must also match the SCID it features (see https://identity.foundation/didwebvh/v1.0/#entry-hash-generation-and-verification),
+//! 4. the Data Integrity proof of each DID log entry must be valid and created by a key listed in the `updateKeys` in force
+//!    (see [`authorize_proof`]), whereas in case of pre-rotation (i.e. non-empty `nextKeyHashes`) the `updateKeys` published by
+//!    a DID log entry are in force as of the entry itself, thus each of them must be committed to by the previous DID log entry
+//!    (see [`authorize_update_keys`]),
+//! 5. the DID method parameters (featured by a DID log entry only if changed) are merged and
+//! 6. the DID document is extracted from the latest DID log entry, whereas it must feature the very DID (and SCID) being resolved.
+//!
+//! Both forms of DID log entries are supported, i.e.:
+//! - the JSON array (`[versionId, versionTime, parameters, state, proof]`) of `did:tdw` (v0.3), whereas the proof secures
+//!   the DID document (featured by `state` as either `value` or `patch`) and must feature the `versionId` as `challenge`,
+//! - the JSON object (`versionId`, `versionTime`, `parameters`, `state`, `proof`) of `did:webvh` (v1.0),
+//!   whereas the proof secures the DID log entry (lacking the `proof`) itself.
+//!
+//! In case of a DID log migrating from `did:tdw` v0.3 to `did:webvh` v1.0 (see [`ResolutionOptions::transition_version`]),
+//! the DID log entries preceding the transition entry must be of the former form (and the transition entry as well as
+//! any subsequent one of the latter). Thus, the transition entry is validated against both rule sets, i.e. it must follow
+//! the `did:webvh` v1.0 rules (switching the `method` from `did:tdw:0.3` to `did:webvh:1.0`) and must also be accepted by
+//! the legacy validator (see [`ResolutionOptions::legacy_validator`]), while its proof must be created by a key authorized
+//! by the DID method parameters active under the `did:tdw` v0.3 rules. Otherwise, all the DID log entries must be of the same form.
+//!
+//! Besides, a verified DID log (featuring the state of the DID as of each DID log entry) is also available
+//! (see [`crate::did_log::DidLog`]).
+//!
+//! Optionally (see [`ResolutionOptions::controller_resolver`]), a proof not created by any of the `updateKeys` in force may
+//! also be created by a controller of the DID (see [`authorize_proof_or_controller`]), as listed by the DID document in force,
+//! i.e. the one featured by the previous DID log entry (or by the very first DID log entry itself).
+//!
+//! As the proof of a DID log entry of the JSON array form secures the DID document only, the DID method parameters
+//! (e.g. `updateKeys`) are authenticated solely by the entry hash chain, which is therefore verified prior to any key authorization.
+//!
+//! CAUTION Witness proofs are not verified here, as they are rather a matter of the respective DID method
+//! (see [`crate::did_resolver::DidResolver`]).
+
+use crate::bounded_json::from_str_bounded;
+use crate::did_controllers::{authorize_proof_or_controller, ControllerResolver};
+use crate::did_doc::{parse_did_doc, DidDocExtended};
+use crate::did_doc_patch::{apply_json_patch, json_patch_from_json};
+use crate::did_jsonschema::{
+    DidLogEntryJsonSchema, DidLogEntryValidator, DidLogEntryValidatorError,
+};
+use crate::did_log::{DidDocUpdate, DidLogEntry};
+use crate::did_log_verifier::DidLogVerifier;
+use crate::did_method_parameters::DidMethodParameter;
+use crate::ed25519::{Ed25519VerifyingKey, MultiBaseConverter as _, VerificationOptions};
+use crate::errors::DidSidekicksError;
+use crate::jcs_sha256_hasher::JcsSha256Hasher;
+use crate::panic_boundary::catch_panic;
+use crate::vc_data_integrity::{
+    authorize_proof, authorize_update_keys, is_pre_rotation_active, DataIntegrityProof,
+    EddsaJcs2022Cryptosuite, VCDataIntegrity as _, PARAM_UPDATE_KEYS,
+};
+use crate::verification_policy::VerificationPolicy;
+use crate::version_id::VersionId;
+use chrono::{DateTime, FixedOffset, SecondsFormat};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The (minimal) JSON schema each DID log entry is validated against by [`verify_and_resolve`].
+///
+/// Unless supplied otherwise (see [`ResolutionOptions::validator`]), both forms of DID log entries are supported
+/// (see [module documentation](self)).
+pub const DEFAULT_DID_LOG_ENTRY_SCHEMA: &str = r#"{
+    "$schema": "https://json-schema.org/draft/2020-12/schema",
+    "anyOf": [
+        {
+            "type": "array",
+            "did-log-entry": true,
+            "prefixItems": [
+                {"type": "string", "did-version-id": true},
+                {"type": "string", "did-version-time": true},
+                {"type": "object"},
+                {"type": "object"},
+                {"type": "array", "minItems": 1}
+            ]
+        },
+        {
+            "type": "object",
+            "properties": {
+                "versionId": {"type": "string", "did-version-id": true},
+                "versionTime": {"type": "string", "did-version-time": true},
+                "parameters": {"type": "object"},
+                "state": {"type": "object"},
+                "proof": {"type": "array", "minItems": 1}
+            },
+            "required": ["versionId", "versionTime", "parameters", "state", "proof"]
+        }
+    ]
+}"#;
+
+/// The [`DEFAULT_DID_LOG_ENTRY_SCHEMA`], as required by [`DidLogEntryValidator::from_cached`].
+struct DefaultDidLogEntryJsonSchema;
+
+#[expect(clippy::missing_trait_methods, reason = "..")]
+impl DidLogEntryJsonSchema for DefaultDidLogEntryJsonSchema {
+    #[inline]
+    fn get_json_schema(&self) -> String {
+        DEFAULT_DID_LOG_ENTRY_SCHEMA.to_owned()
+    }
+}
+
+/// The name of the DID method parameter denoting the DID method (specification version) whose rules a DID log entry follows.
+const PARAM_METHOD: &str = "method";
+/// The DID method (specification version) a DID log is migrated to, as specified by https://identity.foundation/didwebvh/v1.0/#didwebvh-did-method-parameters.
+const METHOD_WEBVH_1_0: &str = "did:webvh:1.0";
+/// The DID method (specification version) a DID log is migrated from.
+const METHOD_TDW_0_3: &str = "did:tdw:0.3";
+/// The name of the DID method parameter featuring the SCID.
+const PARAM_SCID: &str = "scid";
+/// The placeholder of the SCID, as specified by https://identity.foundation/didwebvh/v1.0/#generate-scid.
+const SCID_PLACEHOLDER: &str = "{SCID}";
+
+/// The options of [`verify_and_resolve`]. By default, [`DEFAULT_DID_LOG_ENTRY_SCHEMA`] is used and no policy is enforced.
+///
+/// A UniFFI-compliant record.
+#[derive(Clone, Default)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct ResolutionOptions {
+    /// The validator each DID log entry is validated against, if other than [`DEFAULT_DID_LOG_ENTRY_SCHEMA`] (e.g. DID method-specific).
+    pub validator: Option<Arc<DidLogEntryValidator>>,
+    /// The verification policy to enforce on top, if any.
+    pub policy: Option<Arc<VerificationPolicy>>,
+    /// The number of the `versionId` of the transition entry, if the DID log migrates from `did:tdw` v0.3 to `did:webvh` v1.0
+    /// (see [module documentation](self)).
+    pub transition_version: Option<u64>,
+    /// The validator each DID log entry preceding the transition entry (as well as the transition entry itself) is validated against
+    /// (in case of a migration), if other than [`DEFAULT_DID_LOG_ENTRY_SCHEMA`].
+    pub legacy_validator: Option<Arc<DidLogEntryValidator>>,
+    /// The resolver of controller DIDs, if a DID log entry may also be authorized by a controller of the DID
+    /// (see [module documentation](self)).
+    pub controller_resolver: Option<Arc<dyn ControllerResolver>>,
+}
+
+impl core::fmt::Debug for ResolutionOptions {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("ResolutionOptions")
+            .field("validator", &self.validator)
+            .field("policy", &self.policy)
+            .field("transition_version", &self.transition_version)
+            .field("legacy_validator", &self.legacy_validator)
+            .field("controller_resolver", &self.controller_resolver.is_some())
+            .finish()
+    }
+}
+
+/// The outcome of a (successful) [`verify_and_resolve`] call.
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct ResolutionResult {
+    /// The DID document featured by the latest DID log entry, along with the (merged) DID method parameters.
+    pub did_doc: Arc<DidDocExtended>,
+    /// The `versionId` of the latest DID log entry.
+    pub version_id: String,
+    /// The `versionTime` of the latest DID log entry.
+    pub version_time: String,
+    /// Whether the DID is deactivated (i.e. the `deactivated` DID method parameter is set).
+    pub deactivated: bool,
+    /// The number of DID log entries.
+    pub entry_count: u64,
+}
+
+/// Resolves the supplied `did` from the supplied DID log (`log_jsonl`, in JSON Lines format) according to the supplied `options`.
+///
+/// The whole (offline) verification pipeline is run beforehand (see [module documentation](self)).
+///
+/// Any failure is denoted by the very first [`DidSidekicksError`] occurred, e.g.:
+/// - [`DidSidekicksError::InvalidDidDocument`], if any DID log entry is invalid (e.g. w.r.t. the schema or the verification policy)
+///   or the DID log is not continuous,
+/// - [`DidSidekicksError::KeyNotAuthorized`] or [`DidSidekicksError::SignatureInvalid`], if any proof is invalid.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn verify_and_resolve(
+    did: &str,
+    log_jsonl: &str,
+    options: ResolutionOptions,
+) -> Result<ResolutionResult, DidSidekicksError> {
+    catch_panic(
+        || {
+            let (state, _) = replay(log_jsonl, &options)?;
+            state.into_result(did)
+        },
+        DidSidekicksError::InternalError,
+    )
+}
+
+/// Verifies and applies all the DID log entries of the supplied DID log, thus delivering the final state of the DID
+/// along with each (verified) DID log entry.
+pub(crate) fn replay(
+    log_jsonl: &str,
+    options: &ResolutionOptions,
+) -> Result<(ResolutionState, Vec<DidLogEntry>), DidSidekicksError> {
+    // the default schema is compiled only once (if caching is enabled, see crate::cache::set_caching_enabled)
+    let validator_or_default = |validator: Option<&Arc<DidLogEntryValidator>>| {
+        validator.map_or_else(
+            || {
+                DidLogEntryValidator::from_cached(Arc::new(DefaultDidLogEntryJsonSchema))
+                    .map(Arc::new)
+                    .map_err(|err| DidSidekicksError::InternalError(err.to_string()))
+            },
+            |supplied| Ok(Arc::clone(supplied)),
+        )
+    };
+    let validator = validator_or_default(options.validator.as_ref())?;
+    let verifier = match (options.transition_version, options.policy.as_ref()) {
+        (Some(transition_version), policy) => DidLogVerifier::with_migration(
+            validator_or_default(options.legacy_validator.as_ref())?,
+            validator,
+            transition_version,
+            policy.map(Arc::clone),
+        ),
+        (None, Some(policy)) => DidLogVerifier::with_policy(validator, Arc::clone(policy)),
+        (None, None) => DidLogVerifier::new(validator),
+    };
+    verifier
+        .feed_chunk(log_jsonl.as_bytes().to_vec())
+        .and_then(|_| verifier.finish())
+        .map_err(|err| match err {
+            DidLogEntryValidatorError::DeserializationError(detail) => {
+                DidSidekicksError::DeserializationFailed(detail)
+            }
+            DidLogEntryValidatorError::InternalError(detail) => {
+                DidSidekicksError::InternalError(detail)
+            }
+            DidLogEntryValidatorError::ValidationError(detail)
+            | DidLogEntryValidatorError::BudgetExceeded(detail) => {
+                DidSidekicksError::InvalidDidDocument(detail)
+            }
+        })?;
+
+    let mut state = ResolutionState {
+        transition_version: options.transition_version,
+        controller_resolver: options.controller_resolver.as_ref().map(Arc::clone),
+        ..ResolutionState::default()
+    };
+    let entries = verifier
+        .get_verified_entries()
+        .iter()
+        .map(|entry| state.apply(&from_str_bounded(entry)?))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((state, entries))
+}
+
+/// The state of a DID, as of the DID log entries applied so far.
+#[derive(Default)]
+pub(crate) struct ResolutionState {
+    parameters: HashMap<String, Arc<DidMethodParameter>>,
+    did_doc: Option<Value>,
+    version_id: Option<VersionId>,
+    version_time: Option<DateTime<FixedOffset>>,
+    /// The SCID featured (and matched) by the very first DID log entry.
+    scid: Option<String>,
+    deactivated: bool,
+    entry_count: u64,
+    /// Whether the DID log entries are of the JSON array form, as determined by the very first one.
+    is_array_form: Option<bool>,
+    /// See [`ResolutionOptions::transition_version`].
+    transition_version: Option<u64>,
+    /// See [`ResolutionOptions::controller_resolver`].
+    controller_resolver: Option<Arc<dyn ControllerResolver>>,
+}
+
+impl ResolutionState {
+    /// Verifies the supplied (next) DID log `entry` against this state, thus applying it on success.
+    fn apply(&mut self, entry: &Value) -> Result<DidLogEntry, DidSidekicksError> {
+        if self.deactivated {
+            return Err(DidSidekicksError::InvalidDidDocument(
+                "the DID is already deactivated".to_owned(),
+            ));
+        }
+
+        let is_array_form = entry.is_array();
+        let member = |index: usize, name: &str| {
+            let value = if is_array_form {
+                entry.get(index)
+            } else {
+                entry.get(name)
+            };
+            value
+                .cloned()
+                .ok_or_else(|| DidSidekicksError::InvalidDidDocument(format!("missing '{name}'")))
+        };
+
+        let (version_id, version_time) =
+            self.check_continuity(&member(0, "versionId")?, &member(1, "versionTime")?)?;
+        let parameters_value = member(2, "parameters")?;
+        self.check_form(&version_id, is_array_form, &parameters_value)?;
+        // the DID method parameters are authenticated by the entry hash only (see module documentation)
+        let scid = self.check_entry_hash(entry, &version_id, &parameters_value)?;
+
+        // in case of pre-rotation, the newly published update keys must have been committed to beforehand
+        let is_pre_rotation = is_pre_rotation_active(&self.parameters);
+        if is_pre_rotation {
+            let update_keys = parameters_value
+                .get(PARAM_UPDATE_KEYS)
+                .and_then(Value::as_array)
+                .ok_or_else(|| {
+                    DidSidekicksError::InvalidDidDocument(format!(
+                        "the DID log entry '{version_id}' features no '{PARAM_UPDATE_KEYS}', although pre-rotation is active"
+                    ))
+                })?
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_owned)
+                .collect::<Vec<_>>();
+            authorize_update_keys(&update_keys, &self.parameters)?;
+        }
+
+        // DID method parameters are featured only if changed, whereas a proof must be created by a key listed in the
+        // `updateKeys` in force, i.e. the ones active so far or, in case of the very first DID log entry as well as
+        // in case of pre-rotation, the ones published by the DID log entry itself
+        let mut parameters = self.parameters.clone();
+        let mut changed_parameters = Vec::new();
+        if let Some(members) = parameters_value.as_object() {
+            for (name, value) in members {
+                parameters.insert(
+                    name.to_owned(),
+                    Arc::new(DidMethodParameter::try_from_value(name, value.clone())?),
+                );
+                changed_parameters.push(name.to_owned());
+            }
+        }
+        let authorizing = if self.version_id.is_none() || is_pre_rotation {
+            &parameters
+        } else {
+            &self.parameters
+        };
+
+        let doc_state = member(3, "state")?;
+        let (did_doc, did_doc_update) = if is_array_form {
+            self.extract_did_doc(&doc_state)?
+        } else {
+            (doc_state, DidDocUpdate::Replaced)
+        };
+
+        // the controllers listed by the DID document in force (if enabled at all)
+        let controllers = self
+            .controller_resolver
+            .as_deref()
+            .map(|resolver| (self.did_doc.as_ref().unwrap_or(&did_doc), resolver));
+        let proof = DataIntegrityProof::from(member(4, "proof")?.to_string())?;
+        if is_array_form {
+            proof.verify_challenge(&version_id.to_string())?;
+            verify_proof(&proof, authorizing, controllers, &did_doc)?;
+        } else {
+            let mut unsecured = entry.clone();
+            if let Some(members) = unsecured.as_object_mut() {
+                members.remove("proof");
+            }
+            verify_proof(&proof, authorizing, controllers, &unsecured)?;
+        }
+
+        let verified = DidLogEntry::new(
+            version_id.to_string(),
+            version_time,
+            proof.verification_method,
+            did_doc.clone(),
+            did_doc_update,
+            changed_parameters,
+            parameters.clone(),
+        );
+
+        self.deactivated = verified.is_deactivated();
+        self.parameters = parameters;
+        self.did_doc = Some(did_doc);
+        self.version_id = Some(version_id);
+        self.version_time = Some(version_time);
+        self.scid = Some(scid);
+        self.entry_count = self.entry_count.saturating_add(1);
+        self.is_array_form = Some(is_array_form);
+        Ok(verified)
+    }
+
+    /// Ensures the form of the supplied DID log entry (featuring the supplied `version_id` and `parameters`) is the expected one, i.e.:
+    /// - in case of a migration, the JSON array form (`did:tdw` v0.3) preceding the transition entry and the JSON object form
+    ///   (`did:webvh` v1.0) as of the transition entry, whereas the transition entry must switch the `method` to `did:webvh:1.0`,
+    /// - otherwise, the form of the very first DID log entry.
+    fn check_form(
+        &self,
+        version_id: &VersionId,
+        is_array_form: bool,
+        parameters: &Value,
+    ) -> Result<(), DidSidekicksError> {
+        let form_name = |is_array: bool| {
+            if is_array {
+                "JSON array"
+            } else {
+                "JSON object"
+            }
+        };
+        let Some(transition_version) = self.transition_version else {
+            return match self.is_array_form {
+                Some(expected) if expected != is_array_form => {
+                    Err(DidSidekicksError::InvalidDidDocument(format!(
+                        "the DID log entry '{version_id}' is of the {} form instead of the {} one",
+                        form_name(is_array_form),
+                        form_name(expected)
+                    )))
+                }
+                Some(_) | None => Ok(()),
+            };
+        };
+
+        let expected = version_id.number() < transition_version;
+        if is_array_form != expected {
+            return Err(DidSidekicksError::InvalidDidDocument(format!(
+                "the DID log entry '{version_id}' is of the {} form instead of the {} one (as migrated at {transition_version})",
+                form_name(is_array_form),
+                form_name(expected)
+            )));
+        }
+        if version_id.number() != transition_version {
+            return Ok(());
+        }
+        // the transition entry must comply with the rules of both DID methods, i.e. it must be preceded by the legacy one
+        if self
+            .parameters
+            .get(PARAM_METHOD)
+            .and_then(|param| param.get_string_value())
+            .as_deref()
+            != Some(METHOD_TDW_0_3)
+        {
+            return Err(DidSidekicksError::InvalidDidDocument(format!(
+                "the transition entry '{version_id}' must be preceded by DID log entries following '{METHOD_TDW_0_3}'"
+            )));
+        }
+        if parameters.get(PARAM_METHOD).and_then(Value::as_str) != Some(METHOD_WEBVH_1_0) {
+            return Err(DidSidekicksError::InvalidDidDocument(format!(
+                "the transition entry '{version_id}' must switch the '{PARAM_METHOD}' to '{METHOD_WEBVH_1_0}'"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Ensures the entry hash featured by the supplied `version_id` matches the supplied DID log `entry`, chained to
+    /// the previous `versionId` (or to the SCID, in case of the very first DID log entry), whereas the very first DID log entry
+    /// must also match the SCID featured by its `parameters`. Delivers the SCID of the DID.
+    fn check_entry_hash(
+        &self,
+        entry: &Value,
+        version_id: &VersionId,
+        parameters: &Value,
+    ) -> Result<String, DidSidekicksError> {
+        let (scid, predecessor) = match (self.scid.as_ref(), self.version_id.as_ref()) {
+            (Some(scid), Some(previous)) => (scid.to_owned(), previous.to_string()),
+            _ => {
+                let scid = parameters
+                    .get(PARAM_SCID)
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| {
+                        DidSidekicksError::InvalidDidDocument(format!(
+                            "the DID log entry '{version_id}' features no '{PARAM_SCID}'"
+                        ))
+                    })?;
+                let preliminary = entry_hash_input(entry, SCID_PLACEHOLDER)
+                    .to_string()
+                    .replace(scid, SCID_PLACEHOLDER);
+                let expected = JcsSha256Hasher::default()
+                    .base58btc_encode_multihash_text(&preliminary)
+                    .map_err(|err| DidSidekicksError::SerializationFailed(err.to_string()))?;
+                if expected != scid {
+                    return Err(DidSidekicksError::InvalidDidDocument(format!(
+                        "the SCID '{scid}' does not match the DID log entry '{version_id}'"
+                    )));
+                }
+                (scid.to_owned(), scid.to_owned())
+            }
+        };
+
+        let entry_hash = JcsSha256Hasher::default()
+            .base58btc_encode_multihash(&entry_hash_input(entry, &predecessor))
+            .map_err(|err| DidSidekicksError::SerializationFailed(err.to_string()))?;
+        if entry_hash != version_id.entry_hash() {
+            return Err(DidSidekicksError::InvalidDidDocument(format!(
+                "the entry hash of the DID log entry '{version_id}' does not match the DID log entry"
+            )));
+        }
+        Ok(scid)
+    }
+
+    /// Ensures the supplied `versionId` and `versionTime` (of the next DID log entry) succeed the ones applied so far.
+    fn check_continuity(
+        &self,
+        version_id_value: &Value,
+        version_time_value: &Value,
+    ) -> Result<(VersionId, DateTime<FixedOffset>), DidSidekicksError> {
+        let version_id = version_id_value
+            .as_str()
+            .unwrap_or_default()
+            .parse::<VersionId>()?;
+        let is_successor = self.version_id.as_ref().map_or_else(
+            || version_id.number() == 1,
+            |previous| version_id.is_successor_of(previous),
+        );
+        if !is_successor {
+            return Err(DidSidekicksError::InvalidDidDocument(format!(
+                "the versionId '{version_id}' does not succeed the previous one"
+            )));
+        }
+
+        let version_time =
+            DateTime::parse_from_rfc3339(version_time_value.as_str().unwrap_or_default()).map_err(
+                |err| DidSidekicksError::InvalidDidDocument(format!("invalid versionTime: {err}")),
+            )?;
+        if self
+            .version_time
+            .is_some_and(|previous| version_time < previous)
+        {
+            return Err(DidSidekicksError::InvalidDidDocument(format!(
+                "the versionTime '{}' precedes the previous one",
+                version_time.to_rfc3339()
+            )));
+        }
+        Ok((version_id, version_time))
+    }
+
+    /// Extracts the DID document from the supplied `state` of a DID log entry (of the JSON array form),
+    /// featuring either the DID document itself (`value`) or a JSON patch to apply to the current one (`patch`).
+    fn extract_did_doc(
+        &self,
+        doc_state: &Value,
+    ) -> Result<(Value, DidDocUpdate), DidSidekicksError> {
+        match (doc_state.get("value"), doc_state.get("patch")) {
+            (Some(value), _) => Ok((value.clone(), DidDocUpdate::Replaced)),
+            (None, Some(patch)) => {
+                let mut patched = self.did_doc.clone().ok_or_else(|| {
+                    DidSidekicksError::InvalidDidDocument("no DID document to patch".to_owned())
+                })?;
+                apply_json_patch(&mut patched, &json_patch_from_json(patch.to_string())?)?;
+                Ok((patched, DidDocUpdate::Patched))
+            }
+            (None, None) => Err(DidSidekicksError::InvalidDidDocument(
+                "neither 'value' nor 'patch' featured by 'state'".to_owned(),
+            )),
+        }
+    }
+
+    /// Delivers the outcome of the resolution of the supplied `did`, which must be featured by the (latest) DID document.
+    pub(crate) fn into_result(self, did: &str) -> Result<ResolutionResult, DidSidekicksError> {
+        let (Some(doc_value), Some(version_id), Some(version_time)) =
+            (self.did_doc, self.version_id, self.version_time)
+        else {
+            return Err(DidSidekicksError::InvalidDidDocument(
+                "the DID log features no entries".to_owned(),
+            ));
+        };
+        let did_doc = parse_did_doc(&doc_value.to_string())?;
+        if did_doc.id != did {
+            return Err(DidSidekicksError::InvalidDidDocument(format!(
+                "the DID document features '{}' instead of '{did}'",
+                did_doc.id
+            )));
+        }
+        // the SCID is the very first segment of the method-specific identifier (e.g. `did:webvh:{SCID}:example.com`)
+        if let Some(scid) = self.scid.as_ref() {
+            if did.split(':').nth(2) != Some(scid.as_str()) {
+                return Err(DidSidekicksError::InvalidDidDocument(format!(
+                    "the DID '{did}' does not feature the SCID '{scid}'"
+                )));
+            }
+        }
+        Ok(ResolutionResult {
+            did_doc: Arc::new(DidDocExtended::new(did_doc, self.parameters)),
+            version_id: version_id.to_string(),
+            version_time: version_time.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            deactivated: self.deactivated,
+            entry_count: self.entry_count,
+        })
+    }
+}
+
+/// Delivers the input of the entry hash of the supplied DID log `entry` (i.e. lacking the `proof`), whereas the `versionId`
+/// is replaced by the supplied `predecessor` (i.e. the previous `versionId`, the SCID or its placeholder).
+fn entry_hash_input(entry: &Value, predecessor: &str) -> Value {
+    let predecessor_id = Value::String(predecessor.to_owned());
+    if let Some(items) = entry.as_array() {
+        return core::iter::once(predecessor_id)
+            .chain(items.iter().skip(1).take(3).cloned())
+            .collect();
+    }
+    let mut unsecured = entry.clone();
+    if let Some(members) = unsecured.as_object_mut() {
+        members.remove("proof");
+        members.insert("versionId".to_owned(), predecessor_id);
+    }
+    unsecured
+}
+
+/// Verifies the supplied `proof` of the supplied `document`, whereas it must be created by a key authorized by the supplied `parameters`
+/// or, if supplied, by a controller of the DID document in force (see [`authorize_proof_or_controller`]).
+fn verify_proof(
+    proof: &DataIntegrityProof,
+    parameters: &HashMap<String, Arc<DidMethodParameter>>,
+    controllers: Option<(&Value, &dyn ControllerResolver)>,
+    document: &Value,
+) -> Result<(), DidSidekicksError> {
+    if let Some((did_doc_json, resolver)) = controllers {
+        let did_doc = parse_did_doc(&did_doc_json.to_string())?;
+        return authorize_proof_or_controller(proof, parameters, &did_doc, document, resolver)
+            .map(|_| ());
+    }
+    let signer_key = authorize_proof(proof, parameters)?;
+    let suite = EddsaJcs2022Cryptosuite {
+        verifying_key: Some(Ed25519VerifyingKey::from_multibase(&signer_key)?),
+        signing_key: None,
+        verification_options: VerificationOptions::default(),
+    };
+    suite.verify_proof(proof, document)
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(
+    clippy::indexing_slicing,
+    reason = "panic-safe as long as test case setup is correct"
+)]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+#[expect(clippy::shadow_reuse, reason = "..")]
+#[expect(clippy::shadow_unrelated, reason = "..")]
+mod test {
+    use super::*;
+    use crate::did_doc::DidDoc;
+    use crate::ed25519::Ed25519KeyPair;
+    use crate::errors::{DidResolverError, DidSidekicksErrorKind};
+    use crate::jcs_sha256_hasher::JcsSha256Hasher;
+    use crate::test::assert_error;
+    use crate::vc_data_integrity::CryptoSuiteProofOptions;
+    use rstest::rstest;
+    use serde_json::json;
+
+    const TDW_JS_DID_SINGLE_KEY: &str =
+        "did:tdw:QmXjp5qhSEvm8oXip43cDX62hZhHZdAMYv7Magy1tkffSz:example.com";
+    const TDW_JS_DID_DEACTIVATED: &str =
+        "did:tdw:QmdSU7F2rF8r4m6GZK7Evi2tthfDDxhw3NppU8pJMbd2hB:example.com";
+    const DIDTOOLBOX_DID: &str = "did:tdw:QmPsui8ffosRTxUBP8vJoejauqEUGvhmWe77BNo1StgLk7:identifier-reg.trust-infra.swiyu-int.admin.ch:api:v1:did:18fa7c77-9dd1-4e20-a147-fb1bec146085";
+
+    #[rstest]
+    #[case(
+        TDW_JS_DID_SINGLE_KEY,
+        include_str!("../test_data/generated_by_tdw_js/single_update_key.jsonl"),
+        3,
+        false
+    )]
+    #[case(
+        TDW_JS_DID_DEACTIVATED,
+        include_str!("../test_data/generated_by_tdw_js/deactivated.jsonl"),
+        3,
+        true
+    )]
+    #[case(
+        DIDTOOLBOX_DID,
+        include_str!("../test_data/generated_by_didtoolbox_java/v005_did.jsonl"),
+        5,
+        false
+    )]
+    fn test_verify_and_resolve(
+        #[case] did: &str,
+        #[case] log_jsonl: &str,
+        #[case] entry_count: u64,
+        #[case] deactivated: bool,
+    ) {
+        let result = verify_and_resolve(did, log_jsonl, ResolutionOptions::default()).unwrap();
+
+        assert_eq!(result.did_doc.get_did_doc_obj().id, did);
+        assert_eq!(result.entry_count, entry_count);
+        assert_eq!(result.deactivated, deactivated);
+        assert!(result.version_id.starts_with(&format!("{entry_count}-")));
+        assert!(!result.version_time.is_empty());
+    }
+
+    const WEBVH_DID: &str = "did:webvh:{SCID}:example.com";
+    const VERSION_TIME: &str = "2012-12-12T12:12:12Z";
+
+    /// Delivers a DID along with its DID log (featuring 3 DID log entries) migrating from `did:tdw` v0.3 to `did:webvh` v1.0
+    /// at the supplied `transition_version`, whereas the transition entry switches the `method` to the supplied one.
+    fn migrating_log(transition_version: u64, method: &str) -> (String, String) {
+        migrating_log_from(METHOD_TDW_0_3, transition_version, method)
+    }
+
+    /// The same as [`migrating_log`], but the very first DID log entry features the supplied `legacy_method`.
+    fn migrating_log_from(
+        legacy_method: &str,
+        transition_version: u64,
+        method: &str,
+    ) -> (String, String) {
+        let key_pair = Ed25519KeyPair::generate();
+        let update_key = key_pair.verifying_key.to_multibase();
+        let suite = EddsaJcs2022Cryptosuite {
+            verifying_key: Some(key_pair.verifying_key.clone()),
+            signing_key: Some(key_pair.signing_key),
+            verification_options: VerificationOptions::default(),
+        };
+        let proof_options = CryptoSuiteProofOptions::from_verification_method(&format!(
+            "did:key:{update_key}#{update_key}"
+        ))
+        .with_created(DateTime::parse_from_rfc3339(VERSION_TIME).unwrap().to_utc())
+        .with_proof_purpose("assertionMethod");
+        let mut did_doc = json!({
+            "@context": ["https://www.w3.org/ns/did/v1"],
+            "id": WEBVH_DID,
+            "verificationMethod": [],
+        });
+
+        let mut scid = String::new();
+        let mut previous = SCID_PLACEHOLDER.to_owned();
+        let mut entries = Vec::new();
+        for number in 1..=3 {
+            let is_array_form = number < transition_version;
+            let parameters = match number {
+                1 => {
+                    json!({"method": legacy_method, "scid": SCID_PLACEHOLDER, "updateKeys": [update_key]})
+                }
+                transition if transition == transition_version => json!({"method": method}),
+                _ => json!({}),
+            };
+            let mut entry = if is_array_form {
+                json!([previous, VERSION_TIME, parameters, {"value": did_doc}])
+            } else {
+                json!({
+                    "versionId": previous,
+                    "versionTime": VERSION_TIME,
+                    "parameters": parameters,
+                    "state": did_doc,
+                })
+            };
+            if number == 1 {
+                scid = JcsSha256Hasher::default()
+                    .base58btc_encode_multihash(&entry)
+                    .unwrap();
+                entry = serde_json::from_str(&entry.to_string().replace(SCID_PLACEHOLDER, &scid))
+                    .unwrap();
+                did_doc["id"] = json!(WEBVH_DID.replace(SCID_PLACEHOLDER, &scid));
+            }
+            let entry_hash = JcsSha256Hasher::default()
+                .base58btc_encode_multihash(&entry)
+                .unwrap();
+            let version_id = format!("{number}-{entry_hash}");
+
+            let entry = if is_array_form {
+                entry[0] = json!(version_id);
+                let secured = suite
+                    .add_proof(
+                        &entry[3]["value"],
+                        &proof_options.clone().with_challenge(&version_id),
+                    )
+                    .unwrap();
+                entry.as_array_mut().unwrap().push(secured["proof"].clone());
+                entry
+            } else {
+                entry["versionId"] = json!(version_id);
+                suite.add_proof(&entry, &proof_options).unwrap()
+            };
+            entries.push(entry.to_string());
+            previous = version_id;
+        }
+        (
+            WEBVH_DID.replace(SCID_PLACEHOLDER, &scid),
+            entries.join("\n"),
+        )
+    }
+
+    #[rstest]
+    #[case(migrating_log(2, METHOD_WEBVH_1_0), Some(2), None)]
+    #[case(migrating_log(3, METHOD_WEBVH_1_0), Some(3), None)]
+    #[case(
+        migrating_log(3, METHOD_WEBVH_1_0),
+        Some(2),
+        Some("is of the JSON array form instead of the JSON object one")
+    )]
+    #[case(
+        migrating_log(2, METHOD_WEBVH_1_0),
+        Some(3),
+        Some("is of the JSON object form instead of the JSON array one")
+    )]
+    #[case(
+        migrating_log(2, "did:tdw:0.3"),
+        Some(2),
+        Some("must switch the 'method' to 'did:webvh:1.0'")
+    )]
+    #[case(
+        migrating_log(2, METHOD_WEBVH_1_0),
+        None,
+        Some("is of the JSON object form instead of the JSON array one")
+    )]
+    #[case(
+        migrating_log_from(METHOD_WEBVH_1_0, 2, METHOD_WEBVH_1_0),
+        Some(2),
+        Some("must be preceded by DID log entries following 'did:tdw:0.3'")
+    )]
+    fn test_verify_and_resolve_migration(
+        #[case] did_log: (String, String),
+        #[case] transition_version: Option<u64>,
+        #[case] error_contains: Option<&str>,
+    ) {
+        let (did, log_jsonl) = did_log;
+        let options = ResolutionOptions {
+            transition_version,
+            ..ResolutionOptions::default()
+        };
+        let res = verify_and_resolve(&did, &log_jsonl, options);
+        match error_contains {
+            None => {
+                let result = res.unwrap();
+                assert_eq!(result.entry_count, 3);
+                assert!(result.version_id.starts_with("3-"));
+            }
+            Some(expected) => {
+                assert_error(res, DidSidekicksErrorKind::InvalidDidDocument, expected)
+            }
+        }
+    }
+
+    #[rstest]
+    fn test_verify_and_resolve_migration_legacy_rules() {
+        // the legacy rules (i.e. the JSON array form only) are broken by the transition entry
+        let (did, log_jsonl) = migrating_log(2, METHOD_WEBVH_1_0);
+        let options = ResolutionOptions {
+            transition_version: Some(2),
+            legacy_validator: Some(Arc::new(DidLogEntryValidator::from(r#"{"type":"array"}"#))),
+            ..ResolutionOptions::default()
+        };
+        assert_error(
+            verify_and_resolve(&did, &log_jsonl, options),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            "line 2: ",
+        );
+
+        // whereas the very same DID log complies with legacy rules admitting the transition entry
+        let options = ResolutionOptions {
+            transition_version: Some(2),
+            legacy_validator: Some(Arc::new(DidLogEntryValidator::from(
+                r#"{"anyOf": [{"type": "array"}, {"type": "object", "properties": {"parameters": {"required": ["method"]}}}]}"#,
+            ))),
+            ..ResolutionOptions::default()
+        };
+        verify_and_resolve(&did, &log_jsonl, options).unwrap();
+    }
+
+    /// Delivers the key pair derived from the supplied (single-byte) `seed`, for the sake of reproducible DID logs.
+    fn key_pair(seed: u8) -> Ed25519KeyPair {
+        Ed25519KeyPair::from_seed(&[seed; 32])
+    }
+
+    /// Delivers the multikeys of the key pairs derived from the supplied `seeds` (see [`key_pair`]), as listed in `updateKeys`.
+    fn update_keys(seeds: &[u8]) -> Vec<String> {
+        seeds
+            .iter()
+            .map(|seed| key_pair(*seed).verifying_key.to_multibase())
+            .collect()
+    }
+
+    /// Delivers the hashes of the keys derived from the supplied `seeds` (see [`key_pair`]), as listed in `nextKeyHashes`.
+    fn next_key_hashes(seeds: &[u8]) -> Vec<String> {
+        update_keys(seeds)
+            .iter()
+            .map(|key| JcsSha256Hasher::default().base58btc_encode_multihash_multikey(key))
+            .collect()
+    }
+
+    /// Delivers a DID along with its DID log (of the JSON object form) featuring a DID log entry per supplied DID method
+    /// `parameters`, each signed by the key pair derived from the supplied seed (see [`key_pair`]).
+    fn webvh_log(entries: &[(Value, u8)]) -> (String, String) {
+        webvh_log_controlled_by(&[], entries)
+    }
+
+    /// The same as [`webvh_log`], but the DID document lists the supplied `controllers` (if any).
+    #[expect(clippy::pattern_type_mismatch, reason = "..")]
+    fn webvh_log_controlled_by(controllers: &[&str], entries: &[(Value, u8)]) -> (String, String) {
+        let mut did_doc = json!({
+            "@context": ["https://www.w3.org/ns/did/v1"],
+            "id": WEBVH_DID,
+            "verificationMethod": [],
+        });
+        if !controllers.is_empty() {
+            did_doc["controller"] = json!(controllers);
+        }
+
+        let mut scid = String::new();
+        let mut previous = SCID_PLACEHOLDER.to_owned();
+        let mut log_entries = Vec::new();
+        for (number, (parameters, signer)) in (1..).zip(entries) {
+            let mut entry = json!({
+                "versionId": previous,
+                "versionTime": VERSION_TIME,
+                "parameters": parameters,
+                "state": did_doc,
+            });
+            if number == 1 {
+                scid = JcsSha256Hasher::default()
+                    .base58btc_encode_multihash(&entry)
+                    .unwrap();
+                entry = serde_json::from_str(&entry.to_string().replace(SCID_PLACEHOLDER, &scid))
+                    .unwrap();
+                did_doc["id"] = json!(WEBVH_DID.replace(SCID_PLACEHOLDER, &scid));
+            }
+            let entry_hash = JcsSha256Hasher::default()
+                .base58btc_encode_multihash(&entry)
+                .unwrap();
+            let version_id = format!("{number}-{entry_hash}");
+            entry["versionId"] = json!(version_id);
+
+            let key_pair = key_pair(*signer);
+            let key = key_pair.verifying_key.to_multibase();
+            let suite = EddsaJcs2022Cryptosuite {
+                verifying_key: Some(key_pair.verifying_key.clone()),
+                signing_key: Some(key_pair.signing_key.clone()),
+                verification_options: VerificationOptions::default(),
+            };
+            let proof_options =
+                CryptoSuiteProofOptions::from_verification_method(&format!("did:key:{key}#{key}"))
+                    .with_created(DateTime::parse_from_rfc3339(VERSION_TIME).unwrap().to_utc())
+                    .with_proof_purpose("assertionMethod");
+            log_entries.push(suite.add_proof(&entry, &proof_options).unwrap().to_string());
+            previous = version_id;
+        }
+        (
+            WEBVH_DID.replace(SCID_PLACEHOLDER, &scid),
+            log_entries.join("\n"),
+        )
+    }
+
+    /// The DID method parameters of the very first DID log entry of a DID log featuring pre-rotation, i.e. the key pair
+    /// derived from the seed 1 is authorized, whereas the one derived from the seed 2 is committed to.
+    fn pre_rotation_genesis() -> Value {
+        json!({
+            "method": METHOD_WEBVH_1_0,
+            "scid": SCID_PLACEHOLDER,
+            "updateKeys": update_keys(&[1]),
+            "nextKeyHashes": next_key_hashes(&[2]),
+        })
+    }
+
+    #[rstest]
+    fn test_verify_and_resolve_pre_rotation() {
+        let (did, log_jsonl) = webvh_log(&[
+            (pre_rotation_genesis(), 1),
+            // each DID log entry is signed by the very key it publishes (as committed to by the previous one)
+            (
+                json!({"updateKeys": update_keys(&[2]), "nextKeyHashes": next_key_hashes(&[3, 4])}),
+                2,
+            ),
+            (
+                json!({"updateKeys": update_keys(&[3, 4]), "nextKeyHashes": next_key_hashes(&[5])}),
+                4,
+            ),
+            // pre-rotation is deactivated, hence the update keys published so far remain in force
+            (
+                json!({"updateKeys": update_keys(&[5]), "nextKeyHashes": []}),
+                5,
+            ),
+            (json!({}), 5),
+        ]);
+
+        let result = verify_and_resolve(&did, &log_jsonl, ResolutionOptions::default()).unwrap();
+        assert_eq!(result.entry_count, 5);
+        assert!(result.version_id.starts_with("5-"));
+    }
+
+    #[rstest]
+    // the previous update key is not in force anymore, once the committed one is published
+    #[case(
+        Some(&[2][..]),
+        1,
+        DidSidekicksErrorKind::KeyNotAuthorized,
+        "not listed in 'updateKeys'"
+    )]
+    // rotating to a key not committed to
+    #[case(
+        Some(&[3][..]),
+        3,
+        DidSidekicksErrorKind::KeyNotAuthorized,
+        "not committed to in 'nextKeyHashes'"
+    )]
+    // a committed key may not install any key not committed to
+    #[case(
+        Some(&[2, 3][..]),
+        2,
+        DidSidekicksErrorKind::KeyNotAuthorized,
+        "not committed to in 'nextKeyHashes'"
+    )]
+    #[case(
+        None,
+        2,
+        DidSidekicksErrorKind::InvalidDidDocument,
+        "features no 'updateKeys'"
+    )]
+    fn test_verify_and_resolve_pre_rotation_invalid(
+        #[case] published_keys: Option<&[u8]>,
+        #[case] signer: u8,
+        #[case] error_kind: DidSidekicksErrorKind,
+        #[case] error_contains: &str,
+    ) {
+        let parameters = published_keys.map_or_else(
+            || json!({}),
+            |seeds| json!({"updateKeys": update_keys(seeds)}),
+        );
+        let (did, log_jsonl) = webvh_log(&[(pre_rotation_genesis(), 1), (parameters, signer)]);
+
+        assert_error(
+            verify_and_resolve(&did, &log_jsonl, ResolutionOptions::default()),
+            error_kind,
+            error_contains,
+        );
+    }
+
+    #[rstest]
+    fn test_verify_and_resolve_pre_rotation_genesis() {
+        // the very first DID log entry activates pre-rotation, whereas it is signed by a key listed in its own updateKeys,
+        // thus passing the key authorization (its P-384 key is not supported by the eddsa-jcs-2022 cryptosuite, though)
+        assert_error(
+            verify_and_resolve(
+                "did:tdw:Qma6mc1qZw3NqxwX6SB5GPQYzP4pGN2nXD15Jwi4bcDBKu:domain.example",
+                include_str!("../test_data/example_did.jsonl"),
+                ResolutionOptions::default(),
+            ),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "",
+        );
+
+        // whereas the very same holds for a DID log of the JSON object form
+        let (did, log_jsonl) = webvh_log(&[(pre_rotation_genesis(), 1)]);
+        verify_and_resolve(&did, &log_jsonl, ResolutionOptions::default()).unwrap();
+    }
+
+    #[rstest]
+    fn test_verify_and_resolve_controller() {
+        /// Resolves any `did:key` DID into a DID document featuring its key as assertion method.
+        struct DidKeyResolver;
+        impl ControllerResolver for DidKeyResolver {
+            fn resolve_controller(&self, did: String) -> Result<Arc<DidDoc>, DidResolverError> {
+                let key = did.trim_start_matches("did:key:");
+                let method = json!({
+                    "id": format!("{did}#{key}"),
+                    "type": "Multikey",
+                    "controller": did,
+                    "publicKeyMultibase": key,
+                });
+                DidDoc::from_json(
+                    &json!({
+                        "@context": ["https://www.w3.org/ns/did/v1"],
+                        "id": did,
+                        "verificationMethod": [method],
+                        "assertionMethod": [method],
+                    })
+                    .to_string(),
+                )
+                .map(Arc::new)
+                .map_err(|err| DidResolverError::InvalidDidDocument(err.to_string()))
+            }
+        }
+
+        let controller = format!("did:key:{}", update_keys(&[9])[0]);
+        let genesis = json!({
+            "method": METHOD_WEBVH_1_0,
+            "scid": SCID_PLACEHOLDER,
+            "updateKeys": update_keys(&[1]),
+        });
+        // the DID log entry is signed by the controller rather than by any key listed in updateKeys
+        let (did, log_jsonl) =
+            webvh_log_controlled_by(&[&controller], &[(genesis.clone(), 1), (json!({}), 9)]);
+        let options = ResolutionOptions {
+            controller_resolver: Some(Arc::new(DidKeyResolver)),
+            ..ResolutionOptions::default()
+        };
+
+        assert_error(
+            verify_and_resolve(&did, &log_jsonl, ResolutionOptions::default()),
+            DidSidekicksErrorKind::KeyNotAuthorized,
+            "not listed in 'updateKeys'",
+        );
+        let result = verify_and_resolve(&did, &log_jsonl, options.clone()).unwrap();
+        assert_eq!(result.entry_count, 2);
+
+        // neither an update key nor a controller
+        let (did, log_jsonl) =
+            webvh_log_controlled_by(&[&controller], &[(genesis, 1), (json!({}), 8)]);
+        assert_error(
+            verify_and_resolve(&did, &log_jsonl, options),
+            DidSidekicksErrorKind::KeyNotAuthorized,
+            "does not refer to any controller",
+        );
+    }
+
+    #[rstest]
+    // the parameters of the very first DID log entry are covered by the SCID
+    #[case(
+        0,
+        r#""deactivated":false"#,
+        r#""deactivated":true"#,
+        "does not match the DID log entry '1-"
+    )]
+    // the parameters (e.g. updateKeys) of a DID log entry of the JSON array form are not covered by its proof
+    #[case(
+        1,
+        r#"{"witnesses":[]"#,
+        r#"{"updateKeys":["z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2"],"witnesses":[]"#,
+        "the entry hash of the DID log entry '2-"
+    )]
+    #[case(
+        2,
+        r#""2012-12-12T12:12:12Z""#,
+        r#""2012-12-12T12:12:13Z""#,
+        "the entry hash of the DID log entry '3-"
+    )]
+    fn test_verify_and_resolve_tampered(
+        #[case] line: usize,
+        #[case] from: &str,
+        #[case] to: &str,
+        #[case] error_contains: &str,
+    ) {
+        let log_jsonl = include_str!("../test_data/generated_by_tdw_js/single_update_key.jsonl")
+            .lines()
+            .enumerate()
+            .map(|(index, entry)| {
+                if index == line {
+                    assert!(entry.contains(from));
+                    entry.replacen(from, to, 1)
+                } else {
+                    entry.to_owned()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert_error(
+            verify_and_resolve(
+                TDW_JS_DID_SINGLE_KEY,
+                &log_jsonl,
+                ResolutionOptions::default(),
+            ),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            error_contains,
+        );
+    }
+
+    #[rstest]
+    #[case(
+        TDW_JS_DID_DEACTIVATED,
+        include_str!("../test_data/generated_by_tdw_js/already_deactivated.jsonl"),
+        DidSidekicksErrorKind::InvalidDidDocument,
+        "already deactivated"
+    )]
+    #[case(
+        TDW_JS_DID_SINGLE_KEY,
+        include_str!("../test_data/generated_by_tdw_js/unhappy_path/not_authorized.jsonl"),
+        DidSidekicksErrorKind::KeyNotAuthorized,
+        ""
+    )]
+    #[case(
+        TDW_JS_DID_SINGLE_KEY,
+        include_str!("../test_data/generated_by_didtoolbox_java/v005_did.jsonl"),
+        DidSidekicksErrorKind::InvalidDidDocument,
+        "instead of"
+    )]
+    #[case(
+        TDW_JS_DID_SINGLE_KEY,
+        "",
+        DidSidekicksErrorKind::InvalidDidDocument,
+        "features no entries"
+    )]
+    fn test_verify_and_resolve_invalid(
+        #[case] did: &str,
+        #[case] log_jsonl: &str,
+        #[case] error_kind: DidSidekicksErrorKind,
+        #[case] error_contains: &str,
+    ) {
+        assert_error(
+            verify_and_resolve(did, log_jsonl, ResolutionOptions::default()),
+            error_kind,
+            error_contains,
+        );
+    }
+}
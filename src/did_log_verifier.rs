@@ -0,0 +1,623 @@
+// SPDX-License-Identifier: MIT
+
+use crate::bounded_json::{from_str_bounded, get_deserialization_limits};
+use crate::did_jsonschema::{DidLogEntryValidator, DidLogEntryValidatorError};
+use crate::errors::DidSidekicksError;
+use crate::panic_boundary::catch_panic;
+use crate::verification_policy::VerificationPolicy;
+use chrono::{DateTime, Utc};
+use core::cmp::Ordering;
+use serde_json::Value;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Instant;
+
+/// The (policy-relevant) facts gathered from the DID log entries verified so far.
+#[derive(Debug, Default, Clone, Copy)]
+struct PolicyProgress {
+    /// The threshold of the currently active `witness` DID method parameter (zero, if witnessing is disabled).
+    witness_threshold: u64,
+    /// The `versionTime` of the latest DID log entry, if any.
+    version_time: Option<DateTime<Utc>>,
+}
+
+/// The migration of a DID log from one specification version to another (see [`DidLogVerifier::with_migration`]).
+#[derive(Debug)]
+struct DidLogMigration {
+    /// The validator of the DID log entries preceding the transition entry.
+    legacy_validator: Arc<DidLogEntryValidator>,
+    /// The number of the `versionId` of the transition entry, i.e. the very first DID log entry under the new rules.
+    transition_version: u64,
+}
+
+/// The internal (resumable) state of a [`DidLogVerifier`].
+#[derive(Debug, Default)]
+struct DidLogVerifierState {
+    /// Bytes of a (still) partial line, i.e. not yet terminated by a newline.
+    ///
+    /// Bounded by the configured maximum size of a single JSON text (see [`crate::bounded_json::DeserializationLimits`]).
+    pending: Vec<u8>,
+    /// All the DID log entries verified so far (in the order of their appearance).
+    verified_entries: Vec<String>,
+    /// The number of lines processed so far (including blank ones).
+    line_count: usize,
+    /// Relevant only if a [`VerificationPolicy`] is supplied.
+    progress: PolicyProgress,
+}
+
+/// A DID log verifier capable of consuming a DID log delivered in chunks (e.g. via HTTP range requests or paginated APIs).
+///
+/// Chunks may be split at arbitrary byte positions (even inside a multi-byte UTF-8 character),
+/// as partial lines are buffered internally until completed by any of subsequent chunks (or [`DidLogVerifier::finish`]).
+/// A partial line exceeding the maximum size of a single JSON text (see [`crate::bounded_json::DeserializationLimits`])
+/// is rejected right away, rather than buffered.
+///
+/// Each complete DID log entry (line) is validated against the [`DidLogEntryValidator`] supplied via constructor.
+/// The verification state is resumable, i.e. feeding may continue right after the chunk
+/// that has been successfully verified, so a failed fetch of a chunk can simply be retried.
+///
+/// Optionally, a [`VerificationPolicy`] is enforced on top, i.e. the cryptosuites of all proofs are checked per DID log entry,
+/// whereas the (finally active) witness threshold and the age of the latest DID log entry are checked by [`DidLogVerifier::finish`].
+///
+/// In migration mode (see [`DidLogVerifier::with_migration`]), a DID log switching from one specification version to another
+/// (e.g. from `did:tdw` v0.3 to `did:webvh` v1.0) at a given `versionId` is supported as well.
+///
+/// A UniFFI-compliant object.
+#[derive(Debug)]
+pub struct DidLogVerifier {
+    validator: Arc<DidLogEntryValidator>,
+    policy: Option<Arc<VerificationPolicy>>,
+    migration: Option<DidLogMigration>,
+    state: Mutex<DidLogVerifierState>,
+}
+
+impl DidLogVerifier {
+    /// Create a new (chunk-wise) DID log verifier relying on the supplied DID log entry `validator`.
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub fn new(validator: Arc<DidLogEntryValidator>) -> Self {
+        Self {
+            validator,
+            policy: None,
+            migration: None,
+            state: Mutex::new(DidLogVerifierState::default()),
+        }
+    }
+
+    /// Create a new (chunk-wise) DID log verifier relying on the supplied DID log entry `validator`,
+    /// while also enforcing the supplied verification `policy`.
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub fn with_policy(
+        validator: Arc<DidLogEntryValidator>,
+        policy: Arc<VerificationPolicy>,
+    ) -> Self {
+        Self {
+            validator,
+            policy: Some(policy),
+            migration: None,
+            state: Mutex::new(DidLogVerifierState::default()),
+        }
+    }
+
+    /// Create a new (chunk-wise) DID log verifier in migration mode, i.e. for a DID log switching from one specification version
+    /// to another at the supplied `transition_version` (i.e. the number of the `versionId` of the transition entry), whereas:
+    /// - any DID log entry preceding the transition entry is validated against the supplied `legacy_validator`,
+    /// - the transition entry is validated against both validators, as it must still comply with the legacy rules
+    ///   (while already following the new ones), so the `legacy_validator` must accept the form of the transition entry and
+    /// - any DID log entry subsequent to the transition entry is validated against the supplied `validator`.
+    ///
+    /// If supplied, the verification `policy` is enforced on top (regardless of the specification version).
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub fn with_migration(
+        legacy_validator: Arc<DidLogEntryValidator>,
+        validator: Arc<DidLogEntryValidator>,
+        transition_version: u64,
+        policy: Option<Arc<VerificationPolicy>>,
+    ) -> Self {
+        Self {
+            validator,
+            policy,
+            migration: Some(DidLogMigration {
+                legacy_validator,
+                transition_version,
+            }),
+            state: Mutex::new(DidLogVerifierState::default()),
+        }
+    }
+
+    /// Feed the verifier with the next `chunk` of a DID log. Any (trailing) partial line is buffered internally.
+    ///
+    /// Returns the number of DID log entries verified while processing the `chunk`.
+    ///
+    /// [`DidLogEntryValidatorError`] is returned as soon the very first invalid DID log entry occurs
+    /// (or the partial line to be buffered exceeds the maximum size of a single JSON text).
+    /// As the maximum duration of a single validation (see [`crate::did_jsonschema::ValidationLimits`]) applies to the whole `chunk`,
+    /// [`DidLogEntryValidatorError::BudgetExceeded`] is returned once it is exceeded between any of its DID log entries.
+    /// In such case, the verifier state remains as it was before the `chunk` has been fed.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn feed_chunk(&self, chunk: Vec<u8>) -> Result<u64, DidLogEntryValidatorError> {
+        catch_panic(
+            || self.process_chunk(&chunk),
+            DidLogEntryValidatorError::InternalError,
+        )
+    }
+
+    fn process_chunk(&self, chunk: &[u8]) -> Result<u64, DidLogEntryValidatorError> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        // the chunk is split (once) right after its last newline, so only the (trailing) partial line is buffered
+        let complete_len = chunk
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |newline_pos| newline_pos.saturating_add(1));
+        let (complete, remainder) = chunk.split_at(complete_len);
+
+        // a partial line is never going to be deserialized, once exceeding the maximum size of a single JSON text
+        let pending_len = if complete.is_empty() {
+            state.pending.len().saturating_add(remainder.len())
+        } else {
+            remainder.len()
+        };
+        let max_size = get_deserialization_limits().max_size;
+        if u64::try_from(pending_len).unwrap_or(u64::MAX) > max_size {
+            return Err(DidLogEntryValidatorError::DeserializationError(format!(
+                "DID log entry at line {} exceeds the maximum size of {max_size} bytes",
+                complete
+                    .iter()
+                    .filter(|byte| **byte == b'\n')
+                    .count()
+                    .saturating_add(state.line_count)
+                    .saturating_add(1)
+            )));
+        }
+
+        // the state is not altered before all the (complete) lines are verified
+        let deadline = self.validator.limits().deadline();
+        let mut entries = Vec::new();
+        let mut line_count = state.line_count;
+        let mut progress = state.progress;
+        for (index, line) in complete.split_inclusive(|byte| *byte == b'\n').enumerate() {
+            line_count = line_count.saturating_add(1);
+            self.check_deadline(deadline, line_count)?;
+            let entry = if index == 0 && !state.pending.is_empty() {
+                // the very first line is preceded by the partial line buffered so far
+                self.verify_line(
+                    &[state.pending.as_slice(), line].concat(),
+                    line_count,
+                    &mut progress,
+                )?
+            } else {
+                self.verify_line(line, line_count, &mut progress)?
+            };
+            if let Some(verified_entry) = entry {
+                entries.push(verified_entry);
+            }
+        }
+
+        let verified = u64::try_from(entries.len()).unwrap_or(u64::MAX);
+        if !complete.is_empty() {
+            state.pending.clear();
+        }
+        state.pending.extend_from_slice(remainder);
+        state.line_count = line_count;
+        state.progress = progress;
+        state.verified_entries.append(&mut entries);
+        drop(state);
+        Ok(verified)
+    }
+
+    /// Signal the end of the DID log, thus verifying any remaining (buffered) line not terminated by a newline.
+    /// If a [`VerificationPolicy`] is supplied, the (finally active) witness threshold and the age of the latest DID log entry are checked.
+    ///
+    /// Returns the total number of DID log entries verified so far.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn finish(&self) -> Result<u64, DidLogEntryValidatorError> {
+        catch_panic(
+            || self.process_remainder(),
+            DidLogEntryValidatorError::InternalError,
+        )
+    }
+
+    fn process_remainder(&self) -> Result<u64, DidLogEntryValidatorError> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if !state.pending.is_empty() {
+            let line_count = state.line_count.saturating_add(1);
+            let mut progress = state.progress;
+            if let Some(entry) = self.verify_line(&state.pending, line_count, &mut progress)? {
+                state.verified_entries.push(entry);
+            }
+            state.pending.clear();
+            state.line_count = line_count;
+            state.progress = progress;
+        }
+
+        if let Some(policy) = self.policy.as_ref() {
+            policy
+                .check_witness_threshold(state.progress.witness_threshold)
+                .and_then(|()| {
+                    state.progress.version_time.map_or(Ok(()), |version_time| {
+                        policy.check_log_age(version_time, Utc::now())
+                    })
+                })
+                .map_err(|err| DidLogEntryValidatorError::ValidationError(err.to_string()))?;
+        }
+
+        Ok(u64::try_from(state.verified_entries.len()).unwrap_or(u64::MAX))
+    }
+
+    /// Delivers all the DID log entries verified so far (in the order of their appearance).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn get_verified_entries(&self) -> Vec<String> {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .verified_entries
+            .clone()
+    }
+
+    /// Delivers the number of bytes (of a partial line) currently buffered.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn get_pending_bytes(&self) -> u64 {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        u64::try_from(state.pending.len()).unwrap_or(u64::MAX)
+    }
+
+    /// Denotes the supplied `deadline` (of a single chunk) being exceeded before the supplied line is verified
+    /// by [`DidLogEntryValidatorError::BudgetExceeded`].
+    fn check_deadline(
+        &self,
+        deadline: Option<Instant>,
+        line_number: usize,
+    ) -> Result<(), DidLogEntryValidatorError> {
+        if deadline.is_some_and(|instant| Instant::now() >= instant) {
+            return Err(DidLogEntryValidatorError::BudgetExceeded(format!(
+                "line {line_number}: more than {} ms spent on verifying a single chunk",
+                self.validator.limits().max_duration_millis
+            )));
+        }
+        Ok(())
+    }
+
+    /// Verifies a single (complete) line. Blank lines are simply skipped.
+    fn verify_line(
+        &self,
+        line: &[u8],
+        line_number: usize,
+        progress: &mut PolicyProgress,
+    ) -> Result<Option<String>, DidLogEntryValidatorError> {
+        let entry = match core::str::from_utf8(line) {
+            Ok(entry) => entry.trim(),
+            Err(err) => {
+                return Err(DidLogEntryValidatorError::DeserializationError(format!(
+                    "DID log entry at line {line_number} is not a valid UTF-8 string: {err}"
+                )))
+            }
+        };
+        if entry.is_empty() {
+            return Ok(None);
+        }
+
+        match self
+            .validators_for(entry)
+            .into_iter()
+            .try_for_each(|validator| validator.validate_str(entry))
+            .and_then(|()| {
+                self.policy.as_ref().map_or(Ok(()), |policy| {
+                    enforce_policy(policy, entry, progress)
+                        .map_err(|err| DidLogEntryValidatorError::ValidationError(err.to_string()))
+                })
+            }) {
+            Ok(()) => Ok(Some(entry.to_owned())),
+            Err(DidLogEntryValidatorError::ValidationError(err)) => Err(
+                DidLogEntryValidatorError::ValidationError(format!("line {line_number}: {err}")),
+            ),
+            Err(DidLogEntryValidatorError::DeserializationError(err)) => {
+                Err(DidLogEntryValidatorError::DeserializationError(format!(
+                    "line {line_number}: {err}"
+                )))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Delivers the validators the supplied DID log `entry` is validated against, i.e. in migration mode:
+    /// - the legacy one in case of a DID log entry preceding the transition entry,
+    /// - both of them in case of the transition entry itself.
+    ///
+    /// Otherwise, the regular one.
+    fn validators_for(&self, entry: &str) -> Vec<&DidLogEntryValidator> {
+        let Some(migration) = self.migration.as_ref() else {
+            return vec![&self.validator];
+        };
+        match version_number(entry).map(|number| number.cmp(&migration.transition_version)) {
+            Some(Ordering::Less) => vec![&migration.legacy_validator],
+            Some(Ordering::Equal) => vec![&self.validator, &migration.legacy_validator],
+            Some(Ordering::Greater) | None => vec![&self.validator],
+        }
+    }
+}
+
+/// Delivers the number of the `versionId` of the supplied DID log `entry` (of either form), if any.
+#[expect(clippy::pattern_type_mismatch, reason = "..")]
+#[expect(clippy::single_call_fn, reason = "..")]
+fn version_number(entry: &str) -> Option<u64> {
+    let json: Value = from_str_bounded(entry).ok()?;
+    let version_id = match &json {
+        Value::Array(items) => items.first(),
+        Value::Object(members) => members.get("versionId"),
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => None,
+    };
+    let (number, _) = version_id.and_then(Value::as_str)?.split_once('-')?;
+    number.parse().ok()
+}
+
+/// Enforces the supplied `policy` on a single (already validated) DID log `entry`, while updating the `progress` accordingly.
+///
+/// Both DID log entry forms are supported, i.e. the JSON object (`parameters`, `versionTime`, `proof`)
+/// as well as the JSON array (`[versionId, versionTime, parameters, state, proof]`) one.
+#[expect(clippy::pattern_type_mismatch, reason = "..")]
+#[expect(clippy::single_call_fn, reason = "..")]
+fn enforce_policy(
+    policy: &VerificationPolicy,
+    entry: &str,
+    progress: &mut PolicyProgress,
+) -> Result<(), DidSidekicksError> {
+    let json: Value = from_str_bounded(entry)?;
+    let (version_time, parameters, proofs) = match &json {
+        Value::Array(items) => (items.get(1), items.get(2), items.get(4)),
+        Value::Object(members) => (
+            members.get("versionTime"),
+            members.get("parameters"),
+            members.get("proof"),
+        ),
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => (None, None, None),
+    };
+
+    if let Some(entry_proofs) = proofs {
+        policy.check_proofs(entry_proofs)?;
+    }
+    // DID method parameters are featured only if changed, so the active witness threshold remains as is otherwise
+    if let Some(witness) = parameters.and_then(|changed| changed.get("witness")) {
+        progress.witness_threshold = witness
+            .get("threshold")
+            .and_then(Value::as_u64)
+            .unwrap_or_default();
+    }
+    if let Some(text) = version_time.and_then(Value::as_str) {
+        let parsed = DateTime::parse_from_rfc3339(text).map_err(|err| {
+            DidSidekicksError::PolicyViolation(format!(
+                "the versionTime '{text}' is not a valid RFC 3339 datetime: {err}"
+            ))
+        })?;
+        progress.version_time = Some(parsed.with_timezone(&Utc));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::DidLogVerifier;
+    use crate::bounded_json::DeserializationLimits;
+    use crate::did_jsonschema::{
+        DidLogEntryJsonSchema, DidLogEntryValidator, DidLogEntryValidatorErrorKind,
+        ValidationLimits,
+    };
+    use crate::verification_policy::VerificationPolicy;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    const DID_LOG: &str = include_str!("../test_data/generated_by_didtoolbox_java/v_0_3_eid_conform/did_doc_without_controller.jsonl");
+
+    fn verifier() -> DidLogVerifier {
+        DidLogVerifier::new(Arc::new(DidLogEntryValidator::from(
+            r#"{"type":["array","object"]}"#,
+        )))
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(7)]
+    #[case(64)]
+    #[case(DID_LOG.len())]
+    fn test_feed_chunks(#[case] chunk_size: usize) {
+        let did_log_verifier = verifier();
+        for chunk in DID_LOG.as_bytes().chunks(chunk_size) {
+            did_log_verifier.feed_chunk(chunk.to_vec()).unwrap();
+        }
+        did_log_verifier.finish().unwrap();
+
+        let expected: Vec<String> = DID_LOG
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_owned)
+            .collect();
+        assert_eq!(did_log_verifier.get_verified_entries(), expected);
+        assert_eq!(did_log_verifier.get_pending_bytes(), 0);
+    }
+
+    #[rstest]
+    fn test_feed_chunk_is_resumable_after_failure() {
+        let did_log_verifier = verifier();
+        assert_eq!(did_log_verifier.feed_chunk(b"[1]\n[2".to_vec()).unwrap(), 1);
+        assert_eq!(did_log_verifier.get_pending_bytes(), 2);
+
+        let err = did_log_verifier.feed_chunk(b"]\n\"invalid\"\n".to_vec());
+        assert_eq!(
+            err.unwrap_err().kind(),
+            DidLogEntryValidatorErrorKind::ValidationError
+        );
+        // state remains untouched
+        assert_eq!(
+            did_log_verifier.get_verified_entries(),
+            vec!["[1]".to_owned()]
+        );
+        assert_eq!(did_log_verifier.get_pending_bytes(), 2);
+
+        assert_eq!(did_log_verifier.feed_chunk(b"]\n{}".to_vec()).unwrap(), 1);
+        assert_eq!(did_log_verifier.finish().unwrap(), 3);
+    }
+
+    #[rstest]
+    fn test_feed_chunk_pending_bytes_are_bounded() {
+        let max_size = DeserializationLimits::DEFAULT.max_size;
+        let did_log_verifier = verifier();
+        let partial = vec![b' '; usize::try_from(max_size).unwrap()];
+        assert_eq!(did_log_verifier.feed_chunk(partial).unwrap(), 0);
+        assert_eq!(did_log_verifier.get_pending_bytes(), max_size);
+
+        let err = did_log_verifier.feed_chunk(b" ".to_vec()).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            DidLogEntryValidatorErrorKind::DeserializationError
+        );
+        assert!(err.to_string().contains(&format!(
+            "line 1 exceeds the maximum size of {max_size} bytes"
+        )));
+        // state remains untouched
+        assert_eq!(did_log_verifier.get_pending_bytes(), max_size);
+
+        // completing the (blank) partial line
+        assert_eq!(did_log_verifier.feed_chunk(b"\n[1]".to_vec()).unwrap(), 0);
+        assert_eq!(did_log_verifier.finish().unwrap(), 1);
+    }
+
+    #[rstest]
+    fn test_feed_chunk_within_deadline() {
+        struct StaticSchema(&'static str);
+        #[expect(clippy::missing_trait_methods, reason = "..")]
+        impl DidLogEntryJsonSchema for StaticSchema {
+            fn get_json_schema(&self) -> String {
+                self.0.to_owned()
+            }
+        }
+
+        let validator = DidLogEntryValidator::try_from_schema_with_limits(
+            Arc::new(StaticSchema(r#"{"type":["array","object"]}"#)),
+            ValidationLimits {
+                max_duration_millis: 0,
+                ..ValidationLimits::DEFAULT
+            },
+        )
+        .unwrap();
+        let did_log_verifier = DidLogVerifier::new(Arc::new(validator));
+
+        let err = did_log_verifier.feed_chunk(b"[1]\n[2]".to_vec()).unwrap_err();
+        assert_eq!(err.kind(), DidLogEntryValidatorErrorKind::BudgetExceeded);
+        assert!(err.to_string().contains("line 1: more than 0 ms"));
+        // state remains untouched
+        assert!(did_log_verifier.get_verified_entries().is_empty());
+        assert_eq!(did_log_verifier.get_pending_bytes(), 0);
+    }
+
+    #[rstest]
+    fn test_finish_with_malformed_trailing_line() {
+        let did_log_verifier = verifier();
+        did_log_verifier.feed_chunk(b"[1]\n[2".to_vec()).unwrap();
+        assert_eq!(
+            did_log_verifier.finish().unwrap_err().kind(),
+            DidLogEntryValidatorErrorKind::DeserializationError
+        );
+    }
+
+    #[rstest]
+    #[case("{}", None)]
+    #[case(r#"{"allowedCryptosuites": ["eddsa-jcs-2022"]}"#, None)]
+    #[case(r#"{"allowedCryptosuites": ["ecdsa-rdfc-2019"]}"#, Some("line 1: "))]
+    #[case(
+        r#"{"minWitnessThreshold": 1}"#,
+        Some("the witness threshold 0 is below the required 1")
+    )]
+    #[case(r#"{"maxLogAgeSeconds": 60}"#, Some("is older than 60 seconds"))]
+    fn test_with_policy(#[case] policy_json: &str, #[case] expected_err: Option<&str>) {
+        let did_log_verifier = DidLogVerifier::with_policy(
+            Arc::new(DidLogEntryValidator::from(r#"{"type":["array","object"]}"#)),
+            Arc::new(VerificationPolicy::from_json(policy_json).unwrap()),
+        );
+        let res = did_log_verifier
+            .feed_chunk(DID_LOG.as_bytes().to_vec())
+            .and_then(|_| did_log_verifier.finish());
+        match expected_err {
+            None => assert!(res.is_ok()),
+            Some(expected) => {
+                let err = res.unwrap_err();
+                assert_eq!(err.kind(), DidLogEntryValidatorErrorKind::ValidationError);
+                assert!(err.to_string().contains(expected), "{err}");
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(
+        b"[\"1-QmA\"]\n{\"versionId\": \"2-QmB\"}\n{\"versionId\": \"3-QmC\"}\n",
+        None
+    )]
+    #[case(b"[\"1-QmA\"]\n", None)]
+    #[case(b"[\"1-QmA\"]\n[\"2-QmB\"]\n", Some("line 2: "))]
+    #[case(b"{\"versionId\": \"1-QmA\", \"state\": {}}\n", Some("line 1: "))]
+    // the transition entry must comply with the legacy rules as well
+    #[case(
+        b"[\"1-QmA\"]\n{\"versionId\": \"2-QmB\", \"parameters\": {}}\n",
+        Some("line 2: ")
+    )]
+    #[case(
+        b"[\"1-QmA\"]\n{\"versionId\": \"2-QmB\"}\n{\"versionId\": \"3-QmC\", \"parameters\": {}}\n",
+        None
+    )]
+    fn test_with_migration(#[case] did_log: &[u8], #[case] expected_err: Option<&str>) {
+        // the legacy entries are arrays, whereas the transition entry (2) and any subsequent one are objects,
+        // while the legacy rules admit the transition entry as long as it features the versionId only
+        let did_log_verifier = DidLogVerifier::with_migration(
+            Arc::new(DidLogEntryValidator::from(
+                r#"{"anyOf": [{"type": "array"}, {"type": "object", "maxProperties": 1}]}"#,
+            )),
+            Arc::new(DidLogEntryValidator::from(r#"{"type":"object"}"#)),
+            2,
+            None,
+        );
+        let res = did_log_verifier
+            .feed_chunk(did_log.to_vec())
+            .and_then(|_| did_log_verifier.finish());
+        match expected_err {
+            None => assert!(res.is_ok()),
+            Some(expected) => {
+                let err = res.unwrap_err();
+                assert_eq!(err.kind(), DidLogEntryValidatorErrorKind::ValidationError);
+                assert!(err.to_string().contains(expected), "{err}");
+            }
+        }
+    }
+
+    #[rstest]
+    fn test_with_policy_witness_threshold() {
+        let did_log_verifier = DidLogVerifier::with_policy(
+            Arc::new(DidLogEntryValidator::from(r#"{"type":["array","object"]}"#)),
+            Arc::new(VerificationPolicy::from_json(r#"{"minWitnessThreshold": 2}"#).unwrap()),
+        );
+        did_log_verifier
+            .feed_chunk(br#"{"parameters": {"witness": {"threshold": 2}}}"#.to_vec())
+            .unwrap();
+        did_log_verifier
+            .feed_chunk(b"\n{\"parameters\": {}}\n".to_vec())
+            .unwrap();
+        // the threshold remains active, as long as not changed
+        assert_eq!(did_log_verifier.finish().unwrap(), 2);
+    }
+}
@@ -1,15 +1,27 @@
 // SPDX-License-Identifier: MIT
 
+use crate::bounded_json::from_str_bounded;
 use crate::errors::DidSidekicksError;
-use serde_json::{from_str as json_from_str, to_string as json_to_string, Value};
+use crate::json_emission::{emit_json, EmitOptions};
+use crate::panic_boundary::catch_panic;
+use core::hash::{Hash, Hasher};
+use serde_json::Value;
+use serde_json_canonicalizer::to_string as jcs_to_string;
+use std::sync::Arc;
 
 /// A generic DID method parameter as seen from the perspective of a JSON deserializer.
 ///
 /// The value returned by [`DidMethodParameter::get_json_text`] is guaranteed to be deserializable back into a JSON object.
+///
+/// Two parameters are equal (and feature the same hash) if they are equally named and their values are equal
+/// once canonicalized (as specified by https://www.rfc-editor.org/rfc/rfc8785), regardless of their JSON text,
+/// e.g. `1` and `1.0` or `{"a":1,"b":2}` and `{"b":2,"a":1}` are considered equal.
 #[derive(Debug, Clone)]
 pub struct DidMethodParameter {
     name: String,
     json_text: String,
+    /// The canonical (JCS) form of `json_text`, serving as the basis for equality and hashing.
+    canonical_json_text: String,
     is_bool: bool,
     is_string: bool,
     is_f64: bool,
@@ -29,78 +41,92 @@ pub struct DidMethodParameter {
 }
 
 impl DidMethodParameter {
+    /// The preferred constructor of the type, superseding the `new_*` ones, whereas the supplied `value`
+    /// may be anything convertible into a JSON value, e.g. `true`, `"did:key:z6Mk..."`, `vec!["z6Mk..."]` or `3_u64`.
+    ///
+    /// Fails if the `name` is empty.
+    #[inline]
+    pub fn try_from_value<V: Into<Value>>(name: &str, value: V) -> Result<Self, DidSidekicksError> {
+        Self::new(name, value.into().to_string())
+    }
+
     /// Yet another non-empty constructor of the type.
+    ///
+    /// Fails if the `name` is empty.
     #[inline]
-    // TODO Ensure panic-safety
-    #[expect(clippy::unwrap_used, reason = "see TODO")]
-    pub fn new_bool(name: &str, value: bool) -> Self {
-        Self::new(name, value.to_string()).unwrap()
+    #[deprecated(note = "use `DidMethodParameter::try_from_value` instead")]
+    pub fn new_bool(name: &str, value: bool) -> Result<Self, DidSidekicksError> {
+        Self::try_from_value(name, value)
     }
 
     /// Yet another non-empty constructor of the type.
+    ///
+    /// Fails if the `name` is empty.
     #[inline]
-    pub fn new_bool_from_option(name: &str, value: Option<bool>) -> Self {
-        if let Some(val) = value {
-            return Self::new_bool(name, val);
-        }
-        Self::new_false(name) // fallback for an optional param
+    #[deprecated(note = "use `DidMethodParameter::try_from_value` instead")]
+    pub fn new_bool_from_option(
+        name: &str,
+        value: Option<bool>,
+    ) -> Result<Self, DidSidekicksError> {
+        // fallback to false for an optional param
+        Self::try_from_value(name, value.unwrap_or(false))
     }
 
     /// Yet another non-empty constructor of the type.
+    ///
+    /// Fails if the `name` is empty.
     #[inline]
-    #[expect(clippy::unwrap_used, reason = "panic-safe unwrap call")]
-    pub fn new_true(name: &str) -> Self {
-        // panic-safe unwrap call
-        Self::new(name, "true".to_owned()).unwrap()
+    #[deprecated(note = "use `DidMethodParameter::try_from_value` instead")]
+    pub fn new_true(name: &str) -> Result<Self, DidSidekicksError> {
+        Self::try_from_value(name, true)
     }
 
     /// Yet another non-empty constructor of the type.
+    ///
+    /// Fails if the `name` is empty.
     #[inline]
-    #[expect(clippy::unwrap_used, reason = "panic-safe unwrap call")]
-    pub fn new_false(name: &str) -> Self {
-        // panic-safe unwrap call
-        Self::new(name, "false".to_owned()).unwrap()
+    #[deprecated(note = "use `DidMethodParameter::try_from_value` instead")]
+    pub fn new_false(name: &str) -> Result<Self, DidSidekicksError> {
+        Self::try_from_value(name, false)
     }
 
     /// Yet another non-empty constructor of the type.
+    ///
+    /// CAUTION The supplied `value` is taken verbatim (i.e. unescaped) as JSON string content.
     #[inline]
+    #[deprecated(
+        note = "use `DidMethodParameter::try_from_value` instead (which escapes the value properly)"
+    )]
     pub fn new_string(name: &str, value: String) -> Result<Self, DidSidekicksError> {
         Self::new(name, format!("\"{value}\""))
     }
 
     /// Yet another non-empty constructor of the type.
     #[inline]
+    #[deprecated(note = "use `DidMethodParameter::try_from_value` instead")]
     pub fn new_string_from_option(
         name: &str,
         value: Option<String>,
     ) -> Result<Self, DidSidekicksError> {
         if let Some(val) = value {
-            return Self::new_string(name, val);
+            return Self::new(name, format!("\"{val}\""));
         }
-        Err(DidSidekicksError::InvalidDidMethodParameter(format!(
-            "DID method parameter omitted: {name}"
-        )))
+        Err(omitted(name))
     }
 
     /// Yet another non-empty constructor of the type.
     #[inline]
-    #[expect(clippy::unwrap_in_result, reason = "panic-safe unwrap call")]
-    #[expect(clippy::unwrap_used, reason = "panic-safe unwrap call")]
+    #[deprecated(note = "use `DidMethodParameter::try_from_value` instead")]
     pub fn new_string_array_from_option(
         name: &str,
         value: Option<Vec<String>>,
     ) -> Result<Self, DidSidekicksError> {
-        if let Some(val) = value {
-            // panic-safe unwrap call
-            return Self::new(name, json_to_string(&val).unwrap());
-        }
-        Err(DidSidekicksError::InvalidDidMethodParameter(format!(
-            "DID method parameter omitted: {name}"
-        )))
+        value.map_or_else(|| Err(omitted(name)), |val| Self::try_from_value(name, val))
     }
 
     /// Yet another non-empty constructor of the type.
     #[inline]
+    #[deprecated(note = "use `DidMethodParameter::try_from_value` instead")]
     pub fn new_number_from_option(
         name: &str,
         value: Option<usize>,
@@ -108,15 +134,25 @@ impl DidMethodParameter {
         if let Some(size) = value {
             return Self::new(name, size.to_string());
         }
-        Err(DidSidekicksError::InvalidDidMethodParameter(format!(
-            "DID method parameter omitted: {name}"
-        )))
+        Err(omitted(name))
+    }
+
+    /// Yet another constructor of the type, extracting an arbitrary nested value from the supplied `json`
+    /// by means of a JSON pointer (as specified by https://www.rfc-editor.org/rfc/rfc6901).
+    ///
+    /// The `pointer` itself is used as name of the parameter (`#` in case of the whole document, i.e. empty `pointer`).
+    ///
+    /// Returns `None` if the `pointer` is malformed or refers to a non-existing value.
+    #[inline]
+    pub fn from_json_pointer(json: &Value, pointer: &str) -> Option<Self> {
+        let value = json.pointer(pointer)?;
+        let name = if pointer.is_empty() { "#" } else { pointer };
+        Self::new(name, value.to_string()).ok()
     }
 
     /// The only non-empty constructor of the type.
     ///
     /// The supplied string of JSON text (`json_text`) must be deserializable into a JSON object.
-    #[expect(clippy::unwrap_in_result, reason = "..")]
     #[expect(
         clippy::unwrap_used,
         reason = "panic-safe unwrap call, see comments below"
@@ -131,6 +167,7 @@ impl DidMethodParameter {
         let mut param = Self {
             name: name.to_owned(),
             json_text: json_text.clone(),
+            canonical_json_text: String::new(),
             is_bool: false,
             is_string: false,
             is_f64: false,
@@ -149,7 +186,16 @@ impl DidMethodParameter {
             u64_value: None,
         };
 
-        match json_from_str::<Value>(json_text.as_str()) {
+        let parsed = from_str_bounded::<Value>(json_text.as_str());
+        if let Ok(value) = parsed.as_ref() {
+            param.canonical_json_text = jcs_to_string(value).map_err(|err| {
+                DidSidekicksError::InvalidDidMethodParameter(format!(
+                    "'{json_text}' denoting the DID method parameter '{name}' cannot be canonicalized: {err}"
+                ))
+            })?;
+        }
+
+        match parsed {
             Ok(Value::Bool(entry)) => {
                 param.is_bool = true;
                 param.bool_value = Some(entry);
@@ -201,7 +247,8 @@ impl DidMethodParameter {
             }
             Err(err) => {
                 return Err(DidSidekicksError::InvalidDidMethodParameter(format!(
-                    "'{json_text}' denoting the DID method parameter '{name}' is not a valid JSON text: {err}"
+                    "'{json_text}' denoting the DID method parameter '{name}' is not a valid JSON text: {}",
+                    err.detail()
                 )))
             }
         };
@@ -223,6 +270,29 @@ impl DidMethodParameter {
         self.json_text.clone()
     }
 
+    /// Delivers an equal copy of this DID method parameter, whose JSON text is canonicalized
+    /// (as specified by https://www.rfc-editor.org/rfc/rfc8785), e.g. `1.0` becomes `1`.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn normalized(&self) -> Arc<Self> {
+        Arc::new(
+            Self::new(&self.name, self.canonical_json_text.clone())
+                .unwrap_or_else(|_| self.clone()),
+        )
+    }
+
+    /// Emits the value of this DID method parameter as JSON text according to the supplied `options`.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn to_json(&self, options: EmitOptions) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || emit_json(&from_str_bounded::<Value>(&self.json_text)?, options),
+            DidSidekicksError::InternalError,
+        )
+    }
+
     /// A UniFFI-compliant getter.
     #[inline]
     pub const fn is_bool(&self) -> bool {
@@ -361,3 +431,91 @@ impl DidMethodParameter {
         None
     }
 }
+
+#[expect(clippy::missing_trait_methods, reason = "..")]
+impl PartialEq for DidMethodParameter {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.canonical_json_text == other.canonical_json_text
+    }
+}
+
+#[expect(clippy::missing_trait_methods, reason = "..")]
+impl Eq for DidMethodParameter {}
+
+#[expect(clippy::missing_trait_methods, reason = "..")]
+impl Hash for DidMethodParameter {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.canonical_json_text.hash(state);
+    }
+}
+
+/// The error denoting an omitted (i.e. `None`) DID method parameter.
+fn omitted(name: &str) -> DidSidekicksError {
+    DidSidekicksError::InvalidDidMethodParameter(format!("DID method parameter omitted: {name}"))
+}
+
+/// The helper parses the supplied JSON text (e.g. a DID log entry or any secured document).
+///
+/// Afterwards, it extracts an arbitrary nested value by means of a JSON pointer (as specified by https://www.rfc-editor.org/rfc/rfc6901).
+///
+/// Returns `None` if the `json_text` cannot be parsed or the `pointer` refers to a non-existing value.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn get_by_json_pointer(json_text: String, pointer: String) -> Option<Arc<DidMethodParameter>> {
+    let json = from_str_bounded::<Value>(&json_text).ok()?;
+    DidMethodParameter::from_json_pointer(&json, &pointer).map(Arc::new)
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(clippy::shadow_unrelated, reason = "..")]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_try_from_value() {
+        let param = DidMethodParameter::try_from_value("portable", true).unwrap();
+        assert_eq!(param.get_name(), "portable");
+        assert_eq!(param.get_bool_value(), Some(true));
+
+        let param = DidMethodParameter::try_from_value("method", "did:webvh:1.0").unwrap();
+        assert_eq!(param.get_string_value(), Some("did:webvh:1.0".to_owned()));
+
+        // properly escaped, as opposed to the deprecated `DidMethodParameter::new_string`
+        let param = DidMethodParameter::try_from_value("quoted", "a\"b").unwrap();
+        assert_eq!(param.get_json_text(), r#""a\"b""#);
+        assert_eq!(param.get_string_value(), Some("a\"b".to_owned()));
+
+        let param = DidMethodParameter::try_from_value("updateKeys", vec!["z6Mk"]).unwrap();
+        assert_eq!(
+            param.get_string_array_value(),
+            Some(vec!["z6Mk".to_owned()])
+        );
+
+        let param = DidMethodParameter::try_from_value::<u64>("ttl", 3600).unwrap();
+        assert_eq!(param.get_i64_value(), Some(3600));
+
+        // equal once canonicalized
+        let ttl: f64 = 3600.0;
+        assert_eq!(
+            param,
+            DidMethodParameter::try_from_value("ttl", ttl).unwrap()
+        );
+
+        assert_error(
+            DidMethodParameter::try_from_value("", true),
+            DidSidekicksErrorKind::InvalidDidMethodParameter,
+            "must be properly named",
+        );
+    }
+}
@@ -0,0 +1,375 @@
+// SPDX-License-Identifier: MIT
+
+//! The `did:webvh` identifier grammar, as specified by https://identity.foundation/didwebvh/v1.0/#method-specific-identifier:
+//!
+//! `did:webvh:<SCID>:<host>[%3A<port>][:<path segment>]*`, e.g. `did:webvh:QmHash:example.com%3A8080:path1:path2`.
+//!
+//! i.e. the port is separated by a percent-encoded colon, whereas each `/` of the path is replaced by `:`.
+//! Any other character of a path segment not allowed by https://www.rfc-editor.org/rfc/rfc3986#section-3.3 (including `:` and `%`)
+//! is percent-encoded, so that any DID composed here is valid and maps back to the very same HTTPS URL.
+
+use crate::errors::DidResolverError;
+use core::fmt;
+use core::str::FromStr;
+
+/// The prefix of any `did:webvh` DID.
+pub const DID_WEBVH_PREFIX: &str = "did:webvh:";
+
+/// The (percent-encoded) separator between host and port.
+const PORT_SEPARATOR: &str = "%3A";
+
+/// The maximum length of a host name, as specified by https://www.rfc-editor.org/rfc/rfc1035#section-2.3.4.
+const MAX_HOST_LENGTH: usize = 253;
+
+/// The file name of a DID log.
+const DID_LOG_FILE_NAME: &str = "did.jsonl";
+
+/// A (validated) `did:webvh` DID, decomposed into its parts.
+///
+/// (De)composed via [`WebVhDid::compose`] and [`WebVhDid::decompose`] (or [`FromStr`]), whereas [`fmt::Display`] delivers the DID itself.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WebVhDid {
+    scid: String,
+    host: String,
+    port: Option<u16>,
+    /// The (percent-decoded) path segments.
+    path: Vec<String>,
+}
+
+impl WebVhDid {
+    /// Composes a `did:webvh` DID out of the supplied `scid`, `domain` (a host name, optionally followed by `:<port>`)
+    /// and (URL) `path` (e.g. `path1/path2`, may be empty), thus encoding both port and path as required.
+    ///
+    /// Any invalid part is denoted by [`DidResolverError::InvalidMethodSpecificId`].
+    #[inline]
+    pub fn compose(scid: &str, domain: &str, path: &str) -> Result<String, DidResolverError> {
+        let (host, port) = match domain.rsplit_once(':') {
+            Some((host, port)) => (host, Some(parse_port(port)?)),
+            None => (domain, None),
+        };
+        let trimmed_path = path.trim_matches('/');
+        let segments = if trimmed_path.is_empty() {
+            Vec::new()
+        } else {
+            trimmed_path.split('/').map(str::to_owned).collect()
+        };
+        Self::new(scid, host, port, segments).map(|did| did.to_string())
+    }
+
+    /// Decomposes the supplied `did:webvh` DID into its parts.
+    ///
+    /// Any violation of the grammar is denoted by [`DidResolverError::InvalidMethodSpecificId`].
+    #[inline]
+    pub fn decompose(did: &str) -> Result<Self, DidResolverError> {
+        let method_specific_id = did.strip_prefix(DID_WEBVH_PREFIX).ok_or_else(|| {
+            invalid(format!(
+                "'{did}' is not a DID featuring the '{DID_WEBVH_PREFIX}' prefix"
+            ))
+        })?;
+        let mut parts = method_specific_id.split(':');
+        let scid = parts.next().unwrap_or_default();
+        let domain = parts
+            .next()
+            .ok_or_else(|| invalid(format!("'{did}' features no domain")))?;
+
+        // the port separator is case-insensitive, as is any percent-encoding
+        let (host, port) = match domain.to_ascii_uppercase().find(PORT_SEPARATOR) {
+            Some(pos) => (
+                domain.get(..pos).unwrap_or_default(),
+                Some(parse_port(
+                    domain
+                        .get(pos.saturating_add(PORT_SEPARATOR.len())..)
+                        .unwrap_or_default(),
+                )?),
+            ),
+            None => (domain, None),
+        };
+        let segments = parts.map(percent_decode).collect::<Result<Vec<_>, _>>()?;
+        Self::new(scid, host, port, segments)
+    }
+
+    fn new(
+        scid: &str,
+        host: &str,
+        port: Option<u16>,
+        path: Vec<String>,
+    ) -> Result<Self, DidResolverError> {
+        if scid.is_empty() || !scid.chars().all(|ch| ch.is_ascii_alphanumeric()) {
+            return Err(invalid(format!(
+                "the SCID '{scid}' must be a non-empty (base58btc-encoded) multihash"
+            )));
+        }
+        validate_host(host)?;
+        for segment in &path {
+            if segment.is_empty() || segment == "." || segment == ".." {
+                return Err(invalid(format!(
+                    "the path segment '{segment}' is not allowed"
+                )));
+            }
+        }
+        Ok(Self {
+            scid: scid.to_owned(),
+            host: host.to_ascii_lowercase(),
+            port,
+            path,
+        })
+    }
+
+    /// The self-certifying identifier (SCID).
+    #[inline]
+    pub fn scid(&self) -> &str {
+        &self.scid
+    }
+
+    /// The host name (lowercase).
+    #[inline]
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// The port, if any.
+    #[inline]
+    pub const fn port(&self) -> Option<u16> {
+        self.port
+    }
+
+    /// The (percent-decoded) path segments, if any.
+    #[inline]
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// The domain, i.e. the host name optionally followed by `:<port>`.
+    #[inline]
+    pub fn domain(&self) -> String {
+        self.port.map_or_else(
+            || self.host.to_owned(),
+            |port| format!("{}:{port}", self.host),
+        )
+    }
+
+    /// The HTTPS URL of the DID log, as specified by https://identity.foundation/didwebvh/v1.0/#the-did-to-https-transformation
+    /// e.g. `https://example.com/.well-known/did.jsonl` or `https://example.com:8080/path1/path2/did.jsonl`.
+    #[inline]
+    pub fn did_log_url(&self) -> String {
+        let path = if self.path.is_empty() {
+            ".well-known".to_owned()
+        } else {
+            self.path
+                .iter()
+                .map(|segment| percent_encode(segment))
+                .collect::<Vec<_>>()
+                .join("/")
+        };
+        format!("https://{}/{path}/{DID_LOG_FILE_NAME}", self.domain())
+    }
+}
+
+impl fmt::Display for WebVhDid {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{DID_WEBVH_PREFIX}{}:{}", self.scid, self.host)?;
+        if let Some(port) = self.port {
+            write!(f, "{PORT_SEPARATOR}{port}")?;
+        }
+        for segment in &self.path {
+            write!(f, ":{}", percent_encode(segment))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for WebVhDid {
+    type Err = DidResolverError;
+
+    #[inline]
+    fn from_str(did: &str) -> Result<Self, Self::Err> {
+        Self::decompose(did)
+    }
+}
+
+fn invalid(detail: String) -> DidResolverError {
+    DidResolverError::InvalidMethodSpecificId(detail)
+}
+
+fn parse_port(port: &str) -> Result<u16, DidResolverError> {
+    port.parse::<u16>()
+        .ok()
+        .filter(|&number| number > 0 && port.chars().all(|ch| ch.is_ascii_digit()))
+        .ok_or_else(|| {
+            invalid(format!(
+                "the port '{port}' must be a number between 1 and 65535"
+            ))
+        })
+}
+
+/// Validates the supplied `host` name, i.e. dot-separated labels of ASCII letters, digits and hyphens (neither leading nor trailing).
+///
+/// Internationalized domain names are expected in their ASCII-compatible (punycode) form.
+#[expect(clippy::single_call_fn, reason = "..")]
+fn validate_host(host: &str) -> Result<(), DidResolverError> {
+    let valid = !host.is_empty()
+        && host.len() <= MAX_HOST_LENGTH
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+        });
+    if !valid {
+        return Err(invalid(format!("the host name '{host}' is invalid")));
+    }
+    Ok(())
+}
+
+/// Whether the supplied `ch` may appear unencoded in a path segment (of a DID), i.e. `pchar` (https://www.rfc-editor.org/rfc/rfc3986#section-3.3)
+/// excluding `:` (the segment separator) and `%` (always denoting a percent-encoding).
+fn is_path_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || "-._~!$&'()*+,;=@".contains(ch)
+}
+
+fn percent_encode(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for ch in segment.chars() {
+        if is_path_char(ch) {
+            encoded.push(ch);
+        } else {
+            let mut buf: [u8; 4] = [0; 4];
+            for byte in ch.encode_utf8(&mut buf).bytes() {
+                encoded.push('%');
+                encoded.push_str(&hex::encode_upper([byte]));
+            }
+        }
+    }
+    encoded
+}
+
+#[expect(clippy::single_call_fn, reason = "..")]
+fn percent_decode(segment: &str) -> Result<String, DidResolverError> {
+    let malformed = || invalid(format!("the path segment '{segment}' is malformed"));
+    let mut bytes = Vec::with_capacity(segment.len());
+    let mut chars = segment.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '%' {
+            let hex = chars.by_ref().take(2).collect::<String>();
+            if hex.len() != 2 {
+                return Err(malformed());
+            }
+            bytes.push(u8::from_str_radix(&hex, 16).ok().ok_or_else(malformed)?);
+        } else if is_path_char(ch) {
+            let mut buf: [u8; 4] = [0; 4];
+            bytes.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+        } else {
+            return Err(malformed());
+        }
+    }
+    String::from_utf8(bytes).ok().ok_or_else(malformed)
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidResolverErrorKind;
+    use rstest::rstest;
+
+    const SCID: &str = "QmZf4Pb1GoPdYaZBF3Sc1nVspXef4qc816C7eBzzuXMoGk";
+
+    #[rstest]
+    #[case(
+        "example.com",
+        "",
+        "did:webvh:{SCID}:example.com",
+        "https://example.com/.well-known/did.jsonl"
+    )]
+    #[case(
+        "Example.COM",
+        "/",
+        "did:webvh:{SCID}:example.com",
+        "https://example.com/.well-known/did.jsonl"
+    )]
+    #[case(
+        "domain.com:8080",
+        "path1/path2",
+        "did:webvh:{SCID}:domain.com%3A8080:path1:path2",
+        "https://domain.com:8080/path1/path2/did.jsonl"
+    )]
+    #[case(
+        "example.com",
+        "/a b/c:d/100%/",
+        "did:webvh:{SCID}:example.com:a%20b:c%3Ad:100%25",
+        "https://example.com/a%20b/c%3Ad/100%25/did.jsonl"
+    )]
+    #[case(
+        "example.com",
+        "\u{e4}",
+        "did:webvh:{SCID}:example.com:%C3%A4",
+        "https://example.com/%C3%A4/did.jsonl"
+    )]
+    fn test_compose_and_decompose(
+        #[case] domain: &str,
+        #[case] path: &str,
+        #[case] expected_did: &str,
+        #[case] expected_url: &str,
+    ) {
+        let did = WebVhDid::compose(SCID, domain, path).unwrap();
+        assert_eq!(did, expected_did.replace("{SCID}", SCID));
+
+        let decomposed = WebVhDid::decompose(&did).unwrap();
+        assert_eq!(decomposed.scid(), SCID);
+        assert_eq!(decomposed.domain(), domain.to_ascii_lowercase());
+        assert_eq!(decomposed.path().join("/"), path.trim_matches('/'));
+        assert_eq!(decomposed.did_log_url(), expected_url);
+        assert_eq!(decomposed.to_string(), did); // roundtrip
+    }
+
+    #[rstest]
+    fn test_decompose_lowercase_port_separator() {
+        let did = WebVhDid::decompose(&format!("did:webvh:{SCID}:domain.com%3a8080")).unwrap();
+        assert_eq!(did.port(), Some(8080));
+        assert_eq!(did.host(), "domain.com");
+    }
+
+    #[rstest]
+    #[case(SCID, "example.com:0", "")]
+    #[case(SCID, "example.com:65536", "")]
+    #[case(SCID, "example.com:", "")]
+    #[case(SCID, "-example.com", "")]
+    #[case(SCID, "example..com", "")]
+    #[case(SCID, "exa_mple.com", "")]
+    #[case(SCID, "", "")]
+    #[case("", "example.com", "")]
+    #[case("Qm-Hash", "example.com", "")]
+    #[case(SCID, "example.com", "a//b")]
+    #[case(SCID, "example.com", "a/../b")]
+    fn test_compose_invalid(#[case] scid: &str, #[case] domain: &str, #[case] path: &str) {
+        assert_eq!(
+            WebVhDid::compose(scid, domain, path).unwrap_err().kind(),
+            DidResolverErrorKind::InvalidMethodSpecificId
+        );
+    }
+
+    #[rstest]
+    #[case("did:web:example.com")]
+    #[case("did:webvh:{SCID}")]
+    #[case("did:webvh:{SCID}:example.com:")]
+    #[case("did:webvh:{SCID}:example.com:a%2")]
+    #[case("did:webvh:{SCID}:example.com:a%zz")]
+    #[case("did:webvh:{SCID}:example.com:a%FF")] // invalid UTF-8
+    #[case("did:webvh:{SCID}:example.com:a/b")]
+    #[case("did:webvh:{SCID}:example.com%3Aport")]
+    fn test_decompose_invalid(#[case] did: &str) {
+        assert_eq!(
+            did.replace("{SCID}", SCID)
+                .parse::<WebVhDid>()
+                .unwrap_err()
+                .kind(),
+            DidResolverErrorKind::InvalidMethodSpecificId
+        );
+    }
+}
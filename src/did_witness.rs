@@ -0,0 +1,803 @@
+// SPDX-License-Identifier: MIT
+
+//! Support for the `did:webvh` witness file format (`did-witness.json`) as well as the (typed) `witness` DID method parameter,
+//! along with (optional) witness weights.
+//!
+//! See https://identity.foundation/didwebvh/v1.0/#the-witness-proofs-file.
+
+use crate::bounded_json::from_str_bounded;
+use crate::did_method_parameters::DidMethodParameter;
+#[cfg(feature = "resolvers")]
+use crate::did_resolver::DidResolver;
+use crate::ed25519::{Ed25519VerifyingKey, MultiBaseConverter as _, VerificationOptions};
+#[cfg(feature = "resolvers")]
+use crate::errors::DidResolverError;
+use crate::errors::DidSidekicksError;
+use crate::jcs_sha256_hasher::JcsSha256Hasher;
+use crate::json_emission::{emit_json, EmitOptions};
+#[cfg(feature = "resolvers")]
+use crate::key_interop::verifying_key_from_verification_method;
+use crate::vc_data_integrity::{
+    normalize_proofs, DataIntegrityProof, EddsaJcs2022Cryptosuite, UnsupportedCryptosuitePolicy,
+    VCDataIntegrity as _,
+};
+use crate::version_id::VersionId;
+use core::hash::BuildHasher;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value::Array as JsonArray, Value::String as JsonString};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// A single entry of a witness file, i.e. all the witness proofs related to a single `versionId`.
+#[derive(Debug, Clone)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct WitnessProofEntry {
+    pub version_id: VersionId,
+    pub proof: Vec<DataIntegrityProof>,
+}
+
+impl WitnessProofEntry {
+    /// Delivers (deduplicated) `did:key` identifiers of all witnesses that have signed this entry's `versionId`,
+    /// while taking only cryptographically valid proofs into account.
+    #[inline]
+    pub fn get_verified_witnesses(&self) -> Result<HashSet<String>, DidSidekicksError> {
+        // See https://identity.foundation/didwebvh/v1.0/#witness-proofs:
+        // The data to be signed by a witness is a JSON object containing the versionId only.
+        let doc_hash = match JcsSha256Hasher::default().encode_hex(&json!({
+            "versionId": self.version_id,
+        })) {
+            Ok(doc_hash) => doc_hash,
+            Err(err) => {
+                return Err(DidSidekicksError::SerializationFailed(format!(
+                    "Could not serialize witnessed versionId: {err}"
+                )))
+            }
+        };
+
+        let mut witnesses = HashSet::new();
+        for proof in &self.proof {
+            let witness_key = proof.extract_update_key()?;
+            let cryptosuite = EddsaJcs2022Cryptosuite {
+                verifying_key: Some(Ed25519VerifyingKey::from_multibase(&witness_key)?),
+                signing_key: None,
+                verification_options: VerificationOptions::default(),
+            };
+            if cryptosuite.verify_proof(proof, &doc_hash).is_ok() {
+                witnesses.insert(format!("did:key:{witness_key}"));
+            }
+        }
+        Ok(witnesses)
+    }
+}
+
+/// The content of a `did:webvh` witness file (`did-witness.json`), mapping witness proofs to `versionId`s.
+#[derive(Debug, Clone, Default)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct WitnessProofCollection {
+    pub entries: Vec<WitnessProofEntry>,
+}
+
+impl WitnessProofCollection {
+    /// The parsing constructor, expecting a JSON array of objects featuring `versionId` and `proof` properties.
+    ///
+    /// Each proof is validated the same way [`DataIntegrityProof::from`] does it, hence any proof featuring
+    /// an unsupported cryptosuite is denoted by [`DidSidekicksError::UnsupportedCryptosuite`].
+    #[inline]
+    pub fn from_json(json: &str) -> Result<Self, DidSidekicksError> {
+        Self::from_json_with_policy(json, UnsupportedCryptosuitePolicy::Reject)
+    }
+
+    /// The same as [`WitnessProofCollection::from_json`], while proofs featuring an unsupported cryptosuite
+    /// are treated according to the supplied `policy`, i.e. they might be skipped (instead of failing).
+    #[inline]
+    #[expect(clippy::pattern_type_mismatch, reason = "..")]
+    pub fn from_json_with_policy(
+        json: &str,
+        policy: UnsupportedCryptosuitePolicy,
+    ) -> Result<Self, DidSidekicksError> {
+        let json_entries = match from_str_bounded(json) {
+            Ok(JsonArray(json_entries)) => json_entries,
+            Ok(_) => {
+                return Err(DidSidekicksError::InvalidWitnessProof(
+                    "Malformed witness file format, expected JSON array".to_owned(),
+                ))
+            }
+            Err(err) => {
+                return Err(DidSidekicksError::InvalidWitnessProof(format!(
+                    "Malformed witness file format, expected JSON array: {}",
+                    err.detail()
+                )))
+            }
+        };
+
+        let entries = json_entries
+            .into_iter()
+            .map(|entry| {
+                let version_id = match entry.get("versionId") {
+                    Some(JsonString(version_id)) => {
+                        version_id.parse::<VersionId>().map_err(|err| {
+                            DidSidekicksError::InvalidWitnessProof(format!(
+                                "Malformed witness entry's versionId: {}",
+                                err.detail()
+                            ))
+                        })?
+                    }
+                    _ => {
+                        return Err(DidSidekicksError::InvalidWitnessProof(
+                            "Missing witness entry's versionId".to_owned(),
+                        ))
+                    }
+                };
+                let proof = match entry.get("proof") {
+                    Some(JsonArray(proofs)) => proofs
+                        .iter()
+                        .map(|proof| {
+                            DataIntegrityProof::from(JsonArray(vec![proof.to_owned()]).to_string())
+                        })
+                        .filter(|proof| {
+                            policy != UnsupportedCryptosuitePolicy::Skip
+                                || !matches!(
+                                    *proof,
+                                    Err(DidSidekicksError::UnsupportedCryptosuite(_))
+                                )
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                    _ => {
+                        return Err(DidSidekicksError::InvalidWitnessProof(format!(
+                            "Missing proof array of witness entry for versionId '{version_id}'"
+                        )))
+                    }
+                };
+                Ok(WitnessProofEntry { version_id, proof })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { entries })
+    }
+
+    /// Serializes the witness file back into JSON, according to the supplied `options`.
+    #[inline]
+    pub fn to_json(&self, options: EmitOptions) -> Result<String, DidSidekicksError> {
+        let entries: Vec<serde_json::Value> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                json!({
+                    "versionId": entry.version_id,
+                    "proof": entry.proof.iter().map(proof_to_json).collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+
+        emit_json(&entries, options).map_err(|err| {
+            DidSidekicksError::SerializationFailed(format!(
+                "Could not serialize witness file: {}",
+                err.detail()
+            ))
+        })
+    }
+
+    /// Normalizes the witness file (e.g. as aggregated from the witness files of several witnesses), i.e.:
+    /// - all the entries featuring the same `versionId` are merged into a single one,
+    /// - the entries are ordered by the number of their `versionId` and
+    /// - the proofs of each entry are normalized (see [`normalize_proofs`]), while duplicate proofs are stripped, if `strip_duplicates` is set.
+    ///
+    /// Thus, the very same witness proofs are always serialized (see [`WitnessProofCollection::to_json`]) the very same way.
+    #[inline]
+    pub fn normalize(&mut self, strip_duplicates: bool) {
+        let mut merged: Vec<WitnessProofEntry> = Vec::with_capacity(self.entries.len());
+        for entry in self.entries.drain(..) {
+            match merged
+                .iter_mut()
+                .find(|existing| existing.version_id == entry.version_id)
+            {
+                Some(existing) => existing.proof.extend(entry.proof),
+                None => merged.push(entry),
+            }
+        }
+        merged.sort_by_key(|entry| entry.version_id.number());
+        for entry in &mut merged {
+            normalize_proofs(&mut entry.proof, strip_duplicates);
+        }
+        self.entries = merged;
+    }
+
+    /// Ensures that every `versionId` in `required_version_ids` (in the order of their appearance in the DID log)
+    /// is witnessed by at least `threshold` distinct witnesses out of `witnesses` (`did:key` identifiers).
+    ///
+    /// W.r.t. https://identity.foundation/didwebvh/v1.0/#witness-proofs, a proof for a later `versionId`
+    /// implicitly approves all the earlier ones, hence it counts for the earlier ones as well.
+    ///
+    /// If coverage is insufficient for any of `required_version_ids`, [`DidSidekicksError::InvalidWitnessProof`] is returned.
+    #[inline]
+    pub fn validate_coverage(
+        &self,
+        required_version_ids: &[VersionId],
+        witnesses: &[String],
+        threshold: usize,
+    ) -> Result<(), DidSidekicksError> {
+        let mut covering_witnesses = HashSet::new();
+        // iterate backwards, so the witnesses of later versions are accumulated
+        for version_id in required_version_ids.iter().rev() {
+            for entry in self
+                .entries
+                .iter()
+                .filter(|entry| entry.version_id == *version_id)
+            {
+                covering_witnesses.extend(
+                    entry
+                        .get_verified_witnesses()?
+                        .into_iter()
+                        .filter(|witness| witnesses.contains(witness)),
+                );
+            }
+
+            if covering_witnesses.len() < threshold {
+                return Err(DidSidekicksError::InvalidWitnessProof(format!(
+                    "Insufficient witness coverage for versionId '{version_id}': {} of required {threshold} witness proofs",
+                    covering_witnesses.len()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The name of the DID method parameter configuring the witnesses, as specified by https://identity.foundation/didwebvh/v1.0/#did-witnesses.
+pub const PARAM_WITNESS: &str = "witness";
+
+/// A single witness, as listed by the [`PARAM_WITNESS`] DID method parameter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct Witness {
+    /// The DID of the witness, typically a `did:key` DID.
+    pub id: String,
+    /// The weight of the witness proofs, `1` by default (hence omitted from serialization).
+    #[serde(default = "default_weight", skip_serializing_if = "is_default_weight")]
+    pub weight: u64,
+}
+
+const fn default_weight() -> u64 {
+    1
+}
+
+const fn is_default_weight(weight: &u64) -> bool {
+    *weight == 1
+}
+
+/// The (typed) value of the [`PARAM_WITNESS`] DID method parameter, i.e. the witnesses along with the threshold
+/// their (summed up) weights must reach for each DID log entry to be considered witnessed.
+///
+/// No witnesses along with a zero threshold (e.g. `{}`) denote disabled witnessing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct WitnessConfig {
+    #[serde(default)]
+    pub threshold: u64,
+    #[serde(default)]
+    pub witnesses: Vec<Witness>,
+}
+
+impl WitnessConfig {
+    /// The parsing (and validating, see [`WitnessConfig::validate`]) constructor.
+    ///
+    /// Any malformed JSON is denoted by [`DidSidekicksError::InvalidDidMethodParameter`].
+    #[inline]
+    pub fn from_json(json: &str) -> Result<Self, DidSidekicksError> {
+        let config: Self = serde_json::from_value(from_str_bounded(json)?).map_err(|err| {
+            DidSidekicksError::InvalidDidMethodParameter(format!(
+                "Malformed '{PARAM_WITNESS}' DID method parameter: {err}"
+            ))
+        })?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Delivers the (validated) witness configuration featured by the supplied (active) DID method `parameters`, if any.
+    #[inline]
+    pub fn from_parameters<S: BuildHasher>(
+        parameters: &HashMap<String, Arc<DidMethodParameter>, S>,
+    ) -> Result<Option<Self>, DidSidekicksError> {
+        parameters
+            .get(PARAM_WITNESS)
+            .map(|param| Self::from_json(&param.get_json_text()))
+            .transpose()
+    }
+
+    /// Whether witnessing is enabled, i.e. any witness is listed.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        !self.witnesses.is_empty()
+    }
+
+    /// The sum of the weights of all witnesses.
+    #[inline]
+    pub fn total_weight(&self) -> u64 {
+        self.witnesses
+            .iter()
+            .fold(0, |total, witness| total.saturating_add(witness.weight))
+    }
+
+    /// Ensures the configuration is consistent, i.e.:
+    /// - each witness is a distinct DID featuring a non-zero weight and
+    /// - the threshold is reachable (i.e. not exceeding the total weight) and non-zero, unless witnessing is disabled.
+    ///
+    /// Any violation is denoted by [`DidSidekicksError::InvalidDidMethodParameter`].
+    #[inline]
+    pub fn validate(&self) -> Result<(), DidSidekicksError> {
+        for (pos, witness) in self.witnesses.iter().enumerate() {
+            if !witness.id.starts_with("did:") || witness.id.contains(['#', '?', '/']) {
+                return Err(DidSidekicksError::InvalidDidMethodParameter(format!(
+                    "The witness '{}' is not a DID",
+                    witness.id
+                )));
+            }
+            if witness.weight == 0 {
+                return Err(DidSidekicksError::InvalidDidMethodParameter(format!(
+                    "The witness '{}' features no weight",
+                    witness.id
+                )));
+            }
+            if self
+                .witnesses
+                .iter()
+                .skip(pos.saturating_add(1))
+                .any(|other| other.id == witness.id)
+            {
+                return Err(DidSidekicksError::InvalidDidMethodParameter(format!(
+                    "The witness '{}' is listed more than once",
+                    witness.id
+                )));
+            }
+        }
+
+        let total_weight = self.total_weight();
+        if self.is_enabled() && (self.threshold == 0 || self.threshold > total_weight) {
+            return Err(DidSidekicksError::InvalidDidMethodParameter(format!(
+                "The witness threshold {} is out of range (1 to {total_weight})",
+                self.threshold
+            )));
+        }
+        if !self.is_enabled() && self.threshold != 0 {
+            return Err(DidSidekicksError::InvalidDidMethodParameter(format!(
+                "The witness threshold {} is unreachable, as no witnesses are listed",
+                self.threshold
+            )));
+        }
+        Ok(())
+    }
+
+    /// Delivers the mapping of each `did:key` witness to itself, i.e. its proof key (see [`WitnessProofCollection::validate_weighted_coverage`]).
+    ///
+    /// Any other witness is to be resolved first (see [`WitnessConfig::resolve_witness_keys`]).
+    #[inline]
+    pub fn did_key_witness_keys(&self) -> HashMap<String, String> {
+        self.witnesses
+            .iter()
+            .filter(|witness| witness.id.starts_with("did:key:"))
+            .map(|witness| (witness.id.to_owned(), witness.id.to_owned()))
+            .collect()
+    }
+
+    /// Resolves each (non-`did:key`) witness DID by means of the supplied `resolve` function (typically a method-specific resolver constructor),
+    /// thus delivering the mapping of each proof key (as `did:key` DID) to the witness DID.
+    ///
+    /// The proof keys of a witness are the keys listed as `assertionMethod` by its DID document. Returns:
+    /// - [`DidSidekicksError::InvalidDidDocument`], if any witness DID could not be resolved and
+    /// - [`DidSidekicksError::KeyNotFound`], if its DID document features no (Ed25519) `assertionMethod` key.
+    #[cfg(feature = "resolvers")]
+    #[inline]
+    pub fn resolve_witness_keys<R, F>(
+        &self,
+        mut resolve: F,
+    ) -> Result<HashMap<String, String>, DidSidekicksError>
+    where
+        R: DidResolver,
+        F: FnMut(&str) -> Result<R, DidResolverError>,
+    {
+        let mut witness_keys = self.did_key_witness_keys();
+        for witness in self
+            .witnesses
+            .iter()
+            .filter(|witness| !witness.id.starts_with("did:key:"))
+        {
+            let witness_doc = resolve(&witness.id)
+                .map_err(|err| {
+                    DidSidekicksError::InvalidDidDocument(format!(
+                        "Could not resolve witness '{}': {err}",
+                        witness.id
+                    ))
+                })?
+                .get_did_doc_obj();
+            let keys: Vec<_> = witness_doc
+                .assertion_method
+                .iter()
+                .filter_map(|method| verifying_key_from_verification_method(method).ok())
+                .map(|key| format!("did:key:{}", key.to_multibase()))
+                .collect();
+            if keys.is_empty() {
+                return Err(DidSidekicksError::KeyNotFound(format!(
+                    "The witness '{}' features no 'assertionMethod' key",
+                    witness.id
+                )));
+            }
+            witness_keys.extend(keys.into_iter().map(|key| (key, witness.id.to_owned())));
+        }
+        Ok(witness_keys)
+    }
+}
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
+impl WitnessProofCollection {
+    /// The weighted counterpart of [`WitnessProofCollection::validate_coverage`], i.e. the (summed up) weights of distinct witnesses
+    /// (as configured by the supplied `config`) must reach its threshold for every `versionId` in `required_version_ids`.
+    ///
+    /// The supplied `witness_keys` map the proof keys (as `did:key` DIDs) to the witness DIDs (see [`WitnessConfig::did_key_witness_keys`]),
+    /// whereas proofs by any other key do not count.
+    ///
+    /// If coverage is insufficient for any of `required_version_ids`, [`DidSidekicksError::InvalidWitnessProof`] is returned.
+    #[inline]
+    pub fn validate_weighted_coverage<S: BuildHasher>(
+        &self,
+        required_version_ids: &[VersionId],
+        config: &WitnessConfig,
+        witness_keys: &HashMap<String, String, S>,
+    ) -> Result<(), DidSidekicksError> {
+        let mut covering_witnesses = HashSet::new();
+        // iterate backwards, so the witnesses of later versions are accumulated
+        for version_id in required_version_ids.iter().rev() {
+            for entry in self
+                .entries
+                .iter()
+                .filter(|entry| entry.version_id == *version_id)
+            {
+                covering_witnesses.extend(
+                    entry
+                        .get_verified_witnesses()?
+                        .iter()
+                        .filter_map(|key| witness_keys.get(key))
+                        .cloned(),
+                );
+            }
+
+            let weight = config
+                .witnesses
+                .iter()
+                .filter(|witness| covering_witnesses.contains(&witness.id))
+                .fold(0, |total: u64, witness| {
+                    total.saturating_add(witness.weight)
+                });
+            if weight < config.threshold {
+                return Err(DidSidekicksError::InvalidWitnessProof(format!(
+                    "Insufficient witness coverage for versionId '{version_id}': weight {weight} of required {}",
+                    config.threshold
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Serializes a proof the same way [`VCDataIntegrity::add_proof`] does it.
+#[expect(clippy::indexing_slicing, reason = "panic-safe indexing")]
+#[expect(clippy::single_call_fn, reason = "..")]
+fn proof_to_json(proof: &DataIntegrityProof) -> serde_json::Value {
+    let mut value = proof.proof_config();
+    value["proofValue"] = json!(proof.proof_value);
+    value
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(
+    clippy::indexing_slicing,
+    reason = "panic-safe as long as test case setup is correct"
+)]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+mod test {
+    use super::{WitnessConfig, WitnessProofCollection};
+    use crate::ed25519::{Ed25519KeyPair, MultiBaseConverter as _, VerificationOptions};
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::json_emission::EmitOptions;
+    use crate::test::assert_error;
+    use crate::vc_data_integrity::{
+        CryptoSuiteProofOptions, EddsaJcs2022Cryptosuite, UnsupportedCryptosuitePolicy,
+        VCDataIntegrity as _,
+    };
+    use crate::version_id::VersionId;
+    use rstest::rstest;
+    use serde_json::json;
+
+    fn witness_proof(witness: &Ed25519KeyPair, version_id: &str) -> serde_json::Value {
+        let key = witness.verifying_key.to_multibase();
+        let suite = EddsaJcs2022Cryptosuite {
+            verifying_key: Some(witness.verifying_key.clone()),
+            signing_key: Some(witness.signing_key.clone()),
+            verification_options: VerificationOptions::default(),
+        };
+        let options =
+            CryptoSuiteProofOptions::from_verification_method(&format!("did:key:{key}#{key}"))
+                .with_proof_purpose("assertionMethod");
+        let secured = suite
+            .add_proof(&json!({"versionId": version_id}), &options)
+            .unwrap();
+        secured.get("proof").unwrap().get(0).unwrap().to_owned()
+    }
+
+    #[rstest]
+    fn test_witness_file_normalization() {
+        let witness_a = Ed25519KeyPair::generate();
+        let witness_b = Ed25519KeyPair::generate();
+        let proof_a_1 = witness_proof(&witness_a, "1-QmA");
+        let proof_a_2 = witness_proof(&witness_a, "2-QmB");
+        let proof_b_2 = witness_proof(&witness_b, "2-QmB");
+
+        // as aggregated from the witness files of both witnesses, i.e. featuring a duplicate proof
+        let aggregated = json!([
+            {"versionId": "2-QmB", "proof": [proof_a_2]},
+            {"versionId": "1-QmA", "proof": [proof_a_1]},
+            {"versionId": "2-QmB", "proof": [proof_b_2, proof_a_2]},
+        ]);
+        let reordered = json!([
+            {"versionId": "2-QmB", "proof": [proof_a_2, proof_b_2]},
+            {"versionId": "1-QmA", "proof": [proof_a_1]},
+        ]);
+
+        let mut collection = WitnessProofCollection::from_json(&aggregated.to_string()).unwrap();
+        collection.normalize(false);
+        assert_eq!(collection.entries.len(), 2);
+        assert_eq!(collection.entries[0].version_id.number(), 1);
+        assert_eq!(collection.entries[1].proof.len(), 3);
+
+        collection.normalize(true);
+        assert_eq!(collection.entries[0].proof.len(), 1);
+        assert_eq!(collection.entries[1].proof.len(), 2);
+
+        let mut other = WitnessProofCollection::from_json(&reordered.to_string()).unwrap();
+        other.normalize(true);
+        assert_eq!(
+            collection.to_json(EmitOptions::COMPACT).unwrap(),
+            other.to_json(EmitOptions::COMPACT).unwrap()
+        );
+    }
+
+    #[rstest]
+    fn test_witness_file_roundtrip_and_coverage() {
+        let witness_a = Ed25519KeyPair::generate();
+        let witness_b = Ed25519KeyPair::generate();
+        let witnesses = vec![
+            format!("did:key:{}", witness_a.verifying_key.to_multibase()),
+            format!("did:key:{}", witness_b.verifying_key.to_multibase()),
+        ];
+
+        let witness_file = json!([
+            {"versionId": "1-QmA", "proof": [witness_proof(&witness_a, "1-QmA")]},
+            {"versionId": "2-QmB", "proof": [
+                witness_proof(&witness_a, "2-QmB"),
+                witness_proof(&witness_b, "2-QmB"),
+            ]},
+        ])
+        .to_string();
+
+        let collection = WitnessProofCollection::from_json(&witness_file).unwrap();
+        assert_eq!(collection.entries.len(), 2);
+
+        let reparsed =
+            WitnessProofCollection::from_json(&collection.to_json(EmitOptions::PRETTY).unwrap())
+                .unwrap();
+        assert_eq!(reparsed.entries.len(), 2);
+
+        let version_ids: Vec<VersionId> = vec!["1-QmA".parse().unwrap(), "2-QmB".parse().unwrap()];
+        // versionId 1 is witnessed by a only, yet implicitly also by b (thanks to versionId 2)
+        reparsed
+            .validate_coverage(&version_ids, &witnesses, 2)
+            .unwrap();
+        assert_error(
+            reparsed.validate_coverage(&version_ids[..1], &witnesses, 2),
+            DidSidekicksErrorKind::InvalidWitnessProof,
+            "Insufficient witness coverage for versionId '1-QmA'",
+        );
+        // proofs by unknown witnesses do not count
+        assert_error(
+            reparsed.validate_coverage(&version_ids, &witnesses[..1], 2),
+            DidSidekicksErrorKind::InvalidWitnessProof,
+            "Insufficient witness coverage for versionId '2-QmB'",
+        );
+    }
+
+    #[rstest]
+    fn test_invalid_signature_does_not_count() {
+        let witness = Ed25519KeyPair::generate();
+        let witnesses = vec![format!("did:key:{}", witness.verifying_key.to_multibase())];
+        // signed over a different versionId
+        let witness_file = json!([
+            {"versionId": "1-QmA", "proof": [witness_proof(&witness, "1-QmX")]},
+        ])
+        .to_string();
+
+        let collection = WitnessProofCollection::from_json(&witness_file).unwrap();
+        assert_error(
+            collection.validate_coverage(&["1-QmA".parse().unwrap()], &witnesses, 1),
+            DidSidekicksErrorKind::InvalidWitnessProof,
+            "Insufficient witness coverage",
+        );
+    }
+
+    #[rstest]
+    fn test_unsupported_cryptosuite() {
+        let witness = Ed25519KeyPair::generate();
+        let witnesses = vec![format!("did:key:{}", witness.verifying_key.to_multibase())];
+        let mut foreign_proof = witness_proof(&witness, "1-QmA");
+        foreign_proof["cryptosuite"] = json!("bbs-2023");
+        let witness_file = json!([
+            {"versionId": "1-QmA", "proof": [foreign_proof, witness_proof(&witness, "1-QmA")]},
+        ])
+        .to_string();
+
+        assert_error(
+            WitnessProofCollection::from_json(&witness_file),
+            DidSidekicksErrorKind::UnsupportedCryptosuite,
+            "bbs-2023",
+        );
+
+        let collection = WitnessProofCollection::from_json_with_policy(
+            &witness_file,
+            UnsupportedCryptosuitePolicy::Skip,
+        )
+        .unwrap();
+        assert_eq!(collection.entries[0].proof.len(), 1);
+        collection
+            .validate_coverage(&["1-QmA".parse().unwrap()], &witnesses, 1)
+            .unwrap();
+    }
+
+    #[rstest]
+    #[case("{}", "expected JSON array")]
+    #[case("[{\"proof\": []}]", "Missing witness entry's versionId")]
+    #[case("[{\"versionId\": \"1-QmA\"}]", "Missing proof array")]
+    #[case(
+        "[{\"versionId\": \"01-QmA\", \"proof\": []}]",
+        "Malformed witness entry's versionId"
+    )]
+    fn test_invalid_witness_file(#[case] json: &str, #[case] error_string: &str) {
+        assert_error(
+            WitnessProofCollection::from_json(json),
+            DidSidekicksErrorKind::InvalidWitnessProof,
+            error_string,
+        );
+    }
+
+    #[rstest]
+    #[case(json!({"threshold": 0, "witnesses": []}), true, "")]
+    #[case(json!({"threshold": 2, "witnesses": [{"id": "did:key:z6MkA"}, {"id": "did:key:z6MkB"}]}), true, "")]
+    #[case(json!({"threshold": 3, "witnesses": [{"id": "did:key:z6MkA", "weight": 2}, {"id": "did:key:z6MkB"}]}), true, "")]
+    #[case(json!({"threshold": 3, "witnesses": [{"id": "did:key:z6MkA"}, {"id": "did:key:z6MkB"}]}), false, "is out of range (1 to 2)")]
+    #[case(json!({"threshold": 0, "witnesses": [{"id": "did:key:z6MkA"}]}), false, "is out of range")]
+    #[case(json!({"threshold": 1}), false, "as no witnesses are listed")]
+    #[case(json!({"threshold": 1, "witnesses": [{"id": "did:key:z6MkA", "weight": 0}]}), false, "features no weight")]
+    #[case(json!({"threshold": 1, "witnesses": [{"id": "did:key:z6MkA"}, {"id": "did:key:z6MkA"}]}), false, "is listed more than once")]
+    #[case(json!({"threshold": 1, "witnesses": [{"id": "z6MkA"}]}), false, "is not a DID")]
+    #[case(json!({"threshold": -1}), false, "Malformed 'witness' DID method parameter")]
+    fn test_witness_config(
+        #[case] json: serde_json::Value,
+        #[case] valid: bool,
+        #[case] error_contains: &str,
+    ) {
+        let res = WitnessConfig::from_json(&json.to_string());
+        if valid {
+            let config = res.unwrap();
+            // the default weight is omitted, so the very same JSON is delivered back
+            assert_eq!(serde_json::to_value(&config).unwrap(), json);
+        } else {
+            assert_error(
+                res,
+                DidSidekicksErrorKind::InvalidDidMethodParameter,
+                error_contains,
+            );
+        }
+    }
+
+    #[rstest]
+    fn test_weighted_coverage() {
+        let witness_a = Ed25519KeyPair::generate();
+        let witness_b = Ed25519KeyPair::generate();
+        let did_a = format!("did:key:{}", witness_a.verifying_key.to_multibase());
+        let did_b = format!("did:key:{}", witness_b.verifying_key.to_multibase());
+        let config = WitnessConfig::from_json(
+            &json!({"threshold": 2, "witnesses": [{"id": did_a, "weight": 2}, {"id": did_b}]})
+                .to_string(),
+        )
+        .unwrap();
+
+        let witness_file = json!([
+            {"versionId": "1-QmA", "proof": [witness_proof(&witness_b, "1-QmA")]},
+            {"versionId": "2-QmB", "proof": [witness_proof(&witness_a, "2-QmB")]},
+        ])
+        .to_string();
+        let collection = WitnessProofCollection::from_json(&witness_file).unwrap();
+        let version_ids: Vec<VersionId> = vec!["1-QmA".parse().unwrap(), "2-QmB".parse().unwrap()];
+
+        let witness_keys = config.did_key_witness_keys();
+        // a single proof by the heavier witness suffices
+        collection
+            .validate_weighted_coverage(&version_ids, &config, &witness_keys)
+            .unwrap();
+        assert_error(
+            collection.validate_weighted_coverage(&version_ids[..1], &config, &witness_keys),
+            DidSidekicksErrorKind::InvalidWitnessProof,
+            "Insufficient witness coverage for versionId '1-QmA': weight 1 of required 2",
+        );
+    }
+
+    #[cfg(feature = "resolvers")]
+    #[rstest]
+    fn test_resolve_witness_keys() {
+        use crate::did_doc::DidDoc;
+        use crate::did_method_parameters::DidMethodParameter;
+        use crate::did_resolver::DidResolver;
+        use crate::errors::DidResolverError;
+        use std::collections::HashMap;
+        use std::sync::Arc;
+
+        struct StaticDidResolver(Arc<DidDoc>);
+        #[expect(clippy::missing_trait_methods, reason = "..")]
+        impl DidResolver for StaticDidResolver {
+            fn get_did_doc_obj(&self) -> Arc<DidDoc> {
+                Arc::clone(&self.0)
+            }
+            fn collect_did_method_parameters_map(
+                &self,
+            ) -> Result<HashMap<String, Arc<DidMethodParameter>>, DidResolverError> {
+                Ok(HashMap::new())
+            }
+        }
+
+        let witness = Ed25519KeyPair::generate();
+        let witness_did = "did:webvh:QmHash:witness.example.com";
+        let witness_doc = DidDoc::from_json(
+            &json!({
+                "@context": ["https://www.w3.org/ns/did/v1"],
+                "id": witness_did,
+                "verificationMethod": [],
+                "assertionMethod": [{
+                    "id": format!("{witness_did}#assert-key-01"),
+                    "type": "Multikey",
+                    "publicKeyMultibase": witness.verifying_key.to_multibase(),
+                }],
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let config = WitnessConfig::from_json(
+            &json!({"threshold": 1, "witnesses": [{"id": witness_did}]}).to_string(),
+        )
+        .unwrap();
+
+        let witness_keys = config
+            .resolve_witness_keys(|_| Ok(StaticDidResolver(Arc::new(witness_doc.clone()))))
+            .unwrap();
+        let collection = WitnessProofCollection::from_json(
+            &json!([{"versionId": "1-QmA", "proof": [witness_proof(&witness, "1-QmA")]}])
+                .to_string(),
+        )
+        .unwrap();
+        collection
+            .validate_weighted_coverage(&["1-QmA".parse().unwrap()], &config, &witness_keys)
+            .unwrap();
+
+        assert_error(
+            config.resolve_witness_keys(|did| {
+                Err::<StaticDidResolver, _>(DidResolverError::InvalidMethodSpecificId(
+                    did.to_owned(),
+                ))
+            }),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            "Could not resolve witness",
+        );
+    }
+}
@@ -0,0 +1,490 @@
+// SPDX-License-Identifier: MIT
+
+//! DIDComm v2 message envelope helpers, as specified by https://identity.foundation/didcomm-messaging/spec/v2.1.
+//!
+//! Supported envelopes:
+//! - signed (JWS, `EdDSA` over Ed25519 keys referenced by `authentication` verification relationship) and
+//! - anonymous-encrypted (JWE, `ECDH-ES+A256KW`/`A256GCM` over X25519 keys referenced by `keyAgreement` verification relationship).
+
+use crate::bounded_json::{from_slice_bounded, from_str_bounded};
+use crate::did_doc::{DidDoc, VerificationMethod};
+use crate::ed25519::{
+    Ed25519SigningKey, Ed25519VerifyingKey, MultiBaseConverter as _, VerificationOptions,
+};
+use crate::errors::DidSidekicksError;
+use aes_gcm::aead::{Aead as _, Payload};
+use aes_gcm::{Aes256Gcm, KeyInit as _, Nonce};
+use aes_kw::KekAes256;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use ed25519_dalek::{Signature, VerifyingKey, PUBLIC_KEY_LENGTH};
+use rand::rngs::OsRng;
+use rand::RngCore as _;
+use serde_json::{json, Value};
+use sha2::{Digest as _, Sha256};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+/// The media type of a signed DIDComm message.
+pub const DIDCOMM_SIGNED_MEDIA_TYPE: &str = "application/didcomm-signed+json";
+/// The media type of an encrypted DIDComm message.
+pub const DIDCOMM_ENCRYPTED_MEDIA_TYPE: &str = "application/didcomm-encrypted+json";
+
+const JWS_ALG_EDDSA: &str = "EdDSA";
+const JWE_ALG_ECDH_ES_A256KW: &str = "ECDH-ES+A256KW";
+const JWE_ENC_A256GCM: &str = "A256GCM";
+const AES_GCM_IV_LENGTH: usize = 12;
+const AES_GCM_TAG_LENGTH: usize = 16;
+const CEK_LENGTH: usize = 32;
+
+fn didcomm_error(msg: impl core::fmt::Display) -> DidSidekicksError {
+    DidSidekicksError::InvalidDidCommMessage(msg.to_string())
+}
+
+fn b64_encode(bytes: impl AsRef<[u8]>) -> String {
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn b64_decode(name: &str, encoded: &str) -> Result<Vec<u8>, DidSidekicksError> {
+    URL_SAFE_NO_PAD
+        .decode(encoded)
+        .map_err(|err| didcomm_error(format!("Malformed '{name}' (base64url expected): {err}")))
+}
+
+fn get_str<'val>(value: &'val Value, name: &str) -> Result<&'val str, DidSidekicksError> {
+    value
+        .get(name)
+        .and_then(Value::as_str)
+        .ok_or_else(|| didcomm_error(format!("Missing '{name}' property")))
+}
+
+fn decode_key<const N: usize>(encoded: &str) -> Result<[u8; N], DidSidekicksError> {
+    <[u8; N]>::try_from(b64_decode("x", encoded)?).map_err(|decoded: Vec<u8>| {
+        didcomm_error(format!(
+            "Invalid key length {}, expected {N} bytes",
+            decoded.len()
+        ))
+    })
+}
+
+/// Looks up the verification method denoted by `kid` within the supplied `relationship`.
+#[expect(clippy::single_call_fn, reason = "..")]
+fn find_verification_method<'doc>(
+    relationship: &'doc [VerificationMethod],
+    kid: &str,
+    relationship_name: &str,
+) -> Result<&'doc VerificationMethod, DidSidekicksError> {
+    relationship
+        .iter()
+        .find(|method| method.id == kid)
+        .ok_or_else(|| {
+            didcomm_error(format!(
+                "No '{relationship_name}' verification method found for kid '{kid}'"
+            ))
+        })
+}
+
+/// Delivers an Ed25519 verifying key (either `OKP`/`Ed25519` JWK or Multikey) out of the supplied verification method.
+#[expect(clippy::single_call_fn, reason = "..")]
+fn ed25519_verifying_key(
+    method: &VerificationMethod,
+) -> Result<Ed25519VerifyingKey, DidSidekicksError> {
+    if let Some(jwk) = method.public_key_jwk.as_ref() {
+        if jwk.kty.as_deref() != Some("OKP") || jwk.crv.as_deref() != Some("Ed25519") {
+            return Err(didcomm_error(format!(
+                "Verification method '{}' does not feature an Ed25519 key",
+                method.id
+            )));
+        }
+        let x = jwk
+            .x
+            .as_deref()
+            .ok_or_else(|| didcomm_error("Missing 'x' property of JWK"))?;
+        let key = VerifyingKey::from_bytes(&decode_key::<PUBLIC_KEY_LENGTH>(x)?)
+            .map_err(|err| didcomm_error(format!("Invalid Ed25519 key: {err}")))?;
+        return Ok(Ed25519VerifyingKey::new(key));
+    }
+
+    let multibase = method.public_key_multibase.as_deref().ok_or_else(|| {
+        didcomm_error(format!(
+            "Verification method '{}' features no public key",
+            method.id
+        ))
+    })?;
+    Ed25519VerifyingKey::from_multibase(multibase)
+}
+
+/// Delivers an X25519 public key (`OKP`/`X25519` JWK) out of the supplied verification method.
+#[expect(clippy::single_call_fn, reason = "..")]
+fn x25519_public_key(method: &VerificationMethod) -> Result<PublicKey, DidSidekicksError> {
+    let Some(jwk) = method
+        .public_key_jwk
+        .as_ref()
+        .filter(|jwk| jwk.kty.as_deref() == Some("OKP") && jwk.crv.as_deref() == Some("X25519"))
+    else {
+        return Err(didcomm_error(format!(
+            "Verification method '{}' does not feature an X25519 JWK",
+            method.id
+        )));
+    };
+
+    let x = jwk
+        .x
+        .as_deref()
+        .ok_or_else(|| didcomm_error("Missing 'x' property of JWK"))?;
+    Ok(PublicKey::from(decode_key::<32>(x)?))
+}
+
+/// Packs the supplied plaintext DIDComm `message` (JSON text) into a signed envelope.
+///
+/// The envelope is a JWS in General JSON Serialization, as specified by https://identity.foundation/didcomm-messaging/spec/v2.1/#didcomm-signed-messages.
+///
+/// The `kid` must denote the verification method (of the sender's DID document) matching the `signing_key`.
+#[inline]
+pub fn pack_signed(
+    message: &str,
+    kid: &str,
+    signing_key: &Ed25519SigningKey,
+) -> Result<String, DidSidekicksError> {
+    if from_str_bounded::<Value>(message).is_err() {
+        return Err(didcomm_error("The plaintext message must be a valid JSON text"));
+    }
+
+    let protected = b64_encode(
+        json!({
+            "typ": DIDCOMM_SIGNED_MEDIA_TYPE,
+            "alg": JWS_ALG_EDDSA,
+            "kid": kid,
+        })
+        .to_string(),
+    );
+    let payload = b64_encode(message);
+    let signature = signing_key.sign_bytes(format!("{protected}.{payload}").as_bytes())?;
+
+    Ok(json!({
+        "payload": payload,
+        "signatures": [{
+            "protected": protected,
+            "signature": b64_encode(signature.signature.to_bytes()),
+            "header": { "kid": kid },
+        }],
+    })
+    .to_string())
+}
+
+/// Unpacks a signed DIDComm envelope (as created by [`pack_signed`]), thus delivering the plaintext message (JSON text).
+///
+/// All the signatures are verified against the keys referenced by the `authentication` verification relationship
+/// of the supplied `sender_did_doc`.
+#[inline]
+pub fn unpack_signed(envelope: &str, sender_did_doc: &DidDoc) -> Result<String, DidSidekicksError> {
+    let jws: Value = from_str_bounded(envelope)
+        .map_err(|err| didcomm_error(format!("Malformed signed envelope: {}", err.detail())))?;
+    let payload = get_str(&jws, "payload")?;
+    let signatures = jws
+        .get("signatures")
+        .and_then(Value::as_array)
+        .filter(|signatures| !signatures.is_empty())
+        .ok_or_else(|| didcomm_error("Missing 'signatures' property"))?;
+
+    for entry in signatures {
+        let protected = get_str(entry, "protected")?;
+        let header: Value = from_slice_bounded(&b64_decode("protected", protected)?)
+            .map_err(|err| didcomm_error(format!("Malformed protected header: {}", err.detail())))?;
+        if get_str(&header, "alg")? != JWS_ALG_EDDSA {
+            return Err(didcomm_error(format!(
+                "Unsupported 'alg', expected '{JWS_ALG_EDDSA}'"
+            )));
+        }
+
+        let kid = get_str(&header, "kid")?;
+        let method =
+            find_verification_method(&sender_did_doc.authentication, kid, "authentication")?;
+        let verifying_key = ed25519_verifying_key(method)?;
+
+        let signature = Signature::from_slice(&b64_decode("signature", get_str(entry, "signature")?)?)
+            .map_err(|err| didcomm_error(format!("Malformed signature: {err}")))?;
+        verifying_key
+            .verify_with_options(
+                format!("{protected}.{payload}").as_bytes(),
+                &signature,
+                VerificationOptions::default(),
+            )
+            .map_err(|err| didcomm_error(format!("Invalid signature by '{kid}': {err}")))?;
+    }
+
+    String::from_utf8(b64_decode("payload", payload)?)
+        .map_err(|err| didcomm_error(format!("Malformed payload: {err}")))
+}
+
+/// Derives a key-encryption key (KEK) using Concat KDF, as specified by https://www.rfc-editor.org/rfc/rfc7518#section-4.6.2.
+#[expect(clippy::big_endian_bytes, reason = "..")]
+fn concat_kdf(shared_secret: &[u8], apv: &[u8]) -> Result<[u8; 32], DidSidekicksError> {
+    let length_prefixed = |data: &[u8]| -> Result<Vec<u8>, DidSidekicksError> {
+        let len = u32::try_from(data.len()).map_err(didcomm_error)?;
+        Ok([len.to_be_bytes().as_slice(), data].concat())
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update(u32::to_be_bytes(1)); // round
+    hasher.update(shared_secret);
+    hasher.update(length_prefixed(JWE_ALG_ECDH_ES_A256KW.as_bytes())?); // AlgorithmID
+    hasher.update(length_prefixed(&[])?); // PartyUInfo (apu is not used by anoncrypt)
+    hasher.update(length_prefixed(apv)?); // PartyVInfo
+    hasher.update(u32::to_be_bytes(256)); // SuppPubInfo (keydatalen in bits)
+    Ok(hasher.finalize().into())
+}
+
+/// Delivers the `apv` header parameter, as specified by https://identity.foundation/didcomm-messaging/spec/v2.1/#ecdh-es-key-wrapping-and-common-protected-headers.
+#[expect(clippy::single_call_fn, reason = "..")]
+fn apv(kids: &[&str]) -> Vec<u8> {
+    let mut sorted = kids.to_vec();
+    sorted.sort_unstable();
+    Sha256::digest(sorted.join(".").as_bytes()).to_vec()
+}
+
+/// Packs the supplied plaintext (or signed) DIDComm `message` into an anonymous-encrypted envelope.
+///
+/// The envelope is a JWE in General JSON Serialization (`ECDH-ES+A256KW`/`A256GCM`),
+/// as specified by https://identity.foundation/didcomm-messaging/spec/v2.1/#didcomm-encrypted-messages.
+///
+/// The message is encrypted for all the X25519 keys referenced by the `keyAgreement` verification relationship
+/// of the supplied `recipient_did_doc`.
+#[inline]
+pub fn pack_anoncrypt(message: &str, recipient_did_doc: &DidDoc) -> Result<String, DidSidekicksError> {
+    let recipients = recipient_did_doc
+        .key_agreement
+        .iter()
+        .map(|method| Ok((method.id.as_str(), x25519_public_key(method)?)))
+        .collect::<Result<Vec<_>, DidSidekicksError>>()?;
+    if recipients.is_empty() {
+        return Err(didcomm_error("No 'keyAgreement' verification method found"));
+    }
+
+    let kids: Vec<&str> = recipients.iter().map(|&(kid, _)| kid).collect();
+    let apv = apv(&kids);
+    let ephemeral_secret = StaticSecret::random_from_rng(OsRng);
+    let protected = b64_encode(
+        json!({
+            "typ": DIDCOMM_ENCRYPTED_MEDIA_TYPE,
+            "alg": JWE_ALG_ECDH_ES_A256KW,
+            "enc": JWE_ENC_A256GCM,
+            "apv": b64_encode(&apv),
+            "epk": {
+                "kty": "OKP",
+                "crv": "X25519",
+                "x": b64_encode(PublicKey::from(&ephemeral_secret).as_bytes()),
+            },
+        })
+        .to_string(),
+    );
+
+    let mut cek: [u8; CEK_LENGTH] = [0; CEK_LENGTH];
+    OsRng.fill_bytes(&mut cek);
+    let mut iv: [u8; AES_GCM_IV_LENGTH] = [0; AES_GCM_IV_LENGTH];
+    OsRng.fill_bytes(&mut iv);
+
+    let recipients_json = recipients
+        .iter()
+        .map(|&(kid, public_key)| {
+            let shared_secret = ephemeral_secret.diffie_hellman(&public_key);
+            let kek = KekAes256::from(concat_kdf(shared_secret.as_bytes(), &apv)?);
+            let encrypted_key = kek
+                .wrap_vec(&cek)
+                .map_err(|err| didcomm_error(format!("Key wrapping failed: {err}")))?;
+            Ok(json!({
+                "header": { "kid": kid },
+                "encrypted_key": b64_encode(encrypted_key),
+            }))
+        })
+        .collect::<Result<Vec<_>, DidSidekicksError>>()?;
+
+    let cipher = Aes256Gcm::new_from_slice(&cek).map_err(didcomm_error)?;
+    let encrypted = cipher
+        .encrypt(
+            Nonce::from_slice(&iv),
+            Payload {
+                msg: message.as_bytes(),
+                aad: protected.as_bytes(),
+            },
+        )
+        .map_err(|err| didcomm_error(format!("Encryption failed: {err}")))?;
+    let tag_pos = encrypted
+        .len()
+        .checked_sub(AES_GCM_TAG_LENGTH)
+        .ok_or_else(|| didcomm_error("Encryption failed"))?;
+    let (ciphertext, tag) = encrypted.split_at(tag_pos);
+
+    Ok(json!({
+        "protected": protected,
+        "recipients": recipients_json,
+        "iv": b64_encode(iv),
+        "ciphertext": b64_encode(ciphertext),
+        "tag": b64_encode(tag),
+    })
+    .to_string())
+}
+
+/// Unpacks an anonymous-encrypted DIDComm envelope (as created by [`pack_anoncrypt`]), thus delivering the enclosed message.
+///
+/// The `recipient_secret_key` is the (raw) X25519 secret key matching the verification method denoted by `recipient_kid`.
+#[inline]
+pub fn unpack_anoncrypt(
+    envelope: &str,
+    recipient_kid: &str,
+    recipient_secret_key: [u8; 32],
+) -> Result<String, DidSidekicksError> {
+    let jwe: Value = from_str_bounded(envelope)
+        .map_err(|err| didcomm_error(format!("Malformed encrypted envelope: {}", err.detail())))?;
+    let protected = get_str(&jwe, "protected")?;
+    let header: Value = from_slice_bounded(&b64_decode("protected", protected)?)
+        .map_err(|err| didcomm_error(format!("Malformed protected header: {}", err.detail())))?;
+    if get_str(&header, "alg")? != JWE_ALG_ECDH_ES_A256KW || get_str(&header, "enc")? != JWE_ENC_A256GCM {
+        return Err(didcomm_error(format!(
+            "Unsupported 'alg'/'enc', expected '{JWE_ALG_ECDH_ES_A256KW}'/'{JWE_ENC_A256GCM}'"
+        )));
+    }
+    let apv = b64_decode("apv", get_str(&header, "apv")?)?;
+    let epk = header
+        .get("epk")
+        .ok_or_else(|| didcomm_error("Missing 'epk' property"))?;
+    let ephemeral_public_key = PublicKey::from(decode_key::<32>(get_str(epk, "x")?)?);
+
+    let recipient = jwe
+        .get("recipients")
+        .and_then(Value::as_array)
+        .and_then(|recipients| {
+            recipients.iter().find(|recipient| {
+                recipient
+                    .get("header")
+                    .and_then(|hdr| hdr.get("kid"))
+                    .and_then(Value::as_str)
+                    == Some(recipient_kid)
+            })
+        })
+        .ok_or_else(|| didcomm_error(format!("No recipient found for kid '{recipient_kid}'")))?;
+
+    let shared_secret = StaticSecret::from(recipient_secret_key).diffie_hellman(&ephemeral_public_key);
+    let kek = KekAes256::from(concat_kdf(shared_secret.as_bytes(), &apv)?);
+    let cek = kek
+        .unwrap_vec(&b64_decode("encrypted_key", get_str(recipient, "encrypted_key")?)?)
+        .map_err(|err| didcomm_error(format!("Key unwrapping failed: {err}")))?;
+
+    let iv = b64_decode("iv", get_str(&jwe, "iv")?)?;
+    if iv.len() != AES_GCM_IV_LENGTH {
+        return Err(didcomm_error("Invalid 'iv' length"));
+    }
+    let encrypted = [
+        b64_decode("ciphertext", get_str(&jwe, "ciphertext")?)?,
+        b64_decode("tag", get_str(&jwe, "tag")?)?,
+    ]
+    .concat();
+
+    let cipher = Aes256Gcm::new_from_slice(&cek).map_err(didcomm_error)?;
+    let plaintext = cipher
+        .decrypt(
+            Nonce::from_slice(&iv),
+            Payload {
+                msg: &encrypted,
+                aad: protected.as_bytes(),
+            },
+        )
+        .map_err(|err| didcomm_error(format!("Decryption failed: {err}")))?;
+
+    String::from_utf8(plaintext).map_err(|err| didcomm_error(format!("Malformed message: {err}")))
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::ed25519::Ed25519KeyPair;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+
+    const DID: &str = "did:example:alice";
+
+    fn did_doc(ed25519: &Ed25519KeyPair, x25519: &StaticSecret) -> DidDoc {
+        DidDoc::from_json(
+            &json!({
+                "@context": ["https://www.w3.org/ns/did/v1"],
+                "id": DID,
+                "verificationMethod": [],
+                "authentication": [{
+                    "id": format!("{DID}#key-1"),
+                    "type": "Multikey",
+                    "publicKeyMultibase": ed25519.verifying_key.to_multibase(),
+                }],
+                "keyAgreement": [{
+                    "id": format!("{DID}#key-x25519-1"),
+                    "type": "JsonWebKey2020",
+                    "publicKeyJwk": {
+                        "kty": "OKP",
+                        "crv": "X25519",
+                        "x": b64_encode(PublicKey::from(x25519).as_bytes()),
+                    },
+                }],
+            })
+            .to_string(),
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    fn test_pack_unpack_signed() {
+        let ed25519 = Ed25519KeyPair::generate();
+        let doc = did_doc(&ed25519, &StaticSecret::random_from_rng(OsRng));
+        let message = json!({"id": "1234", "type": "https://didcomm.org/basicmessage/2.0/message", "body": {}}).to_string();
+
+        let envelope = pack_signed(&message, &format!("{DID}#key-1"), &ed25519.signing_key).unwrap();
+        assert_eq!(unpack_signed(&envelope, &doc).unwrap(), message);
+
+        // signed by a different key
+        let other = Ed25519KeyPair::generate();
+        let forged_envelope =
+            pack_signed(&message, &format!("{DID}#key-1"), &other.signing_key).unwrap();
+        assert_error(
+            unpack_signed(&forged_envelope, &doc),
+            DidSidekicksErrorKind::InvalidDidCommMessage,
+            "Invalid signature",
+        );
+
+        // unknown kid
+        let unknown_kid_envelope =
+            pack_signed(&message, &format!("{DID}#key-2"), &ed25519.signing_key).unwrap();
+        assert_error(
+            unpack_signed(&unknown_kid_envelope, &doc),
+            DidSidekicksErrorKind::InvalidDidCommMessage,
+            "No 'authentication' verification method found",
+        );
+    }
+
+    #[rstest]
+    fn test_pack_unpack_anoncrypt() {
+        let x25519 = StaticSecret::random_from_rng(OsRng);
+        let doc = did_doc(&Ed25519KeyPair::generate(), &x25519);
+        let message = json!({"id": "1234", "type": "https://didcomm.org/basicmessage/2.0/message", "body": {}}).to_string();
+
+        let envelope = pack_anoncrypt(&message, &doc).unwrap();
+        let kid = format!("{DID}#key-x25519-1");
+        assert_eq!(
+            unpack_anoncrypt(&envelope, &kid, x25519.to_bytes()).unwrap(),
+            message
+        );
+
+        let other = StaticSecret::random_from_rng(OsRng);
+        assert_error(
+            unpack_anoncrypt(&envelope, &kid, other.to_bytes()),
+            DidSidekicksErrorKind::InvalidDidCommMessage,
+            "Key unwrapping failed",
+        );
+        assert_error(
+            unpack_anoncrypt(&envelope, "did:example:bob#key-1", x25519.to_bytes()),
+            DidSidekicksErrorKind::InvalidDidCommMessage,
+            "No recipient found",
+        );
+    }
+}
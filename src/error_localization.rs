@@ -0,0 +1,457 @@
+// SPDX-License-Identifier: MIT
+
+//! An error-code-to-message catalog featuring a pluggable translation hook (see [`ErrorTranslator`]).
+//!
+//! Hence, FFI consumers may map any [`DidSidekicksErrorKind`] (along with its structured params)
+//! to a localized user-facing message, without having to parse any English error message.
+
+use crate::errors::{DidSidekicksError, DidSidekicksErrorKind};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// The name of the (structured) param carrying error details, as supplied to [`ErrorTranslator::translate`].
+pub const PARAM_DETAIL: &str = "detail";
+
+/// The name of the (structured) param carrying the verification method of a proof, e.g. in case of [`DidSidekicksErrorKind::SignatureInvalid`].
+pub const PARAM_VERIFICATION_METHOD: &str = "verificationMethod";
+
+/// The name of the (structured) param carrying the expected value, e.g. in case of [`DidSidekicksErrorKind::KeyMismatch`].
+pub const PARAM_EXPECTED: &str = "expected";
+
+/// The name of the (structured) param carrying the actual value, e.g. in case of [`DidSidekicksErrorKind::KeyMismatch`].
+pub const PARAM_ACTUAL: &str = "actual";
+
+/// The name of the (structured) param carrying the expiry of a proof, in case of [`DidSidekicksErrorKind::ProofExpired`].
+pub const PARAM_EXPIRES: &str = "expires";
+
+/// The name of the (structured) param carrying the time of the expiry check, in case of [`DidSidekicksErrorKind::ProofExpired`].
+pub const PARAM_CHECKED_AT: &str = "checkedAt";
+
+/// The name of the (structured) param carrying the offending character, in case of [`DidSidekicksErrorKind::InvalidMultibaseCharacter`].
+pub const PARAM_CHARACTER: &str = "character";
+
+/// The name of the (structured) param carrying the (byte) offset of the offending character, in case of [`DidSidekicksErrorKind::InvalidMultibaseCharacter`].
+pub const PARAM_OFFSET: &str = "offset";
+
+static ERROR_TRANSLATOR: RwLock<Option<Arc<dyn ErrorTranslator>>> = RwLock::new(None);
+
+/// The locales supported by the built-in error message catalog.
+///
+/// A UniFFI-compliant enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum ErrorLocale {
+    English,
+    German,
+    French,
+    Italian,
+}
+
+/// A pluggable translation hook, taking precedence over the built-in error message catalog.
+///
+/// A UniFFI-compliant trait (that may also be implemented by foreign code).
+pub trait ErrorTranslator: Send + Sync {
+    /// Delivers a localized message for the supplied (stable) error `code` (see [`DidSidekicksErrorKind::code`])
+    /// and structured `params` (e.g. [`PARAM_DETAIL`]), if available.
+    ///
+    /// If `None` is returned, the built-in error message catalog is used instead.
+    fn translate(
+        &self,
+        code: String,
+        params: HashMap<String, String>,
+        locale: ErrorLocale,
+    ) -> Option<String>;
+}
+
+/// Registers the supplied `translator` globally, thus replacing any previously registered one.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn set_error_translator(translator: Arc<dyn ErrorTranslator>) {
+    *ERROR_TRANSLATOR
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = Some(translator);
+}
+
+/// Unregisters any previously registered [`ErrorTranslator`], so only the built-in error message catalog is used afterwards.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn clear_error_translator() {
+    *ERROR_TRANSLATOR
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+impl DidSidekicksErrorKind {
+    /// Delivers a stable (locale-independent) error code, intended to be used as a lookup key in translation catalogs.
+    #[inline]
+    pub const fn code(&self) -> &'static str {
+        match *self {
+            Self::SerializationFailed => "SERIALIZATION_FAILED",
+            Self::DeserializationFailed => "DESERIALIZATION_FAILED",
+            Self::InvalidDidDocument => "INVALID_DID_DOCUMENT",
+            Self::InvalidIntegrityProof => "INVALID_INTEGRITY_PROOF",
+            Self::InvalidDidMethodParameter => "INVALID_DID_METHOD_PARAMETER",
+            Self::KeyNotFound => "KEY_NOT_FOUND",
+            Self::NonExistingKeyReferenced => "NON_EXISTING_KEY_REFERENCED",
+            Self::InvalidWitnessProof => "INVALID_WITNESS_PROOF",
+            Self::InternalError => "INTERNAL_ERROR",
+            Self::InvalidDidCommMessage => "INVALID_DIDCOMM_MESSAGE",
+            Self::InvalidJws => "INVALID_JWS",
+            Self::KeyNotAuthorized => "KEY_NOT_AUTHORIZED",
+            Self::DuplicateJsonKey => "DUPLICATE_JSON_KEY",
+            Self::UnsupportedCryptosuite => "UNSUPPORTED_CRYPTOSUITE",
+            Self::SignatureInvalid => "SIGNATURE_INVALID",
+            Self::KeyMismatch => "KEY_MISMATCH",
+            Self::ProofExpired => "PROOF_EXPIRED",
+            Self::ChallengeMismatch => "CHALLENGE_MISMATCH",
+            Self::DomainMismatch => "DOMAIN_MISMATCH",
+            Self::InvalidMultibaseCharacter => "INVALID_MULTIBASE_CHARACTER",
+            Self::PolicyViolation => "POLICY_VIOLATION",
+        }
+    }
+
+    /// Delivers the built-in (catalog) message for the supplied `locale`. The message contains no error details.
+    #[inline]
+    #[expect(clippy::non_ascii_literal, reason = "localized messages")]
+    pub const fn catalog_message(&self, locale: ErrorLocale) -> &'static str {
+        match (*self, locale) {
+            (Self::SerializationFailed, ErrorLocale::English) => "Failed to serialize the DID document",
+            (Self::SerializationFailed, ErrorLocale::German) => "Das DID-Dokument konnte nicht serialisiert werden",
+            (Self::SerializationFailed, ErrorLocale::French) => "Échec de la sérialisation du document DID",
+            (Self::SerializationFailed, ErrorLocale::Italian) => "Impossibile serializzare il documento DID",
+            (Self::DeserializationFailed, ErrorLocale::English) => "The supplied DID document is invalid",
+            (Self::DeserializationFailed, ErrorLocale::German) => "Das angegebene DID-Dokument ist ungültig",
+            (Self::DeserializationFailed, ErrorLocale::French) => "Le document DID fourni n'est pas valide",
+            (Self::DeserializationFailed, ErrorLocale::Italian) => "Il documento DID fornito non è valido",
+            (Self::InvalidDidDocument, ErrorLocale::English) => "Invalid DID document",
+            (Self::InvalidDidDocument, ErrorLocale::German) => "Ungültiges DID-Dokument",
+            (Self::InvalidDidDocument, ErrorLocale::French) => "Document DID non valide",
+            (Self::InvalidDidDocument, ErrorLocale::Italian) => "Documento DID non valido",
+            (Self::InvalidIntegrityProof, ErrorLocale::English) => "Invalid DID log integrity proof",
+            (Self::InvalidIntegrityProof, ErrorLocale::German) => "Ungültiger Integritätsnachweis des DID-Logs",
+            (Self::InvalidIntegrityProof, ErrorLocale::French) => "Preuve d'intégrité du journal DID non valide",
+            (Self::InvalidIntegrityProof, ErrorLocale::Italian) => "Prova di integrità del registro DID non valida",
+            (Self::InvalidDidMethodParameter, ErrorLocale::English) => "Invalid DID method parameter",
+            (Self::InvalidDidMethodParameter, ErrorLocale::German) => "Ungültiger DID-Methodenparameter",
+            (Self::InvalidDidMethodParameter, ErrorLocale::French) => "Paramètre de méthode DID non valide",
+            (Self::InvalidDidMethodParameter, ErrorLocale::Italian) => "Parametro del metodo DID non valido",
+            (Self::KeyNotFound, ErrorLocale::English) => "No such key in the DID document",
+            (Self::KeyNotFound, ErrorLocale::German) => "Kein solcher Schlüssel im DID-Dokument",
+            (Self::KeyNotFound, ErrorLocale::French) => "Clé introuvable dans le document DID",
+            (Self::KeyNotFound, ErrorLocale::Italian) => "Chiave non trovata nel documento DID",
+            (Self::NonExistingKeyReferenced, ErrorLocale::English) => "Non-existing key referenced in the DID document",
+            (Self::NonExistingKeyReferenced, ErrorLocale::German) => "Im DID-Dokument wird ein nicht existierender Schlüssel referenziert",
+            (Self::NonExistingKeyReferenced, ErrorLocale::French) => "Le document DID référence une clé inexistante",
+            (Self::NonExistingKeyReferenced, ErrorLocale::Italian) => "Il documento DID fa riferimento a una chiave inesistente",
+            (Self::InvalidWitnessProof, ErrorLocale::English) => "Invalid witness proof",
+            (Self::InvalidWitnessProof, ErrorLocale::German) => "Ungültiger Zeugennachweis",
+            (Self::InvalidWitnessProof, ErrorLocale::French) => "Preuve de témoin non valide",
+            (Self::InvalidWitnessProof, ErrorLocale::Italian) => "Prova del testimone non valida",
+            (Self::InternalError, ErrorLocale::English) => "Internal error",
+            (Self::InternalError, ErrorLocale::German) => "Interner Fehler",
+            (Self::InternalError, ErrorLocale::French) => "Erreur interne",
+            (Self::InternalError, ErrorLocale::Italian) => "Errore interno",
+            (Self::InvalidDidCommMessage, ErrorLocale::English) => "Invalid DIDComm message",
+            (Self::InvalidDidCommMessage, ErrorLocale::German) => "Ungültige DIDComm-Nachricht",
+            (Self::InvalidDidCommMessage, ErrorLocale::French) => "Message DIDComm non valide",
+            (Self::InvalidDidCommMessage, ErrorLocale::Italian) => "Messaggio DIDComm non valido",
+            (Self::InvalidJws, ErrorLocale::English) => "Invalid JWS",
+            (Self::InvalidJws, ErrorLocale::German) => "Ungültige JWS",
+            (Self::InvalidJws, ErrorLocale::French) => "JWS non valide",
+            (Self::InvalidJws, ErrorLocale::Italian) => "JWS non valida",
+            (Self::KeyNotAuthorized, ErrorLocale::English) => "The key is not authorized",
+            (Self::KeyNotAuthorized, ErrorLocale::German) => "Der Schlüssel ist nicht autorisiert",
+            (Self::KeyNotAuthorized, ErrorLocale::French) => "La clé n'est pas autorisée",
+            (Self::KeyNotAuthorized, ErrorLocale::Italian) => "La chiave non è autorizzata",
+            (Self::DuplicateJsonKey, ErrorLocale::English) => "Duplicate JSON key",
+            (Self::DuplicateJsonKey, ErrorLocale::German) => "Doppelter JSON-Schlüssel",
+            (Self::DuplicateJsonKey, ErrorLocale::French) => "Clé JSON en double",
+            (Self::DuplicateJsonKey, ErrorLocale::Italian) => "Chiave JSON duplicata",
+            (Self::UnsupportedCryptosuite, ErrorLocale::English) => "Unsupported cryptosuite",
+            (Self::UnsupportedCryptosuite, ErrorLocale::German) => "Nicht unterstützte Cryptosuite",
+            (Self::UnsupportedCryptosuite, ErrorLocale::French) => "Cryptosuite non prise en charge",
+            (Self::UnsupportedCryptosuite, ErrorLocale::Italian) => "Cryptosuite non supportata",
+            (Self::SignatureInvalid, ErrorLocale::English) => "Invalid signature",
+            (Self::SignatureInvalid, ErrorLocale::German) => "Ungültige Signatur",
+            (Self::SignatureInvalid, ErrorLocale::French) => "Signature non valide",
+            (Self::SignatureInvalid, ErrorLocale::Italian) => "Firma non valida",
+            (Self::KeyMismatch, ErrorLocale::English) => "Key mismatch",
+            (Self::KeyMismatch, ErrorLocale::German) => "Der Schlüssel stimmt nicht überein",
+            (Self::KeyMismatch, ErrorLocale::French) => "La clé ne correspond pas",
+            (Self::KeyMismatch, ErrorLocale::Italian) => "La chiave non corrisponde",
+            (Self::ProofExpired, ErrorLocale::English) => "The proof has expired",
+            (Self::ProofExpired, ErrorLocale::German) => "Der Nachweis ist abgelaufen",
+            (Self::ProofExpired, ErrorLocale::French) => "La preuve a expiré",
+            (Self::ProofExpired, ErrorLocale::Italian) => "La prova è scaduta",
+            (Self::ChallengeMismatch, ErrorLocale::English) => "Challenge mismatch",
+            (Self::ChallengeMismatch, ErrorLocale::German) => "Die Challenge stimmt nicht überein",
+            (Self::ChallengeMismatch, ErrorLocale::French) => "Le challenge ne correspond pas",
+            (Self::ChallengeMismatch, ErrorLocale::Italian) => "La challenge non corrisponde",
+            (Self::DomainMismatch, ErrorLocale::English) => "Domain mismatch",
+            (Self::DomainMismatch, ErrorLocale::German) => "Die Domäne stimmt nicht überein",
+            (Self::DomainMismatch, ErrorLocale::French) => "Le domaine ne correspond pas",
+            (Self::DomainMismatch, ErrorLocale::Italian) => "Il dominio non corrisponde",
+            (Self::InvalidMultibaseCharacter, ErrorLocale::English) => "Invalid multibase character",
+            (Self::InvalidMultibaseCharacter, ErrorLocale::German) => "Ungültiges Multibase-Zeichen",
+            (Self::InvalidMultibaseCharacter, ErrorLocale::French) => "Caractère multibase non valide",
+            (Self::InvalidMultibaseCharacter, ErrorLocale::Italian) => "Carattere multibase non valido",
+            (Self::PolicyViolation, ErrorLocale::English) => "Verification policy violated",
+            (Self::PolicyViolation, ErrorLocale::German) => "Prüfrichtlinie verletzt",
+            (Self::PolicyViolation, ErrorLocale::French) => "Politique de vérification violée",
+            (Self::PolicyViolation, ErrorLocale::Italian) => "Criterio di verifica violato",
+        }
+    }
+}
+
+impl DidSidekicksError {
+    /// Delivers the error details (the very same ones supplied while creating the error).
+    ///
+    /// In case of a structured error (e.g. [`DidSidekicksError::KeyMismatch`]), the details are composed of its fields
+    /// (see [`DidSidekicksError::params`]).
+    #[inline]
+    #[expect(clippy::pattern_type_mismatch, reason = "..")]
+    pub fn detail(&self) -> Cow<'_, str> {
+        match self {
+            Self::SerializationFailed(detail)
+            | Self::DeserializationFailed(detail)
+            | Self::InvalidDidDocument(detail)
+            | Self::InvalidDataIntegrityProof(detail)
+            | Self::InvalidDidMethodParameter(detail)
+            | Self::KeyNotFound(detail)
+            | Self::NonExistingKeyReferenced(detail)
+            | Self::InvalidWitnessProof(detail)
+            | Self::InternalError(detail)
+            | Self::InvalidDidCommMessage(detail)
+            | Self::InvalidJws(detail)
+            | Self::KeyNotAuthorized(detail)
+            | Self::DuplicateJsonKey(detail)
+            | Self::UnsupportedCryptosuite(detail)
+            | Self::PolicyViolation(detail)
+            | Self::SignatureInvalid { reason: detail, .. } => Cow::Borrowed(detail),
+            Self::KeyMismatch { expected, actual }
+            | Self::ChallengeMismatch { expected, actual }
+            | Self::DomainMismatch { expected, actual } => {
+                Cow::Owned(format!("expected '{expected}', got '{actual}'"))
+            }
+            Self::ProofExpired {
+                expires,
+                checked_at,
+            } => Cow::Owned(format!("expired at {expires} (checked at {checked_at})")),
+            Self::InvalidMultibaseCharacter { character, offset } => {
+                Cow::Owned(format!("'{character}' at offset {offset}"))
+            }
+        }
+    }
+
+    /// Delivers the structured params of this error (as supplied to [`ErrorTranslator::translate`]),
+    /// i.e. [`PARAM_DETAIL`] along with the fields of a structured error (e.g. [`PARAM_EXPECTED`] and [`PARAM_ACTUAL`]).
+    #[inline]
+    #[expect(clippy::pattern_type_mismatch, reason = "..")]
+    pub fn params(&self) -> HashMap<String, String> {
+        let mut params = HashMap::from([(PARAM_DETAIL.to_owned(), self.detail().into_owned())]);
+        match self {
+            Self::SignatureInvalid {
+                verification_method,
+                ..
+            } => {
+                params.insert(
+                    PARAM_VERIFICATION_METHOD.to_owned(),
+                    verification_method.to_owned(),
+                );
+            }
+            Self::KeyMismatch { expected, actual }
+            | Self::ChallengeMismatch { expected, actual }
+            | Self::DomainMismatch { expected, actual } => {
+                params.insert(PARAM_EXPECTED.to_owned(), expected.to_owned());
+                params.insert(PARAM_ACTUAL.to_owned(), actual.to_owned());
+            }
+            Self::ProofExpired {
+                expires,
+                checked_at,
+            } => {
+                params.insert(PARAM_EXPIRES.to_owned(), expires.to_owned());
+                params.insert(PARAM_CHECKED_AT.to_owned(), checked_at.to_owned());
+            }
+            Self::InvalidMultibaseCharacter { character, offset } => {
+                params.insert(PARAM_CHARACTER.to_owned(), character.to_string());
+                params.insert(PARAM_OFFSET.to_owned(), offset.to_string());
+            }
+            Self::SerializationFailed(_)
+            | Self::DeserializationFailed(_)
+            | Self::InvalidDidDocument(_)
+            | Self::InvalidDataIntegrityProof(_)
+            | Self::InvalidDidMethodParameter(_)
+            | Self::KeyNotFound(_)
+            | Self::NonExistingKeyReferenced(_)
+            | Self::InvalidWitnessProof(_)
+            | Self::InternalError(_)
+            | Self::InvalidDidCommMessage(_)
+            | Self::InvalidJws(_)
+            | Self::KeyNotAuthorized(_)
+            | Self::DuplicateJsonKey(_)
+            | Self::UnsupportedCryptosuite(_)
+            | Self::PolicyViolation(_) => {}
+        }
+        params
+    }
+
+    /// Delivers a localized message for this error, by relying on the registered [`ErrorTranslator`] (if any)
+    /// or the built-in error message catalog otherwise.
+    #[inline]
+    pub fn localized_message(&self, locale: ErrorLocale) -> String {
+        localize(self.kind(), &self.detail(), self.params(), locale)
+    }
+}
+
+/// Delivers a localized message for the supplied error `kind` and `detail`.
+///
+/// Relies on the registered [`ErrorTranslator`] (if any) or the built-in error message catalog otherwise
+/// (in the latter case, the `detail` is appended to the message).
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn localize_error_message(
+    kind: DidSidekicksErrorKind,
+    detail: String,
+    locale: ErrorLocale,
+) -> String {
+    let params = HashMap::from([(PARAM_DETAIL.to_owned(), detail.to_owned())]);
+    localize(kind, &detail, params, locale)
+}
+
+/// The same as [`localize_error_message`], yet supplying the translator with the supplied (structured) `params`.
+fn localize(
+    kind: DidSidekicksErrorKind,
+    detail: &str,
+    params: HashMap<String, String>,
+    locale: ErrorLocale,
+) -> String {
+    let translator = ERROR_TRANSLATOR
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    if let Some(translated) = translator
+        .and_then(|registered| registered.translate(kind.code().to_owned(), params, locale))
+    {
+        return translated;
+    }
+
+    let message = kind.catalog_message(locale);
+    if detail.is_empty() {
+        return message.to_owned();
+    }
+    format!("{message}: {detail}")
+}
+
+#[cfg(test)]
+#[expect(clippy::non_ascii_literal, reason = "localized messages")]
+mod test {
+    use super::*;
+    use rstest::rstest;
+
+    struct GermanOnlyTranslator;
+    impl ErrorTranslator for GermanOnlyTranslator {
+        fn translate(
+            &self,
+            code: String,
+            params: HashMap<String, String>,
+            locale: ErrorLocale,
+        ) -> Option<String> {
+            (locale == ErrorLocale::German && code == "KEY_NOT_FOUND").then(|| {
+                format!(
+                    "Schlüssel fehlt: {}",
+                    params.get(PARAM_DETAIL).cloned().unwrap_or_default()
+                )
+            })
+        }
+    }
+
+    #[rstest]
+    #[case(ErrorLocale::English, "No such key in the DID document: auth-key-01")]
+    #[case(ErrorLocale::French, "Clé introuvable dans le document DID: auth-key-01")]
+    #[case(ErrorLocale::Italian, "Chiave non trovata nel documento DID: auth-key-01")]
+    fn test_localized_message(#[case] locale: ErrorLocale, #[case] expected: &str) {
+        let err = DidSidekicksError::KeyNotFound("auth-key-01".to_owned());
+        assert_eq!(err.localized_message(locale), expected);
+    }
+
+    #[rstest]
+    fn test_localized_message_using_translator() {
+        set_error_translator(Arc::new(GermanOnlyTranslator));
+
+        let err = DidSidekicksError::KeyNotFound("auth-key-01".to_owned());
+        assert_eq!(
+            err.localized_message(ErrorLocale::German),
+            "Schlüssel fehlt: auth-key-01"
+        );
+        // fallback to the built-in catalog
+        assert_eq!(
+            DidSidekicksError::InvalidDidDocument(String::new()).localized_message(ErrorLocale::German),
+            "Ungültiges DID-Dokument"
+        );
+
+        clear_error_translator();
+    }
+
+    #[rstest]
+    fn test_structured_error() {
+        let err = DidSidekicksError::KeyMismatch {
+            expected: "did:key:z6MkA".to_owned(),
+            actual: "did:key:z6MkB".to_owned(),
+        };
+        assert_eq!(err.kind().code(), "KEY_MISMATCH");
+        assert_eq!(
+            err.localized_message(ErrorLocale::English),
+            "Key mismatch: expected 'did:key:z6MkA', got 'did:key:z6MkB'"
+        );
+
+        let params = err.params();
+        assert_eq!(params.len(), 3);
+        assert_eq!(
+            params.get(PARAM_EXPECTED).map(String::as_str),
+            Some("did:key:z6MkA")
+        );
+        assert_eq!(
+            params.get(PARAM_ACTUAL).map(String::as_str),
+            Some("did:key:z6MkB")
+        );
+
+        let invalid_signature = DidSidekicksError::SignatureInvalid {
+            verification_method: "did:key:z6MkA#z6MkA".to_owned(),
+            reason: "Verification equation was not satisfied".to_owned(),
+        };
+        assert_eq!(
+            invalid_signature.detail(),
+            "Verification equation was not satisfied"
+        );
+        assert_eq!(
+            invalid_signature
+                .params()
+                .get(PARAM_VERIFICATION_METHOD)
+                .map(String::as_str),
+            Some("did:key:z6MkA#z6MkA")
+        );
+
+        let invalid_character = DidSidekicksError::InvalidMultibaseCharacter {
+            character: '0',
+            offset: 7,
+        };
+        assert_eq!(
+            invalid_character.localized_message(ErrorLocale::German),
+            "Ungültiges Multibase-Zeichen: '0' at offset 7"
+        );
+        assert_eq!(
+            invalid_character
+                .params()
+                .get(PARAM_OFFSET)
+                .map(String::as_str),
+            Some("7")
+        );
+    }
+}
@@ -4,37 +4,83 @@
 
 /// Yet another UniFFI-compliant error.
 ///
-/// Resembles ssi::dids::resolution::Error
+/// Resembles ssi::dids::resolution::Error.
 #[derive(Debug, thiserror::Error, PartialEq, Eq)]
 #[expect(
     clippy::exhaustive_enums,
     reason = "further enum variants may be added in the future"
 )]
 pub enum DidSidekicksError {
-    /// Failed to serialize ID document (to JSON)
+    /// Failed to serialize ID document (to JSON).
     #[error("failed to serialize DID document (to JSON): {0}")]
     SerializationFailed(String),
-    /// The supplied DID document is invalid or contains an argument which isn't part of the did specification/recommendation
+    /// The supplied DID document is invalid or contains an argument which isn't part of the did specification/recommendation.
     #[error("the supplied DID document is invalid or contains an argument which isn't part of the did specification/recommendation: {0}"
     )]
     DeserializationFailed(String),
-    /// Invalid DID document
+    /// Invalid DID document.
     #[error("invalid DID document: {0}")]
     InvalidDidDocument(String),
-    /// Invalid DID log integration proof
+    /// Invalid DID log integration proof.
     #[error("invalid DID log integration proof: {0}")]
     InvalidDataIntegrityProof(String),
-    /// Invalid DID method parameter
+    /// Invalid DID method parameter.
     #[error("invalid DID method parameter: {0}")]
     InvalidDidMethodParameter(String),
-    /// No such JWK in the DID document
+    /// No such JWK in the DID document.
     #[error("no such JWK in the DID document: {0}")]
     KeyNotFound(String),
-    /// Non-existing key referenced in the DID document
+    /// Non-existing key referenced in the DID document.
     #[error("non-existing key referenced in the DID document: {0}")]
     NonExistingKeyReferenced(String),
+    /// Invalid (or insufficient) witness proof.
+    #[error("invalid witness proof: {0}")]
+    InvalidWitnessProof(String),
+    /// An unexpected internal error (e.g. a panic caught at the FFI boundary).
+    #[error("internal error: {0}")]
+    InternalError(String),
+    /// Invalid DIDComm message (envelope).
+    #[error("invalid DIDComm message: {0}")]
+    InvalidDidCommMessage(String),
+    /// Invalid (or unverifiable) JWS.
+    #[error("invalid JWS: {0}")]
+    InvalidJws(String),
+    /// The key is not authorized (e.g. not listed in the active updateKeys).
+    #[error("the key is not authorized: {0}")]
+    KeyNotAuthorized(String),
+    /// Duplicate JSON key detected (in strict parsing mode).
+    #[error("duplicate JSON key: {0}")]
+    DuplicateJsonKey(String),
+    /// Unsupported (e.g. unknown or future) cryptosuite, denoted by its name.
+    #[error("unsupported cryptosuite: {0}")]
+    UnsupportedCryptosuite(String),
+    /// The signature of a proof does not verify (against the key denoted by its verification method).
+    #[error("invalid signature by '{verification_method}': {reason}")]
+    SignatureInvalid {
+        verification_method: String,
+        reason: String,
+    },
+    /// The key (e.g. the signer of a proof) does not match the expected one.
+    #[error("key mismatch: expected '{expected}', got '{actual}'")]
+    KeyMismatch { expected: String, actual: String },
+    /// The proof has expired.
+    #[error("proof expired at {expires} (checked at {checked_at})")]
+    ProofExpired { expires: String, checked_at: String },
+    /// The challenge of a proof does not match the expected one.
+    #[error("challenge mismatch: expected '{expected}', got '{actual}'")]
+    ChallengeMismatch { expected: String, actual: String },
+    /// The domain of a proof does not match the expected one.
+    #[error("domain mismatch: expected '{expected}', got '{actual}'")]
+    DomainMismatch { expected: String, actual: String },
+    /// Invalid character (w.r.t. the alphabet) in a multibase string, denoted by its (byte) offset (in strict validation mode).
+    #[error("invalid multibase character '{character}' at offset {offset}")]
+    InvalidMultibaseCharacter { character: char, offset: usize },
+    /// The (declarative) verification policy is violated (see [`crate::verification_policy::VerificationPolicy`]).
+    #[error("verification policy violated: {0}")]
+    PolicyViolation(String),
 }
 
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
 impl DidSidekicksError {
     /// Returns the error kind.
     #[inline]
@@ -47,6 +93,22 @@ impl DidSidekicksError {
             Self::InvalidDidMethodParameter(_) => DidSidekicksErrorKind::InvalidDidMethodParameter,
             Self::KeyNotFound(_) => DidSidekicksErrorKind::KeyNotFound,
             Self::NonExistingKeyReferenced(_) => DidSidekicksErrorKind::NonExistingKeyReferenced,
+            Self::InvalidWitnessProof(_) => DidSidekicksErrorKind::InvalidWitnessProof,
+            Self::InternalError(_) => DidSidekicksErrorKind::InternalError,
+            Self::InvalidDidCommMessage(_) => DidSidekicksErrorKind::InvalidDidCommMessage,
+            Self::InvalidJws(_) => DidSidekicksErrorKind::InvalidJws,
+            Self::KeyNotAuthorized(_) => DidSidekicksErrorKind::KeyNotAuthorized,
+            Self::DuplicateJsonKey(_) => DidSidekicksErrorKind::DuplicateJsonKey,
+            Self::UnsupportedCryptosuite(_) => DidSidekicksErrorKind::UnsupportedCryptosuite,
+            Self::SignatureInvalid { .. } => DidSidekicksErrorKind::SignatureInvalid,
+            Self::KeyMismatch { .. } => DidSidekicksErrorKind::KeyMismatch,
+            Self::ProofExpired { .. } => DidSidekicksErrorKind::ProofExpired,
+            Self::ChallengeMismatch { .. } => DidSidekicksErrorKind::ChallengeMismatch,
+            Self::DomainMismatch { .. } => DidSidekicksErrorKind::DomainMismatch,
+            Self::InvalidMultibaseCharacter { .. } => {
+                DidSidekicksErrorKind::InvalidMultibaseCharacter
+            }
+            Self::PolicyViolation(_) => DidSidekicksErrorKind::PolicyViolation,
         }
     }
 }
@@ -67,6 +129,20 @@ pub enum DidSidekicksErrorKind {
     InvalidDidMethodParameter,
     KeyNotFound,
     NonExistingKeyReferenced,
+    InvalidWitnessProof,
+    InternalError,
+    InvalidDidCommMessage,
+    InvalidJws,
+    KeyNotAuthorized,
+    DuplicateJsonKey,
+    UnsupportedCryptosuite,
+    SignatureInvalid,
+    KeyMismatch,
+    ProofExpired,
+    ChallengeMismatch,
+    DomainMismatch,
+    InvalidMultibaseCharacter,
+    PolicyViolation,
 }
 
 /// The error accompanying [`DidResolver`] trait.
@@ -78,25 +154,49 @@ pub enum DidSidekicksErrorKind {
     reason = "further enum variants may be added in the future"
 )]
 pub enum DidResolverError {
-    /// Invalid method-specific identifier
+    /// Invalid method-specific identifier.
     #[error("invalid method specific identifier: {0}")]
     InvalidMethodSpecificId(String),
-    /// Failed to serialize DID document (to JSON)
+    /// Failed to serialize DID document (to JSON).
     #[error("failed to serialize DID document (to JSON): {0}")]
     SerializationFailed(String),
-    /// The supplied DID document is invalid or contains an argument which isn't part of the did specification/recommendation
+    /// The supplied DID document is invalid or contains an argument which isn't part of the did specification/recommendation.
     #[error("the supplied DID document is invalid or contains an argument which isn't part of the did specification/recommendation: {0}"
     )]
     DeserializationFailed(String),
-    /// Invalid DID parameter
+    /// Invalid DID parameter.
     #[error("invalid DID parameter: {0}")]
     InvalidDidParameter(String),
-    /// Invalid DID document
+    /// Invalid DID document.
     #[error("invalid DID document: {0}")]
     InvalidDidDocument(String),
-    /// Invalid DID log integration proof
+    /// Invalid DID log integration proof.
     #[error("invalid DID log integration proof: {0}")]
     InvalidDataIntegrityProof(String),
+    /// Unsupported (e.g. unknown or future) cryptosuite, denoted by its name.
+    #[error("unsupported cryptosuite: {0}")]
+    UnsupportedCryptosuite(String),
+    /// The signature of a proof does not verify (against the key denoted by its verification method).
+    #[error("invalid signature by '{verification_method}': {reason}")]
+    SignatureInvalid {
+        verification_method: String,
+        reason: String,
+    },
+    /// The key (e.g. the signer of a proof) does not match the expected one.
+    #[error("key mismatch: expected '{expected}', got '{actual}'")]
+    KeyMismatch { expected: String, actual: String },
+    /// The proof has expired.
+    #[error("proof expired at {expires} (checked at {checked_at})")]
+    ProofExpired { expires: String, checked_at: String },
+    /// The challenge of a proof does not match the expected one.
+    #[error("challenge mismatch: expected '{expected}', got '{actual}'")]
+    ChallengeMismatch { expected: String, actual: String },
+    /// The domain of a proof does not match the expected one.
+    #[error("domain mismatch: expected '{expected}', got '{actual}'")]
+    DomainMismatch { expected: String, actual: String },
+    /// An unexpected internal error (e.g. a caught panic) occurred during resolution.
+    #[error("internal error: {0}")]
+    InternalError(String),
 }
 
 impl DidResolverError {
@@ -110,6 +210,13 @@ impl DidResolverError {
             Self::InvalidDidParameter(_) => DidResolverErrorKind::InvalidDidParameter,
             Self::InvalidDidDocument(_) => DidResolverErrorKind::InvalidDidDocument,
             Self::InvalidDataIntegrityProof(_) => DidResolverErrorKind::InvalidIntegrityProof,
+            Self::UnsupportedCryptosuite(_) => DidResolverErrorKind::UnsupportedCryptosuite,
+            Self::SignatureInvalid { .. } => DidResolverErrorKind::SignatureInvalid,
+            Self::KeyMismatch { .. } => DidResolverErrorKind::KeyMismatch,
+            Self::ProofExpired { .. } => DidResolverErrorKind::ProofExpired,
+            Self::ChallengeMismatch { .. } => DidResolverErrorKind::ChallengeMismatch,
+            Self::DomainMismatch { .. } => DidResolverErrorKind::DomainMismatch,
+            Self::InternalError(_) => DidResolverErrorKind::InternalError,
         }
     }
 }
@@ -129,4 +236,209 @@ pub enum DidResolverErrorKind {
     InvalidDidParameter,
     InvalidDidDocument,
     InvalidIntegrityProof,
+    UnsupportedCryptosuite,
+    SignatureInvalid,
+    KeyMismatch,
+    ProofExpired,
+    ChallengeMismatch,
+    DomainMismatch,
+    InternalError,
+}
+
+/// The base of the (standardized) error type URIs, as specified by https://w3c.github.io/did-resolution/#errors.
+pub const DID_RESOLUTION_ERROR_URI_BASE: &str = "https://www.w3.org/ns/did#";
+
+/// The errors standardized by the DID Resolution specification (see https://w3c.github.io/did-resolution/#errors).
+///
+/// Each of them is denoted by an error type URI (e.g. `https://www.w3.org/ns/did#INVALID_DID`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum DidResolutionErrorType {
+    InvalidDid,
+    InvalidDidUrl,
+    NotFound,
+    RepresentationNotSupported,
+    MethodNotSupported,
+    InvalidDidDocument,
+    InvalidOptions,
+    InternalError,
+}
+
+impl DidResolutionErrorType {
+    const ALL: [Self; 8] = [
+        Self::InvalidDid,
+        Self::InvalidDidUrl,
+        Self::NotFound,
+        Self::RepresentationNotSupported,
+        Self::MethodNotSupported,
+        Self::InvalidDidDocument,
+        Self::InvalidOptions,
+        Self::InternalError,
+    ];
+
+    /// Delivers the (standardized) error code, e.g. `INVALID_DID`.
+    #[inline]
+    pub const fn code(&self) -> &'static str {
+        match *self {
+            Self::InvalidDid => "INVALID_DID",
+            Self::InvalidDidUrl => "INVALID_DID_URL",
+            Self::NotFound => "NOT_FOUND",
+            Self::RepresentationNotSupported => "REPRESENTATION_NOT_SUPPORTED",
+            Self::MethodNotSupported => "METHOD_NOT_SUPPORTED",
+            Self::InvalidDidDocument => "INVALID_DID_DOCUMENT",
+            Self::InvalidOptions => "INVALID_OPTIONS",
+            Self::InternalError => "INTERNAL_ERROR",
+        }
+    }
+
+    /// Delivers the legacy (camelCase) error code, as used by earlier drafts of the specification, e.g. `invalidDid`.
+    #[inline]
+    pub const fn legacy_code(&self) -> &'static str {
+        match *self {
+            Self::InvalidDid => "invalidDid",
+            Self::InvalidDidUrl => "invalidDidUrl",
+            Self::NotFound => "notFound",
+            Self::RepresentationNotSupported => "representationNotSupported",
+            Self::MethodNotSupported => "methodNotSupported",
+            Self::InvalidDidDocument => "invalidDidDocument",
+            Self::InvalidOptions => "invalidOptions",
+            Self::InternalError => "internalError",
+        }
+    }
+
+    /// Delivers the (standardized) error type URI, e.g. `https://www.w3.org/ns/did#INVALID_DID`.
+    #[inline]
+    pub fn uri(&self) -> String {
+        format!("{DID_RESOLUTION_ERROR_URI_BASE}{}", self.code())
+    }
+
+    /// Parses the supplied `error` (e.g. as delivered by a remote resolver), while accepting any of
+    /// the error type URI (`https://www.w3.org/ns/did#INVALID_DID`), the bare error code (`INVALID_DID`)
+    /// or the legacy error code (`invalidDid`). Returns `None` if the `error` is not standardized.
+    #[inline]
+    pub fn from_spec_error(error: &str) -> Option<Self> {
+        let code = error
+            .strip_prefix(DID_RESOLUTION_ERROR_URI_BASE)
+            .unwrap_or(error);
+        Self::ALL
+            .into_iter()
+            .find(|error_type| error_type.code() == code || error_type.legacy_code() == code)
+    }
+
+    /// Delivers the matching [`DidResolverErrorKind`], if any.
+    #[inline]
+    pub const fn as_resolver_error_kind(&self) -> Option<DidResolverErrorKind> {
+        match *self {
+            Self::InvalidDid => Some(DidResolverErrorKind::InvalidMethodSpecificId),
+            Self::InvalidDidUrl => Some(DidResolverErrorKind::InvalidDidParameter),
+            Self::InvalidDidDocument => Some(DidResolverErrorKind::InvalidDidDocument),
+            Self::InternalError => Some(DidResolverErrorKind::InternalError),
+            Self::NotFound
+            | Self::RepresentationNotSupported
+            | Self::MethodNotSupported
+            | Self::InvalidOptions => None,
+        }
+    }
+}
+
+impl DidResolverErrorKind {
+    /// Delivers the matching error standardized by the DID Resolution specification
+    /// (see https://w3c.github.io/did-resolution/#errors), e.g. to be reported by a spec-conformant resolver (gateway).
+    #[inline]
+    pub const fn as_spec_error(&self) -> DidResolutionErrorType {
+        match *self {
+            Self::InvalidMethodSpecificId => DidResolutionErrorType::InvalidDid,
+            Self::InvalidDidParameter => DidResolutionErrorType::InvalidDidUrl,
+            Self::DeserializationFailed
+            | Self::InvalidDidDocument
+            | Self::InvalidIntegrityProof
+            | Self::UnsupportedCryptosuite
+            | Self::SignatureInvalid
+            | Self::KeyMismatch
+            | Self::ProofExpired
+            | Self::ChallengeMismatch
+            | Self::DomainMismatch => DidResolutionErrorType::InvalidDidDocument,
+            Self::SerializationFailed | Self::InternalError => {
+                DidResolutionErrorType::InternalError
+            }
+        }
+    }
+}
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
+impl DidResolverError {
+    /// Converts the supplied `error` (as delivered by a remote resolver, see [`DidResolutionErrorType::from_spec_error`])
+    /// into a [`DidResolverError`] featuring the supplied `detail`.
+    ///
+    /// Returns `None` if the `error` is not standardized or there is no matching [`DidResolverError`] (e.g. `NOT_FOUND`).
+    #[inline]
+    pub fn from_spec_error(error: &str, detail: String) -> Option<Self> {
+        match DidResolutionErrorType::from_spec_error(error)?.as_resolver_error_kind()? {
+            DidResolverErrorKind::InvalidMethodSpecificId => {
+                Some(Self::InvalidMethodSpecificId(detail))
+            }
+            DidResolverErrorKind::InvalidDidParameter => Some(Self::InvalidDidParameter(detail)),
+            DidResolverErrorKind::InvalidDidDocument => Some(Self::InvalidDidDocument(detail)),
+            DidResolverErrorKind::InternalError => Some(Self::InternalError(detail)),
+            DidResolverErrorKind::SerializationFailed
+            | DidResolverErrorKind::DeserializationFailed
+            | DidResolverErrorKind::InvalidIntegrityProof
+            | DidResolverErrorKind::UnsupportedCryptosuite
+            | DidResolverErrorKind::SignatureInvalid
+            | DidResolverErrorKind::KeyMismatch
+            | DidResolverErrorKind::ProofExpired
+            | DidResolverErrorKind::ChallengeMismatch
+            | DidResolverErrorKind::DomainMismatch => None,
+        }
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("https://www.w3.org/ns/did#INVALID_DID", Some(DidResolutionErrorType::InvalidDid))]
+    #[case("INVALID_DID_URL", Some(DidResolutionErrorType::InvalidDidUrl))]
+    #[case("notFound", Some(DidResolutionErrorType::NotFound))]
+    #[case("https://www.w3.org/ns/did#methodNotSupported", Some(DidResolutionErrorType::MethodNotSupported))]
+    #[case("https://example.com#INVALID_DID", None)]
+    #[case("invalid_did", None)]
+    fn test_from_spec_error(#[case] error: &str, #[case] expected: Option<DidResolutionErrorType>) {
+        assert_eq!(DidResolutionErrorType::from_spec_error(error), expected);
+    }
+
+    #[rstest]
+    fn test_spec_error_roundtrip() {
+        for error_type in DidResolutionErrorType::ALL {
+            assert_eq!(
+                DidResolutionErrorType::from_spec_error(&error_type.uri()),
+                Some(error_type)
+            );
+        }
+
+        let err = DidResolverError::InvalidMethodSpecificId("did:example".to_owned());
+        let spec_error = err.kind().as_spec_error();
+        assert_eq!(spec_error.uri(), "https://www.w3.org/ns/did#INVALID_DID");
+        assert_eq!(
+            DidResolverError::from_spec_error(&spec_error.uri(), "did:example".to_owned()).unwrap(),
+            err
+        );
+        assert_eq!(
+            DidResolverError::from_spec_error("NOT_FOUND", "did:example:123".to_owned()),
+            None
+        );
+        assert_eq!(
+            DidResolverError::from_spec_error("INTERNAL_ERROR", "panic".to_owned()).unwrap(),
+            DidResolverError::InternalError("panic".to_owned())
+        );
+    }
 }
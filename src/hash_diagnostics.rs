@@ -0,0 +1,364 @@
+// SPDX-License-Identifier: MIT
+
+//! Diagnostics of (JCS) hashes produced by other implementations, e.g. of a partner vendor (cross-implementation check mode).
+//!
+//! Given a JSON document along with the hash claimed by another implementation, all the plausible variants of the hash
+//! (see [`HashCanonicalization`], [`MultihashAlgorithm`] and [`HashEncoding`]) are computed, thus revealing whether
+//! the mismatch (if any) stems from canonicalization, digest choice or encoding (see [`diagnose_jcs_hash`]).
+
+use crate::bounded_json::from_str_bounded;
+use crate::errors::DidSidekicksError;
+use crate::jcs_sha256_hasher::strip_colon_ff;
+use crate::multibase::MultibaseEncoderDecoder;
+use crate::multihash::{Multihash, MultihashAlgorithm};
+use crate::panic_boundary::catch_panic;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine as _;
+use serde_json::Value;
+use serde_json_canonicalizer::to_string as jcs_to_string;
+
+/// The way a JSON document is turned into the bytes to be hashed.
+///
+/// A UniFFI-compliant enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum HashCanonicalization {
+    /// JCS as applied by this crate (see [`crate::jcs_sha256_hasher::JcsSha256Hasher::base58btc_encode_multihash`]),
+    /// i.e. including the workaround replacing any `:ff` by `:`.
+    Jcs,
+    /// JCS strictly as specified by https://www.rfc-editor.org/rfc/rfc8785, i.e. lacking the `:ff` workaround.
+    JcsStrict,
+    /// Compact (minified) JSON retaining the order of the object members, i.e. no canonicalization at all.
+    Compact,
+    /// The JSON text exactly as supplied.
+    Verbatim,
+}
+
+impl HashCanonicalization {
+    /// All the canonicalizations, the one applied by this crate first.
+    pub const ALL: [Self; 4] = [Self::Jcs, Self::JcsStrict, Self::Compact, Self::Verbatim];
+}
+
+/// The textual encoding of a hash.
+///
+/// A UniFFI-compliant enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum HashEncoding {
+    /// Base58btc-encoded multihash lacking any multibase identifier, e.g. an `entryHash` or SCID (`Qm...` in case of sha2-256).
+    Base58btcMultihash,
+    /// Multibase (base58btc) encoded multihash, e.g. a `digestMultibase` value (`zQm...` in case of sha2-256).
+    MultibaseMultihash,
+    /// Base58btc-encoded (bare) digest.
+    Base58btc,
+    /// Lowercase hexadecimal (bare) digest.
+    Hex,
+    /// Uppercase hexadecimal (bare) digest.
+    HexUpper,
+    /// Lowercase hexadecimal multihash.
+    HexMultihash,
+    /// Base64 (https://www.rfc-editor.org/rfc/rfc4648#section-4, padded) encoded (bare) digest, e.g. as featured by `digestSRI` values.
+    Base64,
+    /// Base64url (https://www.rfc-editor.org/rfc/rfc4648#section-5, unpadded) encoded (bare) digest.
+    Base64Url,
+}
+
+impl HashEncoding {
+    /// All the encodings, the one applied by this crate first.
+    pub const ALL: [Self; 8] = [
+        Self::Base58btcMultihash,
+        Self::MultibaseMultihash,
+        Self::Base58btc,
+        Self::Hex,
+        Self::HexUpper,
+        Self::HexMultihash,
+        Self::Base64,
+        Self::Base64Url,
+    ];
+
+    fn encode(self, multihash: &Multihash) -> String {
+        let multibase = MultibaseEncoderDecoder::default();
+        match self {
+            Self::Base58btcMultihash => multibase
+                .encode_base58btc(&multihash.to_bytes())
+                .split_off(1),
+            Self::MultibaseMultihash => multibase.encode_base58btc(&multihash.to_bytes()),
+            Self::Base58btc => multibase.encode_base58btc(multihash.digest()).split_off(1),
+            Self::Hex => hex::encode(multihash.digest()),
+            Self::HexUpper => hex::encode_upper(multihash.digest()),
+            Self::HexMultihash => hex::encode(multihash.to_bytes()),
+            Self::Base64 => STANDARD.encode(multihash.digest()),
+            Self::Base64Url => URL_SAFE_NO_PAD.encode(multihash.digest()),
+        }
+    }
+}
+
+/// A single variant of a hash, i.e. a combination of canonicalization, digest and encoding.
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct HashVariant {
+    pub canonicalization: HashCanonicalization,
+    pub digest: MultihashAlgorithm,
+    pub encoding: HashEncoding,
+}
+
+impl HashVariant {
+    /// The variant applied by this crate, i.e. the base58btc-encoded sha2-256 multihash of the JCS form (e.g. an `entryHash`).
+    pub const REFERENCE: Self = Self {
+        canonicalization: HashCanonicalization::Jcs,
+        digest: MultihashAlgorithm::Sha2_256,
+        encoding: HashEncoding::Base58btcMultihash,
+    };
+
+    /// Delivers the aspects this variant differs in from the [reference](Self::REFERENCE) one.
+    #[expect(clippy::single_call_fn, reason = "..")]
+    fn causes(self) -> Vec<HashMismatchCause> {
+        let reference = Self::REFERENCE;
+        [
+            (
+                self.canonicalization != reference.canonicalization,
+                HashMismatchCause::Canonicalization,
+            ),
+            (self.digest != reference.digest, HashMismatchCause::Digest),
+            (
+                self.encoding != reference.encoding,
+                HashMismatchCause::Encoding,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(differs, cause)| differs.then_some(cause))
+        .collect()
+    }
+}
+
+/// The aspect a claimed hash differs in from the one computed by this crate.
+///
+/// A UniFFI-compliant enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum HashMismatchCause {
+    /// The document has been canonicalized differently (see [`HashCanonicalization`]).
+    Canonicalization,
+    /// Another hash algorithm has been used (see [`MultihashAlgorithm`]).
+    Digest,
+    /// The hash has been encoded differently (see [`HashEncoding`]).
+    Encoding,
+}
+
+/// The outcome of [`diagnose_jcs_hash`].
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct HashDiagnosis {
+    /// The hash as computed by this crate (see [`HashVariant::REFERENCE`]).
+    pub expected_hash: String,
+    /// The variant matching the claimed hash (the one closest to the reference, if several match),
+    /// whereas `None` denotes a hash that cannot be explained by any variant (e.g. another document has been hashed).
+    pub matching_variant: Option<HashVariant>,
+    /// The aspects the matching variant differs in from the reference one, hence empty in case of matching hashes.
+    pub causes: Vec<HashMismatchCause>,
+}
+
+impl HashDiagnosis {
+    /// Whether the claimed hash matches the one computed by this crate.
+    #[inline]
+    pub fn is_match(&self) -> bool {
+        self.matching_variant == Some(HashVariant::REFERENCE)
+    }
+}
+
+/// Diagnoses the supplied `claimed_hash` of the supplied `json` document, as produced by another implementation,
+/// by computing all the plausible variants of the hash (see [`HashVariant`]).
+///
+/// Surrounding whitespace of the `claimed_hash` is ignored, whereas the comparison is case-sensitive otherwise.
+/// Any JSON text that is not deserializable is denoted by [`DidSidekicksError::DeserializationFailed`].
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn diagnose_jcs_hash(
+    json: String,
+    claimed_hash: String,
+) -> Result<HashDiagnosis, DidSidekicksError> {
+    catch_panic(
+        || {
+            let value: Value = from_str_bounded(&json)?;
+            let claimed = claimed_hash.trim();
+
+            let mut expected_hash = String::new();
+            let mut matching: Option<HashVariant> = None;
+            for canonicalization in HashCanonicalization::ALL {
+                let bytes = canonicalize(&value, &json, canonicalization)?;
+                for digest in MultihashAlgorithm::ALL {
+                    let multihash = Multihash::hash(digest, &bytes);
+                    for encoding in HashEncoding::ALL {
+                        let variant = HashVariant {
+                            canonicalization,
+                            digest,
+                            encoding,
+                        };
+                        let hash = encoding.encode(&multihash);
+                        if variant == HashVariant::REFERENCE {
+                            expected_hash.clone_from(&hash);
+                        }
+                        // the variant closest to the reference one wins, the first one in case of a tie
+                        if hash == claimed
+                            && matching.is_none_or(|current| {
+                                variant.causes().len() < current.causes().len()
+                            })
+                        {
+                            matching = Some(variant);
+                        }
+                    }
+                }
+            }
+
+            Ok(HashDiagnosis {
+                expected_hash,
+                matching_variant: matching,
+                causes: matching.map(HashVariant::causes).unwrap_or_default(),
+            })
+        },
+        DidSidekicksError::InternalError,
+    )
+}
+
+#[expect(clippy::single_call_fn, reason = "..")]
+fn canonicalize(
+    value: &Value,
+    json: &str,
+    canonicalization: HashCanonicalization,
+) -> Result<Vec<u8>, DidSidekicksError> {
+    let serialization_failed =
+        |err: serde_json::Error| DidSidekicksError::SerializationFailed(err.to_string());
+    match canonicalization {
+        HashCanonicalization::Jcs => {
+            let mut bytes = jcs_to_string(value)
+                .map_err(serialization_failed)?
+                .into_bytes();
+            strip_colon_ff(&mut bytes);
+            Ok(bytes)
+        }
+        HashCanonicalization::JcsStrict => jcs_to_string(value)
+            .map(String::into_bytes)
+            .map_err(serialization_failed),
+        HashCanonicalization::Compact => serde_json::to_vec(value).map_err(serialization_failed),
+        HashCanonicalization::Verbatim => Ok(json.as_bytes().to_vec()),
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::jcs_sha256_hasher::JcsSha256Hasher;
+    use crate::test::assert_error;
+    use rstest::rstest;
+    use sha2::{Digest as _, Sha256, Sha384};
+
+    const JSON: &str = r#"{"b": "x:ffy", "a": 1}"#;
+    const JCS: &str = r#"{"a":1,"b":"x:y"}"#;
+    const JCS_STRICT: &str = r#"{"a":1,"b":"x:ffy"}"#;
+    const COMPACT: &str = r#"{"b":"x:ffy","a":1}"#;
+
+    #[rstest]
+    fn test_diagnose_matching_hash() {
+        let expected = JcsSha256Hasher::default()
+            .base58btc_encode_multihash(&serde_json::from_str(JSON).unwrap())
+            .unwrap();
+        let diagnosis = diagnose_jcs_hash(JSON.to_owned(), format!(" {expected}\n")).unwrap();
+        assert!(diagnosis.is_match());
+        assert_eq!(diagnosis.expected_hash, expected);
+        assert!(diagnosis.causes.is_empty());
+    }
+
+    #[rstest]
+    #[case(
+        hex::encode(Sha256::digest(JCS)),
+        HashCanonicalization::Jcs,
+        MultihashAlgorithm::Sha2_256,
+        HashEncoding::Hex,
+        vec![HashMismatchCause::Encoding]
+    )]
+    #[case(
+        MultibaseEncoderDecoder::default()
+            .encode_base58btc(&Multihash::hash(MultihashAlgorithm::Sha2_256, JCS_STRICT.as_bytes()).to_bytes())
+            .split_off(1),
+        HashCanonicalization::JcsStrict,
+        MultihashAlgorithm::Sha2_256,
+        HashEncoding::Base58btcMultihash,
+        vec![HashMismatchCause::Canonicalization]
+    )]
+    #[case(
+        MultibaseEncoderDecoder::default()
+            .encode_base58btc(&Multihash::hash(MultihashAlgorithm::Blake3, JCS.as_bytes()).to_bytes())
+            .split_off(1),
+        HashCanonicalization::Jcs,
+        MultihashAlgorithm::Blake3,
+        HashEncoding::Base58btcMultihash,
+        vec![HashMismatchCause::Digest]
+    )]
+    #[case(
+        STANDARD.encode(Sha384::digest(COMPACT)),
+        HashCanonicalization::Compact,
+        MultihashAlgorithm::Sha2_384,
+        HashEncoding::Base64,
+        vec![HashMismatchCause::Canonicalization, HashMismatchCause::Digest, HashMismatchCause::Encoding]
+    )]
+    #[case(
+        hex::encode_upper(Sha256::digest(JSON)),
+        HashCanonicalization::Verbatim,
+        MultihashAlgorithm::Sha2_256,
+        HashEncoding::HexUpper,
+        vec![HashMismatchCause::Canonicalization, HashMismatchCause::Encoding]
+    )]
+    fn test_diagnose_mismatching_hash(
+        #[case] claimed_hash: String,
+        #[case] canonicalization: HashCanonicalization,
+        #[case] digest: MultihashAlgorithm,
+        #[case] encoding: HashEncoding,
+        #[case] causes: Vec<HashMismatchCause>,
+    ) {
+        let diagnosis = diagnose_jcs_hash(JSON.to_owned(), claimed_hash).unwrap();
+        assert!(!diagnosis.is_match());
+        assert_eq!(
+            diagnosis.matching_variant,
+            Some(HashVariant {
+                canonicalization,
+                digest,
+                encoding,
+            })
+        );
+        assert_eq!(diagnosis.causes, causes);
+    }
+
+    #[rstest]
+    fn test_diagnose_unexplainable_hash() {
+        let diagnosis =
+            diagnose_jcs_hash(JSON.to_owned(), hex::encode(Sha256::digest("{}"))).unwrap();
+        assert!(!diagnosis.is_match());
+        assert_eq!(diagnosis.matching_variant, None);
+        assert!(diagnosis.causes.is_empty());
+
+        assert_error(
+            diagnose_jcs_hash("{".to_owned(), String::new()),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "",
+        );
+    }
+}
@@ -0,0 +1,366 @@
+// SPDX-License-Identifier: MIT
+
+//! Issuance of verifiable credentials bound to a particular state (version) of the issuer DID.
+//!
+//! When adding a proof (see [`IssuanceContext::add_proof`]), the `versionId` and `versionTime` of the DID log entry
+//! the issuer DID document was resolved from are embedded into the credential (as [`ISSUER_DID_STATE`] member),
+//! thus covered by the proof. Hence, a verifier may resolve the issuer DID at that very version (see [`IssuerDidState::from_credential`])
+//! and check the credential against the DID state at the time of issuance (see [`IssuanceContext::verify`]),
+//! regardless of any later key rotation.
+
+use crate::did_doc::DidDoc;
+use crate::ed25519::{
+    did_key_verification_method_from_verifying_key, Ed25519KeyPair, MultiBaseConverter as _,
+    VerificationOptions,
+};
+use crate::errors::DidSidekicksError;
+use crate::key_interop::verifying_key_from_verification_method;
+#[cfg(feature = "jsonschema-validation")]
+use crate::trust_registry::{TrustRegistry, TRUST_ROLE_ISSUER};
+use crate::vc_data_integrity::{
+    verify_secured_document, CryptoSuiteProofOptions, EddsaJcs2022Cryptosuite, VCDataIntegrity as _,
+};
+#[cfg(feature = "jsonschema-validation")]
+use crate::verification_policy::VerificationPolicy;
+use crate::version_id::VersionId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+#[cfg(feature = "jsonschema-validation")]
+use std::collections::HashMap;
+#[cfg(feature = "jsonschema-validation")]
+use std::sync::Arc;
+
+/// The name of the credential member featuring the [`IssuerDidState`].
+pub const ISSUER_DID_STATE: &str = "issuerDidState";
+
+/// The state (version) of the issuer DID, as embedded into a credential.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct IssuerDidState {
+    /// The `versionId` of the DID log entry the issuer DID document was resolved from.
+    pub version_id: VersionId,
+    /// The `versionTime` of the DID log entry the issuer DID document was resolved from.
+    pub version_time: DateTime<Utc>,
+}
+
+impl IssuerDidState {
+    /// Extracts the issuer DID state embedded into the supplied `credential` (see [`ISSUER_DID_STATE`]).
+    ///
+    /// A missing or malformed state is denoted by [`DidSidekicksError::DeserializationFailed`].
+    #[inline]
+    pub fn from_credential(credential: &Value) -> Result<Self, DidSidekicksError> {
+        let state = credential.get(ISSUER_DID_STATE).ok_or_else(|| {
+            DidSidekicksError::DeserializationFailed(format!(
+                "The credential features no '{ISSUER_DID_STATE}' member"
+            ))
+        })?;
+        Self::deserialize(state).map_err(|err| {
+            DidSidekicksError::DeserializationFailed(format!(
+                "Malformed '{ISSUER_DID_STATE}' member: {err}"
+            ))
+        })
+    }
+}
+
+/// The context of a credential issuance, i.e. the issuer DID document along with its state (version).
+#[derive(Debug, Clone)]
+pub struct IssuanceContext {
+    issuer: DidDoc,
+    state: IssuerDidState,
+}
+
+impl IssuanceContext {
+    /// The only non-empty constructor of the type, capturing the supplied `issuer` DID document
+    /// as resolved from the DID log entry featuring the supplied `version_id` and `version_time`.
+    ///
+    /// A deactivated `issuer` DID is denoted by [`DidSidekicksError::InvalidDidDocument`].
+    #[inline]
+    pub fn new(
+        issuer: DidDoc,
+        version_id: VersionId,
+        version_time: DateTime<Utc>,
+    ) -> Result<Self, DidSidekicksError> {
+        if issuer.deactivated.unwrap_or(false) {
+            return Err(DidSidekicksError::InvalidDidDocument(format!(
+                "The issuer DID {} is deactivated",
+                issuer.id
+            )));
+        }
+        Ok(Self {
+            issuer,
+            state: IssuerDidState {
+                version_id,
+                version_time,
+            },
+        })
+    }
+
+    /// The issuer DID document.
+    #[inline]
+    pub const fn issuer(&self) -> &DidDoc {
+        &self.issuer
+    }
+
+    /// The state (version) of the issuer DID.
+    #[inline]
+    pub const fn state(&self) -> &IssuerDidState {
+        &self.state
+    }
+
+    /// Embeds the issuer DID state into the supplied (unsecured) `credential` (a JSON object) and secures it
+    /// by a proof created by the supplied `key_pair` (see [`EddsaJcs2022Cryptosuite`]).
+    ///
+    /// The key must be listed as `assertionMethod` by the issuer DID document, otherwise [`DidSidekicksError::KeyNotAuthorized`] is returned.
+    #[inline]
+    pub fn add_proof(
+        &self,
+        credential: &Value,
+        key_pair: &Ed25519KeyPair,
+    ) -> Result<Value, DidSidekicksError> {
+        self.authorize_key(&key_pair.verifying_key.to_multibase())?;
+
+        let mut unsecured = credential.clone();
+        let Some(members) = unsecured.as_object_mut() else {
+            return Err(DidSidekicksError::SerializationFailed(
+                "The credential must be a JSON object".to_owned(),
+            ));
+        };
+        members.insert(
+            ISSUER_DID_STATE.to_owned(),
+            serde_json::to_value(&self.state)
+                .map_err(|err| DidSidekicksError::SerializationFailed(err.to_string()))?,
+        );
+
+        let suite = EddsaJcs2022Cryptosuite {
+            verifying_key: Some(key_pair.verifying_key.clone()),
+            signing_key: Some(key_pair.signing_key.clone()),
+            verification_options: VerificationOptions::default(),
+        };
+        let options = CryptoSuiteProofOptions::from_verification_method(
+            &did_key_verification_method_from_verifying_key(&key_pair.verifying_key),
+        )
+        .with_proof_purpose("assertionMethod");
+        suite.add_proof(&unsecured, &options)
+    }
+
+    /// Verifies the supplied (secured) `credential` against the issuer DID state captured by this context, i.e.:
+    /// - the proof must be valid (otherwise [`DidSidekicksError::InvalidDataIntegrityProof`] or [`DidSidekicksError::SignatureInvalid`] is returned),
+    /// - the embedded issuer DID state must match this one (otherwise [`DidSidekicksError::InvalidDataIntegrityProof`] is returned) and
+    /// - the signing key must be listed as `assertionMethod` by the issuer DID document (otherwise [`DidSidekicksError::KeyNotAuthorized`] is returned).
+    ///
+    /// Returns the `did:key` DID of the signing key.
+    #[inline]
+    pub fn verify(&self, credential: &Value) -> Result<String, DidSidekicksError> {
+        let signer = verify_secured_document(credential)?;
+
+        let state = IssuerDidState::from_credential(credential)?;
+        if state != self.state {
+            return Err(DidSidekicksError::InvalidDataIntegrityProof(format!(
+                "The credential is bound to the issuer DID state {} ({}), whereas {} ({}) is expected",
+                state.version_id,
+                state.version_time.to_rfc3339(),
+                self.state.version_id,
+                self.state.version_time.to_rfc3339()
+            )));
+        }
+
+        self.authorize_key(signer.trim_start_matches("did:key:"))?;
+        Ok(signer)
+    }
+
+    /// Same as [`IssuanceContext::verify`], while also enforcing the supplied verification `policy`, i.e.:
+    /// - the cryptosuite of each proof must be allowed and
+    /// - the issuer DID must be trusted (as [`TRUST_ROLE_ISSUER`]) by any of the trusted `registries` (keyed by name).
+    ///
+    /// Any policy violation is denoted by [`DidSidekicksError::PolicyViolation`].
+    #[cfg(feature = "jsonschema-validation")]
+    #[inline]
+    pub fn verify_with_policy(
+        &self,
+        credential: &Value,
+        policy: &VerificationPolicy,
+        registries: HashMap<String, Arc<dyn TrustRegistry>>,
+    ) -> Result<String, DidSidekicksError> {
+        if let Some(proofs) = credential.get("proof") {
+            policy.check_proofs(proofs)?;
+        }
+        let signer = self.verify(credential)?;
+        policy.check_trust(&self.issuer.id, TRUST_ROLE_ISSUER, registries)?;
+        Ok(signer)
+    }
+
+    /// Ensures the supplied `multikey` is listed as `assertionMethod` by the issuer DID document.
+    fn authorize_key(&self, multikey: &str) -> Result<(), DidSidekicksError> {
+        let listed = self.issuer.assertion_method.iter().any(|method| {
+            verifying_key_from_verification_method(method)
+                .is_ok_and(|key| key.to_multibase() == multikey)
+        });
+        if !listed {
+            return Err(DidSidekicksError::KeyNotAuthorized(format!(
+                "The key '{multikey}' is not listed as 'assertionMethod' by the issuer DID {} (version {})",
+                self.issuer.id, self.state.version_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+    use serde_json::json;
+
+    const ENTRY_HASH: &str = "QmQq6Kg4ZZ1p49znzxnWmes4LkkWgMWLrnrfPre8UD56bz";
+
+    #[expect(clippy::single_call_fn, reason = "..")]
+    fn issuer_doc(key_pair: &Ed25519KeyPair) -> DidDoc {
+        DidDoc::from_json(
+            &json!({
+                "@context": ["https://www.w3.org/ns/did/v1"],
+                "id": "did:webvh:QmHash:example.com",
+                "verificationMethod": [],
+                "assertionMethod": [{
+                    "id": "did:webvh:QmHash:example.com#assert-key-01",
+                    "type": "Multikey",
+                    "publicKeyMultibase": key_pair.verifying_key.to_multibase(),
+                }],
+            })
+            .to_string(),
+        )
+        .unwrap()
+    }
+
+    fn context(key_pair: &Ed25519KeyPair, version: u64) -> IssuanceContext {
+        IssuanceContext::new(
+            issuer_doc(key_pair),
+            VersionId::new(version, ENTRY_HASH).unwrap(),
+            DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+                .unwrap()
+                .to_utc(),
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    fn test_add_and_verify_proof() {
+        let key_pair = Ed25519KeyPair::generate();
+        let ctx = context(&key_pair, 3);
+        let credential = ctx
+            .add_proof(
+                &json!({"credentialSubject": {"id": "did:example:holder"}}),
+                &key_pair,
+            )
+            .unwrap();
+
+        assert_eq!(
+            IssuerDidState::from_credential(&credential).unwrap(),
+            *ctx.state()
+        );
+        assert_eq!(
+            ctx.verify(&credential).unwrap(),
+            format!("did:key:{}", key_pair.verifying_key.to_multibase())
+        );
+
+        // the very same credential is bound to version 3 only
+        assert_error(
+            context(&key_pair, 4).verify(&credential),
+            DidSidekicksErrorKind::InvalidIntegrityProof,
+            "bound to the issuer DID state 3-",
+        );
+    }
+
+    #[rstest]
+    fn test_unauthorized_key() {
+        let key_pair = Ed25519KeyPair::generate();
+        let other = Ed25519KeyPair::generate();
+        let ctx = context(&key_pair, 1);
+        assert_error(
+            ctx.add_proof(&json!({}), &other),
+            DidSidekicksErrorKind::KeyNotAuthorized,
+            "is not listed as 'assertionMethod'",
+        );
+
+        // a credential issued by a key rotated out of the DID document meanwhile
+        let credential = context(&other, 1).add_proof(&json!({}), &other).unwrap();
+        assert_error(
+            ctx.verify(&credential),
+            DidSidekicksErrorKind::KeyNotAuthorized,
+            "is not listed as 'assertionMethod'",
+        );
+    }
+
+    #[rstest]
+    #[expect(clippy::indexing_slicing, reason = "panic-safe indexing")]
+    fn test_tampered_issuer_did_state() {
+        let key_pair = Ed25519KeyPair::generate();
+        let ctx = context(&key_pair, 1);
+        let mut credential = ctx.add_proof(&json!({}), &key_pair).unwrap();
+        credential[ISSUER_DID_STATE]["versionId"] = json!(format!("2-{ENTRY_HASH}"));
+        assert_error(
+            context(&key_pair, 2).verify(&credential),
+            DidSidekicksErrorKind::SignatureInvalid,
+            "invalid signature by 'did:key:",
+        );
+    }
+
+    #[cfg(feature = "jsonschema-validation")]
+    #[rstest]
+    fn test_verify_with_policy() {
+        use crate::trust_registry::TrustStatus;
+
+        struct TrustingRegistry;
+        impl TrustRegistry for TrustingRegistry {
+            fn is_trusted(&self, _did: String, _role: String) -> TrustStatus {
+                TrustStatus::Trusted
+            }
+        }
+
+        let key_pair = Ed25519KeyPair::generate();
+        let ctx = context(&key_pair, 1);
+        let credential = ctx.add_proof(&json!({}), &key_pair).unwrap();
+        let registry: Arc<dyn TrustRegistry> = Arc::new(TrustingRegistry);
+        let registries = HashMap::from([("swiss".to_owned(), registry)]);
+
+        let policy = VerificationPolicy::from_json(
+            r#"{"allowedCryptosuites": ["eddsa-jcs-2022"], "trustedRegistries": ["swiss"]}"#,
+        )
+        .unwrap();
+        ctx.verify_with_policy(&credential, &policy, registries.clone())
+            .unwrap();
+
+        let suite_policy =
+            VerificationPolicy::from_json(r#"{"allowedCryptosuites": ["eddsa-rdfc-2022"]}"#)
+                .unwrap();
+        assert_error(
+            ctx.verify_with_policy(&credential, &suite_policy, registries),
+            DidSidekicksErrorKind::PolicyViolation,
+            "the cryptosuite 'eddsa-jcs-2022' is not allowed",
+        );
+
+        let trust_policy =
+            VerificationPolicy::from_json(r#"{"trustedRegistries": ["swiss"]}"#).unwrap();
+        assert_error(
+            ctx.verify_with_policy(&credential, &trust_policy, HashMap::new()),
+            DidSidekicksErrorKind::PolicyViolation,
+            "the trust registry 'swiss' is not available",
+        );
+    }
+
+    #[rstest]
+    fn test_missing_issuer_did_state() {
+        assert_error(
+            IssuerDidState::from_credential(&json!({})),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "features no 'issuerDidState' member",
+        );
+    }
+}
@@ -0,0 +1,277 @@
+// SPDX-License-Identifier: MIT
+
+//! A (single-pass, allocation-light) detector of JSON text already in canonical JCS form.
+//!
+//! As specified by https://www.rfc-editor.org/rfc/rfc8785. Such text may be hashed as is, i.e. without any parse/serialize
+//! round trip (see [`crate::jcs_sha256_hasher::JcsSha256Hasher::encode_hex_text`]).
+//!
+//! The detector is conservative, i.e. it never reports a non-canonical text as canonical, whereas it may report
+//! a canonical text as non-canonical (e.g. in case of any non-integer number), thus merely missing the fast path.
+
+/// The maximum nesting depth of a JSON text considered by [`is_canonical_json`], any deeper one is reported as non-canonical.
+const MAX_DEPTH: usize = 128;
+
+/// The maximum number of digits of an integer considered by [`is_canonical_json`], as any such integer
+/// is exactly representable by an IEEE 754 double (hence serialized by JCS as is).
+const MAX_INTEGER_DIGITS: usize = 15;
+
+/// Checks whether the supplied JSON `text` is (well-formed and) already in canonical JCS form, i.e.:
+/// - features no insignificant whitespace,
+/// - all object members are sorted by their (UTF-16 code units of) names, which are unique,
+/// - all strings are escaped the JCS way (i.e. merely `"`, `\` and control characters) and
+/// - all numbers are integers featuring at most 15 digits (other numbers are conservatively reported as non-canonical).
+#[inline]
+pub fn is_canonical_json(text: &str) -> bool {
+    let mut scanner = Scanner {
+        bytes: text.as_bytes(),
+        pos: 0,
+    };
+    scanner.value(0) && scanner.pos == scanner.bytes.len()
+}
+
+struct Scanner<'text> {
+    bytes: &'text [u8],
+    pos: usize,
+}
+
+impl Scanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn advance(&mut self) {
+        self.pos = self.pos.saturating_add(1);
+    }
+
+    fn expect(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.advance();
+            return true;
+        }
+        false
+    }
+
+    fn value(&mut self, depth: usize) -> bool {
+        if depth > MAX_DEPTH {
+            return false;
+        }
+        match self.peek() {
+            Some(b'{') => self.object(depth),
+            Some(b'[') => self.array(depth),
+            Some(b'"') => self.string().is_some(),
+            Some(b't') => self.literal(b"true"),
+            Some(b'f') => self.literal(b"false"),
+            Some(b'n') => self.literal(b"null"),
+            Some(b'-' | b'0'..=b'9') => self.integer(),
+            Some(_) | None => false,
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> bool {
+        self.advance(); // '{'
+        if self.expect(b'}') {
+            return true;
+        }
+        let mut previous_name: Option<String> = None;
+        loop {
+            let Some(name) = self.string().map(unescape) else {
+                return false;
+            };
+            // members must be sorted by (and unique in terms of) the UTF-16 code units of their names
+            if previous_name
+                .as_ref()
+                .is_some_and(|previous| !previous.encode_utf16().lt(name.encode_utf16()))
+            {
+                return false;
+            }
+            if !self.expect(b':') || !self.value(depth.saturating_add(1)) {
+                return false;
+            }
+            previous_name = Some(name);
+            match self.peek() {
+                Some(b',') => self.advance(),
+                Some(b'}') => {
+                    self.advance();
+                    return true;
+                }
+                Some(_) | None => return false,
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> bool {
+        self.advance(); // '['
+        if self.expect(b']') {
+            return true;
+        }
+        loop {
+            if !self.value(depth.saturating_add(1)) {
+                return false;
+            }
+            match self.peek() {
+                Some(b',') => self.advance(),
+                Some(b']') => {
+                    self.advance();
+                    return true;
+                }
+                Some(_) | None => return false,
+            }
+        }
+    }
+
+    /// Scans a (JCS-escaped) string and delivers its raw content, i.e. lacking the quotes.
+    fn string(&mut self) -> Option<&[u8]> {
+        if !self.expect(b'"') {
+            return None;
+        }
+        let start = self.pos;
+        loop {
+            match self.peek()? {
+                b'"' => {
+                    let content = self.bytes.get(start..self.pos);
+                    self.advance();
+                    return content;
+                }
+                b'\\' => {
+                    self.advance();
+                    match self.peek()? {
+                        b'"' | b'\\' | b'b' | b't' | b'n' | b'f' | b'r' => self.advance(),
+                        b'u' => {
+                            // only control characters lacking a short escape are to be \u-escaped (using lower case hex digits)
+                            let escaped = self
+                                .bytes
+                                .get(self.pos.saturating_add(1)..self.pos.saturating_add(5))?;
+                            let [b'0', b'0', high @ (b'0' | b'1'), low] = *escaped else {
+                                return None;
+                            };
+                            if !matches!(low, b'0'..=b'9' | b'a'..=b'f')
+                                || (high == b'0' && matches!(low, b'8' | b'9' | b'a' | b'c' | b'd'))
+                            {
+                                return None;
+                            }
+                            self.pos = self.pos.saturating_add(5);
+                        }
+                        _ => return None,
+                    }
+                }
+                0x00..=0x1F => return None, // control characters must be escaped
+                _ => self.advance(),
+            }
+        }
+    }
+
+    fn literal(&mut self, literal: &[u8]) -> bool {
+        let end = self.pos.saturating_add(literal.len());
+        if self.bytes.get(self.pos..end) == Some(literal) {
+            self.pos = end;
+            return true;
+        }
+        false
+    }
+
+    /// Scans an integer featuring neither leading zeros nor `-0`, while rejecting any fraction or exponent.
+    fn integer(&mut self) -> bool {
+        let negative = self.expect(b'-');
+        let start = self.pos;
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.advance();
+        }
+        let digits = self.bytes.get(start..self.pos).unwrap_or_default();
+        let valid = match *digits {
+            [] => false,
+            [b'0'] => !negative,
+            [b'0', ..] => false,
+            _ => digits.len() <= MAX_INTEGER_DIGITS,
+        };
+        valid && !matches!(self.peek(), Some(b'.' | b'e' | b'E'))
+    }
+}
+
+/// Unescapes the supplied (JCS-escaped, hence already validated) raw string `content`.
+#[expect(clippy::single_call_fn, reason = "..")]
+fn unescape(content: &[u8]) -> String {
+    let text = String::from_utf8_lossy(content);
+    if !text.contains('\\') {
+        return text.into_owned();
+    }
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(character) = chars.next() {
+        if character != '\\' {
+            unescaped.push(character);
+            continue;
+        }
+        let escaped = match chars.next() {
+            Some('b') => '\u{8}',
+            Some('t') => '\t',
+            Some('n') => '\n',
+            Some('f') => '\u{c}',
+            Some('r') => '\r',
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .unwrap_or(char::REPLACEMENT_CHARACTER)
+            }
+            Some(other) => other,
+            None => break,
+        };
+        unescaped.push(escaped);
+    }
+    unescaped
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("{}")]
+    #[case("[]")]
+    #[case(r#"{"a":[true,false,null],"b":{"c":-12,"d":0}}"#)]
+    #[case("{\"a\\n\":1,\"a\\u001f\":2,\"\u{e9}\":3}")]
+    #[case("{\"\u{1f600}\":1,\"\u{fb01}\":2}")] // sorted by UTF-16 code units, rather than by code points
+    #[case(r#""\\\"""#)]
+    fn test_canonical(#[case] text: &str) {
+        // the JCS serialization of any canonical text is the text itself
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(text) {
+            assert_eq!(serde_json_canonicalizer::to_string(&value).unwrap(), text);
+        }
+        assert!(is_canonical_json(text));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case(r#"{"a": 1}"#)] // whitespace
+    #[case(r#"{"b":1,"a":2}"#)] // unsorted
+    #[case(r#"{"a":1,"a":2}"#)] // duplicate
+    #[case(r#"{"a":01}"#)] // leading zero
+    #[case(r#"{"a":-0}"#)]
+    #[case(r#"{"a":1.0}"#)] // conservatively non-canonical
+    #[case(r#"{"a":1e3}"#)]
+    #[case(r#"{"a":1234567890123456}"#)]
+    #[case(r#"{"a":"\u00e9"}"#)] // needless escape
+    #[case(r#"{"a":"\u000A"}"#)] // upper case hex digits
+    #[case(r#"{"a":"\u000a"}"#)] // short escape available
+    #[case(r#"{"a":"\/"}"#)]
+    #[case("{\"a\":\"\t\"}")] // unescaped control character
+    #[case(r#"{"a":1"#)]
+    #[case(r#"{"a":1}x"#)]
+    #[case("[1,]")]
+    fn test_non_canonical(#[case] text: &str) {
+        assert!(!is_canonical_json(text));
+    }
+
+    #[rstest]
+    fn test_max_depth() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(is_canonical_json(&nested(MAX_DEPTH)));
+        assert!(!is_canonical_json(&nested(MAX_DEPTH.saturating_add(2))));
+    }
+}
@@ -1,5 +1,9 @@
 // SPDX-License-Identifier: MIT
 
+use crate::bounded_json::from_str_bounded;
+use crate::jcs_canonical::is_canonical_json;
+use crate::metrics::count_hash_operation;
+use crate::multihash::{Multihash, MultihashAlgorithm};
 use bs58::{encode as base58_encode, Alphabet as Alphabet58};
 use hex;
 use hex::ToHex as _;
@@ -7,9 +11,52 @@ use hex::ToHex as _;
 // CAUTION Beware that using the "serde_jcs" crate here may cause
 //         "not yet implemented: Handle number str (u128/i128)" error
 //         in case of numeric json properties, e.g. "witnessThreshold".
+use serde::de::Error as _;
 use serde_json::error::Error as JsonError;
-use serde_json_canonicalizer::to_string as jcs_to_string;
+use serde_json_canonicalizer::to_writer as jcs_to_writer;
 use sha2::{Digest as _, Sha256};
+use std::cell::RefCell;
+
+thread_local! {
+    /// The (per-thread) scratch buffer reused for JCS serialization, hence no intermediate `String` is built per call.
+    static JCS_SCRATCH_BUFFER: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Serializes the supplied `json` as JCS UTF-8 bytes into the (thread-local) scratch buffer and
+/// delivers the result of the supplied `consume` function applied to the buffer.
+///
+/// In the (rather unlikely) case of a reentrant call, a fresh buffer is used instead.
+fn with_jcs_bytes<R>(
+    json: &serde_json::Value,
+    consume: impl FnOnce(&mut Vec<u8>) -> R,
+) -> Result<R, JsonError> {
+    JCS_SCRATCH_BUFFER.with(|scratch| {
+        let mut fresh = Vec::new();
+        let mut borrowed = scratch.try_borrow_mut().ok();
+        let buffer = borrowed.as_deref_mut().unwrap_or(&mut fresh);
+        buffer.clear();
+        jcs_to_writer(json, buffer)?;
+        Ok(consume(buffer))
+    })
+}
+
+/// Removes any `:ff` byte sequence following a colon, in place (the equivalent of `str::replace(":ff", ":")`).
+pub(crate) fn strip_colon_ff(buffer: &mut Vec<u8>) {
+    let len = buffer.len();
+    let mut read: usize = 0;
+    let mut write: usize = 0;
+    while read < len {
+        let skip = if buffer.get(read..read.saturating_add(3)) == Some(b":ff".as_slice()) {
+            3
+        } else {
+            1
+        };
+        buffer.copy_within(read..read.saturating_add(1), write);
+        write = write.saturating_add(1);
+        read = read.saturating_add(skip);
+    }
+    buffer.truncate(write);
+}
 
 /// A helper capable of SHA2-256 hashing of canonical JSON structures.
 //#[derive(Default, Clone)]
@@ -18,7 +65,7 @@ pub struct JcsSha256Hasher {
 }
 impl JcsSha256Hasher {
     /// Serialize the given data structure as a JCS UTF-8 string and calculate SHA2-256 hash out of it.
-    /// The hash encoded as hex strict representation is returned. Lower case letters are used (e.g. f9b4ca)
+    /// The hash encoded as hex strict representation is returned. Lower case letters are used (e.g. f9b4ca).
     ///
     /// # Errors
     ///
@@ -26,25 +73,45 @@ impl JcsSha256Hasher {
     /// fail, or if `T` contains a map with non-string keys.
     #[inline]
     pub fn encode_hex(&mut self, json: &serde_json::Value) -> Result<String, JsonError> {
+        count_hash_operation("encode_hex");
         self.hasher.reset();
-        let jcs_string = jcs_to_string(json)?;
-        self.hasher.update(jcs_string);
-        Ok(self.hasher.clone().finalize().encode_hex())
+        with_jcs_bytes(json, |jcs_bytes| self.hasher.update(jcs_bytes))?;
+        Ok(self.hasher.finalize_reset().encode_hex())
+    }
+
+    /// The same as [`JcsSha256Hasher::encode_hex`], but for the supplied JSON `text`.
+    ///
+    /// Any text already in canonical JCS form (see [`is_canonical_json`]), e.g. a DID log entry produced by this very crate,
+    /// is hashed as is, i.e. without any parse/serialize round trip. Otherwise, it is parsed and canonicalized first.
+    ///
+    /// # Errors
+    ///
+    /// Parsing fails if the `text` is no valid JSON or exceeds the configured [`crate::bounded_json::DeserializationLimits`].
+    #[inline]
+    pub fn encode_hex_text(&mut self, text: &str) -> Result<String, JsonError> {
+        if !is_canonical_json(text) {
+            return self.encode_hex(&from_str_bounded(text).map_err(JsonError::custom)?);
+        }
+        count_hash_operation("encode_hex");
+        self.hasher.reset();
+        self.hasher.update(text.as_bytes());
+        Ok(self.hasher.finalize_reset().encode_hex())
     }
 
     /// Implementation of the multihash specification (https://www.w3.org/TR/controller-document/#multihash).
     /// Its output is a hash of the input using the associated <hash algorithm>, prefixed with a hash algorithm identifier and the hash size.
     #[inline]
-    #[expect(
-        clippy::unseparated_literal_suffix,
-        reason = "to prevent clippy::separated_literal_suffix warning"
-    )]
-    //#[expect(clippy::separated_literal_suffix, reason = "to prevent clippy::unseparated_literal_suffix warning")]
-    #[expect(clippy::as_conversions, reason = "..")]
     pub fn encode_multihash(&mut self, str: String) -> Vec<u8> {
+        self.encode_multihash_bytes(str.as_bytes())
+    }
+
+    /// The same as [`JcsSha256Hasher::encode_multihash`], but for arbitrary (e.g. binary) data.
+    #[inline]
+    pub fn encode_multihash_bytes(&mut self, data: &[u8]) -> Vec<u8> {
+        count_hash_operation("encode_multihash");
         self.hasher.reset();
-        self.hasher.update(str);
-        let digest = self.hasher.clone().finalize();
+        self.hasher.update(data);
+        let digest = self.hasher.finalize_reset();
 
         // According to https://identity.foundation/trustdidweb/v0.3/#didtdw-version-changelog:
         //              Use multihash in the SCID to differentiate the different hash function outputs.
@@ -54,25 +121,23 @@ impl JcsSha256Hasher {
         // Its output is a hash of the input using the associated <hash algorithm>, prefixed with a hash algorithm identifier and the hash size.
         // Multihash Identifier	Multihash Header	Description
         // sha2-256	            0x12	            SHA-2 with 256 bits (32 bytes) of output, as defined by [RFC6234].
-        let multihash_header: &[u8] = &[
-            0x12u8,             // hash algorithm (sha2-256) identifier
-            digest.len() as u8, // hash size (in bytes)
-        ];
-
-        [multihash_header, digest.as_slice()].concat()
+        Multihash::wrap(MultihashAlgorithm::Sha2_256, &digest)
+            .map(|multihash| multihash.to_bytes())
+            .unwrap_or_default()
     }
 
     /// Serialize the given data structure as a JCS UTF-8 string and calculate SHA2-256 multihash out of it.
-    /// The multihash encoded in base58btc format is returned
+    /// The multihash encoded in base58btc format is returned.
     #[inline]
     pub fn base58btc_encode_multihash(
         &mut self,
         json: &serde_json::Value,
     ) -> serde_json::Result<String> {
-        let canonical = jcs_to_string(json)?;
-
-        // WORKAROUND (":ff" -> ":") in case of numeric json properties (e.g. witnessThreshold)
-        let multihash_sha256 = self.encode_multihash(canonical.replace(":ff", ":"));
+        let multihash_sha256 = with_jcs_bytes(json, |jcs_bytes| {
+            // WORKAROUND (":ff" -> ":") in case of numeric json properties (e.g. witnessThreshold)
+            strip_colon_ff(jcs_bytes);
+            self.encode_multihash_bytes(jcs_bytes)
+        })?;
 
         //
         // Since v0.3 (https://identity.foundation/trustdidweb/v0.3/#didtdw-version-changelog):
@@ -86,6 +151,27 @@ impl JcsSha256Hasher {
         Ok(encoded)
     }
 
+    /// The same as [`JcsSha256Hasher::base58btc_encode_multihash`], but for the supplied JSON `text`.
+    ///
+    /// Any text already in canonical JCS form (see [`is_canonical_json`]) is hashed without any parse/serialize round trip.
+    ///
+    /// # Errors
+    ///
+    /// Parsing fails if the `text` is no valid JSON or exceeds the configured [`crate::bounded_json::DeserializationLimits`].
+    #[inline]
+    pub fn base58btc_encode_multihash_text(&mut self, text: &str) -> serde_json::Result<String> {
+        if !is_canonical_json(text) {
+            return self
+                .base58btc_encode_multihash(&from_str_bounded(text).map_err(JsonError::custom)?);
+        }
+        let mut jcs_bytes = text.as_bytes().to_vec();
+        // WORKAROUND (":ff" -> ":") the very same way as done by base58btc_encode_multihash
+        strip_colon_ff(&mut jcs_bytes);
+        Ok(base58_encode(self.encode_multihash_bytes(&jcs_bytes))
+            .with_alphabet(Alphabet58::BITCOIN)
+            .into_string())
+    }
+
     /// This helper calculates the hash string as `base58btc(multihash(multikey))`, where:
     /// - `multikey` is the multikey representation of a public key
     /// - `multihash` is an implementation of the <a href="https://www.w3.org/TR/controller-document/#multihash">multihash</a> specification.
@@ -117,6 +203,10 @@ impl Default for JcsSha256Hasher {
 }
 
 #[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
 mod test {
     use super::*;
     use hex::encode as hex_encode;
@@ -133,6 +223,51 @@ mod test {
         assert_eq!(hash, expected);
     }
 
+    #[rstest]
+    #[case(r#"{"b":{"witnessThreshold":2},"a":"x:ffy"}"#)]
+    #[case(r#"[1.0,"\u00e9",null,{"a:ff":[]}]"#)]
+    fn test_scratch_buffer_equivalence(#[case] json: &str) {
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        let canonical = serde_json_canonicalizer::to_string(&value).unwrap();
+
+        let mut hasher = JcsSha256Hasher::default();
+        assert_eq!(
+            hasher.encode_hex(&value).unwrap(),
+            hex_encode(Sha256::digest(canonical.as_bytes()))
+        );
+        assert_eq!(
+            hasher.base58btc_encode_multihash(&value).unwrap(),
+            base58_encode(hasher.encode_multihash(canonical.replace(":ff", ":"))).into_string()
+        );
+        // the (reused) scratch buffer delivers the very same result
+        assert_eq!(
+            hasher.encode_hex(&value).unwrap(),
+            hex_encode(Sha256::digest(canonical.as_bytes()))
+        );
+    }
+
+    #[rstest]
+    #[case(r#"{"a":[1,"x:ffy"],"b":{"witnessThreshold":2}}"#)] // canonical, hence the fast path
+    #[case(r#"{"b":{"witnessThreshold":2}, "a":[1.0,"x:ffy"]}"#)]
+    fn test_text_equivalence(#[case] text: &str) {
+        let value: serde_json::Value = serde_json::from_str(text).unwrap();
+        let mut hasher = JcsSha256Hasher::default();
+        assert_eq!(
+            hasher.encode_hex_text(text).unwrap(),
+            hasher.encode_hex(&value).unwrap()
+        );
+        assert_eq!(
+            hasher.base58btc_encode_multihash_text(text).unwrap(),
+            hasher.base58btc_encode_multihash(&value).unwrap()
+        );
+        hasher.encode_hex_text("{").unwrap_err();
+        // any non-canonical text is subject to the configured deserialization limits
+        let nested = format!("{} {}", "[".repeat(65), "]".repeat(65));
+        assert!(hasher
+            .base58btc_encode_multihash_text(&nested)
+            .is_err_and(|err| err.to_string().contains("maximum nesting depth of 64")));
+    }
+
     #[rstest]
     #[case(
         // Example taken from https://identity.foundation/didwebvh/v0.3/#log-file-for-version-2
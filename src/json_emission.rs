@@ -0,0 +1,93 @@
+// SPDX-License-Identifier: MIT
+
+//! JSON emission options shared by all `to_json`-style methods (e.g. [`crate::did_doc::DidDoc::to_json`]).
+//!
+//! Depending on the purpose, the very same JSON value is emitted differently, e.g.:
+//! - DID log files must be [`JsonFormat::Compact`] (a single line per entry),
+//! - debug output should be [`JsonFormat::Pretty`] and
+//! - any hash input must be [`JsonFormat::Canonical`] (as specified by https://www.rfc-editor.org/rfc/rfc8785).
+
+use crate::errors::DidSidekicksError;
+use serde::Serialize;
+use serde_json_canonicalizer::to_string as jcs_to_string;
+
+/// The supported JSON formats.
+///
+/// A UniFFI-compliant enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum JsonFormat {
+    /// No whitespace whatsoever, while preserving the order of properties.
+    #[default]
+    Compact,
+    /// Human-readable, i.e. indented by two spaces.
+    Pretty,
+    /// As specified by JCS (https://www.rfc-editor.org/rfc/rfc8785), i.e. compact and featuring sorted properties.
+    Canonical,
+}
+
+/// The options controlling the JSON emission.
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct EmitOptions {
+    pub format: JsonFormat,
+}
+
+impl EmitOptions {
+    /// The options for compact JSON emission (e.g. for DID log files).
+    pub const COMPACT: Self = Self {
+        format: JsonFormat::Compact,
+    };
+    /// The options for human-readable JSON emission (e.g. for debug output).
+    pub const PRETTY: Self = Self {
+        format: JsonFormat::Pretty,
+    };
+    /// The options for canonical JSON emission (e.g. for hash input).
+    pub const CANONICAL: Self = Self {
+        format: JsonFormat::Canonical,
+    };
+}
+
+/// Emits the supplied `value` as JSON text according to the supplied `options`.
+///
+/// Any failure is denoted by [`DidSidekicksError::SerializationFailed`].
+#[inline]
+pub fn emit_json<T: Serialize>(
+    value: &T,
+    options: EmitOptions,
+) -> Result<String, DidSidekicksError> {
+    match options.format {
+        JsonFormat::Compact => serde_json::to_string(value).map_err(|err| err.to_string()),
+        JsonFormat::Pretty => serde_json::to_string_pretty(value).map_err(|err| err.to_string()),
+        JsonFormat::Canonical => jcs_to_string(value).map_err(|err| err.to_string()),
+    }
+    .map_err(DidSidekicksError::SerializationFailed)
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+mod test {
+    use super::*;
+    use rstest::rstest;
+    use serde_json::json;
+
+    #[rstest]
+    #[case(EmitOptions::COMPACT, r#"{"b":1,"a":[true]}"#)]
+    #[case(EmitOptions::PRETTY, "{\n  \"b\": 1,\n  \"a\": [\n    true\n  ]\n}")]
+    #[case(EmitOptions::CANONICAL, r#"{"a":[true],"b":1}"#)]
+    fn test_emit_json(#[case] options: EmitOptions, #[case] expected: &str) {
+        assert_eq!(
+            emit_json(&json!({"b": 1, "a": [true]}), options).unwrap(),
+            expected
+        );
+    }
+}
@@ -0,0 +1,697 @@
+// SPDX-License-Identifier: MIT
+
+//! A lenient (yet fuzz-resistant) JSON parsing mode, intended for DID logs and documents served by misconfigured web servers.
+//!
+//! Tolerated are:
+//! - a leading byte order mark (BOM),
+//! - UTF-16 (either endianness, with or without BOM) encoded input, transcoded to UTF-8 (see [`parse_did_log_bytes_lenient`]),
+//! - CRLF (or lone CR) line endings, even if mixed,
+//! - trailing whitespace/newlines (and blank lines in case of DID logs) and
+//! - duplicate JSON keys (according to the supplied [`DuplicateKeyPolicy`]).
+//!
+//! Everything normalized along the way is reported (see [`JsonNormalization`]).
+//! The recursion limit of the underlying (`serde_json`) parser still applies, so deeply nested input is rejected.
+//!
+//! Conversely, a strict parsing mode (see [`parse_json_strict`]) is also available, intended for security-critical documents,
+//! as duplicate-key smuggling is a known attack vector against canonicalization-based signatures.
+
+use crate::bounded_json::{check_bounds, get_deserialization_limits};
+use crate::errors::DidSidekicksError;
+use crate::panic_boundary::catch_panic;
+use core::cell::RefCell;
+use core::fmt;
+use serde::de::{DeserializeSeed, Deserializer, Error as _, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Number, Value};
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+const UTF16_LE_BYTE_ORDER_MARK: [u8; 2] = [0xFF, 0xFE];
+const UTF16_BE_BYTE_ORDER_MARK: [u8; 2] = [0xFE, 0xFF];
+
+/// Determines how duplicate JSON keys (silently collapsed by most JSON parsers) are handled.
+///
+/// A UniFFI-compliant enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum DuplicateKeyPolicy {
+    /// The first occurrence of a key wins.
+    First,
+    /// The last occurrence of a key wins (the usual behaviour of most JSON parsers).
+    Last,
+    /// Duplicate keys are rejected.
+    Error,
+}
+
+/// Denotes what has been normalized while parsing in lenient mode.
+///
+/// A UniFFI-compliant enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum JsonNormalizationKind {
+    /// A leading byte order mark (BOM) has been removed.
+    ByteOrderMark,
+    /// CRLF (or lone CR) line endings have been replaced by LF.
+    CarriageReturn,
+    /// Trailing whitespace (e.g. newlines) has been removed.
+    TrailingWhitespace,
+    /// A blank line (of a DID log) has been skipped.
+    BlankLine,
+    /// A duplicate key has been collapsed (according to [`DuplicateKeyPolicy`]).
+    DuplicateKey,
+    /// UTF-16 encoded input has been transcoded to UTF-8.
+    Utf16Transcoded,
+}
+
+/// A single normalization done while parsing in lenient mode.
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct JsonNormalization {
+    pub kind: JsonNormalizationKind,
+    /// Where the normalization took place, e.g. `line 2: /proof/0/type` (may be empty).
+    pub detail: String,
+}
+
+/// A JSON document parsed in lenient mode (see [`parse_json_lenient`]).
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct LenientJsonDocument {
+    /// The normalized JSON document (free of duplicate keys).
+    pub json_text: String,
+    pub normalizations: Vec<JsonNormalization>,
+}
+
+/// A DID log parsed in lenient mode (see [`parse_did_log_lenient`]).
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct LenientDidLog {
+    /// The normalized DID log entries (free of duplicate keys), in the order of their appearance.
+    pub entries: Vec<String>,
+    pub normalizations: Vec<JsonNormalization>,
+}
+
+/// Parses the supplied JSON `text` in lenient mode (as described in the module docs).
+///
+/// Malformed JSON is denoted by [`DidSidekicksError::DeserializationFailed`],
+/// whereas a duplicate key (in case of [`DuplicateKeyPolicy::Error`]) is denoted by [`DidSidekicksError::DuplicateJsonKey`].
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn parse_json_lenient(
+    text: String,
+    policy: DuplicateKeyPolicy,
+) -> Result<LenientJsonDocument, DidSidekicksError> {
+    catch_panic(
+        || {
+            let mut normalizations = Vec::new();
+            let normalized = normalize_text(&text, &mut normalizations);
+            let value = parse_value(&normalized, policy, "", &mut normalizations)?;
+            Ok(LenientJsonDocument {
+                json_text: value.to_string(),
+                normalizations,
+            })
+        },
+        DidSidekicksError::InternalError,
+    )
+}
+
+/// Parses the supplied (JSON Lines) `did_log` in lenient mode (as described in the module docs),
+/// thus also skipping any blank line.
+///
+/// Any malformed DID log entry is denoted by [`DidSidekicksError::DeserializationFailed`],
+/// whereas a duplicate key (in case of [`DuplicateKeyPolicy::Error`]) is denoted by [`DidSidekicksError::DuplicateJsonKey`].
+/// Either of them mentions the line number.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn parse_did_log_lenient(
+    did_log: String,
+    policy: DuplicateKeyPolicy,
+) -> Result<LenientDidLog, DidSidekicksError> {
+    catch_panic(
+        || {
+            let mut normalizations = Vec::new();
+            let normalized = normalize_text(&did_log, &mut normalizations);
+
+            let mut entries = Vec::new();
+            for (index, line) in normalized.split('\n').enumerate() {
+                let location = format!("line {}", index.saturating_add(1));
+                let entry = line.trim();
+                if entry.is_empty() {
+                    normalizations.push(JsonNormalization {
+                        kind: JsonNormalizationKind::BlankLine,
+                        detail: location,
+                    });
+                    continue;
+                }
+
+                let value =
+                    parse_value(entry, policy, &format!("{location}: "), &mut normalizations)?;
+                entries.push(value.to_string());
+            }
+
+            Ok(LenientDidLog {
+                entries,
+                normalizations,
+            })
+        },
+        DidSidekicksError::InternalError,
+    )
+}
+
+/// Parses the supplied (JSON Lines) `did_log` bytes in lenient mode (see [`parse_did_log_lenient`]).
+///
+/// As fetched from an arbitrary web server, the bytes are either UTF-8 (with or without BOM) or UTF-16 (transcoded to UTF-8) encoded.
+///
+/// UTF-16 is recognized either by its BOM or, in absence of any, by the NUL byte accompanying the (ASCII) first character of the JSON text.
+///
+/// Undecodable bytes (as well as input exceeding the configured maximum size, see [`crate::bounded_json::DeserializationLimits`])
+/// are denoted by [`DidSidekicksError::DeserializationFailed`].
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn parse_did_log_bytes_lenient(
+    did_log: Vec<u8>,
+    policy: DuplicateKeyPolicy,
+) -> Result<LenientDidLog, DidSidekicksError> {
+    catch_panic(
+        || {
+            let mut normalizations = Vec::new();
+            let text = decode_text(&did_log, &mut normalizations)?;
+            let mut parsed = parse_did_log_lenient(text, policy)?;
+            normalizations.append(&mut parsed.normalizations);
+            parsed.normalizations = normalizations;
+            Ok(parsed)
+        },
+        DidSidekicksError::InternalError,
+    )
+}
+
+/// Parses the supplied JSON `text` in strict mode, i.e. without any normalization whatsoever while rejecting duplicate keys
+/// (silently collapsed by most JSON parsers, including `serde_json`).
+///
+/// A duplicate key is denoted by [`DidSidekicksError::DuplicateJsonKey`] (featuring its JSON pointer),
+/// whereas any other parsing failure is denoted by [`DidSidekicksError::DeserializationFailed`].
+#[inline]
+pub fn parse_json_strict(text: &str) -> Result<serde_json::Value, DidSidekicksError> {
+    parse_value(text, DuplicateKeyPolicy::Error, "", &mut Vec::new())
+}
+
+/// Validates the supplied JSON `text` in strict mode (see [`parse_json_strict`]), e.g. prior to verifying its signature.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn validate_json_strict(text: String) -> Result<(), DidSidekicksError> {
+    catch_panic(
+        || parse_json_strict(&text).map(|_| ()),
+        DidSidekicksError::InternalError,
+    )
+}
+
+/// Decodes the supplied UTF-8 or UTF-16 encoded `bytes`, while reporting any transcoding.
+///
+/// A UTF-8 BOM is left intact, as it is reported by [`normalize_text`].
+#[expect(clippy::single_call_fn, reason = "..")]
+#[expect(clippy::little_endian_bytes, reason = "..")]
+#[expect(clippy::big_endian_bytes, reason = "..")]
+fn decode_text(
+    bytes: &[u8],
+    normalizations: &mut Vec<JsonNormalization>,
+) -> Result<String, DidSidekicksError> {
+    let max_size = get_deserialization_limits().max_size;
+    if u64::try_from(bytes.len()).unwrap_or(u64::MAX) > max_size {
+        return Err(DidSidekicksError::DeserializationFailed(format!(
+            "The input exceeds the maximum size of {max_size} bytes"
+        )));
+    }
+
+    let utf16 = bytes
+        .strip_prefix(&UTF16_LE_BYTE_ORDER_MARK)
+        .map(|payload| (payload, true, "UTF-16LE"))
+        .or_else(|| {
+            bytes
+                .strip_prefix(&UTF16_BE_BYTE_ORDER_MARK)
+                .map(|payload| (payload, false, "UTF-16BE"))
+        })
+        .or_else(|| {
+            // JSON text always starts with an ASCII character, hence accompanied by a NUL byte in case of UTF-16
+            match (bytes.first(), bytes.get(1)) {
+                (Some(&first), Some(&0)) if first != 0 => {
+                    Some((bytes, true, "UTF-16LE (detected without BOM)"))
+                }
+                (Some(&0), Some(&second)) if second != 0 => {
+                    Some((bytes, false, "UTF-16BE (detected without BOM)"))
+                }
+                (Some(_) | None, Some(_) | None) => None,
+            }
+        });
+
+    let Some((payload, little_endian, encoding)) = utf16 else {
+        return String::from_utf8(bytes.to_vec()).map_err(|err| {
+            DidSidekicksError::DeserializationFailed(format!(
+                "Invalid UTF-8 sequence at byte {}",
+                err.utf8_error().valid_up_to()
+            ))
+        });
+    };
+
+    if payload.len().rem_euclid(2) != 0 {
+        return Err(DidSidekicksError::DeserializationFailed(format!(
+            "Truncated {encoding} input, as it features an odd number of bytes"
+        )));
+    }
+    let units = payload
+        .chunks_exact(2)
+        .map(|chunk| {
+            let pair = <[u8; 2]>::try_from(chunk).unwrap_or_default();
+            if little_endian {
+                u16::from_le_bytes(pair)
+            } else {
+                u16::from_be_bytes(pair)
+            }
+        })
+        .collect::<Vec<u16>>();
+    let text = String::from_utf16(&units).map_err(|err| {
+        DidSidekicksError::DeserializationFailed(format!("Invalid {encoding} input: {err}"))
+    })?;
+    if payload.len() < bytes.len() {
+        normalizations.push(JsonNormalization {
+            kind: JsonNormalizationKind::ByteOrderMark,
+            detail: String::new(),
+        });
+    }
+    normalizations.push(JsonNormalization {
+        kind: JsonNormalizationKind::Utf16Transcoded,
+        detail: encoding.to_owned(),
+    });
+    Ok(text)
+}
+
+/// Gets rid of a leading BOM, CRLF (or lone CR) line endings and trailing whitespace, while reporting each of them.
+fn normalize_text(text: &str, normalizations: &mut Vec<JsonNormalization>) -> String {
+    let without_bom = text.strip_prefix(BYTE_ORDER_MARK).map_or(text, |stripped| {
+        normalizations.push(JsonNormalization {
+            kind: JsonNormalizationKind::ByteOrderMark,
+            detail: String::new(),
+        });
+        stripped
+    });
+
+    let mut normalized = if without_bom.contains('\r') {
+        // the detail denotes the line ending conventions encountered, as they may be mixed
+        let crlf = without_bom.matches("\r\n").count();
+        let lone_cr = without_bom.matches('\r').count().saturating_sub(crlf);
+        normalizations.push(JsonNormalization {
+            kind: JsonNormalizationKind::CarriageReturn,
+            detail: [(crlf, "CRLF"), (lone_cr, "CR")]
+                .iter()
+                .filter(|&&(count, _)| count > 0)
+                .map(|&(_, convention)| convention)
+                .collect::<Vec<_>>()
+                .join(", "),
+        });
+        without_bom.replace("\r\n", "\n").replace('\r', "\n")
+    } else {
+        without_bom.to_owned()
+    };
+
+    let trimmed_len = normalized.trim_end().len();
+    if trimmed_len < normalized.len() {
+        normalizations.push(JsonNormalization {
+            kind: JsonNormalizationKind::TrailingWhitespace,
+            detail: String::new(),
+        });
+        normalized.truncate(trimmed_len);
+    }
+
+    normalized
+}
+
+/// Parses a single JSON value while handling duplicate keys according to the supplied `policy`.
+/// Each collapsed duplicate key is reported, whereas any error is prefixed by `location`.
+fn parse_value(
+    text: &str,
+    policy: DuplicateKeyPolicy,
+    location: &str,
+    normalizations: &mut Vec<JsonNormalization>,
+) -> Result<Value, DidSidekicksError> {
+    check_bounds(text).map_err(|err| {
+        DidSidekicksError::DeserializationFailed(format!("{location}{}", err.detail()))
+    })?;
+
+    let duplicates = RefCell::new(Vec::new());
+    let mut deserializer = serde_json::Deserializer::from_str(text);
+    let parsed = ValueSeed {
+        policy,
+        pointer: String::new(),
+        duplicates: &duplicates,
+    }
+    .deserialize(&mut deserializer)
+    .and_then(|value| deserializer.end().map(|()| value));
+    let duplicate_keys = duplicates.into_inner();
+
+    let value = match parsed {
+        Ok(value) => value,
+        Err(err) => {
+            // in case of DuplicateKeyPolicy::Error, the very first duplicate key is the culprit
+            if let (DuplicateKeyPolicy::Error, Some(pointer)) = (policy, duplicate_keys.first()) {
+                return Err(DidSidekicksError::DuplicateJsonKey(format!(
+                    "{location}{pointer}"
+                )));
+            }
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "{location}{err}"
+            )));
+        }
+    };
+
+    normalizations.extend(
+        duplicate_keys
+            .into_iter()
+            .map(|pointer| JsonNormalization {
+                kind: JsonNormalizationKind::DuplicateKey,
+                detail: format!("{location}{pointer}"),
+            }),
+    );
+    Ok(value)
+}
+
+/// A [`serde_json::Value`] deserializer keeping track of the current position (as JSON pointer),
+/// thus capable of handling (and reporting) duplicate keys.
+struct ValueSeed<'dup> {
+    policy: DuplicateKeyPolicy,
+    pointer: String,
+    duplicates: &'dup RefCell<Vec<String>>,
+}
+
+impl ValueSeed<'_> {
+    fn child(&self, token: &str) -> Self {
+        Self {
+            policy: self.policy,
+            // as specified by https://www.rfc-editor.org/rfc/rfc6901#section-3
+            pointer: format!(
+                "{}/{}",
+                self.pointer,
+                token.replace('~', "~0").replace('/', "~1")
+            ),
+            duplicates: self.duplicates,
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for ValueSeed<'_> {
+    type Value = Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+#[expect(
+    clippy::missing_trait_methods,
+    reason = "all the JSON-relevant trait methods are implemented"
+)]
+#[expect(
+    clippy::renamed_function_params,
+    reason = "to prevent clippy::min_ident_chars warning"
+)]
+impl<'de> Visitor<'de> for ValueSeed<'_> {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any valid JSON value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E> {
+        Ok(Value::Bool(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(Value::from(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(Value::from(value))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> {
+        Ok(Number::from_f64(value).map_or(Value::Null, Value::Number))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(Value::String(value.to_owned()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Self::Value, E> {
+        Ok(Value::String(value))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::new();
+        while let Some(value) = seq.next_element_seed(self.child(&values.len().to_string()))? {
+            values.push(value);
+        }
+        Ok(Value::Array(values))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut object = Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let child = self.child(&key);
+            let pointer = child.pointer.to_owned();
+            let value = map.next_value_seed(child)?;
+            if !object.contains_key(&key) {
+                object.insert(key, value);
+                continue;
+            }
+
+            match self.policy {
+                DuplicateKeyPolicy::First => (),
+                DuplicateKeyPolicy::Last => {
+                    object.insert(key, value);
+                }
+                DuplicateKeyPolicy::Error => {
+                    let err = A::Error::custom(format!("duplicate key '{pointer}'"));
+                    self.duplicates.borrow_mut().push(pointer);
+                    return Err(err);
+                }
+            }
+            self.duplicates.borrow_mut().push(pointer);
+        }
+        Ok(Value::Object(object))
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(DuplicateKeyPolicy::First, r#"{"a":{"b":1}}"#)]
+    #[case(DuplicateKeyPolicy::Last, r#"{"a":{"b":2}}"#)]
+    fn test_parse_json_lenient(#[case] policy: DuplicateKeyPolicy, #[case] expected: &str) {
+        let doc = parse_json_lenient(
+            "\u{feff}{\"a\":{\"b\":1,\"b\":2}}\r\n\r\n".to_owned(),
+            policy,
+        )
+        .unwrap();
+        assert_eq!(doc.json_text, expected);
+        assert_eq!(
+            doc.normalizations
+                .iter()
+                .map(|normalization| normalization.kind)
+                .collect::<Vec<_>>(),
+            vec![
+                JsonNormalizationKind::ByteOrderMark,
+                JsonNormalizationKind::CarriageReturn,
+                JsonNormalizationKind::TrailingWhitespace,
+                JsonNormalizationKind::DuplicateKey,
+            ]
+        );
+        assert_eq!(doc.normalizations.last().unwrap().detail, "/a/b");
+    }
+
+    #[rstest]
+    fn test_parse_json_lenient_duplicate_key_error() {
+        assert_error(
+            parse_json_lenient(r#"{"a":[{"b/c":1,"b/c":2}]}"#.to_owned(), DuplicateKeyPolicy::Error),
+            DidSidekicksErrorKind::DuplicateJsonKey,
+            "/a/0/b~1c",
+        );
+    }
+
+    #[rstest]
+    #[case(r#"{"proof":[{"proofValue":"z1","type":"DataIntegrityProof","proofValue":"z2"}]}"#, "/proof/0/proofValue")]
+    #[case(r#"{"a":1,"b":{"c":2},"a":3}"#, "/a")]
+    fn test_parse_json_strict_duplicate_key(#[case] text: &str, #[case] pointer: &str) {
+        assert_error(
+            parse_json_strict(text),
+            DidSidekicksErrorKind::DuplicateJsonKey,
+            pointer,
+        );
+        validate_json_strict(text.to_owned()).unwrap_err();
+    }
+
+    #[rstest]
+    fn test_parse_json_strict() {
+        parse_json_strict(r#"{"a":{"a":1},"b":[{"a":2},{"a":3}]}"#).unwrap();
+        // no normalization whatsoever
+        assert_error(
+            parse_json_strict("\u{feff}{}"),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "",
+        );
+    }
+
+    #[rstest]
+    fn test_parse_did_log_lenient() {
+        let did_log = parse_did_log_lenient(
+            "[1]\r\n\r\n{\"x\":1,\"x\":2}\r\n".to_owned(),
+            DuplicateKeyPolicy::Last,
+        )
+        .unwrap();
+        assert_eq!(did_log.entries, vec!["[1]".to_owned(), r#"{"x":2}"#.to_owned()]);
+        assert!(did_log.normalizations.contains(&JsonNormalization {
+            kind: JsonNormalizationKind::BlankLine,
+            detail: "line 2".to_owned(),
+        }));
+        assert!(did_log.normalizations.contains(&JsonNormalization {
+            kind: JsonNormalizationKind::DuplicateKey,
+            detail: "line 3: /x".to_owned(),
+        }));
+
+        assert_error(
+            parse_did_log_lenient("[1]\n[2".to_owned(), DuplicateKeyPolicy::Last),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "line 2",
+        );
+    }
+
+    #[expect(clippy::little_endian_bytes, reason = "..")]
+    #[expect(clippy::big_endian_bytes, reason = "..")]
+    fn utf16(text: &str, little_endian: bool, bom: bool) -> Vec<u8> {
+        let units = (if bom { "\u{feff}" } else { "" })
+            .encode_utf16()
+            .chain(text.encode_utf16());
+        units
+            .flat_map(|unit| {
+                if little_endian {
+                    unit.to_le_bytes()
+                } else {
+                    unit.to_be_bytes()
+                }
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case(utf16("[1]\r\n[\"\u{e4}\"]\n", true, true), "UTF-16LE")]
+    #[case(utf16("[1]\r\n[\"\u{e4}\"]\n", false, true), "UTF-16BE")]
+    #[case(
+        utf16("[1]\r\n[\"\u{e4}\"]\n", true, false),
+        "UTF-16LE (detected without BOM)"
+    )]
+    #[case(
+        utf16("[1]\r\n[\"\u{e4}\"]\n", false, false),
+        "UTF-16BE (detected without BOM)"
+    )]
+    fn test_parse_did_log_bytes_lenient_utf16(#[case] bytes: Vec<u8>, #[case] encoding: &str) {
+        let did_log = parse_did_log_bytes_lenient(bytes, DuplicateKeyPolicy::Error).unwrap();
+        assert_eq!(
+            did_log.entries,
+            vec!["[1]".to_owned(), "[\"\u{e4}\"]".to_owned()]
+        );
+        assert!(did_log.normalizations.contains(&JsonNormalization {
+            kind: JsonNormalizationKind::Utf16Transcoded,
+            detail: encoding.to_owned(),
+        }));
+    }
+
+    #[rstest]
+    fn test_parse_did_log_bytes_lenient_utf8() {
+        let did_log = parse_did_log_bytes_lenient(
+            b"\xEF\xBB\xBF[1]\r\n[2]\r[3]\n".to_vec(),
+            DuplicateKeyPolicy::Error,
+        )
+        .unwrap();
+        assert_eq!(did_log.entries, vec!["[1]", "[2]", "[3]"]);
+        assert_eq!(
+            did_log.normalizations,
+            vec![
+                JsonNormalization {
+                    kind: JsonNormalizationKind::ByteOrderMark,
+                    detail: String::new(),
+                },
+                JsonNormalization {
+                    kind: JsonNormalizationKind::CarriageReturn,
+                    detail: "CRLF, CR".to_owned(),
+                },
+                JsonNormalization {
+                    kind: JsonNormalizationKind::TrailingWhitespace,
+                    detail: String::new(),
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(b"[1]\n[\xC3]".to_vec(), "Invalid UTF-8 sequence at byte 5")]
+    #[case(vec![0xFF, 0xFE, b'[', 0, b']'], "Truncated UTF-16LE input")]
+    #[case(vec![0xFF, 0xFE, 0x00, 0xD8, b'[', 0], "Invalid UTF-16LE input")] // lone surrogate
+    fn test_parse_did_log_bytes_lenient_invalid(
+        #[case] bytes: Vec<u8>,
+        #[case] error_string: &str,
+    ) {
+        assert_error(
+            parse_did_log_bytes_lenient(bytes, DuplicateKeyPolicy::Error),
+            DidSidekicksErrorKind::DeserializationFailed,
+            error_string,
+        );
+    }
+
+    #[rstest]
+    fn test_parse_json_lenient_deeply_nested() {
+        parse_json_lenient("[".repeat(10_000), DuplicateKeyPolicy::Last).unwrap_err();
+    }
+}
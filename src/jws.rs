@@ -0,0 +1,238 @@
+// SPDX-License-Identifier: MIT
+
+//! Raw JWS (compact serialization) sign/verify helpers, as specified by https://www.rfc-editor.org/rfc/rfc7515.
+//!
+//! Intended for surrounding protocols relying on plain JWS (e.g. status lists or trust statements),
+//! so no fully-fledged JOSE crate is required for the purpose.
+//!
+//! Currently, only `EdDSA` (over Ed25519 keys, see https://www.rfc-editor.org/rfc/rfc8037#section-3.1) is supported.
+
+use crate::bounded_json::from_slice_bounded;
+use crate::ed25519::{Ed25519SigningKey, Ed25519VerifyingKey, VerificationOptions};
+use crate::errors::DidSidekicksError;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use ed25519_dalek::Signature;
+use serde_json::{Map, Value};
+
+/// The name of the JOSE header parameter denoting the signature algorithm.
+pub const JWS_HEADER_ALG: &str = "alg";
+
+/// The JWS signature algorithms supported by this module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum JwsAlgorithm {
+    /// Ed25519 signature algorithm, as specified by https://www.rfc-editor.org/rfc/rfc8037#section-3.1.
+    EdDsa,
+}
+
+impl JwsAlgorithm {
+    /// Delivers the value of the `alg` header parameter denoting the algorithm.
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        match *self {
+            Self::EdDsa => "EdDSA",
+        }
+    }
+}
+
+/// The JOSE header and payload of a successfully verified JWS (see [`verify_compact`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct VerifiedJws {
+    /// The (protected) JOSE header.
+    pub header: Map<String, Value>,
+    /// The (decoded) payload.
+    pub payload: Vec<u8>,
+}
+
+fn jws_error(msg: impl core::fmt::Display) -> DidSidekicksError {
+    DidSidekicksError::InvalidJws(msg.to_string())
+}
+
+fn b64_decode(name: &str, encoded: &str) -> Result<Vec<u8>, DidSidekicksError> {
+    URL_SAFE_NO_PAD
+        .decode(encoded)
+        .map_err(|err| jws_error(format!("Malformed {name} (base64url expected): {err}")))
+}
+
+/// Creates a JWS (in compact serialization) by signing the supplied `payload` using the supplied `signing_key`.
+///
+/// The supplied (protected) JOSE `header` (e.g. featuring `typ` or `kid`) is completed by the `alg` header parameter.
+/// However, if already present, the `alg` header parameter must denote `EdDSA`.
+#[inline]
+pub fn sign_compact(
+    header: &Map<String, Value>,
+    payload: &[u8],
+    signing_key: &Ed25519SigningKey,
+) -> Result<String, DidSidekicksError> {
+    let alg = JwsAlgorithm::EdDsa.as_str();
+    let mut protected_header = header.clone();
+    match protected_header.get(JWS_HEADER_ALG) {
+        None => {
+            protected_header.insert(JWS_HEADER_ALG.to_owned(), Value::String(alg.to_owned()));
+        }
+        Some(value) if value.as_str() == Some(alg) => (),
+        Some(value) => {
+            return Err(jws_error(format!(
+                "Unsupported '{JWS_HEADER_ALG}' header parameter {value}, expected '{alg}'"
+            )))
+        }
+    }
+
+    let signing_input = format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(Value::Object(protected_header).to_string()),
+        URL_SAFE_NO_PAD.encode(payload)
+    );
+    let signature = signing_key.sign_bytes(signing_input.as_bytes())?;
+
+    Ok(format!(
+        "{signing_input}.{}",
+        URL_SAFE_NO_PAD.encode(signature.signature.to_bytes())
+    ))
+}
+
+/// Decodes the (protected) JOSE header of the supplied `jws` (in compact serialization) WITHOUT verifying its signature.
+///
+/// E.g. to figure out the `kid` of the verifying key to be supplied to [`verify_compact`].
+#[inline]
+pub fn decode_header_unverified(jws: &str) -> Result<Map<String, Value>, DidSidekicksError> {
+    let protected = jws
+        .split('.')
+        .next()
+        .ok_or_else(|| jws_error("Missing JOSE header"))?;
+    match from_slice_bounded(&b64_decode("JOSE header", protected)?) {
+        Ok(Value::Object(header)) => Ok(header),
+        Ok(_) => Err(jws_error("The JOSE header must be a JSON object")),
+        Err(err) => Err(jws_error(format!("Malformed JOSE header: {}", err.detail()))),
+    }
+}
+
+/// Verifies the supplied `jws` (in compact serialization) using the supplied `verifying_key`,
+/// thus delivering its JOSE header and (decoded) payload.
+///
+/// The `alg` header parameter must denote `EdDSA`, whereas JWS featuring a detached payload or
+/// critical header parameters (`crit`) are rejected.
+#[inline]
+pub fn verify_compact(
+    jws: &str,
+    verifying_key: &Ed25519VerifyingKey,
+) -> Result<VerifiedJws, DidSidekicksError> {
+    let mut parts = jws.split('.');
+    let (Some(protected), Some(payload), Some(signature), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(jws_error(
+            "Malformed JWS (compact serialization featuring exactly three parts expected)",
+        ));
+    };
+    if payload.is_empty() {
+        return Err(jws_error("Detached payload is not supported"));
+    }
+
+    let header = decode_header_unverified(protected)?;
+    let alg = JwsAlgorithm::EdDsa.as_str();
+    if header.get(JWS_HEADER_ALG).and_then(Value::as_str) != Some(alg) {
+        return Err(jws_error(format!(
+            "Unsupported '{JWS_HEADER_ALG}' header parameter, expected '{alg}'"
+        )));
+    }
+    if header.contains_key("crit") {
+        return Err(jws_error(
+            "Critical header parameters ('crit') are not supported",
+        ));
+    }
+
+    let decoded_signature = Signature::from_slice(&b64_decode("signature", signature)?)
+        .map_err(|err| jws_error(format!("Malformed signature: {err}")))?;
+    verifying_key
+        .verify_with_options(
+            format!("{protected}.{payload}").as_bytes(),
+            &decoded_signature,
+            VerificationOptions::default(),
+        )
+        .map_err(|err| jws_error(format!("Invalid signature: {err}")))?;
+
+    Ok(VerifiedJws {
+        header,
+        payload: b64_decode("payload", payload)?,
+    })
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::ed25519::Ed25519KeyPair;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+    use serde_json::json;
+
+    fn header(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[rstest]
+    fn test_sign_verify_compact() {
+        let key_pair = Ed25519KeyPair::generate();
+        let jws = sign_compact(
+            &header(json!({"typ": "statuslist+jwt", "kid": "did:example:issuer#key-1"})),
+            b"{\"sub\":\"https://example.com/statuslists/1\"}",
+            &key_pair.signing_key,
+        )
+        .unwrap();
+
+        assert_eq!(
+            decode_header_unverified(&jws).unwrap().get("kid").unwrap(),
+            "did:example:issuer#key-1"
+        );
+        let verified = verify_compact(&jws, &key_pair.verifying_key).unwrap();
+        assert_eq!(verified.header.get(JWS_HEADER_ALG).unwrap(), "EdDSA");
+        assert_eq!(
+            verified.payload,
+            b"{\"sub\":\"https://example.com/statuslists/1\"}"
+        );
+
+        // signed by a different key
+        assert_error(
+            verify_compact(&jws, &Ed25519KeyPair::generate().verifying_key),
+            DidSidekicksErrorKind::InvalidJws,
+            "Invalid signature",
+        );
+    }
+
+    #[rstest]
+    #[case("a.b", "exactly three parts")]
+    #[case("a.b.c.d", "exactly three parts")]
+    #[case("eyJhbGciOiJFZERTQSJ9..c2ln", "Detached payload")]
+    #[case("eyJhbGciOiJFUzI1NiJ9.cGF5bG9hZA.c2ln", "Unsupported 'alg'")]
+    #[case("!.cGF5bG9hZA.c2ln", "Malformed JOSE header")]
+    fn test_verify_compact_malformed(#[case] jws: &str, #[case] error_contains: &str) {
+        assert_error(
+            verify_compact(jws, &Ed25519KeyPair::generate().verifying_key),
+            DidSidekicksErrorKind::InvalidJws,
+            error_contains,
+        );
+    }
+
+    #[rstest]
+    fn test_sign_compact_unsupported_alg() {
+        assert_error(
+            sign_compact(
+                &header(json!({"alg": "ES256"})),
+                b"payload",
+                &Ed25519KeyPair::generate().signing_key,
+            ),
+            DidSidekicksErrorKind::InvalidJws,
+            "Unsupported 'alg'",
+        );
+    }
+}
@@ -0,0 +1,241 @@
+// SPDX-License-Identifier: MIT
+
+//! Ed25519 → X25519 conversion.
+//!
+//! A DID document may feature a `keyAgreement` entry derived from the very same controller key
+//! (instead of managing a dedicated X25519 key), e.g. for the sake of DIDComm anoncrypt (see `didcomm` module).
+//!
+//! The conversion is the one of libsodium (`crypto_sign_ed25519_pk_to_curve25519`/`crypto_sign_ed25519_sk_to_curve25519`), i.e.:
+//! - a verifying key (Edwards point) is mapped onto the Montgomery curve (see https://www.rfc-editor.org/rfc/rfc7748#section-4.1),
+//! - a signing key is converted into the scalar it is expanded into (as specified by https://www.rfc-editor.org/rfc/rfc8032#section-5.1.5).
+//!
+//! CAUTION Reusing a single key for both signing and key agreement is a deliberate trade-off: compromising either usage
+//! compromises the other one as well.
+
+use crate::did_doc::{Jwk, VerificationMethod, VerificationType};
+use crate::ed25519::{Ed25519KeyPair, Ed25519VerifyingKey, MultiBaseConverter};
+use crate::errors::DidSidekicksError;
+use crate::multibase::MultibaseEncoderDecoder;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use ed25519_dalek::SigningKey;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+/// The length of an X25519 public (or secret) key, as specified by https://www.rfc-editor.org/rfc/rfc7748#section-5.
+pub const X25519_KEY_LENGTH: usize = 32;
+
+/// The multicodec `x25519-pub` (0xec) as varint, as specified by https://github.com/multiformats/multicodec/blob/master/table.csv.
+const X25519_PUB_MULTICODEC: [u8; 2] = [0xec, 0x01];
+
+/// An X25519 (key agreement) public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct X25519PublicKey {
+    public_key: PublicKey,
+}
+
+impl X25519PublicKey {
+    /// Delivers the raw 32-byte public key (i.e. the u-coordinate, as specified by https://www.rfc-editor.org/rfc/rfc7748#section-5).
+    #[inline]
+    pub fn to_bytes(&self) -> [u8; X25519_KEY_LENGTH] {
+        self.public_key.to_bytes()
+    }
+
+    /// Exports this public key as (public) OKP JWK, i.e. `{"kty":"OKP","crv":"X25519","x":"..."}` (see https://www.rfc-editor.org/rfc/rfc8037#section-2).
+    #[inline]
+    pub fn to_jwk(&self) -> Jwk {
+        Jwk {
+            alg: None,
+            kid: None,
+            kty: Some("OKP".to_owned()),
+            crv: Some("X25519".to_owned()),
+            x: Some(URL_SAFE_NO_PAD.encode(self.public_key.as_bytes())),
+            y: None,
+        }
+    }
+
+    /// Delivers a `JsonWebKey2020` verification method (featuring the supplied `id`), ready to be referenced by
+    /// the `keyAgreement` verification relationship of a DID document.
+    #[inline]
+    pub fn to_verification_method(&self, id: String) -> VerificationMethod {
+        VerificationMethod {
+            id,
+            controller: String::new(),
+            verification_type: VerificationType::JsonWebKey2020,
+            public_key_multibase: None,
+            public_key_jwk: Some(self.to_jwk()),
+            attested: false,
+        }
+    }
+}
+
+impl MultiBaseConverter for X25519PublicKey {
+    /// As specified by https://w3c-ccg.github.io/did-key-spec/#x25519:
+    ///
+    /// The encoding of an X25519 public key starts with the two-byte prefix 0xec01 (the varint expression of 0xec),
+    /// followed by the 32-byte public key data. The resulting 34-byte value is then encoded using the base-58-btc alphabet
+    /// and prepended with the base-58-btc Multibase header (z), i.e. `z6LS...`.
+    #[inline]
+    fn to_multibase(&self) -> String {
+        let mut public_key_with_prefix: [u8; X25519_KEY_LENGTH + 2] = [0; X25519_KEY_LENGTH + 2];
+        public_key_with_prefix[..2].copy_from_slice(&X25519_PUB_MULTICODEC);
+        public_key_with_prefix[2..].copy_from_slice(self.public_key.as_bytes());
+        MultibaseEncoderDecoder::default().encode_base58btc(&public_key_with_prefix)
+    }
+
+    /// The counterpart of [`X25519PublicKey::to_multibase`].
+    ///
+    /// Any multikey lacking the `x25519-pub` multicodec prefix (e.g. an Ed25519 one) is denoted by [`DidSidekicksError::DeserializationFailed`].
+    #[inline]
+    fn from_multibase(multibase: &str) -> Result<Self, DidSidekicksError> {
+        let mut public_key_buff: [u8; X25519_KEY_LENGTH + 2] = [0; X25519_KEY_LENGTH + 2];
+        if let Err(err) =
+            MultibaseEncoderDecoder::default().decode_base58_onto(multibase, &mut public_key_buff)
+        {
+            return Err(DidSidekicksError::DeserializationFailed(format!("{err}")));
+        }
+        if public_key_buff[..2] != X25519_PUB_MULTICODEC {
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "{multibase} is not an X25519 multikey"
+            )));
+        }
+
+        let mut public_key: [u8; X25519_KEY_LENGTH] = [0; X25519_KEY_LENGTH];
+        public_key.copy_from_slice(&public_key_buff[2..]); // get rid of the multibase header
+        Ok(Self {
+            public_key: PublicKey::from(public_key),
+        })
+    }
+}
+
+/// An X25519 key pair (as derived by [`Ed25519KeyPair::to_x25519`]).
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct X25519KeyPair {
+    pub public_key: X25519PublicKey,
+    pub secret_key: StaticSecret,
+}
+
+impl X25519KeyPair {
+    /// Delivers the raw 32-byte secret key, e.g. as `recipient_secret_key` of `didcomm::unpack_anoncrypt`.
+    #[inline]
+    pub fn secret_key_bytes(&self) -> [u8; X25519_KEY_LENGTH] {
+        self.secret_key.to_bytes()
+    }
+}
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
+impl Ed25519VerifyingKey {
+    /// Converts this verifying key into its X25519 counterpart (see [module documentation](crate::key_agreement)).
+    #[inline]
+    pub fn to_x25519(&self) -> X25519PublicKey {
+        X25519PublicKey {
+            public_key: PublicKey::from(self.verifying_key.to_montgomery().to_bytes()),
+        }
+    }
+}
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
+impl Ed25519KeyPair {
+    /// Converts this key pair into its X25519 counterpart (see [module documentation](crate::key_agreement)),
+    /// whereas the public key of the delivered key pair matches [`Ed25519VerifyingKey::to_x25519`].
+    ///
+    /// For a non-extractable (platform) signing key, [`DidSidekicksError::SerializationFailed`] is returned instead.
+    #[inline]
+    pub fn to_x25519(&self) -> Result<X25519KeyPair, DidSidekicksError> {
+        let secret_key = StaticSecret::from(
+            SigningKey::from_bytes(&self.signing_key.to_bytes()?).to_scalar_bytes(),
+        );
+        Ok(X25519KeyPair {
+            public_key: X25519PublicKey {
+                public_key: PublicKey::from(&secret_key),
+            },
+            secret_key,
+        })
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use hex::encode as hex_encode;
+    use rstest::rstest;
+
+    #[rstest]
+    // Ed25519 key pair as specified by https://www.rfc-editor.org/rfc/rfc8032#section-7.1 (TEST 1),
+    // whereas the X25519 public key is the one delivered by libsodium's crypto_sign_ed25519_pk_to_curve25519
+    #[case(
+        "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+        "d85e07ec22b0ad881537c2f44d662d1a143cf830c57aca4305d85c7a90f6b62e",
+        "z6LSrEnPXPcLyNLKJPhdJ1eWqyYKARWket5BbiN1rjdUsQ9b",
+        "2F4H7CKwrYgVN8L0TWYtGhQ8-DDFespDBdhcepD2ti4"
+    )]
+    fn test_to_x25519(
+        #[case] seed_hex: &str,
+        #[case] public_key_hex: &str,
+        #[case] multikey: &str,
+        #[case] jwk_x: &str,
+    ) {
+        let seed: [u8; 32] = hex::decode(seed_hex).unwrap().try_into().unwrap();
+        let ed25519 = Ed25519KeyPair::from_seed(&seed);
+
+        let public_key = ed25519.verifying_key.to_x25519();
+        assert_eq!(hex_encode(public_key.to_bytes()), public_key_hex);
+        assert_eq!(public_key.to_multibase(), multikey);
+        assert_eq!(
+            X25519PublicKey::from_multibase(multikey).unwrap(),
+            public_key
+        );
+        assert_eq!(public_key.to_jwk().x.unwrap(), jwk_x);
+
+        // the secret key must match the (converted) public key
+        let key_pair = ed25519.to_x25519().unwrap();
+        assert_eq!(key_pair.public_key, public_key);
+        assert_eq!(
+            PublicKey::from(&StaticSecret::from(key_pair.secret_key_bytes())),
+            public_key.public_key
+        );
+
+        let method = public_key.to_verification_method("did:example:alice#key-x25519-1".to_owned());
+        assert_eq!(method.verification_type, VerificationType::JsonWebKey2020);
+        assert_eq!(method.public_key_jwk.unwrap().crv.unwrap(), "X25519");
+    }
+
+    #[rstest]
+    fn test_key_agreement() {
+        let alice = Ed25519KeyPair::generate().to_x25519().unwrap();
+        let bob = Ed25519KeyPair::generate();
+
+        // a shared secret agreed upon solely by relying on the (public) Ed25519 verifying key of the counterpart
+        assert_eq!(
+            alice
+                .secret_key
+                .diffie_hellman(&bob.verifying_key.to_x25519().public_key)
+                .to_bytes(),
+            bob.to_x25519()
+                .unwrap()
+                .secret_key
+                .diffie_hellman(&alice.public_key.public_key)
+                .to_bytes()
+        );
+    }
+
+    #[rstest]
+    // an Ed25519 multikey
+    #[case(
+        "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2",
+        "is not an X25519 multikey"
+    )]
+    #[case("z6LS", "")]
+    fn test_from_multibase_invalid(#[case] multibase: &str, #[case] error_contains: &str) {
+        assert_error(
+            X25519PublicKey::from_multibase(multibase),
+            DidSidekicksErrorKind::DeserializationFailed,
+            error_contains,
+        );
+    }
+}
@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: MIT
+
+//! Key attestation verification hooks, intended to prove keys referenced by a DID document are hardware-bound
+//! (as required by issuance policy).
+//!
+//! Platform key attestations (e.g. Android Key Attestation or Apple App Attest) are supplied as opaque bytes
+//! and verified by means of a (typically foreign) [`KeyAttestationVerifier`], whereas the outcome is reflected
+//! by the [`VerificationMethod::attested`] flag (see [`DidDoc::with_key_attestations`]).
+
+use crate::did_doc::{DidDoc, VerificationMethod};
+use crate::errors::DidSidekicksError;
+use crate::panic_boundary::catch_panic;
+use std::sync::Arc;
+
+/// The supported platform key attestation formats.
+///
+/// A UniFFI-compliant enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum KeyAttestationFormat {
+    /// See https://developer.android.com/privacy-and-security/security-key-attestation.
+    AndroidKeyAttestation,
+    /// See https://developer.apple.com/documentation/devicecheck/validating-apps-that-connect-to-your-server.
+    AppleAppAttest,
+}
+
+/// A platform key attestation of a key referenced by a DID document.
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct KeyAttestation {
+    /// The id of the (attested) verification method, e.g. `did:example:123#key-1`.
+    pub verification_method_id: String,
+    pub format: KeyAttestationFormat,
+    /// The attestation itself (e.g. a certificate chain or an attestation object), as opaque bytes.
+    pub attestation: Vec<u8>,
+}
+
+/// A verifier of platform key attestations.
+///
+/// A UniFFI-compliant trait (that may also be implemented by foreign code).
+pub trait KeyAttestationVerifier: Send + Sync {
+    /// Verifies the supplied (opaque) `attestation` in the supplied `format` against the supplied `verification_method`
+    /// (featuring the attested public key). Returns `true` if the key is proven to be hardware-bound.
+    fn verify_attestation(
+        &self,
+        format: KeyAttestationFormat,
+        attestation: Vec<u8>,
+        verification_method: VerificationMethod,
+    ) -> bool;
+}
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
+impl DidDoc {
+    /// Delivers a copy of this DID document, where the [`VerificationMethod::attested`] flag is set for each verification method
+    /// (across all verification relationships) whose attestation has been successfully verified by the supplied `verifier`.
+    ///
+    /// If any of supplied `attestations` refers to a non-existing verification method, [`DidSidekicksError::KeyNotFound`] is returned.
+    /// A failed verification is not an error, as it simply leaves the flag unset (so it is up to the caller's policy).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn with_key_attestations(
+        &self,
+        attestations: Vec<KeyAttestation>,
+        verifier: Arc<dyn KeyAttestationVerifier>,
+    ) -> Result<Arc<Self>, DidSidekicksError> {
+        catch_panic(
+            || {
+                let mut did_doc = self.clone();
+                for attestation in attestations {
+                    let method = did_doc
+                        .verification_methods()
+                        .find(|method| method.id == attestation.verification_method_id)
+                        .cloned()
+                        .ok_or_else(|| {
+                            DidSidekicksError::KeyNotFound(
+                                attestation.verification_method_id.to_owned(),
+                            )
+                        })?;
+
+                    if verifier.verify_attestation(
+                        attestation.format,
+                        attestation.attestation,
+                        method,
+                    ) {
+                        did_doc
+                            .verification_methods_mut()
+                            .filter(|attested| attested.id == attestation.verification_method_id)
+                            .for_each(|attested| attested.attested = true);
+                    }
+                }
+                Ok(Arc::new(did_doc))
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    fn verification_methods(&self) -> impl Iterator<Item = &VerificationMethod> {
+        self.verification_method
+            .iter()
+            .chain(self.authentication.iter())
+            .chain(self.capability_invocation.iter())
+            .chain(self.capability_delegation.iter())
+            .chain(self.assertion_method.iter())
+            .chain(self.key_agreement.iter())
+    }
+
+    fn verification_methods_mut(&mut self) -> impl Iterator<Item = &mut VerificationMethod> {
+        self.verification_method
+            .iter_mut()
+            .chain(self.authentication.iter_mut())
+            .chain(self.capability_invocation.iter_mut())
+            .chain(self.capability_delegation.iter_mut())
+            .chain(self.assertion_method.iter_mut())
+            .chain(self.key_agreement.iter_mut())
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+    use serde_json::json;
+
+    const DID: &str = "did:example:holder";
+
+    /// Accepts Android key attestations consisting of the very same bytes as the id of the attested verification method.
+    struct MockVerifier;
+    impl KeyAttestationVerifier for MockVerifier {
+        fn verify_attestation(
+            &self,
+            format: KeyAttestationFormat,
+            attestation: Vec<u8>,
+            verification_method: VerificationMethod,
+        ) -> bool {
+            format == KeyAttestationFormat::AndroidKeyAttestation
+                && attestation == verification_method.id.as_bytes()
+        }
+    }
+
+    fn did_doc() -> DidDoc {
+        let method = json!({"id": format!("{DID}#key-1"), "type": "Multikey", "publicKeyMultibase": "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2"});
+        let other = json!({"id": format!("{DID}#key-2"), "type": "Multikey", "publicKeyMultibase": "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2"});
+        DidDoc::from_json(
+            &json!({
+                "@context": ["https://www.w3.org/ns/did/v1"],
+                "id": DID,
+                "verificationMethod": [method, other],
+                "authentication": [method],
+            })
+            .to_string(),
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    fn test_with_key_attestations() {
+        let key_1 = format!("{DID}#key-1");
+        let key_2 = format!("{DID}#key-2");
+        let attested = did_doc()
+            .with_key_attestations(
+                vec![
+                    KeyAttestation {
+                        verification_method_id: key_1.to_owned(),
+                        format: KeyAttestationFormat::AndroidKeyAttestation,
+                        attestation: key_1.as_bytes().to_vec(),
+                    },
+                    KeyAttestation {
+                        verification_method_id: key_2.to_owned(),
+                        format: KeyAttestationFormat::AppleAppAttest,
+                        attestation: key_2.as_bytes().to_vec(),
+                    },
+                ],
+                Arc::new(MockVerifier),
+            )
+            .unwrap();
+
+        assert_eq!(
+            attested
+                .verification_method
+                .iter()
+                .map(|method| method.attested)
+                .collect::<Vec<_>>(),
+            vec![true, false]
+        );
+        assert!(attested.authentication.first().unwrap().attested);
+        // the flag is never serialized
+        assert!(!serde_json::to_string(attested.as_ref()).unwrap().contains("attested"));
+    }
+
+    #[rstest]
+    fn test_with_key_attestations_unknown_key() {
+        assert_error(
+            did_doc().with_key_attestations(
+                vec![KeyAttestation {
+                    verification_method_id: format!("{DID}#unknown"),
+                    format: KeyAttestationFormat::AndroidKeyAttestation,
+                    attestation: vec![],
+                }],
+                Arc::new(MockVerifier),
+            ),
+            DidSidekicksErrorKind::KeyNotFound,
+            "#unknown",
+        );
+    }
+}
@@ -0,0 +1,414 @@
+// SPDX-License-Identifier: MIT
+
+//! Auditable key ceremonies, recorded as a signed transcript.
+//!
+//! A ceremony covers the generation of genesis (update) keys, the commitment to pre-rotation keys
+//! (`nextKeyHashes`) and the creation of the resulting genesis DID log entry.
+//!
+//! The transcript is a human-readable JSON document secured by an `eddsa-jcs-2022` Data Integrity proof of
+//! the ceremony officer, along with a (printable) summary hash to be e.g. written down in the ceremony minutes.
+//!
+//! CAUTION Neither signing keys nor pre-rotation (verifying) keys are ever recorded in a transcript,
+//! as only their commitment (hash) is to be disclosed in advance.
+
+use crate::bounded_json::from_str_bounded;
+use crate::ed25519::{
+    did_key_verification_method_from_verifying_key, Ed25519KeyPair, MultiBaseConverter as _,
+    VerificationOptions,
+};
+use crate::errors::DidSidekicksError;
+use crate::jcs_sha256_hasher::JcsSha256Hasher;
+use crate::json_emission::{emit_json, EmitOptions};
+use crate::vc_data_integrity::{
+    verify_secured_document, CryptoSuiteProofOptions, EddsaJcs2022Cryptosuite,
+    VCDataIntegrity as _, PARAM_NEXT_KEY_HASHES, PARAM_UPDATE_KEYS,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// The version of the transcript format, to be incremented on any change of the structure of the transcript.
+pub const KEY_CEREMONY_TRANSCRIPT_FORMAT_VERSION: u64 = 1;
+
+/// A single (recorded) step of a [`KeyCeremony`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "event",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum KeyCeremonyEvent {
+    /// An update key (multikey) was generated.
+    UpdateKeyGenerated {
+        timestamp: DateTime<Utc>,
+        update_key: String,
+    },
+    /// A pre-rotation key was generated, while only its hash was disclosed.
+    NextKeyHashCommitted {
+        timestamp: DateTime<Utc>,
+        next_key_hash: String,
+    },
+    /// The genesis DID log entry (featuring the generated keys) was created.
+    GenesisEntryRecorded {
+        timestamp: DateTime<Utc>,
+        entry_hash: String,
+        entry: Value,
+    },
+}
+
+/// A key ceremony in progress, recording each of its steps.
+///
+/// Once all keys are generated and the genesis DID log entry is recorded,
+/// the ceremony is to be concluded by the ceremony officer (see [`KeyCeremony::conclude`]).
+#[derive(Debug, Clone)]
+pub struct KeyCeremony {
+    ceremony_id: String,
+    participants: Vec<String>,
+    started: DateTime<Utc>,
+    events: Vec<KeyCeremonyEvent>,
+}
+
+impl KeyCeremony {
+    /// The only non-empty constructor of the type, thus starting a ceremony attended by the supplied `participants`.
+    #[inline]
+    pub fn new(ceremony_id: &str, participants: Vec<String>) -> Self {
+        Self {
+            ceremony_id: ceremony_id.to_owned(),
+            participants,
+            started: Utc::now(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Generates an update key, thus recording its (public) multikey.
+    #[inline]
+    pub fn generate_update_key(&mut self) -> Ed25519KeyPair {
+        let key_pair = Ed25519KeyPair::generate();
+        self.events.push(KeyCeremonyEvent::UpdateKeyGenerated {
+            timestamp: Utc::now(),
+            update_key: key_pair.verifying_key.to_multibase(),
+        });
+        key_pair
+    }
+
+    /// Generates a pre-rotation key, thus recording (and delivering) its hash only, as required for `nextKeyHashes`.
+    #[inline]
+    pub fn generate_next_key(&mut self) -> (Ed25519KeyPair, String) {
+        let key_pair = Ed25519KeyPair::generate();
+        let next_key_hash = JcsSha256Hasher::default()
+            .base58btc_encode_multihash_multikey(&key_pair.verifying_key.to_multibase());
+        self.events.push(KeyCeremonyEvent::NextKeyHashCommitted {
+            timestamp: Utc::now(),
+            next_key_hash: next_key_hash.to_owned(),
+        });
+        (key_pair, next_key_hash)
+    }
+
+    /// Records the supplied genesis DID log `entry` (JSON text), thus delivering its (JCS-based) entry hash.
+    ///
+    /// Both `updateKeys` and `nextKeyHashes` DID method parameters of the entry must feature all the keys
+    /// generated in the course of this ceremony so far, otherwise [`DidSidekicksError::InvalidDidMethodParameter`] is returned.
+    #[inline]
+    #[expect(clippy::pattern_type_mismatch, reason = "..")]
+    pub fn record_genesis_entry(&mut self, entry: &str) -> Result<String, DidSidekicksError> {
+        let json_entry = from_str_bounded::<Value>(entry)?;
+        // did:webvh entries feature a `parameters` object, whereas (legacy) did:tdw entries are JSON arrays
+        let parameters = json_entry
+            .get("parameters")
+            .or_else(|| json_entry.get(2))
+            .cloned()
+            .unwrap_or_default();
+        let listed = |name: &str, value: &str| {
+            parameters
+                .get(name)
+                .and_then(Value::as_array)
+                .is_some_and(|values| values.iter().any(|val| val.as_str() == Some(value)))
+        };
+        for event in &self.events {
+            let (name, value) = match event {
+                KeyCeremonyEvent::UpdateKeyGenerated { update_key, .. } => {
+                    (PARAM_UPDATE_KEYS, update_key)
+                }
+                KeyCeremonyEvent::NextKeyHashCommitted { next_key_hash, .. } => {
+                    (PARAM_NEXT_KEY_HASHES, next_key_hash)
+                }
+                KeyCeremonyEvent::GenesisEntryRecorded { .. } => continue,
+            };
+            if !listed(name, value) {
+                return Err(DidSidekicksError::InvalidDidMethodParameter(format!(
+                    "The genesis entry's '{name}' DID method parameter lacks '{value}' generated in the course of the key ceremony"
+                )));
+            }
+        }
+
+        let entry_hash = JcsSha256Hasher::default()
+            .base58btc_encode_multihash(&json_entry)
+            .map_err(|err| DidSidekicksError::SerializationFailed(err.to_string()))?;
+        self.events.push(KeyCeremonyEvent::GenesisEntryRecorded {
+            timestamp: Utc::now(),
+            entry_hash: entry_hash.to_owned(),
+            entry: json_entry,
+        });
+        Ok(entry_hash)
+    }
+
+    /// Delivers all the steps recorded so far.
+    #[inline]
+    pub fn events(&self) -> &[KeyCeremonyEvent] {
+        &self.events
+    }
+
+    /// Concludes this ceremony, thus delivering its transcript signed by the supplied ceremony `officer`.
+    ///
+    /// A ceremony lacking the genesis DID log entry is denoted by [`DidSidekicksError::SerializationFailed`].
+    #[inline]
+    pub fn conclude(
+        self,
+        officer: &Ed25519KeyPair,
+    ) -> Result<KeyCeremonyTranscript, DidSidekicksError> {
+        if !self
+            .events
+            .iter()
+            .any(|event| matches!(*event, KeyCeremonyEvent::GenesisEntryRecorded { .. }))
+        {
+            return Err(DidSidekicksError::SerializationFailed(format!(
+                "The key ceremony '{}' is incomplete, as no genesis entry is recorded",
+                self.ceremony_id
+            )));
+        }
+
+        let unsecured = json!({
+            "version": KEY_CEREMONY_TRANSCRIPT_FORMAT_VERSION,
+            "ceremonyId": self.ceremony_id,
+            "participants": self.participants,
+            "started": self.started,
+            "concluded": Utc::now(),
+            "events": self.events,
+        });
+        let suite = EddsaJcs2022Cryptosuite {
+            verifying_key: Some(officer.verifying_key.clone()),
+            signing_key: Some(officer.signing_key.clone()),
+            verification_options: VerificationOptions::default(),
+        };
+        let options = CryptoSuiteProofOptions::from_verification_method(
+            &did_key_verification_method_from_verifying_key(&officer.verifying_key),
+        )
+        .with_proof_purpose("assertionMethod");
+        Ok(KeyCeremonyTranscript {
+            document: suite.add_proof(&unsecured, &options)?,
+        })
+    }
+}
+
+/// The signed transcript of a concluded [`KeyCeremony`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyCeremonyTranscript {
+    document: Value,
+}
+
+impl KeyCeremonyTranscript {
+    /// The parsing constructor, whereas any transcript of an unsupported version is denoted by
+    /// [`DidSidekicksError::DeserializationFailed`]. The proof is NOT verified (see [`KeyCeremonyTranscript::verify`]).
+    #[inline]
+    pub fn from_json(json: &str) -> Result<Self, DidSidekicksError> {
+        let document = from_str_bounded::<Value>(json)?;
+        match document.get("version").and_then(Value::as_u64) {
+            Some(KEY_CEREMONY_TRANSCRIPT_FORMAT_VERSION) => Ok(Self { document }),
+            version => Err(DidSidekicksError::DeserializationFailed(format!(
+                "Unsupported key ceremony transcript version: {version:?}"
+            ))),
+        }
+    }
+
+    /// Emits this transcript as JSON text according to the supplied `options`.
+    #[inline]
+    pub fn to_json(&self, options: EmitOptions) -> Result<String, DidSidekicksError> {
+        emit_json(&self.document, options)
+    }
+
+    /// Delivers all the recorded steps of the ceremony.
+    #[inline]
+    pub fn events(&self) -> Result<Vec<KeyCeremonyEvent>, DidSidekicksError> {
+        serde_json::from_value(self.document.get("events").cloned().unwrap_or_default())
+            .map_err(|err| DidSidekicksError::DeserializationFailed(err.to_string()))
+    }
+
+    /// Delivers the summary hash of this (signed) transcript, i.e. the base58btc-encoded SHA2-256 multihash
+    /// of its JCS canonical form, to be written down in the ceremony minutes.
+    #[inline]
+    pub fn summary_hash(&self) -> Result<String, DidSidekicksError> {
+        JcsSha256Hasher::default()
+            .base58btc_encode_multihash(&self.document)
+            .map_err(|err| DidSidekicksError::SerializationFailed(err.to_string()))
+    }
+
+    /// Delivers a printable (plain text) summary of this transcript, concluded by its summary hash.
+    #[inline]
+    #[expect(clippy::pattern_type_mismatch, reason = "..")]
+    pub fn printable_summary(&self) -> Result<String, DidSidekicksError> {
+        let text = |name: &str| {
+            self.document
+                .get(name)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_owned()
+        };
+        let mut lines = vec![
+            format!("Key ceremony: {}", text("ceremonyId")),
+            format!(
+                "Participants: {}",
+                self.document
+                    .get("participants")
+                    .and_then(Value::as_array)
+                    .map(|participants| participants
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<_>>()
+                        .join(", "))
+                    .unwrap_or_default()
+            ),
+            format!("Started: {}", text("started")),
+        ];
+        lines.extend(self.events()?.iter().map(|event| match event {
+            KeyCeremonyEvent::UpdateKeyGenerated {
+                timestamp,
+                update_key,
+            } => format!(
+                "{}  update key generated: {update_key}",
+                to_rfc3339(timestamp)
+            ),
+            KeyCeremonyEvent::NextKeyHashCommitted {
+                timestamp,
+                next_key_hash,
+            } => format!(
+                "{}  next key hash committed: {next_key_hash}",
+                to_rfc3339(timestamp)
+            ),
+            KeyCeremonyEvent::GenesisEntryRecorded {
+                timestamp,
+                entry_hash,
+                ..
+            } => format!(
+                "{}  genesis entry recorded: {entry_hash}",
+                to_rfc3339(timestamp)
+            ),
+        }));
+        lines.push(format!("Concluded: {}", text("concluded")));
+        lines.push(format!("Summary hash: {}", self.summary_hash()?));
+        Ok(lines.join("\n"))
+    }
+
+    /// Verifies the proof of this transcript, thus delivering the `did:key` DID of the ceremony officer.
+    ///
+    /// A missing or invalid proof is denoted by [`DidSidekicksError::InvalidDataIntegrityProof`].
+    #[inline]
+    pub fn verify(&self) -> Result<String, DidSidekicksError> {
+        verify_secured_document(&self.document)
+    }
+}
+
+fn to_rfc3339(timestamp: &DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(clippy::shadow_reuse, reason = "..")]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+
+    fn genesis_entry(update_keys: &[String], next_key_hashes: &[String]) -> String {
+        json!({
+            "versionId": "1-QmGenesis",
+            "versionTime": "2025-01-01T00:00:00Z",
+            "parameters": {
+                "method": "did:webvh:1.0",
+                "updateKeys": update_keys,
+                "nextKeyHashes": next_key_hashes,
+            },
+            "state": {},
+        })
+        .to_string()
+    }
+
+    #[rstest]
+    fn test_key_ceremony() {
+        let officer = Ed25519KeyPair::generate();
+        let mut ceremony = KeyCeremony::new("2025-01", vec!["Alice".to_owned(), "Bob".to_owned()]);
+        let update_key = ceremony.generate_update_key().verifying_key.to_multibase();
+        let (_, next_key_hash) = ceremony.generate_next_key();
+
+        assert_error(
+            ceremony.record_genesis_entry(&genesis_entry(&[update_key.to_owned()], &[])),
+            DidSidekicksErrorKind::InvalidDidMethodParameter,
+            "'nextKeyHashes' DID method parameter lacks",
+        );
+        let entry_hash = ceremony
+            .record_genesis_entry(&genesis_entry(&[update_key.to_owned()], &[next_key_hash]))
+            .unwrap();
+        assert_eq!(ceremony.events().len(), 3);
+
+        let transcript = ceremony.conclude(&officer).unwrap();
+        // roundtrip
+        let transcript =
+            KeyCeremonyTranscript::from_json(&transcript.to_json(EmitOptions::PRETTY).unwrap())
+                .unwrap();
+        assert_eq!(
+            transcript.verify().unwrap(),
+            format!("did:key:{}", officer.verifying_key.to_multibase())
+        );
+
+        let summary = transcript.printable_summary().unwrap();
+        assert!(summary.contains("Participants: Alice, Bob"));
+        assert!(summary.contains(&format!("update key generated: {update_key}")));
+        assert!(summary.contains(&format!("genesis entry recorded: {entry_hash}")));
+        assert!(summary.ends_with(&format!(
+            "Summary hash: {}",
+            transcript.summary_hash().unwrap()
+        )));
+    }
+
+    #[rstest]
+    fn test_tampered_transcript() {
+        let mut ceremony = KeyCeremony::new("2025-02", vec![]);
+        ceremony
+            .record_genesis_entry(&genesis_entry(&[], &[]))
+            .unwrap();
+        let json = ceremony
+            .conclude(&Ed25519KeyPair::generate())
+            .unwrap()
+            .to_json(EmitOptions::COMPACT)
+            .unwrap()
+            .replace("2025-02", "2025-03");
+        KeyCeremonyTranscript::from_json(&json)
+            .unwrap()
+            .verify()
+            .unwrap_err();
+    }
+
+    #[rstest]
+    fn test_incomplete_key_ceremony() {
+        let mut ceremony = KeyCeremony::new("2025-04", vec![]);
+        ceremony.generate_update_key();
+        assert_error(
+            ceremony.conclude(&Ed25519KeyPair::generate()),
+            DidSidekicksErrorKind::SerializationFailed,
+            "no genesis entry is recorded",
+        );
+        assert_error(
+            KeyCeremonyTranscript::from_json(r#"{"version":2}"#),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "Unsupported key ceremony transcript version",
+        );
+    }
+}
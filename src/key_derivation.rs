@@ -0,0 +1,351 @@
+// SPDX-License-Identifier: MIT
+
+//! Hierarchical deterministic derivation of Ed25519 keys.
+//!
+//! As specified by SLIP-0010 (https://github.com/satoshilabs/slips/blob/master/slip-0010.md),
+//! so a single master seed may deterministically (re)derive any number of keys, e.g. per-DID update keys
+//! as well as per-purpose (authentication vs. assertion) keys.
+//!
+//! The keys intended for DIDs are derived along the path `m/<DID_KEY_DERIVATION_PURPOSE>'/<did_index>'/<purpose>'/<key_index>'`
+//! (see [`did_key_derivation_path`]), whereas `purpose` denotes the [`KeyPurpose`] and `key_index` the (e.g. pre-rotation) generation of the key.
+//!
+//! CAUTION As SLIP-0010 supports hardened derivation only in case of Ed25519, any non-hardened path segment is rejected.
+
+use crate::ed25519::Ed25519KeyPair;
+use crate::errors::DidSidekicksError;
+use crate::panic_boundary::catch_panic;
+use ed25519_dalek::SECRET_KEY_LENGTH;
+use hmac::{Hmac, Mac as _};
+use sha2::Sha512;
+use std::sync::Arc;
+
+/// The offset of hardened child indices, i.e. the index `i'` denotes the child index `i + 2^31`.
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// The (hardened) first segment of any DID key derivation path (see [`did_key_derivation_path`]), i.e. `DID` in ASCII.
+pub const DID_KEY_DERIVATION_PURPOSE: u32 = 0x0044_4944;
+
+/// The minimum length (in bytes) of a master seed, as specified by BIP-0032 (i.e. 128 bits).
+pub const MIN_SEED_LENGTH: usize = 16;
+
+/// The maximum length (in bytes) of a master seed, as specified by BIP-0032 (i.e. 512 bits).
+pub const MAX_SEED_LENGTH: usize = 64;
+
+/// The HMAC-SHA512 key used to derive the master key from the seed, as specified by SLIP-0010 for the Ed25519 curve.
+const ED25519_CURVE_SEED: &[u8] = b"ed25519 seed";
+
+/// The purpose of a key derived along a DID key derivation path (see [`did_key_derivation_path`]).
+///
+/// A UniFFI-compliant enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum KeyPurpose {
+    /// A key authorized to update the DID (i.e. listed by the `updateKeys` DID method parameter).
+    Update,
+    /// A key referenced by the `authentication` verification relationship of the DID document.
+    Authentication,
+    /// A key referenced by the `assertionMethod` verification relationship of the DID document.
+    AssertionMethod,
+}
+
+impl KeyPurpose {
+    /// The (unhardened) index of the purpose segment of a DID key derivation path.
+    #[inline]
+    pub const fn index(self) -> u32 {
+        match self {
+            Self::Update => 0,
+            Self::Authentication => 1,
+            Self::AssertionMethod => 2,
+        }
+    }
+}
+
+/// Delivers the derivation path (e.g. `m/4475204'/0'/1'/0'`) of the key featuring the supplied `purpose` and `key_index`
+/// intended for the DID denoted by the supplied `did_index`.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn did_key_derivation_path(did_index: u32, purpose: KeyPurpose, key_index: u32) -> String {
+    format!(
+        "m/{DID_KEY_DERIVATION_PURPOSE}'/{did_index}'/{}'/{key_index}'",
+        purpose.index()
+    )
+}
+
+/// Parses the supplied derivation `path` (e.g. `m/0'/1'`), thus delivering the (hardened) child indices.
+///
+/// Both `'` and `h` (or `H`) denote a hardened segment.
+///
+/// Any malformed or non-hardened segment is denoted by [`DidSidekicksError::DeserializationFailed`].
+#[inline]
+pub fn parse_derivation_path(path: &str) -> Result<Vec<u32>, DidSidekicksError> {
+    let mut segments = path.split('/');
+    if segments.next() != Some("m") {
+        return Err(DidSidekicksError::DeserializationFailed(format!(
+            "Invalid derivation path '{path}': it must start with 'm'"
+        )));
+    }
+    segments
+        .map(|segment| {
+            let Some(index) = segment
+                .strip_suffix(['\'', 'h', 'H'])
+                .and_then(|index| index.parse::<u32>().ok())
+            else {
+                return Err(DidSidekicksError::DeserializationFailed(format!(
+                    "Invalid derivation path '{path}': the segment '{segment}' is not a hardened index"
+                )));
+            };
+            if index >= HARDENED_OFFSET {
+                return Err(DidSidekicksError::DeserializationFailed(format!(
+                    "Invalid derivation path '{path}': the index {index} exceeds {}",
+                    HARDENED_OFFSET.saturating_sub(1)
+                )));
+            }
+            Ok(index)
+        })
+        .collect()
+}
+
+/// A SLIP-0010 extended (Ed25519) secret key, i.e. a secret key along with its chain code.
+#[derive(Clone, PartialEq, Eq)]
+struct ExtendedSecretKey {
+    secret_key: [u8; SECRET_KEY_LENGTH],
+    chain_code: [u8; 32],
+}
+
+impl ExtendedSecretKey {
+    /// Computes HMAC-SHA512 of the supplied `data` using the supplied `key`, whereas the left half of the result
+    /// denotes the secret key and the right half the chain code.
+    fn from_hmac(key: &[u8], data: &[&[u8]]) -> Result<Self, DidSidekicksError> {
+        let mut mac = Hmac::<Sha512>::new_from_slice(key)
+            .map_err(|err| DidSidekicksError::InternalError(err.to_string()))?;
+        data.iter().for_each(|chunk| mac.update(chunk));
+        let output = mac.finalize().into_bytes();
+        output
+            .split_first_chunk::<SECRET_KEY_LENGTH>()
+            .and_then(|(secret_key, rest)| {
+                rest.first_chunk::<32>().map(|chain_code| Self {
+                    secret_key: *secret_key,
+                    chain_code: *chain_code,
+                })
+            })
+            .ok_or_else(|| {
+                DidSidekicksError::InternalError("HMAC-SHA512 output too short".to_owned())
+            })
+    }
+
+    /// Derives the hardened child key denoted by the supplied (unhardened) `index`.
+    #[expect(clippy::big_endian_bytes, reason = "..")]
+    fn derive_child(&self, index: u32) -> Result<Self, DidSidekicksError> {
+        Self::from_hmac(
+            &self.chain_code,
+            &[
+                &[0x00],
+                &self.secret_key,
+                &index.saturating_add(HARDENED_OFFSET).to_be_bytes(),
+            ],
+        )
+    }
+}
+
+/// A SLIP-0010 derivation of Ed25519 keys from a single master seed (see [module documentation](self)).
+///
+/// The seed itself is never retained, but the master (extended) key derived from it.
+///
+/// A UniFFI-compliant object.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Ed25519KeyDerivation {
+    master: ExtendedSecretKey,
+}
+
+impl core::fmt::Debug for Ed25519KeyDerivation {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Ed25519KeyDerivation")
+            .finish_non_exhaustive()
+    }
+}
+
+impl Ed25519KeyDerivation {
+    /// Derives the master key from the supplied `seed` (e.g. a BIP-0039 seed), as specified by SLIP-0010.
+    ///
+    /// A seed of improper length (see [`MIN_SEED_LENGTH`] and [`MAX_SEED_LENGTH`]) is denoted by [`DidSidekicksError::DeserializationFailed`].
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub fn from_seed(seed: &[u8]) -> Result<Self, DidSidekicksError> {
+        catch_panic(
+            || {
+                if !(MIN_SEED_LENGTH..=MAX_SEED_LENGTH).contains(&seed.len()) {
+                    return Err(DidSidekicksError::DeserializationFailed(format!(
+                        "Invalid seed length {} ({MIN_SEED_LENGTH} to {MAX_SEED_LENGTH} bytes expected)",
+                        seed.len()
+                    )));
+                }
+                Ok(Self {
+                    master: ExtendedSecretKey::from_hmac(ED25519_CURVE_SEED, &[seed])?,
+                })
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Derives the key pair denoted by the supplied derivation `path` (e.g. `m/0'/1'`, see [`parse_derivation_path`]).
+    ///
+    /// A malformed (or non-hardened) `path` is denoted by [`DidSidekicksError::DeserializationFailed`].
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn derive(&self, path: &str) -> Result<Arc<Ed25519KeyPair>, DidSidekicksError> {
+        catch_panic(
+            || {
+                parse_derivation_path(path)?
+                    .into_iter()
+                    .try_fold(self.master.clone(), |parent, index| {
+                        parent.derive_child(index)
+                    })
+                    .map(|extended| Arc::new(Ed25519KeyPair::from_seed(&extended.secret_key)))
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Derives the key pair featuring the supplied `purpose` and `key_index` intended for the DID denoted by the supplied `did_index`,
+    /// i.e. along the path delivered by [`did_key_derivation_path`].
+    ///
+    /// Any index exceeding `2^31 - 1` is denoted by [`DidSidekicksError::DeserializationFailed`].
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn derive_did_key(
+        &self,
+        did_index: u32,
+        purpose: KeyPurpose,
+        key_index: u32,
+    ) -> Result<Arc<Ed25519KeyPair>, DidSidekicksError> {
+        catch_panic(
+            || self.derive(&did_key_derivation_path(did_index, purpose, key_index)),
+            DidSidekicksError::InternalError,
+        )
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use hex::{decode as hex_decode, encode as hex_encode};
+    use rstest::rstest;
+
+    #[rstest]
+    // Test vector 1 for ed25519, as specified by https://github.com/satoshilabs/slips/blob/master/slip-0010.md#test-vector-1-for-ed25519
+    #[case(
+        "000102030405060708090a0b0c0d0e0f",
+        "m",
+        "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+    )]
+    #[case(
+        "000102030405060708090a0b0c0d0e0f",
+        "m/0'",
+        "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+    )]
+    #[case(
+        "000102030405060708090a0b0c0d0e0f",
+        "m/0H/1H/2H",
+        "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9"
+    )]
+    #[case(
+        "000102030405060708090a0b0c0d0e0f",
+        "m/0'/1'/2'/2'/1000000000'",
+        "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793"
+    )]
+    // Test vector 2 for ed25519, as specified by https://github.com/satoshilabs/slips/blob/master/slip-0010.md#test-vector-2-for-ed25519
+    #[case(
+        "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+        "m/0'/2147483647'/1'/2147483646'/2'",
+        "551d333177df541ad876a60ea71f00447931c0a9da16f227c11ea080d7391b8d"
+    )]
+    fn test_derive(#[case] seed: &str, #[case] path: &str, #[case] expected_secret_key: &str) {
+        let derivation = Ed25519KeyDerivation::from_seed(&hex_decode(seed).unwrap()).unwrap();
+        let key_pair = derivation.derive(path).unwrap();
+        assert_eq!(
+            hex_encode(key_pair.signing_key.to_bytes().unwrap()),
+            expected_secret_key
+        );
+    }
+
+    #[rstest]
+    fn test_derive_did_key() {
+        let seed: Vec<u8> = (0..32).collect();
+        let derivation = Ed25519KeyDerivation::from_seed(&seed).unwrap();
+        assert_eq!(
+            did_key_derivation_path(0, KeyPurpose::Update, 0),
+            "m/4475204'/0'/0'/0'"
+        );
+        let update_key = derivation.derive_did_key(0, KeyPurpose::Update, 0).unwrap();
+        assert_eq!(
+            hex_encode(update_key.signing_key.to_bytes().unwrap()),
+            "a68164f0781c8fd061021ef490288e9e434eedc4b20cd87eba85b4f41437f753"
+        );
+        // deterministic, yet distinct per DID, purpose and key index
+        assert_eq!(
+            derivation.derive_did_key(0, KeyPurpose::Update, 0).unwrap(),
+            update_key
+        );
+        assert_ne!(
+            derivation.derive_did_key(1, KeyPurpose::Update, 0).unwrap(),
+            update_key
+        );
+        assert_ne!(
+            derivation
+                .derive_did_key(0, KeyPurpose::Authentication, 0)
+                .unwrap(),
+            derivation
+                .derive_did_key(0, KeyPurpose::AssertionMethod, 0)
+                .unwrap()
+        );
+        assert_ne!(
+            derivation.derive_did_key(0, KeyPurpose::Update, 1).unwrap(),
+            update_key
+        );
+        assert_error(
+            derivation.derive_did_key(HARDENED_OFFSET, KeyPurpose::Update, 0),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "the index 2147483648 exceeds 2147483647",
+        );
+    }
+
+    #[rstest]
+    #[case("", "it must start with 'm'")]
+    #[case("0'/1'", "it must start with 'm'")]
+    #[case("m/0", "the segment '0' is not a hardened index")]
+    #[case("m/0'/x'", "the segment 'x'' is not a hardened index")]
+    #[case("m/", "the segment '' is not a hardened index")]
+    #[case("m/2147483648'", "the index 2147483648 exceeds 2147483647")]
+    fn test_parse_derivation_path_invalid(#[case] path: &str, #[case] error_contains: &str) {
+        assert_error(
+            parse_derivation_path(path),
+            DidSidekicksErrorKind::DeserializationFailed,
+            error_contains,
+        );
+    }
+
+    #[rstest]
+    #[case(15)]
+    #[case(65)]
+    fn test_from_seed_invalid(#[case] length: usize) {
+        assert_error(
+            Ed25519KeyDerivation::from_seed(&vec![0; length]),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "Invalid seed length",
+        );
+    }
+}
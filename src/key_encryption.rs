@@ -0,0 +1,339 @@
+// SPDX-License-Identifier: MIT
+
+//! Password-protected export/import of Ed25519 key pairs, so controller keys may be persisted at rest
+//! without inventing yet another container.
+//!
+//! The 32-byte secret key is encrypted using AES-256-GCM, whereas the key-encryption key is derived from the password
+//! using PBKDF2-HMAC-SHA256 (see https://www.rfc-editor.org/rfc/rfc8018#section-5.2), both wrapped in a small JSON envelope, e.g.:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "kdf": "pbkdf2-hmac-sha256",
+//!   "iterations": 600000,
+//!   "salt": "<base64url>",
+//!   "cipher": "aes-256-gcm",
+//!   "nonce": "<base64url>",
+//!   "publicKeyMultibase": "z6Mk...",
+//!   "ciphertext": "<base64url>"
+//! }
+//! ```
+//!
+//! The (plain) `publicKeyMultibase` identifies the key without decrypting it, while it is bound to the ciphertext
+//! as additional authenticated data (AAD), hence it cannot be altered unnoticed.
+
+use crate::bounded_json::from_str_bounded;
+use crate::ed25519::{Ed25519KeyPair, MultiBaseConverter as _};
+use crate::errors::DidSidekicksError;
+use crate::json_emission::{emit_json, EmitOptions};
+use crate::panic_boundary::catch_panic;
+use crate::rng::fill_random_bytes;
+use aes_gcm::aead::{Aead as _, Payload};
+use aes_gcm::{Aes256Gcm, KeyInit as _, Nonce};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use ed25519_dalek::SECRET_KEY_LENGTH;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+/// The (only) version of the envelope currently supported.
+const ENVELOPE_VERSION: u8 = 1;
+/// The name of the (only) key derivation function currently supported.
+const KDF_PBKDF2_HMAC_SHA256: &str = "pbkdf2-hmac-sha256";
+/// The name of the (only) cipher currently supported.
+const CIPHER_AES_256_GCM: &str = "aes-256-gcm";
+/// The length (in bytes) of the (random) salt of the key derivation function.
+const SALT_LENGTH: usize = 16;
+/// The length (in bytes) of the (random) AES-GCM nonce.
+const NONCE_LENGTH: usize = 12;
+/// The length (in bytes) of the AES-256 key-encryption key.
+const KEY_ENCRYPTION_KEY_LENGTH: usize = 32;
+
+/// The number of PBKDF2 iterations used by [`Ed25519KeyPair::to_encrypted_json`],
+/// as recommended by OWASP (https://cheatsheetseries.owasp.org/cheatsheets/Password_Storage_Cheat_Sheet.html#pbkdf2).
+pub const DEFAULT_KDF_ITERATIONS: u32 = 600_000;
+/// The minimum number of PBKDF2 iterations accepted (on both export and import).
+pub const MIN_KDF_ITERATIONS: u32 = 10_000;
+/// The maximum number of PBKDF2 iterations accepted (on both export and import), thus bounding the cost of an import.
+pub const MAX_KDF_ITERATIONS: u32 = 10_000_000;
+
+/// The JSON envelope of an encrypted key pair (see [module documentation](self)).
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct EncryptedKeyEnvelope {
+    version: u8,
+    kdf: String,
+    iterations: u32,
+    salt: String,
+    cipher: String,
+    nonce: String,
+    public_key_multibase: String,
+    ciphertext: String,
+}
+
+/// Derives the AES-256 key-encryption key from the supplied `password`, `salt` and number of `iterations`.
+fn derive_key_encryption_key(
+    password: &str,
+    salt: &[u8],
+    iterations: u32,
+) -> Result<Aes256Gcm, DidSidekicksError> {
+    if !(MIN_KDF_ITERATIONS..=MAX_KDF_ITERATIONS).contains(&iterations) {
+        return Err(DidSidekicksError::DeserializationFailed(format!(
+            "Invalid number of KDF iterations {iterations} ({MIN_KDF_ITERATIONS} to {MAX_KDF_ITERATIONS} expected)"
+        )));
+    }
+    let mut key_encryption_key: [u8; KEY_ENCRYPTION_KEY_LENGTH] = [0; KEY_ENCRYPTION_KEY_LENGTH];
+    pbkdf2::pbkdf2_hmac::<Sha256>(
+        password.as_bytes(),
+        salt,
+        iterations,
+        &mut key_encryption_key,
+    );
+    Aes256Gcm::new_from_slice(&key_encryption_key)
+        .map_err(|err| DidSidekicksError::InternalError(err.to_string()))
+}
+
+/// Decodes the supplied (base64url-encoded) envelope `member` featuring the supplied `name`.
+fn decode_member(member: &str, name: &str) -> Result<Vec<u8>, DidSidekicksError> {
+    URL_SAFE_NO_PAD.decode(member).map_err(|err| {
+        DidSidekicksError::DeserializationFailed(format!(
+            "Malformed '{name}' of encrypted key: {err}"
+        ))
+    })
+}
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
+impl Ed25519KeyPair {
+    /// Exports the key pair encrypted by the supplied `password` (see [module documentation](crate::key_encryption)),
+    /// whereas [`DEFAULT_KDF_ITERATIONS`] are used to derive the key-encryption key.
+    ///
+    /// An empty `password` or a non-extractable (platform) key is denoted by [`DidSidekicksError::SerializationFailed`].
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn to_encrypted_json(&self, password: &str) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || self.to_encrypted_json_with_iterations(password, DEFAULT_KDF_ITERATIONS),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// The same as [`Ed25519KeyPair::to_encrypted_json`], while the supplied number of `iterations` is used
+    /// to derive the key-encryption key (see [`MIN_KDF_ITERATIONS`] and [`MAX_KDF_ITERATIONS`]).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn to_encrypted_json_with_iterations(
+        &self,
+        password: &str,
+        iterations: u32,
+    ) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || {
+                if password.is_empty() {
+                    return Err(DidSidekicksError::SerializationFailed(
+                        "A key must not be encrypted by an empty password".to_owned(),
+                    ));
+                }
+                let secret_key = self.signing_key.to_bytes()?;
+                let public_key_multibase = self.verifying_key.to_multibase();
+
+                let mut salt: [u8; SALT_LENGTH] = [0; SALT_LENGTH];
+                fill_random_bytes(&mut salt);
+                let mut nonce: [u8; NONCE_LENGTH] = [0; NONCE_LENGTH];
+                fill_random_bytes(&mut nonce);
+
+                let ciphertext = derive_key_encryption_key(password, &salt, iterations)?
+                    .encrypt(
+                        Nonce::from_slice(&nonce),
+                        Payload {
+                            msg: &secret_key,
+                            aad: public_key_multibase.as_bytes(),
+                        },
+                    )
+                    .map_err(|err| DidSidekicksError::SerializationFailed(err.to_string()))?;
+
+                emit_json(
+                    &EncryptedKeyEnvelope {
+                        version: ENVELOPE_VERSION,
+                        kdf: KDF_PBKDF2_HMAC_SHA256.to_owned(),
+                        iterations,
+                        salt: URL_SAFE_NO_PAD.encode(salt),
+                        cipher: CIPHER_AES_256_GCM.to_owned(),
+                        nonce: URL_SAFE_NO_PAD.encode(nonce),
+                        public_key_multibase,
+                        ciphertext: URL_SAFE_NO_PAD.encode(ciphertext),
+                    },
+                    EmitOptions::COMPACT,
+                )
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Imports a key pair from the supplied (JSON) envelope `json`, as exported by [`Ed25519KeyPair::to_encrypted_json`],
+    /// thus decrypting it by the supplied `password`.
+    ///
+    /// A malformed (or unsupported) envelope as well as a wrong `password` is denoted by [`DidSidekicksError::DeserializationFailed`].
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub fn from_encrypted_json(json: &str, password: &str) -> Result<Self, DidSidekicksError> {
+        catch_panic(
+            || {
+                let envelope = from_str_bounded::<EncryptedKeyEnvelope>(json)?;
+                if envelope.version != ENVELOPE_VERSION
+                    || envelope.kdf != KDF_PBKDF2_HMAC_SHA256
+                    || envelope.cipher != CIPHER_AES_256_GCM
+                {
+                    return Err(DidSidekicksError::DeserializationFailed(format!(
+                        "Unsupported encrypted key (version {}, kdf '{}', cipher '{}')",
+                        envelope.version, envelope.kdf, envelope.cipher
+                    )));
+                }
+                let nonce = decode_member(&envelope.nonce, "nonce")?;
+                if nonce.len() != NONCE_LENGTH {
+                    return Err(DidSidekicksError::DeserializationFailed(format!(
+                        "Malformed 'nonce' of encrypted key: {NONCE_LENGTH} bytes expected"
+                    )));
+                }
+
+                let secret_key = derive_key_encryption_key(
+                    password,
+                    &decode_member(&envelope.salt, "salt")?,
+                    envelope.iterations,
+                )?
+                .decrypt(
+                    Nonce::from_slice(&nonce),
+                    Payload {
+                        msg: &decode_member(&envelope.ciphertext, "ciphertext")?,
+                        aad: envelope.public_key_multibase.as_bytes(),
+                    },
+                )
+                .ok()
+                .ok_or_else(|| {
+                    DidSidekicksError::DeserializationFailed(
+                        "Could not decrypt the key, as either the password is wrong or the encrypted key is corrupted".to_owned(),
+                    )
+                })?;
+                let seed: [u8; SECRET_KEY_LENGTH] =
+                    secret_key.try_into().map_err(|decrypted: Vec<u8>| {
+                        DidSidekicksError::DeserializationFailed(format!(
+                            "Malformed encrypted key: {SECRET_KEY_LENGTH} bytes expected, {} found",
+                            decrypted.len()
+                        ))
+                    })?;
+
+                // as authenticated, the public key must match (unless the envelope was crafted that way)
+                let key_pair = Self::from_seed(&seed);
+                if key_pair.verifying_key.to_multibase() != envelope.public_key_multibase {
+                    return Err(DidSidekicksError::KeyMismatch {
+                        expected: envelope.public_key_multibase,
+                        actual: key_pair.verifying_key.to_multibase(),
+                    });
+                }
+                Ok(key_pair)
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+    use serde_json::{json, Value};
+
+    const PASSWORD: &str = "correct horse battery staple";
+
+    // As generated by Python (pyca/cryptography), i.e. PBKDF2-HMAC-SHA256 (10000 iterations) and AES-256-GCM,
+    // encrypting the secret key 0x00..0x1f
+    const ENCRYPTED_KEY: &str = r#"{"version":1,"kdf":"pbkdf2-hmac-sha256","iterations":10000,"salt":"ZGVmZ2hpamtsbW5vcHFycw","cipher":"aes-256-gcm","nonce":"yMnKy8zNzs_Q0dLT","publicKeyMultibase":"z6MkehRgf7yJbgaGfYsdoAsKdBPE3dj2CYhowQdcjqSJgvVd","ciphertext":"aWYtm9T6oZ0CQtjZVn-wA_xN6HJrBjbRUo5xB7ttxX3UHZpbrmc2kABB-IOcSOdM"}"#;
+
+    /// Delivers the supplied `json` envelope featuring the supplied `member` replaced by the supplied `value`.
+    #[expect(clippy::indexing_slicing, reason = "panic-safe indexing")]
+    fn tampered(json: &str, member: &str, value: Value) -> String {
+        let mut envelope: Value = serde_json::from_str(json).unwrap();
+        envelope[member] = value;
+        envelope.to_string()
+    }
+
+    #[rstest]
+    fn test_encrypted_key_roundtrip() {
+        let key_pair = Ed25519KeyPair::generate();
+        let json = key_pair
+            .to_encrypted_json_with_iterations(PASSWORD, MIN_KDF_ITERATIONS)
+            .unwrap();
+        assert!(!json.contains(&key_pair.signing_key.to_multibase().unwrap()));
+
+        let imported = Ed25519KeyPair::from_encrypted_json(&json, PASSWORD).unwrap();
+        assert_eq!(imported, key_pair);
+
+        // a fresh salt and nonce each time
+        assert_ne!(
+            key_pair
+                .to_encrypted_json_with_iterations(PASSWORD, MIN_KDF_ITERATIONS)
+                .unwrap(),
+            json
+        );
+    }
+
+    #[rstest]
+    fn test_encrypted_key_interop() {
+        let key_pair = Ed25519KeyPair::from_encrypted_json(ENCRYPTED_KEY, PASSWORD).unwrap();
+        let expected: [u8; SECRET_KEY_LENGTH] =
+            core::array::from_fn(|index| index.try_into().unwrap());
+        assert_eq!(key_pair.signing_key.to_bytes().unwrap(), expected);
+        assert_eq!(
+            key_pair.verifying_key.to_multibase(),
+            "z6MkehRgf7yJbgaGfYsdoAsKdBPE3dj2CYhowQdcjqSJgvVd"
+        );
+    }
+
+    #[rstest]
+    #[case(ENCRYPTED_KEY.to_owned(), "wrong password", "password is wrong")]
+    #[case(tampered(ENCRYPTED_KEY, "ciphertext", json!("aWYtm9T6oZ0CQtjZVn-wA_xN6HJrBjbRUo5xB7ttxX3UHZpbrmc2kABB-IOcSOdN")), PASSWORD, "password is wrong")]
+    #[case(tampered(ENCRYPTED_KEY, "publicKeyMultibase", json!("z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2")), PASSWORD, "password is wrong")]
+    #[case(tampered(ENCRYPTED_KEY, "version", json!(2)), PASSWORD, "Unsupported encrypted key")]
+    #[case(tampered(ENCRYPTED_KEY, "kdf", json!("argon2id")), PASSWORD, "Unsupported encrypted key")]
+    #[case(tampered(ENCRYPTED_KEY, "iterations", json!(1)), PASSWORD, "Invalid number of KDF iterations")]
+    #[case(tampered(ENCRYPTED_KEY, "nonce", json!("yMnK")), PASSWORD, "Malformed 'nonce'")]
+    #[case(tampered(ENCRYPTED_KEY, "salt", json!("not base64!")), PASSWORD, "Malformed 'salt'")]
+    #[case(tampered(ENCRYPTED_KEY, "extra", json!(true)), PASSWORD, "unknown field")]
+    #[case("{}".to_owned(), PASSWORD, "missing field")]
+    fn test_invalid_encrypted_key(
+        #[case] json: String,
+        #[case] password: &str,
+        #[case] error_contains: &str,
+    ) {
+        assert_error(
+            Ed25519KeyPair::from_encrypted_json(&json, password),
+            DidSidekicksErrorKind::DeserializationFailed,
+            error_contains,
+        );
+    }
+
+    #[rstest]
+    #[case("", MIN_KDF_ITERATIONS, DidSidekicksErrorKind::SerializationFailed)]
+    #[case(PASSWORD, 1, DidSidekicksErrorKind::DeserializationFailed)]
+    #[case(PASSWORD, u32::MAX, DidSidekicksErrorKind::DeserializationFailed)]
+    fn test_invalid_encrypted_key_export(
+        #[case] password: &str,
+        #[case] iterations: u32,
+        #[case] error_kind: DidSidekicksErrorKind,
+    ) {
+        assert_error(
+            Ed25519KeyPair::generate().to_encrypted_json_with_iterations(password, iterations),
+            error_kind,
+            "",
+        );
+    }
+}
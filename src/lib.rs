@@ -4,33 +4,232 @@
 //!
 //! - General util structs reused by other libraries of swiyu-admin-ch
 //!
+//! The crate is split into the following cargo features (all enabled by default),
+//! so consumers may opt out of anything they do not require (e.g. to shrink the binary size):
+//!
+//! - `jsonschema-validation`: JSON schema validation of DID log entries
+//! - `ed25519`: Ed25519 keys and signatures, raw JWS sign/verify helpers, SLIP-0010 key derivation
+//! - `bip39`: BIP-0039 mnemonic (seed phrase) backup of Ed25519 keys (implies `ed25519`)
+//! - `key-encryption`: password-protected export/import of Ed25519 key pairs (implies `ed25519`)
+//! - `vc-data-integrity`: Data Integrity proofs (implies `ed25519`)
+//! - `resolvers`: the `DidResolver` trait, the accompanying conformance test-kit, OpenID4VP key binding helpers and resolution cache stores
+//! - `uniffi`: UniFFI bindings organized into namespace objects (implies `jsonschema-validation`, `ed25519`, `bip39`, `key-encryption` and `vc-data-integrity`)
+//! - `key-agreement`: Ed25519 → X25519 conversion for `keyAgreement` (implies `ed25519`, not enabled by default)
+//! - `didcomm`: DIDComm v2 signed/anoncrypt message envelope helpers (implies `ed25519`, not enabled by default)
+//! - `deterministic-rng`: a deterministic (seeded ChaCha20) RNG for reproducible fixtures (implies `ed25519`, not enabled by default)
+//! - `sqlite-cache`: a SQLite-backed resolution cache store (implies `resolvers`, not enabled by default)
+//! - `capi`: a minimal (stable) `extern "C"` interface in parallel to UniFFI bindings (implies `jsonschema-validation` and `vc-data-integrity`, not enabled by default)
+//!
 
 extern crate core;
 
+pub mod bounded_json;
+pub mod cache;
+#[cfg(feature = "capi")]
+pub mod capi;
+pub mod checked_arithmetic;
+#[cfg(all(feature = "jsonschema-validation", feature = "vc-data-integrity"))]
+pub mod compromise_response;
+#[cfg(feature = "jsonschema-validation")]
 pub mod custom_jsonschema_keywords;
+#[cfg_attr(feature = "vc-data-integrity", expect(clippy::pub_use, reason = ".."))]
+pub mod did_controllers;
 pub mod did_doc;
+pub mod did_doc_metrics;
+pub mod did_doc_patch;
+pub mod did_import;
+#[cfg(feature = "jsonschema-validation")]
 pub mod did_jsonschema;
+pub mod did_jwks;
+#[cfg(all(feature = "jsonschema-validation", feature = "vc-data-integrity"))]
+pub mod did_log;
+#[cfg(feature = "jsonschema-validation")]
+mod did_log_entry_example;
+pub mod did_log_merkle;
+#[cfg(all(feature = "jsonschema-validation", feature = "vc-data-integrity"))]
+pub mod did_log_resolution;
+#[cfg(feature = "jsonschema-validation")]
+pub mod did_log_verifier;
 pub mod did_method_parameters;
+#[cfg(feature = "resolvers")]
 pub mod did_resolver;
+pub mod did_webvh;
+#[cfg(feature = "vc-data-integrity")]
+pub mod did_witness;
+#[cfg(feature = "didcomm")]
+pub mod didcomm;
+#[cfg(feature = "ed25519")]
 pub mod ed25519;
+pub mod error_localization;
 pub mod errors;
+pub mod hash_diagnostics;
+#[cfg(feature = "vc-data-integrity")]
+pub mod issuance;
+pub mod jcs_canonical;
 pub mod jcs_sha256_hasher;
+pub mod json_emission;
+pub mod json_parsing;
+#[cfg(feature = "ed25519")]
+pub mod jws;
+#[cfg(feature = "key-agreement")]
+pub mod key_agreement;
+pub mod key_attestation;
+#[cfg(feature = "vc-data-integrity")]
+pub mod key_ceremony;
+#[cfg(feature = "ed25519")]
+pub mod key_derivation;
+#[cfg(feature = "key-encryption")]
+pub mod key_encryption;
+#[cfg(feature = "ed25519")]
+pub mod key_interop;
+pub mod lifecycle;
+pub mod metrics;
+#[cfg(feature = "bip39")]
+pub mod mnemonic;
 pub mod multibase;
+pub mod multihash;
+#[cfg(feature = "resolvers")]
+pub mod openid4vp;
+mod panic_boundary;
+pub mod problem_details;
+pub mod redaction;
+#[cfg(feature = "resolvers")]
+pub mod resolution_cache;
+#[cfg(feature = "resolvers")]
+pub mod resolver_conformance;
+pub mod resource_digest;
+#[cfg(feature = "ed25519")]
+pub mod rng;
+pub mod trust_registry;
+#[cfg(feature = "uniffi")]
+pub mod uniffi_namespaces;
+#[cfg(feature = "jsonschema-validation")]
+pub mod validator_service;
+#[cfg(feature = "vc-data-integrity")]
 pub mod vc_data_integrity;
+#[cfg(feature = "ed25519")]
+pub mod vectors;
+#[cfg(all(feature = "jsonschema-validation", feature = "vc-data-integrity"))]
+pub mod verification_bundle;
+#[cfg(feature = "jsonschema-validation")]
+pub mod verification_policy;
+pub mod version_id;
 
 // CAUTION All structs required by UniFFI bindings generator (declared in UDL) MUST also be "used" here
 #[allow(unused_imports, reason = "required by the UniFFI compiler!")]
 #[expect(clippy::allow_attributes, reason = "..")]
+use bounded_json::*;
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use cache::*;
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use did_controllers::*;
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
 use did_doc::*;
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use did_doc_metrics::*;
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use did_doc_patch::*;
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use metrics::*;
 //use ed25519::*;
+#[cfg(feature = "ed25519")]
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use ed25519::{
+    Ed25519KeyPair, Ed25519SigningKey, Ed25519VerificationMode, Ed25519VerifyingKey,
+    MultiBaseConverter, VerificationOptions,
+};
+#[cfg(feature = "jsonschema-validation")]
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
 use did_jsonschema::*;
+#[cfg(all(feature = "jsonschema-validation", feature = "vc-data-integrity"))]
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use did_log::*;
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use did_log_merkle::*;
+#[cfg(all(feature = "jsonschema-validation", feature = "vc-data-integrity"))]
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use did_log_resolution::*;
+#[cfg(feature = "jsonschema-validation")]
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use did_log_verifier::*;
+#[cfg(feature = "jsonschema-validation")]
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use validator_service::*;
+#[cfg(feature = "jsonschema-validation")]
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use verification_policy::*;
 #[allow(unused_imports, reason = "required by the UniFFI compiler!")]
 #[expect(clippy::allow_attributes, reason = "..")]
 use did_method_parameters::*;
 #[allow(unused_imports, reason = "required by the UniFFI compiler!")]
 #[expect(clippy::allow_attributes, reason = "..")]
+use error_localization::*;
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use hash_diagnostics::*;
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use json_emission::*;
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use json_parsing::*;
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use key_attestation::*;
+#[cfg(feature = "ed25519")]
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use key_derivation::{did_key_derivation_path, Ed25519KeyDerivation, KeyPurpose};
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use lifecycle::*;
+#[cfg(feature = "bip39")]
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use mnemonic::generate_mnemonic;
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use multibase::{MultibaseAlgorithm, MultibaseCodec};
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use multihash::MultihashAlgorithm;
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use resource_digest::*;
+#[cfg(feature = "ed25519")]
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use rng::*;
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use trust_registry::*;
+#[cfg(feature = "uniffi")]
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use uniffi_namespaces::*;
+#[cfg(feature = "ed25519")]
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
+use vectors::*;
+#[allow(unused_imports, reason = "required by the UniFFI compiler!")]
+#[expect(clippy::allow_attributes, reason = "..")]
 use errors::*;
 
+#[cfg(feature = "uniffi")]
 uniffi::include_scaffolding!("did_sidekicks");
 
 #[cfg(test)]
@@ -38,8 +237,10 @@ uniffi::include_scaffolding!("did_sidekicks");
     clippy::unwrap_used,
     reason = "unwrap calls are panic-safe as long as test case setup is correct"
 )]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
 mod test {
     use crate::did_doc;
+    use crate::did_method_parameters;
     use crate::errors::*;
     use rand::distributions::Alphanumeric;
     use rand::Rng as _;
@@ -263,4 +464,129 @@ mod test {
             );
         })
     }
+
+    #[rstest]
+    #[case("did:example:123#auth-key-01", Some("auth-key-01"), None)]
+    #[case("did:example:123", Some("auth-key-01"), Some("did:example:123#auth-key-01"))]
+    #[case("did:example:123#", None, None)]
+    #[case("#auth-key-01", Some("auth-key-01"), Some("did:example:123#auth-key-01"))]
+    #[case("did:example:456#auth-key-01", None, Some("did:example:123#auth-key-01"))]
+    #[case("did:example:123#key-1", Some("auth-key-01"), Some("did:example:123#auth-key-01"))]
+    fn test_check_verification_method_ids(
+        #[case] id: &str,
+        #[case] kid: Option<&str>,
+        #[case] expected_suggestion: Option<&str>,
+    ) {
+        let did_doc = did_doc::DidDoc::from_json(
+            &json!({
+                "@context": [],
+                "id": "did:example:123",
+                "verificationMethod": [{
+                    "id": id,
+                    "type": "JsonWebKey2020",
+                    "publicKeyJwk": {
+                        "kty": "EC",
+                        "crv": "P-256",
+                        "kid": kid,
+                        "x": "3-xR-ApvKYCKtXxjvypxIb4tHJSUTHCl0uUYVAvP6sE",
+                        "y": "jkQdXwStFmrJjHuWw8PE_AG43c4OQwd6-Rkr4sPiC7Y"
+                    }
+                }],
+                // the very same verification method referenced again (issues are deduplicated)
+                "assertionMethod": [{
+                    "id": id,
+                    "type": "JsonWebKey2020",
+                    "publicKeyJwk": {
+                        "kty": "EC",
+                        "crv": "P-256",
+                        "kid": kid,
+                        "x": "3-xR-ApvKYCKtXxjvypxIb4tHJSUTHCl0uUYVAvP6sE",
+                        "y": "jkQdXwStFmrJjHuWw8PE_AG43c4OQwd6-Rkr4sPiC7Y"
+                    }
+                }]
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let issues = did_doc.check_verification_method_ids();
+        if id == "did:example:123#auth-key-01" {
+            assert!(issues.is_empty());
+            did_doc.validate_verification_method_ids().unwrap();
+            return;
+        }
+
+        assert_eq!(issues.len(), 1);
+        let issue = issues.first().unwrap();
+        assert_eq!(issue.id, id);
+        assert_eq!(issue.suggestion.as_deref(), expected_suggestion);
+        assert_error(
+            did_doc.validate_verification_method_ids(),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            "Malformed verification method id(s)",
+        );
+    }
+
+    #[rstest]
+    fn test_get_by_pointer() {
+        let did_doc_json = json!({
+            "@context": ["https://www.w3.org/ns/did/v1"],
+            "id": "did:example:123",
+            "verificationMethod": [{
+                "id": "did:example:123#auth-key-01",
+                "type": "JsonWebKey2020",
+                "publicKeyJwk": {
+                    "kty": "EC",
+                    "crv": "P-256",
+                    "kid": "auth-key-01",
+                    "x": "3-xR-ApvKYCKtXxjvypxIb4tHJSUTHCl0uUYVAvP6sE",
+                    "y": "jkQdXwStFmrJjHuWw8PE_AG43c4OQwd6-Rkr4sPiC7Y"
+                }
+            }]
+        });
+        let did_doc = did_doc::DidDoc::from_json(&did_doc_json.to_string()).unwrap();
+
+        let kid = did_doc
+            .get_by_pointer("/verificationMethod/0/publicKeyJwk/kid".to_owned())
+            .unwrap();
+        assert_eq!(kid.get_name(), "/verificationMethod/0/publicKeyJwk/kid");
+        assert_eq!(kid.get_string_value().unwrap(), "auth-key-01");
+        assert!(did_doc
+            .get_by_pointer("/@context".to_owned())
+            .unwrap()
+            .is_string_array());
+        assert!(did_doc.get_by_pointer("".to_owned()).unwrap().is_object());
+        assert!(did_doc.get_by_pointer("/nonExisting".to_owned()).is_none());
+        assert!(did_doc.get_by_pointer("invalid-pointer".to_owned()).is_none());
+
+        let id = did_method_parameters::get_by_json_pointer(did_doc_json.to_string(), "/id".to_owned())
+            .unwrap();
+        assert_eq!(id.get_string_value().unwrap(), "did:example:123");
+        assert!(
+            did_method_parameters::get_by_json_pointer("{".to_owned(), "/id".to_owned()).is_none()
+        );
+    }
+
+    #[rstest]
+    #[case(json!(1), json!(1.0))]
+    #[case(json!({"a": 1, "b": [true, "x"]}), json!({"b": [true, "x"], "a": 1.0}))]
+    #[case(json!("\u{e9}"), json!("\u{e9}"))]
+    fn test_did_method_parameter_equality(#[case] value: Value, #[case] other: Value) {
+        use did_method_parameters::DidMethodParameter;
+        use std::collections::HashSet;
+
+        let param = DidMethodParameter::from_json_pointer(&json!({"p": value}), "/p").unwrap();
+        let other_param = DidMethodParameter::from_json_pointer(&json!({"p": other}), "/p").unwrap();
+        assert_eq!(param, other_param);
+        assert_eq!(
+            HashSet::from([param.to_owned(), other_param.to_owned()]).len(),
+            1
+        );
+        assert_eq!(param.normalized().get_json_text(), other_param.normalized().get_json_text());
+        assert_eq!(*param.normalized(), param);
+
+        // differently named
+        let renamed = DidMethodParameter::from_json_pointer(&json!({"q": value}), "/q").unwrap();
+        assert_ne!(param, renamed);
+    }
 }
@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: MIT
+
+//! An explicit (idempotent and thread-safe) library lifecycle, i.e. [`init_library`] and [`shutdown_library`].
+//!
+//! All global state of this crate (caches, deserialization limits, metrics sink, error translator and source of randomness)
+//! is configured in one go by [`init_library`] and reset to its defaults by [`shutdown_library`], so it behaves
+//! the very same way regardless of bindings (UniFFI, C interface or Rust) and of the order of any prior call.
+//!
+//! Concurrent calls are serialized, hence a configuration is never applied partially.
+//! Each of the global setters (e.g. [`crate::cache::set_caching_enabled`]) remains available for fine-tuning afterwards.
+
+use crate::bounded_json::{set_deserialization_limits, DeserializationLimits};
+use crate::cache::{
+    flush_caches, set_caching_enabled, set_signature_cache_capacity, MAX_CACHE_ENTRIES,
+};
+use crate::error_localization::clear_error_translator;
+use crate::errors::DidSidekicksError;
+use crate::metrics::clear_metrics_sink;
+use crate::panic_boundary::catch_panic;
+#[cfg(feature = "ed25519")]
+use crate::rng::reset_rng_source;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// The configuration currently applied by [`init_library`], if any.
+static LIBRARY_CONFIG: Mutex<Option<LibraryConfig>> = Mutex::new(None);
+
+/// The configuration of all global state of this crate, as applied by [`init_library`].
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct LibraryConfig {
+    /// Whether the opt-in global cache layer is enabled (see [`crate::cache::set_caching_enabled`]), `false` by default.
+    pub caching_enabled: bool,
+    /// The maximum number of verified signatures held by the (global) cache, [`MAX_CACHE_ENTRIES`] by default.
+    pub signature_cache_capacity: u64,
+    /// The limits enforced while deserializing untrusted JSON input, [`DeserializationLimits::DEFAULT`] by default.
+    pub deserialization_limits: DeserializationLimits,
+}
+
+impl Default for LibraryConfig {
+    /// The default configuration, i.e. the very same state the library features prior to [`init_library`].
+    #[inline]
+    fn default() -> Self {
+        Self {
+            caching_enabled: false,
+            signature_cache_capacity: u64::try_from(MAX_CACHE_ENTRIES).unwrap_or(u64::MAX),
+            deserialization_limits: DeserializationLimits::DEFAULT,
+        }
+    }
+}
+
+/// Initializes all global state of this crate according to the supplied `config`.
+///
+/// The call is idempotent, i.e. initializing an already initialized library using the very same `config` is a no-op.
+/// Returns whether the `config` was applied (i.e. `false` in case of a no-op).
+///
+/// An already initialized library using a different configuration is denoted by [`DidSidekicksError::InternalError`],
+/// as it must be shut down (see [`shutdown_library`]) prior to reinitialization.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn init_library(config: LibraryConfig) -> Result<bool, DidSidekicksError> {
+    catch_panic(
+        || {
+            // CAUTION The lock is held while applying the config, so concurrent calls never interleave
+            let mut current = lock();
+            if !requires_init(current.as_ref(), &config)? {
+                return Ok(false);
+            }
+            apply(&config);
+            *current = Some(config);
+            drop(current);
+            Ok(true)
+        },
+        DidSidekicksError::InternalError,
+    )
+}
+
+/// Resets all global state of this crate to its defaults, i.e. flushes all caches and unregisters any sink or translator.
+///
+/// The call is idempotent. Returns whether the library was initialized (see [`init_library`]) prior to the call.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn shutdown_library() -> bool {
+    let mut current = lock();
+    flush_caches();
+    apply(&LibraryConfig::default());
+    clear_metrics_sink();
+    clear_error_translator();
+    #[cfg(feature = "ed25519")]
+    reset_rng_source();
+    current.take().is_some()
+}
+
+/// Whether the library is currently initialized (see [`init_library`]).
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn is_library_initialized() -> bool {
+    lock().is_some()
+}
+
+/// Delivers the configuration currently applied by [`init_library`], if any.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn get_library_config() -> Option<LibraryConfig> {
+    *lock()
+}
+
+fn lock() -> MutexGuard<'static, Option<LibraryConfig>> {
+    // the config is replaced as a whole, so a poisoned lock still holds a consistent one
+    LIBRARY_CONFIG
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Whether the `requested` config is to be applied, given the `current` one (if any).
+#[cfg_attr(not(test), expect(clippy::single_call_fn, reason = ".."))]
+fn requires_init(
+    current: Option<&LibraryConfig>,
+    requested: &LibraryConfig,
+) -> Result<bool, DidSidekicksError> {
+    match current {
+        None => Ok(true),
+        Some(config) if config == requested => Ok(false),
+        Some(config) => Err(DidSidekicksError::InternalError(format!(
+            "The library is already initialized using a different configuration ({config:?}), hence it must be shut down first"
+        ))),
+    }
+}
+
+fn apply(config: &LibraryConfig) {
+    set_caching_enabled(config.caching_enabled);
+    set_signature_cache_capacity(config.signature_cache_capacity);
+    set_deserialization_limits(config.deserialization_limits);
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+
+    // CAUTION The global state itself is deliberately left intact here, as it is shared by all (concurrently running) test cases
+
+    #[rstest]
+    fn test_requires_init() {
+        let config = LibraryConfig {
+            caching_enabled: true,
+            ..LibraryConfig::default()
+        };
+        assert!(requires_init(None, &config).unwrap());
+        assert!(!requires_init(Some(&config), &config).unwrap()); // idempotent
+        assert_error(
+            requires_init(Some(&LibraryConfig::default()), &config),
+            DidSidekicksErrorKind::InternalError,
+            "already initialized using a different configuration",
+        );
+    }
+
+    #[rstest]
+    fn test_default_library_config() {
+        assert_eq!(
+            LibraryConfig::default().signature_cache_capacity,
+            u64::try_from(MAX_CACHE_ENTRIES).unwrap()
+        );
+        assert!(!LibraryConfig::default().caching_enabled);
+    }
+}
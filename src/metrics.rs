@@ -0,0 +1,224 @@
+// SPDX-License-Identifier: MIT
+
+//! Structured metrics hooks.
+//!
+//! Once a [`MetricsSink`] is registered (see [`set_metrics_sink`]), it receives counters and histograms for:
+//! - verifications performed (see [`VERIFICATIONS_TOTAL`]),
+//! - verification failures by error kind (see [`VERIFICATION_FAILURES_TOTAL`]),
+//! - verification durations (see [`VERIFICATION_DURATION_SECONDS`]) and
+//! - hash operations (see [`HASH_OPERATIONS_TOTAL`]).
+//!
+//! Hence, host applications may pipe them into Prometheus, StatsD etc. without patching this crate.
+
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
+#[cfg(any(feature = "jsonschema-validation", feature = "vc-data-integrity"))]
+use std::time::Instant;
+
+/// Counter: the total number of verifications performed, labeled by [`LABEL_OPERATION`].
+pub const VERIFICATIONS_TOTAL: &str = "did_sidekicks_verifications_total";
+/// Counter: the total number of failed verifications, labeled by [`LABEL_OPERATION`] and [`LABEL_ERROR_KIND`].
+pub const VERIFICATION_FAILURES_TOTAL: &str = "did_sidekicks_verification_failures_total";
+/// Histogram: the verification duration (in seconds), labeled by [`LABEL_OPERATION`].
+pub const VERIFICATION_DURATION_SECONDS: &str = "did_sidekicks_verification_duration_seconds";
+/// Counter: the total number of (SHA2-256) hash operations performed, labeled by [`LABEL_OPERATION`].
+pub const HASH_OPERATIONS_TOTAL: &str = "did_sidekicks_hash_operations_total";
+
+/// The label denoting the operation a metric refers to (e.g. `data_integrity_proof`).
+pub const LABEL_OPERATION: &str = "operation";
+/// The label denoting the kind of error a failure metric refers to (e.g. `InvalidIntegrityProof`).
+pub const LABEL_ERROR_KIND: &str = "error_kind";
+
+static METRICS_SINK: RwLock<Option<Arc<dyn MetricsSink>>> = RwLock::new(None);
+
+/// A receiver of metrics (counters and histograms) emitted by this crate.
+///
+/// Implementations are expected to be cheap, as they are called synchronously and in-line.
+///
+/// A UniFFI-compliant trait (that may also be implemented by foreign code).
+pub trait MetricsSink: Send + Sync {
+    /// Increments the counter denoted by `name` (and `labels`) by the supplied `value`.
+    fn increment_counter(&self, name: String, labels: HashMap<String, String>, value: u64);
+
+    /// Records the supplied `value` into the histogram denoted by `name` (and `labels`).
+    fn record_histogram(&self, name: String, labels: HashMap<String, String>, value: f64);
+}
+
+/// Registers the supplied `sink` globally, thus replacing any previously registered one.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn set_metrics_sink(sink: Arc<dyn MetricsSink>) {
+    *METRICS_SINK.write().unwrap_or_else(PoisonError::into_inner) = Some(sink);
+}
+
+/// Unregisters any previously registered [`MetricsSink`]. Metrics are no longer emitted afterwards.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn clear_metrics_sink() {
+    *METRICS_SINK.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+#[cfg_attr(
+    not(any(feature = "jsonschema-validation", feature = "vc-data-integrity")),
+    expect(clippy::single_call_fn, reason = "..")
+)]
+fn current_sink() -> Option<Arc<dyn MetricsSink>> {
+    METRICS_SINK
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+#[cfg_attr(
+    not(any(feature = "jsonschema-validation", feature = "vc-data-integrity")),
+    expect(clippy::single_call_fn, reason = "..")
+)]
+fn operation_labels(operation: &str) -> HashMap<String, String> {
+    HashMap::from([(LABEL_OPERATION.to_owned(), operation.to_owned())])
+}
+
+/// Emits the [`HASH_OPERATIONS_TOTAL`] counter, if a [`MetricsSink`] is registered.
+pub(crate) fn count_hash_operation(operation: &str) {
+    if let Some(sink) = current_sink() {
+        sink.increment_counter(
+            HASH_OPERATIONS_TOTAL.to_owned(),
+            operation_labels(operation),
+            1,
+        );
+    }
+}
+
+/// Runs the supplied `verification` and emits [`VERIFICATIONS_TOTAL`], [`VERIFICATION_DURATION_SECONDS`] and,
+/// in case of failure, [`VERIFICATION_FAILURES_TOTAL`] (labeled by `error_kind`), if a [`MetricsSink`] is registered.
+#[cfg(any(feature = "jsonschema-validation", feature = "vc-data-integrity"))]
+#[cfg_attr(
+    not(any(test, all(feature = "jsonschema-validation", feature = "vc-data-integrity"))),
+    expect(clippy::single_call_fn, reason = "..")
+)]
+pub(crate) fn observe_verification<T, E, F, K>(
+    operation: &str,
+    error_kind: K,
+    verification: F,
+) -> Result<T, E>
+where
+    F: FnOnce() -> Result<T, E>,
+    K: FnOnce(&E) -> String,
+{
+    let Some(sink) = current_sink() else {
+        return verification();
+    };
+
+    let start = Instant::now();
+    let result = verification();
+    let elapsed = start.elapsed().as_secs_f64();
+
+    let labels = operation_labels(operation);
+    sink.record_histogram(
+        VERIFICATION_DURATION_SECONDS.to_owned(),
+        labels.clone(),
+        elapsed,
+    );
+    sink.increment_counter(VERIFICATIONS_TOTAL.to_owned(), labels.clone(), 1);
+    if let Err(err) = result.as_ref() {
+        let mut failure_labels = labels;
+        failure_labels.insert(LABEL_ERROR_KIND.to_owned(), error_kind(err));
+        sink.increment_counter(VERIFICATION_FAILURES_TOTAL.to_owned(), failure_labels, 1);
+    }
+
+    result
+}
+
+#[cfg(test)]
+#[cfg(any(feature = "jsonschema-validation", feature = "vc-data-integrity"))]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use rstest::rstest;
+    use std::sync::Mutex;
+
+    type Recordings<T> = Mutex<Vec<(String, HashMap<String, String>, T)>>;
+
+    #[derive(Default)]
+    struct RecordingMetricsSink {
+        counters: Recordings<u64>,
+        histograms: Recordings<f64>,
+    }
+
+    impl MetricsSink for RecordingMetricsSink {
+        fn increment_counter(&self, name: String, labels: HashMap<String, String>, value: u64) {
+            self.counters.lock().unwrap().push((name, labels, value));
+        }
+
+        fn record_histogram(&self, name: String, labels: HashMap<String, String>, value: f64) {
+            self.histograms.lock().unwrap().push((name, labels, value));
+        }
+    }
+
+    #[rstest]
+    fn test_observe_verification() {
+        let sink = Arc::new(RecordingMetricsSink::default());
+        set_metrics_sink(Arc::<RecordingMetricsSink>::clone(&sink));
+
+        let ok = observe_verification(
+            "test_op",
+            |_: &String| String::new(),
+            || Ok::<u8, String>(1),
+        );
+        assert_eq!(ok.unwrap(), 1);
+        let failed = observe_verification(
+            "test_op",
+            |err: &String| err.to_owned(),
+            || Err::<u8, String>("TestErrorKind".to_owned()),
+        );
+        failed.unwrap_err();
+        count_hash_operation("test_op");
+
+        clear_metrics_sink();
+        // no longer recorded
+        count_hash_operation("test_op");
+
+        let counters = sink.counters.lock().unwrap().clone();
+        let count = |name: &str| {
+            counters
+                .iter()
+                .filter(|counter| {
+                    counter.0 == name
+                        && counter
+                            .1
+                            .get(LABEL_OPERATION)
+                            .is_some_and(|operation| operation == "test_op")
+                })
+                .count()
+        };
+        assert_eq!(count(VERIFICATIONS_TOTAL), 2);
+        assert_eq!(count(VERIFICATION_FAILURES_TOTAL), 1);
+        assert_eq!(count(HASH_OPERATIONS_TOTAL), 1);
+        assert!(counters.iter().any(|counter| {
+            counter.0 == VERIFICATION_FAILURES_TOTAL
+                && counter
+                    .1
+                    .get(LABEL_ERROR_KIND)
+                    .is_some_and(|kind| kind == "TestErrorKind")
+        }));
+        assert_eq!(
+            sink.histograms
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|histogram| {
+                    histogram.0 == VERIFICATION_DURATION_SECONDS
+                        && histogram
+                            .1
+                            .get(LABEL_OPERATION)
+                            .is_some_and(|operation| operation == "test_op")
+                })
+                .count(),
+            2
+        );
+    }
+}
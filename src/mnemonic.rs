@@ -0,0 +1,227 @@
+// SPDX-License-Identifier: MIT
+
+//! Mnemonic (seed phrase) backup of Ed25519 keys.
+//!
+//! As specified by BIP-0039 (https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki),
+//! so wallet-style consumers may back up and restore DID controller keys from a seed phrase (e.g. written down on paper)
+//! instead of persisting raw multibase secrets.
+//!
+//! The (512-bit) BIP-0039 seed of a mnemonic (and an optional passphrase) is used as SLIP-0010 seed (see [`crate::key_derivation`]), i.e.:
+//! - [`Ed25519KeyPair::from_mnemonic`] delivers the master key pair (i.e. derived along the path `m`),
+//! - [`Ed25519KeyDerivation::from_mnemonic`] enables derivation of any further key pair (e.g. per DID and purpose).
+//!
+//! CAUTION Only the English wordlist is supported. As the passphrase is part of the seed, any (mistyped) passphrase
+//! delivers a valid, yet entirely different key pair.
+
+use crate::ed25519::Ed25519KeyPair;
+use crate::errors::DidSidekicksError;
+use crate::key_derivation::Ed25519KeyDerivation;
+use crate::panic_boundary::catch_panic;
+use crate::rng::fill_random_bytes;
+use bip39::{Language, Mnemonic};
+use std::sync::Arc;
+
+/// The SLIP-0010 derivation path of the master key pair.
+const MASTER_KEY_PATH: &str = "m";
+
+/// Generates a new (random) mnemonic featuring the supplied `word_count` (i.e. 12, 15, 18, 21 or 24 words),
+/// relying on the globally configured [`crate::rng::RngSource`].
+///
+/// Any other `word_count` is denoted by [`DidSidekicksError::DeserializationFailed`].
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn generate_mnemonic(word_count: u8) -> Result<String, DidSidekicksError> {
+    catch_panic(
+        || {
+            // As specified by BIP-0039, each 3 words (33 bits) feature 32 bits of entropy (and a single checksum bit)
+            let entropy_length: usize = match word_count {
+                12 => 16,
+                15 => 20,
+                18 => 24,
+                21 => 28,
+                24 => 32,
+                _ => {
+                    return Err(DidSidekicksError::DeserializationFailed(format!(
+                        "Invalid mnemonic word count {word_count} (12, 15, 18, 21 or 24 expected)"
+                    )))
+                }
+            };
+            let mut entropy: Vec<u8> = vec![0; entropy_length];
+            fill_random_bytes(&mut entropy);
+            Mnemonic::from_entropy_in(Language::English, &entropy)
+                .map(|mnemonic| mnemonic.to_string())
+                .map_err(|err| DidSidekicksError::InternalError(err.to_string()))
+        },
+        DidSidekicksError::InternalError,
+    )
+}
+
+/// Delivers the (64-byte) BIP-0039 seed of the supplied mnemonic `phrase` and `passphrase` (empty, if none).
+///
+/// A malformed `phrase` (e.g. featuring an unknown word or an invalid checksum) is denoted by [`DidSidekicksError::DeserializationFailed`].
+#[inline]
+pub fn mnemonic_to_seed(phrase: &str, passphrase: &str) -> Result<[u8; 64], DidSidekicksError> {
+    Mnemonic::parse_in(Language::English, phrase)
+        .map(|mnemonic| mnemonic.to_seed(passphrase))
+        .map_err(|err| DidSidekicksError::DeserializationFailed(format!("Invalid mnemonic: {err}")))
+}
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
+impl Ed25519KeyPair {
+    /// Restores the (master) key pair from the supplied mnemonic `phrase` and `passphrase` (empty, if none),
+    /// i.e. the SLIP-0010 master key of the BIP-0039 seed (see [module documentation](crate::mnemonic)).
+    ///
+    /// A malformed `phrase` is denoted by [`DidSidekicksError::DeserializationFailed`].
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub fn from_mnemonic(phrase: &str, passphrase: &str) -> Result<Self, DidSidekicksError> {
+        catch_panic(
+            || {
+                Ed25519KeyDerivation::from_mnemonic(phrase, passphrase)?
+                    .derive(MASTER_KEY_PATH)
+                    .map(Arc::unwrap_or_clone)
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+}
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
+impl Ed25519KeyDerivation {
+    /// Derives the master key from the BIP-0039 seed of the supplied mnemonic `phrase` and `passphrase` (empty, if none).
+    ///
+    /// A malformed `phrase` is denoted by [`DidSidekicksError::DeserializationFailed`].
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub fn from_mnemonic(phrase: &str, passphrase: &str) -> Result<Self, DidSidekicksError> {
+        catch_panic(
+            || Self::from_seed(&mnemonic_to_seed(phrase, passphrase)?),
+            DidSidekicksError::InternalError,
+        )
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::key_derivation::{did_key_derivation_path, KeyPurpose};
+    use crate::test::assert_error;
+    use hex::encode as hex_encode;
+    use rstest::rstest;
+
+    #[rstest]
+    // Mnemonics (and passphrase) as specified by https://github.com/trezor/python-mnemonic/blob/master/vectors.json
+    #[case(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        "TREZOR",
+        "c55257c360c07c72029aebc1b53c05ed",
+        "8e07aa919abc1427adf010d10467dfba6f1f354b6707916dc9c059771ec13ecd"
+    )]
+    #[case(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        "",
+        "5eb00bbddcf069084889a8ab91555681",
+        "e96b1c6b8769fdb0b34fbecfdf85c33b053cecad9517e1ab88cba614335775c1"
+    )]
+    #[case(
+        "legal winner thank year wave sausage worth useful legal winner thank yellow",
+        "TREZOR",
+        "2e8905819b8723fe2c1d161860e5ee18",
+        "21fd896ed401e9961c859db3860efd45a5c953a58a559e9f83479dc72ce988ad"
+    )]
+    #[case(
+        "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+        "TREZOR",
+        "dd48c104698c30cfe2b6142103248622",
+        "1fa611179c8be83c1a7f6aaceae7cf3c22725aff655ce0874c7d1f453a83f2da"
+    )]
+    fn test_from_mnemonic(
+        #[case] phrase: &str,
+        #[case] passphrase: &str,
+        #[case] seed_prefix_hex: &str,
+        #[case] public_key_hex: &str,
+    ) {
+        let seed = mnemonic_to_seed(phrase, passphrase).unwrap();
+        assert!(hex_encode(seed).starts_with(seed_prefix_hex));
+
+        let key_pair = Ed25519KeyPair::from_mnemonic(phrase, passphrase).unwrap();
+        assert_eq!(
+            hex_encode(key_pair.get_verifying_key().to_bytes()),
+            public_key_hex
+        );
+        assert_eq!(
+            *Ed25519KeyDerivation::from_seed(&seed)
+                .unwrap()
+                .derive(MASTER_KEY_PATH)
+                .unwrap(),
+            key_pair
+        );
+    }
+
+    #[rstest]
+    #[case(12)]
+    #[case(15)]
+    #[case(18)]
+    #[case(21)]
+    #[case(24)]
+    fn test_generate_mnemonic(#[case] word_count: u8) {
+        let phrase = generate_mnemonic(word_count).unwrap();
+        assert_eq!(phrase.split(' ').count(), usize::from(word_count));
+
+        // a backup must restore the very same keys
+        let derivation = Ed25519KeyDerivation::from_mnemonic(&phrase, "").unwrap();
+        let path = did_key_derivation_path(0, KeyPurpose::Update, 0);
+        assert_eq!(
+            derivation.derive(&path).unwrap(),
+            Ed25519KeyDerivation::from_mnemonic(&phrase, "")
+                .unwrap()
+                .derive(&path)
+                .unwrap()
+        );
+        assert_ne!(
+            Ed25519KeyPair::from_mnemonic(&phrase, "").unwrap(),
+            Ed25519KeyPair::from_mnemonic(&phrase, "passphrase").unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(11)]
+    #[case(13)]
+    #[case(25)]
+    fn test_generate_mnemonic_invalid_word_count(#[case] word_count: u8) {
+        assert_error(
+            generate_mnemonic(word_count),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "Invalid mnemonic word count",
+        );
+    }
+
+    #[rstest]
+    // invalid checksum
+    #[case(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon"
+    )]
+    // unknown word
+    #[case(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon sidekick"
+    )]
+    // invalid word count
+    #[case("abandon abandon abandon about")]
+    #[case("")]
+    fn test_from_mnemonic_invalid(#[case] phrase: &str) {
+        assert_error(
+            Ed25519KeyPair::from_mnemonic(phrase, ""),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "Invalid mnemonic",
+        );
+    }
+}
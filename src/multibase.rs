@@ -1,25 +1,98 @@
 // SPDX-License-Identifier: MIT
 
 use crate::errors::DidSidekicksError;
+use crate::panic_boundary::catch_panic;
 use bs58::{decode as base58_decode, encode as base58_encode, Alphabet as Alphabet58};
 use core::cmp::PartialEq;
+use std::iter::repeat_n;
 
-/// See https://www.ietf.org/archive/id/draft-multiformats-multibase-08.html#appendix-D.1
+/// See https://www.ietf.org/archive/id/draft-multiformats-multibase-08.html#appendix-D.1.
 pub const BASE58BTC_MULTIBASE_IDENTIFIER: &str = "z";
 
-/// See https://www.ietf.org/archive/id/draft-multiformats-multibase-08.html#appendix-D.1
-#[derive(PartialEq, Eq, Debug)]
+/// See https://github.com/multiformats/multibase/blob/master/multibase.csv.
+pub const BASE36_MULTIBASE_IDENTIFIER: &str = "k";
+
+/// The uppercase variant of [`BASE36_MULTIBASE_IDENTIFIER`], accepted while decoding only.
+///
+/// See https://github.com/multiformats/multibase/blob/master/multibase.csv.
+pub const BASE36_UPPER_MULTIBASE_IDENTIFIER: &str = "K";
+
+/// See https://github.com/multiformats/multibase/blob/master/multibase.csv.
+pub const BASE16_MULTIBASE_IDENTIFIER: &str = "f";
+
+/// The uppercase variant of [`BASE16_MULTIBASE_IDENTIFIER`].
+///
+/// See https://github.com/multiformats/multibase/blob/master/multibase.csv.
+pub const BASE16_UPPER_MULTIBASE_IDENTIFIER: &str = "F";
+
+/// See https://github.com/multiformats/multibase/blob/master/multibase.csv.
+pub const BASE32_MULTIBASE_IDENTIFIER: &str = "b";
+
+/// The uppercase variant of [`BASE32_MULTIBASE_IDENTIFIER`].
+///
+/// See https://github.com/multiformats/multibase/blob/master/multibase.csv.
+pub const BASE32_UPPER_MULTIBASE_IDENTIFIER: &str = "B";
+
+/// See https://github.com/multiformats/multibase/blob/master/multibase.csv.
+pub const BASE45_MULTIBASE_IDENTIFIER: &str = "R";
+
+/// See https://www.ietf.org/archive/id/draft-msporny-base58-03.txt.
+const BASE58BTC_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+const BASE36_ALPHABET: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+const BASE16_ALPHABET: &[u8] = b"0123456789abcdef";
+
+/// The (lowercase) alphabet specified by https://www.rfc-editor.org/rfc/rfc4648#section-6.
+const BASE32_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// See https://www.rfc-editor.org/rfc/rfc9285#section-4.
+const BASE45_ALPHABET: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// See https://www.ietf.org/archive/id/draft-multiformats-multibase-08.html#appendix-D.1.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
 #[expect(
     clippy::exhaustive_enums,
     reason = "further enum variants may be added in the future"
 )]
 pub enum MultibaseAlgorithm {
-    /// Base58 bitcoin
+    /// Base58 bitcoin.
     Base58btc,
+    /// Base36 (lowercase), e.g. for case-insensitive QR code payloads.
+    Base36,
+    /// Base16 (lowercase), i.e. hexadecimal as specified by https://www.rfc-editor.org/rfc/rfc4648#section-8.
+    Base16,
+    /// Base16 (uppercase), i.e. hexadecimal as specified by https://www.rfc-editor.org/rfc/rfc4648#section-8.
+    Base16Upper,
+    /// Base32 (lowercase, no padding) as specified by https://www.rfc-editor.org/rfc/rfc4648#section-6.
+    Base32,
+    /// Base32 (uppercase, no padding) as specified by https://www.rfc-editor.org/rfc/rfc4648#section-6.
+    Base32Upper,
+    /// Base45 as specified by https://www.rfc-editor.org/rfc/rfc9285, i.e. the most efficient one for QR codes (alphanumeric mode).
+    Base45,
+}
+
+impl MultibaseAlgorithm {
+    /// Detects the algorithm by the multibase identifier (prefix) of the supplied `multibase` string.
+    ///
+    /// Returns `None` if the identifier is missing or unsupported.
+    #[inline]
+    pub fn detect(multibase: &str) -> Option<Self> {
+        match multibase.get(..1)? {
+            BASE58BTC_MULTIBASE_IDENTIFIER => Some(Self::Base58btc),
+            BASE36_MULTIBASE_IDENTIFIER | BASE36_UPPER_MULTIBASE_IDENTIFIER => Some(Self::Base36),
+            BASE32_MULTIBASE_IDENTIFIER => Some(Self::Base32),
+            BASE32_UPPER_MULTIBASE_IDENTIFIER => Some(Self::Base32Upper),
+            BASE16_MULTIBASE_IDENTIFIER => Some(Self::Base16),
+            BASE16_UPPER_MULTIBASE_IDENTIFIER => Some(Self::Base16Upper),
+            BASE45_MULTIBASE_IDENTIFIER => Some(Self::Base45),
+            _ => None,
+        }
+    }
 }
 
 /// A helper capable of encoding/decoding data in Multibase format according to
-/// https://www.ietf.org/archive/id/draft-multiformats-multibase-08.html
+/// https://www.ietf.org/archive/id/draft-multiformats-multibase-08.html.
 pub struct MultibaseEncoderDecoder {
     algorithm: MultibaseAlgorithm,
     alphabet: &'static Alphabet58,
@@ -50,6 +123,38 @@ impl MultibaseEncoderDecoder {
         format!("{BASE58BTC_MULTIBASE_IDENTIFIER}{encoded}")
     }
 
+    /// Decode into a new (owned) buffer, regardless of the length of the decoded data.
+    ///
+    /// Same as [`Self::decode_to_vec`], to which it delegates.
+    #[inline]
+    pub fn decode_base58(&self, multibase: &str) -> Result<Vec<u8>, DidSidekicksError> {
+        self.decode_to_vec(multibase)
+    }
+
+    /// Decode into a new (owned) buffer of the exact length of the decoded data,
+    /// thus sparing callers of [`Self::decode_base58_onto`] from guessing the buffer size.
+    #[inline]
+    #[expect(clippy::panic_in_result_fn, reason = "..")]
+    #[expect(clippy::panic, reason = "sanity guard")]
+    pub fn decode_to_vec(&self, multibase: &str) -> Result<Vec<u8>, DidSidekicksError> {
+        // sanity guard
+        if self.algorithm != MultibaseAlgorithm::Base58btc {
+            panic!("Unsupported multibase algorithm {:?}", self.algorithm);
+        }
+
+        let Some(to_decode) = multibase.strip_prefix(BASE58BTC_MULTIBASE_IDENTIFIER) else {
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "Invalid multibase algorithm identifier '{:?}'",
+                self.algorithm
+            )));
+        };
+
+        base58_decode(to_decode)
+            .with_alphabet(self.alphabet)
+            .into_vec()
+            .map_err(|err| DidSidekicksError::DeserializationFailed(format!("{err}")))
+    }
+
     /// Decode into the given buffer.
     ///
     /// If the buffer is resizeable it will be extended and the new data will be written to the end
@@ -57,6 +162,9 @@ impl MultibaseEncoderDecoder {
     ///
     /// If the buffer is not resizeable bytes will be written from the beginning and bytes after
     /// the final encoded byte will not be touched.
+    ///
+    /// Unless the length of the decoded data is known upfront, consider using [`Self::decode_to_vec`] instead.
+    /// In case of secret material (e.g. signing keys), use [`Self::decode_base58_secret_onto`] instead.
     #[inline]
     #[expect(clippy::panic_in_result_fn, reason = "..")]
     #[expect(clippy::panic, reason = "sanity guard")]
@@ -89,6 +197,639 @@ impl MultibaseEncoderDecoder {
             Err(err) => Err(DidSidekicksError::DeserializationFailed(format!("{err}"))),
         }
     }
+
+    /// Decode secret material (e.g. a signing key) into the given (fixed-length) buffer in a data-independent manner,
+    /// i.e. neither branches nor memory accesses depend on the encoded data, thus reducing timing side channels.
+    /// The running time depends on the lengths of the supplied `multibase` string and `result` buffer only.
+    ///
+    /// Unlike [`Self::decode_base58_onto`], the decoded data must fill the buffer exactly.
+    /// For the sake of secrecy, any malformation is denoted by the very same [`DidSidekicksError::DeserializationFailed`],
+    /// regardless of its cause (e.g. offending character or improper length), whereas the buffer is zeroed.
+    #[inline]
+    pub fn decode_base58_secret_onto(
+        &self,
+        multibase: &str,
+        result: &mut [u8],
+    ) -> Result<(), DidSidekicksError> {
+        let Some(to_decode) = multibase.strip_prefix(BASE58BTC_MULTIBASE_IDENTIFIER) else {
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "Invalid multibase algorithm identifier '{:?}'",
+                self.algorithm
+            )));
+        };
+
+        // any non-zero value denotes a malformation
+        let mut invalid: u32 = 0;
+        // each leading '1' character denotes a leading zero byte
+        let mut leading_ones: u32 = 0;
+        let mut in_leading_ones: u32 = 1;
+        // the decoded (big-endian) number, a byte per element
+        let mut decoded: Vec<u32> = vec![0; result.len()];
+        for character in to_decode.bytes() {
+            let (digit, found) = ct_base58_digit(character);
+            invalid |= found ^ 1;
+            in_leading_ones &= ct_eq(digit, 0) & found;
+            leading_ones = leading_ones.wrapping_add(in_leading_ones);
+
+            let mut carry = digit;
+            for byte in decoded.iter_mut().rev() {
+                carry = carry.wrapping_add(byte.wrapping_mul(58));
+                *byte = carry & 0xff;
+                carry = carry.wrapping_shr(8);
+            }
+            // the number does not fit the buffer
+            invalid |= carry;
+        }
+
+        // the decoded data must fill the buffer exactly, i.e. the leading zero bytes must be denoted by leading '1' characters
+        let mut leading_zeros: u32 = 0;
+        let mut in_leading_zeros: u32 = 1;
+        for byte in &decoded {
+            in_leading_zeros &= ct_eq(*byte, 0);
+            leading_zeros = leading_zeros.wrapping_add(in_leading_zeros);
+        }
+        invalid |= leading_zeros ^ leading_ones;
+
+        for (target, byte) in result.iter_mut().zip(&decoded) {
+            *target = u8::try_from(byte & 0xff).unwrap_or_default();
+        }
+        decoded.fill(0);
+
+        if invalid != 0 {
+            result.fill(0);
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "Invalid base58btc encoding of {} bytes of secret material",
+                result.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Encode bytes into a new owned base36 (lowercase) multibase string.
+    ///
+    /// Leading zero bytes are preserved (as leading `0` characters), the same way as by [`Self::encode_base58btc`].
+    #[inline]
+    pub fn encode_base36(&self, data: &[u8]) -> String {
+        format!(
+            "{BASE36_MULTIBASE_IDENTIFIER}{}",
+            encode_radix(data, BASE36_ALPHABET)
+        )
+    }
+
+    /// Encode bytes into a new owned base16 (i.e. lowercase hexadecimal) multibase string.
+    #[inline]
+    pub fn encode_base16(&self, data: &[u8]) -> String {
+        format!("{BASE16_MULTIBASE_IDENTIFIER}{}", hex::encode(data))
+    }
+
+    /// Encode bytes into a new owned base16 (i.e. uppercase hexadecimal) multibase string.
+    #[inline]
+    pub fn encode_base16_upper(&self, data: &[u8]) -> String {
+        format!(
+            "{BASE16_UPPER_MULTIBASE_IDENTIFIER}{}",
+            hex::encode_upper(data)
+        )
+    }
+
+    /// Encode bytes into a new owned base32 (https://www.rfc-editor.org/rfc/rfc4648#section-6, lowercase, no padding) multibase string.
+    #[inline]
+    pub fn encode_base32(&self, data: &[u8]) -> String {
+        format!("{BASE32_MULTIBASE_IDENTIFIER}{}", encode_base32(data))
+    }
+
+    /// Encode bytes into a new owned base32 (https://www.rfc-editor.org/rfc/rfc4648#section-6, uppercase, no padding) multibase string.
+    #[inline]
+    pub fn encode_base32_upper(&self, data: &[u8]) -> String {
+        format!(
+            "{BASE32_UPPER_MULTIBASE_IDENTIFIER}{}",
+            encode_base32(data).to_ascii_uppercase()
+        )
+    }
+
+    /// Encode bytes into a new owned base45 (https://www.rfc-editor.org/rfc/rfc9285) multibase string.
+    #[inline]
+    pub fn encode_base45(&self, data: &[u8]) -> String {
+        format!("{BASE45_MULTIBASE_IDENTIFIER}{}", encode_base45(data))
+    }
+
+    /// Encode bytes into a new owned multibase string using the supplied `algorithm`.
+    #[inline]
+    pub fn encode(&self, algorithm: MultibaseAlgorithm, data: &[u8]) -> String {
+        match algorithm {
+            MultibaseAlgorithm::Base58btc => Self::default().encode_base58btc(data),
+            MultibaseAlgorithm::Base36 => self.encode_base36(data),
+            MultibaseAlgorithm::Base16 => self.encode_base16(data),
+            MultibaseAlgorithm::Base16Upper => self.encode_base16_upper(data),
+            MultibaseAlgorithm::Base32 => self.encode_base32(data),
+            MultibaseAlgorithm::Base32Upper => self.encode_base32_upper(data),
+            MultibaseAlgorithm::Base45 => self.encode_base45(data),
+        }
+    }
+
+    /// Validates the supplied `multibase` string strictly, i.e. each character (following the multibase identifier)
+    /// must be part of the alphabet denoted by the identifier, including its case (e.g. uppercase only in case of `K`, `F` or `B`).
+    /// Thus, delivering the detected algorithm.
+    ///
+    /// The very first offending character (including an unsupported identifier) is denoted by
+    /// [`DidSidekicksError::InvalidMultibaseCharacter`] featuring its (byte) offset within the supplied `multibase` string,
+    /// whereas an empty string is denoted by [`DidSidekicksError::DeserializationFailed`].
+    #[inline]
+    pub fn validate_strict(
+        &self,
+        multibase: &str,
+    ) -> Result<MultibaseAlgorithm, DidSidekicksError> {
+        let Some(identifier) = multibase.chars().next() else {
+            return Err(DidSidekicksError::DeserializationFailed(
+                "Empty multibase string".to_owned(),
+            ));
+        };
+        let Some(algorithm) = MultibaseAlgorithm::detect(multibase) else {
+            return Err(DidSidekicksError::InvalidMultibaseCharacter {
+                character: identifier,
+                offset: 0,
+            });
+        };
+        let alphabet = match algorithm {
+            MultibaseAlgorithm::Base58btc => BASE58BTC_ALPHABET,
+            MultibaseAlgorithm::Base36 => BASE36_ALPHABET,
+            MultibaseAlgorithm::Base16 | MultibaseAlgorithm::Base16Upper => BASE16_ALPHABET,
+            MultibaseAlgorithm::Base32 | MultibaseAlgorithm::Base32Upper => BASE32_ALPHABET,
+            MultibaseAlgorithm::Base45 => BASE45_ALPHABET,
+        };
+        // all the uppercase variants rely on the (lowercase) alphabet of their counterparts
+        let uppercase = matches!(identifier, 'K' | 'F' | 'B');
+
+        let in_alphabet =
+            |character: char| u8::try_from(character).is_ok_and(|byte| alphabet.contains(&byte));
+        let offending = multibase.char_indices().skip(1).find(|&(_, character)| {
+            if uppercase {
+                character.is_ascii_lowercase() || !in_alphabet(character.to_ascii_lowercase())
+            } else {
+                !in_alphabet(character)
+            }
+        });
+        match offending {
+            Some((offset, character)) => {
+                Err(DidSidekicksError::InvalidMultibaseCharacter { character, offset })
+            }
+            None => Ok(algorithm),
+        }
+    }
+
+    /// The same as [`Self::decode`], yet validating the supplied `multibase` string strictly beforehand (see [`Self::validate_strict`]).
+    #[inline]
+    pub fn decode_strict(&self, multibase: &str) -> Result<Vec<u8>, DidSidekicksError> {
+        self.validate_strict(multibase)?;
+        self.decode(multibase)
+    }
+
+    /// Decode into a new (owned) buffer, whereas the algorithm is determined by the multibase identifier (prefix)
+    /// of the supplied `multibase` string (see [`MultibaseAlgorithm::detect`]).
+    #[inline]
+    pub fn decode(&self, multibase: &str) -> Result<Vec<u8>, DidSidekicksError> {
+        self.decode_any(multibase).map(|(decoded, _)| decoded)
+    }
+
+    /// The same as [`Self::decode`], yet delivering the detected algorithm alongside the decoded bytes.
+    /// Base58btc, base36, base32, base16 (each lower- and uppercase) and base45 are supported.
+    #[inline]
+    pub fn decode_any(
+        &self,
+        multibase: &str,
+    ) -> Result<(Vec<u8>, MultibaseAlgorithm), DidSidekicksError> {
+        let Some(algorithm) = MultibaseAlgorithm::detect(multibase) else {
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "Unsupported multibase algorithm identifier in '{multibase}'"
+            )));
+        };
+        // get rid of the multibase identifier, which is always a single (ASCII) character
+        let to_decode = multibase.get(1..).unwrap_or_default();
+
+        let decoded = match algorithm {
+            MultibaseAlgorithm::Base58btc => self.decode_base58(multibase)?,
+            MultibaseAlgorithm::Base36 => {
+                if multibase.starts_with(BASE36_UPPER_MULTIBASE_IDENTIFIER) {
+                    decode_radix(&to_decode.to_ascii_lowercase(), BASE36_ALPHABET)?
+                } else {
+                    decode_radix(to_decode, BASE36_ALPHABET)?
+                }
+            }
+            MultibaseAlgorithm::Base16 | MultibaseAlgorithm::Base16Upper => hex::decode(to_decode)
+                .map_err(|err| {
+                    DidSidekicksError::DeserializationFailed(format!(
+                        "Invalid base16 encoding: {err} in '{multibase}'"
+                    ))
+                })?,
+            MultibaseAlgorithm::Base32 => decode_base32(to_decode)?,
+            MultibaseAlgorithm::Base32Upper => decode_base32(&to_decode.to_ascii_lowercase())?,
+            MultibaseAlgorithm::Base45 => decode_base45(to_decode)?,
+        };
+        Ok((decoded, algorithm))
+    }
+}
+
+/// The multibase API (see [`MultibaseEncoderDecoder`]) exposed to foreign (e.g. Kotlin/Swift) code.
+/// Stateless, hence cheap to construct.
+///
+/// A UniFFI-compliant object.
+#[derive(Debug, Default)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct MultibaseCodec;
+
+impl MultibaseCodec {
+    /// The only (empty) constructor of the type.
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Encodes the supplied `data` into a multibase string using the supplied `algorithm`.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn encode(&self, algorithm: MultibaseAlgorithm, data: Vec<u8>) -> String {
+        MultibaseEncoderDecoder::default().encode(algorithm, &data)
+    }
+
+    /// Decodes the supplied `multibase` string, whereas the algorithm is determined by its multibase identifier (prefix).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn decode(&self, multibase: String) -> Result<Vec<u8>, DidSidekicksError> {
+        catch_panic(
+            || MultibaseEncoderDecoder::default().decode(&multibase),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// The same as [`Self::decode`], yet validating the supplied `multibase` string strictly beforehand
+    /// (see [`MultibaseEncoderDecoder::validate_strict`]).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn decode_strict(&self, multibase: String) -> Result<Vec<u8>, DidSidekicksError> {
+        catch_panic(
+            || MultibaseEncoderDecoder::default().decode_strict(&multibase),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Detects the algorithm by the multibase identifier (prefix) of the supplied `multibase` string, if supported.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn detect_algorithm(&self, multibase: String) -> Option<MultibaseAlgorithm> {
+        MultibaseAlgorithm::detect(&multibase)
+    }
+}
+
+/// The type of key featured by a Multikey (see https://www.w3.org/TR/controller-document/#Multikey),
+/// denoted by its multicodec (see https://github.com/multiformats/multicodec/blob/master/table.csv).
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum MulticodecKeyType {
+    /// Ed25519 public key (multicodec 0xed).
+    Ed25519Pub,
+    /// Ed25519 private key (multicodec 0x1300).
+    Ed25519Priv,
+    /// X25519 public key (multicodec 0xec).
+    X25519Pub,
+    /// Secp256k1 public key, compressed (multicodec 0xe7).
+    Secp256k1Pub,
+    /// P-256 public key, compressed (multicodec 0x1200).
+    P256Pub,
+    /// P-384 public key, compressed (multicodec 0x1201).
+    P384Pub,
+}
+
+impl MulticodecKeyType {
+    /// All supported key types.
+    pub const ALL: [Self; 6] = [
+        Self::Ed25519Pub,
+        Self::Ed25519Priv,
+        Self::X25519Pub,
+        Self::Secp256k1Pub,
+        Self::P256Pub,
+        Self::P384Pub,
+    ];
+
+    /// The (unsigned varint expression of the) multicodec prefixing the key data, e.g. 0xed01 for [`Self::Ed25519Pub`].
+    #[inline]
+    pub const fn prefix(self) -> [u8; 2] {
+        match self {
+            Self::Ed25519Pub => [0xed, 0x01],
+            Self::Ed25519Priv => [0x80, 0x26],
+            Self::X25519Pub => [0xec, 0x01],
+            Self::Secp256k1Pub => [0xe7, 0x01],
+            Self::P256Pub => [0x80, 0x24],
+            Self::P384Pub => [0x81, 0x24],
+        }
+    }
+
+    /// The length (in bytes) of the key data following the prefix.
+    #[inline]
+    pub const fn key_length(self) -> usize {
+        match self {
+            Self::Ed25519Pub | Self::Ed25519Priv | Self::X25519Pub => 32,
+            Self::Secp256k1Pub | Self::P256Pub => 33,
+            Self::P384Pub => 49,
+        }
+    }
+}
+
+/// A key expressed as Multikey (see https://www.w3.org/TR/controller-document/#Multikey).
+///
+/// I.e. the key data prefixed by its multicodec (see [`MulticodecKeyType::prefix`]) and encoded as base58btc multibase string,
+/// as featured by `publicKeyMultibase` (or `secretKeyMultibase`).
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Multikey {
+    key_type: MulticodecKeyType,
+    key: Vec<u8>,
+}
+
+impl Multikey {
+    /// The constructor featuring validation of the length of the supplied (raw) `key` data.
+    ///
+    /// A key of unexpected length is denoted by [`DidSidekicksError::DeserializationFailed`].
+    #[inline]
+    pub fn new(key_type: MulticodecKeyType, key: &[u8]) -> Result<Self, DidSidekicksError> {
+        if key.len() != key_type.key_length() {
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "Invalid {key_type:?} key length {}, expected {}",
+                key.len(),
+                key_type.key_length()
+            )));
+        }
+        Ok(Self {
+            key_type,
+            key: key.to_vec(),
+        })
+    }
+
+    /// Decodes the supplied base58btc `multibase` string, whereas the type of key is determined by its multicodec prefix.
+    ///
+    /// Any failure (e.g. an unsupported multicodec or a key of unexpected length) is denoted by
+    /// [`DidSidekicksError::DeserializationFailed`].
+    #[inline]
+    pub fn from_multibase(multibase: &str) -> Result<Self, DidSidekicksError> {
+        let decoded = MultibaseEncoderDecoder::default().decode_to_vec(multibase)?;
+        let Some((key_type, key)) = MulticodecKeyType::ALL.into_iter().find_map(|key_type| {
+            decoded
+                .strip_prefix(key_type.prefix().as_slice())
+                .map(|key| (key_type, key))
+        }) else {
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "Unsupported multicodec in multikey '{multibase}'"
+            )));
+        };
+        Self::new(key_type, key)
+    }
+
+    /// Encodes this key as base58btc multibase string, prefixed by its multicodec.
+    #[inline]
+    pub fn to_multibase(&self) -> String {
+        let mut prefixed = self.key_type.prefix().to_vec();
+        prefixed.extend_from_slice(&self.key);
+        MultibaseEncoderDecoder::default().encode_base58btc(&prefixed)
+    }
+
+    /// The type of this key.
+    #[inline]
+    pub const fn key_type(&self) -> MulticodecKeyType {
+        self.key_type
+    }
+
+    /// The (raw) key data, lacking any multicodec prefix.
+    #[inline]
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+}
+
+/// Delivers `1` if both supplied values are equal (`0` otherwise), in a data-independent manner.
+const fn ct_eq(left: u32, right: u32) -> u32 {
+    let diff = left ^ right;
+    // the most significant bit of `diff | -diff` is set for any non-zero `diff`
+    (diff | diff.wrapping_neg()).wrapping_shr(31) ^ 1
+}
+
+/// Delivers the value of the supplied base58btc `character` along with `1` if it is part of the alphabet (`0` otherwise),
+/// in a data-independent manner, i.e. the whole alphabet is always scanned.
+#[expect(clippy::single_call_fn, reason = "..")]
+fn ct_base58_digit(character: u8) -> (u32, u32) {
+    let mut digit: u32 = 0;
+    let mut found: u32 = 0;
+    for (value, symbol) in (0..).zip(BASE58BTC_ALPHABET) {
+        let matches = ct_eq(u32::from(character), u32::from(*symbol));
+        digit |= value & matches.wrapping_neg();
+        found |= matches;
+    }
+    (digit, found)
+}
+
+/// Encodes the supplied `data` as (big-endian) number in the radix of the supplied `alphabet`,
+/// whereas each leading zero byte is expressed by the first character of the alphabet.
+#[expect(clippy::single_call_fn, reason = "..")]
+fn encode_radix(data: &[u8], alphabet: &[u8]) -> String {
+    let radix = u32::try_from(alphabet.len()).unwrap_or(u32::MAX);
+    let zeros = data.iter().take_while(|byte| **byte == 0).count();
+
+    let mut digits: Vec<u32> = Vec::new(); // the least significant digit first
+    for byte in data.iter().skip(zeros) {
+        let mut carry = u32::from(*byte);
+        for digit in &mut digits {
+            carry = carry.saturating_add(digit.saturating_mul(256));
+            *digit = carry.rem_euclid(radix);
+            carry = carry.div_euclid(radix);
+        }
+        while carry > 0 {
+            digits.push(carry.rem_euclid(radix));
+            carry = carry.div_euclid(radix);
+        }
+    }
+
+    repeat_n(0, zeros)
+        .chain(digits.into_iter().rev())
+        .filter_map(|digit| alphabet.get(usize::try_from(digit).ok()?))
+        .map(|character| char::from(*character))
+        .collect()
+}
+
+/// The inverse of [`encode_radix`].
+fn decode_radix(encoded: &str, alphabet: &[u8]) -> Result<Vec<u8>, DidSidekicksError> {
+    let radix = u32::try_from(alphabet.len()).unwrap_or(u32::MAX);
+    let values = to_alphabet_values(encoded, alphabet)?;
+    let zeros = values.iter().take_while(|value| **value == 0).count();
+
+    let mut bytes: Vec<u8> = Vec::new(); // the least significant byte first
+    for value in values.into_iter().skip(zeros) {
+        let mut carry = value;
+        for byte in &mut bytes {
+            carry = carry.saturating_add(u32::from(*byte).saturating_mul(radix));
+            *byte = low_byte(carry);
+            carry = carry.div_euclid(256);
+        }
+        while carry > 0 {
+            bytes.push(low_byte(carry));
+            carry = carry.div_euclid(256);
+        }
+    }
+
+    bytes.extend(repeat_n(0, zeros));
+    bytes.reverse();
+    Ok(bytes)
+}
+
+/// Encodes the supplied `data` as specified by https://www.rfc-editor.org/rfc/rfc4648#section-6 (yet lowercase and without padding),
+/// i.e. each five bytes into eight characters (and the last incomplete chunk, if any, into the least number of characters).
+fn encode_base32(data: &[u8]) -> String {
+    data.chunks(5)
+        .flat_map(|chunk| {
+            let length = match chunk.len() {
+                1 => 2,
+                2 => 4,
+                3 => 5,
+                4 => 7,
+                5 => 8,
+                _ => 0,
+            };
+            // the chunk as 40-bit (big-endian) value, padded with zero bits
+            let value = (0..5).fold(0, |value: u64, index| {
+                value
+                    .saturating_mul(256)
+                    .saturating_add(chunk.get(index).copied().map_or(0, u64::from))
+            });
+            (0..length).map(move |index| {
+                value
+                    .div_euclid(u64::saturating_pow(32, u32::saturating_sub(7, index)))
+                    .rem_euclid(32)
+            })
+        })
+        .filter_map(|digit| BASE32_ALPHABET.get(usize::try_from(digit).ok()?))
+        .map(|character| char::from(*character))
+        .collect()
+}
+
+/// The inverse of [`encode_base32`].
+fn decode_base32(encoded: &str) -> Result<Vec<u8>, DidSidekicksError> {
+    let values = to_alphabet_values(encoded, BASE32_ALPHABET)?;
+    let mut bytes = Vec::with_capacity(values.len());
+    for chunk in values.chunks(8) {
+        let length = match chunk.len() {
+            2 => 1,
+            4 => 2,
+            5 => 3,
+            7 => 4,
+            8 => 5,
+            _ => {
+                return Err(DidSidekicksError::DeserializationFailed(format!(
+                    "Invalid base32 encoding: dangling character(s) in '{encoded}'"
+                )))
+            }
+        };
+        // the chunk as 40-bit (big-endian) value, padded with zero bits
+        let value = (0..8).fold(0, |value: u64, index| {
+            value
+                .saturating_mul(32)
+                .saturating_add(chunk.get(index).copied().map_or(0, u64::from))
+        });
+        bytes.extend((0..length).map(|index: u32| {
+            u8::try_from(
+                value
+                    .div_euclid(u64::saturating_pow(256, u32::saturating_sub(4, index)))
+                    .rem_euclid(256),
+            )
+            .unwrap_or_default()
+        }));
+    }
+    Ok(bytes)
+}
+
+/// Encodes the supplied `data` as specified by https://www.rfc-editor.org/rfc/rfc9285#section-4,
+/// i.e. each two bytes into three characters (and the odd last byte, if any, into two characters).
+#[expect(clippy::single_call_fn, reason = "..")]
+fn encode_base45(data: &[u8]) -> String {
+    data.chunks(2)
+        .flat_map(|chunk| {
+            let (value, length): (u32, u32) = match *chunk {
+                [first, second] => (
+                    u32::from(first)
+                        .saturating_mul(256)
+                        .saturating_add(u32::from(second)),
+                    3,
+                ),
+                [first] => (u32::from(first), 2),
+                _ => (0, 0),
+            };
+            (0..length).scan(value, |rest, _| {
+                let digit = rest.rem_euclid(45);
+                *rest = rest.div_euclid(45);
+                Some(digit)
+            })
+        })
+        .filter_map(|digit| BASE45_ALPHABET.get(usize::try_from(digit).ok()?))
+        .map(|character| char::from(*character))
+        .collect()
+}
+
+/// The inverse of [`encode_base45`].
+#[expect(clippy::single_call_fn, reason = "..")]
+#[expect(clippy::big_endian_bytes, reason = "..")]
+fn decode_base45(encoded: &str) -> Result<Vec<u8>, DidSidekicksError> {
+    let values = to_alphabet_values(encoded, BASE45_ALPHABET)?;
+    let mut bytes = Vec::with_capacity(values.len());
+    for chunk in values.chunks(3) {
+        let value = chunk.iter().rev().fold(0, |value: u32, digit| {
+            value.saturating_mul(45).saturating_add(*digit)
+        });
+        let overflow = || {
+            DidSidekicksError::DeserializationFailed(format!(
+                "Invalid base45 encoding: the value of a chunk exceeds the allowed range in '{encoded}'"
+            ))
+        };
+        match chunk.len() {
+            3 => bytes.extend(
+                u16::try_from(value)
+                    .ok()
+                    .ok_or_else(overflow)?
+                    .to_be_bytes(),
+            ),
+            2 => bytes.push(u8::try_from(value).ok().ok_or_else(overflow)?),
+            _ => {
+                return Err(DidSidekicksError::DeserializationFailed(format!(
+                    "Invalid base45 encoding: dangling character in '{encoded}'"
+                )))
+            }
+        }
+    }
+    Ok(bytes)
+}
+
+/// Maps each character of the supplied `encoded` string to its position (value) in the supplied `alphabet`.
+fn to_alphabet_values(encoded: &str, alphabet: &[u8]) -> Result<Vec<u32>, DidSidekicksError> {
+    encoded
+        .chars()
+        .map(|character| {
+            alphabet
+                .iter()
+                .position(|candidate| char::from(*candidate) == character)
+                .and_then(|position| u32::try_from(position).ok())
+                .ok_or_else(|| {
+                    DidSidekicksError::DeserializationFailed(format!(
+                        "Invalid character '{character}' in '{encoded}'"
+                    ))
+                })
+        })
+        .collect()
+}
+
+fn low_byte(value: u32) -> u8 {
+    u8::try_from(value.rem_euclid(256)).unwrap_or_default()
 }
 
 #[cfg(test)]
@@ -99,6 +840,7 @@ impl MultibaseEncoderDecoder {
 mod test {
     use super::*;
     use crate::errors::*;
+    use crate::test::assert_error;
     use rstest::rstest;
     use std::vec;
 
@@ -149,4 +891,295 @@ mod test {
             .to_string()
             .contains("buffer provided to decode base58 encoded string into was too small"));
     }
+
+    #[rstest]
+    #[case(b"helloworld")]
+    #[case(b"\x00\x00helloworld")]
+    #[case(b"\x00")]
+    #[case(b"")]
+    fn test_multibase_decode_to_vec(#[case] data: &[u8]) {
+        let multibase = MultibaseEncoderDecoder::default();
+        let encoded = multibase.encode_base58btc(data);
+        assert_eq!(multibase.decode_to_vec(&encoded).unwrap(), data);
+
+        assert_error(
+            multibase.decode_to_vec(encoded.trim_start_matches(BASE58BTC_MULTIBASE_IDENTIFIER)),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "Invalid multibase algorithm identifier 'Base58btc'",
+        );
+    }
+
+    #[rstest]
+    #[case(MulticodecKeyType::Ed25519Pub, 32, "z6Mk")]
+    #[case(MulticodecKeyType::Ed25519Priv, 32, "z3u2")]
+    #[case(MulticodecKeyType::X25519Pub, 32, "z6LS")]
+    #[case(MulticodecKeyType::Secp256k1Pub, 33, "zQ3s")]
+    #[case(MulticodecKeyType::P256Pub, 33, "zDn")]
+    #[case(MulticodecKeyType::P384Pub, 49, "z82")]
+    fn test_multikey(
+        #[case] key_type: MulticodecKeyType,
+        #[case] key_length: usize,
+        #[case] expected_prefix: &str,
+    ) {
+        let key = vec![2; key_length];
+        let multikey = Multikey::new(key_type, &key).unwrap();
+        let encoded = multikey.to_multibase();
+        assert!(encoded.starts_with(expected_prefix));
+
+        let decoded = Multikey::from_multibase(&encoded).unwrap();
+        assert_eq!(decoded, multikey);
+        assert_eq!(decoded.key_type(), key_type);
+        assert_eq!(decoded.key(), key.as_slice());
+    }
+
+    #[rstest]
+    fn test_multikey_invalid() {
+        assert_error(
+            Multikey::new(MulticodecKeyType::Ed25519Pub, &[0; 31]),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "Invalid Ed25519Pub key length 31, expected 32",
+        );
+
+        let multibase = MultibaseEncoderDecoder::default();
+        assert_error(
+            Multikey::from_multibase(&multibase.encode_base58btc(&[0xed, 0x01, 0])),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "Invalid Ed25519Pub key length 1, expected 32",
+        );
+        assert_error(
+            Multikey::from_multibase(&multibase.encode_base58btc(&[0x12, 0x00, 0])),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "Unsupported multicodec",
+        );
+    }
+
+    #[rstest]
+    #[case("z6sBRWyteSSzHrs", MultibaseAlgorithm::Base58btc)]
+    #[case("k2lcpzo5yikidynfl", MultibaseAlgorithm::Base36)]
+    #[case("K2LCPZO5YIKIDYNFL", MultibaseAlgorithm::Base36)]
+    #[case("f796573206d616e692021", MultibaseAlgorithm::Base16)]
+    #[case("F796573206D616E692021", MultibaseAlgorithm::Base16Upper)]
+    #[case("bpfsxgidnmfxgsibb", MultibaseAlgorithm::Base32)]
+    #[case("BPFSXGIDNMFXGSIBB", MultibaseAlgorithm::Base32Upper)]
+    #[case("RRFF.OEB$D5/Q", MultibaseAlgorithm::Base45)]
+    fn test_multibase_validate_strict(
+        #[case] multibase: &str,
+        #[case] expected: MultibaseAlgorithm,
+    ) {
+        let codec = MultibaseEncoderDecoder::default();
+        assert_eq!(codec.validate_strict(multibase).unwrap(), expected);
+        assert_eq!(
+            codec.decode_strict(multibase).unwrap(),
+            codec.decode(multibase).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case("z6sBRWy0eSSzHrs", '0', 7)] // no zero in the base58btc alphabet
+    #[case("z6sBRWyteSSzHrl", 'l', 14)]
+    #[case("k2lcpzo5yikidynfL", 'L', 16)] // lowercase only
+    #[case("F796573206d616e692021", 'd', 10)] // uppercase only
+    #[case("bpfsxgidnmfxgsib1", '1', 16)]
+    #[case("z\u{e4}6sBRWyteSSzHrs", '\u{e4}', 1)]
+    #[case("6sBRWyteSSzHrs", '6', 0)] // missing multibase identifier
+    fn test_multibase_validate_strict_invalid(
+        #[case] multibase: &str,
+        #[case] character: char,
+        #[case] offset: usize,
+    ) {
+        let codec = MultibaseEncoderDecoder::default();
+        let err = codec.validate_strict(multibase).unwrap_err();
+        assert_eq!(
+            err,
+            DidSidekicksError::InvalidMultibaseCharacter { character, offset }
+        );
+        assert_eq!(err.kind(), DidSidekicksErrorKind::InvalidMultibaseCharacter);
+        codec.decode_strict(multibase).unwrap_err();
+        assert_error(
+            codec.validate_strict(""),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "Empty multibase string",
+        );
+    }
+
+    #[rstest]
+    fn test_multibase_decode_base58() {
+        let multibase = MultibaseEncoderDecoder::default();
+        let encoded = multibase.encode_base58btc(b"helloworld"); // == "z6sBRWyteSSzHrs"
+        assert_eq!(multibase.decode_base58(&encoded).unwrap(), b"helloworld");
+
+        multibase.decode_base58("6sBRWyteSSzHrs").unwrap_err();
+    }
+
+    #[rstest]
+    // Examples taken from https://github.com/multiformats/multibase/blob/master/tests/basic.csv
+    #[case(b"yes mani !", "k2lcpzo5yikidynfl")]
+    #[case(b"\x00yes mani !", "k02lcpzo5yikidynfl")]
+    #[case(b"\x00\x00yes mani !", "k002lcpzo5yikidynfl")]
+    #[case(b"", "k")]
+    fn test_multibase_base36(#[case] data: &[u8], #[case] expected: &str) {
+        let multibase = MultibaseEncoderDecoder::default();
+        let encoded = multibase.encode_base36(data);
+        assert_eq!(encoded, expected);
+        assert_eq!(multibase.decode(&encoded).unwrap(), data);
+        assert_eq!(
+            multibase.decode(&encoded.to_ascii_uppercase()).unwrap(),
+            data
+        );
+    }
+
+    #[rstest]
+    // Examples taken from https://www.rfc-editor.org/rfc/rfc9285#section-4.3
+    #[case(b"AB", "RBB8")]
+    #[case(b"Hello!!", "R%69 VD92EX0")]
+    #[case(b"base-45", "RUJCLQE7W581")]
+    #[case(b"ietf!", "RQED8WEX0")]
+    #[case(b"", "R")]
+    fn test_multibase_base45(#[case] data: &[u8], #[case] expected: &str) {
+        let multibase = MultibaseEncoderDecoder::default();
+        let encoded = multibase.encode_base45(data);
+        assert_eq!(encoded, expected);
+        assert_eq!(multibase.decode(&encoded).unwrap(), data);
+    }
+
+    #[rstest]
+    // Examples taken from https://www.rfc-editor.org/rfc/rfc4648#section-10 and https://github.com/multiformats/multibase/blob/master/tests/basic.csv
+    #[case(b"", "b")]
+    #[case(b"f", "bmy")]
+    #[case(b"fo", "bmzxq")]
+    #[case(b"foo", "bmzxw6")]
+    #[case(b"foob", "bmzxw6yq")]
+    #[case(b"fooba", "bmzxw6ytb")]
+    #[case(b"foobar", "bmzxw6ytboi")]
+    #[case(b"yes mani !", "bpfsxgidnmfxgsibb")]
+    #[case(b"\x00yes mani !", "bab4wk4zanvqw42jaee")]
+    fn test_multibase_base32(#[case] data: &[u8], #[case] expected: &str) {
+        let multibase = MultibaseEncoderDecoder::default();
+        let encoded = multibase.encode_base32(data);
+        assert_eq!(encoded, expected);
+        assert_eq!(multibase.decode(&encoded).unwrap(), data);
+
+        let encoded_upper = multibase.encode_base32_upper(data);
+        assert_eq!(encoded_upper, expected.to_ascii_uppercase());
+        assert_eq!(multibase.decode(&encoded_upper).unwrap(), data);
+    }
+
+    #[rstest]
+    // Examples taken from https://www.rfc-editor.org/rfc/rfc4648#section-10 and https://github.com/multiformats/multibase/blob/master/tests/basic.csv
+    #[case(b"", "f")]
+    #[case(b"foobar", "f666f6f626172")]
+    #[case(b"yes mani !", "f796573206d616e692021")]
+    #[case(b"\x00yes mani !", "f00796573206d616e692021")]
+    fn test_multibase_base16(#[case] data: &[u8], #[case] expected: &str) {
+        let multibase = MultibaseEncoderDecoder::default();
+        let encoded = multibase.encode_base16(data);
+        assert_eq!(encoded, expected);
+        assert_eq!(multibase.decode(&encoded).unwrap(), data);
+
+        let encoded_upper = multibase.encode_base16_upper(data);
+        assert_eq!(encoded_upper, expected.to_ascii_uppercase());
+        assert_eq!(multibase.decode(&encoded_upper).unwrap(), data);
+    }
+
+    #[rstest]
+    #[case("RGGW", "exceeds the allowed range")] // See https://www.rfc-editor.org/rfc/rfc9285#section-4.3
+    #[case("RBB8B", "dangling character")]
+    #[case("Rbb8", "Invalid character 'b'")]
+    #[case("k2lcpzo5yikidynfl!", "Invalid character '!'")]
+    #[case("bmzxw6ytboi=", "Invalid character '='")] // padding is not supported
+    #[case("bmzx", "dangling character(s)")]
+    #[case("f666f6", "Invalid base16 encoding")] // odd number of digits
+    #[case("f666g", "Invalid base16 encoding")]
+    #[case("m2lcpzo5yikidynfl", "Unsupported multibase algorithm identifier")]
+    fn test_multibase_decode_invalid(#[case] multibase: &str, #[case] error_contains: &str) {
+        assert_error(
+            MultibaseEncoderDecoder::default().decode(multibase),
+            DidSidekicksErrorKind::DeserializationFailed,
+            error_contains,
+        );
+    }
+
+    #[rstest]
+    #[case("z6sBRWyteSSzHrs", MultibaseAlgorithm::Base58btc)]
+    #[case("k2lcpzo5yikidynfl", MultibaseAlgorithm::Base36)]
+    #[case("K2LCPZO5YIKIDYNFL", MultibaseAlgorithm::Base36)]
+    #[case("bpfsxgidnmfxgsibb", MultibaseAlgorithm::Base32)]
+    #[case("BPFSXGIDNMFXGSIBB", MultibaseAlgorithm::Base32Upper)]
+    #[case("f796573206d616e692021", MultibaseAlgorithm::Base16)]
+    #[case("F796573206D616E692021", MultibaseAlgorithm::Base16Upper)]
+    #[case("RQED8WEX0", MultibaseAlgorithm::Base45)]
+    fn test_multibase_decode_any(#[case] multibase: &str, #[case] expected: MultibaseAlgorithm) {
+        assert_eq!(MultibaseAlgorithm::detect(multibase), Some(expected));
+
+        let (decoded, algorithm) = MultibaseEncoderDecoder::default()
+            .decode_any(multibase)
+            .unwrap();
+        assert_eq!(algorithm, expected);
+        assert!(!decoded.is_empty());
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("m2lcpzo5yikidynfl")]
+    #[case("\u{e9}abc")]
+    fn test_multibase_detect_unsupported(#[case] multibase: &str) {
+        assert_eq!(MultibaseAlgorithm::detect(multibase), None);
+        assert_error(
+            MultibaseEncoderDecoder::default().decode_any(multibase),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "Unsupported multibase algorithm identifier",
+        );
+    }
+
+    #[rstest]
+    #[case(MultibaseAlgorithm::Base58btc, "z6sBRWyteSSzHrs")]
+    #[case(MultibaseAlgorithm::Base36, "k28a3cg5m0r002fs4")]
+    #[case(MultibaseAlgorithm::Base16Upper, "F68656C6C6F776F726C64")]
+    #[case(MultibaseAlgorithm::Base32, "bnbswy3dpo5xxe3de")]
+    #[case(MultibaseAlgorithm::Base45, "R+8D VD54E04ESVD")]
+    fn test_multibase_codec(#[case] algorithm: MultibaseAlgorithm, #[case] expected: &str) {
+        let codec = MultibaseCodec::new();
+        let encoded = codec.encode(algorithm, b"helloworld".to_vec());
+        assert_eq!(encoded, expected);
+        assert_eq!(codec.detect_algorithm(encoded.clone()), Some(algorithm));
+        assert_eq!(codec.decode(encoded.clone()).unwrap(), b"helloworld");
+        assert_eq!(codec.decode_strict(encoded).unwrap(), b"helloworld");
+    }
+
+    #[rstest]
+    #[case(&[0x80, 0x26, 0xff, 0x01])]
+    #[case(&[0, 0, 0x13, 0x00])]
+    #[case(&[0, 0, 0, 0])]
+    #[case(&[0xff; 34])]
+    fn test_multibase_decode_base58_secret(#[case] data: &[u8]) {
+        let multibase = MultibaseEncoderDecoder::default();
+        let encoded = multibase.encode_base58btc(data);
+        let mut decoded = vec![0; data.len()];
+        multibase
+            .decode_base58_secret_onto(&encoded, &mut decoded)
+            .unwrap();
+        assert_eq!(decoded, data);
+        assert_eq!(multibase.decode_to_vec(&encoded).unwrap(), data);
+    }
+
+    #[rstest]
+    #[case("z6sBRWyteSSzHrs", 9)] // too short a buffer
+    #[case("z6sBRWyteSSzHrs", 11)] // too long a buffer
+    #[case("z16sBRWyteSSzHrs", 10)] // a superfluous leading zero
+    #[case("z6sBRWyteSSzHr0", 10)] // '0' is not in the alphabet
+    #[case("z6sBRWyteSSzHr\u{e9}", 10)]
+    #[case("6sBRWyteSSzHrs", 10)]
+    fn test_multibase_decode_base58_secret_invalid(#[case] multibase: &str, #[case] len: usize) {
+        let mut decoded = vec![0xaa; len];
+        MultibaseEncoderDecoder::default()
+            .decode_base58_secret_onto(multibase, &mut decoded)
+            .unwrap_err();
+        assert!(decoded.iter().all(|byte| *byte == 0 || *byte == 0xaa));
+    }
+
+    #[rstest]
+    fn test_multibase_decode_base58btc() {
+        let multibase = MultibaseEncoderDecoder::default();
+        assert_eq!(multibase.decode("z6sBRWyteSSzHrs").unwrap(), b"helloworld");
+    }
 }
@@ -0,0 +1,304 @@
+// SPDX-License-Identifier: MIT
+
+//! Low-level multihash (https://github.com/multiformats/multihash) encoding/decoding.
+//!
+//! A multihash is a digest prefixed by the (unsigned varint) code of its hash algorithm and the (unsigned varint) digest length,
+//! e.g. `0x12 0x20 <32 bytes>` in case of sha2-256.
+//!
+//! The unsigned varint (https://github.com/multiformats/unsigned-varint) helpers are available as well,
+//! thus sparing downstream crates from reimplementing them for the sake of content addressing.
+
+use crate::errors::DidSidekicksError;
+use crate::metrics::count_hash_operation;
+use sha2::{Digest as _, Sha256, Sha384, Sha512};
+
+/// The maximum length (in bytes) of an unsigned varint, as specified by https://github.com/multiformats/unsigned-varint#practical-maximum-of-9-bytes-for-security.
+pub const MAX_VARINT_LENGTH: usize = 9;
+
+/// The hash algorithms supported by [`Multihash`], as listed by https://github.com/multiformats/multicodec/blob/master/table.csv.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum MultihashAlgorithm {
+    /// SHA-2 with 256 bits (32 bytes) of output, as defined by RFC 6234 (code 0x12).
+    Sha2_256,
+    /// SHA-2 with 384 bits (48 bytes) of output, as defined by RFC 6234 (code 0x20).
+    Sha2_384,
+    /// SHA-2 with 512 bits (64 bytes) of output, as defined by RFC 6234 (code 0x13).
+    Sha2_512,
+    /// BLAKE3 with (default) 256 bits (32 bytes) of output (code 0x1e).
+    Blake3,
+}
+
+impl MultihashAlgorithm {
+    /// All the supported hash algorithms.
+    pub const ALL: [Self; 4] = [Self::Sha2_256, Self::Sha2_384, Self::Sha2_512, Self::Blake3];
+
+    /// The multicodec code of the hash algorithm.
+    #[inline]
+    pub const fn code(self) -> u64 {
+        match self {
+            Self::Sha2_256 => 0x12,
+            Self::Sha2_384 => 0x20,
+            Self::Sha2_512 => 0x13,
+            Self::Blake3 => 0x1e,
+        }
+    }
+
+    /// The length (in bytes) of the digest produced by the hash algorithm.
+    #[inline]
+    pub const fn digest_length(self) -> usize {
+        match self {
+            Self::Sha2_256 | Self::Blake3 => 32,
+            Self::Sha2_384 => 48,
+            Self::Sha2_512 => 64,
+        }
+    }
+
+    /// Delivers the hash algorithm denoted by the supplied multicodec `code`, if supported.
+    #[inline]
+    pub fn from_code(code: u64) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.code() == code)
+    }
+
+    /// Computes the (bare) digest of the supplied `data`.
+    #[inline]
+    pub fn digest(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sha2_256 => Sha256::digest(data).to_vec(),
+            Self::Sha2_384 => Sha384::digest(data).to_vec(),
+            Self::Sha2_512 => Sha512::digest(data).to_vec(),
+            Self::Blake3 => blake3::hash(data).as_bytes().to_vec(),
+        }
+    }
+}
+
+/// Appends the unsigned varint expression of the supplied `value` to the supplied `buffer`.
+///
+/// I.e. seven bits per byte (least significant group first), whereas the most significant bit denotes continuation.
+#[inline]
+pub fn encode_varint(value: u64, buffer: &mut Vec<u8>) {
+    let mut rest = value;
+    while rest >= 0x80 {
+        buffer.push(u8::try_from(rest & 0x7f).unwrap_or_default() | 0x80);
+        rest = rest.checked_shr(7).unwrap_or_default();
+    }
+    buffer.push(u8::try_from(rest).unwrap_or_default());
+}
+
+/// Decodes the unsigned varint the supplied `bytes` start with, thus delivering its value along with its length (in bytes).
+///
+/// A truncated, overlong (i.e. not minimally encoded) or too long (see [`MAX_VARINT_LENGTH`]) varint
+/// is denoted by [`DidSidekicksError::DeserializationFailed`].
+#[inline]
+pub fn decode_varint(bytes: &[u8]) -> Result<(u64, usize), DidSidekicksError> {
+    let mut value: u64 = 0;
+    for (index, byte) in bytes.iter().take(MAX_VARINT_LENGTH).enumerate() {
+        let shift = u32::try_from(index.saturating_mul(7)).unwrap_or(u32::MAX);
+        value |= u64::from(byte & 0x7f)
+            .checked_shl(shift)
+            .unwrap_or_default();
+        if byte & 0x80 == 0 {
+            if *byte == 0 && index > 0 {
+                return Err(DidSidekicksError::DeserializationFailed(
+                    "Invalid varint: not minimally encoded".to_owned(),
+                ));
+            }
+            return Ok((value, index.saturating_add(1)));
+        }
+    }
+    Err(DidSidekicksError::DeserializationFailed(format!(
+        "Invalid varint: truncated or longer than {MAX_VARINT_LENGTH} bytes"
+    )))
+}
+
+/// A multihash, i.e. a digest along with the hash algorithm that produced it.
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
+pub struct Multihash {
+    algorithm: MultihashAlgorithm,
+    digest: Vec<u8>,
+}
+
+impl Multihash {
+    /// Computes the multihash of the supplied `data` using the supplied `algorithm`.
+    #[inline]
+    #[expect(clippy::same_name_method, reason = "..")]
+    pub fn hash(algorithm: MultihashAlgorithm, data: &[u8]) -> Self {
+        count_hash_operation("encode_multihash");
+        Self {
+            algorithm,
+            digest: algorithm.digest(data),
+        }
+    }
+
+    /// Wraps an already computed `digest` produced by the supplied `algorithm`.
+    ///
+    /// A digest of improper length is denoted by [`DidSidekicksError::DeserializationFailed`].
+    #[inline]
+    pub fn wrap(algorithm: MultihashAlgorithm, digest: &[u8]) -> Result<Self, DidSidekicksError> {
+        if digest.len() != algorithm.digest_length() {
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "Invalid {algorithm:?} digest length {} ({} expected)",
+                digest.len(),
+                algorithm.digest_length()
+            )));
+        }
+        Ok(Self {
+            algorithm,
+            digest: digest.to_vec(),
+        })
+    }
+
+    /// Decodes the supplied multihash `bytes`, which must be featuring nothing but the multihash itself.
+    ///
+    /// An unsupported hash algorithm or any malformation is denoted by [`DidSidekicksError::DeserializationFailed`].
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DidSidekicksError> {
+        let (code, code_length) = decode_varint(bytes)?;
+        let Some(algorithm) = MultihashAlgorithm::from_code(code) else {
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "Unsupported multihash algorithm code {code:#x}"
+            )));
+        };
+        let rest = bytes.get(code_length..).unwrap_or_default();
+        let (length, length_length) = decode_varint(rest)?;
+        let digest = rest.get(length_length..).unwrap_or_default();
+        if u64::try_from(digest.len()).ok() != Some(length) {
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "Invalid multihash: {} digest bytes featured, whereas {length} declared",
+                digest.len()
+            )));
+        }
+        Self::wrap(algorithm, digest)
+    }
+
+    /// Encodes this multihash, i.e. the varint-encoded algorithm code and digest length followed by the digest itself.
+    #[inline]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.digest.len().saturating_add(4));
+        encode_varint(self.algorithm.code(), &mut bytes);
+        encode_varint(
+            u64::try_from(self.digest.len()).unwrap_or(u64::MAX),
+            &mut bytes,
+        );
+        bytes.extend_from_slice(&self.digest);
+        bytes
+    }
+
+    /// Whether this multihash matches the supplied `data`, i.e. the very same digest is (re)computed.
+    #[inline]
+    pub fn matches(&self, data: &[u8]) -> bool {
+        count_hash_operation("encode_multihash");
+        self.algorithm.digest(data) == self.digest
+    }
+
+    /// The hash algorithm that produced the digest.
+    #[inline]
+    pub const fn algorithm(&self) -> MultihashAlgorithm {
+        self.algorithm
+    }
+
+    /// The (bare) digest.
+    #[inline]
+    pub fn digest(&self) -> &[u8] {
+        &self.digest
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use hex::encode as hex_encode;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0, "00")]
+    #[case(1, "01")]
+    #[case(127, "7f")]
+    #[case(128, "8001")]
+    #[case(255, "ff01")]
+    #[case(300, "ac02")]
+    #[case(0x1300, "8026")]
+    #[case(16384, "808001")]
+    fn test_varint(#[case] value: u64, #[case] expected: &str) {
+        let mut buffer = Vec::new();
+        encode_varint(value, &mut buffer);
+        assert_eq!(hex_encode(&buffer), expected);
+        assert_eq!(decode_varint(&buffer).unwrap(), (value, buffer.len()));
+    }
+
+    #[rstest]
+    #[case(&[], "truncated")]
+    #[case(&[0x80], "truncated")]
+    #[case(&[0x80, 0x00], "not minimally encoded")]
+    #[case(&[0xff; 10], "longer than 9 bytes")]
+    fn test_varint_invalid(#[case] bytes: &[u8], #[case] error_contains: &str) {
+        assert_error(
+            decode_varint(bytes),
+            DidSidekicksErrorKind::DeserializationFailed,
+            error_contains,
+        );
+    }
+
+    #[rstest]
+    #[case(
+        // Example taken from https://multiformats.io/multihash/#sha2-256---256-bits-aka-sha256
+        MultihashAlgorithm::Sha2_256,
+        "122041dd7b6443542e75701aa98a0c235951a28a0d851b11564d20022ab11d2589a8"
+    )]
+    #[case(
+        // Example taken from https://multiformats.io/multihash/#sha2-512---512-bits-aka-sha512
+        MultihashAlgorithm::Sha2_512,
+        "134052eb4dd19f1ec522859e12d89706156570f8fbab1824870bc6f8c7d235eef5f4c2cbbafd365f96fb12b1d98a0334870c2ce90355da25e6a1108a6e17c4aaebb0"
+    )]
+    fn test_multihash(#[case] algorithm: MultihashAlgorithm, #[case] expected: &str) {
+        let data = "Merkle\u{2013}Damg\u{e5}rd".as_bytes(); // "Merkle–Damgård"
+        let multihash = Multihash::hash(algorithm, data);
+        assert_eq!(hex_encode(multihash.to_bytes()), expected);
+        assert!(multihash.matches(data));
+        assert!(!multihash.matches(b"tampered"));
+        assert_eq!(
+            Multihash::from_bytes(&multihash.to_bytes()).unwrap(),
+            multihash
+        );
+    }
+
+    #[rstest]
+    fn test_multihash_round_trip() {
+        for algorithm in MultihashAlgorithm::ALL {
+            let multihash = Multihash::hash(algorithm, b"helloworld");
+            assert_eq!(multihash.digest().len(), algorithm.digest_length());
+            assert_eq!(
+                MultihashAlgorithm::from_code(algorithm.code()),
+                Some(algorithm)
+            );
+            assert_eq!(
+                Multihash::from_bytes(&multihash.to_bytes()).unwrap(),
+                multihash
+            );
+        }
+    }
+
+    #[rstest]
+    #[case(&[0x11, 0x00], "Unsupported multihash algorithm code 0x11")]
+    #[case(&[0x12, 0x20, 0x00], "1 digest bytes featured, whereas 32 declared")]
+    #[case(&[0x12, 0x01, 0x00], "Invalid Sha2_256 digest length 1 (32 expected)")]
+    #[case(&[0x12], "truncated")]
+    fn test_multihash_invalid(#[case] bytes: &[u8], #[case] error_contains: &str) {
+        assert_error(
+            Multihash::from_bytes(bytes),
+            DidSidekicksErrorKind::DeserializationFailed,
+            error_contains,
+        );
+    }
+}
@@ -0,0 +1,203 @@
+// SPDX-License-Identifier: MIT
+
+//! OpenID4VP key binding helpers, tying together [`DidResolver`], [`DidDoc`] and [`Jwk`].
+//!
+//! See https://openid.net/specs/openid-4-verifiable-presentations-1_0.html#name-client-identifier-prefix-an.
+
+use crate::did_doc::{DidDoc, Jwk, VerificationMethod};
+use crate::did_resolver::DidResolver;
+use crate::errors::DidResolverError;
+use crate::panic_boundary::catch_panic;
+
+/// The client identifier prefix denoting a verifier identified by a DID.
+pub const CLIENT_ID_PREFIX_DECENTRALIZED_IDENTIFIER: &str = "decentralized_identifier:";
+
+/// Delivers the verifier's DID out of the supplied OpenID4VP `client_id`.
+///
+/// Both the prefixed (`decentralized_identifier:did:...`) and the legacy (`did:...`) form are supported.
+#[inline]
+pub fn did_from_client_id(client_id: &str) -> Result<&str, DidResolverError> {
+    let did = client_id
+        .strip_prefix(CLIENT_ID_PREFIX_DECENTRALIZED_IDENTIFIER)
+        .unwrap_or(client_id);
+    if !did.starts_with("did:") || did.contains('#') {
+        return Err(DidResolverError::InvalidMethodSpecificId(format!(
+            "client_id '{client_id}' does not denote a DID"
+        )));
+    }
+    Ok(did)
+}
+
+/// Resolves the verifier's DID denoted by the supplied OpenID4VP `client_id`, by means of the supplied `resolve` function
+/// (typically a method-specific resolver constructor).
+///
+/// Any panic of the `resolve` function is denoted by [`DidResolverError::InternalError`].
+#[inline]
+pub fn resolve_verifier<R, F>(client_id: &str, resolve: F) -> Result<R, DidResolverError>
+where
+    R: DidResolver,
+    F: FnOnce(&str) -> Result<R, DidResolverError>,
+{
+    let did = did_from_client_id(client_id)?;
+    catch_panic(|| resolve(did), DidResolverError::InternalError)
+}
+
+fn all_verification_methods(did_doc: &DidDoc) -> impl Iterator<Item = &VerificationMethod> {
+    did_doc
+        .verification_method
+        .iter()
+        .chain(did_doc.authentication.iter())
+        .chain(did_doc.capability_invocation.iter())
+        .chain(did_doc.capability_delegation.iter())
+        .chain(did_doc.assertion_method.iter())
+        .chain(did_doc.key_agreement.iter())
+}
+
+/// Extracts all (deduplicated by verification method id) JWKs of the verifier resolved by the supplied `resolver`.
+#[inline]
+pub fn extract_verifier_jwks(resolver: &dyn DidResolver) -> Vec<Jwk> {
+    let did_doc = resolver.get_did_doc_obj();
+    let mut ids: Vec<&str> = Vec::new();
+    let mut jwks: Vec<Jwk> = Vec::new();
+    all_verification_methods(&did_doc).for_each(|method| {
+        if let Some(jwk) = method.public_key_jwk.as_ref() {
+            if !ids.contains(&method.id.as_str()) {
+                ids.push(method.id.as_str());
+                jwks.push(jwk.to_owned());
+            }
+        }
+    });
+    jwks
+}
+
+/// Validates the binding between the supplied OpenID4VP `client_id` and the `kid` (JOSE header parameter)
+/// of the signed request object, thus delivering the JWK to be used for signature verification.
+///
+/// The `kid` must be a DID URL referring to a verification method (featuring a JWK) of the verifier's DID document
+/// resolved by the supplied `resolver`, whereas the DID itself must match the `client_id`.
+#[inline]
+pub fn validate_client_id_binding(
+    resolver: &dyn DidResolver,
+    client_id: &str,
+    kid: &str,
+) -> Result<Jwk, DidResolverError> {
+    let did = did_from_client_id(client_id)?;
+    let did_doc = resolver.get_did_doc_obj();
+    if did_doc.id != did {
+        return Err(DidResolverError::InvalidDidDocument(format!(
+            "the resolved DID document '{}' does not match client_id '{client_id}'",
+            did_doc.id
+        )));
+    }
+
+    if !matches!(kid.split_once('#'), Some((kid_did, fragment)) if kid_did == did && !fragment.is_empty())
+    {
+        return Err(DidResolverError::InvalidDidParameter(format!(
+            "kid '{kid}' is not a DID URL bound to client_id '{client_id}'"
+        )));
+    }
+
+    let method = all_verification_methods(&did_doc)
+        .find(|method| method.id == kid)
+        .ok_or_else(|| {
+            DidResolverError::InvalidDidDocument(format!(
+                "no verification method found for kid '{kid}'"
+            ))
+        })?;
+
+    method.public_key_jwk.to_owned().ok_or_else(|| {
+        DidResolverError::InvalidDidDocument(format!(
+            "verification method '{kid}' features no JWK"
+        ))
+    })
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::did_method_parameters::DidMethodParameter;
+    use crate::errors::DidResolverErrorKind;
+    use rstest::rstest;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    const DID: &str = "did:example:verifier";
+
+    struct StaticDidResolver(Arc<DidDoc>);
+    #[expect(clippy::missing_trait_methods, reason = "..")]
+    impl DidResolver for StaticDidResolver {
+        fn get_did_doc_obj(&self) -> Arc<DidDoc> {
+            Arc::clone(&self.0)
+        }
+        fn collect_did_method_parameters_map(
+            &self,
+        ) -> Result<HashMap<String, Arc<DidMethodParameter>>, DidResolverError> {
+            Ok(HashMap::new())
+        }
+    }
+
+    fn resolve(did: &str) -> Result<StaticDidResolver, DidResolverError> {
+        let jwk = json!({
+            "kty": "EC",
+            "crv": "P-256",
+            "kid": "assert-key-01",
+            "x": "3-xR-ApvKYCKtXxjvypxIb4tHJSUTHCl0uUYVAvP6sE",
+            "y": "jkQdXwStFmrJjHuWw8PE_AG43c4OQwd6-Rkr4sPiC7Y"
+        });
+        let did_doc = DidDoc::from_json(
+            &json!({
+                "@context": ["https://www.w3.org/ns/did/v1"],
+                "id": did,
+                "verificationMethod": [
+                    {"id": format!("{did}#assert-key-01"), "type": "JsonWebKey2020", "publicKeyJwk": jwk},
+                    {"id": format!("{did}#multikey"), "type": "Multikey", "publicKeyMultibase": "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2"}
+                ],
+                "assertionMethod": [
+                    {"id": format!("{did}#assert-key-01"), "type": "JsonWebKey2020", "publicKeyJwk": jwk}
+                ]
+            })
+            .to_string(),
+        )
+        .map_err(|err| DidResolverError::InvalidDidDocument(err.to_string()))?;
+        Ok(StaticDidResolver(Arc::new(did_doc)))
+    }
+
+    #[rstest]
+    #[case("decentralized_identifier:did:example:verifier")]
+    #[case("did:example:verifier")]
+    fn test_client_id_binding(#[case] client_id: &str) {
+        let resolver = resolve_verifier(client_id, resolve).unwrap();
+        assert_eq!(extract_verifier_jwks(&resolver).len(), 1);
+
+        let jwk =
+            validate_client_id_binding(&resolver, client_id, &format!("{DID}#assert-key-01"))
+                .unwrap();
+        assert_eq!(jwk.kid.unwrap(), "assert-key-01");
+    }
+
+    #[rstest]
+    #[case("x509_san_dns:example.com", "did:example:verifier#assert-key-01", DidResolverErrorKind::InvalidMethodSpecificId)]
+    #[case("did:example:verifier", "did:example:other#assert-key-01", DidResolverErrorKind::InvalidDidParameter)]
+    #[case("did:example:verifier", "#assert-key-01", DidResolverErrorKind::InvalidDidParameter)]
+    #[case("did:example:verifier", "did:example:verifier#unknown", DidResolverErrorKind::InvalidDidDocument)]
+    #[case("did:example:verifier", "did:example:verifier#multikey", DidResolverErrorKind::InvalidDidDocument)]
+    #[case("did:example:other", "did:example:other#assert-key-01", DidResolverErrorKind::InvalidDidDocument)]
+    fn test_client_id_binding_failure(
+        #[case] client_id: &str,
+        #[case] kid: &str,
+        #[case] expected_error_kind: DidResolverErrorKind,
+    ) {
+        let resolver = resolve(DID).unwrap();
+        assert_eq!(
+            validate_client_id_binding(&resolver, client_id, kid)
+                .unwrap_err()
+                .kind(),
+            expected_error_kind
+        );
+    }
+}
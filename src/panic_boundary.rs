@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: MIT
+
+//! A safe panic boundary for FFI entry points.
+//!
+//! Any panic occurring within an FFI entry point is caught and translated into an error (e.g. `InternalError`),
+//! instead of unwinding across the FFI boundary (and thus potentially aborting the host process).
+
+use core::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Runs the supplied `entry_point`, while translating any panic into an error by means of `internal_error`
+/// (supplied with the panic message).
+///
+/// CAUTION Catching panics is only possible if the crate is built using `panic = "unwind"` (default) strategy.
+#[inline]
+pub fn catch_panic<T, E, F, P>(entry_point: F, internal_error: P) -> Result<T, E>
+where
+    F: FnOnce() -> Result<T, E>,
+    P: FnOnce(String) -> E,
+{
+    // The state possibly left behind by a panic is never observed by the caller, as the error is returned instead
+    catch_unwind(AssertUnwindSafe(entry_point))
+        .unwrap_or_else(|payload| Err(internal_error(panic_message(payload.as_ref()))))
+}
+
+#[expect(clippy::single_call_fn, reason = "..")]
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|msg| (*msg).to_owned())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_owned())
+}
+
+#[cfg(test)]
+#[expect(clippy::panic, reason = "panics are deliberately caused here")]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+mod test {
+    use super::catch_panic;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_catch_panic() {
+        assert_eq!(catch_panic(|| Ok::<u8, String>(1), |msg| msg), Ok(1));
+        assert_eq!(
+            catch_panic(|| Err::<u8, String>("error".to_owned()), |msg| msg),
+            Err("error".to_owned())
+        );
+        assert_eq!(
+            catch_panic::<u8, String, _, _>(|| panic!("static message"), |msg| msg),
+            Err("static message".to_owned())
+        );
+        let detail = 42;
+        assert_eq!(
+            catch_panic::<u8, String, _, _>(|| panic!("formatted message {detail}"), |msg| msg),
+            Err("formatted message 42".to_owned())
+        );
+    }
+}
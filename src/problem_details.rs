@@ -0,0 +1,357 @@
+// SPDX-License-Identifier: MIT
+
+//! Conversion of error reports into RFC 7807 problem details (see https://www.rfc-editor.org/rfc/rfc7807).
+//!
+//! HTTP services built on top of this crate may thus respond using consistent, machine-readable `application/problem+json` bodies.
+//!
+//! The `type` URI of a problem is derived from the error kind, i.e.:
+//! - a [`DidResolverError`] is denoted by the URI of the matching DID resolution error (see [`DidResolutionErrorType::uri`]),
+//! - any other error (e.g. [`DidSidekicksError`]) by its stable error code appended to [`ProblemDetailsOptions::type_uri_base`].
+//!
+//! Whenever the offending part of the input is known (e.g. a duplicate JSON key), it is denoted by the `instance` member,
+//! i.e. a URI fragment featuring a JSON pointer (as specified by https://www.rfc-editor.org/rfc/rfc6901#section-6).
+
+#[cfg(feature = "jsonschema-validation")]
+use crate::did_jsonschema::{DidLogEntryValidatorError, DidLogEntryValidatorErrorKind};
+use crate::error_localization::ErrorLocale;
+use crate::errors::{
+    DidResolutionErrorType, DidResolverError, DidSidekicksError, DidSidekicksErrorKind,
+};
+use crate::json_emission::{emit_json, EmitOptions};
+use serde::{Deserialize, Serialize};
+
+/// The media type of a problem details JSON object, as registered by https://www.rfc-editor.org/rfc/rfc7807#section-6.1.
+pub const PROBLEM_JSON_MEDIA_TYPE: &str = "application/problem+json";
+
+/// The default [`ProblemDetailsOptions::type_uri_base`].
+pub const DEFAULT_PROBLEM_TYPE_URI_BASE: &str = "urn:did-sidekicks:problem:";
+
+/// The options controlling the conversion of an error into [`ProblemDetails`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct ProblemDetailsOptions {
+    /// The base of the `type` URI of a problem, followed by the (stable) error code, [`DEFAULT_PROBLEM_TYPE_URI_BASE`] by default.
+    pub type_uri_base: String,
+    /// Whether the (English) error details are to be included as the `detail` member, `true` by default.
+    ///
+    /// Should be disabled whenever the details must not be disclosed to the client.
+    pub include_detail: bool,
+}
+
+impl Default for ProblemDetailsOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            type_uri_base: DEFAULT_PROBLEM_TYPE_URI_BASE.to_owned(),
+            include_detail: true,
+        }
+    }
+}
+
+/// A problem details JSON object, as specified by https://www.rfc-editor.org/rfc/rfc7807#section-3.1.
+///
+/// Besides the standard members, the (stable) error `code` is featured as an extension member.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct ProblemDetails {
+    /// A URI reference identifying the problem type.
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// A short, human-readable summary of the problem type.
+    pub title: String,
+    /// The HTTP status code.
+    pub status: u16,
+    /// A human-readable explanation specific to this occurrence of the problem.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub detail: Option<String>,
+    /// A URI reference identifying the specific occurrence of the problem, e.g. `#/a/b` (a JSON pointer).
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub instance: Option<String>,
+    /// The (stable) error code, e.g. `DUPLICATE_JSON_KEY`.
+    pub code: String,
+}
+
+impl ProblemDetails {
+    /// Converts the supplied `error` according to the supplied `options`.
+    ///
+    /// In case of [`DidSidekicksError::DuplicateJsonKey`], the `instance` denotes the duplicate key.
+    #[inline]
+    #[expect(clippy::pattern_type_mismatch, reason = "..")]
+    pub fn from_sidekicks_error(
+        error: &DidSidekicksError,
+        options: &ProblemDetailsOptions,
+    ) -> Self {
+        let kind = error.kind();
+        let instance = if let DidSidekicksError::DuplicateJsonKey(detail) = error {
+            pointer_fragment(detail)
+        } else {
+            None
+        };
+        Self {
+            problem_type: format!("{}{}", options.type_uri_base, kind.code()),
+            title: kind.catalog_message(ErrorLocale::English).to_owned(),
+            status: sidekicks_error_status(kind),
+            detail: options.include_detail.then(|| error.detail().into_owned()),
+            instance,
+            code: kind.code().to_owned(),
+        }
+    }
+
+    /// Converts the supplied `error` according to the supplied `options`.
+    ///
+    /// The `type` is the URI of the matching DID resolution error (see [`DidResolutionErrorType::uri`]),
+    /// hence [`ProblemDetailsOptions::type_uri_base`] is ignored.
+    #[inline]
+    pub fn from_resolver_error(error: &DidResolverError, options: &ProblemDetailsOptions) -> Self {
+        let spec_error = error.kind().as_spec_error();
+        Self {
+            problem_type: spec_error.uri(),
+            title: resolution_error_title(spec_error).to_owned(),
+            status: resolution_error_status(spec_error),
+            detail: options.include_detail.then(|| error.to_string()),
+            instance: None,
+            code: spec_error.code().to_owned(),
+        }
+    }
+
+    /// Converts the supplied `error` according to the supplied `options`.
+    #[cfg(feature = "jsonschema-validation")]
+    #[inline]
+    #[expect(clippy::pattern_type_mismatch, reason = "..")]
+    pub fn from_validator_error(
+        error: &DidLogEntryValidatorError,
+        options: &ProblemDetailsOptions,
+    ) -> Self {
+        let (code, title, status) = match error.kind() {
+            DidLogEntryValidatorErrorKind::ValidationError => {
+                ("INVALID_DID_LOG", "The supplied DID log is invalid", 422)
+            }
+            DidLogEntryValidatorErrorKind::DeserializationError => (
+                "DESERIALIZATION_FAILED",
+                "The supplied DID log cannot be deserialized",
+                400,
+            ),
+            DidLogEntryValidatorErrorKind::BudgetExceeded => (
+                "VALIDATION_BUDGET_EXCEEDED",
+                "The validation budget is exceeded",
+                422,
+            ),
+            DidLogEntryValidatorErrorKind::InternalError => {
+                ("INTERNAL_ERROR", "Internal error", 500)
+            }
+        };
+        let detail = match error {
+            DidLogEntryValidatorError::ValidationError(detail)
+            | DidLogEntryValidatorError::DeserializationError(detail)
+            | DidLogEntryValidatorError::BudgetExceeded(detail)
+            | DidLogEntryValidatorError::InternalError(detail) => detail,
+        };
+        Self {
+            problem_type: format!("{}{code}", options.type_uri_base),
+            title: title.to_owned(),
+            status,
+            detail: options.include_detail.then(|| detail.to_owned()),
+            instance: None,
+            code: code.to_owned(),
+        }
+    }
+
+    /// Replaces the `instance` by the supplied JSON `pointer` (e.g. `/proof/0`), denoted as URI fragment (e.g. `#/proof/0`).
+    #[inline]
+    #[must_use]
+    pub fn with_instance_pointer(mut self, pointer: &str) -> Self {
+        self.instance = Some(to_fragment(pointer));
+        self
+    }
+
+    /// Emits this problem as `application/problem+json` body (see [`PROBLEM_JSON_MEDIA_TYPE`]).
+    ///
+    /// Any failure is denoted by [`DidSidekicksError::SerializationFailed`].
+    #[inline]
+    pub fn to_json(&self) -> Result<String, DidSidekicksError> {
+        emit_json(self, EmitOptions::COMPACT)
+    }
+}
+
+impl From<&DidSidekicksError> for ProblemDetails {
+    #[inline]
+    fn from(error: &DidSidekicksError) -> Self {
+        Self::from_sidekicks_error(error, &ProblemDetailsOptions::default())
+    }
+}
+
+impl From<&DidResolverError> for ProblemDetails {
+    #[inline]
+    fn from(error: &DidResolverError) -> Self {
+        Self::from_resolver_error(error, &ProblemDetailsOptions::default())
+    }
+}
+
+#[cfg(feature = "jsonschema-validation")]
+impl From<&DidLogEntryValidatorError> for ProblemDetails {
+    #[inline]
+    fn from(error: &DidLogEntryValidatorError) -> Self {
+        Self::from_validator_error(error, &ProblemDetailsOptions::default())
+    }
+}
+
+/// Delivers the HTTP status code matching the supplied error `kind`, i.e.:
+/// - `400` (Bad Request) for malformed input,
+/// - `500` (Internal Server Error) for any internal failure and
+/// - `422` (Unprocessable Content) for well-formed, yet invalid input.
+#[expect(clippy::single_call_fn, reason = "..")]
+const fn sidekicks_error_status(kind: DidSidekicksErrorKind) -> u16 {
+    match kind {
+        DidSidekicksErrorKind::DeserializationFailed
+        | DidSidekicksErrorKind::DuplicateJsonKey
+        | DidSidekicksErrorKind::InvalidMultibaseCharacter => 400,
+        DidSidekicksErrorKind::SerializationFailed | DidSidekicksErrorKind::InternalError => 500,
+        DidSidekicksErrorKind::InvalidDidDocument
+        | DidSidekicksErrorKind::InvalidIntegrityProof
+        | DidSidekicksErrorKind::InvalidDidMethodParameter
+        | DidSidekicksErrorKind::KeyNotFound
+        | DidSidekicksErrorKind::NonExistingKeyReferenced
+        | DidSidekicksErrorKind::InvalidWitnessProof
+        | DidSidekicksErrorKind::InvalidDidCommMessage
+        | DidSidekicksErrorKind::InvalidJws
+        | DidSidekicksErrorKind::KeyNotAuthorized
+        | DidSidekicksErrorKind::UnsupportedCryptosuite
+        | DidSidekicksErrorKind::SignatureInvalid
+        | DidSidekicksErrorKind::KeyMismatch
+        | DidSidekicksErrorKind::ProofExpired
+        | DidSidekicksErrorKind::ChallengeMismatch
+        | DidSidekicksErrorKind::DomainMismatch
+        | DidSidekicksErrorKind::PolicyViolation => 422,
+    }
+}
+
+/// Delivers the HTTP status code matching the supplied `error`, as specified by https://w3c.github.io/did-resolution/#bindings-https.
+#[expect(clippy::single_call_fn, reason = "..")]
+const fn resolution_error_status(error: DidResolutionErrorType) -> u16 {
+    match error {
+        DidResolutionErrorType::InvalidDid
+        | DidResolutionErrorType::InvalidDidUrl
+        | DidResolutionErrorType::InvalidOptions => 400,
+        DidResolutionErrorType::NotFound => 404,
+        DidResolutionErrorType::RepresentationNotSupported => 406,
+        DidResolutionErrorType::MethodNotSupported => 501,
+        DidResolutionErrorType::InvalidDidDocument | DidResolutionErrorType::InternalError => 500,
+    }
+}
+
+#[expect(clippy::single_call_fn, reason = "..")]
+const fn resolution_error_title(error: DidResolutionErrorType) -> &'static str {
+    match error {
+        DidResolutionErrorType::InvalidDid => "Invalid DID",
+        DidResolutionErrorType::InvalidDidUrl => "Invalid DID URL",
+        DidResolutionErrorType::NotFound => "DID not found",
+        DidResolutionErrorType::RepresentationNotSupported => "Representation not supported",
+        DidResolutionErrorType::MethodNotSupported => "DID method not supported",
+        DidResolutionErrorType::InvalidDidDocument => "Invalid DID document",
+        DidResolutionErrorType::InvalidOptions => "Invalid resolution options",
+        DidResolutionErrorType::InternalError => "Internal error",
+    }
+}
+
+/// Extracts the JSON pointer trailing the supplied `detail` (e.g. `line 3: /a/b`), if any, as URI fragment.
+#[expect(clippy::single_call_fn, reason = "..")]
+fn pointer_fragment(detail: &str) -> Option<String> {
+    let pointer = detail.rsplit_once(": ").map_or(detail, |(_, tail)| tail);
+    (pointer.is_empty() || pointer.starts_with('/')).then(|| to_fragment(pointer))
+}
+
+/// Denotes the supplied JSON `pointer` as URI fragment, while percent-encoding any character not allowed therein
+/// (see https://www.rfc-editor.org/rfc/rfc6901#section-6).
+fn to_fragment(pointer: &str) -> String {
+    let mut fragment = String::from("#");
+    for byte in pointer.bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~!$&'()*+,;=:@".contains(&byte) {
+            fragment.push(char::from(byte));
+        } else {
+            fragment.push('%');
+            fragment.push_str(&hex::encode_upper([byte]));
+        }
+    }
+    fragment
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+mod test {
+    use super::*;
+    use rstest::rstest;
+    use serde_json::{json, Value};
+
+    #[rstest]
+    fn test_from_sidekicks_error() {
+        let problem = ProblemDetails::from(&DidSidekicksError::DuplicateJsonKey(
+            "line 3: /a/b c".to_owned(),
+        ));
+        assert_eq!(
+            problem.problem_type,
+            "urn:did-sidekicks:problem:DUPLICATE_JSON_KEY"
+        );
+        assert_eq!(problem.status, 400);
+        assert_eq!(problem.instance.as_deref(), Some("#/a/b%20c"));
+        assert_eq!(
+            serde_json::from_str::<Value>(&problem.to_json().unwrap()).unwrap(),
+            json!({
+                "type": "urn:did-sidekicks:problem:DUPLICATE_JSON_KEY",
+                "title": problem.title,
+                "status": 400,
+                "detail": "line 3: /a/b c",
+                "instance": "#/a/b%20c",
+                "code": "DUPLICATE_JSON_KEY",
+            })
+        );
+    }
+
+    #[rstest]
+    fn test_options() {
+        let options = ProblemDetailsOptions {
+            type_uri_base: "https://example.com/problems/".to_owned(),
+            include_detail: false,
+        };
+        let problem = ProblemDetails::from_sidekicks_error(
+            &DidSidekicksError::KeyNotAuthorized("secret".to_owned()),
+            &options,
+        )
+        .with_instance_pointer("/proof");
+        assert_eq!(
+            problem.problem_type,
+            "https://example.com/problems/KEY_NOT_AUTHORIZED"
+        );
+        assert_eq!(problem.status, 422);
+        assert_eq!(problem.detail, None);
+        assert_eq!(problem.instance.as_deref(), Some("#/proof"));
+        assert!(!problem.to_json().unwrap().contains("secret"));
+    }
+
+    #[rstest]
+    #[case(DidResolverError::InvalidMethodSpecificId("x".to_owned()), "https://www.w3.org/ns/did#INVALID_DID", 400)]
+    #[case(DidResolverError::InvalidDidParameter("x".to_owned()), "https://www.w3.org/ns/did#INVALID_DID_URL", 400)]
+    #[case(DidResolverError::InvalidDidDocument("x".to_owned()), "https://www.w3.org/ns/did#INVALID_DID_DOCUMENT", 500)]
+    fn test_from_resolver_error(
+        #[case] error: DidResolverError,
+        #[case] expected_type: &str,
+        #[case] expected_status: u16,
+    ) {
+        let problem = ProblemDetails::from(&error);
+        assert_eq!(problem.problem_type, expected_type);
+        assert_eq!(problem.status, expected_status);
+    }
+
+    #[cfg(feature = "jsonschema-validation")]
+    #[rstest]
+    fn test_from_validator_error() {
+        let problem = ProblemDetails::from(&DidLogEntryValidatorError::ValidationError(
+            "\"versionId\" is a required property".to_owned(),
+        ));
+        assert_eq!(problem.code, "INVALID_DID_LOG");
+        assert_eq!(problem.status, 422);
+    }
+}
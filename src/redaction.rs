@@ -0,0 +1,291 @@
+// SPDX-License-Identifier: MIT
+
+//! Redacted copies of DID documents and Data Integrity proofs, safe for logging, i.e.:
+//! - proof values are truncated,
+//! - private key material (e.g. the `d` member of a JWK) is dropped and
+//! - long fields (e.g. data URLs of services) are replaced by their (truncated) SHA2-256 hash.
+//!
+//! The `Debug` implementation of both [`DidDoc`] and `DataIntegrityProof` relies on it,
+//! so a full proof never ends up in the logs by accident.
+
+use crate::did_doc::{DidDoc, Jwk, VerificationMethod};
+#[cfg(feature = "vc-data-integrity")]
+use crate::vc_data_integrity::DataIntegrityProof;
+use core::fmt;
+use serde_json::{Map, Value};
+use sha2::{Digest as _, Sha256};
+
+/// The number of leading characters of a proof value kept by redaction.
+pub const REDACTED_PROOF_VALUE_LENGTH: usize = 8;
+
+/// The maximum length (in characters) of a field kept as is by redaction, whereas any longer one is hashed.
+pub const MAX_UNREDACTED_FIELD_LENGTH: usize = 128;
+
+/// The number of leading hex digits of the SHA2-256 hash replacing a long field.
+const REDACTED_HASH_LENGTH: usize = 16;
+
+/// The members of a JWK featuring private key material, as registered by https://www.rfc-editor.org/rfc/rfc7518#section-7.5.
+const PRIVATE_JWK_MEMBERS: [&str; 8] = ["d", "p", "q", "dp", "dq", "qi", "oth", "k"];
+
+/// Delivers a redacted copy of the supplied (arbitrary) JSON `value`, i.e.:
+/// - any `proofValue` is truncated,
+/// - any private member of a JWK (i.e. of a JSON object featuring `kty`) is dropped and
+/// - any string longer than [`MAX_UNREDACTED_FIELD_LENGTH`] is replaced by its (truncated) hash.
+#[inline]
+#[expect(clippy::pattern_type_mismatch, reason = "..")]
+pub fn redact_json(value: &Value) -> Value {
+    match value {
+        Value::String(text) => Value::String(redact_long_field(text)),
+        Value::Array(elements) => Value::Array(elements.iter().map(redact_json).collect()),
+        Value::Object(members) => {
+            let is_jwk = members.contains_key("kty");
+            Value::Object(
+                members
+                    .iter()
+                    .filter(|&(name, _)| !is_jwk || !PRIVATE_JWK_MEMBERS.contains(&name.as_str()))
+                    .map(|(name, member)| {
+                        let redacted = match member.as_str() {
+                            Some(proof_value) if name == "proofValue" => {
+                                Value::String(truncate(proof_value))
+                            }
+                            Some(_) | None => redact_json(member),
+                        };
+                        (name.to_owned(), redacted)
+                    })
+                    .collect::<Map<String, Value>>(),
+            )
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) => value.to_owned(),
+    }
+}
+
+/// Truncates the supplied (proof) `value` to its first [`REDACTED_PROOF_VALUE_LENGTH`] characters.
+#[cfg_attr(
+    not(feature = "vc-data-integrity"),
+    expect(clippy::single_call_fn, reason = "..")
+)]
+fn truncate(value: &str) -> String {
+    if value.chars().count() <= REDACTED_PROOF_VALUE_LENGTH {
+        return value.to_owned();
+    }
+    format!(
+        "{}...",
+        value
+            .chars()
+            .take(REDACTED_PROOF_VALUE_LENGTH)
+            .collect::<String>()
+    )
+}
+
+/// Replaces the supplied `field` by its (truncated) hash, if longer than [`MAX_UNREDACTED_FIELD_LENGTH`].
+fn redact_long_field(field: &str) -> String {
+    let len = field.chars().count();
+    if len <= MAX_UNREDACTED_FIELD_LENGTH {
+        return field.to_owned();
+    }
+    let hash = hex::encode(Sha256::digest(field.as_bytes()));
+    format!(
+        "<redacted sha256:{}... ({len} chars)>",
+        hash.get(..REDACTED_HASH_LENGTH).unwrap_or_default()
+    )
+}
+
+#[expect(clippy::single_call_fn, reason = "..")]
+fn redact_verification_method(method: &VerificationMethod) -> VerificationMethod {
+    VerificationMethod {
+        id: redact_long_field(&method.id),
+        controller: redact_long_field(&method.controller),
+        verification_type: method.verification_type.to_owned(),
+        public_key_multibase: method
+            .public_key_multibase
+            .as_deref()
+            .map(redact_long_field),
+        // any private key material is dropped by design, as Jwk features public members only
+        public_key_jwk: method.public_key_jwk.as_ref().map(|jwk| Jwk {
+            alg: jwk.alg.as_deref().map(redact_long_field),
+            kid: jwk.kid.as_deref().map(redact_long_field),
+            kty: jwk.kty.to_owned(),
+            crv: jwk.crv.to_owned(),
+            x: jwk.x.as_deref().map(redact_long_field),
+            y: jwk.y.as_deref().map(redact_long_field),
+        }),
+        attested: method.attested,
+    }
+}
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
+impl DidDoc {
+    /// Delivers a redacted copy of this DID document, safe for logging (see [`redact_json`]).
+    #[inline]
+    pub fn redacted(&self) -> Self {
+        let redact_all = |fields: &[String]| {
+            fields
+                .iter()
+                .map(|field| redact_long_field(field))
+                .collect()
+        };
+        let redact_methods = |methods: &[VerificationMethod]| {
+            methods.iter().map(redact_verification_method).collect()
+        };
+        Self {
+            context: redact_all(&self.context),
+            id: redact_long_field(&self.id),
+            verification_method: redact_methods(&self.verification_method),
+            authentication: redact_methods(&self.authentication),
+            capability_invocation: redact_methods(&self.capability_invocation),
+            capability_delegation: redact_methods(&self.capability_delegation),
+            assertion_method: redact_methods(&self.assertion_method),
+            key_agreement: redact_methods(&self.key_agreement),
+            controller: redact_all(&self.controller),
+            also_known_as: redact_all(&self.also_known_as),
+            deactivated: self.deactivated,
+            service: self.service.iter().map(redact_json).collect(),
+        }
+    }
+}
+
+impl fmt::Debug for DidDoc {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let redacted = self.redacted();
+        f.debug_struct("DidDoc")
+            .field("context", &redacted.context)
+            .field("id", &redacted.id)
+            .field("verification_method", &redacted.verification_method)
+            .field("authentication", &redacted.authentication)
+            .field("capability_invocation", &redacted.capability_invocation)
+            .field("capability_delegation", &redacted.capability_delegation)
+            .field("assertion_method", &redacted.assertion_method)
+            .field("key_agreement", &redacted.key_agreement)
+            .field("controller", &redacted.controller)
+            .field("also_known_as", &redacted.also_known_as)
+            .field("deactivated", &redacted.deactivated)
+            .field("service", &redacted.service)
+            .finish()
+    }
+}
+
+#[cfg(feature = "vc-data-integrity")]
+impl DataIntegrityProof {
+    /// Delivers a redacted copy of this proof, safe for logging, i.e. featuring a truncated `proofValue`.
+    #[inline]
+    pub fn redacted(&self) -> Self {
+        Self {
+            proof_type: self.proof_type.to_owned(),
+            crypto_suite: self.crypto_suite.to_owned(),
+            crypto_suite_type: self.crypto_suite_type.to_owned(),
+            created: self.created,
+            verification_method: redact_long_field(&self.verification_method),
+            proof_purpose: self.proof_purpose.to_owned(),
+            context: self.context.to_owned(),
+            challenge: self.challenge.as_deref().map(redact_long_field),
+            proof_value: truncate(&self.proof_value),
+            extensions: self.extensions.to_owned(),
+        }
+    }
+}
+
+#[cfg(feature = "vc-data-integrity")]
+impl fmt::Debug for DataIntegrityProof {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let redacted = self.redacted();
+        f.debug_struct("DataIntegrityProof")
+            .field("proof_type", &redacted.proof_type)
+            .field("crypto_suite", &redacted.crypto_suite)
+            .field("crypto_suite_type", &redacted.crypto_suite_type)
+            .field("created", &redacted.created)
+            .field("verification_method", &redacted.verification_method)
+            .field("proof_purpose", &redacted.proof_purpose)
+            .field("context", &redacted.context)
+            .field("challenge", &redacted.challenge)
+            .field("proof_value", &redacted.proof_value)
+            .field("extensions", &redacted.extensions)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+mod test {
+    use super::*;
+    use rstest::rstest;
+    use serde_json::json;
+
+    const PROOF_VALUE: &str =
+        "z3FXQjecWufY46yg5abdVZsXqLhxhueuSoZgNSARiKBk9czhSePTFehP8c3PGfb6a22gkfUKods5D2UpwrtqjEmqg";
+
+    #[rstest]
+    fn test_redact_json() {
+        let long_endpoint = format!("data:text/plain,{}", "a".repeat(200));
+        let redacted = redact_json(&json!({
+            "proof": [{"type": "DataIntegrityProof", "proofValue": PROOF_VALUE}],
+            "publicKeyJwk": {"kty": "OKP", "crv": "Ed25519", "x": "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo", "d": "secret"},
+            "d": "not a JWK member",
+            "serviceEndpoint": long_endpoint,
+            "count": 1,
+        }));
+        assert_eq!(
+            redacted.pointer("/proof/0/proofValue"),
+            Some(&json!("z3FXQjec..."))
+        );
+        assert!(redacted.pointer("/publicKeyJwk/d").is_none());
+        assert!(redacted.pointer("/publicKeyJwk/x").is_some());
+        assert_eq!(redacted.get("d"), Some(&json!("not a JWK member")));
+        let endpoint = redacted.get("serviceEndpoint").unwrap().as_str().unwrap();
+        assert!(endpoint.starts_with("<redacted sha256:"));
+        assert!(endpoint.ends_with("(216 chars)>"));
+        assert_eq!(redacted.get("count"), Some(&json!(1)));
+    }
+
+    #[rstest]
+    fn test_did_doc_redacted() {
+        let did_doc = DidDoc::from_json(
+            &json!({
+                "@context": ["https://www.w3.org/ns/did/v1"],
+                "id": "did:example:123",
+                "verificationMethod": [],
+                "service": [{"id": "#files", "type": "LinkedDomains", "serviceEndpoint": "x".repeat(300)}],
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let redacted = did_doc.redacted();
+        assert_eq!(redacted.id, did_doc.id);
+        assert!(redacted
+            .service
+            .first()
+            .and_then(|service| service.get("serviceEndpoint"))
+            .and_then(Value::as_str)
+            .unwrap()
+            .starts_with("<redacted sha256:"));
+        assert!(!format!("{did_doc:?}").contains(&"x".repeat(300)));
+    }
+
+    #[cfg(feature = "vc-data-integrity")]
+    #[rstest]
+    fn test_data_integrity_proof_redacted() {
+        let proof = DataIntegrityProof::from(
+            json!([{
+                "type": "DataIntegrityProof",
+                "cryptosuite": "eddsa-jcs-2022",
+                "created": "2025-01-01T00:00:00Z",
+                "verificationMethod": "did:key:z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2#z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2",
+                "proofPurpose": "assertionMethod",
+                "proofValue": PROOF_VALUE,
+            }])
+            .to_string(),
+        )
+        .unwrap();
+        assert_eq!(proof.redacted().proof_value, "z3FXQjec...");
+        assert_eq!(proof.proof_value, PROOF_VALUE); // the original is left intact
+
+        let debug = format!("{proof:?}");
+        assert!(debug.contains("z3FXQjec..."));
+        assert!(!debug.contains(PROOF_VALUE));
+    }
+}
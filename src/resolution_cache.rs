@@ -0,0 +1,452 @@
+// SPDX-License-Identifier: MIT
+
+//! Pluggable persistence of resolution results (e.g. DID logs), so resolver caches may survive process restarts
+//! (e.g. to meet the cold-start budget of a mobile wallet).
+//!
+//! Any [`ResolutionCacheStore`] implementation maps a DID onto its (serialized) resolution result, each entry featuring
+//! its own time-to-live. Besides the [`InMemoryResolutionCacheStore`], a SQLite-backed [`SqliteResolutionCacheStore`]
+//! is available, as long as the crate is built using the (non-default) `sqlite-cache` feature.
+//!
+//! Furthermore, a [`VerifiedLogCache`] holds fully verified resolution results keyed by the content hash of the DID log,
+//! so byte-identical DID logs (e.g. as repeatedly received by gateways from upstream mirrors) are neither re-parsed nor re-verified.
+
+use crate::did_resolver::DidResolver;
+use crate::errors::{DidResolverError, DidSidekicksError};
+use sha2::{Digest as _, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A persistence layer of a caching resolver.
+///
+/// The trait is `dyn` compatible, as elaborated at https://doc.rust-lang.org/reference/items/traits.html#dyn-compatibility.
+pub trait ResolutionCacheStore: Send + Sync {
+    /// Delivers the resolution result cached for the supplied `did`, unless missing or expired.
+    fn get(&self, did: &str) -> Result<Option<String>, DidSidekicksError>;
+
+    /// Caches the supplied resolution result (`value`) for the supplied `did`, thus replacing the previously cached one (if any).
+    /// The entry expires as soon as `ttl_seconds` elapse.
+    fn put(&self, did: &str, value: &str, ttl_seconds: u64) -> Result<(), DidSidekicksError>;
+
+    /// Removes the resolution result cached for the supplied `did` (if any).
+    fn evict(&self, did: &str) -> Result<(), DidSidekicksError>;
+}
+
+/// The number of seconds elapsed since the UNIX epoch.
+fn now_seconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// A cached resolution result along with its expiry (in seconds since the UNIX epoch).
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    value: String,
+    expires_at: u64,
+}
+
+/// A (thread-safe) [`ResolutionCacheStore`] holding all entries in memory, hence not surviving process restarts.
+///
+/// Expired entries are removed lazily, i.e. as soon as requested.
+#[derive(Debug, Default)]
+pub struct InMemoryResolutionCacheStore {
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl InMemoryResolutionCacheStore {
+    /// The only (empty) constructor of the type.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ResolutionCacheStore for InMemoryResolutionCacheStore {
+    #[inline]
+    fn get(&self, did: &str) -> Result<Option<String>, DidSidekicksError> {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let value = match entries.get(did) {
+            Some(entry) if entry.expires_at > now_seconds() => Some(entry.value.to_owned()),
+            Some(_) => {
+                entries.remove(did);
+                None
+            }
+            None => None,
+        };
+        drop(entries);
+        Ok(value)
+    }
+
+    #[inline]
+    fn put(&self, did: &str, value: &str, ttl_seconds: u64) -> Result<(), DidSidekicksError> {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(
+                did.to_owned(),
+                CacheEntry {
+                    value: value.to_owned(),
+                    expires_at: now_seconds().saturating_add(ttl_seconds),
+                },
+            );
+        Ok(())
+    }
+
+    #[inline]
+    fn evict(&self, did: &str) -> Result<(), DidSidekicksError> {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(did);
+        Ok(())
+    }
+}
+
+/// A [`ResolutionCacheStore`] persisting all entries in a SQLite database (file), hence surviving process restarts.
+///
+/// Expired entries are removed lazily, i.e. as soon as requested.
+#[cfg(feature = "sqlite-cache")]
+#[derive(Debug)]
+pub struct SqliteResolutionCacheStore {
+    connection: Mutex<rusqlite::Connection>,
+}
+
+#[cfg(feature = "sqlite-cache")]
+impl SqliteResolutionCacheStore {
+    /// Opens (or creates) the SQLite database at the supplied `path`, thus creating the required table (if missing).
+    ///
+    /// Any failure is denoted by [`DidSidekicksError::InternalError`].
+    #[inline]
+    pub fn open(path: &str) -> Result<Self, DidSidekicksError> {
+        Self::init(rusqlite::Connection::open(path).map_err(sqlite_error)?)
+    }
+
+    /// Creates a (transient) in-memory SQLite database, e.g. for testing purposes.
+    #[inline]
+    pub fn open_in_memory() -> Result<Self, DidSidekicksError> {
+        Self::init(rusqlite::Connection::open_in_memory().map_err(sqlite_error)?)
+    }
+
+    fn init(connection: rusqlite::Connection) -> Result<Self, DidSidekicksError> {
+        connection
+            .execute(
+                "CREATE TABLE IF NOT EXISTS resolution_cache (did TEXT PRIMARY KEY, value TEXT NOT NULL, expires_at INTEGER NOT NULL)",
+                [],
+            )
+            .map_err(sqlite_error)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, rusqlite::Connection> {
+        self.connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(feature = "sqlite-cache")]
+impl ResolutionCacheStore for SqliteResolutionCacheStore {
+    #[inline]
+    fn get(&self, did: &str) -> Result<Option<String>, DidSidekicksError> {
+        use rusqlite::OptionalExtension as _;
+
+        let connection = self.lock();
+        let now = i64::try_from(now_seconds()).unwrap_or(i64::MAX);
+        connection
+            .execute(
+                "DELETE FROM resolution_cache WHERE did = ?1 AND expires_at <= ?2",
+                rusqlite::params![did, now],
+            )
+            .map_err(sqlite_error)?;
+        connection
+            .query_row(
+                "SELECT value FROM resolution_cache WHERE did = ?1",
+                rusqlite::params![did],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(sqlite_error)
+    }
+
+    #[inline]
+    fn put(&self, did: &str, value: &str, ttl_seconds: u64) -> Result<(), DidSidekicksError> {
+        let expires_at =
+            i64::try_from(now_seconds().saturating_add(ttl_seconds)).unwrap_or(i64::MAX);
+        self.lock()
+            .execute(
+                "INSERT OR REPLACE INTO resolution_cache (did, value, expires_at) VALUES (?1, ?2, ?3)",
+                rusqlite::params![did, value, expires_at],
+            )
+            .map_err(sqlite_error)?;
+        Ok(())
+    }
+
+    #[inline]
+    fn evict(&self, did: &str) -> Result<(), DidSidekicksError> {
+        self.lock()
+            .execute(
+                "DELETE FROM resolution_cache WHERE did = ?1",
+                rusqlite::params![did],
+            )
+            .map_err(sqlite_error)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite-cache")]
+fn sqlite_error(err: rusqlite::Error) -> DidSidekicksError {
+    DidSidekicksError::InternalError(format!("SQLite resolution cache failure: {err}"))
+}
+
+/// The maximum number of resolution results held by a [`VerifiedLogCache`] (by default).
+pub const DEFAULT_VERIFIED_LOG_CACHE_CAPACITY: usize = 256;
+
+/// A bounded (thread-safe) cache of fully verified resolution results, keyed by the SHA2-256 hash of the DID along with
+/// the DID log text. Once full, the least recently used result is evicted.
+///
+/// Only successful resolutions are cached, so a failing DID log is re-verified on each attempt.
+pub struct VerifiedLogCache<R> {
+    capacity: usize,
+    state: Mutex<VerifiedLogCacheState<R>>,
+}
+
+/// The (mutable) state of a [`VerifiedLogCache`].
+struct VerifiedLogCacheState<R> {
+    /// Each resolution result along with the tick of its most recent use.
+    entries: HashMap<[u8; 32], (Arc<R>, u64)>,
+    /// A monotonic counter denoting the most recent use.
+    tick: u64,
+}
+
+impl<R: DidResolver> Default for VerifiedLogCache<R> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(DEFAULT_VERIFIED_LOG_CACHE_CAPACITY)
+    }
+}
+
+impl<R: DidResolver> VerifiedLogCache<R> {
+    /// Creates an empty cache holding at most `capacity` resolution results. A zero `capacity` disables caching.
+    #[inline]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(VerifiedLogCacheState {
+                entries: HashMap::new(),
+                tick: 0,
+            }),
+        }
+    }
+
+    /// Delivers the resolution result cached for the supplied `did` and (byte-identical) `did_log`, if any.
+    /// Otherwise, the `did_log` is resolved (hence fully verified) by the supplied `resolve` function and its result cached.
+    #[inline]
+    pub fn resolve_cached<F>(
+        &self,
+        did: &str,
+        did_log: &str,
+        resolve: F,
+    ) -> Result<Arc<R>, DidResolverError>
+    where
+        F: FnOnce(&str, &str) -> Result<R, DidResolverError>,
+    {
+        if self.capacity == 0 {
+            return resolve(did, did_log).map(Arc::new);
+        }
+
+        let key: [u8; 32] = Sha256::new()
+            .chain_update(did.as_bytes())
+            .chain_update([0]) // the separator, as a DID never contains a NUL character
+            .chain_update(did_log.as_bytes())
+            .finalize()
+            .into();
+
+        {
+            let mut state = self.lock();
+            let tick = state.next_tick();
+            if let Some(entry) = state.entries.get_mut(&key) {
+                entry.1 = tick;
+                return Ok(Arc::clone(&entry.0));
+            }
+        }
+
+        // resolve without holding the lock, as verification may take a while
+        let resolved = Arc::new(resolve(did, did_log)?);
+
+        let mut state = self.lock();
+        if !state.entries.contains_key(&key) && state.entries.len() >= self.capacity {
+            let least_recently_used = state
+                .entries
+                .iter()
+                .min_by_key(|&(_, &(_, last_used))| last_used)
+                .map(|(lru_key, _)| *lru_key);
+            if let Some(lru_key) = least_recently_used {
+                state.entries.remove(&lru_key);
+            }
+        }
+        let tick = state.next_tick();
+        state.entries.insert(key, (Arc::clone(&resolved), tick));
+        drop(state);
+        Ok(resolved)
+    }
+
+    /// The number of resolution results currently held by the cache.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Whether the cache holds no resolution results at all.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all resolution results held by the cache.
+    #[inline]
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VerifiedLogCacheState<R>> {
+        // a poisoned cache is still a perfectly valid cache
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<R> VerifiedLogCacheState<R> {
+    fn next_tick(&mut self) -> u64 {
+        self.tick = self.tick.saturating_add(1);
+        self.tick
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+mod test {
+    use super::*;
+    use crate::did_doc::DidDoc;
+    use crate::did_method_parameters::DidMethodParameter;
+    use core::cell::Cell;
+    use rstest::rstest;
+
+    const DID: &str = "did:webvh:QmScid:example.com";
+
+    #[cfg_attr(
+        not(feature = "sqlite-cache"),
+        expect(clippy::single_call_fn, reason = "..")
+    )]
+    fn assert_store(store: &dyn ResolutionCacheStore) {
+        assert_eq!(store.get(DID).unwrap(), None);
+
+        store.put(DID, "log-1", 3600).unwrap();
+        assert_eq!(store.get(DID).unwrap(), Some("log-1".to_owned()));
+        store.put(DID, "log-2", 3600).unwrap(); // replaced
+        assert_eq!(store.get(DID).unwrap(), Some("log-2".to_owned()));
+
+        store.evict(DID).unwrap();
+        assert_eq!(store.get(DID).unwrap(), None);
+        store.evict(DID).unwrap(); // evicting a missing entry is fine
+
+        store.put(DID, "log-3", 0).unwrap(); // expires immediately
+        assert_eq!(store.get(DID).unwrap(), None);
+    }
+
+    #[rstest]
+    fn test_in_memory_store() {
+        assert_store(&InMemoryResolutionCacheStore::new());
+    }
+
+    #[cfg(feature = "sqlite-cache")]
+    #[rstest]
+    fn test_sqlite_store() {
+        assert_store(&SqliteResolutionCacheStore::open_in_memory().unwrap());
+    }
+
+    /// A resolver merely remembering the DID log it was created from.
+    struct LogDidResolver {
+        did_log: String,
+    }
+    #[expect(clippy::missing_trait_methods, reason = "..")]
+    impl DidResolver for LogDidResolver {
+        fn get_did_doc_obj(&self) -> Arc<DidDoc> {
+            Arc::new(
+                DidDoc::from_json(r#"{"@context":[],"id":"","verificationMethod":[]}"#).unwrap(),
+            )
+        }
+        fn collect_did_method_parameters_map(
+            &self,
+        ) -> Result<HashMap<String, Arc<DidMethodParameter>>, DidResolverError> {
+            Ok(HashMap::new())
+        }
+    }
+
+    #[rstest]
+    fn test_verified_log_cache() {
+        let cache = VerifiedLogCache::<LogDidResolver>::new(2);
+        let resolutions = Cell::<u32>::new(0);
+        let resolve = |did: &str, did_log: &str| {
+            resolutions.set(resolutions.get().saturating_add(1));
+            if did_log.is_empty() {
+                return Err(DidResolverError::InvalidMethodSpecificId(did.to_owned()));
+            }
+            Ok(LogDidResolver {
+                did_log: did_log.to_owned(),
+            })
+        };
+
+        let resolved = cache.resolve_cached(DID, "log-1", resolve).unwrap();
+        assert_eq!(resolved.did_log, "log-1");
+        let cached = cache.resolve_cached(DID, "log-1", resolve).unwrap();
+        assert!(Arc::ptr_eq(&resolved, &cached));
+        assert_eq!(resolutions.get(), 1);
+
+        // failures are never cached
+        assert!(cache.resolve_cached(DID, "", resolve).is_err());
+        assert!(cache.resolve_cached(DID, "", resolve).is_err());
+        assert_eq!(resolutions.get(), 3);
+        assert_eq!(cache.len(), 1);
+
+        // the least recently used result (log-2) gets evicted
+        cache.resolve_cached(DID, "log-2", resolve).unwrap();
+        cache.resolve_cached(DID, "log-1", resolve).unwrap();
+        cache.resolve_cached(DID, "log-3", resolve).unwrap();
+        assert_eq!(resolutions.get(), 5);
+        assert_eq!(cache.len(), 2);
+        cache.resolve_cached(DID, "log-1", resolve).unwrap();
+        assert_eq!(resolutions.get(), 5);
+        cache.resolve_cached(DID, "log-2", resolve).unwrap();
+        assert_eq!(resolutions.get(), 6);
+
+        // keyed by the DID as well
+        cache
+            .resolve_cached("did:webvh:QmScid:example.org", "log-2", resolve)
+            .unwrap();
+        assert_eq!(resolutions.get(), 7);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[rstest]
+    fn test_verified_log_cache_disabled() {
+        let cache = VerifiedLogCache::<LogDidResolver>::new(0);
+        for _ in 0..2 {
+            cache
+                .resolve_cached(DID, "log-1", |_, did_log| {
+                    Ok(LogDidResolver {
+                        did_log: did_log.to_owned(),
+                    })
+                })
+                .unwrap();
+        }
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,307 @@
+// SPDX-License-Identifier: MIT
+
+//! A reusable conformance test-kit for any [`DidResolver`] trait implementation.
+//!
+//! The [`DidResolver`] trait alone guarantees nothing about semantics. Hence, every DID method crate
+//! building on this trait is encouraged to run the very same corpus of valid/invalid DID logs
+//! (see [`corpus`]) against its own implementation, simply by calling [`run`] in its test suite, e.g.:
+//!
+//! ```ignore
+//! #[test]
+//! fn test_resolver_conformance() {
+//!     assert_eq!(resolver_conformance::run::<MyDidResolver>(), Ok(()));
+//! }
+//! ```
+
+use crate::did_resolver::DidResolver;
+use crate::errors::DidResolverError;
+
+/// A [`DidResolver`] implementation able to take part in the conformance test-kit.
+///
+/// As the [`DidResolver`] trait says nothing about how a resolver gets created, this is the only piece of
+/// glue code required to be supplied by a DID method crate.
+pub trait ConformanceSubject: DidResolver + Sized {
+    /// Resolve the supplied `did` using the supplied DID log (in JSON Lines format).
+    fn resolve(did: &str, did_log: &str) -> Result<Self, DidResolverError>;
+}
+
+/// The outcome expected while resolving a [`ConformanceCase`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum ExpectedOutcome {
+    /// The DID log is valid and must be resolved into a DID document featuring the very same DID.
+    Resolved {
+        /// Whether the DID is expected to be deactivated by the last DID log entry.
+        deactivated: bool,
+    },
+    /// The DID log is invalid and must be rejected by the resolver (regardless of the error kind).
+    Rejected,
+}
+
+/// A single entry of the conformance test-kit [`corpus`].
+#[derive(Debug, Clone)]
+pub struct ConformanceCase {
+    /// Human-readable (and unique) name of the case.
+    pub name: &'static str,
+    /// The DID to resolve.
+    pub did: &'static str,
+    /// The DID log (in JSON Lines format) to resolve the DID from.
+    pub did_log: &'static str,
+    /// The outcome expected while resolving the DID.
+    pub expected: ExpectedOutcome,
+}
+
+/// Describes a [`ConformanceCase`] not met by the [`ConformanceSubject`] under test.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConformanceFailure {
+    /// The name of the failed [`ConformanceCase`].
+    pub case_name: String,
+    /// The reason of the failure.
+    pub reason: String,
+}
+
+const TDW_JS_DID_SINGLE_KEY: &str =
+    "did:tdw:QmXjp5qhSEvm8oXip43cDX62hZhHZdAMYv7Magy1tkffSz:example.com";
+const TDW_JS_DID_MULTIPLE_KEYS: &str =
+    "did:tdw:QmdSU7F2rF8r4m6GZK7Evi2tthfDDxhw3NppU8pJMbd2hB:example.com";
+const DIDTOOLBOX_DID: &str = "did:tdw:QmPsui8ffosRTxUBP8vJoejauqEUGvhmWe77BNo1StgLk7:identifier-reg.trust-infra.swiyu-int.admin.ch:api:v1:did:18fa7c77-9dd1-4e20-a147-fb1bec146085";
+const DIDTOOLBOX_UNHAPPY_PATH_DID: &str = "did:tdw:QmT7BM5RsM9SoaqAQKkNKHBzSEzpS2NRzT2oKaaaPYPpGr:identifier-reg.trust-infra.swiyu-int.admin.ch:api:v1:did:18fa7c77-9dd1-4e20-a147-fb1bec146085";
+const DIDTOOLBOX_EID_CONFORM_DID: &str =
+    "did:tdw:QmZf4Pb1GoPdYaZBF3Sc1nVspXef4qc816C7eBzzuXMoGk:domain.com%3A8080:path1:path2";
+
+/// Delivers the whole corpus of valid/invalid DID logs (accompanied by expected outcomes)
+/// every [`ConformanceSubject`] is expected to comply with.
+#[inline]
+pub fn corpus() -> Vec<ConformanceCase> {
+    vec![
+        ConformanceCase {
+            name: "single_update_key",
+            did: TDW_JS_DID_SINGLE_KEY,
+            did_log: include_str!("../test_data/generated_by_tdw_js/single_update_key.jsonl"),
+            expected: ExpectedOutcome::Resolved { deactivated: false },
+        },
+        ConformanceCase {
+            name: "unique_update_keys",
+            did: TDW_JS_DID_SINGLE_KEY,
+            did_log: include_str!("../test_data/generated_by_tdw_js/unique_update_keys.jsonl"),
+            expected: ExpectedOutcome::Resolved { deactivated: false },
+        },
+        ConformanceCase {
+            name: "alternate_update_keys",
+            did: TDW_JS_DID_MULTIPLE_KEYS,
+            did_log: include_str!("../test_data/generated_by_tdw_js/alternate_update_keys.jsonl"),
+            expected: ExpectedOutcome::Resolved { deactivated: false },
+        },
+        ConformanceCase {
+            name: "deactivated",
+            did: TDW_JS_DID_MULTIPLE_KEYS,
+            did_log: include_str!("../test_data/generated_by_tdw_js/deactivated.jsonl"),
+            expected: ExpectedOutcome::Resolved { deactivated: true },
+        },
+        ConformanceCase {
+            name: "v005",
+            did: DIDTOOLBOX_DID,
+            did_log: include_str!("../test_data/generated_by_didtoolbox_java/v005_did.jsonl"),
+            expected: ExpectedOutcome::Resolved { deactivated: false },
+        },
+        ConformanceCase {
+            name: "v010",
+            did: DIDTOOLBOX_DID,
+            did_log: include_str!("../test_data/generated_by_didtoolbox_java/v010_did.jsonl"),
+            expected: ExpectedOutcome::Resolved { deactivated: false },
+        },
+        ConformanceCase {
+            name: "did_doc_without_controller",
+            did: DIDTOOLBOX_EID_CONFORM_DID,
+            did_log: include_str!("../test_data/generated_by_didtoolbox_java/v_0_3_eid_conform/did_doc_without_controller.jsonl"),
+            expected: ExpectedOutcome::Resolved { deactivated: false },
+        },
+        ConformanceCase {
+            name: "empty_log",
+            did: DIDTOOLBOX_DID,
+            did_log: "",
+            expected: ExpectedOutcome::Rejected,
+        },
+        ConformanceCase {
+            name: "did_mismatch",
+            did: TDW_JS_DID_SINGLE_KEY,
+            did_log: include_str!("../test_data/generated_by_didtoolbox_java/v005_did.jsonl"),
+            expected: ExpectedOutcome::Rejected,
+        },
+        ConformanceCase {
+            name: "already_deactivated",
+            did: TDW_JS_DID_MULTIPLE_KEYS,
+            did_log: include_str!("../test_data/generated_by_tdw_js/already_deactivated.jsonl"),
+            expected: ExpectedOutcome::Rejected,
+        },
+        ConformanceCase {
+            name: "not_authorized",
+            did: TDW_JS_DID_SINGLE_KEY,
+            did_log: include_str!("../test_data/generated_by_tdw_js/unhappy_path/not_authorized.jsonl"),
+            expected: ExpectedOutcome::Rejected,
+        },
+        ConformanceCase {
+            name: "descending_version_datetime",
+            did: DIDTOOLBOX_UNHAPPY_PATH_DID,
+            did_log: include_str!("../test_data/generated_by_didtoolbox_java/unhappy_path/descending_version_datetime_did.jsonl"),
+            expected: ExpectedOutcome::Rejected,
+        },
+        ConformanceCase {
+            name: "inconsecutive_version_numbers",
+            did: DIDTOOLBOX_UNHAPPY_PATH_DID,
+            did_log: include_str!("../test_data/generated_by_didtoolbox_java/unhappy_path/inconsecutive_version_numbers_did.jsonl"),
+            expected: ExpectedOutcome::Rejected,
+        },
+        ConformanceCase {
+            name: "invalid_initial_version_number",
+            did: DIDTOOLBOX_UNHAPPY_PATH_DID,
+            did_log: include_str!("../test_data/generated_by_didtoolbox_java/unhappy_path/invalid_initial_version_number_did.jsonl"),
+            expected: ExpectedOutcome::Rejected,
+        },
+        ConformanceCase {
+            name: "version_time_in_the_future",
+            did: DIDTOOLBOX_UNHAPPY_PATH_DID,
+            did_log: include_str!("../test_data/generated_by_didtoolbox_java/unhappy_path/version_time_in_the_future_did.jsonl"),
+            expected: ExpectedOutcome::Rejected,
+        },
+    ]
+}
+
+/// Resolve a single [`ConformanceCase`] using the [`ConformanceSubject`] under test
+/// and check the outcome against the expected one.
+#[inline]
+pub fn check<R: ConformanceSubject>(case: &ConformanceCase) -> Result<(), ConformanceFailure> {
+    let failure = |reason: String| ConformanceFailure {
+        case_name: case.name.to_owned(),
+        reason,
+    };
+
+    match (R::resolve(case.did, case.did_log), case.expected) {
+        (Ok(_), ExpectedOutcome::Rejected) => Err(failure(
+            "the DID log was expected to be rejected, but got resolved".to_owned(),
+        )),
+        (Err(_), ExpectedOutcome::Rejected) => Ok(()),
+        (Err(err), ExpectedOutcome::Resolved { .. }) => Err(failure(format!(
+            "the DID log was expected to be resolved, but got rejected: {err}"
+        ))),
+        (Ok(resolver), ExpectedOutcome::Resolved { deactivated }) => {
+            let did_doc = resolver.get_did_doc_obj();
+            if did_doc.get_id() != case.did {
+                return Err(failure(format!(
+                    "the resolved DID document features a different DID: {}",
+                    did_doc.get_id()
+                )));
+            }
+
+            let params = resolver
+                .collect_did_method_parameters_map()
+                .map_err(|err| failure(format!("failed to collect DID method parameters: {err}")))?;
+
+            let is_deactivated = did_doc.get_deactivated()
+                || params
+                    .get("deactivated")
+                    .and_then(|param| param.get_bool_value())
+                    .unwrap_or(false);
+            if is_deactivated != deactivated {
+                return Err(failure(format!(
+                    "the resolved DID was expected to be deactivated={deactivated}, but got deactivated={is_deactivated}"
+                )));
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Run the whole conformance test-kit [`corpus`] against the [`ConformanceSubject`] under test.
+///
+/// All cases are always run, so all failures (if any) are returned at once.
+#[inline]
+pub fn run<R: ConformanceSubject>() -> Result<(), Vec<ConformanceFailure>> {
+    let failures = corpus()
+        .iter()
+        .filter_map(|case| check::<R>(case).err())
+        .collect::<Vec<ConformanceFailure>>();
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures)
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::did_doc::DidDoc;
+    use crate::did_method_parameters::DidMethodParameter;
+    use rstest::rstest;
+    use serde_json::Value;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    /// A resolver rejecting just about anything.
+    struct RejectingDidResolver;
+    #[expect(clippy::missing_trait_methods, reason = "..")]
+    impl DidResolver for RejectingDidResolver {
+        fn get_did_doc_obj(&self) -> Arc<DidDoc> {
+            Arc::new(
+                DidDoc::from_json(r#"{"@context":[],"id":"","verificationMethod":[]}"#).unwrap(),
+            )
+        }
+        fn collect_did_method_parameters_map(
+            &self,
+        ) -> Result<HashMap<String, Arc<DidMethodParameter>>, DidResolverError> {
+            Ok(HashMap::new())
+        }
+    }
+    impl ConformanceSubject for RejectingDidResolver {
+        fn resolve(did: &str, _did_log: &str) -> Result<Self, DidResolverError> {
+            Err(DidResolverError::InvalidMethodSpecificId(did.to_owned()))
+        }
+    }
+
+    #[rstest]
+    fn test_corpus_logs_are_json_lines() {
+        let corpus = corpus();
+        assert!(!corpus.is_empty());
+        corpus.iter().for_each(|case| {
+            case.did_log
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .for_each(|line| {
+                    assert!(
+                        serde_json::from_str::<Value>(line).is_ok_and(|val| val.is_array()),
+                        "{}",
+                        case.name
+                    );
+                });
+        });
+    }
+
+    #[rstest]
+    fn test_corpus_case_names_are_unique() {
+        let corpus = corpus();
+        let mut names = corpus.iter().map(|case| case.name).collect::<Vec<&str>>();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), corpus.len());
+    }
+
+    #[rstest]
+    fn test_run_reports_all_failures() {
+        let failures = run::<RejectingDidResolver>().unwrap_err();
+        let expected_failures = corpus()
+            .iter()
+            .filter(|case| matches!(case.expected, ExpectedOutcome::Resolved { .. }))
+            .count();
+        assert_eq!(failures.len(), expected_failures);
+    }
+}
@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: MIT
+
+//! Helpers capable of computing and verifying content hashes of external resources referenced by DID documents
+//! (e.g. service endpoints, logos, schemas or status lists), thus pinning such resources.
+//!
+//! Both the `digestMultibase` (https://www.w3.org/TR/vc-data-integrity/#resource-integrity) and
+//! the `digestSRI` (https://www.w3.org/TR/SRI/#the-integrity-attribute) representations are supported.
+
+use crate::errors::DidSidekicksError;
+use crate::jcs_sha256_hasher::JcsSha256Hasher;
+use crate::metrics::count_hash_operation;
+use crate::multibase::MultibaseEncoderDecoder;
+use crate::multihash::Multihash;
+use crate::panic_boundary::catch_panic;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use sha2::{Digest as _, Sha256, Sha384, Sha512};
+
+/// The hash algorithm (prefix) used while computing `digestSRI` values.
+pub const SRI_HASH_ALGORITHM_SHA256: &str = "sha256";
+
+/// Computes the `digestMultibase` value of the supplied `content`, i.e. `base58btc(multihash(content, sha2-256))`.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn compute_digest_multibase(content: Vec<u8>) -> String {
+    MultibaseEncoderDecoder::default()
+        .encode_base58btc(&JcsSha256Hasher::default().encode_multihash_bytes(&content))
+}
+
+/// Computes the `digestSRI` value of the supplied `content`, e.g. `sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=`.
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn compute_digest_sri(content: Vec<u8>) -> String {
+    count_hash_operation("digest_sri");
+    format!(
+        "{SRI_HASH_ALGORITHM_SHA256}-{}",
+        STANDARD.encode(Sha256::digest(&content))
+    )
+}
+
+/// Verifies the supplied `content` against the supplied `digest_multibase` value.
+///
+/// Returns `false` if the content does not match, whereas a malformed `digest_multibase`
+/// is denoted by [`DidSidekicksError::DeserializationFailed`].
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn verify_digest_multibase(
+    content: Vec<u8>,
+    digest_multibase: String,
+) -> Result<bool, DidSidekicksError> {
+    catch_panic(
+        || {
+            let decoded = MultibaseEncoderDecoder::default().decode_to_vec(&digest_multibase)?;
+            let multihash = Multihash::from_bytes(&decoded).map_err(|err| {
+                DidSidekicksError::DeserializationFailed(format!(
+                    "Unsupported multihash in digestMultibase '{digest_multibase}': {err}"
+                ))
+            })?;
+
+            Ok(multihash.matches(&content))
+        },
+        DidSidekicksError::InternalError,
+    )
+}
+
+/// Verifies the supplied `content` against the supplied `digest_sri` value.
+///
+/// The value may consist of several (whitespace-separated) hashes, each prefixed by any of
+/// `sha256`, `sha384` or `sha512` hash algorithm. As suggested by https://www.w3.org/TR/SRI/#agility,
+/// only the hashes featuring the strongest hash algorithm are taken into account, whereas the content must match any of them.
+///
+/// Returns `false` if the content does not match, whereas a malformed `digest_sri`
+/// is denoted by [`DidSidekicksError::DeserializationFailed`].
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn verify_digest_sri(content: Vec<u8>, digest_sri: String) -> Result<bool, DidSidekicksError> {
+    catch_panic(
+        || {
+            let mut strongest: Vec<(u8, Vec<u8>)> = Vec::new();
+            for token in digest_sri.split_whitespace() {
+                // any options (e.g. "sha256-...?foo") are ignored, as mandated by the spec
+                let hash = token.split('?').next().unwrap_or_default();
+                let Some((algorithm, encoded)) = hash.split_once('-') else {
+                    return Err(malformed_sri(&digest_sri));
+                };
+                let strength = match algorithm {
+                    "sha256" => 1,
+                    "sha384" => 2,
+                    "sha512" => 3,
+                    _ => return Err(malformed_sri(&digest_sri)),
+                };
+                let decoded = STANDARD
+                    .decode(encoded)
+                    .map_err(|_err| malformed_sri(&digest_sri))?;
+
+                if strongest
+                    .first()
+                    .is_some_and(|&(max_strength, _)| strength < max_strength)
+                {
+                    continue;
+                }
+                if strongest
+                    .first()
+                    .is_some_and(|&(max_strength, _)| strength > max_strength)
+                {
+                    strongest.clear();
+                }
+                strongest.push((strength, decoded));
+            }
+
+            let Some(&(strength, _)) = strongest.first() else {
+                return Err(malformed_sri(&digest_sri));
+            };
+
+            count_hash_operation("digest_sri");
+            let digest = match strength {
+                1 => Sha256::digest(&content).to_vec(),
+                2 => Sha384::digest(&content).to_vec(),
+                _ => Sha512::digest(&content).to_vec(),
+            };
+            Ok(strongest.iter().any(|expected| expected.1 == digest))
+        },
+        DidSidekicksError::InternalError,
+    )
+}
+
+fn malformed_sri(digest_sri: &str) -> DidSidekicksError {
+    DidSidekicksError::DeserializationFailed(format!(
+        "Malformed digestSRI '{digest_sri}' (e.g. 'sha256-<base64>' expected)"
+    ))
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+
+    const CONTENT: &[u8] = b"alert('Hello, world.');";
+
+    #[rstest]
+    fn test_digest_multibase() {
+        let digest = compute_digest_multibase(CONTENT.to_vec());
+        assert!(digest.starts_with("zQm"));
+        assert!(verify_digest_multibase(CONTENT.to_vec(), digest.to_owned()).unwrap());
+        assert!(!verify_digest_multibase(b"tampered".to_vec(), digest).unwrap());
+    }
+
+    #[rstest]
+    #[case(
+        // Example taken from https://www.w3.org/TR/SRI/#the-integrity-attribute
+        "sha384-H8BRh8j48O9oYatfu5AZzq6A9RINhZO5H16dQZngK7T62em8MUt1FLm52t+eX6xO",
+        true
+    )]
+    #[case(
+        "sha256-qznLcsROx4GACP2dm0UCKCzCG+HiZ1guq6ZZDob/Tng= sha384-H8BRh8j48O9oYatfu5AZzq6A9RINhZO5H16dQZngK7T62em8MUt1FLm52t+eX6xO",
+        true
+    )]
+    #[case(
+        // only the strongest algorithm is taken into account
+        "sha256-qznLcsROx4GACP2dm0UCKCzCG+HiZ1guq6ZZDob/Tng= sha512-AAAA",
+        false
+    )]
+    fn test_verify_digest_sri(#[case] digest_sri: &str, #[case] expected: bool) {
+        assert_eq!(
+            verify_digest_sri(CONTENT.to_vec(), digest_sri.to_owned()).unwrap(),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn test_compute_digest_sri() {
+        let digest = compute_digest_sri(CONTENT.to_vec());
+        assert_eq!(digest, "sha256-qznLcsROx4GACP2dm0UCKCzCG+HiZ1guq6ZZDob/Tng=");
+        assert!(verify_digest_sri(CONTENT.to_vec(), digest).unwrap());
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("md5-AAAA")]
+    #[case("sha256")]
+    #[case("sha256-not base64!")]
+    fn test_verify_digest_sri_malformed(#[case] digest_sri: &str) {
+        assert_error(
+            verify_digest_sri(CONTENT.to_vec(), digest_sri.to_owned()),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "Malformed digestSRI",
+        );
+    }
+
+    #[rstest]
+    fn test_verify_digest_multibase_malformed() {
+        assert_error(
+            verify_digest_multibase(CONTENT.to_vec(), "QmInvalid".to_owned()),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "",
+        );
+    }
+}
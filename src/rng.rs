@@ -0,0 +1,187 @@
+// SPDX-License-Identifier: MIT
+
+//! The (globally configured) source of randomness used by key generation (see [`crate::ed25519::Ed25519KeyPair::generate`]).
+//!
+//! The CSPRNG of the operating system is used by default. For the sake of reproducible fixtures
+//! (e.g. the very same artifacts generated by the CI of each of the bindings), a deterministic RNG
+//! (ChaCha20 seeded by a fixed 32-byte seed) may be opted into, as long as the crate is built using
+//! the (non-default) `deterministic-rng` feature.
+//!
+//! CAUTION Never enable the `deterministic-rng` feature in production builds,
+//! as any key generated deterministically is only as secret as the seed it is derived from.
+
+use crate::errors::DidSidekicksError;
+use crate::panic_boundary::catch_panic;
+use rand::rngs::OsRng;
+use rand::RngCore as _;
+#[cfg(feature = "deterministic-rng")]
+use rand::SeedableRng as _;
+#[cfg(feature = "deterministic-rng")]
+use rand_chacha::ChaCha20Rng;
+use std::sync::{Mutex, PoisonError};
+
+/// The length (in bytes) of the seed of a deterministic RNG.
+pub const RNG_SEED_LENGTH: usize = 32;
+
+static GLOBAL_RNG_SOURCE: Mutex<RngSource> = Mutex::new(RngSource::Os);
+
+/// A source of randomness.
+#[derive(Debug)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum RngSource {
+    /// The CSPRNG of the operating system (default).
+    Os,
+    /// A deterministic ChaCha20 RNG, intended for reproducible fixtures only.
+    #[cfg(feature = "deterministic-rng")]
+    Deterministic(Box<ChaCha20Rng>),
+}
+
+impl RngSource {
+    /// Creates a deterministic ChaCha20 RNG seeded by the supplied `seed`,
+    /// hence the very same sequence of random bytes is delivered for the very same `seed`.
+    #[cfg(feature = "deterministic-rng")]
+    #[inline]
+    pub fn deterministic(seed: [u8; RNG_SEED_LENGTH]) -> Self {
+        Self::Deterministic(Box::new(ChaCha20Rng::from_seed(seed)))
+    }
+
+    /// Fills the supplied `dest` with random bytes.
+    #[inline]
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match *self {
+            Self::Os => OsRng.fill_bytes(dest),
+            #[cfg(feature = "deterministic-rng")]
+            Self::Deterministic(ref mut rng) => rng.fill_bytes(dest),
+        }
+    }
+}
+
+/// Configures the supplied `source` globally, thus replacing the previously configured one.
+#[inline]
+pub fn set_rng_source(source: RngSource) {
+    *GLOBAL_RNG_SOURCE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = source;
+}
+
+/// Fills the supplied `dest` with random bytes delivered by the globally configured [`RngSource`].
+#[cfg_attr(
+    not(any(feature = "key-encryption", feature = "bip39")),
+    expect(clippy::single_call_fn, reason = "..")
+)]
+pub(crate) fn fill_random_bytes(dest: &mut [u8]) {
+    GLOBAL_RNG_SOURCE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .fill_bytes(dest);
+}
+
+/// Configures a deterministic (ChaCha20) RNG seeded by the supplied 32-byte `seed` globally, for the sake of reproducible fixtures.
+///
+/// Unless the crate is built using the `deterministic-rng` feature, [`DidSidekicksError::InternalError`] is returned.
+/// A seed of any other length is denoted by [`DidSidekicksError::DeserializationFailed`].
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn set_deterministic_rng_seed(seed: Vec<u8>) -> Result<(), DidSidekicksError> {
+    catch_panic(
+        || {
+            let seed_bytes: [u8; RNG_SEED_LENGTH] =
+                seed.try_into().map_err(|rejected: Vec<u8>| {
+                    DidSidekicksError::DeserializationFailed(format!(
+                        "Invalid RNG seed length {} (expected {RNG_SEED_LENGTH})",
+                        rejected.len()
+                    ))
+                })?;
+            deterministic_rng_source(seed_bytes).map(set_rng_source)
+        },
+        DidSidekicksError::InternalError,
+    )
+}
+
+/// Configures the CSPRNG of the operating system globally (again), i.e. the default [`RngSource`].
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn reset_rng_source() {
+    set_rng_source(RngSource::Os);
+}
+
+#[cfg(feature = "deterministic-rng")]
+#[expect(
+    clippy::unnecessary_wraps,
+    reason = "aligned with the non-deterministic-rng variant"
+)]
+#[expect(clippy::single_call_fn, reason = "..")]
+fn deterministic_rng_source(seed: [u8; RNG_SEED_LENGTH]) -> Result<RngSource, DidSidekicksError> {
+    Ok(RngSource::deterministic(seed))
+}
+
+#[cfg(not(feature = "deterministic-rng"))]
+#[expect(clippy::single_call_fn, reason = "..")]
+fn deterministic_rng_source(_seed: [u8; RNG_SEED_LENGTH]) -> Result<RngSource, DidSidekicksError> {
+    Err(DidSidekicksError::InternalError(
+        "A deterministic RNG requires the crate to be built using the 'deterministic-rng' feature"
+            .to_owned(),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_os_rng_source() {
+        let mut first: [u8; RNG_SEED_LENGTH] = [0; RNG_SEED_LENGTH];
+        let mut second: [u8; RNG_SEED_LENGTH] = [0; RNG_SEED_LENGTH];
+        RngSource::Os.fill_bytes(&mut first);
+        RngSource::Os.fill_bytes(&mut second);
+        assert_ne!(first, second);
+    }
+
+    #[rstest]
+    fn test_invalid_seed_length() {
+        assert_error(
+            set_deterministic_rng_seed(vec![0; 31]),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "Invalid RNG seed length 31",
+        );
+    }
+
+    #[cfg(feature = "deterministic-rng")]
+    #[rstest]
+    fn test_deterministic_rng_source() {
+        use crate::ed25519::{Ed25519KeyPair, MultiBaseConverter as _};
+
+        let generate = |seed: [u8; RNG_SEED_LENGTH]| {
+            let mut rng = RngSource::deterministic(seed);
+            core::iter::repeat_with(|| {
+                Ed25519KeyPair::generate_with(&mut rng)
+                    .verifying_key
+                    .to_multibase()
+            })
+            .take(3)
+            .collect::<Vec<_>>()
+        };
+        let keys = generate([7; RNG_SEED_LENGTH]);
+        assert_eq!(keys, generate([7; RNG_SEED_LENGTH])); // reproducible
+        assert_ne!(keys, generate([8; RNG_SEED_LENGTH]));
+        assert_ne!(keys.first(), keys.get(1));
+    }
+
+    #[cfg(not(feature = "deterministic-rng"))]
+    #[rstest]
+    fn test_deterministic_rng_unavailable() {
+        assert_error(
+            set_deterministic_rng_seed(vec![0; RNG_SEED_LENGTH]),
+            DidSidekicksErrorKind::InternalError,
+            "requires the crate to be built using the 'deterministic-rng' feature",
+        );
+    }
+}
@@ -0,0 +1,139 @@
+// SPDX-License-Identifier: MIT
+
+//! Trust registry lookups as a pluggable interface, so that the outcome of a resolution/verification
+//! may carry a trust annotation (as mandated by the Swiss trust infrastructure).
+//!
+//! The registry itself is supplied by means of a (typically foreign) [`TrustRegistry`] implementation,
+//! whereas the lookup is always optional, i.e. [`TrustStatus::Unknown`] is reported if no registry is supplied.
+
+use crate::did_doc::DidDoc;
+use std::sync::Arc;
+
+/// The role of an issuer of verifiable credentials.
+pub const TRUST_ROLE_ISSUER: &str = "issuer";
+/// The role of a verifier of verifiable presentations.
+pub const TRUST_ROLE_VERIFIER: &str = "verifier";
+/// The role of a DID log witness.
+pub const TRUST_ROLE_WITNESS: &str = "witness";
+
+/// The outcome of a trust registry lookup.
+///
+/// A UniFFI-compliant enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum TrustStatus {
+    /// The DID is registered as trusted for the role.
+    Trusted,
+    /// The DID is registered, but not (or no longer) trusted for the role.
+    NotTrusted,
+    /// The DID is not registered (or no registry was consulted at all).
+    #[default]
+    Unknown,
+}
+
+/// A trust registry, e.g. the Swiss trust registry.
+///
+/// A UniFFI-compliant trait (that may also be implemented by foreign code).
+pub trait TrustRegistry: Send + Sync {
+    /// Looks up whether the supplied `did` is trusted for the supplied `role` (e.g. [`TRUST_ROLE_ISSUER`]).
+    ///
+    /// Any lookup failure (e.g. an unreachable registry) should be reported as [`TrustStatus::Unknown`].
+    fn is_trusted(&self, did: String, role: String) -> TrustStatus;
+}
+
+/// A trust annotation of a resolved (or verified) DID.
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct TrustAnnotation {
+    pub did: String,
+    pub role: String,
+    pub status: TrustStatus,
+}
+
+/// Consults the supplied `registry` (if any) on whether the supplied `did` is trusted for the supplied `role`.
+///
+/// If no `registry` is supplied, [`TrustStatus::Unknown`] is reported.
+#[inline]
+pub fn annotate_trust(
+    did: &str,
+    role: &str,
+    registry: Option<&dyn TrustRegistry>,
+) -> TrustAnnotation {
+    TrustAnnotation {
+        did: did.to_owned(),
+        role: role.to_owned(),
+        status: registry.map_or(TrustStatus::Unknown, |reg| {
+            reg.is_trusted(did.to_owned(), role.to_owned())
+        }),
+    }
+}
+
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
+impl DidDoc {
+    /// Consults the supplied `registry` (if any) on whether the DID of this DID document is trusted for the supplied `role`.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn annotate_trust(
+        &self,
+        role: String,
+        registry: Option<Arc<dyn TrustRegistry>>,
+    ) -> TrustAnnotation {
+        annotate_trust(&self.id, &role, registry.as_deref())
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use rstest::rstest;
+    use serde_json::json;
+
+    /// Trusts `did:example:issuer` as issuer only, whereas `did:example:revoked` is no longer trusted.
+    struct MockRegistry;
+    impl TrustRegistry for MockRegistry {
+        fn is_trusted(&self, did: String, role: String) -> TrustStatus {
+            match (did.as_str(), role.as_str()) {
+                ("did:example:issuer", TRUST_ROLE_ISSUER) => TrustStatus::Trusted,
+                ("did:example:revoked", _) => TrustStatus::NotTrusted,
+                _ => TrustStatus::Unknown,
+            }
+        }
+    }
+
+    #[rstest]
+    #[case("did:example:issuer", TRUST_ROLE_ISSUER, TrustStatus::Trusted)]
+    #[case("did:example:issuer", TRUST_ROLE_VERIFIER, TrustStatus::Unknown)]
+    #[case("did:example:revoked", TRUST_ROLE_ISSUER, TrustStatus::NotTrusted)]
+    #[case("did:example:other", TRUST_ROLE_WITNESS, TrustStatus::Unknown)]
+    fn test_annotate_trust(#[case] did: &str, #[case] role: &str, #[case] expected: TrustStatus) {
+        let did_doc = DidDoc::from_json(
+            &json!({"@context": ["https://www.w3.org/ns/did/v1"], "id": did, "verificationMethod": []}).to_string(),
+        )
+        .unwrap();
+
+        let annotation = did_doc.annotate_trust(role.to_owned(), Some(Arc::new(MockRegistry)));
+        assert_eq!(
+            annotation,
+            TrustAnnotation {
+                did: did.to_owned(),
+                role: role.to_owned(),
+                status: expected,
+            }
+        );
+        // no registry consulted at all
+        assert_eq!(
+            did_doc.annotate_trust(role.to_owned(), None).status,
+            TrustStatus::Unknown
+        );
+    }
+}
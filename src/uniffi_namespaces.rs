@@ -0,0 +1,550 @@
+// SPDX-License-Identifier: MIT
+
+//! The UniFFI surface organized into namespace objects (`Crypto`, `Encoding`, `Validation` and `Resolution`),
+//! so binding code reads e.g. `Encoding().multibaseDecode(...)`.
+//!
+//! Each namespace object is stateless, hence cheap to construct. Its methods are named consistently, i.e.
+//! prefixed by the algorithm/format they rely on (e.g. `multibase_`, `jcs_`, `ed25519_`, `digest_`).
+//! The former (flat) namespace functions remain available for the sake of backward compatibility.
+
+use crate::did_doc::{get_key_from_did_doc, Jwk};
+use crate::did_method_parameters::{get_by_json_pointer, DidMethodParameter};
+use crate::ed25519::{
+    did_key_from_verifying_key, Ed25519KeyPair, Ed25519Signature, Ed25519SigningKey,
+    Ed25519VerifyingKey, MultiBaseConverter as _, VerificationOptions,
+};
+use crate::errors::DidSidekicksError;
+use crate::jcs_sha256_hasher::JcsSha256Hasher;
+use crate::json_parsing::{parse_json_strict, validate_json_strict};
+use crate::key_interop::{
+    signing_key_from_base58_seed, signing_key_from_jwk, signing_key_to_base58_seed,
+    signing_key_to_jwk, verifying_key_from_jwk, verifying_key_to_jwk,
+};
+use crate::multibase::MultibaseEncoderDecoder;
+use crate::panic_boundary::catch_panic;
+use crate::resource_digest::{
+    compute_digest_multibase, compute_digest_sri, verify_digest_multibase, verify_digest_sri,
+};
+use serde_json_canonicalizer::to_string as jcs_to_string;
+use std::sync::Arc;
+
+/// Cryptographic primitives (Ed25519 keys/signatures and content hashing).
+///
+/// A UniFFI-compliant object.
+#[derive(Debug, Default)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct Crypto;
+
+impl Crypto {
+    /// The only (empty) constructor of the type.
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Generates a new Ed25519 signing key, delivered as multikey (multibase-encoded).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn ed25519_generate_signing_key(&self) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || Ed25519KeyPair::generate().signing_key.to_multibase(),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Delivers the Ed25519 verifying key (multikey) matching the supplied `signing_key` (multikey).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn ed25519_verifying_key(&self, signing_key: String) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || {
+                Ok(Ed25519KeyPair::from(&signing_key)?
+                    .verifying_key
+                    .to_multibase())
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Signs the supplied `message` using the supplied Ed25519 `signing_key` (multikey),
+    /// thus delivering a multibase-encoded signature.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn ed25519_sign(
+        &self,
+        signing_key: String,
+        message: Vec<u8>,
+    ) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || {
+                Ok(Ed25519KeyPair::from(&signing_key)?
+                    .signing_key
+                    .sign_bytes(&message)?
+                    .to_multibase())
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Verifies the supplied (multibase-encoded) `signature` of the supplied `message`
+    /// using the supplied Ed25519 `verifying_key` (multikey).
+    ///
+    /// Returns `false` if the signature does not match, whereas malformed input is denoted by [`DidSidekicksError`].
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn ed25519_verify(
+        &self,
+        verifying_key: String,
+        message: Vec<u8>,
+        signature: String,
+    ) -> Result<bool, DidSidekicksError> {
+        catch_panic(
+            || {
+                self.ed25519_verify_with_options(
+                    verifying_key,
+                    message,
+                    signature,
+                    VerificationOptions::default(),
+                )
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// The same as [`Crypto::ed25519_verify`], but according to the supplied `options`
+    /// (e.g. [`crate::ed25519::Ed25519VerificationMode::Standard`] for interoperability purposes).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn ed25519_verify_with_options(
+        &self,
+        verifying_key: String,
+        message: Vec<u8>,
+        signature: String,
+        options: VerificationOptions,
+    ) -> Result<bool, DidSidekicksError> {
+        catch_panic(
+            || {
+                let key = Ed25519VerifyingKey::from_multibase(&verifying_key)?;
+                let sig = Ed25519Signature::from_multibase(&signature)?;
+                Ok(key
+                    .verify_with_options(&message, &sig.signature, options)
+                    .is_ok())
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Delivers the `did:key` DID for the supplied Ed25519 `verifying_key` (multikey).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn ed25519_did_key(&self, verifying_key: String) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || {
+                Ok(did_key_from_verifying_key(
+                    &Ed25519VerifyingKey::from_multibase(&verifying_key)?,
+                ))
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Converts the supplied Ed25519 `signing_key` (multikey) into a (private) OKP JWK (JSON text).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn ed25519_signing_key_to_jwk(
+        &self,
+        signing_key: String,
+    ) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || signing_key_to_jwk(&Ed25519SigningKey::from_multibase(&signing_key)?),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Converts the supplied (private) OKP `jwk` (JSON text) into an Ed25519 signing key (multikey).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn ed25519_signing_key_from_jwk(&self, jwk: String) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || signing_key_from_jwk(&jwk)?.to_multibase(),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Converts the supplied Ed25519 `verifying_key` (multikey) into a (public) OKP JWK (JSON text).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn ed25519_verifying_key_to_jwk(
+        &self,
+        verifying_key: String,
+    ) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || verifying_key_to_jwk(&Ed25519VerifyingKey::from_multibase(&verifying_key)?),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Converts the supplied OKP `jwk` (JSON text) into an Ed25519 verifying key (multikey).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn ed25519_verifying_key_from_jwk(&self, jwk: String) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || Ok(verifying_key_from_jwk(&jwk)?.to_multibase()),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Converts the supplied Ed25519 `signing_key` (multikey) into a (plain) base58 seed.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn ed25519_signing_key_to_base58_seed(
+        &self,
+        signing_key: String,
+    ) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || signing_key_to_base58_seed(&Ed25519SigningKey::from_multibase(&signing_key)?),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Converts the supplied base58 `seed` (either 32-byte secret key or 64-byte keypair) into an Ed25519 signing key (multikey).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn ed25519_signing_key_from_base58_seed(
+        &self,
+        seed: String,
+    ) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || signing_key_from_base58_seed(&seed)?.to_multibase(),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Computes the `digestMultibase` value of the supplied `content`.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn digest_multibase(&self, content: Vec<u8>) -> String {
+        compute_digest_multibase(content)
+    }
+
+    /// Computes the `digestSRI` value of the supplied `content`.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn digest_sri(&self, content: Vec<u8>) -> String {
+        compute_digest_sri(content)
+    }
+}
+
+/// Encoding primitives (multibase and JCS).
+///
+/// A UniFFI-compliant object.
+#[derive(Debug, Default)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct Encoding;
+
+impl Encoding {
+    /// The only (empty) constructor of the type.
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Encodes the supplied `data` in multibase (base58btc) format.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn multibase_encode(&self, data: Vec<u8>) -> String {
+        MultibaseEncoderDecoder::default().encode_base58btc(&data)
+    }
+
+    /// Decodes the supplied `multibase` (base58btc) value.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn multibase_decode(&self, multibase: String) -> Result<Vec<u8>, DidSidekicksError> {
+        catch_panic(
+            || MultibaseEncoderDecoder::default().decode_base58(&multibase),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Encodes the supplied `data` in multibase (base36, lowercase) format, e.g. for case-insensitive QR code payloads.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn multibase_encode_base36(&self, data: Vec<u8>) -> String {
+        MultibaseEncoderDecoder::default().encode_base36(&data)
+    }
+
+    /// Encodes the supplied `data` in multibase (base45, see https://www.rfc-editor.org/rfc/rfc9285) format,
+    /// i.e. the most efficient one for QR codes (alphanumeric mode).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn multibase_encode_base45(&self, data: Vec<u8>) -> String {
+        MultibaseEncoderDecoder::default().encode_base45(&data)
+    }
+
+    /// Decodes the supplied `multibase` value of any supported algorithm (base58btc, base36 or base45),
+    /// as denoted by its multibase identifier (prefix).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn multibase_decode_any(&self, multibase: String) -> Result<Vec<u8>, DidSidekicksError> {
+        catch_panic(
+            || MultibaseEncoderDecoder::default().decode(&multibase),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Canonicalizes the supplied JSON text according to JCS (https://www.rfc-editor.org/rfc/rfc8785).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn jcs_canonicalize(&self, json: String) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || {
+                jcs_to_string(&parse_json_strict(&json)?)
+                    .map_err(|err| DidSidekicksError::SerializationFailed(err.to_string()))
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Canonicalizes the supplied JSON text according to JCS and delivers its SHA2-256 hash (as lowercase hex string).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn jcs_sha256_hex(&self, json: String) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || {
+                JcsSha256Hasher::default()
+                    .encode_hex(&parse_json_strict(&json)?)
+                    .map_err(|err| DidSidekicksError::SerializationFailed(err.to_string()))
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Canonicalizes the supplied JSON text according to JCS and delivers its SHA2-256 multihash (base58btc-encoded).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn jcs_sha256_multihash(&self, json: String) -> Result<String, DidSidekicksError> {
+        catch_panic(
+            || {
+                JcsSha256Hasher::default()
+                    .base58btc_encode_multihash(&parse_json_strict(&json)?)
+                    .map_err(|err| DidSidekicksError::SerializationFailed(err.to_string()))
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+}
+
+/// Validation primitives.
+///
+/// A UniFFI-compliant object.
+#[derive(Debug, Default)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct Validation;
+
+impl Validation {
+    /// The only (empty) constructor of the type.
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Validates the supplied JSON `text` in strict mode (rejecting duplicate keys).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn json_strict(&self, text: String) -> Result<(), DidSidekicksError> {
+        catch_panic(
+            || validate_json_strict(text),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Verifies the supplied `content` against the supplied `digest_multibase` value.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn digest_multibase(
+        &self,
+        content: Vec<u8>,
+        digest_multibase: String,
+    ) -> Result<bool, DidSidekicksError> {
+        catch_panic(
+            || verify_digest_multibase(content, digest_multibase),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Verifies the supplied `content` against the supplied `digest_sri` value.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn digest_sri(
+        &self,
+        content: Vec<u8>,
+        digest_sri: String,
+    ) -> Result<bool, DidSidekicksError> {
+        catch_panic(
+            || verify_digest_sri(content, digest_sri),
+            DidSidekicksError::InternalError,
+        )
+    }
+}
+
+/// Resolution primitives (key/field lookup in DID documents and DID log entries).
+///
+/// A UniFFI-compliant object.
+#[derive(Debug, Default)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct Resolution;
+
+impl Resolution {
+    /// The only (empty) constructor of the type.
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Parses the supplied DID doc and returns a cryptographic public key (`Jwk`) referenced by the supplied `key_id`, if any.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn did_doc_key(&self, did_doc: String, key_id: String) -> Result<Jwk, DidSidekicksError> {
+        catch_panic(
+            || get_key_from_did_doc(did_doc, key_id),
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Extracts an arbitrary nested value of the supplied JSON text by means of a JSON pointer (RFC 6901).
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn json_pointer(
+        &self,
+        json_text: String,
+        pointer: String,
+    ) -> Option<Arc<DidMethodParameter>> {
+        get_by_json_pointer(json_text, pointer)
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_crypto_namespace() {
+        let crypto = Crypto::new();
+        let signing_key = crypto.ed25519_generate_signing_key().unwrap();
+        let verifying_key = crypto
+            .ed25519_verifying_key(signing_key.to_owned())
+            .unwrap();
+        let signature = crypto
+            .ed25519_sign(signing_key.to_owned(), b"message".to_vec())
+            .unwrap();
+        assert!(crypto
+            .ed25519_verify(
+                verifying_key.to_owned(),
+                b"message".to_vec(),
+                signature.to_owned()
+            )
+            .unwrap());
+        assert!(!crypto
+            .ed25519_verify(verifying_key.to_owned(), b"tampered".to_vec(), signature)
+            .unwrap());
+        assert_eq!(
+            crypto.ed25519_did_key(verifying_key.to_owned()).unwrap(),
+            format!("did:key:{verifying_key}")
+        );
+
+        let jwk = crypto
+            .ed25519_signing_key_to_jwk(signing_key.to_owned())
+            .unwrap();
+        assert_eq!(
+            crypto.ed25519_signing_key_from_jwk(jwk.to_owned()).unwrap(),
+            signing_key
+        );
+        assert_eq!(
+            crypto.ed25519_verifying_key_from_jwk(jwk).unwrap(),
+            verifying_key
+        );
+        let seed = crypto
+            .ed25519_signing_key_to_base58_seed(signing_key.to_owned())
+            .unwrap();
+        assert_eq!(
+            crypto.ed25519_signing_key_from_base58_seed(seed).unwrap(),
+            signing_key
+        );
+    }
+
+    #[rstest]
+    fn test_encoding_namespace() {
+        let encoding = Encoding::new();
+        let encoded = encoding.multibase_encode(b"helloworld".to_vec());
+        assert_eq!(encoding.multibase_decode(encoded).unwrap(), b"helloworld");
+        for encoded in [
+            encoding.multibase_encode_base36(b"helloworld".to_vec()),
+            encoding.multibase_encode_base45(b"helloworld".to_vec()),
+        ] {
+            assert_eq!(
+                encoding.multibase_decode_any(encoded).unwrap(),
+                b"helloworld"
+            );
+        }
+        assert_eq!(
+            encoding
+                .jcs_canonicalize(r#"{"b": 1, "a": [true]}"#.to_owned())
+                .unwrap(),
+            r#"{"a":[true],"b":1}"#
+        );
+        encoding
+            .jcs_canonicalize(r#"{"a":1,"a":2}"#.to_owned())
+            .unwrap_err();
+    }
+
+    #[rstest]
+    fn test_validation_namespace() {
+        let validation = Validation::new();
+        let content = b"content".to_vec();
+        let digest = Crypto::new().digest_sri(content.to_owned());
+        assert!(validation.digest_sri(content, digest).unwrap());
+        validation
+            .json_strict(r#"{"a":1,"a":2}"#.to_owned())
+            .unwrap_err();
+    }
+}
@@ -0,0 +1,214 @@
+// SPDX-License-Identifier: MIT
+
+use crate::panic_boundary::catch_panic;
+use crate::did_jsonschema::{DidLogEntryJsonSchema, DidLogEntryValidator, DidLogEntryValidatorError};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// Describes a schema currently held by a [`ValidatorService`], as delivered by [`ValidatorService::get_schema_versions`].
+///
+/// A UniFFI-compliant record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct SchemaVersionInfo {
+    /// The schema version (e.g. a DID method specification version) the schema was registered under.
+    pub version: String,
+    /// The revision of the schema, incremented (service-wide) on each (re)registration.
+    pub revision: u64,
+}
+
+/// A long-lived (thread-safe) service object holding [`DidLogEntryValidator`] objects for several schema versions.
+///
+/// Any schema may be hot-swapped at runtime (e.g. when the trust infrastructure publishes an updated schema),
+/// as the replacement is atomic: validations already in progress keep using the previous validator,
+/// whereas all subsequent validations use the new one.
+///
+/// A UniFFI-compliant object.
+#[derive(Debug, Default)]
+pub struct ValidatorService {
+    validators: RwLock<BTreeMap<String, (Arc<DidLogEntryValidator>, u64)>>,
+    revision: AtomicU64,
+}
+
+impl ValidatorService {
+    /// The only (empty) constructor of the type.
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// (Re)registers the supplied `schema` under the supplied `version`, thus (atomically) replacing any previously registered one.
+    ///
+    /// The schema is compiled prior to replacement, so a malformed `schema` never replaces a working one.
+    /// In such case, [`DidLogEntryValidatorError::InternalError`] is returned.
+    ///
+    /// Returns the (new) revision of the schema.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn register_schema(
+        &self,
+        version: String,
+        schema: Arc<dyn DidLogEntryJsonSchema>,
+    ) -> Result<u64, DidLogEntryValidatorError> {
+        catch_panic(
+            || {
+                let validator = Arc::new(DidLogEntryValidator::try_from_schema(schema)?);
+                let revision = self
+                    .revision
+                    .fetch_add(1, Ordering::SeqCst)
+                    .saturating_add(1);
+                self.validators
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .insert(version, (validator, revision));
+                Ok(revision)
+            },
+            DidLogEntryValidatorError::InternalError,
+        )
+    }
+
+    /// Removes the schema registered under the supplied `version`, if any. Returns whether such schema existed.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn remove_schema(&self, version: String) -> bool {
+        self.validators
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&version)
+            .is_some()
+    }
+
+    /// Delivers the validator currently registered under the supplied `version`, if any.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn get_validator(&self, version: String) -> Option<Arc<DidLogEntryValidator>> {
+        self.validators
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&version)
+            .map(|entry| Arc::clone(&entry.0))
+    }
+
+    /// Delivers all the schema versions (along with their revisions) currently held by the service.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn get_schema_versions(&self) -> Vec<SchemaVersionInfo> {
+        self.validators
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(version, &(_, revision))| SchemaVersionInfo {
+                version: version.to_owned(),
+                revision,
+            })
+            .collect()
+    }
+
+    /// Validate the supplied `instance` against the schema currently registered under the supplied `version`.
+    ///
+    /// If no such schema exists, [`DidLogEntryValidatorError::ValidationError`] is returned.
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn validate(&self, version: String, instance: String) -> Result<(), DidLogEntryValidatorError> {
+        catch_panic(
+            || {
+                // CAUTION The lock is released prior to validation, so a hot-swap is never blocked by a (lengthy) validation
+                let validator = self.get_validator(version.to_owned()).ok_or_else(|| {
+                    DidLogEntryValidatorError::ValidationError(format!(
+                        "no schema registered for version '{version}'"
+                    ))
+                })?;
+                validator.validate(instance)
+            },
+            DidLogEntryValidatorError::InternalError,
+        )
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::did_jsonschema::DidLogEntryValidatorErrorKind;
+    use rstest::rstest;
+
+    struct StaticSchema(&'static str);
+    #[expect(clippy::missing_trait_methods, reason = "..")]
+    impl DidLogEntryJsonSchema for StaticSchema {
+        fn get_json_schema(&self) -> String {
+            self.0.to_owned()
+        }
+    }
+
+    #[rstest]
+    fn test_hot_swap_schema() {
+        let service = ValidatorService::new();
+        let revision = service
+            .register_schema(
+                "1.0".to_owned(),
+                Arc::new(StaticSchema(r#"{"type":"array"}"#)),
+            )
+            .unwrap();
+        service.validate("1.0".to_owned(), "[]".to_owned()).unwrap();
+        service
+            .validate("1.0".to_owned(), "{}".to_owned())
+            .unwrap_err();
+
+        let previous_validator = service.get_validator("1.0".to_owned()).unwrap();
+        let new_revision = service
+            .register_schema(
+                "1.0".to_owned(),
+                Arc::new(StaticSchema(r#"{"type":"object"}"#)),
+            )
+            .unwrap();
+        assert!(new_revision > revision);
+        service.validate("1.0".to_owned(), "{}".to_owned()).unwrap();
+        // the previous validator remains usable
+        previous_validator.validate("[]".to_owned()).unwrap();
+
+        assert_eq!(
+            service.get_schema_versions(),
+            vec![SchemaVersionInfo {
+                version: "1.0".to_owned(),
+                revision: new_revision
+            }]
+        );
+    }
+
+    #[rstest]
+    fn test_malformed_schema_does_not_replace_working_one() {
+        let service = ValidatorService::new();
+        service
+            .register_schema("1.0".to_owned(), Arc::new(StaticSchema(r#"{"type":"array"}"#)))
+            .unwrap();
+        assert_eq!(
+            service
+                .register_schema("1.0".to_owned(), Arc::new(StaticSchema("")))
+                .unwrap_err()
+                .kind(),
+            DidLogEntryValidatorErrorKind::InternalError
+        );
+        service.validate("1.0".to_owned(), "[]".to_owned()).unwrap();
+
+        assert!(service.remove_schema("1.0".to_owned()));
+        assert!(!service.remove_schema("1.0".to_owned()));
+        assert_eq!(
+            service
+                .validate("1.0".to_owned(), "[]".to_owned())
+                .unwrap_err()
+                .kind(),
+            DidLogEntryValidatorErrorKind::ValidationError
+        );
+    }
+}
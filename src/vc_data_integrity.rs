@@ -1,36 +1,47 @@
 // SPDX-License-Identifier: MIT
 
+use crate::bounded_json::from_str_bounded;
+use crate::cache::SIGNATURE_CACHE;
+use crate::did_method_parameters::DidMethodParameter;
 use crate::ed25519::*;
 use crate::errors::DidSidekicksError;
 use crate::jcs_sha256_hasher::JcsSha256Hasher;
+use crate::json_emission::{emit_json, EmitOptions};
+use crate::metrics::observe_verification;
 use chrono::{serde::ts_seconds, DateTime, SecondsFormat, Utc};
+use core::hash::BuildHasher;
 use core::ops::Deref as _;
 use hex;
 use serde::{Deserialize, Serialize};
 use serde_json::{
     json, Value::Array as JsonArray, Value::Null as JsonNull, Value::String as JsonString,
 };
+use sha2::{Digest as _, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[expect(
     clippy::exhaustive_enums,
     reason = "further enum variants may be added in the future"
 )]
 pub enum CryptoSuiteType {
+    #[serde(rename = "bbs-2023")]
     Bbs2023,
+    #[serde(rename = "ecdsa-rdfc-2019")]
     EcdsaRdfc2019,
+    #[serde(rename = "ecdsa-jcs-2019")]
     EcdsaJcs2019,
+    #[serde(rename = "ecdsa-sd-2019")]
     EcdsaSd2019,
+    #[serde(rename = "eddsa-rdfc-2022")]
     EddsaRdfc2022,
+    #[serde(rename = "eddsa-jcs-2022")]
     EddsaJcs2022,
 }
 
 impl core::fmt::Display for CryptoSuiteType {
     #[inline]
-    #[expect(
-        clippy::min_ident_chars,
-        reason = "to prevent clippy::renamed_function_params warning"
-    )]
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match *self {
             Self::Bbs2023 => write!(f, "bbs-2023"),
@@ -43,24 +54,95 @@ impl core::fmt::Display for CryptoSuiteType {
     }
 }
 
-/// As specified by https://www.w3.org/TR/vc-di-eddsa/#proof-configuration-eddsa-jcs-2022
-#[derive(Clone)]
+/// The version of the (persisted) [`CryptoSuiteProofOptions`] format, to be incremented on any change of its structure.
+pub const PROOF_OPTIONS_FORMAT_VERSION: u64 = 1;
+
+/// As specified by https://www.w3.org/TR/vc-di-eddsa/#proof-configuration-eddsa-jcs-2022.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 #[expect(clippy::exhaustive_structs, reason = "..")]
 pub struct CryptoSuiteProofOptions {
+    #[serde(rename = "type")]
     pub proof_type: String,
+    #[serde(rename = "cryptosuite")]
     pub crypto_suite: CryptoSuiteType,
     pub created: DateTime<Utc>,
     pub verification_method: String,
     pub proof_purpose: String,
+    #[serde(rename = "@context", skip_serializing_if = "Option::is_none", default)]
     pub context: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     pub challenge: Option<String>,
+    /// Any further (e.g. ecosystem-specific) members of the proof configuration, thus covered by the signature.
+    /// See [`CryptoSuiteProofOptions::with_extension`].
+    #[serde(skip_serializing_if = "serde_json::Map::is_empty", default)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
 }
 
 impl CryptoSuiteProofOptions {
+    /// The preferred constructor of the type, superseding [`Self::new`], thus featuring the supplied `verification_method`
+    /// along with the default values of all other members (see [`Self::default()`]), whereas any of them may be set
+    /// by the respective builder method, e.g.:
+    ///
+    /// ```ignore
+    /// let options = CryptoSuiteProofOptions::from_verification_method("did:key:z6Mk...#z6Mk...")
+    ///     .with_proof_purpose("assertionMethod")
+    ///     .with_challenge("1-Qm...");
+    /// ```
+    #[inline]
+    pub fn from_verification_method(verification_method: &str) -> Self {
+        let mut options = Self::default();
+        verification_method.clone_into(&mut options.verification_method);
+        options
+    }
+
+    /// Sets the cryptosuite (`eddsa-jcs-2022` by default).
+    #[inline]
+    #[must_use]
+    pub const fn with_crypto_suite(mut self, crypto_suite: CryptoSuiteType) -> Self {
+        self.crypto_suite = crypto_suite;
+        self
+    }
+
+    /// Sets the creation datetime (the current datetime by default).
+    #[inline]
+    #[must_use]
+    pub const fn with_created(mut self, created: DateTime<Utc>) -> Self {
+        self.created = created;
+        self
+    }
+
+    /// Sets the proof purpose (`authentication` by default).
+    #[inline]
+    #[must_use]
+    pub fn with_proof_purpose(mut self, proof_purpose: &str) -> Self {
+        proof_purpose.clone_into(&mut self.proof_purpose);
+        self
+    }
+
+    /// Sets the (JSON-LD) context (none by default).
+    #[inline]
+    #[must_use]
+    pub fn with_context(mut self, context: Vec<String>) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// Sets the challenge (none by default), e.g. the `versionId` of a `did:tdw` DID log entry.
+    #[inline]
+    #[must_use]
+    pub fn with_challenge(mut self, challenge: &str) -> Self {
+        self.challenge = Some(challenge.to_owned());
+        self
+    }
+
     /// The only (super-potent) non-empty constructor.
     ///
     /// As nearly all arguments are optional, see [`Self::default()`] constructor for default values.
     #[inline]
+    #[deprecated(
+        note = "use `CryptoSuiteProofOptions::from_verification_method` along with the `with_*` builder methods instead"
+    )]
     pub fn new(
         crypto_suite_type: Option<CryptoSuiteType>,
         created_dt: Option<DateTime<Utc>>,
@@ -95,7 +177,6 @@ impl CryptoSuiteProofOptions {
     /// - crypto_suite: "eddsa-jcs-2022"
     /// - created: \<current datetime\>
     /// - proof_purpose: "authentication"
-    #[expect(clippy::single_call_fn, reason = "..")]
     pub(crate) fn default() -> Self {
         Self {
             proof_type: "DataIntegrityProof".to_owned(),
@@ -105,13 +186,148 @@ impl CryptoSuiteProofOptions {
             proof_purpose: "authentication".to_owned(),
             context: None,
             challenge: None,
+            extensions: serde_json::Map::new(),
         }
     }
+
+    /// Adds the supplied extension member (`name`/`value`) to the proof configuration, so that it is signed along
+    /// with the standard members (and preserved by [`DataIntegrityProof::from`] on the verifying side).
+    ///
+    /// Any member name reserved by https://www.w3.org/TR/vc-data-integrity/#proofs (e.g. `proofValue`) or any value
+    /// not surviving JCS canonicalization unaltered (see [`check_proof_extensions`]) is denoted by [`DidSidekicksError::InvalidDataIntegrityProof`].
+    #[inline]
+    pub fn with_extension(
+        mut self,
+        name: &str,
+        value: serde_json::Value,
+    ) -> Result<Self, DidSidekicksError> {
+        self.extensions.insert(name.to_owned(), value);
+        check_proof_extensions(&self.extensions)?;
+        Ok(self)
+    }
+
+    /// The parsing constructor, as counterpart of [`CryptoSuiteProofOptions::to_json`], e.g. to resume a signing session
+    /// (waiting for a remote HSM) from persisted options.
+    ///
+    /// Any malformed JSON text or any unsupported `version` of it is denoted by [`DidSidekicksError::DeserializationFailed`].
+    #[inline]
+    pub fn from_json(json: &str) -> Result<Self, DidSidekicksError> {
+        let mut value = from_str_bounded::<serde_json::Value>(json)?;
+        match value.as_object_mut().and_then(|obj| obj.remove("version")) {
+            Some(version) if version.as_u64() == Some(PROOF_OPTIONS_FORMAT_VERSION) => {}
+            version => {
+                return Err(DidSidekicksError::DeserializationFailed(format!(
+                    "Unsupported proof options version: {}",
+                    version.unwrap_or_default()
+                )))
+            }
+        }
+        serde_json::from_value(value).map_err(|err| {
+            DidSidekicksError::DeserializationFailed(format!("Malformed proof options: {err}"))
+        })
+    }
+
+    /// Emits these options as (versioned) JSON text according to the supplied `options`, so they may be persisted
+    /// and restored later on (see [`CryptoSuiteProofOptions::from_json`]).
+    ///
+    /// The `created` datetime is emitted at full (sub-second) precision, hence the very same proof may be created after restore.
+    #[inline]
+    pub fn to_json(&self, options: EmitOptions) -> Result<String, DidSidekicksError> {
+        let mut value = serde_json::to_value(self)
+            .map_err(|err| DidSidekicksError::SerializationFailed(err.to_string()))?;
+        if let Some(obj) = value.as_object_mut() {
+            obj.insert("version".to_owned(), PROOF_OPTIONS_FORMAT_VERSION.into());
+        }
+        emit_json(&value, options)
+    }
+}
+
+/// The standard members of a proof (see https://www.w3.org/TR/vc-data-integrity/#proofs), hence unavailable as extension members.
+pub const PROOF_MEMBERS: [&str; 8] = [
+    "type",
+    "cryptosuite",
+    "created",
+    "verificationMethod",
+    "proofPurpose",
+    "@context",
+    "challenge",
+    "proofValue",
+];
+
+/// The largest integer surviving JCS canonicalization unaltered, as JCS serializes numbers as IEEE 754 doubles (RFC 8785, section 3.2.2.3).
+const MAX_SAFE_INTEGER: u64 = 9_007_199_254_740_991;
+
+/// Ensures the supplied proof `extensions` (see [`CryptoSuiteProofOptions::extensions`]) are safe to be signed.
+///
+/// I.e. none of them is named as any of the [`PROOF_MEMBERS`] and none of them features an integer beyond `±(2^53 - 1)`
+/// (which other JCS implementations would canonicalize differently).
+///
+/// Any violation is denoted by [`DidSidekicksError::InvalidDataIntegrityProof`].
+#[inline]
+pub fn check_proof_extensions(
+    extensions: &serde_json::Map<String, serde_json::Value>,
+) -> Result<(), DidSidekicksError> {
+    extensions.iter().try_for_each(|(name, value)| {
+        if PROOF_MEMBERS.contains(&name.as_str()) {
+            return Err(DidSidekicksError::InvalidDataIntegrityProof(format!(
+                "The proof extension '{name}' is reserved"
+            )));
+        }
+        if !is_canonicalization_safe(value) {
+            return Err(DidSidekicksError::InvalidDataIntegrityProof(format!(
+                "The proof extension '{name}' features an integer beyond {MAX_SAFE_INTEGER}"
+            )));
+        }
+        Ok(())
+    })
+}
+
+#[expect(clippy::pattern_type_mismatch, reason = "..")]
+fn is_canonicalization_safe(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Number(number) => number
+            .as_i64()
+            .map(i64::unsigned_abs)
+            .or_else(|| number.as_u64())
+            .is_none_or(|integer| integer <= MAX_SAFE_INTEGER),
+        JsonArray(items) => items.iter().all(is_canonicalization_safe),
+        serde_json::Value::Object(members) => members.values().all(is_canonicalization_safe),
+        JsonNull | serde_json::Value::Bool(_) | JsonString(_) => true,
+    }
+}
+
+/// Adds the supplied `extensions` to the supplied proof `config`, while never overriding any of its (standard) members.
+fn extend_proof_config(
+    config: &mut serde_json::Value,
+    extensions: &serde_json::Map<String, serde_json::Value>,
+) {
+    if let Some(members) = config.as_object_mut() {
+        for (name, value) in extensions {
+            members
+                .entry(name.to_owned())
+                .or_insert_with(|| value.to_owned());
+        }
+    }
+}
+
+/// The policy w.r.t. proofs featuring an unsupported (e.g. unknown or future) cryptosuite, while parsing a set of proofs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum UnsupportedCryptosuitePolicy {
+    /// Reject the whole set of proofs, i.e. fail with [`DidSidekicksError::UnsupportedCryptosuite`].
+    #[default]
+    Reject,
+    /// Skip such proofs, so that the remaining ones may still be verified (e.g. mixed-suite proof sets from other ecosystems).
+    Skip,
 }
 
 // See https://www.w3.org/TR/vc-data-integrity/#dataintegrityproof
 // For EdDSA Cryptosuites v1.0 suites, see https://www.w3.org/TR/vc-di-eddsa/#dataintegrityproof
-#[derive(Serialize, Deserialize, Debug, Clone)]
+// The Debug implementation is redacted, see crate::redaction module.
+#[derive(Serialize, Deserialize, Clone)]
 #[expect(clippy::exhaustive_structs, reason = "..")]
 pub struct DataIntegrityProof {
     #[serde(rename = "type")]
@@ -132,18 +348,27 @@ pub struct DataIntegrityProof {
     pub challenge: Option<String>,
     #[serde(rename = "proofValue")]
     pub proof_value: String,
+    /// Any further (e.g. ecosystem-specific) members of the proof, preserved as they are covered by the signature.
+    #[serde(flatten)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
 }
+#[expect(clippy::multiple_inherent_impl, reason = "..")]
 impl DataIntegrityProof {
-    /// The non-empty parsing constructor featuring validation in terms of supported type/proofPurpose/cryptosuite
+    /// The non-empty parsing constructor featuring validation in terms of supported type/proofPurpose/cryptosuite.
+    ///
+    /// An unsupported (e.g. unknown or future) cryptosuite is denoted by [`DidSidekicksError::UnsupportedCryptosuite`]
+    /// (featuring its name), whereas any other validation failure is denoted by [`DidSidekicksError::InvalidDataIntegrityProof`].
     #[inline]
-    // TODO Ensure panic-safe indexing
-    #[expect(clippy::indexing_slicing, reason = "see TODO")]
+    #[expect(
+        clippy::indexing_slicing,
+        reason = "panic-safe, as indexing a JSON value by a member name never panics (but delivers null, if missing)"
+    )]
     #[expect(
         clippy::wildcard_enum_match_arm,
         reason = "wildcard match ignorable as no further JSON variants are possible"
     )]
     pub fn from(json: String) -> Result<Self, DidSidekicksError> {
-        let value = match serde_json::from_str(&json) {
+        let value = match from_str_bounded(&json) {
             Ok(JsonArray(entry)) => {
                 if entry.len() > 1 {
                     return Err(DidSidekicksError::InvalidDataIntegrityProof(
@@ -162,7 +387,8 @@ impl DataIntegrityProof {
             }
             Err(err) => {
                 return Err(DidSidekicksError::InvalidDataIntegrityProof(format!(
-                    "Malformed proof format, expected single-element JSON array: {err}"
+                    "Malformed proof format, expected single-element JSON array: {}",
+                    err.detail()
                 )))
             }
             _ => {
@@ -189,11 +415,10 @@ impl DataIntegrityProof {
             },
             crypto_suite: match value["cryptosuite"].to_owned() {
                 JsonString(str) => {
+                    // An unknown (e.g. future) cryptosuite is denoted by its name,
+                    // so that proof sets featuring mixed cryptosuites may degrade gracefully
                     if str != CryptoSuiteType::EddsaJcs2022.to_string().deref() {
-                        return Err(DidSidekicksError::InvalidDataIntegrityProof(format!(
-                            "Unsupported proof's cryptosuite. Expected '{}'",
-                            CryptoSuiteType::EddsaJcs2022
-                        )));
+                        return Err(DidSidekicksError::UnsupportedCryptosuite(str));
                     }
                     str
                 }
@@ -284,28 +509,103 @@ impl DataIntegrityProof {
                     "Wrong format of proofValue parameter. Expected a proofValue of type string.".to_owned(),
                 ))
             },
+            // any other member is preserved as is, since covered by the signature
+            extensions: value
+                .as_object()
+                .map(|members| {
+                    members
+                        .iter()
+                        .filter(|&(name, _)| !PROOF_MEMBERS.contains(&name.as_str()))
+                        .map(|(name, member)| (name.to_owned(), member.to_owned()))
+                        .collect()
+                })
+                .unwrap_or_default(),
         })
     }
 
-    /// Construct a serde_json::Value from this DataIntegrityProof
+    /// The parsing constructor of a proof set, i.e. a non-empty JSON array of proofs, each of them validated
+    /// the same way [`DataIntegrityProof::from`] does it.
+    ///
+    /// Any proof featuring an unsupported cryptosuite is treated according to the supplied `policy`, i.e. it is either
+    /// denoted by [`DidSidekicksError::UnsupportedCryptosuite`] or skipped (hence, the delivered proof set might be empty).
     #[inline]
-    #[expect(clippy::indexing_slicing, reason = "panic-safe indexing")]
-    pub fn json_value(&self) -> Result<serde_json::Value, DidSidekicksError> {
-        let mut value = match serde_json::to_value(self) {
-            Ok(val) => val,
+    pub fn from_proof_set(
+        json: &str,
+        policy: UnsupportedCryptosuitePolicy,
+    ) -> Result<Vec<Self>, DidSidekicksError> {
+        let proofs = match from_str_bounded(json) {
+            Ok(JsonArray(proofs)) => proofs,
             Err(err) => {
-                return Err(DidSidekicksError::SerializationFailed(format!(
-                    "Could not serialize proof: {err}"
+                return Err(DidSidekicksError::InvalidDataIntegrityProof(format!(
+                    "Malformed proof set format, expected JSON array: {}",
+                    err.detail()
                 )))
             }
+            _ => {
+                return Err(DidSidekicksError::InvalidDataIntegrityProof(
+                    "Malformed proof set format, expected JSON array".to_owned(),
+                ))
+            }
         };
+        if proofs.is_empty() {
+            return Err(DidSidekicksError::InvalidDataIntegrityProof(
+                "Empty proof array detected.".to_owned(),
+            ));
+        }
+
+        proofs
+            .into_iter()
+            .map(|proof| Self::from(JsonArray(vec![proof]).to_string()))
+            .filter(|proof| {
+                policy != UnsupportedCryptosuitePolicy::Skip
+                    || !matches!(*proof, Err(DidSidekicksError::UnsupportedCryptosuite(_)))
+            })
+            .collect()
+    }
+
+    /// The proof configuration, i.e. this proof lacking `proofValue` (see https://www.w3.org/TR/vc-di-eddsa/#proof-configuration-eddsa-jcs-2022),
+    /// featuring any [`DataIntegrityProof::extensions`] as well.
+    #[inline]
+    #[expect(clippy::indexing_slicing, reason = "panic-safe indexing")]
+    pub fn proof_config(&self) -> serde_json::Value {
+        // CAUTION Beware that only serde_json::json macro is able to serialize proof.created field properly (if used directly)!
+        //         (thanks to #[serde(with = "ts_seconds")])
+        let mut config = json!({
+            "type": self.proof_type,
+            "cryptosuite": self.crypto_suite,
+            // The created is not used directly here, due to more error-prone conversion that requires #[serde(with = "ts_seconds")] attribute
+            "created": self.created.to_rfc3339_opts(SecondsFormat::Secs, true),
+            "verificationMethod": self.verification_method,
+            "proofPurpose": self.proof_purpose,
+        });
+        if let Some(challenge) = self.challenge.to_owned() {
+            config["challenge"] = json!(challenge) // EIDSYS-429
+        }
+
+        if let Some(ctx) = self.context.to_owned() {
+            config["@context"] = json!(ctx);
+        }
+        extend_proof_config(&mut config, &self.extensions);
+        config
+    }
 
-        value["created"] =
-            serde_json::Value::String(self.created.to_rfc3339_opts(SecondsFormat::Secs, true));
+    /// Construct a serde_json::Value from this DataIntegrityProof, i.e. its [`DataIntegrityProof::proof_config`] along with the `proofValue`,
+    /// so that the very same proof is delivered by [`DataIntegrityProof::from`] again.
+    #[inline]
+    #[expect(clippy::indexing_slicing, reason = "panic-safe indexing")]
+    pub fn json_value(&self) -> Result<serde_json::Value, DidSidekicksError> {
+        let mut value = self.proof_config();
+        value["proofValue"] = JsonString(self.proof_value.to_owned());
         Ok(value)
     }
 
-    /// Delivers first available update key
+    /// Emits this DataIntegrityProof (see [`DataIntegrityProof::json_value`]) as JSON text according to the supplied `options`.
+    #[inline]
+    pub fn to_json(&self, options: EmitOptions) -> Result<String, DidSidekicksError> {
+        emit_json(&self.json_value()?, options)
+    }
+
+    /// Delivers first available update key.
     #[inline]
     #[expect(
         clippy::indexing_slicing,
@@ -327,12 +627,300 @@ impl DataIntegrityProof {
             ))
         }
     }
+    /// Ensures the challenge of this proof matches the `expected` one (e.g. the `versionId` of the DID log entry the proof is bound to).
+    ///
+    /// A missing or different challenge is denoted by [`DidSidekicksError::ChallengeMismatch`].
+    #[inline]
+    pub fn verify_challenge(&self, expected: &str) -> Result<(), DidSidekicksError> {
+        match self.challenge.as_deref() {
+            Some(challenge) if challenge == expected => Ok(()),
+            challenge => Err(DidSidekicksError::ChallengeMismatch {
+                expected: expected.to_owned(),
+                actual: challenge.unwrap_or_default().to_owned(),
+            }),
+        }
+    }
+
+    /// Whether this proof duplicates the supplied `other` one, i.e. both are created by the same key (`verificationMethod`)
+    /// at the same time (`created`), while featuring the same signature (`proofValue`).
+    #[inline]
+    pub fn is_duplicate_of(&self, other: &Self) -> bool {
+        self.verification_method == other.verification_method
+            && self.created == other.created
+            && self.proof_value == other.proof_value
+    }
+}
+
+/// Orders the supplied proof set deterministically, i.e. by `verificationMethod`, `created` and `proofValue` (in this order).
+///
+/// The same proof set is thus always serialized (and hashed) the same way, regardless of the order its proofs were aggregated in.
+#[inline]
+pub fn sort_proofs(proofs: &mut [DataIntegrityProof]) {
+    proofs.sort_by(|left, right| {
+        left.verification_method
+            .cmp(&right.verification_method)
+            .then_with(|| left.created.cmp(&right.created))
+            .then_with(|| left.proof_value.cmp(&right.proof_value))
+    });
+}
+
+/// Delivers the (ascending) indices of all the proofs of the supplied proof set duplicating any of the preceding ones.
+///
+/// See [`DataIntegrityProof::is_duplicate_of`], e.g. as accumulated by aggregating the proofs of several witnesses.
+#[inline]
+pub fn find_duplicate_proofs(proofs: &[DataIntegrityProof]) -> Vec<usize> {
+    let mut seen = HashSet::new();
+    proofs
+        .iter()
+        .enumerate()
+        .filter(|&(_, proof)| {
+            !seen.insert((
+                proof.verification_method.as_str(),
+                proof.created,
+                proof.proof_value.as_str(),
+            ))
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Normalizes the supplied proof set, i.e. orders it deterministically (see [`sort_proofs`]).
+///
+/// If `strip_duplicates` is set, all the duplicate proofs (see [`DataIntegrityProof::is_duplicate_of`]) are stripped as well.
+///
+/// As a duplicate proof adds nothing to a proof set, stripping it neither invalidates nor weakens the proof set.
+#[inline]
+pub fn normalize_proofs(proofs: &mut Vec<DataIntegrityProof>, strip_duplicates: bool) {
+    sort_proofs(proofs);
+    if strip_duplicates {
+        // as sorted, all the duplicates of a proof are adjacent to it
+        proofs.dedup_by(|proof, previous| proof.is_duplicate_of(previous));
+    }
+}
+
+/// The name of the DID method parameter listing the keys authorized to update the DID.
+pub const PARAM_UPDATE_KEYS: &str = "updateKeys";
+/// The name of the DID method parameter listing the hashes of the keys committed to (pre-rotation).
+pub const PARAM_NEXT_KEY_HASHES: &str = "nextKeyHashes";
+
+/// Confirms the `verificationMethod` of the supplied (controller) `proof` corresponds to a key listed in `updateKeys`.
+///
+/// The `updateKeys` are featured by the supplied DID method `parameters` in force
+/// (as delivered by e.g. `DidResolver::collect_did_method_parameters_map`).
+///
+/// CAUTION In case of pre-rotation, the `updateKeys` in force are the ones published by the very DID log entry being authorized,
+/// which must have been committed to by the previous one (see [`authorize_update_keys`]).
+///
+/// Returns the authorized key (multikey), otherwise:
+/// - [`DidSidekicksError::KeyNotFound`], if `updateKeys` is not available (i.e. no keys to look up),
+/// - [`DidSidekicksError::KeyNotAuthorized`], if the key is not listed in `updateKeys` (e.g. as it is empty).
+#[inline]
+pub fn authorize_proof<S: BuildHasher>(
+    proof: &DataIntegrityProof,
+    parameters: &HashMap<String, Arc<DidMethodParameter>, S>,
+) -> Result<String, DidSidekicksError> {
+    let key = proof.extract_update_key()?;
+
+    let Some(param) = parameters.get(PARAM_UPDATE_KEYS) else {
+        return Err(DidSidekicksError::KeyNotFound(format!(
+            "no '{PARAM_UPDATE_KEYS}' DID method parameter available to look up '{key}'"
+        )));
+    };
+    // CAUTION An empty array is featured by no string array value, yet it is available (and authorizes no key at all)
+    if param.is_array() && param.is_empty_array() {
+        return Err(DidSidekicksError::KeyNotAuthorized(format!(
+            "proof's verificationMethod '{}' refers to a key not listed in '{PARAM_UPDATE_KEYS}', as it is empty",
+            proof.verification_method
+        )));
+    }
+    let Some(update_keys) = param.get_string_array_value() else {
+        return Err(DidSidekicksError::KeyNotFound(format!(
+            "no '{PARAM_UPDATE_KEYS}' DID method parameter available to look up '{key}'"
+        )));
+    };
+    if update_keys.contains(&key) {
+        return Ok(key);
+    }
+
+    Err(DidSidekicksError::KeyNotAuthorized(format!(
+        "proof's verificationMethod '{}' refers to a key not listed in '{PARAM_UPDATE_KEYS}'",
+        proof.verification_method
+    )))
+}
+
+/// Whether pre-rotation is active as of the supplied DID method `parameters`, i.e. `nextKeyHashes` is non-empty.
+#[inline]
+pub fn is_pre_rotation_active<S: BuildHasher>(
+    parameters: &HashMap<String, Arc<DidMethodParameter>, S>,
+) -> bool {
+    parameters
+        .get(PARAM_NEXT_KEY_HASHES)
+        .and_then(|param| param.get_string_array_value())
+        .is_some_and(|hashes| !hashes.is_empty())
+}
+
+/// Confirms each of the supplied (newly published) `update_keys` was committed to by the supplied (previous) DID method `parameters`.
+///
+/// In case of pre-rotation (see [`is_pre_rotation_active`]), the hash of each key
+/// (see [`JcsSha256Hasher::base58btc_encode_multihash_multikey`]) must be listed in `nextKeyHashes`.
+///
+/// Any key not committed to is denoted by [`DidSidekicksError::KeyNotAuthorized`], whereas nothing is checked
+/// unless pre-rotation is active.
+#[inline]
+pub fn authorize_update_keys<S: BuildHasher>(
+    update_keys: &[String],
+    parameters: &HashMap<String, Arc<DidMethodParameter>, S>,
+) -> Result<(), DidSidekicksError> {
+    let Some(next_key_hashes) = parameters
+        .get(PARAM_NEXT_KEY_HASHES)
+        .and_then(|param| param.get_string_array_value())
+        .filter(|hashes| !hashes.is_empty())
+    else {
+        return Ok(());
+    };
+
+    for key in update_keys {
+        if !next_key_hashes
+            .contains(&JcsSha256Hasher::default().base58btc_encode_multihash_multikey(key))
+        {
+            return Err(DidSidekicksError::KeyNotAuthorized(format!(
+                "'{PARAM_UPDATE_KEYS}' features the key '{key}' not committed to in '{PARAM_NEXT_KEY_HASHES}' (pre-rotation)"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Verifies the `eddsa-jcs-2022` proof of the supplied (secured) JSON document, created by a `did:key` verification method,
+/// thus delivering the `did:key` DID of the signer.
+///
+/// A missing or invalid proof is denoted by [`DidSidekicksError::InvalidDataIntegrityProof`].
+#[inline]
+pub fn verify_secured_document(
+    secured_document: &serde_json::Value,
+) -> Result<String, DidSidekicksError> {
+    let (unsecured, proof) = split_secured_document(secured_document)?;
+    verify_did_key_proof(&DataIntegrityProof::from(proof.to_string())?, &unsecured)
+}
+
+/// Verifies all the proofs of the proof set of the supplied (secured) JSON document (see [`DataIntegrityProof::from_proof_set`]).
+///
+/// Each of them must be created by a `did:key` verification method, thus the `did:key` DIDs of all the signers are delivered (in order).
+///
+/// Any proof featuring an unsupported cryptosuite is treated according to the supplied `policy`, i.e. it might be skipped
+/// (e.g. in case of mixed-suite proof sets from other ecosystems). However, at least one proof must remain to be verified.
+/// A missing or invalid proof is denoted by [`DidSidekicksError::InvalidDataIntegrityProof`].
+#[inline]
+pub fn verify_secured_document_proof_set(
+    secured_document: &serde_json::Value,
+    policy: UnsupportedCryptosuitePolicy,
+) -> Result<Vec<String>, DidSidekicksError> {
+    let (unsecured, proof_set) = split_secured_document(secured_document)?;
+    let proofs = DataIntegrityProof::from_proof_set(&proof_set.to_string(), policy)?;
+    if proofs.is_empty() {
+        return Err(DidSidekicksError::InvalidDataIntegrityProof(
+            "The supplied document features no proof of a supported cryptosuite".to_owned(),
+        ));
+    }
+    proofs
+        .iter()
+        .map(|proof| verify_did_key_proof(proof, &unsecured))
+        .collect()
+}
+
+/// Splits the supplied (secured) JSON document into the unsecured document (i.e. lacking `proof`) and its proof (set).
+fn split_secured_document(
+    secured_document: &serde_json::Value,
+) -> Result<(serde_json::Value, serde_json::Value), DidSidekicksError> {
+    let mut unsecured = secured_document.clone();
+    match unsecured
+        .as_object_mut()
+        .and_then(|doc| doc.remove("proof"))
+    {
+        Some(proof @ JsonArray(_)) => Ok((unsecured, proof)),
+        Some(_) | None => Err(DidSidekicksError::InvalidDataIntegrityProof(
+            "The supplied document features no proof".to_owned(),
+        )),
+    }
+}
+
+/// Verifies the supplied `eddsa-jcs-2022` proof (of the supplied `unsecured` document) created by a `did:key`
+/// verification method, thus delivering the `did:key` DID of the signer.
+fn verify_did_key_proof(
+    proof: &DataIntegrityProof,
+    unsecured: &serde_json::Value,
+) -> Result<String, DidSidekicksError> {
+    let signer_key = proof.extract_update_key()?;
+    let suite = EddsaJcs2022Cryptosuite {
+        verifying_key: Some(Ed25519VerifyingKey::from_multibase(&signer_key)?),
+        signing_key: None,
+        verification_options: VerificationOptions::default(),
+    };
+    suite.verify_proof(proof, unsecured)?;
+    Ok(format!("did:key:{signer_key}"))
+}
+
+/// The (unsecured) document a proof is verified against (see [`VCDataIntegrity::verify_proof`]), i.e. either of:
+/// - the document itself, hence JCS-canonicalized and SHA2-256 hashed internally,
+/// - the canonical (JCS) bytes of the document, hence SHA2-256 hashed internally or
+/// - the precomputed (hex-encoded) SHA2-256 hash of the canonical bytes of the document.
+///
+/// Thus, callers do not have to know what exactly is to be hashed (e.g. the document lacking its `proof`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "further enum variants may be added in the future"
+)]
+pub enum ProofDocument<'doc> {
+    /// The (unsecured) document, i.e. lacking any `proof`.
+    Value(&'doc serde_json::Value),
+    /// The canonical (JCS, see https://www.rfc-editor.org/rfc/rfc8785) UTF-8 bytes of the (unsecured) document.
+    CanonicalBytes(&'doc [u8]),
+    /// The (lower case) hex-encoded SHA2-256 hash of the canonical bytes of the (unsecured) document.
+    Hash(&'doc str),
+}
+
+impl ProofDocument<'_> {
+    /// Delivers the (hex-encoded) SHA2-256 hash of the canonical bytes of the document, while hashing it if needed.
+    ///
+    /// Any canonicalization failure is denoted by [`DidSidekicksError::SerializationFailed`].
+    #[inline]
+    pub fn to_hash(&self) -> Result<String, DidSidekicksError> {
+        match *self {
+            Self::Value(document) => JcsSha256Hasher::default()
+                .encode_hex(document)
+                .map_err(|err| DidSidekicksError::SerializationFailed(err.to_string())),
+            Self::CanonicalBytes(bytes) => Ok(hex::encode(Sha256::digest(bytes))),
+            Self::Hash(hash) => Ok(hash.to_owned()),
+        }
+    }
+}
+
+impl<'doc> From<&'doc serde_json::Value> for ProofDocument<'doc> {
+    #[inline]
+    fn from(document: &'doc serde_json::Value) -> Self {
+        Self::Value(document)
+    }
+}
+
+impl<'doc> From<&'doc str> for ProofDocument<'doc> {
+    #[inline]
+    fn from(hash: &'doc str) -> Self {
+        Self::Hash(hash)
+    }
+}
+
+impl<'doc> From<&'doc String> for ProofDocument<'doc> {
+    #[inline]
+    fn from(hash: &'doc String) -> Self {
+        Self::Hash(hash)
+    }
 }
 
 /// This is the main entry point for proof generation and validation of a given verifiable credential.
 ///
 /// Function in this class are based on algorithm section in the vc-data-integrity spec
-/// https://www.w3.org/TR/vc-data-integrity/#algorithms
+/// https://www.w3.org/TR/vc-data-integrity/#algorithms.
 pub trait VCDataIntegrity {
     // See https://www.w3.org/TR/vc-data-integrity/#add-proof
     fn add_proof(
@@ -341,10 +929,11 @@ pub trait VCDataIntegrity {
         options: &CryptoSuiteProofOptions,
     ) -> Result<serde_json::Value, DidSidekicksError>;
     // See https://www.w3.org/TR/vc-data-integrity/#verify-proof
-    fn verify_proof(
+    /// The `document` is any of [`ProofDocument`], e.g. the (unsecured) document itself or its precomputed hash.
+    fn verify_proof<'doc, D: Into<ProofDocument<'doc>>>(
         &self,
         proof: &DataIntegrityProof,
-        doc_hash: &str,
+        document: D,
     ) -> Result<(), DidSidekicksError>;
 }
 
@@ -352,26 +941,144 @@ pub trait VCDataIntegrity {
 pub struct EddsaJcs2022Cryptosuite {
     pub verifying_key: Option<Ed25519VerifyingKey>,
     pub signing_key: Option<Ed25519SigningKey>,
+    /// Controls the proof verification, i.e. [`Ed25519VerificationMode::Strict`] by default.
+    pub verification_options: VerificationOptions,
 }
 
 // NOTE Only https://www.w3.org/TR/vc-di-eddsa/#eddsa-jcs-2022 is currently supported
 impl VCDataIntegrity for EddsaJcs2022Cryptosuite {
     // See https://www.w3.org/TR/vc-di-eddsa/#create-proof-eddsa-jcs-2022
     #[inline]
-    #[expect(clippy::indexing_slicing, reason = "panic-safe indexing")]
     fn add_proof(
         &self,
         unsecured_document: &serde_json::Value,
         options: &CryptoSuiteProofOptions,
     ) -> Result<serde_json::Value, DidSidekicksError> {
+        self.add_proof_with_record(unsecured_document, options)
+            .map(|(secured_document, _)| secured_document)
+    }
+
+    // See https://www.w3.org/TR/vc-di-eddsa/#proof-verification-eddsa-jcs-2022
+    // See https://www.w3.org/TR/vc-di-eddsa/#verify-proof-eddsa-jcs-2022
+
+    #[inline]
+    fn verify_proof<'doc, D: Into<ProofDocument<'doc>>>(
+        &self,
+        proof: &DataIntegrityProof,
+        document: D,
+    ) -> Result<(), DidSidekicksError> {
+        let document_2 = document.into();
+        observe_verification(
+            "data_integrity_proof",
+            |err: &DidSidekicksError| format!("{:?}", err.kind()),
+            || self.verify_eddsa_jcs_2022_proof(proof, &document_2.to_hash()?),
+        )
+    }
+}
+
+/// The exact input to be signed in order to secure a document (e.g. a DID log entry), as delivered by
+/// [`EddsaJcs2022Cryptosuite::signing_input`]. Intended for external (e.g. remote) signers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct ProofSigningInput {
+    /// The proof configuration, i.e. the proof lacking `proofValue`.
+    pub proof_config: serde_json::Value,
+    /// The (64 bytes of) `hashData` to be signed, i.e. `proofConfigHash` joined with `transformedDocumentHash`
+    /// (see https://www.w3.org/TR/vc-di-eddsa/#hashing-eddsa-jcs-2022).
+    pub hash_data: Vec<u8>,
+}
+
+/// A structured record of a proof creation, as delivered by [`EddsaJcs2022Cryptosuite::add_proof_with_record`].
+///
+/// Intended for write-ahead audit logging, i.e. to record exactly what was signed before the proof leaves the device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct IssuanceRecord {
+    /// The (hex-encoded) SHA-256 hash of the JCS-canonicalized unsecured document (i.e. `transformedDocumentHash`).
+    pub document_hash: String,
+    /// The JCS-canonicalized proof configuration (i.e. the proof lacking `proofValue`).
+    pub canonical_options: String,
+    /// The `verificationMethod` of the proof, i.e. the id of the signing key.
+    pub key_id: String,
+    /// The `created` datetime of the proof.
+    pub created: DateTime<Utc>,
+    /// The `cryptosuite` of the proof, e.g. `eddsa-jcs-2022`.
+    pub crypto_suite: String,
+    /// The `proofValue` of the proof, i.e. the (multibase-encoded) signature of the `hashData`.
+    pub proof_value: String,
+}
+
+impl IssuanceRecord {
+    /// Emits this record as JSON text according to the supplied `options`, e.g. for the purpose of audit logging.
+    #[inline]
+    pub fn to_json(&self, options: EmitOptions) -> Result<String, DidSidekicksError> {
+        emit_json(
+            &json!({
+                "documentHash": self.document_hash,
+                "canonicalOptions": self.canonical_options,
+                "keyId": self.key_id,
+                "created": self.created.to_rfc3339_opts(SecondsFormat::Secs, true),
+                "cryptosuite": self.crypto_suite,
+                "proofValue": self.proof_value,
+            }),
+            options,
+        )
+    }
+}
+
+impl EddsaJcs2022Cryptosuite {
+    /// The same as [`VCDataIntegrity::add_proof`], while additionally delivering the [`IssuanceRecord`] of the created proof.
+    #[inline]
+    pub fn add_proof_with_record(
+        &self,
+        unsecured_document: &serde_json::Value,
+        options: &CryptoSuiteProofOptions,
+    ) -> Result<(serde_json::Value, IssuanceRecord), DidSidekicksError> {
+        let signing_input = Self::signing_input(unsecured_document, options)?;
+        let signature = match self.signing_key.to_owned() {
+            Some(signing_key) => signing_key.sign_bytes(&signing_input.hash_data)?,
+            None => return Err(DidSidekicksError::InvalidDataIntegrityProof(
+                "Invalid eddsa cryptosuite. Signing key is missing but required for proof creation"
+                    .to_owned(),
+            )),
+        };
+        //let signature_hex = hex::encode(signature.signature.to_bytes()); // checkpoint
+
+        let record = IssuanceRecord {
+            // the hashData is proofConfigHash joined with transformedDocumentHash
+            document_hash: hex::encode(signing_input.hash_data.get(32..).unwrap_or_default()),
+            canonical_options: emit_json(&signing_input.proof_config, EmitOptions::CANONICAL)?,
+            key_id: options.verification_method.to_owned(),
+            created: options.created,
+            crypto_suite: options.crypto_suite.to_string(),
+            proof_value: signature.to_multibase(),
+        };
+        Ok((
+            Self::attach_proof(unsecured_document, signing_input, &signature),
+            record,
+        ))
+    }
+
+    /// Delivers the exact canonical input (`hashData`) that must be signed in order to add a proof to the supplied
+    /// `unsecured_document` (e.g. a DID log entry), thus decoupling the hashing from the signing (as done by [`VCDataIntegrity::add_proof`]).
+    ///
+    /// Once signed (e.g. by a remote signer), the proof is attached by means of [`EddsaJcs2022Cryptosuite::attach_proof`].
+    ///
+    /// An unsupported cryptosuite is denoted by [`DidSidekicksError::UnsupportedCryptosuite`],
+    /// whereas any other failure is denoted by [`DidSidekicksError::InvalidDataIntegrityProof`].
+    #[inline]
+    #[expect(clippy::indexing_slicing, reason = "panic-safe indexing")]
+    pub fn signing_input(
+        unsecured_document: &serde_json::Value,
+        options: &CryptoSuiteProofOptions,
+    ) -> Result<ProofSigningInput, DidSidekicksError> {
         // According to https://www.w3.org/TR/vc-di-eddsa/#proof-configuration-eddsa-jcs-2022:
         // If proofConfig.type is not set to DataIntegrityProof or proofConfig.cryptosuite is not set to eddsa-jcs-2022,
         // an error MUST be raised that SHOULD convey an error type of PROOF_GENERATION_ERROR.
         if !matches!(options.crypto_suite, CryptoSuiteType::EddsaJcs2022) {
-            return Err(DidSidekicksError::InvalidDataIntegrityProof(format!(
-                "Unsupported proof's cryptosuite. Only '{}' is supported",
-                CryptoSuiteType::EddsaJcs2022
-            )));
+            return Err(DidSidekicksError::UnsupportedCryptosuite(
+                options.crypto_suite.to_string(),
+            ));
         }
         if options.proof_type != "DataIntegrityProof" {
             return Err(DidSidekicksError::InvalidDataIntegrityProof(
@@ -396,6 +1103,9 @@ impl VCDataIntegrity for EddsaJcs2022Cryptosuite {
             proof_without_proof_value["@context"] = json!(ctx);
         }
 
+        check_proof_extensions(&options.extensions)?;
+        extend_proof_config(&mut proof_without_proof_value, &options.extensions);
+
         // See https://www.w3.org/TR/vc-di-eddsa/#hashing-eddsa-jcs-2022
         // 1) Let transformedDocumentHash be the result of applying the SHA-256 (SHA-2 with 256-bit output)
         //    cryptographic hashing algorithm [RFC6234] to the transformedDocument. transformedDocumentHash will be exactly 32 bytes in size.
@@ -432,49 +1142,36 @@ impl VCDataIntegrity for EddsaJcs2022Cryptosuite {
             }
         };
 
-        let signature = match self.signing_key.to_owned() {
-            Some(signing_key) => signing_key.sign_bytes(&decoded_hex_data),
-            None => return Err(DidSidekicksError::InvalidDataIntegrityProof(
-                "Invalid eddsa cryptosuite. Signing key is missing but required for proof creation"
-                    .to_owned(),
-            )),
-        };
-        //let signature_hex = hex::encode(signature.signature.to_bytes()); // checkpoint
-
-        let proof_value = signature.to_multibase();
-        proof_without_proof_value["proofValue"] = JsonString(proof_value);
-        let mut secured_document = unsecured_document.clone();
-        secured_document["proof"] = json!([proof_without_proof_value]);
-        Ok(secured_document)
+        Ok(ProofSigningInput {
+            proof_config: proof_without_proof_value,
+            hash_data: decoded_hex_data,
+        })
     }
 
-    // See https://www.w3.org/TR/vc-di-eddsa/#proof-verification-eddsa-jcs-2022
-    // See https://www.w3.org/TR/vc-di-eddsa/#verify-proof-eddsa-jcs-2022
-
+    /// Attaches the proof (featuring the supplied `signature` of [`ProofSigningInput::hash_data`]) to the supplied `unsecured_document`,
+    /// thus delivering the secured document. The counterpart of [`EddsaJcs2022Cryptosuite::signing_input`].
+    ///
+    /// CAUTION The `signature` is not verified here, hence it is up to the caller to verify the secured document.
     #[inline]
     #[expect(clippy::indexing_slicing, reason = "panic-safe indexing")]
-    fn verify_proof(
+    pub fn attach_proof(
+        unsecured_document: &serde_json::Value,
+        signing_input: ProofSigningInput,
+        signature: &Ed25519Signature,
+    ) -> serde_json::Value {
+        let mut proof = signing_input.proof_config;
+        proof["proofValue"] = JsonString(signature.to_multibase());
+        let mut secured_document = unsecured_document.clone();
+        secured_document["proof"] = json!([proof]);
+        secured_document
+    }
+
+    fn verify_eddsa_jcs_2022_proof(
         &self,
         proof: &DataIntegrityProof,
         doc_hash: &str,
     ) -> Result<(), DidSidekicksError> {
-        // CAUTION Beware that only serde_json::json macro is able to serialize proof.created field properly (if used directly)!
-        //         (thanks to #[serde(with = "ts_seconds")])
-        let mut proof_without_proof_value = json!({
-            "type": proof.proof_type,
-            "cryptosuite": proof.crypto_suite,
-            // The proof.created is not used directly here, due to more error-prone conversion that requires #[serde(with = "ts_seconds")] attribute
-            "created": proof.created.to_rfc3339_opts(SecondsFormat::Secs, true),
-            "verificationMethod": proof.verification_method,
-            "proofPurpose": proof.proof_purpose,
-        });
-        if let Some(challenge) = proof.challenge.to_owned() {
-            proof_without_proof_value["challenge"] = json!(challenge) // EIDSYS-429
-        }
-
-        if let Some(ctx) = proof.context.to_owned() {
-            proof_without_proof_value["@context"] = json!(ctx);
-        }
+        let proof_without_proof_value = proof.proof_config();
 
         // See https://www.w3.org/TR/vc-di-eddsa/#hashing-eddsa-jcs-2022
         // 2) Let proofConfigHash be the result of applying the SHA-256 (SHA-2 with 256-bit output)
@@ -498,10 +1195,20 @@ impl VCDataIntegrity for EddsaJcs2022Cryptosuite {
                         "Cannot decode hash value from hex.".to_owned()
                     ))
                 };
-                // Strictly verify a signature on a message with this keypair's public key.
-                // It may respond with: "signature error: Verification equation was not satisfied"
-                verifying_key.verifying_key.verify_strict(&hash_data_decoded, &signature.signature)
-                    .map_err(|err| DidSidekicksError::InvalidDataIntegrityProof(format!("{err}")))
+                // Any signature already verified (by the very same key and options) is not verified again, if caching is enabled
+                let cache_origin = format!(
+                    "{proof_hash}{doc_hash}|{}|{}|{:?}",
+                    verifying_key.to_multibase(), proof.proof_value, self.verification_options
+                );
+                SIGNATURE_CACHE.get_or_try_insert_with(&cache_origin, || {
+                    // Verify a signature on a message with this keypair's public key (strictly, unless configured otherwise).
+                    // It may respond with: "signature error: Verification equation was not satisfied"
+                    verifying_key.verify_with_options(&hash_data_decoded, &signature.signature, self.verification_options)
+                        .map_err(|err| DidSidekicksError::SignatureInvalid {
+                            verification_method: proof.verification_method.to_owned(),
+                            reason: err.to_string(),
+                        })
+                })
             }
             None => Err(DidSidekicksError::InvalidDataIntegrityProof(
                 "Invalid eddsa cryptosuite. Verifying key is missing but required for proof verification".to_owned()
@@ -519,84 +1226,290 @@ impl VCDataIntegrity for EddsaJcs2022Cryptosuite {
     clippy::indexing_slicing,
     reason = "panic-safe as long as test case setup is correct"
 )]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
 mod test {
-    use crate::ed25519::{Ed25519SigningKey, Ed25519VerifyingKey, MultiBaseConverter as _};
-    use crate::errors::DidSidekicksErrorKind;
+    use crate::cache::{get_cache_stats, set_caching_enabled};
+    use crate::did_method_parameters::DidMethodParameter;
+    use crate::ed25519::{
+        Ed25519KeyPair, Ed25519SigningKey, Ed25519VerifyingKey, MultiBaseConverter as _,
+        VerificationOptions,
+    };
+    use crate::errors::{DidSidekicksError, DidSidekicksErrorKind};
     use crate::jcs_sha256_hasher::JcsSha256Hasher;
+    use crate::json_emission::{emit_json, EmitOptions};
     use crate::test::assert_error;
     use crate::vc_data_integrity::{
-        CryptoSuiteProofOptions, DataIntegrityProof, EddsaJcs2022Cryptosuite, VCDataIntegrity as _,
+        authorize_proof, authorize_update_keys, find_duplicate_proofs, normalize_proofs,
+        verify_secured_document_proof_set, CryptoSuiteProofOptions, CryptoSuiteType,
+        DataIntegrityProof, EddsaJcs2022Cryptosuite, ProofDocument, UnsupportedCryptosuitePolicy,
+        VCDataIntegrity as _, PARAM_NEXT_KEY_HASHES, PARAM_UPDATE_KEYS,
     };
     use chrono::DateTime;
     use rstest::rstest;
     use serde_json::json;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    #[rstest]
+    fn test_proof_options_roundtrip() {
+        let options = CryptoSuiteProofOptions::from_verification_method("did:key:z6Mk#z6Mk")
+            .with_proof_purpose("assertionMethod")
+            .with_challenge("1-QmChallenge");
+        let json = options.to_json(EmitOptions::COMPACT).unwrap();
+        assert!(json.contains(r#""cryptosuite":"eddsa-jcs-2022""#));
+        assert!(json.contains(r#""version":1"#));
+        assert!(!json.contains("@context"));
+
+        let restored = CryptoSuiteProofOptions::from_json(&json).unwrap();
+        assert_eq!(restored.created, options.created); // at full precision
+        assert_eq!(restored.verification_method, options.verification_method);
+        assert_eq!(restored.proof_purpose, options.proof_purpose);
+        assert_eq!(restored.challenge, options.challenge);
+        assert_eq!(restored.to_json(EmitOptions::COMPACT).unwrap(), json);
+
+        // the very same proof is created after restore
+        let key_pair = Ed25519KeyPair::generate();
+        let suite = EddsaJcs2022Cryptosuite {
+            verifying_key: Some(key_pair.verifying_key.clone()),
+            signing_key: Some(key_pair.signing_key),
+            verification_options: VerificationOptions::default(),
+        };
+        let doc = json!({"versionId": "1-QmChallenge"});
+        assert_eq!(
+            suite.add_proof(&doc, &options).unwrap(),
+            suite.add_proof(&doc, &restored).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[expect(
+        deprecated,
+        reason = "the deprecated constructor is compared against its successor"
+    )]
+    fn test_proof_options_builder() {
+        let created = DateTime::parse_from_rfc3339("2023-02-24T23:36:38Z")
+            .unwrap()
+            .to_utc();
+        let context = vec!["https://www.w3.org/ns/credentials/v2".to_owned()];
+        let built = CryptoSuiteProofOptions::from_verification_method("did:key:z6Mk#z6Mk")
+            .with_crypto_suite(CryptoSuiteType::EddsaJcs2022)
+            .with_created(created)
+            .with_proof_purpose("assertionMethod")
+            .with_context(context.clone())
+            .with_challenge("1-QmChallenge");
+        let legacy = CryptoSuiteProofOptions::new(
+            None,
+            Some(created),
+            "did:key:z6Mk#z6Mk".to_owned(),
+            Some("assertionMethod".to_owned()),
+            Some(context),
+            Some("1-QmChallenge".to_owned()),
+        );
+        assert_eq!(
+            built.to_json(EmitOptions::COMPACT).unwrap(),
+            legacy.to_json(EmitOptions::COMPACT).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case(
+        r#"{"type":"DataIntegrityProof"}"#,
+        "Unsupported proof options version: null"
+    )]
+    #[case(r#"{"version":2}"#, "Unsupported proof options version: 2")]
+    #[case(r#"{"version":1,"type":"DataIntegrityProof"}"#, "Malformed proof options")]
+    #[case(
+        r#"{"version":1,"type":"DataIntegrityProof","cryptosuite":"unknown-2025","created":"2025-01-01T00:00:00Z","verificationMethod":"","proofPurpose":""}"#,
+        "Malformed proof options"
+    )]
+    fn test_invalid_proof_options(#[case] json: &str, #[case] error_string: &str) {
+        assert_error(
+            CryptoSuiteProofOptions::from_json(json),
+            DidSidekicksErrorKind::DeserializationFailed,
+            error_string,
+        );
+    }
 
     #[rstest]
     // emtpy proof
-    #[case("[]", "Empty proof array detected")]
+    #[case(
+        "[]",
+        "Empty proof array detected",
+        DidSidekicksErrorKind::InvalidIntegrityProof
+    )]
     // two proofs
-    #[case("[\"proof1\", \"proof2\"]", "A single proof is currently supported")]
+    #[case(
+        "[\"proof1\", \"proof2\"]",
+        "A single proof is currently supported",
+        DidSidekicksErrorKind::InvalidIntegrityProof
+    )]
     // invalid json
     #[case(
         "[{\"key:}]",
-        "Malformed proof format, expected single-element JSON array"
+        "Malformed proof format, expected single-element JSON array",
+        DidSidekicksErrorKind::InvalidIntegrityProof
     )]
     // invalid type
     #[case(
         "[{\"type\":\"invalidType\", \"cryptosuite\":\"eddsa-jcs-2022\", \"created\":\"2012-12-12T12:12:12Z\", \"verificationMethod\": \"did:key:123\", \"proofPurpose\":\"authentication\"}]",
-        "Unsupported proof's type"
+        "Unsupported proof's type",
+        DidSidekicksErrorKind::InvalidIntegrityProof,
     )]
     // unsupported cryptosuite
     #[case(
         "[{\"type\":\"DataIntegrityProof\", \"cryptosuite\":\"unsupportedCrypto\", \"created\":\"2012-12-12T12:12:12Z\", \"verificationMethod\": \"did:key:123\", \"proofPurpose\":\"authentication\"}]",
-        "Unsupported proof's cryptosuite"
+        "unsupportedCrypto",
+        DidSidekicksErrorKind::UnsupportedCryptosuite,
     )]
     // invalid created date
     #[case("[{\"type\":\"DataIntegrityProof\", \"cryptosuite\":\"eddsa-jcs-2022\", \"created\":\"invalidDate\", \"verificationMethod\": \"did:key:123\", \"proofPurpose\":\"authentication\"}]",
-        "Invalid proof's creation datetime format"
+        "Invalid proof's creation datetime format",
+        DidSidekicksErrorKind::InvalidIntegrityProof,
     )]
     // invalid verification method
     #[case("[{\"type\":\"DataIntegrityProof\", \"cryptosuite\":\"eddsa-jcs-2022\", \"created\":\"2012-12-12T12:12:12Z\", \"verificationMethod\": \"invalidMethod\", \"proofPurpose\":\"authentication\"}]",
-        "Unsupported proof's verificationMethod"
+        "Unsupported proof's verificationMethod",
+        DidSidekicksErrorKind::InvalidIntegrityProof,
     )]
     // invalid proof purpose
     #[case("[{\"type\":\"DataIntegrityProof\", \"cryptosuite\":\"eddsa-jcs-2022\", \"created\":\"2012-12-12T12:12:12Z\", \"verificationMethod\": \"did:key:123\", \"proofPurpose\":\"invalidPurpose\"}]",
-        "Unsupported proof's proofPurpose"
+        "Unsupported proof's proofPurpose",
+        DidSidekicksErrorKind::InvalidIntegrityProof,
     )]
     // invalid @context
     #[case("[{\"type\":\"DataIntegrityProof\", \"cryptosuite\":\"eddsa-jcs-2022\", \"created\":\"2012-12-12T12:12:12Z\", \"verificationMethod\": \"did:key:123\", \"proofPurpose\":\"authentication\", \"@context\":\"invalidContext\"}]",
-        "Invalid format of 'context' entry"
+        "Invalid format of 'context' entry",
+        DidSidekicksErrorKind::InvalidIntegrityProof,
     )]
     #[case("[{\"type\":\"DataIntegrityProof\", \"cryptosuite\":\"eddsa-jcs-2022\", \"created\":\"2012-12-12T12:12:12Z\", \"verificationMethod\": \"did:key:123\", \"proofPurpose\":\"authentication\", \"@context\":[\"validContext\", true, 3]}]",
-        "Invalid type of 'context' entry"
+        "Invalid type of 'context' entry",
+        DidSidekicksErrorKind::InvalidIntegrityProof,
     )]
     // invalid proof challenge
     #[case("[{\"type\":\"DataIntegrityProof\", \"cryptosuite\":\"eddsa-jcs-2022\", \"created\":\"2012-12-12T12:12:12Z\", \"verificationMethod\": \"did:key:123\", \"proofPurpose\":\"authentication\"}]",
-        "Missing proofValue parameter. Expected a proofValue of type string."
+        "Missing proofValue parameter. Expected a proofValue of type string.",
+        DidSidekicksErrorKind::InvalidIntegrityProof,
     )]
     #[case("[{\"type\":\"DataIntegrityProof\", \"cryptosuite\":\"eddsa-jcs-2022\", \"created\":\"2012-12-12T12:12:12Z\", \"verificationMethod\": \"did:key:123\", \"proofPurpose\":\"authentication\", \"challenge\":[false, 2]}]",
-        "Wrong format of proof's challenge parameter"
+        "Wrong format of proof's challenge parameter",
+        DidSidekicksErrorKind::InvalidIntegrityProof,
     )]
     // invalid proof challenge
     #[case("[{\"type\":\"DataIntegrityProof\", \"cryptosuite\":\"eddsa-jcs-2022\", \"created\":\"2012-12-12T12:12:12Z\", \"verificationMethod\": \"did:key:123\", \"proofPurpose\":\"authentication\", \"challenge\":\"1-hash\"}]",
-        "Missing proofValue parameter"
+        "Missing proofValue parameter",
+        DidSidekicksErrorKind::InvalidIntegrityProof,
     )]
     #[case("[{\"type\":\"DataIntegrityProof\", \"cryptosuite\":\"eddsa-jcs-2022\", \"created\":\"2012-12-12T12:12:12Z\", \"verificationMethod\": \"did:key:123\", \"proofPurpose\":\"authentication\", \"challenge\":\"1-hash\", \"proofValue\":5}]",
-        "Wrong format of proofValue parameter"
+        "Wrong format of proofValue parameter",
+        DidSidekicksErrorKind::InvalidIntegrityProof,
     )]
     fn test_invalid_proof_parsing(
         #[case] input_str: String,
         #[case] error_string: &str,
+        #[case] error_kind: DidSidekicksErrorKind,
     ) -> Result<(), Box<dyn std::error::Error>> {
         assert_error(
             DataIntegrityProof::from(input_str),
-            DidSidekicksErrorKind::InvalidIntegrityProof,
+            error_kind,
             error_string,
         );
 
         Ok(())
     }
 
+    #[rstest]
+    fn test_verify_challenge() {
+        let proof = DataIntegrityProof::from(json!([{"type": "DataIntegrityProof", "cryptosuite": "eddsa-jcs-2022", "created": "2012-12-12T12:12:12Z", "verificationMethod": "did:key:123", "proofPurpose": "authentication", "challenge": "1-hash", "proofValue": "z123"}]).to_string()).unwrap();
+        proof.verify_challenge("1-hash").unwrap();
+        assert_error(
+            proof.verify_challenge("2-hash"),
+            DidSidekicksErrorKind::ChallengeMismatch,
+            "expected '2-hash', got '1-hash'",
+        );
+    }
+
+    #[rstest]
+    #[case("unsupportedCrypto")]
+    #[case("ecdsa-rdfc-2019")]
+    fn test_unsupported_cryptosuite_parsing(#[case] crypto_suite: &str) {
+        let proof = json!([{"type": "DataIntegrityProof", "cryptosuite": crypto_suite, "created": "2012-12-12T12:12:12Z", "verificationMethod": "did:key:123", "proofPurpose": "authentication", "proofValue": "z123"}]);
+        let err = DataIntegrityProof::from(proof.to_string()).err().unwrap();
+        assert_eq!(err, DidSidekicksError::UnsupportedCryptosuite(crypto_suite.to_owned()));
+    }
+
+    #[rstest]
+    fn test_verify_proof_set() {
+        let secure = |key_pair: &Ed25519KeyPair, document: &serde_json::Value| {
+            let key = key_pair.verifying_key.to_multibase();
+            let suite = EddsaJcs2022Cryptosuite {
+                verifying_key: None,
+                signing_key: Some(key_pair.signing_key.clone()),
+                verification_options: VerificationOptions::default(),
+            };
+            let options =
+                CryptoSuiteProofOptions::from_verification_method(&format!("did:key:{key}#{key}"));
+            suite.add_proof(document, &options).unwrap()["proof"][0].to_owned()
+        };
+        let (key_pair_a, key_pair_b) = (Ed25519KeyPair::generate(), Ed25519KeyPair::generate());
+        let document = json!({"id": "urn:example:proof-set"});
+        let mut foreign_proof = secure(&key_pair_a, &document);
+        foreign_proof["cryptosuite"] = json!("bbs-2023");
+        let mut secured = document.clone();
+        secured["proof"] = json!([
+            secure(&key_pair_a, &document),
+            foreign_proof,
+            secure(&key_pair_b, &document)
+        ]);
+
+        assert_error(
+            verify_secured_document_proof_set(&secured, UnsupportedCryptosuitePolicy::Reject),
+            DidSidekicksErrorKind::UnsupportedCryptosuite,
+            "bbs-2023",
+        );
+        let signers =
+            verify_secured_document_proof_set(&secured, UnsupportedCryptosuitePolicy::Skip)
+                .unwrap();
+        assert_eq!(
+            signers,
+            vec![
+                format!("did:key:{}", key_pair_a.verifying_key.to_multibase()),
+                format!("did:key:{}", key_pair_b.verifying_key.to_multibase()),
+            ]
+        );
+
+        // every (supported) proof must verify
+        secured["proof"][2]["proofValue"] = secured["proof"][0]["proofValue"].to_owned();
+
+        verify_secured_document_proof_set(&secured, UnsupportedCryptosuitePolicy::Skip)
+            .unwrap_err();
+
+        // at least one (supported) proof must remain
+        secured["proof"] = json!([secured["proof"][1]]);
+        assert!(DataIntegrityProof::from_proof_set(
+            &secured["proof"].to_string(),
+            UnsupportedCryptosuitePolicy::Skip
+        )
+        .unwrap()
+        .is_empty());
+        assert_error(
+            verify_secured_document_proof_set(&secured, UnsupportedCryptosuitePolicy::Skip),
+            DidSidekicksErrorKind::InvalidIntegrityProof,
+            "no proof of a supported cryptosuite",
+        );
+    }
+
+    #[rstest]
+    #[case("[]", "Empty proof array detected")]
+    #[case("{}", "expected JSON array")]
+    #[case("[\"proof1\"]", "Missing proof's type")]
+    fn test_invalid_proof_set_parsing(#[case] input_str: &str, #[case] error_string: &str) {
+        assert_error(
+            DataIntegrityProof::from_proof_set(input_str, UnsupportedCryptosuitePolicy::Skip),
+            DidSidekicksErrorKind::InvalidIntegrityProof,
+            error_string,
+        );
+    }
+
     #[rstest]
     fn test_cryptosuite_add_and_verify_proof_example() {
         // From https://www.w3.org/TR/vc-di-eddsa/#example-credential-without-proof-0
@@ -624,17 +1537,10 @@ mod test {
             .unwrap(); // SCID
 
         // From https://www.w3.org/TR/vc-di-eddsa/#example-proof-options-document-1
-        let options = CryptoSuiteProofOptions::new(
-            None,
-            Some(DateTime::parse_from_rfc3339("2023-02-24T23:36:38Z").unwrap().to_utc()),
-            "did:key:z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2#z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2".to_owned(),
-            Some("assertionMethod".to_owned()),
-            Some(vec![
+        let options = CryptoSuiteProofOptions::from_verification_method("did:key:z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2#z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2").with_created(DateTime::parse_from_rfc3339("2023-02-24T23:36:38Z").unwrap().to_utc()).with_proof_purpose("assertionMethod").with_context(vec![
                 "https://www.w3.org/ns/credentials/v2".to_owned(),
                 "https://www.w3.org/ns/credentials/examples/v2".to_owned(),
-            ]),
-            None,
-        );
+            ]);
 
         // From https://www.w3.org/TR/vc-di-eddsa/#example-private-and-public-keys-for-signature-1
         let suite = EddsaJcs2022Cryptosuite {
@@ -650,6 +1556,7 @@ mod test {
                 )
                 .unwrap(),
             ),
+            verification_options: VerificationOptions::default(),
         };
 
         let secured_document = suite
@@ -684,6 +1591,194 @@ mod test {
             suite.verify_proof(&data_integrity_proof, &doc_hash).is_ok(),
             "Sanity check failed"
         );
+
+        // the very same secured document, but signed externally
+        let signing_input =
+            EddsaJcs2022Cryptosuite::signing_input(&credentials_without_proof_obj, &options).unwrap();
+        assert_eq!(signing_input.hash_data.len(), 64);
+        assert_eq!(hex::encode(&signing_input.hash_data[32..]), doc_hash);
+        let signature = suite
+            .signing_key
+            .as_ref()
+            .unwrap()
+            .sign_bytes(&signing_input.hash_data)
+            .unwrap();
+        assert_eq!(
+            EddsaJcs2022Cryptosuite::attach_proof(&credentials_without_proof_obj, signing_input, &signature),
+            secured_document
+        );
+
+        // the very same secured document, along with its issuance record
+        let (secured_document_with_record, record) = suite
+            .add_proof_with_record(&credentials_without_proof_obj, &options)
+            .unwrap();
+        assert_eq!(secured_document_with_record, secured_document);
+        assert_eq!(record.document_hash, doc_hash);
+        assert_eq!(record.key_id, options.verification_method);
+        assert_eq!(record.created, options.created);
+        assert_eq!(record.crypto_suite, "eddsa-jcs-2022");
+        assert_eq!(record.proof_value, proof[0]["proofValue"]);
+        let mut proof_config = proof[0].clone();
+        proof_config.as_object_mut().unwrap().remove("proofValue");
+        assert_eq!(
+            record.canonical_options,
+            serde_json_canonicalizer::to_string(&proof_config).unwrap()
+        );
+        let record_json: serde_json::Value =
+            serde_json::from_str(&record.to_json(EmitOptions::COMPACT).unwrap()).unwrap();
+        assert_eq!(record_json["created"], "2023-02-24T23:36:38Z");
+    }
+
+    #[rstest]
+    fn test_verified_signature_cache() {
+        set_caching_enabled(true);
+
+        let key_pair = Ed25519KeyPair::generate();
+        let key = key_pair.verifying_key.to_multibase();
+        let suite = EddsaJcs2022Cryptosuite {
+            verifying_key: Some(key_pair.verifying_key),
+            signing_key: Some(key_pair.signing_key),
+            verification_options: VerificationOptions::default(),
+        };
+        let document = json!({"id": "urn:example:signature-cache"});
+        let options =
+            CryptoSuiteProofOptions::from_verification_method(&format!("did:key:{key}#{key}"));
+        let secured_document = suite.add_proof(&document, &options).unwrap();
+        let proof =
+            DataIntegrityProof::from(secured_document["proof"].to_string()).unwrap();
+        let doc_hash = JcsSha256Hasher::default().encode_hex(&document).unwrap();
+
+        let hits = || {
+            get_cache_stats()
+                .into_iter()
+                .find(|stats| stats.name == "signature")
+                .unwrap()
+                .hits
+        };
+        suite.verify_proof(&proof, &doc_hash).unwrap();
+        let hits_before = hits();
+        suite.verify_proof(&proof, &doc_hash).unwrap(); // cached
+        assert!(hits() > hits_before);
+
+        // a failed verification is never cached
+        let other_doc_hash = JcsSha256Hasher::default()
+            .encode_hex(&json!({"id": "urn:example:other"}))
+            .unwrap();
+        suite.verify_proof(&proof, &other_doc_hash).unwrap_err();
+        suite.verify_proof(&proof, &other_doc_hash).unwrap_err();
+    }
+
+    #[rstest]
+    fn test_verify_proof_document_variants() {
+        let key_pair = Ed25519KeyPair::generate();
+        let key = key_pair.verifying_key.to_multibase();
+        let suite = EddsaJcs2022Cryptosuite {
+            verifying_key: Some(key_pair.verifying_key),
+            signing_key: Some(key_pair.signing_key),
+            verification_options: VerificationOptions::default(),
+        };
+        let document = json!({"b": 1, "a": "urn:example:proof-document"});
+        let options =
+            CryptoSuiteProofOptions::from_verification_method(&format!("did:key:{key}#{key}"));
+        let secured_document = suite.add_proof(&document, &options).unwrap();
+        let proof = DataIntegrityProof::from(secured_document["proof"].to_string()).unwrap();
+        let doc_hash = JcsSha256Hasher::default().encode_hex(&document).unwrap();
+        let canonical_bytes = emit_json(&document, EmitOptions::CANONICAL).unwrap();
+
+        assert_eq!(ProofDocument::Value(&document).to_hash().unwrap(), doc_hash);
+        assert_eq!(
+            ProofDocument::CanonicalBytes(canonical_bytes.as_bytes())
+                .to_hash()
+                .unwrap(),
+            doc_hash
+        );
+        suite.verify_proof(&proof, &document).unwrap();
+        suite
+            .verify_proof(
+                &proof,
+                ProofDocument::CanonicalBytes(canonical_bytes.as_bytes()),
+            )
+            .unwrap();
+        suite.verify_proof(&proof, &doc_hash).unwrap();
+        suite.verify_proof(&proof, doc_hash.as_str()).unwrap();
+
+        // the (non-canonical) serialization of the document is not to be hashed as is
+        suite
+            .verify_proof(
+                &proof,
+                ProofDocument::CanonicalBytes(document.to_string().as_bytes()),
+            )
+            .unwrap_err();
+    }
+
+    #[rstest]
+    fn test_proof_extensions() {
+        let key_pair = Ed25519KeyPair::generate();
+        let key = key_pair.verifying_key.to_multibase();
+        let suite = EddsaJcs2022Cryptosuite {
+            verifying_key: Some(key_pair.verifying_key),
+            signing_key: Some(key_pair.signing_key),
+            verification_options: VerificationOptions::default(),
+        };
+        let document = json!({"id": "urn:example:proof-extensions"});
+        let options =
+            CryptoSuiteProofOptions::from_verification_method(&format!("did:key:{key}#{key}"))
+                .with_extension("expires", json!("2030-01-01T00:00:00Z"))
+                .unwrap()
+                .with_extension("profile", json!({"name": "example-profile", "level": 2}))
+                .unwrap();
+        let restored =
+            CryptoSuiteProofOptions::from_json(&options.to_json(EmitOptions::COMPACT).unwrap())
+                .unwrap();
+        assert_eq!(restored.extensions, options.extensions);
+
+        let secured_document = suite.add_proof(&document, &options).unwrap();
+        assert_eq!(secured_document["proof"][0]["profile"]["level"], 2);
+
+        // parse -> verify -> serialize
+        let proof = DataIntegrityProof::from(secured_document["proof"].to_string()).unwrap();
+        assert_eq!(proof.extensions["expires"], "2030-01-01T00:00:00Z");
+        suite.verify_proof(&proof, &document).unwrap();
+        assert_eq!(proof.json_value().unwrap(), secured_document["proof"][0]);
+        // a proof set (i.e. a JSON array) is expected by DataIntegrityProof::from
+        let reparsed = DataIntegrityProof::from(format!(
+            "[{}]",
+            proof.to_json(EmitOptions::COMPACT).unwrap()
+        ))
+        .unwrap();
+        suite.verify_proof(&reparsed, &document).unwrap();
+
+        // the extensions are covered by the signature
+        let mut tampered = proof.clone();
+        tampered
+            .extensions
+            .insert("expires".to_owned(), json!("2099-01-01T00:00:00Z"));
+        suite.verify_proof(&tampered, &document).unwrap_err();
+        let mut stripped = proof;
+        stripped.extensions.clear();
+        suite.verify_proof(&stripped, &document).unwrap_err();
+    }
+
+    #[rstest]
+    #[case("proofValue", json!("z123"), "The proof extension 'proofValue' is reserved")]
+    #[case("@context", json!([]), "The proof extension '@context' is reserved")]
+    #[case(
+        "nonce",
+        json!({"values": [u64::pow(2, 53)]}),
+        "The proof extension 'nonce' features an integer beyond 9007199254740991"
+    )]
+    #[case("nonce", json!(i64::pow(-2, 53)), "features an integer beyond")]
+    fn test_invalid_proof_extension(
+        #[case] name: &str,
+        #[case] value: serde_json::Value,
+        #[case] error_string: &str,
+    ) {
+        let options = CryptoSuiteProofOptions::from_verification_method("did:key:z6Mk#z6Mk");
+        assert_error(
+            options.with_extension(name, value),
+            DidSidekicksErrorKind::InvalidIntegrityProof,
+            error_string,
+        );
     }
 
     #[rstest]
@@ -713,17 +1808,10 @@ mod test {
             .unwrap();
 
         // From https://www.w3.org/TR/vc-di-eddsa/#example-proof-options-document-1
-        let options = CryptoSuiteProofOptions::new(
-            None,
-            Some(DateTime::parse_from_rfc3339("2023-02-24T23:36:38Z").unwrap().to_utc()),
-            "did:key:z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2#z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2".to_owned(),
-            Some("assertionMethod".to_owned()),
-            Some(vec![
+        let options = CryptoSuiteProofOptions::from_verification_method("did:key:z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2#z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2").with_created(DateTime::parse_from_rfc3339("2023-02-24T23:36:38Z").unwrap().to_utc()).with_proof_purpose("assertionMethod").with_context(vec![
                 "https://www.w3.org/ns/credentials/v2".to_owned(),
                 "https://www.w3.org/ns/credentials/examples/v2".to_owned(),
-            ]),
-            Some(format!("1-{}", scid)),
-        );
+            ]).with_challenge(&format!("1-{}", scid));
 
         // From https://www.w3.org/TR/vc-di-eddsa/#example-private-and-public-keys-for-signature-1
         let suite = EddsaJcs2022Cryptosuite {
@@ -739,6 +1827,7 @@ mod test {
                 )
                 .unwrap(),
             ),
+            verification_options: VerificationOptions::default(),
         };
 
         let secured_document = suite
@@ -776,4 +1865,172 @@ mod test {
             "Sanity check failed"
         );
     }
+
+    #[rstest]
+    #[case(Some(vec![UPDATE_KEY]), None, None)]
+    // the hashes of the keys committed to (pre-rotation) authorize no key on their own
+    #[case(Some(vec![UPDATE_KEY]), Some(vec!["QmOther"]), None)]
+    #[case(None, Some(vec![UPDATE_KEY_HASH]), Some(DidSidekicksErrorKind::KeyNotFound))]
+    #[case(Some(vec!["z6MkOther"]), Some(vec![UPDATE_KEY_HASH]), Some(DidSidekicksErrorKind::KeyNotAuthorized))]
+    #[case(Some(vec!["z6MkOther"]), None, Some(DidSidekicksErrorKind::KeyNotAuthorized))]
+    #[case(Some(vec![]), None, Some(DidSidekicksErrorKind::KeyNotAuthorized))]
+    #[case(None, None, Some(DidSidekicksErrorKind::KeyNotFound))]
+    fn test_authorize_proof(
+        #[case] update_keys: Option<Vec<&str>>,
+        #[case] next_key_hashes: Option<Vec<&str>>,
+        #[case] expected_error_kind: Option<DidSidekicksErrorKind>,
+    ) {
+        let proof = DataIntegrityProof {
+            proof_type: "DataIntegrityProof".to_owned(),
+            crypto_suite: "eddsa-jcs-2022".to_owned(),
+            crypto_suite_type: None,
+            created: DateTime::default(),
+            verification_method: format!("did:key:{UPDATE_KEY}#{UPDATE_KEY}"),
+            proof_purpose: "authentication".to_owned(),
+            context: None,
+            challenge: None,
+            proof_value: String::new(),
+            extensions: serde_json::Map::new(),
+        };
+
+        let res = authorize_proof(&proof, &parameters_of(update_keys, next_key_hashes));
+        match expected_error_kind {
+            None => assert_eq!(res.unwrap(), UPDATE_KEY),
+            Some(kind) => assert_error(res, kind, UPDATE_KEY),
+        }
+    }
+
+    #[rstest]
+    #[case(vec![UPDATE_KEY], None, true)]
+    #[case(vec![UPDATE_KEY], Some(vec![]), true)]
+    #[case(vec![UPDATE_KEY], Some(vec!["QmOther", UPDATE_KEY_HASH]), true)]
+    #[case(vec![], Some(vec![UPDATE_KEY_HASH]), true)]
+    #[case(vec![UPDATE_KEY], Some(vec!["QmOther"]), false)]
+    #[case(vec![UPDATE_KEY, "z6MkOther"], Some(vec![UPDATE_KEY_HASH]), false)]
+    fn test_authorize_update_keys(
+        #[case] update_keys: Vec<&str>,
+        #[case] next_key_hashes: Option<Vec<&str>>,
+        #[case] is_committed: bool,
+    ) {
+        let keys = update_keys
+            .into_iter()
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+        let res = authorize_update_keys(&keys, &parameters_of(None, next_key_hashes));
+        if is_committed {
+            res.unwrap();
+        } else {
+            assert_error(
+                res,
+                DidSidekicksErrorKind::KeyNotAuthorized,
+                "not committed to in 'nextKeyHashes'",
+            );
+        }
+    }
+
+    /// Delivers the DID method parameters featuring the supplied `updateKeys` and `nextKeyHashes` (if any).
+    fn parameters_of(
+        update_keys: Option<Vec<&str>>,
+        next_key_hashes: Option<Vec<&str>>,
+    ) -> HashMap<String, Arc<DidMethodParameter>> {
+        let mut parameters = HashMap::new();
+        for (name, values) in [
+            (PARAM_UPDATE_KEYS, update_keys),
+            (PARAM_NEXT_KEY_HASHES, next_key_hashes),
+        ] {
+            if let Some(vals) = values {
+                parameters.insert(
+                    name.to_owned(),
+                    Arc::new(
+                        DidMethodParameter::try_from_value(
+                            name,
+                            vals.into_iter().map(str::to_owned).collect::<Vec<_>>(),
+                        )
+                        .unwrap(),
+                    ),
+                );
+            }
+        }
+        parameters
+    }
+
+    fn proof_of(key: &str, created: &str, proof_value: &str) -> DataIntegrityProof {
+        DataIntegrityProof {
+            proof_type: "DataIntegrityProof".to_owned(),
+            crypto_suite: "eddsa-jcs-2022".to_owned(),
+            crypto_suite_type: None,
+            created: DateTime::parse_from_rfc3339(created).unwrap().to_utc(),
+            verification_method: format!("did:key:{key}#{key}"),
+            proof_purpose: "assertionMethod".to_owned(),
+            context: None,
+            challenge: None,
+            proof_value: proof_value.to_owned(),
+            extensions: serde_json::Map::new(),
+        }
+    }
+
+    #[rstest]
+    fn test_proof_set_normalization() {
+        let proof_a = proof_of("z6MkA", "2012-12-12T12:12:12Z", "zA");
+        let proof_b = proof_of("z6MkB", "2012-12-12T12:12:12Z", "zB");
+        // same key and signature, but created at another time
+        let proof_a_later = proof_of("z6MkA", "2013-12-12T12:12:12Z", "zA");
+        let aggregated = vec![
+            proof_b.clone(),
+            proof_a_later.clone(),
+            proof_a.clone(),
+            proof_b.clone(),
+            proof_a.clone(),
+        ];
+
+        assert!(proof_a.is_duplicate_of(&proof_a.clone()));
+        assert!(!proof_a.is_duplicate_of(&proof_a_later));
+        assert!(!proof_a.is_duplicate_of(&proof_b));
+        assert_eq!(find_duplicate_proofs(&aggregated), vec![3, 4]);
+
+        let mut sorted = aggregated.clone();
+        normalize_proofs(&mut sorted, false);
+        let verification_methods = |proofs: &[DataIntegrityProof]| {
+            proofs
+                .iter()
+                .map(|proof| {
+                    format!(
+                        "{}@{}",
+                        proof.verification_method,
+                        proof.created.timestamp()
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(sorted.len(), aggregated.len());
+        assert_eq!(
+            verification_methods(&sorted),
+            verification_methods(&[
+                proof_a.clone(),
+                proof_a.clone(),
+                proof_a_later.clone(),
+                proof_b.clone(),
+                proof_b.clone()
+            ])
+        );
+
+        // the order proofs were aggregated in does not matter
+        let mut reversed = aggregated.iter().rev().cloned().collect::<Vec<_>>();
+        normalize_proofs(&mut reversed, true);
+        let mut stripped = aggregated;
+        normalize_proofs(&mut stripped, true);
+        assert!(find_duplicate_proofs(&stripped).is_empty());
+        assert_eq!(
+            verification_methods(&stripped),
+            verification_methods(&[proof_a, proof_a_later, proof_b])
+        );
+        assert_eq!(
+            verification_methods(&reversed),
+            verification_methods(&stripped)
+        );
+    }
+
+    const UPDATE_KEY: &str = "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2";
+    // as computed by JcsSha256Hasher::base58btc_encode_multihash_multikey
+    const UPDATE_KEY_HASH: &str = "QmZgy1yHPsNWRYCMoGtGeRuGDKNpUPW4iuRxLTAgkZoqKH";
 }
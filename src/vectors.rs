@@ -0,0 +1,209 @@
+// SPDX-License-Identifier: MIT
+
+//! Deterministic (canonical) test vectors, intended to be consumed by the test suites of the (Kotlin/Swift) bindings
+//! in order to prove their parity with this crate.
+//!
+//! All vectors are derived from fixed inputs (keys, documents and timestamps), hence the very same JSON
+//! is generated on every run. Any change of the generated JSON denotes a (potentially breaking) behavioural change.
+
+use crate::ed25519::{did_key_from_verifying_key, Ed25519KeyPair, MultiBaseConverter as _};
+use crate::errors::DidSidekicksError;
+use crate::jcs_sha256_hasher::JcsSha256Hasher;
+use crate::json_emission::{emit_json, EmitOptions};
+use crate::multibase::MultibaseEncoderDecoder;
+use crate::panic_boundary::catch_panic;
+use crate::resource_digest::{compute_digest_multibase, compute_digest_sri};
+use serde_json::{json, Value};
+
+/// The version of the test vectors format, to be incremented on any change of the structure of the generated JSON.
+pub const TEST_VECTORS_FORMAT_VERSION: u64 = 1;
+
+/// The fixed signing key (multikey) all key-related vectors are derived from.
+///
+/// Taken from https://www.w3.org/TR/vc-di-eddsa/#example-private-and-public-keys-for-signature-1.
+const SIGNING_KEY_MULTIBASE: &str = "z3u2en7t5LR2WtQH5PfFqMqwVHBeXouLzo6haApm8XHqvjxq";
+
+/// Generates the whole suite of test vectors as (pretty-printed) JSON text, i.e.:
+/// - `multibase`: pairs of (hex-encoded) bytes and their base58btc multibase encoding,
+/// - `multihash`: (hex-encoded) SHA2-256 multihashes of UTF-8 strings,
+/// - `entry_hashes`: base58btc-encoded multihashes of JCS-canonicalized JSON (as used for SCIDs and entry hashes),
+/// - `digests`: `digestMultibase` and `digestSRI` values of UTF-8 strings,
+/// - `keys`: the (multikey) verifying key and `did:key` DID of the fixed signing key and
+/// - `proofs`: `eddsa-jcs-2022` proofs by the fixed signing key (empty, unless the `vc-data-integrity` feature is enabled).
+///
+/// A UniFFI-compliant function.
+#[inline]
+pub fn generate_test_vectors() -> Result<String, DidSidekicksError> {
+    catch_panic(
+        || {
+            let key_pair = Ed25519KeyPair::from(SIGNING_KEY_MULTIBASE)?;
+
+            let vectors = json!({
+                "version": TEST_VECTORS_FORMAT_VERSION,
+                "multibase": multibase_vectors(),
+                "multihash": multihash_vectors(),
+                "entry_hashes": entry_hash_vectors()?,
+                "digests": digest_vectors(),
+                "keys": {
+                    "signing_key": SIGNING_KEY_MULTIBASE,
+                    "verifying_key": key_pair.verifying_key.to_multibase(),
+                    "did_key": did_key_from_verifying_key(&key_pair.verifying_key),
+                },
+                "proofs": proof_vectors(&key_pair)?,
+            });
+            emit_json(&vectors, EmitOptions::PRETTY)
+        },
+        DidSidekicksError::InternalError,
+    )
+}
+
+#[expect(clippy::single_call_fn, reason = "..")]
+fn multibase_vectors() -> Value {
+    let inputs: [&[u8]; 4] = [b"", &[0x00], &[0x00, 0x00, 0xff], b"Hello World!"];
+    inputs
+        .iter()
+        .map(|bytes| {
+            json!({
+                "bytes": hex::encode(bytes),
+                "multibase": MultibaseEncoderDecoder::default().encode_base58btc(bytes),
+            })
+        })
+        .collect()
+}
+
+#[expect(clippy::single_call_fn, reason = "..")]
+fn multihash_vectors() -> Value {
+    ["", "abc", "Merkle\u{2013}Damg\u{e5}rd"]
+        .iter()
+        .map(|input| {
+            json!({
+                "input": input,
+                "multihash": hex::encode(JcsSha256Hasher::default().encode_multihash((*input).to_owned())),
+            })
+        })
+        .collect()
+}
+
+#[expect(clippy::single_call_fn, reason = "..")]
+fn entry_hash_vectors() -> Result<Value, DidSidekicksError> {
+    [
+        json!({}),
+        json!({"b": [1, 2.5, true, null], "a": "\u{e9}\u{1f600}"}),
+        json!({"versionId": "{SCID}", "parameters": {"method": "did:webvh:1.0", "witness": {"threshold": 2}}}),
+    ]
+    .iter()
+    .map(|input| {
+        let entry_hash = JcsSha256Hasher::default()
+            .base58btc_encode_multihash(input)
+            .map_err(|err| DidSidekicksError::SerializationFailed(err.to_string()))?;
+        Ok(json!({
+            "input": input,
+            "canonical": emit_json(input, EmitOptions::CANONICAL)?,
+            "entry_hash": entry_hash,
+        }))
+    })
+    .collect()
+}
+
+#[expect(clippy::single_call_fn, reason = "..")]
+fn digest_vectors() -> Value {
+    ["", "Hello World!"]
+        .iter()
+        .map(|input| {
+            json!({
+                "input": input,
+                "digest_multibase": compute_digest_multibase(input.as_bytes().to_vec()),
+                "digest_sri": compute_digest_sri(input.as_bytes().to_vec()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "vc-data-integrity")]
+#[expect(clippy::single_call_fn, reason = "..")]
+fn proof_vectors(key_pair: &Ed25519KeyPair) -> Result<Value, DidSidekicksError> {
+    use crate::ed25519::VerificationOptions;
+    use crate::vc_data_integrity::{
+        CryptoSuiteProofOptions, EddsaJcs2022Cryptosuite, VCDataIntegrity as _,
+    };
+    use chrono::DateTime;
+
+    let suite = EddsaJcs2022Cryptosuite {
+        verifying_key: Some(key_pair.verifying_key.clone()),
+        signing_key: Some(key_pair.signing_key.clone()),
+        verification_options: VerificationOptions::default(),
+    };
+    let created = DateTime::from_timestamp(1_677_281_798, 0).ok_or_else(|| {
+        DidSidekicksError::InternalError("Invalid timestamp of test vectors".to_owned())
+    })?; // 2023-02-24T23:36:38Z
+    let verification_method = format!(
+        "{}#{}",
+        did_key_from_verifying_key(&key_pair.verifying_key),
+        key_pair.verifying_key.to_multibase()
+    );
+
+    [
+        (json!({"id": "urn:example:1", "name": "Example"}), None),
+        (
+            json!({"versionId": "1-QmA", "state": {"id": "did:example:123"}}),
+            Some("1-QmA"),
+        ),
+    ]
+    .into_iter()
+    .map(|(document, challenge)| {
+        let mut options = CryptoSuiteProofOptions::from_verification_method(&verification_method)
+            .with_created(created);
+        if let Some(version_id) = challenge {
+            options = options.with_challenge(version_id);
+        }
+        Ok(json!({
+            "document": document,
+            "secured_document": suite.add_proof(&document, &options)?,
+        }))
+    })
+    .collect()
+}
+
+#[cfg(not(feature = "vc-data-integrity"))]
+#[expect(
+    clippy::unnecessary_wraps,
+    reason = "aligned with the vc-data-integrity variant"
+)]
+#[expect(clippy::single_call_fn, reason = "..")]
+fn proof_vectors(_key_pair: &Ed25519KeyPair) -> Result<Value, DidSidekicksError> {
+    Ok(json!([]))
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(clippy::shadow_reuse, reason = "..")]
+mod test {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_generate_test_vectors() {
+        let vectors = generate_test_vectors().unwrap();
+        // deterministic
+        assert_eq!(vectors, generate_test_vectors().unwrap());
+
+        let vectors: Value = serde_json::from_str(&vectors).unwrap();
+        // From https://www.w3.org/TR/vc-di-eddsa/#example-private-and-public-keys-for-signature-1
+        assert_eq!(
+            vectors.pointer("/keys/verifying_key").unwrap(),
+            "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2"
+        );
+        // From https://multiformats.io/multihash/#sha2-256---256-bits-aka-sha256
+        assert_eq!(
+            vectors.pointer("/multihash/2/multihash").unwrap(),
+            "122041dd7b6443542e75701aa98a0c235951a28a0d851b11564d20022ab11d2589a8"
+        );
+        assert_eq!(
+            vectors.pointer("/multibase/3/multibase").unwrap(),
+            "z2NEpo7TZRRrLZSi2U"
+        );
+    }
+}
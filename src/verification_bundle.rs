@@ -0,0 +1,874 @@
+// SPDX-License-Identifier: MIT
+
+//! A portable verification bundle, packaging a DID log along with everything else required to verify it offline.
+//!
+//! I.e. the witness proofs (`did-witness.json`), the JSON schemas of DID log entries and snapshots of status lists.
+//!
+//! Intended for offline verification scenarios (e.g. border control), where none of the resources may be fetched on demand.
+//! The bundle is a single JSON document, e.g.:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "didLog": "<DID log in JSON Lines format>",
+//!   "witnessProofs": "<the content of did-witness.json>",
+//!   "schemas": [{"uri": "https://...", "digestMultibase": "z...", "content": "<JSON schema>"}],
+//!   "statusLists": [{"uri": "https://...", "digestMultibase": "z...", "content": "<status list (credential)>"}]
+//! }
+//! ```
+//!
+//! Each bundled resource (schema or status list) is pinned by its `digestMultibase` (see [`crate::resource_digest`]),
+//! whereas the digests are to be supplied by a trusted source other than the bundle itself (see [`VerificationBundle::verify`]).
+//!
+//! The outcome of a verification may be wrapped into a signed "verification receipt" (see [`VerifiedBundle::to_receipt`]),
+//! so downstream systems may rely on it (see [`verify_receipt`]) without re-running the verification.
+
+use crate::bounded_json::from_str_bounded;
+use crate::did_jsonschema::DidLogEntryValidator;
+use crate::did_log::DidLog;
+use crate::did_log_resolution::ResolutionOptions;
+use crate::did_witness::{WitnessConfig, WitnessProofCollection};
+use crate::ed25519::{
+    did_key_from_verifying_key, did_key_verification_method_from_verifying_key, Ed25519KeyPair,
+    VerificationOptions,
+};
+use crate::errors::DidSidekicksError;
+use crate::json_emission::{emit_json, EmitOptions};
+use crate::resource_digest::{compute_digest_multibase, verify_digest_multibase};
+use crate::vc_data_integrity::{
+    verify_secured_document, CryptoSuiteProofOptions, EddsaJcs2022Cryptosuite, VCDataIntegrity as _,
+};
+use crate::version_id::VersionId;
+use chrono::{DateTime, SecondsFormat, Utc};
+use core::hash::BuildHasher;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// The version of the verification bundle format, to be incremented on any (breaking) change of its structure.
+pub const VERIFICATION_BUNDLE_FORMAT_VERSION: u64 = 1;
+
+/// A single resource (e.g. a JSON schema or a status list) bundled along with its `digestMultibase`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct BundledResource {
+    /// The URI the resource has been fetched from (and is referred by).
+    pub uri: String,
+    pub digest_multibase: String,
+    pub content: String,
+}
+
+impl BundledResource {
+    /// Bundles the supplied `content` fetched from the supplied `uri`, thus pinning it by its (freshly computed) `digestMultibase`.
+    #[inline]
+    pub fn new(uri: &str, content: &str) -> Self {
+        Self {
+            uri: uri.to_owned(),
+            digest_multibase: compute_digest_multibase(content.as_bytes().to_vec()),
+            content: content.to_owned(),
+        }
+    }
+
+    /// Verifies the content of this resource against its `digestMultibase`.
+    ///
+    /// If the content does not match, [`DidSidekicksError::DeserializationFailed`] is returned.
+    fn verify_digest(&self) -> Result<(), DidSidekicksError> {
+        if verify_digest_multibase(
+            self.content.as_bytes().to_vec(),
+            self.digest_multibase.to_owned(),
+        )? {
+            return Ok(());
+        }
+        Err(DidSidekicksError::DeserializationFailed(format!(
+            "The content of bundled resource '{}' does not match its digestMultibase '{}'",
+            self.uri, self.digest_multibase
+        )))
+    }
+}
+
+/// A portable verification bundle (see module documentation).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct VerificationBundle {
+    pub version: u64,
+    /// The DID log in JSON Lines format.
+    pub did_log: String,
+    /// The content of the witness file (`did-witness.json`), if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub witness_proofs: Option<String>,
+    /// The JSON schemas each DID log entry must comply with.
+    #[serde(default)]
+    pub schemas: Vec<BundledResource>,
+    /// Snapshots of status lists (e.g. referenced by credentials issued by the DID subject).
+    #[serde(default)]
+    pub status_lists: Vec<BundledResource>,
+}
+
+/// The outcome of a (successful) [`VerificationBundle::verify`] call, hence it may not be constructed otherwise.
+#[derive(Debug, Clone)]
+pub struct VerifiedBundle {
+    /// The verified DID log.
+    did_log: DidLog,
+    /// All the (non-blank) DID log entries, in the order of their appearance.
+    entries: Vec<String>,
+    /// The `versionId`s of all DID log entries, in the order of their appearance.
+    version_ids: Vec<VersionId>,
+    /// The (deduplicated) `did:key` identifiers of all witnesses that have (validly) signed a `versionId`, per `versionId`.
+    verified_witnesses: BTreeMap<String, BTreeSet<String>>,
+    /// The (pinned and verified) snapshots of status lists.
+    status_lists: Vec<BundledResource>,
+}
+
+impl VerificationBundle {
+    /// Packages the supplied DID log (JSON Lines) along with the supplied (optional) witness file content,
+    /// while no schemas nor status lists are bundled yet (see [`Self::with_schema`] and [`Self::with_status_list`]).
+    #[inline]
+    pub fn new(did_log: &str, witness_proofs: Option<&str>) -> Self {
+        Self {
+            version: VERIFICATION_BUNDLE_FORMAT_VERSION,
+            did_log: did_log.to_owned(),
+            witness_proofs: witness_proofs.map(str::to_owned),
+            schemas: Vec::new(),
+            status_lists: Vec::new(),
+        }
+    }
+
+    /// Bundles the supplied JSON `schema` fetched from the supplied `uri`.
+    #[inline]
+    #[must_use]
+    pub fn with_schema(mut self, uri: &str, schema: &str) -> Self {
+        self.schemas.push(BundledResource::new(uri, schema));
+        self
+    }
+
+    /// Bundles the supplied `status_list` snapshot fetched from the supplied `uri`.
+    #[inline]
+    #[must_use]
+    pub fn with_status_list(mut self, uri: &str, status_list: &str) -> Self {
+        self.status_lists
+            .push(BundledResource::new(uri, status_list));
+        self
+    }
+
+    /// The parsing constructor, rejecting any bundle of an unsupported format version.
+    #[inline]
+    pub fn from_json(json: &str) -> Result<Self, DidSidekicksError> {
+        let bundle: Self = from_str_bounded(json)?;
+        if bundle.version != VERIFICATION_BUNDLE_FORMAT_VERSION {
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "Unsupported verification bundle format version {} (expected {VERIFICATION_BUNDLE_FORMAT_VERSION})",
+                bundle.version
+            )));
+        }
+        Ok(bundle)
+    }
+
+    /// Serializes the bundle into JSON, according to the supplied `options`.
+    #[inline]
+    pub fn to_json(&self, options: EmitOptions) -> Result<String, DidSidekicksError> {
+        emit_json(self, options)
+    }
+
+    /// Runs the whole verification pipeline offline, i.e.:
+    /// - each bundled resource (schema or status list) must be pinned by the supplied `pinned_digests` (mapping its URI
+    ///   to its `digestMultibase`, as obtained from a trusted source other than the bundle itself) and match it,
+    /// - each DID log entry is validated against each of bundled schemas,
+    /// - the DID log of the supplied `did` is verified according to the supplied `options` (see [`DidLog::verify`]),
+    ///   i.e. including the entry hash chain, the SCID and the Data Integrity proof of each DID log entry,
+    /// - each witness proof (if any) must refer to a `versionId` of the DID log, whereas only valid proofs are taken into account and
+    /// - each DID log entry must be witnessed according to the `witness` DID method parameter in force as of it (if any),
+    ///   whereas a witness proof for a later `versionId` counts for the earlier ones as well
+    ///   (see [`WitnessProofCollection::validate_weighted_coverage`]).
+    ///
+    /// As no witness DID may be resolved offline, only `did:key` witnesses count towards the witness threshold.
+    /// Any failure is denoted by the very first [`DidSidekicksError`] occurred.
+    #[inline]
+    pub fn verify<S: BuildHasher>(
+        &self,
+        did: &str,
+        pinned_digests: &HashMap<String, String, S>,
+        options: ResolutionOptions,
+    ) -> Result<VerifiedBundle, DidSidekicksError> {
+        for resource in self.schemas.iter().chain(self.status_lists.iter()) {
+            if pinned_digests.get(&resource.uri) != Some(&resource.digest_multibase) {
+                return Err(DidSidekicksError::DeserializationFailed(format!(
+                    "The digestMultibase '{}' of bundled resource '{}' is not pinned",
+                    resource.digest_multibase, resource.uri
+                )));
+            }
+            resource.verify_digest()?;
+        }
+
+        let validators = self
+            .schemas
+            .iter()
+            .map(|schema| {
+                DidLogEntryValidator::try_from_json_schema(&schema.content)
+                    .map(|validator| (schema.uri.as_str(), validator))
+                    .map_err(|err| {
+                        DidSidekicksError::DeserializationFailed(format!(
+                            "Malformed bundled schema '{}': {err}",
+                            schema.uri
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut entries = Vec::new();
+        for (index, line) in self.did_log.lines().enumerate() {
+            let entry = line.trim();
+            if entry.is_empty() {
+                continue;
+            }
+            let line_number = index.saturating_add(1);
+            for (uri, validator) in validators.iter().map(|pair| (pair.0, &pair.1)) {
+                validator.validate_str(entry).map_err(|err| {
+                    DidSidekicksError::InvalidDidDocument(format!(
+                        "DID log entry at line {line_number} does not comply with bundled schema '{uri}': {err}"
+                    ))
+                })?;
+            }
+            entries.push(entry.to_owned());
+        }
+
+        let did_log = DidLog::verify(did, &self.did_log, options)?;
+        let version_ids = did_log
+            .get_entries()
+            .iter()
+            .map(|entry| entry.get_version_id().parse())
+            .collect::<Result<Vec<VersionId>, _>>()?;
+
+        let mut verified_witnesses: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        if let Some(witness_proofs) = self.witness_proofs.as_deref() {
+            for entry in WitnessProofCollection::from_json(witness_proofs)?.entries {
+                if !version_ids.contains(&entry.version_id) {
+                    return Err(DidSidekicksError::InvalidWitnessProof(format!(
+                        "Witness proofs refer to versionId '{}' not featured by the bundled DID log",
+                        entry.version_id
+                    )));
+                }
+                verified_witnesses
+                    .entry(entry.version_id.to_string())
+                    .or_default()
+                    .extend(entry.get_verified_witnesses()?);
+            }
+        }
+        validate_witness_coverage(&did_log, &verified_witnesses)?;
+
+        Ok(VerifiedBundle {
+            did_log,
+            entries,
+            version_ids,
+            verified_witnesses,
+            status_lists: self.status_lists.clone(),
+        })
+    }
+}
+
+/// Ensures each DID log entry of the supplied (verified) `did_log` is witnessed by the supplied `verified_witnesses`
+/// (per `versionId`), according to the `witness` DID method parameter in force as of it (see [`VerificationBundle::verify`]).
+#[expect(clippy::single_call_fn, reason = "..")]
+fn validate_witness_coverage(
+    did_log: &DidLog,
+    verified_witnesses: &BTreeMap<String, BTreeSet<String>>,
+) -> Result<(), DidSidekicksError> {
+    let mut covering_keys = BTreeSet::new();
+    // iterate backwards, so the witnesses of later versions are accumulated
+    for entry in did_log.get_entries().iter().rev() {
+        let version_id = entry.get_version_id();
+        if let Some(witnesses) = verified_witnesses.get(&version_id) {
+            covering_keys.extend(witnesses.iter().cloned());
+        }
+        let Some(config) = WitnessConfig::from_parameters(entry.get_parameters())? else {
+            continue;
+        };
+        let witness_keys = config.did_key_witness_keys();
+        let weight = config
+            .witnesses
+            .iter()
+            .filter(|witness| {
+                covering_keys
+                    .iter()
+                    .any(|key| witness_keys.get(key) == Some(&witness.id))
+            })
+            .fold(0, |total: u64, witness| {
+                total.saturating_add(witness.weight)
+            });
+        if weight < config.threshold {
+            return Err(DidSidekicksError::InvalidWitnessProof(format!(
+                "Insufficient witness coverage for versionId '{version_id}': weight {weight} of required {}",
+                config.threshold
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// The (additional) `type` of a verification receipt (see [`VerifiedBundle::to_receipt`]).
+pub const VERIFICATION_RECEIPT_TYPE: &str = "VerificationReceipt";
+
+/// The base context of a W3C VC, as specified by https://www.w3.org/TR/vc-data-model-2.0/#base-context.
+const VC_CONTEXT_V2: &str = "https://www.w3.org/ns/credentials/v2";
+
+/// The `credentialSubject` of a verification receipt (see [`VerifiedBundle::to_receipt`]), i.e. a summary of a [`VerifiedBundle`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct VerificationReceiptSubject {
+    /// The DID whose DID log has been verified.
+    #[serde(default)]
+    pub did: String,
+    /// The `digestMultibase` (see [`crate::resource_digest`]) of all the (non-blank) DID log entries, joined by a newline.
+    pub did_log_digest_multibase: String,
+    /// The `versionId`s of all DID log entries, in the order of their appearance.
+    pub version_ids: Vec<VersionId>,
+    /// The (deduplicated) `did:key` identifiers of all witnesses that have (validly) signed a `versionId`, per `versionId`.
+    #[serde(default)]
+    pub verified_witnesses: BTreeMap<String, BTreeSet<String>>,
+    /// The `digestMultibase` of each (verified) status list snapshot, per URI.
+    #[serde(default)]
+    pub status_list_digests: BTreeMap<String, String>,
+}
+
+impl VerifiedBundle {
+    /// Delivers the verified DID log.
+    #[inline]
+    pub const fn get_did_log(&self) -> &DidLog {
+        &self.did_log
+    }
+
+    /// Delivers all the (non-blank) DID log entries, in the order of their appearance.
+    #[inline]
+    pub fn get_entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Delivers the `versionId`s of all DID log entries, in the order of their appearance.
+    #[inline]
+    pub fn get_version_ids(&self) -> &[VersionId] {
+        &self.version_ids
+    }
+
+    /// Delivers the (deduplicated) `did:key` identifiers of all witnesses that have (validly) signed a `versionId`, per `versionId`.
+    #[inline]
+    pub const fn get_verified_witnesses(&self) -> &BTreeMap<String, BTreeSet<String>> {
+        &self.verified_witnesses
+    }
+
+    /// Delivers the (pinned and verified) snapshot of the status list fetched from the supplied `uri`, if any.
+    #[inline]
+    pub fn get_status_list(&self, uri: &str) -> Option<&str> {
+        self.status_lists
+            .iter()
+            .find(|status_list| status_list.uri == uri)
+            .map(|status_list| status_list.content.as_str())
+    }
+
+    /// Delivers the `digestMultibase` of all the (non-blank) DID log entries, joined by a newline (see [`VerificationReceiptSubject`]).
+    #[inline]
+    pub fn did_log_digest_multibase(&self) -> String {
+        compute_digest_multibase(self.entries.join("\n").into_bytes())
+    }
+
+    /// Wraps this (completed, see [`VerificationBundle::verify`]) verification outcome into a "verification receipt", i.e. a W3C VC
+    /// (see https://www.w3.org/TR/vc-data-model-2.0/) secured by the supplied `verifier` key (see [`EddsaJcs2022Cryptosuite`]).
+    ///
+    /// Since a [`VerifiedBundle`] is delivered by a successful [`VerificationBundle::verify`] call only,
+    /// no receipt may be issued for a bundle failing the verification (e.g. featuring a tampered or unsigned DID log).
+    /// Hence, downstream systems may rely on a prior verification (see [`verify_receipt`]) without re-running it.
+    /// The `issuer` of the receipt is the `did:key` DID of the `verifier` key.
+    #[inline]
+    pub fn to_receipt(
+        &self,
+        verifier: &Ed25519KeyPair,
+        valid_from: DateTime<Utc>,
+    ) -> Result<Value, DidSidekicksError> {
+        let subject = VerificationReceiptSubject {
+            did: self.did_log.get_did(),
+            did_log_digest_multibase: self.did_log_digest_multibase(),
+            version_ids: self.version_ids.clone(),
+            verified_witnesses: self.verified_witnesses.clone(),
+            status_list_digests: self
+                .status_lists
+                .iter()
+                .map(|status_list| {
+                    (
+                        status_list.uri.clone(),
+                        status_list.digest_multibase.clone(),
+                    )
+                })
+                .collect(),
+        };
+        let receipt = json!({
+            "@context": [VC_CONTEXT_V2],
+            "type": ["VerifiableCredential", VERIFICATION_RECEIPT_TYPE],
+            "issuer": did_key_from_verifying_key(&verifier.verifying_key),
+            "validFrom": valid_from.to_rfc3339_opts(SecondsFormat::Secs, true),
+            "credentialSubject": subject,
+        });
+
+        let suite = EddsaJcs2022Cryptosuite {
+            verifying_key: Some(verifier.verifying_key.clone()),
+            signing_key: Some(verifier.signing_key.clone()),
+            verification_options: VerificationOptions::default(),
+        };
+        let options = CryptoSuiteProofOptions::from_verification_method(
+            &did_key_verification_method_from_verifying_key(&verifier.verifying_key),
+        )
+        .with_proof_purpose("assertionMethod");
+        suite.add_proof(&receipt, &options)
+    }
+}
+
+/// Verifies the supplied verification `receipt` (see [`VerifiedBundle::to_receipt`]).
+///
+/// Delivers the `did:key` DID of the verifier (i.e. the issuer of the receipt) along with the summary of the verification.
+///
+/// It is up to the caller to decide whether the verifier is trusted and to compare the summary
+/// (e.g. [`VerificationReceiptSubject::did_log_digest_multibase`]) against the DID log at hand. Returns:
+/// - [`DidSidekicksError::InvalidDataIntegrityProof`] or [`DidSidekicksError::SignatureInvalid`], if the proof is invalid,
+/// - [`DidSidekicksError::KeyMismatch`], if the proof is not created by the issuer and
+/// - [`DidSidekicksError::DeserializationFailed`], if the receipt is malformed.
+#[inline]
+pub fn verify_receipt(
+    receipt: &Value,
+) -> Result<(String, VerificationReceiptSubject), DidSidekicksError> {
+    let verifier = verify_secured_document(receipt)?;
+
+    let is_receipt = receipt
+        .get("type")
+        .and_then(Value::as_array)
+        .is_some_and(|types| {
+            types
+                .iter()
+                .any(|receipt_type| receipt_type == VERIFICATION_RECEIPT_TYPE)
+        });
+    if !is_receipt {
+        return Err(DidSidekicksError::DeserializationFailed(format!(
+            "The supplied credential is no '{VERIFICATION_RECEIPT_TYPE}'"
+        )));
+    }
+    let issuer = receipt.get("issuer").and_then(Value::as_str);
+    if issuer != Some(verifier.as_str()) {
+        return Err(DidSidekicksError::KeyMismatch {
+            expected: issuer.unwrap_or_default().to_owned(),
+            actual: verifier,
+        });
+    }
+
+    let subject = receipt
+        .get("credentialSubject")
+        .map(VerificationReceiptSubject::deserialize)
+        .transpose()
+        .map_err(|err| {
+            DidSidekicksError::DeserializationFailed(format!("Malformed receipt subject: {err}"))
+        })?
+        .ok_or_else(|| {
+            DidSidekicksError::DeserializationFailed(
+                "The receipt features no 'credentialSubject'".to_owned(),
+            )
+        })?;
+    Ok((verifier, subject))
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(
+    clippy::indexing_slicing,
+    reason = "panic-safe as long as test case setup is correct"
+)]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+#[expect(clippy::shadow_reuse, reason = "..")]
+#[expect(clippy::shadow_unrelated, reason = "..")]
+mod test {
+    use super::*;
+    use crate::ed25519::{Ed25519KeyPair, MultiBaseConverter as _, VerificationOptions};
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::jcs_sha256_hasher::JcsSha256Hasher;
+    use crate::test::assert_error;
+    use crate::vc_data_integrity::{CryptoSuiteProofOptions, EddsaJcs2022Cryptosuite};
+    use rstest::rstest;
+    use serde_json::json;
+
+    const SCHEMA_URI: &str = "https://example.com/did-log-entry.schema.json";
+    const SCHEMA: &str = r#"{"type":"object","required":["versionId","state"]}"#;
+    const STATUS_LIST_URI: &str = "https://example.com/status-lists/1";
+    const STATUS_LIST: &str = "eyJhbGciOiJFUzI1NiJ9.e30.c2ln";
+    const VERSION_TIME: &str = "2012-12-12T12:12:12Z";
+
+    /// Delivers a DID along with its (signed) `did:webvh` DID log featuring 2 DID log entries,
+    /// whereas the supplied `witness` (if any) is configured by the very first DID log entry.
+    fn signed_log(witness: Option<&Ed25519KeyPair>) -> (String, String) {
+        let key_pair = Ed25519KeyPair::generate();
+        let update_key = key_pair.verifying_key.to_multibase();
+        let options = CryptoSuiteProofOptions::from_verification_method(&format!(
+            "did:key:{update_key}#{update_key}"
+        ))
+        .with_created(DateTime::parse_from_rfc3339(VERSION_TIME).unwrap().to_utc())
+        .with_proof_purpose("assertionMethod");
+        let mut parameters =
+            json!({"method": "did:webvh:1.0", "scid": "{SCID}", "updateKeys": [update_key]});
+        if let Some(witness) = witness {
+            parameters["witness"] = json!({
+                "threshold": 1,
+                "witnesses": [{"id": format!("did:key:{}", witness.verifying_key.to_multibase())}],
+            });
+        }
+        let mut did_doc = json!({"@context": ["https://www.w3.org/ns/did/v1"], "id": "did:webvh:{SCID}:example.com"});
+
+        let mut did = String::new();
+        let mut previous = "{SCID}".to_owned();
+        let mut entries = Vec::new();
+        for number in 1..=2 {
+            let mut entry = json!({
+                "versionId": previous,
+                "versionTime": VERSION_TIME,
+                "parameters": if number == 1 { parameters.clone() } else { json!({}) },
+                "state": did_doc,
+            });
+            if number == 1 {
+                let scid = JcsSha256Hasher::default()
+                    .base58btc_encode_multihash(&entry)
+                    .unwrap();
+                entry = serde_json::from_str(&entry.to_string().replace("{SCID}", &scid)).unwrap();
+                did = format!("did:webvh:{scid}:example.com");
+                did_doc["id"] = json!(did);
+            }
+            let entry_hash = JcsSha256Hasher::default()
+                .base58btc_encode_multihash(&entry)
+                .unwrap();
+            entry["versionId"] = json!(format!("{number}-{entry_hash}"));
+            entries.push(suite(&key_pair).add_proof(&entry, &options).unwrap());
+            previous = format!("{number}-{entry_hash}");
+        }
+        let log_jsonl = entries
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        (did, log_jsonl)
+    }
+
+    /// Delivers the `versionId` of the DID log entry at the supplied `index` of the supplied DID log.
+    fn version_id(log_jsonl: &str, index: usize) -> String {
+        let entries: Vec<Value> = log_jsonl
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        entries[index]["versionId"].as_str().unwrap().to_owned()
+    }
+
+    fn witness_proof(witness: &Ed25519KeyPair, version_id: &str) -> Value {
+        let key = witness.verifying_key.to_multibase();
+        let options =
+            CryptoSuiteProofOptions::from_verification_method(&format!("did:key:{key}#{key}"))
+                .with_proof_purpose("assertionMethod");
+        let secured = suite(witness)
+            .add_proof(&json!({"versionId": version_id}), &options)
+            .unwrap();
+        secured.get("proof").unwrap().get(0).unwrap().to_owned()
+    }
+
+    fn pinned_digests(bundle: &VerificationBundle) -> HashMap<String, String> {
+        bundle
+            .schemas
+            .iter()
+            .chain(bundle.status_lists.iter())
+            .map(|resource| (resource.uri.clone(), resource.digest_multibase.clone()))
+            .collect()
+    }
+
+    #[rstest]
+    fn test_verify_bundle() {
+        let witness = Ed25519KeyPair::generate();
+        let witness_did = format!("did:key:{}", witness.verifying_key.to_multibase());
+        let (did, log_jsonl) = signed_log(Some(&witness));
+        let version_id_2 = version_id(&log_jsonl, 1);
+        let witness_proofs = json!([
+            {"versionId": version_id_2, "proof": [witness_proof(&witness, &version_id_2)]},
+        ])
+        .to_string();
+
+        let bundle = VerificationBundle::new(&log_jsonl, Some(&witness_proofs))
+            .with_schema(SCHEMA_URI, SCHEMA)
+            .with_status_list(STATUS_LIST_URI, STATUS_LIST);
+        // roundtrip
+        let bundle =
+            VerificationBundle::from_json(&bundle.to_json(EmitOptions::COMPACT).unwrap()).unwrap();
+
+        let verified = bundle
+            .verify(&did, &pinned_digests(&bundle), ResolutionOptions::default())
+            .unwrap();
+        assert_eq!(verified.get_entries().len(), 2);
+        assert_eq!(verified.get_did_log().get_did(), did);
+        assert_eq!(
+            verified.get_version_ids(),
+            [
+                version_id(&log_jsonl, 0).parse().unwrap(),
+                version_id_2.parse().unwrap()
+            ]
+        );
+        assert_eq!(
+            verified.get_verified_witnesses(),
+            &BTreeMap::from([(version_id_2, BTreeSet::from([witness_did]))])
+        );
+        assert_eq!(verified.get_status_list(STATUS_LIST_URI), Some(STATUS_LIST));
+        assert_eq!(
+            verified.get_status_list("https://example.com/unknown"),
+            None
+        );
+    }
+
+    #[rstest]
+    #[case(None, "Insufficient witness coverage for versionId '2-")]
+    #[case(Some(0), "Insufficient witness coverage for versionId '2-")]
+    fn test_verify_insufficient_witness_coverage(
+        #[case] witnessed_index: Option<usize>,
+        #[case] error_contains: &str,
+    ) {
+        let witness = Ed25519KeyPair::generate();
+        let (did, log_jsonl) = signed_log(Some(&witness));
+        let witness_proofs = witnessed_index.map(|index| {
+            let version_id = version_id(&log_jsonl, index);
+            json!([{"versionId": version_id, "proof": [witness_proof(&witness, &version_id)]}])
+                .to_string()
+        });
+        assert_error(
+            VerificationBundle::new(&log_jsonl, witness_proofs.as_deref()).verify(
+                &did,
+                &HashMap::new(),
+                ResolutionOptions::default(),
+            ),
+            DidSidekicksErrorKind::InvalidWitnessProof,
+            error_contains,
+        );
+    }
+
+    #[rstest]
+    fn test_verify_witness_implicit_approval() {
+        // a witness proof for a later versionId counts for the earlier ones as well
+        let witness = Ed25519KeyPair::generate();
+        let (did, log_jsonl) = signed_log(Some(&witness));
+        let version_id = version_id(&log_jsonl, 1);
+        let witness_proofs =
+            json!([{"versionId": version_id, "proof": [witness_proof(&witness, &version_id)]}])
+                .to_string();
+        VerificationBundle::new(&log_jsonl, Some(&witness_proofs))
+            .verify(&did, &HashMap::new(), ResolutionOptions::default())
+            .unwrap();
+    }
+
+    #[rstest]
+    #[case(None, "does not match its digestMultibase")]
+    #[case(Some(HashMap::new()), "is not pinned")]
+    #[case(
+        Some(HashMap::from([(STATUS_LIST_URI.to_owned(), compute_digest_multibase(b"tampered".to_vec()))])),
+        "is not pinned"
+    )]
+    fn test_verify_tampered_resource(
+        #[case] pinned: Option<HashMap<String, String>>,
+        #[case] error_contains: &str,
+    ) {
+        let (did, log_jsonl) = signed_log(None);
+        let mut bundle = VerificationBundle::new(&log_jsonl, None)
+            .with_status_list(STATUS_LIST_URI, STATUS_LIST);
+        let pinned = pinned.unwrap_or_else(|| pinned_digests(&bundle));
+        bundle.status_lists[0].content = "eyJhbGciOiJFUzI1NiJ9.e30.dGFtcGVyZWQ".to_owned();
+        assert_error(
+            bundle.verify(&did, &pinned, ResolutionOptions::default()),
+            DidSidekicksErrorKind::DeserializationFailed,
+            error_contains,
+        );
+    }
+
+    #[rstest]
+    fn test_verify_schema_violation() {
+        let (did, log_jsonl) = signed_log(None);
+        let bundle = VerificationBundle::new(&log_jsonl, None)
+            .with_schema(SCHEMA_URI, r#"{"type":"object","required":["witness"]}"#);
+        assert_error(
+            bundle.verify(&did, &pinned_digests(&bundle), ResolutionOptions::default()),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            "at line 1 does not comply with bundled schema",
+        );
+    }
+
+    #[rstest]
+    #[case("{\"versionId\":\"1-QmA\",\"state\":{}}")]
+    #[case("{\"versionId\":\"1-QmA\",\"state\":{}}\n\n{\"versionId\":\"2-QmB\",\"state\":{}}\n")]
+    fn test_verify_unsigned_log(#[case] log_jsonl: &str) {
+        let bundle = VerificationBundle::new(log_jsonl, None).with_schema(SCHEMA_URI, SCHEMA);
+        // complying with the bundled schema, yet neither hash-chained nor signed
+        assert_error(
+            bundle.verify(
+                "did:webvh:QmA:example.com",
+                &pinned_digests(&bundle),
+                ResolutionOptions::default(),
+            ),
+            DidSidekicksErrorKind::InvalidDidDocument,
+            "line 1: ",
+        );
+    }
+
+    #[rstest]
+    #[case(
+        0,
+        "/parameters/updateKeys/0",
+        DidSidekicksErrorKind::InvalidDidDocument,
+        "does not match the DID log entry '1-"
+    )]
+    #[case(
+        1,
+        "/state/id",
+        DidSidekicksErrorKind::InvalidDidDocument,
+        "the entry hash of the DID log entry '2-"
+    )]
+    #[case(1, "/proof/0/proofValue", DidSidekicksErrorKind::SignatureInvalid, "")]
+    fn test_verify_tampered_log(
+        #[case] index: usize,
+        #[case] pointer: &str,
+        #[case] error_kind: DidSidekicksErrorKind,
+        #[case] error_contains: &str,
+    ) {
+        let (did, log_jsonl) = signed_log(None);
+        let mut entries: Vec<Value> = log_jsonl
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        *entries[index].pointer_mut(pointer).unwrap() = json!("z6MkTampered");
+        let log_jsonl = entries
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let res = VerificationBundle::new(&log_jsonl, None).verify(
+            &did,
+            &HashMap::new(),
+            ResolutionOptions::default(),
+        );
+        assert_error(res, error_kind, error_contains);
+    }
+
+    #[rstest]
+    fn test_verify_unknown_witnessed_version_id() {
+        let witness = Ed25519KeyPair::generate();
+        let (did, log_jsonl) = signed_log(None);
+        let witness_proofs = json!([
+            {"versionId": "3-QmC", "proof": [witness_proof(&witness, "3-QmC")]},
+        ])
+        .to_string();
+        assert_error(
+            VerificationBundle::new(&log_jsonl, Some(&witness_proofs)).verify(
+                &did,
+                &HashMap::new(),
+                ResolutionOptions::default(),
+            ),
+            DidSidekicksErrorKind::InvalidWitnessProof,
+            "versionId '3-QmC' not featured by the bundled DID log",
+        );
+    }
+
+    #[rstest]
+    fn test_unsupported_format_version() {
+        let mut bundle = VerificationBundle::new("", None);
+        bundle.version = 2;
+        assert_error(
+            VerificationBundle::from_json(&bundle.to_json(EmitOptions::COMPACT).unwrap()),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "Unsupported verification bundle format version 2",
+        );
+    }
+
+    #[rstest]
+    fn test_verification_receipt() {
+        let (did, log_jsonl) = signed_log(None);
+        let bundle = VerificationBundle::new(&log_jsonl, None)
+            .with_status_list(STATUS_LIST_URI, STATUS_LIST);
+        let verified = bundle
+            .verify(&did, &pinned_digests(&bundle), ResolutionOptions::default())
+            .unwrap();
+        let verifier = Ed25519KeyPair::generate();
+        let valid_from = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+            .unwrap()
+            .to_utc();
+        let receipt = verified.to_receipt(&verifier, valid_from).unwrap();
+        assert_eq!(receipt["validFrom"], json!("2025-01-01T00:00:00Z"));
+
+        let (verifier_did, subject) = verify_receipt(&receipt).unwrap();
+        assert_eq!(
+            verifier_did,
+            did_key_from_verifying_key(&verifier.verifying_key)
+        );
+        assert_eq!(subject.did, did);
+        assert_eq!(subject.version_ids, verified.get_version_ids());
+        assert_eq!(
+            subject.did_log_digest_multibase,
+            compute_digest_multibase(log_jsonl.replace("\n\n", "\n").into_bytes())
+        );
+        assert_eq!(
+            subject.status_list_digests,
+            BTreeMap::from([(
+                STATUS_LIST_URI.to_owned(),
+                compute_digest_multibase(STATUS_LIST.as_bytes().to_vec())
+            )])
+        );
+
+        let mut tampered = receipt.clone();
+        tampered["credentialSubject"]["versionIds"] = json!(["1-QmA"]);
+        assert_error(
+            verify_receipt(&tampered),
+            DidSidekicksErrorKind::SignatureInvalid,
+            "invalid signature by 'did:key:",
+        );
+
+        // a credential secured by its issuer, yet no receipt
+        let mut credential = receipt;
+        credential["type"] = json!(["VerifiableCredential"]);
+        credential.as_object_mut().unwrap().remove("proof");
+        let credential = suite(&verifier)
+            .add_proof(
+                &credential,
+                &CryptoSuiteProofOptions::from_verification_method(
+                    &did_key_verification_method_from_verifying_key(&verifier.verifying_key),
+                )
+                .with_proof_purpose("assertionMethod"),
+            )
+            .unwrap();
+        assert_error(
+            verify_receipt(&credential),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "is no 'VerificationReceipt'",
+        );
+    }
+
+    #[rstest]
+    fn test_no_verification_receipt_for_tampered_bundle() {
+        let (did, log_jsonl) = signed_log(None);
+        let tampered_log = log_jsonl.replacen(&did, "did:webvh:QmTampered:example.com", 2);
+        let res = VerificationBundle::new(&tampered_log, None)
+            .verify(&did, &HashMap::new(), ResolutionOptions::default())
+            .and_then(|verified| {
+                verified.to_receipt(&Ed25519KeyPair::generate(), DateTime::default())
+            });
+        assert_error(
+            res,
+            DidSidekicksErrorKind::InvalidDidDocument,
+            "does not match the DID log entry '1-",
+        );
+    }
+
+    fn suite(key_pair: &Ed25519KeyPair) -> EddsaJcs2022Cryptosuite {
+        EddsaJcs2022Cryptosuite {
+            verifying_key: Some(key_pair.verifying_key.clone()),
+            signing_key: Some(key_pair.signing_key.clone()),
+            verification_options: VerificationOptions::default(),
+        }
+    }
+}
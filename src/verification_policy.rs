@@ -0,0 +1,322 @@
+// SPDX-License-Identifier: MIT
+
+//! A declarative (JSON) verification policy, describing the checks required on top of the regular verification,
+//! so per-deployment policy changes do not require recompiling the bindings. For instance:
+//!
+//! ```json
+//! {
+//!   "minWitnessThreshold": 2,
+//!   "allowedCryptosuites": ["eddsa-jcs-2022"],
+//!   "maxLogAgeSeconds": 2592000,
+//!   "trustedRegistries": ["swiss-trust-registry"]
+//! }
+//! ```
+//!
+//! Any member may be omitted, thus not requiring the respective check. The policy is consumed by
+//! [`crate::did_log_verifier::DidLogVerifier::with_policy`] and (in case of credentials) `IssuanceContext::verify_with_policy`.
+
+use crate::bounded_json::from_str_bounded;
+use crate::errors::DidSidekicksError;
+use crate::panic_boundary::catch_panic;
+use crate::trust_registry::{TrustRegistry, TrustStatus};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A declarative verification policy (see [module documentation](self)).
+///
+/// A UniFFI-compliant object.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[expect(clippy::exhaustive_structs, reason = "..")]
+pub struct VerificationPolicy {
+    /// The minimum threshold of the (active) `witness` DID method parameter, if any (zero otherwise).
+    #[serde(default)]
+    pub min_witness_threshold: u64,
+    /// The cryptosuites allowed for proofs (e.g. `eddsa-jcs-2022`). Any cryptosuite is allowed, if empty.
+    #[serde(default)]
+    pub allowed_cryptosuites: Vec<String>,
+    /// The maximum age (in seconds) of the latest DID log entry (w.r.t. its `versionTime`), if any.
+    #[serde(default)]
+    pub max_log_age_seconds: Option<u64>,
+    /// The names of the trust registries, any of which must trust the DID in question. No trust is required, if empty.
+    #[serde(default)]
+    pub trusted_registries: Vec<String>,
+}
+
+impl VerificationPolicy {
+    /// The parsing constructor.
+    ///
+    /// Any malformed JSON (including unknown members) is denoted by [`DidSidekicksError::DeserializationFailed`].
+    ///
+    /// A UniFFI-compliant constructor.
+    #[inline]
+    pub fn from_json(json: &str) -> Result<Self, DidSidekicksError> {
+        catch_panic(|| from_str_bounded(json), DidSidekicksError::InternalError)
+    }
+
+    /// Ensures the supplied `cryptosuite` (of a proof) is allowed (see [`Self::allowed_cryptosuites`]).
+    ///
+    /// Any violation is denoted by [`DidSidekicksError::PolicyViolation`].
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn check_cryptosuite(&self, cryptosuite: &str) -> Result<(), DidSidekicksError> {
+        catch_panic(
+            || {
+                if self.allowed_cryptosuites.is_empty()
+                    || self
+                        .allowed_cryptosuites
+                        .iter()
+                        .any(|allowed| allowed == cryptosuite)
+                {
+                    return Ok(());
+                }
+                Err(DidSidekicksError::PolicyViolation(format!(
+                    "the cryptosuite '{cryptosuite}' is not allowed"
+                )))
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Ensures the cryptosuite of each proof featured by the supplied `proofs` (a single proof or an array of proofs) is allowed.
+    ///
+    /// Any violation is denoted by [`DidSidekicksError::PolicyViolation`].
+    #[inline]
+    #[expect(clippy::pattern_type_mismatch, reason = "..")]
+    pub fn check_proofs(&self, proofs: &Value) -> Result<(), DidSidekicksError> {
+        let all: Vec<&Value> = match proofs {
+            Value::Array(items) => items.iter().collect(),
+            Value::Object(_) => vec![proofs],
+            Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => vec![],
+        };
+        all.into_iter().try_for_each(|proof| {
+            self.check_cryptosuite(
+                proof
+                    .get("cryptosuite")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default(),
+            )
+        })
+    }
+
+    /// Ensures the supplied (active) witness `threshold` reaches [`Self::min_witness_threshold`].
+    ///
+    /// Any violation is denoted by [`DidSidekicksError::PolicyViolation`].
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn check_witness_threshold(&self, threshold: u64) -> Result<(), DidSidekicksError> {
+        catch_panic(
+            || {
+                if threshold < self.min_witness_threshold {
+                    return Err(DidSidekicksError::PolicyViolation(format!(
+                        "the witness threshold {threshold} is below the required {}",
+                        self.min_witness_threshold
+                    )));
+                }
+                Ok(())
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+
+    /// Ensures the supplied `version_time` (of the latest DID log entry) is not older than [`Self::max_log_age_seconds`] at `now`.
+    ///
+    /// Any violation is denoted by [`DidSidekicksError::PolicyViolation`].
+    #[inline]
+    pub fn check_log_age(
+        &self,
+        version_time: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<(), DidSidekicksError> {
+        let Some(max_age_seconds) = self.max_log_age_seconds else {
+            return Ok(());
+        };
+        let max_age = i64::try_from(max_age_seconds)
+            .ok()
+            .and_then(TimeDelta::try_seconds)
+            .unwrap_or(TimeDelta::MAX);
+        if now.signed_duration_since(version_time) > max_age {
+            return Err(DidSidekicksError::PolicyViolation(format!(
+                "the latest DID log entry ({}) is older than {} seconds",
+                version_time.to_rfc3339(),
+                max_age.num_seconds()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Ensures the supplied `did` is trusted for the supplied `role` by any of the [`Self::trusted_registries`],
+    /// as looked up in the supplied `registries` (keyed by name).
+    ///
+    /// Any violation (including a listed registry not supplied) is denoted by [`DidSidekicksError::PolicyViolation`].
+    ///
+    /// A UniFFI-compliant method.
+    #[inline]
+    pub fn check_trust(
+        &self,
+        did: &str,
+        role: &str,
+        registries: HashMap<String, Arc<dyn TrustRegistry>>,
+    ) -> Result<(), DidSidekicksError> {
+        catch_panic(
+            || {
+                if self.trusted_registries.is_empty() {
+                    return Ok(());
+                }
+                let mut trusted = false;
+                for name in &self.trusted_registries {
+                    let Some(registry) = registries.get(name) else {
+                        return Err(DidSidekicksError::PolicyViolation(format!(
+                            "the trust registry '{name}' is not available"
+                        )));
+                    };
+                    if registry.is_trusted(did.to_owned(), role.to_owned()) == TrustStatus::Trusted
+                    {
+                        trusted = true;
+                        break;
+                    }
+                }
+                if !trusted {
+                    return Err(DidSidekicksError::PolicyViolation(format!(
+                        "{did} is not trusted as {role} by any of the trust registries: {}",
+                        self.trusted_registries.join(", ")
+                    )));
+                }
+                Ok(())
+            },
+            DidSidekicksError::InternalError,
+        )
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use crate::trust_registry::TRUST_ROLE_ISSUER;
+    use rstest::rstest;
+    use serde_json::json;
+
+    const ISSUER: &str = "did:example:issuer";
+
+    struct StaticTrustRegistry(TrustStatus);
+    impl TrustRegistry for StaticTrustRegistry {
+        fn is_trusted(&self, did: String, _role: String) -> TrustStatus {
+            if did == ISSUER {
+                self.0
+            } else {
+                TrustStatus::Unknown
+            }
+        }
+    }
+
+    fn policy() -> VerificationPolicy {
+        VerificationPolicy::from_json(
+            &json!({
+                "minWitnessThreshold": 2,
+                "allowedCryptosuites": ["eddsa-jcs-2022"],
+                "maxLogAgeSeconds": 3600,
+                "trustedRegistries": ["untrusting", "trusting"],
+            })
+            .to_string(),
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    fn test_from_json() {
+        assert_eq!(
+            VerificationPolicy::from_json("{}").unwrap(),
+            VerificationPolicy::default()
+        );
+        assert_eq!(policy().min_witness_threshold, 2);
+        assert_error(
+            VerificationPolicy::from_json(r#"{"minWitnessTreshold": 2}"#),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "unknown field `minWitnessTreshold`",
+        );
+    }
+
+    #[rstest]
+    fn test_check_proofs() {
+        let policy = policy();
+        policy.check_cryptosuite("eddsa-jcs-2022").unwrap();
+        policy
+            .check_proofs(&json!([{"cryptosuite": "eddsa-jcs-2022"}]))
+            .unwrap();
+        assert_error(
+            policy.check_proofs(&json!({"cryptosuite": "ecdsa-rdfc-2019"})),
+            DidSidekicksErrorKind::PolicyViolation,
+            "the cryptosuite 'ecdsa-rdfc-2019' is not allowed",
+        );
+        VerificationPolicy::default()
+            .check_cryptosuite("ecdsa-rdfc-2019")
+            .unwrap();
+    }
+
+    #[rstest]
+    fn test_check_witness_threshold() {
+        let policy = policy();
+        policy.check_witness_threshold(2).unwrap();
+        assert_error(
+            policy.check_witness_threshold(1),
+            DidSidekicksErrorKind::PolicyViolation,
+            "the witness threshold 1 is below the required 2",
+        );
+        VerificationPolicy::default()
+            .check_witness_threshold(0)
+            .unwrap();
+    }
+
+    #[rstest]
+    fn test_check_log_age() {
+        let policy = policy();
+        let now = Utc::now();
+        policy
+            .check_log_age(now.checked_sub_signed(TimeDelta::minutes(59)).unwrap(), now)
+            .unwrap();
+        assert_error(
+            policy.check_log_age(now.checked_sub_signed(TimeDelta::minutes(61)).unwrap(), now),
+            DidSidekicksErrorKind::PolicyViolation,
+            "is older than 3600 seconds",
+        );
+        VerificationPolicy::default()
+            .check_log_age(DateTime::UNIX_EPOCH, now)
+            .unwrap();
+    }
+
+    #[rstest]
+    fn test_check_trust() {
+        let policy = policy();
+        let untrusting: Arc<dyn TrustRegistry> =
+            Arc::new(StaticTrustRegistry(TrustStatus::NotTrusted));
+        let trusting: Arc<dyn TrustRegistry> = Arc::new(StaticTrustRegistry(TrustStatus::Trusted));
+        let registries = HashMap::from([
+            ("untrusting".to_owned(), untrusting),
+            ("trusting".to_owned(), trusting),
+        ]);
+        policy
+            .check_trust(ISSUER, TRUST_ROLE_ISSUER, registries.clone())
+            .unwrap();
+        assert_error(
+            policy.check_trust("did:example:other", TRUST_ROLE_ISSUER, registries),
+            DidSidekicksErrorKind::PolicyViolation,
+            "is not trusted as issuer by any of the trust registries: untrusting, trusting",
+        );
+        assert_error(
+            policy.check_trust(ISSUER, TRUST_ROLE_ISSUER, HashMap::new()),
+            DidSidekicksErrorKind::PolicyViolation,
+            "the trust registry 'untrusting' is not available",
+        );
+    }
+}
@@ -0,0 +1,316 @@
+// SPDX-License-Identifier: MIT
+
+//! A structured `versionId` of a DID log entry, as specified by https://identity.foundation/didwebvh/v1.0/#the-did-log-file:
+//!
+//! The value combines the version number (starting at 1 and incrementing by one per version), a literal dash `-`,
+//! and the hash of the entry (`<n>-<entryHash>`), e.g. `1-QmQq6Kg4ZZ1p49znzxnWmes4LkkWgMWLrnrfPre8UD56bz`.
+
+use crate::errors::DidSidekicksError;
+use crate::multihash::{Multihash, MultihashAlgorithm};
+use core::cmp::Ordering;
+use core::fmt;
+use core::str::FromStr;
+use serde::{Deserialize, Serialize};
+
+/// The separator between the version number and the entry hash.
+const VERSION_ID_SEPARATOR: char = '-';
+
+/// The length (in bytes) of a decoded entry hash, i.e. a SHA2-256 multihash (`0x12`, `0x20` followed by the 32-byte digest).
+const ENTRY_HASH_MULTIHASH_LENGTH: usize = 34;
+
+/// The maximum version number, i.e. the maximum of a signed 64-bit integer, so that no consumer relying on
+/// signed integers (e.g. Kotlin `Long` or Java `long`) ever wraps it silently.
+pub const MAX_VERSION_NUMBER: u64 = i64::MAX.unsigned_abs();
+
+/// A (validated) `versionId` of a DID log entry, i.e. `<n>-<entryHash>`.
+///
+/// The ordering is determined by the version number, whereas the entry hash only serves as tie-breaker.
+/// (De)serialized as plain string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct VersionId {
+    number: u64,
+    entry_hash: String,
+}
+
+impl VersionId {
+    /// The only non-empty constructor of the type, validating both the version `number` (starting at 1, up to [`MAX_VERSION_NUMBER`])
+    /// and the `entry_hash`.
+    ///
+    /// Any violation is denoted by [`DidSidekicksError::DeserializationFailed`].
+    #[inline]
+    pub fn new(number: u64, entry_hash: &str) -> Result<Self, DidSidekicksError> {
+        if number == 0 {
+            return Err(DidSidekicksError::DeserializationFailed(
+                "Invalid versionId: the version number must start at 1".to_owned(),
+            ));
+        }
+        if number > MAX_VERSION_NUMBER {
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "Invalid versionId: the version number {number} exceeds the maximum of {MAX_VERSION_NUMBER}"
+            )));
+        }
+        if entry_hash.is_empty() || !entry_hash.chars().all(|ch| ch.is_ascii_alphanumeric()) {
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "Invalid versionId: the entry hash '{entry_hash}' must be a non-empty (base58btc-encoded) multihash"
+            )));
+        }
+        Ok(Self {
+            number,
+            entry_hash: entry_hash.to_owned(),
+        })
+    }
+
+    /// The version number (starting at 1).
+    #[inline]
+    pub const fn number(&self) -> u64 {
+        self.number
+    }
+
+    /// The hash of the DID log entry.
+    #[inline]
+    pub fn entry_hash(&self) -> &str {
+        &self.entry_hash
+    }
+
+    /// Delivers the `versionId` of the succeeding DID log entry (featuring the supplied `entry_hash`),
+    /// i.e. the version number is incremented by one.
+    #[inline]
+    pub fn next(&self, entry_hash: &str) -> Result<Self, DidSidekicksError> {
+        let number = self.number.checked_add(1).ok_or_else(|| {
+            DidSidekicksError::DeserializationFailed(format!(
+                "Invalid versionId: no version may succeed {self}"
+            ))
+        })?;
+        Self::new(number, entry_hash)
+    }
+
+    /// Ensures that the entry hash is a base58btc-encoded SHA2-256 multihash, i.e. it features the base58btc alphabet only
+    /// and decodes into a multihash of the proper (digest) length. Cheap compared to the (entry hash) verification itself,
+    /// hence intended to short-circuit obviously corrupted DID logs.
+    ///
+    /// Any violation is denoted by [`DidSidekicksError::DeserializationFailed`].
+    #[inline]
+    pub fn check_entry_hash_encoding(&self) -> Result<(), DidSidekicksError> {
+        let multihash = bs58::decode(&self.entry_hash)
+            .with_alphabet(bs58::Alphabet::BITCOIN)
+            .into_vec()
+            .map_err(|err| {
+                DidSidekicksError::DeserializationFailed(format!(
+                    "Invalid versionId '{self}': the entry hash is not base58btc-encoded: {err}"
+                ))
+            })?;
+        let is_sha256 = Multihash::from_bytes(&multihash)
+            .is_ok_and(|decoded| decoded.algorithm() == MultihashAlgorithm::Sha2_256);
+        if !is_sha256 {
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "Invalid versionId '{self}': the entry hash is not a SHA2-256 multihash ({} bytes decoded, {ENTRY_HASH_MULTIHASH_LENGTH} expected)",
+                multihash.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether this `versionId` immediately succeeds the supplied `previous` one, i.e. its version number is greater by one.
+    #[inline]
+    pub fn is_successor_of(&self, previous: &Self) -> bool {
+        previous.number.checked_add(1) == Some(self.number)
+    }
+}
+
+impl FromStr for VersionId {
+    type Err = DidSidekicksError;
+
+    /// Parses the supplied `<n>-<entryHash>` string, whereas the version number must feature no leading zeros.
+    #[inline]
+    fn from_str(version_id: &str) -> Result<Self, Self::Err> {
+        let Some((number, entry_hash)) = version_id.split_once(VERSION_ID_SEPARATOR) else {
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "Invalid versionId '{version_id}', expected '<n>-<entryHash>'"
+            )));
+        };
+        if number.is_empty()
+            || number.starts_with('0')
+            || !number.chars().all(|ch| ch.is_ascii_digit())
+        {
+            return Err(DidSidekicksError::DeserializationFailed(format!(
+                "Invalid versionId '{version_id}': malformed version number '{number}'"
+            )));
+        }
+        let parsed_number = number.parse::<u64>().map_err(|err| {
+            DidSidekicksError::DeserializationFailed(format!(
+                "Invalid versionId '{version_id}': {err}"
+            ))
+        })?;
+        Self::new(parsed_number, entry_hash)
+    }
+}
+
+impl TryFrom<String> for VersionId {
+    type Error = DidSidekicksError;
+
+    #[inline]
+    fn try_from(version_id: String) -> Result<Self, Self::Error> {
+        version_id.parse()
+    }
+}
+
+impl From<VersionId> for String {
+    #[inline]
+    fn from(version_id: VersionId) -> Self {
+        version_id.to_string()
+    }
+}
+
+impl fmt::Display for VersionId {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}{VERSION_ID_SEPARATOR}{}",
+            self.number, self.entry_hash
+        )
+    }
+}
+
+#[expect(clippy::missing_trait_methods, reason = "..")]
+impl PartialOrd for VersionId {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[expect(clippy::missing_trait_methods, reason = "..")]
+impl Ord for VersionId {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.number
+            .cmp(&other.number)
+            .then_with(|| self.entry_hash.cmp(&other.entry_hash))
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "unwrap calls are panic-safe as long as test case setup is correct"
+)]
+#[expect(clippy::default_numeric_fallback, reason = "..")]
+#[expect(clippy::shadow_reuse, reason = "..")]
+mod test {
+    use super::*;
+    use crate::errors::DidSidekicksErrorKind;
+    use crate::test::assert_error;
+    use rand::Rng as _;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_version_id() {
+        let first: VersionId = "1-QmA".parse().unwrap();
+        assert_eq!(first.number(), 1);
+        assert_eq!(first.entry_hash(), "QmA");
+        assert_eq!(first.to_string(), "1-QmA");
+
+        let second = first.next("QmB").unwrap();
+        assert_eq!(second.to_string(), "2-QmB");
+        assert!(second.is_successor_of(&first));
+        assert!(!first.is_successor_of(&second));
+        assert!(first < second);
+        // numerical (rather than lexicographical) ordering
+        assert!(second < "10-QmA".parse::<VersionId>().unwrap());
+
+        assert_eq!(serde_json::to_string(&second).unwrap(), "\"2-QmB\"");
+        assert_eq!(
+            serde_json::from_str::<VersionId>("\"2-QmB\"").unwrap(),
+            second
+        );
+    }
+
+    #[rstest]
+    #[case("QmA", "expected '<n>-<entryHash>'")]
+    #[case("0-QmA", "malformed version number")]
+    #[case("01-QmA", "malformed version number")]
+    #[case("+1-QmA", "malformed version number")]
+    #[case("-QmA", "malformed version number")]
+    #[case("99999999999999999999-QmA", "number too large")]
+    #[case("1-", "must be a non-empty")]
+    #[case("1-Qm-A", "must be a non-empty")]
+    fn test_version_id_invalid(#[case] version_id: &str, #[case] error_contains: &str) {
+        assert_error(
+            version_id.parse::<VersionId>(),
+            DidSidekicksErrorKind::DeserializationFailed,
+            error_contains,
+        );
+    }
+
+    #[rstest]
+    #[case("1-QmQq6Kg4ZZ1p49znzxnWmes4LkkWgMWLrnrfPre8UD56bz", None)]
+    #[case("1-QmQq6Kg4ZZ1p49znzxnWmes4LkkWgMWLrnrfPre8UD560z", Some("is not base58btc-encoded"))] // '0' is not in the alphabet
+    #[case("1-QmQq6Kg4ZZ1p49znzxnWmes4LkkWgMWLrnrfPre8UD56bzz", Some("not a SHA2-256 multihash"))]
+    #[case("1-QmA", Some("not a SHA2-256 multihash"))]
+    fn test_version_id_check_entry_hash_encoding(
+        #[case] version_id: &str,
+        #[case] error_contains: Option<&str>,
+    ) {
+        let res = version_id
+            .parse::<VersionId>()
+            .unwrap()
+            .check_entry_hash_encoding();
+        match error_contains {
+            Some(error_contains) => assert_error(
+                res,
+                DidSidekicksErrorKind::DeserializationFailed,
+                error_contains,
+            ),
+            None => {
+                res.unwrap();
+            }
+        }
+    }
+
+    #[rstest]
+    fn test_version_id_next_overflow() {
+        assert_error(
+            VersionId::new(MAX_VERSION_NUMBER, "QmA")
+                .unwrap()
+                .next("QmB"),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "exceeds the maximum",
+        );
+        assert_error(
+            format!("{}-QmA", u64::MAX).parse::<VersionId>(),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "exceeds the maximum",
+        );
+        assert_error(
+            format!("{}0-QmA", u64::MAX).parse::<VersionId>(),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "number too large",
+        );
+        assert_error(
+            VersionId::new(0, "QmA"),
+            DidSidekicksErrorKind::DeserializationFailed,
+            "must start at 1",
+        );
+    }
+
+    #[rstest]
+    fn test_version_id_fuzz() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..10_000 {
+            let number = if rng.gen() {
+                rng.gen_range(0..1_000)
+            } else {
+                rng.gen::<u64>()
+            };
+            let version_id = format!("{number}-QmA");
+            // must never panic, whereas any parsed version number must be usable as a signed 64-bit integer
+            if let Ok(parsed) = version_id.parse::<VersionId>() {
+                assert_eq!(parsed.number(), number);
+                i64::try_from(parsed.number()).unwrap();
+                assert!(parsed.next("QmB").is_ok() || number == MAX_VERSION_NUMBER);
+            }
+        }
+    }
+}